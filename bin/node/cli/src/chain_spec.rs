@@ -260,6 +260,7 @@ pub fn testnet_genesis(
 			.. Default::default()
 		}),
 		pallet_democracy: Some(DemocracyConfig::default()),
+		pallet_elections_phragmen: Some(Default::default()),
 		pallet_collective_Instance1: Some(CouncilConfig {
 			members: endowed_accounts.iter().cloned()
 				.collect::<Vec<_>>()[..(num_endowed_accounts + 1) / 2].to_vec(),
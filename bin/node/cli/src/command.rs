@@ -59,7 +59,11 @@ where
 			// Setup tracing.
 			if let Some(tracing_targets) = cli_args.import_params.tracing_targets.as_ref() {
 				let subscriber = sc_tracing::ProfilingSubscriber::new(
-					cli_args.import_params.tracing_receiver.into(), tracing_targets
+					cli_args.import_params.tracing_receiver.into_receiver(
+						config.in_chain_config_dir(sc_cli::DEFAULT_TRACES_CONFIG_PATH)
+							.expect("We provided a basepath"),
+					),
+					tracing_targets,
 				);
 				if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
 					panic!("Unable to set global default subscriber {}", e);
@@ -16,11 +16,16 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use node_primitives::Balance;
-use sp_runtime::traits::{Convert, Saturating};
+use node_primitives::{AccountId, Balance};
+use sp_core::u32_trait::{_1, _2, _3};
+use sp_runtime::traits::{Convert, Saturating, EnsureOrigin};
 use sp_runtime::{Fixed64, Perbill};
 use frame_support::{traits::{OnUnbalanced, Currency, Get}, weights::Weight};
-use crate::{Balances, System, Authorship, MaximumBlockWeight, NegativeImbalance};
+use pallet_treasury::SpendTrackId;
+use crate::{
+	Balances, System, Authorship, MaximumBlockWeight, NegativeImbalance, Origin, CouncilCollective,
+	TechnicalCollective, CouncilSpendLimit, TechnicalSpendLimit,
+};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -29,6 +34,24 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 	}
 }
 
+/// The spend tracks of the treasury: a simple majority of the council is spend track 0, limited to
+/// `CouncilSpendLimit` per spend period; two thirds of the technical committee is spend track 1,
+/// limited to `TechnicalSpendLimit`.
+pub struct TreasurySpendOrigin;
+impl EnsureOrigin<Origin> for TreasurySpendOrigin {
+	type Success = (SpendTrackId, Balance);
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		type CouncilMajority =
+			pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, CouncilCollective>;
+		type TechnicalTwoThirds =
+			pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
+
+		CouncilMajority::try_origin(o)
+			.map(|_| (0, CouncilSpendLimit::get()))
+			.or_else(|o| TechnicalTwoThirds::try_origin(o).map(|_| (1, TechnicalSpendLimit::get())))
+	}
+}
+
 /// Struct that handles the conversion of Balance -> `u64`. This is used for staking's election
 /// calculation.
 pub struct CurrencyToVoteHandler;
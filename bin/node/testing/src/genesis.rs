@@ -90,6 +90,7 @@ pub fn config(support_changes_trie: bool, code: Option<&[u8]>) -> GenesisConfig
 		pallet_im_online: Some(Default::default()),
 		pallet_authority_discovery: Some(Default::default()),
 		pallet_democracy: Some(Default::default()),
+		pallet_elections_phragmen: Some(Default::default()),
 		pallet_collective_Instance1: Some(Default::default()),
 		pallet_collective_Instance2: Some(Default::default()),
 		pallet_membership_Instance1: Some(Default::default()),
@@ -228,6 +228,20 @@ pub trait AuxStore {
 	fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>>;
 }
 
+/// Key-value storage of an index maintained by the client, e.g. of the events of blocks.
+///
+/// Unlike the auxiliary database, index storages are not written as part of block import.
+pub trait IndexStorage: Send + Sync {
+	/// Get the value stored under `key`.
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+	/// Get all the keys starting with `prefix`.
+	fn keys_with_prefix(&self, prefix: &[u8]) -> Vec<Vec<u8>>;
+
+	/// Atomically apply the given changes, `None` values delete the key.
+	fn commit(&self, changes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> sp_blockchain::Result<()>;
+}
+
 /// Client backend.
 ///
 /// Manages the data layer.
@@ -284,6 +298,16 @@ pub trait Backend<Block: BlockT>: AuxStore + Send + Sync {
 	/// Returns a handle to offchain storage.
 	fn offchain_storage(&self) -> Option<Self::OffchainStorage>;
 
+	/// Returns a handle to the storage of the event index, if the backend supports it.
+	fn event_index_storage(&self) -> Option<Arc<dyn IndexStorage>> {
+		None
+	}
+
+	/// Returns a handle to the storage of the transaction index, if the backend supports it.
+	fn transaction_index_storage(&self) -> Option<Arc<dyn IndexStorage>> {
+		None
+	}
+
 	/// Returns true if state for given block is available.
 	fn have_state_at(&self, hash: &Block::Hash, _number: NumberFor<Block>) -> bool {
 		self.state_at(BlockId::Hash(hash.clone())).is_ok()
@@ -297,6 +321,17 @@ pub trait Backend<Block: BlockT>: AuxStore + Send + Sync {
 		Ok(())
 	}
 
+	/// Prevents the state of the given block from being pruned until `unpin_block` is called.
+	///
+	/// Pins are reference counted: every successful call must be matched by exactly one
+	/// call to `unpin_block`. Backends that never prune state don't need to track pins.
+	fn pin_block(&self, _hash: &Block::Hash) -> sp_blockchain::Result<()> {
+		Ok(())
+	}
+
+	/// Releases a pin previously taken with `pin_block`.
+	fn unpin_block(&self, _hash: &Block::Hash) {}
+
 	/// Attempts to revert the chain by `n` blocks. If `revert_finalized` is set
 	/// it will attempt to revert past any finalized block, this is unsafe and
 	/// can potentially leave the node in an inconsistent state.
@@ -30,7 +30,7 @@ mod node_key;
 
 use sc_client_api::execution_extensions::ExecutionStrategies;
 use sc_service::{
-	config::{Configuration, DatabaseConfig, KeystoreConfig},
+	config::{Configuration, DatabaseConfig, HealthConfig, KeystoreConfig, PrometheusConfig, ReadinessCriteria},
 	ServiceBuilderCommand,
 	RuntimeGenesis, ChainSpecExtension, PruningMode, ChainSpec,
 	AbstractService, Roles as ServiceRoles,
@@ -45,7 +45,7 @@ use sc_network::{
 };
 
 use std::{
-	io::Write, iter, fmt::Debug, fs,
+	iter, fmt::Debug, fs,
 	net::{Ipv4Addr, SocketAddr}, path::PathBuf,
 };
 
@@ -53,7 +53,7 @@ use regex::Regex;
 use structopt::{StructOpt, clap};
 pub use structopt;
 use params::{
-	NetworkConfigurationParams, TransactionPoolParams, Cors,
+	NetworkConfigurationParams, TransactionPoolParams, Cors, LogFormat,
 };
 pub use params::{
 	SharedParams, ImportParams, ExecutionStrategy, Subcommand, RunCmd, BuildSpecCmd,
@@ -64,6 +64,7 @@ use app_dirs::{AppInfo, AppDataType};
 use log::info;
 use lazy_static::lazy_static;
 use sc_telemetry::TelemetryEndpoints;
+use sc_tracing::logging;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 pub use crate::runtime::{run_until_exit, run_service_until_exit};
 use execution_strategy::*;
@@ -76,6 +77,8 @@ const DEFAULT_NETWORK_CONFIG_PATH : &'static str = "network";
 const DEFAULT_DB_CONFIG_PATH : &'static str = "db";
 /// default sub directory for the key store
 const DEFAULT_KEYSTORE_CONFIG_PATH : &'static str = "keystore";
+/// default sub directory for the Chrome traces of block imports
+pub const DEFAULT_TRACES_CONFIG_PATH : &'static str = "traces";
 
 /// The maximum number of characters for a node name.
 const NODE_NAME_MAX_LENGTH: usize = 32;
@@ -277,7 +280,7 @@ pub fn init(shared_params: &SharedParams, version: &VersionInfo) -> error::Resul
 	sp_panic_handler::set(version.support_url, &full_version);
 
 	fdlimit::raise_fd_limit();
-	init_logger(shared_params.log.as_ref().map(|v| v.as_ref()).unwrap_or(""));
+	init_logger(shared_params.log.as_ref().map(|v| v.as_ref()).unwrap_or(""), shared_params.log_format);
 
 	Ok(())
 }
@@ -590,6 +593,9 @@ where
 		(params::OffchainWorkerEnabled::WhenValidating, _) => false,
 	};
 
+	config.event_index = cli.index_events;
+	config.transaction_index = cli.index_transactions;
+
 	config.roles = role;
 	config.disable_grandpa = cli.no_grandpa;
 
@@ -629,7 +635,43 @@ where
 		);
 	}
 
+	if cli.no_prometheus {
+		config.prometheus_config = None;
+	} else if config.prometheus_config.is_none() || cli.prometheus_port.is_some() {
+		let prometheus_interface: &str = if cli.prometheus_external { "0.0.0.0" } else { "127.0.0.1" };
+		config.prometheus_config = Some(PrometheusConfig::new_with_default_registry(
+			parse_address(&format!("{}:{}", prometheus_interface, 9615), cli.prometheus_port)?
+		));
+	}
+
+	if cli.health && cli.no_prometheus {
+		return Err(error::Error::Input(
+			"--health serves the health check endpoint from the Prometheus endpoint, which \
+			--no-prometheus disables.".to_owned()
+		));
+	}
+	if config.health_config.is_some() || cli.health {
+		let default_readiness = ReadinessCriteria::default();
+		config.health_config = Some(HealthConfig {
+			readiness: ReadinessCriteria {
+				max_blocks_behind: cli.health_max_blocks_behind.unwrap_or(default_readiness.max_blocks_behind),
+				min_peers: cli.health_min_peers.unwrap_or(default_readiness.min_peers),
+				max_finality_lag: cli.health_max_finality_lag,
+				max_import_stall: match cli.health_max_import_stall {
+					Some(0) => None,
+					Some(secs) => Some(std::time::Duration::from_secs(secs)),
+					None => default_readiness.max_import_stall,
+				},
+			},
+		});
+	}
+
 	config.rpc_ws_max_connections = cli.ws_max_connections;
+	config.rpc_methods = cli.rpc_methods.into();
+	config.rpc_max_payload = cli.rpc_max_payload.map(|mib| mib * 1024 * 1024);
+	config.rpc_max_response_size = cli.rpc_max_response_size.map(|mib| mib * 1024 * 1024);
+	config.rpc_rate_limit = cli.rpc_rate_limit;
+	config.rpc_ws_allowed_ips = cli.ws_allowed_ips;
 	config.rpc_cors = cli.rpc_cors.unwrap_or_else(|| if is_dev {
 		log::warn!("Running in --dev mode, RPC CORS has been disabled.");
 		Cors::All
@@ -652,7 +694,9 @@ where
 	}
 
 	config.tracing_targets = cli.import_params.tracing_targets.into();
-	config.tracing_receiver = cli.import_params.tracing_receiver.into();
+	config.tracing_receiver = cli.import_params.tracing_receiver.into_receiver(
+		config.in_chain_config_dir(DEFAULT_TRACES_CONFIG_PATH).expect("We provided a basepath"),
+	);
 
 	// Imply forced authoring on --dev
 	config.force_authoring = cli.shared_params.dev || cli.force_authoring;
@@ -696,64 +740,68 @@ fn parse_address(
 }
 
 /// Initialize the logger
-pub fn init_logger(pattern: &str) {
+///
+/// The log filter can be changed later on with `sc_tracing::logging::add_directives`.
+pub fn init_logger(pattern: &str, format: LogFormat) {
 	use ansi_term::Colour;
 
-	let mut builder = env_logger::Builder::new();
-	// Disable info logging by default for some modules:
-	builder.filter(Some("ws"), log::LevelFilter::Off);
-	builder.filter(Some("hyper"), log::LevelFilter::Warn);
-	builder.filter(Some("cranelift_wasm"), log::LevelFilter::Warn);
-	// Always log the special target `sc_tracing`, overrides global level
-	builder.filter(Some("sc_tracing"), log::LevelFilter::Info);
-	// Enable info for others.
-	builder.filter(None, log::LevelFilter::Info);
+	let mut directives = vec![
+		// Disable info logging by default for some modules:
+		"ws=off,hyper=warn,cranelift_wasm=warn".to_owned(),
+		// Always log the special target `sc_tracing`, overrides global level
+		"sc_tracing=info".to_owned(),
+		// Enable info for others.
+		"info".to_owned(),
+	];
 
 	if let Ok(lvl) = std::env::var("RUST_LOG") {
-		builder.parse_filters(&lvl);
+		directives.push(lvl);
 	}
 
-	builder.parse_filters(pattern);
+	directives.push(pattern.to_owned());
 	let isatty = atty::is(atty::Stream::Stderr);
 	let enable_color = isatty;
 
-	builder.format(move |buf, record| {
-		let now = time::now();
-		let timestamp =
-			time::strftime("%Y-%m-%d %H:%M:%S", &now)
-				.expect("Error formatting log timestamp");
+	let format = match format {
+		LogFormat::Json => logging::LogFormat::Json,
+		LogFormat::Text => logging::LogFormat::Text(Box::new(move |record| {
+			let now = time::now();
+			let timestamp =
+				time::strftime("%Y-%m-%d %H:%M:%S", &now)
+					.expect("Error formatting log timestamp");
 
-		let mut output = if log::max_level() <= log::LevelFilter::Info {
-			format!("{} {}", Colour::Black.bold().paint(timestamp), record.args())
-		} else {
-			let name = ::std::thread::current()
-				.name()
-				.map_or_else(Default::default, |x| format!("{}", Colour::Blue.bold().paint(x)));
-			let millis = (now.tm_nsec as f32 / 1000000.0).round() as usize;
-			let timestamp = format!("{}.{:03}", timestamp, millis);
-			format!(
-				"{} {} {} {}  {}",
-				Colour::Black.bold().paint(timestamp),
-				name,
-				record.level(),
-				record.target(),
-				record.args()
-			)
-		};
+			let mut output = if log::max_level() <= log::LevelFilter::Info {
+				format!("{} {}", Colour::Black.bold().paint(timestamp), record.args())
+			} else {
+				let name = ::std::thread::current()
+					.name()
+					.map_or_else(Default::default, |x| format!("{}", Colour::Blue.bold().paint(x)));
+				let millis = (now.tm_nsec as f32 / 1000000.0).round() as usize;
+				let timestamp = format!("{}.{:03}", timestamp, millis);
+				format!(
+					"{} {} {} {}  {}",
+					Colour::Black.bold().paint(timestamp),
+					name,
+					record.level(),
+					record.target(),
+					record.args()
+				)
+			};
 
-		if !isatty && record.level() <= log::Level::Info && atty::is(atty::Stream::Stdout) {
-			// duplicate INFO/WARN output to console
-			println!("{}", output);
-		}
+			if !isatty && record.level() <= log::Level::Info && atty::is(atty::Stream::Stdout) {
+				// duplicate INFO/WARN output to console
+				println!("{}", output);
+			}
 
-		if !enable_color {
-			output = kill_color(output.as_ref());
-		}
+			if !enable_color {
+				output = kill_color(output.as_ref());
+			}
 
-		writeln!(buf, "{}", output)
-	});
+			output
+		})),
+	};
 
-	if builder.try_init().is_err() {
+	if logging::init_logger(directives, format).is_err() {
 		info!("Not registering Substrate logger, as there is already a global logger registered!");
 	}
 }
@@ -18,7 +18,7 @@ use std::{str::FromStr, path::PathBuf};
 use structopt::{StructOpt, clap::arg_enum};
 use sc_service::{
 	AbstractService, Configuration, ChainSpecExtension, RuntimeGenesis, ServiceBuilderCommand,
-	config::DatabaseConfig,
+	config::{DatabaseConfig, IpNet},
 };
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use crate::VersionInfo;
@@ -113,6 +113,29 @@ pub struct SharedParams {
 	/// Sets a custom logging filter.
 	#[structopt(short = "l", long = "log", value_name = "LOG_PATTERN")]
 	pub log: Option<String>,
+
+	/// Format of the log output.
+	///
+	/// JSON output writes an object per line, including the block number and peer id
+	/// the record relates to where known.
+	#[structopt(
+		long = "log-format",
+		value_name = "FORMAT",
+		possible_values = &LogFormat::variants(),
+		case_insensitive = true,
+		default_value = "Text"
+	)]
+	pub log_format: LogFormat,
+}
+
+arg_enum! {
+	/// Format of the log output.
+	#[allow(missing_docs)]
+	#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+	pub enum LogFormat {
+		Text,
+		Json,
+	}
 }
 
 /// Parameters for block import.
@@ -161,6 +184,9 @@ pub struct ImportParams {
 	pub tracing_targets: Option<String>,
 
 	/// Receiver to process tracing messages
+	///
+	/// `ChromeTrace` writes a file per imported block into the `traces` directory of the
+	/// chain, which can be loaded into Chrome's `about:tracing`.
 	#[structopt(
 		long = "tracing-receiver",
 		value_name = "RECEIVER",
@@ -328,15 +354,39 @@ arg_enum! {
 		Log,
 		Telemetry,
 		Grafana,
+		ChromeTrace,
 	}
 }
 
-impl Into<sc_tracing::TracingReceiver> for TracingReceiver {
-	fn into(self) -> sc_tracing::TracingReceiver {
+impl TracingReceiver {
+	/// Convert into the receiver of `sc_tracing`, Chrome traces are written into `traces_dir`.
+	pub fn into_receiver(self, traces_dir: PathBuf) -> sc_tracing::TracingReceiver {
 		match self {
 			TracingReceiver::Log => sc_tracing::TracingReceiver::Log,
 			TracingReceiver::Telemetry => sc_tracing::TracingReceiver::Telemetry,
 			TracingReceiver::Grafana => sc_tracing::TracingReceiver::Grafana,
+			TracingReceiver::ChromeTrace => sc_tracing::TracingReceiver::ChromeTrace(traces_dir),
+		}
+	}
+}
+
+arg_enum! {
+	/// Available RPC methods.
+	#[allow(missing_docs)]
+	#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+	pub enum RpcMethods {
+		Auto,
+		Safe,
+		Unsafe,
+	}
+}
+
+impl Into<sc_service::config::RpcMethods> for RpcMethods {
+	fn into(self) -> sc_service::config::RpcMethods {
+		match self {
+			RpcMethods::Auto => sc_service::config::RpcMethods::Auto,
+			RpcMethods::Safe => sc_service::config::RpcMethods::Safe,
+			RpcMethods::Unsafe => sc_service::config::RpcMethods::Unsafe,
 		}
 	}
 }
@@ -482,6 +532,12 @@ pub struct RunCmd {
 	#[structopt(long = "grafana-external")]
 	pub grafana_external: bool,
 
+	/// Listen to all Prometheus endpoint interfaces.
+	///
+	/// Default is local.
+	#[structopt(long = "prometheus-external")]
+	pub prometheus_external: bool,
+
 	/// Specify HTTP RPC server TCP port.
 	#[structopt(long = "rpc-port", value_name = "PORT")]
 	pub rpc_port: Option<u16>,
@@ -504,10 +560,81 @@ pub struct RunCmd {
 	#[structopt(long = "rpc-cors", value_name = "ORIGINS", parse(try_from_str = parse_cors))]
 	pub rpc_cors: Option<Cors>,
 
+	/// RPC methods to expose.
+	///
+	/// - `Unsafe`: Exposes every RPC method.
+	/// - `Safe`: Exposes only a safe subset of RPC methods, denying unsafe RPC methods.
+	/// - `Auto`: Acts as `Safe` if RPC is served on an external interface, `Unsafe` otherwise.
+	#[structopt(
+		long = "rpc-methods",
+		value_name = "METHOD SET",
+		possible_values = &RpcMethods::variants(),
+		case_insensitive = true,
+		default_value = "Auto",
+	)]
+	pub rpc_methods: RpcMethods,
+
+	/// Maximum size of HTTP & WS RPC requests in MiB. Default is 15.
+	#[structopt(long = "rpc-max-payload", value_name = "MiB")]
+	pub rpc_max_payload: Option<usize>,
+
+	/// Maximum size of HTTP & WS RPC responses in MiB. Default is unlimited.
+	#[structopt(long = "rpc-max-response-size", value_name = "MiB")]
+	pub rpc_max_response_size: Option<usize>,
+
+	/// Maximum number of RPC calls per second for each HTTP or WS connection.
+	///
+	/// Default is unlimited.
+	#[structopt(long = "rpc-rate-limit", value_name = "COUNT")]
+	pub rpc_rate_limit: Option<u32>,
+
+	/// Networks allowed to connect to the WS RPC server.
+	///
+	/// A comma-separated list of networks in CIDR notation, e.g. `10.0.0.0/8,::1/128`.
+	/// Default is to allow every peer.
+	#[structopt(long = "ws-allowed-ips", value_name = "NETWORKS", use_delimiter = true)]
+	pub ws_allowed_ips: Option<Vec<IpNet>>,
+
 	/// Specify Grafana data source server TCP Port.
 	#[structopt(long = "grafana-port", value_name = "PORT")]
 	pub grafana_port: Option<u16>,
 
+	/// Specify Prometheus endpoint TCP Port.
+	#[structopt(long = "prometheus-port", value_name = "PORT")]
+	pub prometheus_port: Option<u16>,
+
+	/// Do not expose a Prometheus metric endpoint.
+	///
+	/// Prometheus metric endpoint is enabled by default.
+	#[structopt(long = "no-prometheus")]
+	pub no_prometheus: bool,
+
+	/// Serve the `/health` and `/ready` endpoints from the Prometheus endpoint.
+	///
+	/// Can't be used together with `--no-prometheus`.
+	#[structopt(long = "health")]
+	pub health: bool,
+
+	/// Maximum number of blocks the node may be behind the network to be reported as ready.
+	#[structopt(long = "health-max-blocks-behind", value_name = "COUNT")]
+	pub health_max_blocks_behind: Option<u64>,
+
+	/// Minimum number of peers for the node to be reported as ready.
+	///
+	/// Only checked if the chain has boot nodes.
+	#[structopt(long = "health-min-peers", value_name = "COUNT")]
+	pub health_min_peers: Option<usize>,
+
+	/// Maximum number of blocks the finalized block may be behind the best block for the node
+	/// to be reported as ready.
+	#[structopt(long = "health-max-finality-lag", value_name = "COUNT")]
+	pub health_max_finality_lag: Option<u64>,
+
+	/// Maximum number of seconds without block import while the node is behind the network
+	/// for it to be reported as ready. `0` disables the check.
+	#[structopt(long = "health-max-import-stall", value_name = "SECONDS")]
+	pub health_max_import_stall: Option<u64>,
+
 	/// The human-readable name for this node.
 	///
 	/// The node name will be reported to the telemetry server, if enabled.
@@ -526,6 +653,10 @@ pub struct RunCmd {
 	/// telemetry endpoints. Verbosity levels range from 0-9, with 0 denoting
 	/// the least verbosity. If no verbosity level is specified the default is
 	/// 0.
+	///
+	/// Besides WebSocket URLs, `tcp://host:port`, `stdout://` and
+	/// `file://path?max_size=BYTES&max_files=COUNT` are supported, which
+	/// receive one JSON object per line.
 	#[structopt(long = "telemetry-url", value_name = "URL VERBOSITY", parse(try_from_str = parse_telemetry_endpoints))]
 	pub telemetry_endpoints: Vec<(String, u8)>,
 
@@ -541,6 +672,18 @@ pub struct RunCmd {
 	)]
 	pub offchain_worker: OffchainWorkerEnabled,
 
+	/// Index the events of imported blocks and enable the `chain_queryEvents` RPC.
+	///
+	/// Events can only be indexed for blocks with available state, run an archive node
+	/// (`--pruning archive`) to index the complete chain.
+	#[structopt(long = "index-events")]
+	pub index_events: bool,
+
+	/// Index the blocks including extrinsics, so `author_transactionStatus` reports
+	/// included extrinsics which are no longer tracked by the transaction pool.
+	#[structopt(long = "index-transactions")]
+	pub index_transactions: bool,
+
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub shared_params: SharedParams,
@@ -0,0 +1,82 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RocksDB-based storage of client maintained indexes.
+
+use std::sync::Arc;
+
+use kvdb::KeyValueDB;
+use sc_client_api::backend::IndexStorage;
+use crate::utils::db_err;
+
+/// Index storage backed by a single database column.
+#[derive(Clone)]
+pub struct DbIndexStorage {
+	db: Arc<dyn KeyValueDB>,
+	column: u32,
+}
+
+impl DbIndexStorage {
+	/// Create index storage using the given column of the `KeyValueDB` backend.
+	pub fn new(db: Arc<dyn KeyValueDB>, column: u32) -> Self {
+		DbIndexStorage { db, column }
+	}
+}
+
+impl IndexStorage for DbIndexStorage {
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+		self.db.get(self.column, key)
+			.ok()
+			.and_then(|x| x)
+			.map(|v| v.to_vec())
+	}
+
+	fn keys_with_prefix(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+		self.db.iter_from_prefix(self.column, prefix).map(|(key, _)| key.into_vec()).collect()
+	}
+
+	fn commit(&self, changes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> sp_blockchain::Result<()> {
+		let mut tx = self.db.transaction();
+		for (key, value) in changes {
+			match value {
+				Some(value) => tx.put_vec(self.column, &key, value),
+				None => tx.delete(self.column, &key),
+			}
+		}
+		self.db.write(tx).map_err(db_err)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::columns;
+
+	#[test]
+	fn should_commit_changes() {
+		let db = Arc::new(kvdb_memorydb::create(crate::utils::NUM_COLUMNS));
+		let storage = DbIndexStorage::new(db, columns::EVENT_INDEX);
+
+		storage.commit(vec![(b"a".to_vec(), Some(b"1".to_vec())), (b"b".to_vec(), Some(b"2".to_vec()))]).unwrap();
+		storage.commit(vec![(b"a".to_vec(), None), (b"bc".to_vec(), Some(Vec::new()))]).unwrap();
+
+		assert_eq!(storage.get(b"a"), None);
+		assert_eq!(storage.get(b"b"), Some(b"2".to_vec()));
+		let mut keys = storage.keys_with_prefix(b"b");
+		keys.sort();
+		assert_eq!(keys, vec![b"b".to_vec(), b"bc".to_vec()]);
+	}
+}
@@ -28,6 +28,7 @@
 
 pub mod light;
 pub mod offchain;
+pub mod index_storage;
 
 mod children;
 mod cache;
@@ -78,6 +79,7 @@ use sp_blockchain::{CachedHeaderMetadata, HeaderMetadata, HeaderMetadataCache};
 use crate::storage_cache::{CachingState, SharedCache, new_shared_cache};
 use crate::stats::StateUsageStats;
 use log::{trace, debug, warn};
+use prometheus_endpoint::Registry;
 pub use sc_state_db::PruningMode;
 
 #[cfg(feature = "test-helpers")]
@@ -283,6 +285,7 @@ pub fn new_client<E, S, Block, RA>(
 	fork_blocks: ForkBlocks<Block>,
 	bad_blocks: BadBlocks<Block>,
 	execution_extensions: ExecutionExtensions<Block>,
+	prometheus_registry: Option<Registry>,
 ) -> Result<(
 		sc_client::Client<
 			Backend<Block>,
@@ -309,6 +312,7 @@ pub fn new_client<E, S, Block, RA>(
 			fork_blocks,
 			bad_blocks,
 			execution_extensions,
+			prometheus_registry,
 		)?,
 		backend,
 	))
@@ -328,6 +332,10 @@ pub(crate) mod columns {
 	/// Offchain workers local storage
 	pub const OFFCHAIN: u32 = 9;
 	pub const CACHE: u32 = 10;
+	/// Events of imported blocks, maintained by the event indexer.
+	pub const EVENT_INDEX: u32 = 11;
+	/// Blocks including extrinsics, maintained by the transaction indexer.
+	pub const TRANSACTION_INDEX: u32 = 12;
 }
 
 struct PendingBlock<Block: BlockT> {
@@ -740,6 +748,8 @@ impl<T: Clone> FrozenForDuration<T> {
 pub struct Backend<Block: BlockT> {
 	storage: Arc<StorageDb<Block>>,
 	offchain_storage: offchain::LocalStorage,
+	event_index_storage: Arc<index_storage::DbIndexStorage>,
+	transaction_index_storage: Arc<index_storage::DbIndexStorage>,
 	changes_tries_storage: DbChangesTrieStorage<Block>,
 	blockchain: BlockchainDb<Block>,
 	canonicalization_delay: u64,
@@ -791,6 +801,10 @@ impl<Block: BlockT> Backend<Block> {
 			state_db,
 		};
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
+		let event_index_storage = Arc::new(index_storage::DbIndexStorage::new(db.clone(), columns::EVENT_INDEX));
+		let transaction_index_storage = Arc::new(
+			index_storage::DbIndexStorage::new(db.clone(), columns::TRANSACTION_INDEX)
+		);
 		let changes_tries_storage = DbChangesTrieStorage::new(
 			db,
 			columns::META,
@@ -809,6 +823,8 @@ impl<Block: BlockT> Backend<Block> {
 		Ok(Backend {
 			storage: Arc::new(storage_db),
 			offchain_storage,
+			event_index_storage,
+			transaction_index_storage,
 			changes_tries_storage,
 			blockchain,
 			canonicalization_delay,
@@ -1441,6 +1457,13 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		Some(self.offchain_storage.clone())
 	}
 
+	fn event_index_storage(&self) -> Option<Arc<dyn sc_client_api::backend::IndexStorage>> {
+		Some(self.event_index_storage.clone())
+	}
+
+	fn transaction_index_storage(&self) -> Option<Arc<dyn sc_client_api::backend::IndexStorage>> {
+		Some(self.transaction_index_storage.clone())
+	}
 
 	fn usage_info(&self) -> Option<UsageInfo> {
 		let (io_stats, state_stats) = self.io_stats.take_or_else(||
@@ -1602,6 +1625,16 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		}
 	}
 
+	fn pin_block(&self, hash: &Block::Hash) -> ClientResult<()> {
+		self.storage.state_db.pin(hash).map_err(|_|
+			sp_blockchain::Error::UnknownBlock(format!("State already discarded for {:?}", hash))
+		)
+	}
+
+	fn unpin_block(&self, hash: &Block::Hash) {
+		self.storage.state_db.unpin(hash)
+	}
+
 	fn have_state_at(&self, hash: &Block::Hash, number: NumberFor<Block>) -> bool {
 		if self.is_archive {
 			match self.blockchain.header(BlockId::Hash(hash.clone())) {
@@ -2275,4 +2308,25 @@ pub(crate) mod tests {
 			backend.commit_operation(op).unwrap_err();
 		}
 	}
+
+	#[test]
+	fn pinned_block_state_is_not_pruned() {
+		let backend = Backend::<Block>::new_test(1, 0);
+
+		let block0 = insert_header(&backend, 0, Default::default(), None, Default::default());
+		let block1 = insert_header(&backend, 1, block0, None, Default::default());
+		backend.pin_block(&block1).unwrap();
+
+		let mut parent = block1;
+		for number in 2..5 {
+			parent = insert_header(&backend, number, parent, None, Default::default());
+		}
+		assert!(!backend.have_state_at(&block0, 0));
+		assert!(backend.have_state_at(&block1, 1));
+
+		backend.unpin_block(&block1);
+		insert_header(&backend, 5, parent, None, Default::default());
+		assert!(!backend.have_state_at(&block1, 1));
+		assert!(backend.pin_block(&block1).is_err());
+	}
 }
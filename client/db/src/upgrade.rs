@@ -36,17 +36,32 @@ use crate::{
 const VERSION_FILE_NAME: &'static str = "db_version";
 
 /// Current db version.
-const CURRENT_VERSION: u32 = 1;
+const CURRENT_VERSION: u32 = 3;
 
 /// Number of columns in v0.
 const V0_NUM_COLUMNS: u32 = 10;
 
+/// Number of columns in v1.
+const V1_NUM_COLUMNS: u32 = 11;
+
+/// Number of columns in v2.
+const V2_NUM_COLUMNS: u32 = 12;
+
 /// Upgrade database to current version.
 pub fn upgrade_db<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> sp_blockchain::Result<()> {
 	let db_version = current_version(db_path)?;
 	match db_version {
-		0 => migrate_0_to_1::<Block>(db_path, db_type)?,
-		1 => (),
+		0 => {
+			migrate_0_to_1::<Block>(db_path, db_type)?;
+			migrate_1_to_2(db_path, db_type)?;
+			migrate_2_to_3(db_path, db_type)?;
+		},
+		1 => {
+			migrate_1_to_2(db_path, db_type)?;
+			migrate_2_to_3(db_path, db_type)?;
+		},
+		2 => migrate_2_to_3(db_path, db_type)?,
+		3 => (),
 		_ => Err(sp_blockchain::Error::Backend(format!("Future database version: {}", db_version)))?,
 	}
 
@@ -102,6 +117,22 @@ fn migrate_0_to_1<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> sp_bl
 	Ok(())
 }
 
+/// Migration from version1 to version2:
+/// the number of columns has changed from 11 to 12, adding the event index column.
+fn migrate_1_to_2(db_path: &Path, db_type: DatabaseType) -> sp_blockchain::Result<()> {
+	let db = open_database(db_path, db_type, V1_NUM_COLUMNS)?;
+	db.add_column().map_err(db_err)?;
+	db.flush().map_err(db_err)
+}
+
+/// Migration from version2 to version3:
+/// the number of columns has changed from 12 to 13, adding the transaction index column.
+fn migrate_2_to_3(db_path: &Path, db_type: DatabaseType) -> sp_blockchain::Result<()> {
+	let db = open_database(db_path, db_type, V2_NUM_COLUMNS)?;
+	db.add_column().map_err(db_err)?;
+	db.flush().map_err(db_err)
+}
+
 /// Reads current database version from the file at given path.
 /// If the file does not exist returns 0.
 fn current_version(path: &Path) -> sp_blockchain::Result<u32> {
@@ -185,6 +216,34 @@ mod tests {
 		assert_eq!(current_version(db_dir.path()).unwrap(), CURRENT_VERSION);
 	}
 
+	#[test]
+	fn upgrade_from_1_to_2_works() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let db_path = db_dir.path();
+		{
+			let db_cfg = DatabaseConfig::with_columns(V1_NUM_COLUMNS);
+			Database::open(&db_cfg, db_path.to_str().unwrap()).unwrap();
+		}
+		fs::File::create(version_file_path(db_path)).unwrap().write_all(b"1").unwrap();
+
+		open_database(db_path).unwrap();
+		assert_eq!(current_version(db_path).unwrap(), CURRENT_VERSION);
+	}
+
+	#[test]
+	fn upgrade_from_2_to_3_works() {
+		let db_dir = tempfile::TempDir::new().unwrap();
+		let db_path = db_dir.path();
+		{
+			let db_cfg = DatabaseConfig::with_columns(V2_NUM_COLUMNS);
+			Database::open(&db_cfg, db_path.to_str().unwrap()).unwrap();
+		}
+		fs::File::create(version_file_path(db_path)).unwrap().write_all(b"2").unwrap();
+
+		open_database(db_path).unwrap();
+		assert_eq!(current_version(db_path).unwrap(), CURRENT_VERSION);
+	}
+
 	#[test]
 	fn upgrade_from_0_to_1_works() {
 		for version_from_file in &[None, Some(0)] {
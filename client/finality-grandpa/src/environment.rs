@@ -61,6 +61,7 @@ use crate::consensus_changes::SharedConsensusChanges;
 use crate::justification::GrandpaJustification;
 use crate::until_imported::UntilVoteTargetImported;
 use crate::voting_rule::VotingRule;
+use crate::metrics::Metrics;
 use sp_finality_grandpa::{AuthorityId, AuthoritySignature, SetId, RoundNumber};
 
 type HistoricalVotes<Block> = finality_grandpa::HistoricalVotes<
@@ -388,6 +389,7 @@ pub(crate) struct Environment<B, E, Block: BlockT, N: NetworkT<Block>, RA, SC, V
 	pub(crate) set_id: SetId,
 	pub(crate) voter_set_state: SharedVoterSetState<Block>,
 	pub(crate) voting_rule: VR,
+	pub(crate) metrics: Option<Metrics>,
 }
 
 impl<B, E, Block: BlockT, N: NetworkT<Block>, RA, SC, VR> Environment<B, E, Block, N, RA, SC, VR> {
@@ -662,6 +664,10 @@ where
 
 			crate::aux_schema::write_voter_set_state(&*self.client, &set_state)?;
 
+			if let Some(metrics) = self.metrics.as_ref() {
+				metrics.note_vote("propose");
+			}
+
 			Ok(Some(set_state))
 		})?;
 
@@ -703,6 +709,10 @@ where
 
 			crate::aux_schema::write_voter_set_state(&*self.client, &set_state)?;
 
+			if let Some(metrics) = self.metrics.as_ref() {
+				metrics.note_vote("prevote");
+			}
+
 			Ok(Some(set_state))
 		})?;
 
@@ -754,6 +764,10 @@ where
 
 			crate::aux_schema::write_voter_set_state(&*self.client, &set_state)?;
 
+			if let Some(metrics) = self.metrics.as_ref() {
+				metrics.note_vote("precommit");
+			}
+
 			Ok(Some(set_state))
 		})?;
 
@@ -776,6 +790,10 @@ where
 			state.finalized.as_ref().map(|e| e.1),
 		);
 
+		if let Some(metrics) = self.metrics.as_ref() {
+			metrics.round.set(round as f64);
+		}
+
 		self.update_voter_set_state(|voter_set_state| {
 			// NOTE: we don't use `with_current_round` here, it is possible that
 			// we are not currently tracking this round if it is a round we
@@ -89,6 +89,7 @@ mod finality_proof;
 mod import;
 mod justification;
 mod light_import;
+mod metrics;
 mod observer;
 mod until_imported;
 mod voting_rule;
@@ -103,6 +104,8 @@ pub use voting_rule::{
 
 use aux_schema::PersistentData;
 use environment::{Environment, VoterSetState};
+use metrics::Metrics;
+use prometheus_endpoint::Registry;
 use import::GrandpaBlockImport;
 use until_imported::UntilGlobalMessageBlocksImported;
 use communication::{NetworkBridge, Network as NetworkT};
@@ -533,6 +536,8 @@ pub struct GrandpaParams<B, E, Block: BlockT, N, RA, SC, VR, X, Sp> {
 	pub voting_rule: VR,
 	/// How to spawn background tasks.
 	pub executor: Sp,
+	/// Registry for recording Prometheus metrics to. `None` if metrics are disabled.
+	pub prometheus_registry: Option<Registry>,
 }
 
 /// Run a GRANDPA voter as a task. Provide configuration and a link to a
@@ -562,6 +567,7 @@ pub fn run_grandpa_voter<B, E, Block: BlockT, N, RA, SC, VR, X, Sp>(
 		telemetry_on_connect,
 		voting_rule,
 		executor,
+		prometheus_registry,
 	} = grandpa_params;
 
 	let LinkHalf {
@@ -607,6 +613,13 @@ pub fn run_grandpa_voter<B, E, Block: BlockT, N, RA, SC, VR, X, Sp>(
 		future::Either::Right(future::pending())
 	};
 
+	let metrics = match prometheus_registry {
+		Some(registry) => Some(Metrics::register(&registry).map_err(|e|
+			ClientError::Msg(format!("Failed to register GRANDPA metrics: {}", e))
+		)?),
+		None => None,
+	};
+
 	let voter_work = VoterWork::new(
 		client,
 		config,
@@ -615,6 +628,7 @@ pub fn run_grandpa_voter<B, E, Block: BlockT, N, RA, SC, VR, X, Sp>(
 		voting_rule,
 		persistent_data,
 		voter_commands_rx,
+		metrics,
 	);
 
 	let voter_work = voter_work
@@ -656,6 +670,7 @@ where
 		voting_rule: VR,
 		persistent_data: PersistentData<Block>,
 		voter_commands_rx: mpsc::UnboundedReceiver<VoterCommand<Block::Hash, NumberFor<Block>>>,
+		metrics: Option<Metrics>,
 	) -> Self {
 
 		let voters = persistent_data.authority_set.current_authorities();
@@ -670,6 +685,7 @@ where
 			authority_set: persistent_data.authority_set.clone(),
 			consensus_changes: persistent_data.consensus_changes.clone(),
 			voter_set_state: persistent_data.set_state.clone(),
+			metrics,
 		});
 
 		let mut work = VoterWork {
@@ -789,6 +805,7 @@ where
 					consensus_changes: self.env.consensus_changes.clone(),
 					network: self.env.network.clone(),
 					voting_rule: self.env.voting_rule.clone(),
+					metrics: self.env.metrics.clone(),
 				});
 
 				self.rebuild_voter();
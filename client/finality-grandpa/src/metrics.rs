@@ -0,0 +1,51 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the GRANDPA voter.
+
+use prometheus_endpoint::{register, CounterVec, Gauge, Opts, PrometheusError, Registry};
+
+/// Metrics of the rounds and of the votes cast by the voter.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	/// The round the voter is in.
+	pub(crate) round: Gauge,
+	/// The votes cast by the local voter, by kind.
+	pub(crate) votes: CounterVec,
+}
+
+impl Metrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Metrics {
+			round: register(
+				Gauge::new("substrate_finality_grandpa_round", "Highest completed GRANDPA round")?,
+				registry,
+			)?,
+			votes: register(CounterVec::new(
+				Opts::new(
+					"substrate_finality_grandpa_votes_total",
+					"Votes cast by the local voter, by kind",
+				),
+				&["kind"],
+			)?, registry)?,
+		})
+	}
+
+	/// Account for a vote of the given kind cast by the local voter.
+	pub(crate) fn note_vote(&self, kind: &str) {
+		self.votes.with_label_values(&[kind]).inc();
+	}
+}
@@ -0,0 +1,248 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of the events deposited by `frame_system`.
+
+use std::marker::PhantomData;
+use codec::{Compact, Decode};
+use serde_json::Value;
+use crate::{Error, Metadata, Result};
+
+/// The phase of the block an event was deposited in, mirrors `frame_system::Phase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Decode)]
+pub enum Phase {
+	/// Applying the extrinsic with the given index.
+	ApplyExtrinsic(u32),
+	/// Finalizing the block.
+	Finalization,
+}
+
+/// A decoded `frame_system::EventRecord`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventRecord<Hash> {
+	/// The phase of the block the event was deposited in.
+	pub phase: Phase,
+	/// Name of the module which deposited the event.
+	pub module: String,
+	/// Name of the event.
+	pub event: String,
+	/// The decoded arguments of the event.
+	pub arguments: Vec<Value>,
+	/// The topics of the event.
+	pub topics: Vec<Hash>,
+}
+
+/// Iterator over the records of an encoded `System::Events` storage value.
+///
+/// Records can not be skipped without decoding them, so iteration ends after the
+/// first error.
+pub struct Events<'a, Hash> {
+	metadata: &'a Metadata,
+	input: &'a [u8],
+	remaining: u32,
+	_marker: PhantomData<Hash>,
+}
+
+impl Metadata {
+	/// Decode the value of the `System::Events` storage entry.
+	pub fn decode_events<'a, Hash: Decode>(&'a self, encoded: &'a [u8]) -> Result<Events<'a, Hash>> {
+		let mut input = encoded;
+		let remaining = Compact::<u32>::decode(&mut input)?.0;
+		Ok(Events { metadata: self, input, remaining, _marker: PhantomData })
+	}
+
+	fn decode_event_record<Hash: Decode>(&self, input: &mut &[u8]) -> Result<EventRecord<Hash>> {
+		let phase = Phase::decode(input)?;
+		let (module_index, event_index) = <(u8, u8)>::decode(input)?;
+		let unknown = || Error::UnknownEvent { module: module_index, event: event_index };
+		let module = self.event_modules.get(module_index as usize).ok_or_else(unknown)?;
+		let event = module.events.get(event_index as usize).ok_or_else(unknown)?;
+
+		let arguments = event.arguments.iter()
+			.map(|ty| self.types.decode(ty, input))
+			.collect::<Result<_>>()?;
+		let topics = Vec::<Hash>::decode(input)?;
+
+		Ok(EventRecord {
+			phase,
+			module: module.name.clone(),
+			event: event.name.clone(),
+			arguments,
+			topics,
+		})
+	}
+}
+
+impl<'a, Hash: Decode> Iterator for Events<'a, Hash> {
+	type Item = Result<EventRecord<Hash>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining == 0 {
+			return None;
+		}
+
+		let record = self.metadata.decode_event_record(&mut self.input);
+		self.remaining = if record.is_ok() { self.remaining - 1 } else { 0 };
+		Some(record)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TypeRegistry;
+	use codec::Encode;
+	use frame_metadata::{
+		DecodeDifferent, EventMetadata, ExtrinsicMetadata, ModuleMetadata, RuntimeMetadataLastVersion,
+		RuntimeMetadataPrefixed,
+	};
+	use serde_json::json;
+	use sp_core::H256;
+
+	fn module(name: &'static str, events: Option<Vec<(&'static str, Vec<&'static str>)>>) -> ModuleMetadata {
+		ModuleMetadata {
+			name: DecodeDifferent::Decoded(name.into()),
+			storage: None,
+			calls: None,
+			event: events.map(|events| DecodeDifferent::Decoded(events.into_iter().map(|(name, arguments)| {
+				EventMetadata {
+					name: DecodeDifferent::Decoded(name.into()),
+					arguments: DecodeDifferent::Decoded(arguments.into_iter().map(Into::into).collect()),
+					documentation: DecodeDifferent::Decoded(vec![]),
+				}
+			}).collect())),
+			constants: DecodeDifferent::Decoded(vec![]),
+			errors: DecodeDifferent::Decoded(vec![]),
+		}
+	}
+
+	fn metadata() -> Metadata {
+		let metadata: RuntimeMetadataPrefixed = RuntimeMetadataLastVersion {
+			modules: DecodeDifferent::Decoded(vec![
+				module("System", Some(vec![("ExtrinsicSuccess", vec!["DispatchInfo"])])),
+				module("Timestamp", None),
+				module("Balances", Some(vec![
+					("Endowed", vec!["AccountId", "Balance"]),
+					("Transfer", vec!["AccountId", "AccountId", "Balance"]),
+				])),
+			]),
+			extrinsic: ExtrinsicMetadata { version: 4, signed_extensions: vec![] },
+		}.into();
+		Metadata::new(&metadata.encode(), TypeRegistry::default()).unwrap()
+	}
+
+	#[test]
+	fn should_decode_events() {
+		let topic = H256::repeat_byte(7);
+		let encoded = vec![
+			(0u8, 1u32, 1u8, 1u8, [1u8; 32], [2u8; 32], 100u128, vec![topic]).encode(),
+			(1u8, 0u8, 0u8, (10u32, 0u8, true), Vec::<H256>::new()).encode(),
+		];
+		let mut events = codec::Compact(2u32).encode();
+		encoded.into_iter().for_each(|record| events.extend(record));
+
+		let metadata = metadata();
+		let records = metadata.decode_events::<H256>(&events).unwrap().collect::<Result<Vec<_>>>().unwrap();
+
+		assert_eq!(records, vec![
+			EventRecord {
+				phase: Phase::ApplyExtrinsic(1),
+				module: "Balances".into(),
+				event: "Transfer".into(),
+				arguments: vec![
+					json!(format!("0x{}", "01".repeat(32))),
+					json!(format!("0x{}", "02".repeat(32))),
+					json!(100),
+				],
+				topics: vec![topic],
+			},
+			EventRecord {
+				phase: Phase::Finalization,
+				module: "System".into(),
+				event: "ExtrinsicSuccess".into(),
+				arguments: vec![json!({ "weight": 10, "class": "Normal", "pays_fee": true })],
+				topics: vec![],
+			},
+		]);
+	}
+
+	#[test]
+	fn should_decode_events_with_v11_metadata() {
+		use crate::v11;
+		use frame_metadata::{StorageEntryModifier, StorageEntryType};
+
+		let module = |name: &str, events: Option<Vec<(&'static str, Vec<&'static str>)>>| v11::ModuleMetadata {
+			name: name.into(),
+			storage: Some(v11::StorageMetadata {
+				prefix: name.into(),
+				entries: vec![v11::StorageEntryMetadata {
+					name: "Events".into(),
+					modifier: StorageEntryModifier::Default,
+					ty: StorageEntryType::Plain(DecodeDifferent::Decoded("Vec<EventRecord>".into())),
+					default: vec![0],
+					documentation: vec![],
+				}],
+			}),
+			calls: None,
+			event: events.map(|events| events.into_iter().map(|(name, arguments)| EventMetadata {
+				name: DecodeDifferent::Decoded(name.into()),
+				arguments: DecodeDifferent::Decoded(arguments.into_iter().map(Into::into).collect()),
+				documentation: DecodeDifferent::Decoded(vec![]),
+			}).collect()),
+			constants: vec![],
+			errors: vec![],
+		};
+		let metadata = v11::RuntimeMetadataV11 {
+			modules: vec![
+				module("System", Some(vec![("ExtrinsicSuccess", vec!["DispatchInfo"])])),
+				module("Balances", Some(vec![("Endowed", vec!["AccountId", "Balance"])])),
+			],
+			extrinsic: ExtrinsicMetadata { version: 4, signed_extensions: vec![] },
+		};
+		let metadata = Metadata::new(
+			&(frame_metadata::META_RESERVED, 11u8, metadata).encode(),
+			TypeRegistry::default(),
+		).unwrap();
+
+		let mut events = codec::Compact(1u32).encode();
+		events.extend((0u8, 0u32, 1u8, 0u8, [1u8; 32], 100u128, Vec::<H256>::new()).encode());
+		let records = metadata.decode_events::<H256>(&events).unwrap().collect::<Result<Vec<_>>>().unwrap();
+
+		assert_eq!(records, vec![EventRecord {
+			phase: Phase::ApplyExtrinsic(0),
+			module: "Balances".into(),
+			event: "Endowed".into(),
+			arguments: vec![json!(format!("0x{}", "01".repeat(32))), json!(100)],
+			topics: vec![],
+		}]);
+	}
+
+	#[test]
+	fn should_stop_after_unknown_event() {
+		let mut events = codec::Compact(2u32).encode();
+		events.extend((1u8, 2u8, 0u8).encode());
+		events.extend((1u8, 0u8, 0u8, (10u32, 0u8, true), Vec::<H256>::new()).encode());
+
+		let metadata = metadata();
+		let mut records = metadata.decode_events::<H256>(&events).unwrap();
+
+		match records.next() {
+			Some(Err(Error::UnknownEvent { module: 2, event: 0 })) => (),
+			other => panic!("Unexpected record {:?}", other),
+		}
+		assert!(records.next().is_none());
+	}
+}
@@ -0,0 +1,297 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of extrinsics in the format of `sp_runtime::generic::UncheckedExtrinsic`.
+
+use codec::{Compact, Decode};
+use serde_json::Value;
+use crate::{Error, Metadata, Result};
+
+/// A decoded extrinsic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extrinsic {
+	/// The extrinsic format version.
+	pub version: u8,
+	/// The signature of signed extrinsics.
+	pub signature: Option<ExtrinsicSignature>,
+	/// The decoded call.
+	pub call: Value,
+}
+
+/// The signature part of a decoded signed extrinsic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtrinsicSignature {
+	/// The address of the signer.
+	pub address: Value,
+	/// The signature.
+	pub signature: Value,
+	/// The identifiers and decoded payloads of the signed extensions, in the order of
+	/// the metadata.
+	pub extensions: Vec<(String, Value)>,
+}
+
+impl Metadata {
+	/// Decode an extrinsic as it is encoded in a block body.
+	///
+	/// The address and signature are decoded as the `Address` and `Signature` types of
+	/// the type registry, the signed extensions as the types named by their identifier.
+	pub fn decode_extrinsic(&self, encoded: &[u8]) -> Result<Extrinsic> {
+		let mut input = encoded;
+		// Extrinsics are encoded like a `Vec<u8>`, prefixed by their length.
+		let len = Compact::<u32>::decode(&mut input)?.0 as usize;
+		if len != input.len() {
+			return Err(Error::InvalidValue("extrinsic length"));
+		}
+
+		let version = u8::decode(&mut input)?;
+		let is_signed = version & 0b1000_0000 != 0;
+		let version = version & 0b0111_1111;
+		if version != self.extrinsic_version {
+			return Err(Error::UnsupportedExtrinsic(version));
+		}
+
+		let signature = if is_signed {
+			Some(ExtrinsicSignature {
+				address: self.types.decode("Address", &mut input)?,
+				signature: self.types.decode("Signature", &mut input)?,
+				extensions: self.signed_extensions.iter()
+					.map(|extension| Ok((extension.clone(), self.types.decode(extension, &mut input)?)))
+					.collect::<Result<_>>()?,
+			})
+		} else {
+			None
+		};
+		let call = self.types.decode("Call", &mut input)?;
+		if !input.is_empty() {
+			return Err(Error::InvalidValue("extrinsic length"));
+		}
+
+		Ok(Extrinsic { version, signature, call })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TypeRegistry;
+	use codec::Encode;
+	use frame_metadata::{
+		DecodeDifferent, ExtrinsicMetadata, FunctionArgumentMetadata, FunctionMetadata, ModuleMetadata,
+		RuntimeMetadataLastVersion, RuntimeMetadataPrefixed,
+	};
+	use serde_json::json;
+
+	fn module(name: &'static str, calls: Option<Vec<(&'static str, Vec<(&'static str, &'static str)>)>>) -> ModuleMetadata {
+		ModuleMetadata {
+			name: DecodeDifferent::Decoded(name.into()),
+			storage: None,
+			calls: calls.map(|calls| DecodeDifferent::Decoded(calls.into_iter().map(|(name, arguments)| {
+				FunctionMetadata {
+					name: DecodeDifferent::Decoded(name.into()),
+					arguments: DecodeDifferent::Decoded(arguments.into_iter().map(|(name, ty)| {
+						FunctionArgumentMetadata {
+							name: DecodeDifferent::Decoded(name.into()),
+							ty: DecodeDifferent::Decoded(ty.into()),
+						}
+					}).collect()),
+					documentation: DecodeDifferent::Decoded(vec![]),
+				}
+			}).collect())),
+			event: None,
+			constants: DecodeDifferent::Decoded(vec![]),
+			errors: DecodeDifferent::Decoded(vec![]),
+		}
+	}
+
+	fn metadata() -> Metadata {
+		let metadata: RuntimeMetadataPrefixed = RuntimeMetadataLastVersion {
+			modules: DecodeDifferent::Decoded(vec![
+				module("System", None),
+				module("Timestamp", Some(vec![("set", vec![("now", "Compact<T::Moment>")])])),
+				module("Balances", Some(vec![
+					("transfer", vec![
+						("dest", "<T::Lookup as StaticLookup>::Source"),
+						("value", "Compact<T::Balance>"),
+					]),
+				])),
+				module("Sudo", Some(vec![("sudo", vec![("proposal", "Box<<T as Trait>::Proposal>")])])),
+			]),
+			extrinsic: ExtrinsicMetadata {
+				version: 4,
+				signed_extensions: vec![
+					DecodeDifferent::Decoded("CheckEra".into()),
+					DecodeDifferent::Decoded("CheckNonce".into()),
+					DecodeDifferent::Decoded("ChargeTransactionPayment".into()),
+				],
+			},
+		}.into();
+		Metadata::new(&metadata.encode(), TypeRegistry::default()).unwrap()
+	}
+
+	fn transfer() -> Value {
+		json!({
+			"module": "Balances",
+			"function": "transfer",
+			"args": [
+				{ "name": "dest", "type": "<T::Lookup as StaticLookup>::Source", "value": { "Index": 3 } },
+				{ "name": "value", "type": "Compact<T::Balance>", "value": 100 },
+			],
+		})
+	}
+
+	#[test]
+	fn should_decode_unsigned_extrinsics() {
+		let call = (0u8, 0u8, Compact(1_000u64)).encode();
+		let encoded = [&[4u8][..], &call].concat().encode();
+
+		assert_eq!(metadata().decode_extrinsic(&encoded).unwrap(), Extrinsic {
+			version: 4,
+			signature: None,
+			call: json!({
+				"module": "Timestamp",
+				"function": "set",
+				"args": [{ "name": "now", "type": "Compact<T::Moment>", "value": 1000 }],
+			}),
+		});
+	}
+
+	#[test]
+	fn should_decode_signed_extrinsics_with_nested_calls() {
+		let mut payload = vec![0x84, 0xff];
+		payload.extend(&[1u8; 32]);
+		payload.extend((1u8, [2u8; 64]).encode());
+		payload.extend(&[0u8]);
+		payload.extend((Compact(5u32), Compact(7u128)).encode());
+		payload.extend((2u8, 0u8, 1u8, 0u8, 3u8, Compact(100u128)).encode());
+
+		let extrinsic = metadata().decode_extrinsic(&payload.encode()).unwrap();
+		assert_eq!(extrinsic, Extrinsic {
+			version: 4,
+			signature: Some(ExtrinsicSignature {
+				address: json!({ "Id": format!("0x{}", "01".repeat(32)) }),
+				signature: json!({ "Sr25519": format!("0x{}", "02".repeat(64)) }),
+				extensions: vec![
+					("CheckEra".into(), json!("Immortal")),
+					("CheckNonce".into(), json!(5)),
+					("ChargeTransactionPayment".into(), json!(7)),
+				],
+			}),
+			call: json!({
+				"module": "Sudo",
+				"function": "sudo",
+				"args": [{ "name": "proposal", "type": "Box<<T as Trait>::Proposal>", "value": transfer() }],
+			}),
+		});
+	}
+
+	#[test]
+	fn should_decode_extrinsics_with_v11_metadata() {
+		use crate::v11;
+		use frame_metadata::{StorageEntryModifier, StorageEntryType, StorageHasher, META_RESERVED};
+
+		// the modules of version 11 only differ by the layout of their storage entries.
+		let storage = v11::StorageMetadata {
+			prefix: "Balances".into(),
+			entries: vec![v11::StorageEntryMetadata {
+				name: "FreeBalance".into(),
+				modifier: StorageEntryModifier::Default,
+				ty: StorageEntryType::Map {
+					hasher: StorageHasher::Blake2_256,
+					key: DecodeDifferent::Decoded("T::AccountId".into()),
+					value: DecodeDifferent::Decoded("T::Balance".into()),
+					is_linked: false,
+				},
+				default: vec![0; 16],
+				documentation: vec![],
+			}],
+		};
+		let v11_module = |module: ModuleMetadata, storage| v11::ModuleMetadata {
+			name: crate::decoded(module.name).unwrap(),
+			storage,
+			calls: module.calls.map(|calls| crate::decoded(calls).unwrap()),
+			event: None,
+			constants: vec![],
+			errors: vec![],
+		};
+		let metadata = v11::RuntimeMetadataV11 {
+			modules: vec![
+				v11_module(module("System", None), None),
+				v11_module(module("Balances", Some(vec![
+					("transfer", vec![
+						("dest", "<T::Lookup as StaticLookup>::Source"),
+						("value", "Compact<T::Balance>"),
+					]),
+				])), Some(storage)),
+			],
+			extrinsic: ExtrinsicMetadata {
+				version: 4,
+				signed_extensions: vec![DecodeDifferent::Decoded("CheckNonce".into())],
+			},
+		};
+		let metadata = Metadata::new(&(META_RESERVED, 11u8, metadata).encode(), TypeRegistry::default()).unwrap();
+
+		let mut payload = vec![0x84, 0xff];
+		payload.extend(&[1u8; 32]);
+		payload.extend((1u8, [2u8; 64]).encode());
+		payload.extend(Compact(5u32).encode());
+		payload.extend((0u8, 0u8, 0xffu8, [3u8; 32], Compact(100u128)).encode());
+
+		assert_eq!(metadata.decode_extrinsic(&payload.encode()).unwrap(), Extrinsic {
+			version: 4,
+			signature: Some(ExtrinsicSignature {
+				address: json!({ "Id": format!("0x{}", "01".repeat(32)) }),
+				signature: json!({ "Sr25519": format!("0x{}", "02".repeat(64)) }),
+				extensions: vec![("CheckNonce".into(), json!(5))],
+			}),
+			call: json!({
+				"module": "Balances",
+				"function": "transfer",
+				"args": [
+					{
+						"name": "dest",
+						"type": "<T::Lookup as StaticLookup>::Source",
+						"value": { "Id": format!("0x{}", "03".repeat(32)) },
+					},
+					{ "name": "value", "type": "Compact<T::Balance>", "value": 100 },
+				],
+			}),
+		});
+
+		// older versions are not supported.
+		match Metadata::new(&(META_RESERVED, 10u8).encode(), TypeRegistry::default()) {
+			Err(Error::UnsupportedMetadata) => (),
+			other => panic!("Unexpected result {:?}", other.map(|_| ())),
+		}
+	}
+
+	#[test]
+	fn should_reject_invalid_extrinsics() {
+		let metadata = metadata();
+		let call = (1u8, 0u8, 3u8, Compact(100u128)).encode();
+
+		assert!(metadata.decode_extrinsic(&[&[4u8][..], &call].concat().encode()).is_ok());
+		match metadata.decode_extrinsic(&[&[3u8][..], &call].concat().encode()) {
+			Err(Error::UnsupportedExtrinsic(3)) => (),
+			other => panic!("Unexpected result {:?}", other),
+		}
+		match metadata.decode_extrinsic(&[&[4u8][..], &[5u8, 0u8][..]].concat().encode()) {
+			Err(Error::UnknownCall { module: 5, call: 0 }) => (),
+			other => panic!("Unexpected result {:?}", other),
+		}
+		assert!(metadata.decode_extrinsic(&[&[4u8][..], &call, &[0u8][..]].concat().encode()).is_err());
+		assert!(metadata.decode_extrinsic(&[&[4u8][..], &call].concat()).is_err());
+	}
+}
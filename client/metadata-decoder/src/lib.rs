@@ -0,0 +1,205 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime metadata driven decoding of SCALE encoded runtime values.
+//!
+//! Clients only know the opaque encoding of runtime types like events. The runtime
+//! metadata describes the modules of a runtime together with the names of the types
+//! they use, which together with a [`TypeRegistry`] is enough to render these values
+//! as JSON.
+
+#![warn(missing_docs)]
+
+mod events;
+mod extrinsics;
+mod types;
+mod v11;
+
+pub use events::{EventRecord, Events, Phase};
+pub use extrinsics::{Extrinsic, ExtrinsicSignature};
+pub use types::{TypeDef, TypeRegistry};
+
+use codec::Decode;
+use frame_metadata::{DecodeDifferent, RuntimeMetadataV12, META_RESERVED};
+
+/// Decoding error.
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum Error {
+	/// Invalid SCALE encoding.
+	#[display(fmt = "Invalid encoding: {}", _0)]
+	Codec(codec::Error),
+	/// The metadata is not of a supported version.
+	#[display(fmt = "Unsupported metadata")]
+	UnsupportedMetadata,
+	/// A type name is not known by the type registry.
+	#[display(fmt = "Unknown type `{}`", _0)]
+	#[from(ignore)]
+	UnknownType(String),
+	/// A type name from the metadata could not be parsed.
+	#[display(fmt = "Invalid type name `{}`", _0)]
+	#[from(ignore)]
+	InvalidTypeName(String),
+	/// A value is not valid for its type.
+	#[display(fmt = "Invalid value of {}", _0)]
+	#[from(ignore)]
+	InvalidValue(&'static str),
+	/// Type definitions are nested too deep.
+	#[display(fmt = "Type definitions nested too deep")]
+	TooDeep,
+	/// An event is not described by the metadata.
+	#[display(fmt = "Unknown event {} of module {}", event, module)]
+	UnknownEvent {
+		/// Index of the module among the modules with events.
+		module: u8,
+		/// Index of the event within the module.
+		event: u8,
+	},
+	/// A call is not described by the metadata.
+	#[display(fmt = "Unknown call {} of module {}", call, module)]
+	#[from(ignore)]
+	UnknownCall {
+		/// Index of the module among the modules with calls.
+		module: u8,
+		/// Index of the call within the module.
+		call: u8,
+	},
+	/// The extrinsic format version is not the one of the runtime.
+	#[display(fmt = "Unsupported extrinsic version {}", _0)]
+	#[from(ignore)]
+	UnsupportedExtrinsic(u8),
+}
+
+impl std::error::Error for Error {}
+
+/// Decoding result.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The parts of the runtime metadata needed for decoding.
+pub struct Metadata {
+	event_modules: Vec<EventModule>,
+	extrinsic_version: u8,
+	signed_extensions: Vec<String>,
+	types: TypeRegistry,
+}
+
+/// A module with dispatchable calls.
+#[derive(Clone)]
+struct CallModule {
+	name: String,
+	calls: Vec<CallMetadata>,
+}
+
+/// A call and the names and type names of its arguments.
+#[derive(Clone)]
+struct CallMetadata {
+	name: String,
+	arguments: Vec<(String, String)>,
+}
+
+/// A module depositing events.
+struct EventModule {
+	name: String,
+	events: Vec<EventMetadata>,
+}
+
+/// An event and the type names of its arguments.
+struct EventMetadata {
+	name: String,
+	arguments: Vec<String>,
+}
+
+impl Metadata {
+	/// Decode the metadata as returned by the `Metadata_metadata` runtime API, using
+	/// `types` to decode the values it describes.
+	///
+	/// Versions 11 and 12 of the metadata are supported.
+	pub fn new(encoded: &[u8], mut types: TypeRegistry) -> Result<Self> {
+		let input = &mut &encoded[..];
+		if u32::decode(input)? != META_RESERVED {
+			return Err(Error::UnsupportedMetadata);
+		}
+		// The version is the index of the `RuntimeMetadata` variant.
+		let (modules, extrinsic) = match u8::decode(input)? {
+			11 => {
+				let metadata = v11::RuntimeMetadataV11::decode(input)?;
+				let modules = metadata.modules.into_iter()
+					.map(|module| (module.name, module.calls, module.event))
+					.collect::<Vec<_>>();
+				(modules, metadata.extrinsic)
+			},
+			12 => {
+				let metadata = RuntimeMetadataV12::decode(input)?;
+				let modules = decoded(metadata.modules)?.into_iter()
+					.map(|module| Ok((
+						decoded(module.name)?,
+						module.calls.map(decoded).transpose()?,
+						module.event.map(decoded).transpose()?,
+					)))
+					.collect::<Result<Vec<_>>>()?;
+				(modules, metadata.extrinsic)
+			},
+			_ => return Err(Error::UnsupportedMetadata),
+		};
+
+		// The outer event and call enums of the runtime only have variants for the modules
+		// depositing events respectively having calls, in the order they are declared in.
+		let mut event_modules = Vec::new();
+		let mut call_modules = Vec::new();
+		for (name, calls, events) in modules {
+			if let Some(calls) = calls {
+				call_modules.push(CallModule {
+					name: name.clone(),
+					calls: calls.into_iter().map(|call| Ok(CallMetadata {
+						name: decoded(call.name)?,
+						arguments: decoded(call.arguments)?.into_iter()
+							.map(|argument| Ok((decoded(argument.name)?, decoded(argument.ty)?)))
+							.collect::<Result<_>>()?,
+					})).collect::<Result<_>>()?,
+				});
+			}
+			if let Some(events) = events {
+				event_modules.push(EventModule {
+					name,
+					events: events.into_iter().map(|event| Ok(EventMetadata {
+						name: decoded(event.name)?,
+						arguments: decoded(event.arguments)?,
+					})).collect::<Result<_>>()?,
+				});
+			}
+		}
+		types.set_calls(call_modules);
+
+		Ok(Metadata {
+			event_modules,
+			extrinsic_version: extrinsic.version,
+			signed_extensions: extrinsic.signed_extensions.into_iter().map(decoded).collect::<Result<_>>()?,
+			types,
+		})
+	}
+
+	/// The type registry used to decode values.
+	pub fn types(&self) -> &TypeRegistry {
+		&self.types
+	}
+}
+
+/// Metadata obtained by decoding is always in its decoded form.
+fn decoded<B, O>(value: DecodeDifferent<B, O>) -> Result<O> {
+	match value {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err(Error::UnsupportedMetadata),
+	}
+}
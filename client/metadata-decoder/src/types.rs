@@ -0,0 +1,620 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of SCALE encoded values given the name of their type.
+//!
+//! The metadata only describes types by the name they have in the runtime source,
+//! e.g. `T::AccountId` or `Vec<(AccountId, Balance)>`. Generic containers and
+//! primitives are understood natively, every other name is resolved through the
+//! [`TypeRegistry`].
+
+use std::collections::HashMap;
+use codec::{Compact, Decode};
+use serde_json::{Map, Value};
+use crate::{CallModule, Error, Result};
+
+/// Maximal nesting of type definitions, protecting against cyclic aliases.
+const MAX_DEPTH: usize = 32;
+
+/// Definition of a named type.
+#[derive(Clone)]
+pub enum TypeDef {
+	/// Another type expression, e.g. `Balance` is `u128`.
+	Alias(String),
+	/// A struct with named fields, rendered as a JSON object.
+	Struct(Vec<(String, String)>),
+	/// An enum with optional variant payloads. Unit variants are rendered as their
+	/// name, other variants as an object mapping the name to the payload.
+	Enum(Vec<(String, Option<String>)>),
+	/// A type with a custom decoding function.
+	Custom(fn(&TypeRegistry, &mut &[u8]) -> Result<Value>),
+	/// The outer call enum of the runtime, described by the metadata. Rendered as an
+	/// object with the module and function name and the decoded arguments.
+	Call,
+}
+
+/// Definitions of the named types used by a runtime.
+///
+/// The default registry knows the types used by the FRAME modules.
+#[derive(Clone)]
+pub struct TypeRegistry {
+	types: HashMap<String, TypeDef>,
+	calls: Vec<CallModule>,
+}
+
+impl TypeRegistry {
+	/// Create a registry without any named types.
+	pub fn empty() -> Self {
+		TypeRegistry { types: HashMap::new(), calls: Vec::new() }
+	}
+
+	/// Register or override the definition of a named type.
+	pub fn register(&mut self, name: impl Into<String>, def: TypeDef) {
+		self.types.insert(name.into(), def);
+	}
+
+	/// Register `name` as another name of the type expression `ty`.
+	pub fn alias(&mut self, name: impl Into<String>, ty: impl Into<String>) {
+		self.register(name, TypeDef::Alias(ty.into()));
+	}
+
+	/// Set the modules with calls of the runtime, used to decode `TypeDef::Call` types.
+	pub(crate) fn set_calls(&mut self, calls: Vec<CallModule>) {
+		self.calls = calls;
+	}
+
+	/// Decode a value of type `ty` from `input`, advancing it past the value.
+	pub fn decode(&self, ty: &str, input: &mut &[u8]) -> Result<Value> {
+		let ty = parse(ty)?;
+		self.decode_ty(&ty, input, 0)
+	}
+
+	fn decode_ty(&self, ty: &Ty, input: &mut &[u8], depth: usize) -> Result<Value> {
+		if depth > MAX_DEPTH {
+			return Err(Error::TooDeep);
+		}
+
+		match ty {
+			Ty::Tuple(fields) if fields.is_empty() => Ok(Value::Null),
+			Ty::Tuple(fields) => fields.iter()
+				.map(|field| self.decode_ty(field, input, depth + 1))
+				.collect::<Result<Vec<_>>>()
+				.map(Value::Array),
+			Ty::Array(item, len) => self.decode_seq(item, *len, input, depth),
+			Ty::Named(name, generics) => match (name.as_str(), generics.as_slice()) {
+				("Vec", [item]) | ("VecDeque", [item]) | ("BTreeSet", [item]) | ("BoundedVec", [item, _]) => {
+					let len = decode_len(input)?;
+					self.decode_seq(item, len, input, depth)
+				},
+				("BTreeMap", [key, value]) | ("BoundedBTreeMap", [key, value, _]) => {
+					let len = decode_len(input)?;
+					(0..len)
+						.map(|_| Ok(Value::Array(vec![
+							self.decode_ty(key, input, depth + 1)?,
+							self.decode_ty(value, input, depth + 1)?,
+						])))
+						.collect::<Result<Vec<_>>>()
+						.map(Value::Array)
+				},
+				("Option", [inner]) => match u8::decode(input)? {
+					0 => Ok(Value::Null),
+					1 => self.decode_ty(inner, input, depth + 1),
+					_ => Err(Error::InvalidValue("Option")),
+				},
+				("Result", [ok, err]) => {
+					let (variant, ty) = match u8::decode(input)? {
+						0 => ("Ok", ok),
+						1 => ("Err", err),
+						_ => return Err(Error::InvalidValue("Result")),
+					};
+					Ok(variant_value(variant, self.decode_ty(ty, input, depth + 1)?))
+				},
+				("Compact", [_]) => Ok(u128_value(Compact::<u128>::decode(input)?.0)),
+				("Box", [inner]) | ("Rc", [inner]) | ("Arc", [inner]) => self.decode_ty(inner, input, depth + 1),
+				("PhantomData", _) => Ok(Value::Null),
+				(name, _) => match decode_primitive(name, input) {
+					Some(value) => value,
+					None => self.decode_named(name, input, depth),
+				},
+			},
+		}
+	}
+
+	fn decode_seq(&self, item: &Ty, len: usize, input: &mut &[u8], depth: usize) -> Result<Value> {
+		if item.is_u8() {
+			return take(input, len).map(hex_value);
+		}
+		(0..len)
+			.map(|_| self.decode_ty(item, input, depth + 1))
+			.collect::<Result<Vec<_>>>()
+			.map(Value::Array)
+	}
+
+	fn decode_named(&self, name: &str, input: &mut &[u8], depth: usize) -> Result<Value> {
+		match self.types.get(name) {
+			Some(TypeDef::Alias(ty)) => self.decode_ty(&parse(ty)?, input, depth + 1),
+			Some(TypeDef::Struct(fields)) => {
+				let mut object = Map::new();
+				for (field, ty) in fields {
+					object.insert(field.clone(), self.decode_ty(&parse(ty)?, input, depth + 1)?);
+				}
+				Ok(Value::Object(object))
+			},
+			Some(TypeDef::Enum(variants)) => {
+				let index = u8::decode(input)? as usize;
+				match variants.get(index) {
+					Some((variant, None)) => Ok(Value::String(variant.clone())),
+					Some((variant, Some(ty))) =>
+						Ok(variant_value(variant, self.decode_ty(&parse(ty)?, input, depth + 1)?)),
+					None => Err(Error::InvalidValue("enum variant")),
+				}
+			},
+			Some(TypeDef::Custom(decode)) => decode(self, input),
+			Some(TypeDef::Call) => self.decode_call(input, depth),
+			None => Err(Error::UnknownType(name.into())),
+		}
+	}
+
+	fn decode_call(&self, input: &mut &[u8], depth: usize) -> Result<Value> {
+		let (module_index, call_index) = <(u8, u8)>::decode(input)?;
+		let unknown = || Error::UnknownCall { module: module_index, call: call_index };
+		let module = self.calls.get(module_index as usize).ok_or_else(unknown)?;
+		let call = module.calls.get(call_index as usize).ok_or_else(unknown)?;
+
+		let args = call.arguments.iter()
+			.map(|(name, ty)| {
+				let mut arg = Map::new();
+				arg.insert("name".into(), Value::String(name.clone()));
+				arg.insert("type".into(), Value::String(ty.clone()));
+				arg.insert("value".into(), self.decode_ty(&parse(ty)?, input, depth + 1)?);
+				Ok(Value::Object(arg))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let mut object = Map::new();
+		object.insert("module".into(), Value::String(module.name.clone()));
+		object.insert("function".into(), Value::String(call.name.clone()));
+		object.insert("args".into(), Value::Array(args));
+		Ok(Value::Object(object))
+	}
+}
+
+impl Default for TypeRegistry {
+	fn default() -> Self {
+		let mut registry = TypeRegistry::empty();
+		for (name, ty) in DEFAULT_ALIASES {
+			registry.alias(*name, *ty);
+		}
+
+		registry.register("DispatchInfo", TypeDef::Struct(vec![
+			("weight".into(), "Weight".into()),
+			("class".into(), "DispatchClass".into()),
+			("pays_fee".into(), "bool".into()),
+		]));
+		registry.register("DispatchClass", unit_enum(&["Normal", "Operational"]));
+		registry.register("DispatchError", TypeDef::Enum(vec![
+			("Other".into(), None),
+			("CannotLookup".into(), None),
+			("BadOrigin".into(), None),
+			("Module".into(), Some("DispatchErrorModule".into())),
+		]));
+		registry.register("DispatchErrorModule", TypeDef::Struct(vec![
+			("index".into(), "u8".into()),
+			("error".into(), "u8".into()),
+		]));
+		registry.register("VoteThreshold", unit_enum(&["SuperMajorityApprove", "SuperMajorityAgainst", "SimpleMajority"]));
+		registry.register("Judgement", TypeDef::Enum(vec![
+			("Unknown".into(), None),
+			("FeePaid".into(), Some("Balance".into())),
+			("Reasonable".into(), None),
+			("KnownGood".into(), None),
+			("OutOfDate".into(), None),
+			("LowQuality".into(), None),
+			("Erroneous".into(), None),
+		]));
+		registry.register("Timepoint", TypeDef::Struct(vec![
+			("height".into(), "BlockNumber".into()),
+			("index".into(), "u32".into()),
+		]));
+		registry.register("Exposure", TypeDef::Struct(vec![
+			("total".into(), "Compact<Balance>".into()),
+			("own".into(), "Compact<Balance>".into()),
+			("others".into(), "Vec<IndividualExposure>".into()),
+		]));
+		registry.register("IndividualExposure", TypeDef::Struct(vec![
+			("who".into(), "AccountId".into()),
+			("value".into(), "Compact<Balance>".into()),
+		]));
+		registry.register("Address", TypeDef::Custom(decode_address));
+		registry.register("Call", TypeDef::Call);
+		registry.register("Signature", TypeDef::Enum(vec![
+			("Ed25519".into(), Some("H512".into())),
+			("Sr25519".into(), Some("H512".into())),
+			("Ecdsa".into(), Some("[u8; 65]".into())),
+		]));
+
+		// The payloads of the signed extensions, named by their identifier.
+		for extension in &["CheckVersion", "CheckGenesis", "CheckWeight", "CheckBlockGasLimit"] {
+			registry.alias(*extension, "()");
+		}
+		registry.register("CheckEra", TypeDef::Custom(decode_era));
+		registry.alias("CheckNonce", "Compact<Index>");
+		registry.alias("ChargeTransactionPayment", "Compact<Balance>");
+		registry.register("ChargeAssetTxPayment", TypeDef::Struct(vec![
+			("tip".into(), "Compact<Balance>".into()),
+			("asset_id".into(), "Option<AssetId>".into()),
+		]));
+
+		registry
+	}
+}
+
+/// Named types of the FRAME modules which are other names of a type expression.
+const DEFAULT_ALIASES: &[(&str, &str)] = &[
+	("AccountId", "[u8; 32]"),
+	("AccountIndex", "u32"),
+	("AssetBalance", "Balance"),
+	("AssetId", "u32"),
+	("AuthorityId", "[u8; 32]"),
+	("AuthorityWeight", "u64"),
+	("AuthorityList", "Vec<(AuthorityId, AuthorityWeight)>"),
+	("Balance", "u128"),
+	("BalanceOf", "Balance"),
+	("BlockNumber", "u32"),
+	("BountyIndex", "u32"),
+	("CallHash", "Hash"),
+	("DispatchResult", "Result<(), DispatchError>"),
+	("Hash", "H256"),
+	("IdentificationTuple", "(AccountId, Exposure)"),
+	("Key", "Vec<u8>"),
+	("Index", "u32"),
+	("Kind", "[u8; 16]"),
+	("LookupSource", "Address"),
+	("MemberCount", "u32"),
+	("Moment", "u64"),
+	("OpaqueTimeSlot", "Vec<u8>"),
+	("Perbill", "u32"),
+	("Percent", "u8"),
+	("Permill", "u32"),
+	("PropIndex", "u32"),
+	("Proposal", "Call"),
+	("ProposalIndex", "u32"),
+	("ReferendumIndex", "u32"),
+	("RegistrarIndex", "u32"),
+	("SessionIndex", "u32"),
+	("Source", "Address"),
+	("Weight", "u32"),
+];
+
+fn unit_enum(variants: &[&str]) -> TypeDef {
+	TypeDef::Enum(variants.iter().map(|variant| (variant.to_string(), None)).collect())
+}
+
+/// Decode the address type of `pallet-indices`, an account id or a compactly encoded index.
+fn decode_address(registry: &TypeRegistry, input: &mut &[u8]) -> Result<Value> {
+	let index = match u8::decode(input)? {
+		x @ 0x00..=0xef => x as u64,
+		0xfc => u16::decode(input)? as u64,
+		0xfd => u32::decode(input)? as u64,
+		0xfe => u64::decode(input)?,
+		0xff => return Ok(variant_value("Id", registry.decode("AccountId", input)?)),
+		_ => return Err(Error::InvalidValue("Address")),
+	};
+	Ok(variant_value("Index", index.into()))
+}
+
+/// Decode the `Era` of a signed extrinsic, mirrors the encoding of `sp_runtime::generic::Era`.
+fn decode_era(_: &TypeRegistry, input: &mut &[u8]) -> Result<Value> {
+	let first = u8::decode(input)?;
+	if first == 0 {
+		return Ok(Value::String("Immortal".into()));
+	}
+
+	let encoded = first as u64 + ((u8::decode(input)? as u64) << 8);
+	let period = 2u64 << (encoded % (1 << 4));
+	let quantize_factor = (period >> 12).max(1);
+	let phase = (encoded >> 4) * quantize_factor;
+	if period < 4 || phase >= period {
+		return Err(Error::InvalidValue("Era"));
+	}
+
+	let mut mortal = Map::new();
+	mortal.insert("period".into(), period.into());
+	mortal.insert("phase".into(), phase.into());
+	Ok(variant_value("Mortal", Value::Object(mortal)))
+}
+
+fn decode_primitive(name: &str, input: &mut &[u8]) -> Option<Result<Value>> {
+	fn int<T: Decode + Into<Value>>(input: &mut &[u8]) -> Result<Value> {
+		Ok(T::decode(input)?.into())
+	}
+
+	Some(match name {
+		"bool" => int::<bool>(input),
+		"u8" => int::<u8>(input),
+		"u16" => int::<u16>(input),
+		"u32" => int::<u32>(input),
+		"u64" => int::<u64>(input),
+		"u128" => u128::decode(input).map(u128_value).map_err(Into::into),
+		"i8" => int::<i8>(input),
+		"i16" => int::<i16>(input),
+		"i32" => int::<i32>(input),
+		"i64" => int::<i64>(input),
+		"i128" => i128::decode(input).map(|value| match value {
+			value if value >= i64::min_value() as i128 && value <= i64::max_value() as i128 =>
+				(value as i64).into(),
+			value => Value::String(value.to_string()),
+		}).map_err(Into::into),
+		"String" | "Text" => String::decode(input).map(Value::String).map_err(Into::into),
+		"Bytes" => decode_len(input).and_then(|len| take(input, len)).map(hex_value),
+		"H160" => take(input, 20).map(hex_value),
+		"H256" => take(input, 32).map(hex_value),
+		"H512" => take(input, 64).map(hex_value),
+		_ => return None,
+	})
+}
+
+/// Render numbers not representable as JSON numbers as hex strings, like `NumberOrHex`.
+fn u128_value(value: u128) -> Value {
+	if value <= u64::max_value() as u128 {
+		(value as u64).into()
+	} else {
+		Value::String(format!("0x{:x}", value))
+	}
+}
+
+fn hex_value(bytes: &[u8]) -> Value {
+	let mut hex = String::with_capacity(2 + bytes.len() * 2);
+	hex.push_str("0x");
+	for byte in bytes {
+		hex.push_str(&format!("{:02x}", byte));
+	}
+	Value::String(hex)
+}
+
+fn variant_value(variant: &str, value: Value) -> Value {
+	let mut object = Map::new();
+	object.insert(variant.into(), value);
+	Value::Object(object)
+}
+
+fn decode_len(input: &mut &[u8]) -> Result<usize> {
+	let len = Compact::<u32>::decode(input)?.0 as usize;
+	// Every item takes at least one byte, this rejects garbage lengths early.
+	if len > input.len() {
+		return Err(Error::InvalidValue("sequence length"));
+	}
+	Ok(len)
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+	if input.len() < len {
+		return Err(Error::InvalidValue("not enough data"));
+	}
+	let (bytes, rest) = input.split_at(len);
+	*input = rest;
+	Ok(bytes)
+}
+
+/// A parsed type expression.
+#[derive(Debug, PartialEq)]
+enum Ty {
+	/// A type name with its generic parameters, paths are reduced to their last segment.
+	Named(String, Vec<Ty>),
+	Tuple(Vec<Ty>),
+	Array(Box<Ty>, usize),
+}
+
+impl Ty {
+	fn is_u8(&self) -> bool {
+		match self {
+			Ty::Named(name, _) => name == "u8",
+			_ => false,
+		}
+	}
+}
+
+/// Parse a type expression as found in the metadata.
+fn parse(ty: &str) -> Result<Ty> {
+	let tokens = ty.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+	let mut parser = Parser { input: &tokens, original: ty };
+	let ty = parser.parse_ty()?;
+	if !parser.input.is_empty() {
+		return Err(parser.error());
+	}
+	Ok(ty)
+}
+
+struct Parser<'a> {
+	input: &'a str,
+	original: &'a str,
+}
+
+impl<'a> Parser<'a> {
+	fn error(&self) -> Error {
+		Error::InvalidTypeName(self.original.into())
+	}
+
+	fn eat(&mut self, token: &str) -> bool {
+		if self.input.starts_with(token) {
+			self.input = &self.input[token.len()..];
+			true
+		} else {
+			false
+		}
+	}
+
+	fn expect(&mut self, token: &str) -> Result<()> {
+		if self.eat(token) { Ok(()) } else { Err(self.error()) }
+	}
+
+	fn parse_ty(&mut self) -> Result<Ty> {
+		if self.eat("(") {
+			let fields = self.parse_list(")")?;
+			return Ok(Ty::Tuple(fields));
+		}
+		if self.eat("[") {
+			let item = self.parse_ty()?;
+			self.expect(";")?;
+			let end = self.input.find(']').ok_or_else(|| self.error())?;
+			let len = self.input[..end].parse().map_err(|_| self.error())?;
+			self.input = &self.input[end + 1..];
+			return Ok(Ty::Array(Box::new(item), len));
+		}
+		if self.eat("<") {
+			// A qualified path like `<T as Trait<I>>::Balance`, only the last segment matters.
+			let mut open = 1;
+			while open > 0 {
+				let c = self.input.chars().next().ok_or_else(|| self.error())?;
+				match c {
+					'<' => open += 1,
+					'>' => open -= 1,
+					_ => (),
+				}
+				self.input = &self.input[c.len_utf8()..];
+			}
+			self.expect("::")?;
+		}
+
+		loop {
+			let end = self.input
+				.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+				.unwrap_or_else(|| self.input.len());
+			if end == 0 {
+				return Err(self.error());
+			}
+			let name = self.input[..end].to_string();
+			self.input = &self.input[end..];
+
+			let generics = if self.eat("<") { self.parse_list(">")? } else { Vec::new() };
+			if !self.eat("::") {
+				return Ok(Ty::Named(name, generics));
+			}
+		}
+	}
+
+	fn parse_list(&mut self, close: &str) -> Result<Vec<Ty>> {
+		let mut items = Vec::new();
+		while !self.eat(close) {
+			items.push(self.parse_ty()?);
+			if !self.eat(",") {
+				self.expect(close)?;
+				break;
+			}
+		}
+		Ok(items)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use serde_json::json;
+
+	fn named(name: &str, generics: Vec<Ty>) -> Ty {
+		Ty::Named(name.into(), generics)
+	}
+
+	#[test]
+	fn should_parse_type_expressions() {
+		assert_eq!(parse("T::AccountId").unwrap(), named("AccountId", vec![]));
+		assert_eq!(parse("<T as Trait<I>>::Balance").unwrap(), named("Balance", vec![]));
+		assert_eq!(
+			parse("Vec<(T::AccountId, BalanceOf<T>)>").unwrap(),
+			named("Vec", vec![Ty::Tuple(vec![
+				named("AccountId", vec![]),
+				named("BalanceOf", vec![named("T", vec![])]),
+			])]),
+		);
+		assert_eq!(parse("[u8; 32]").unwrap(), Ty::Array(Box::new(named("u8", vec![])), 32));
+		assert_eq!(parse("()").unwrap(), Ty::Tuple(vec![]));
+		assert!(parse("Vec<u8").is_err());
+		assert!(parse("Vec<u8>>").is_err());
+	}
+
+	#[test]
+	fn should_decode_values() {
+		let registry = TypeRegistry::default();
+		let decode = |ty: &str, encoded: Vec<u8>| {
+			let mut input = &encoded[..];
+			let value = registry.decode(ty, &mut input).unwrap();
+			assert!(input.is_empty(), "{} left {} bytes", ty, input.len());
+			value
+		};
+
+		assert_eq!(decode("T::Balance", 5u128.encode()), json!(5));
+		assert_eq!(decode("u128", u128::max_value().encode()), json!("0xffffffffffffffffffffffffffffffff"));
+		assert_eq!(decode("Compact<Balance>", Compact(300u128).encode()), json!(300));
+		assert_eq!(decode("Vec<u8>", vec![1u8, 2].encode()), json!("0x0102"));
+		assert_eq!(
+			decode("Vec<(AccountId, Balance)>", vec![([1u8; 32], 7u128)].encode()),
+			json!([[format!("0x{}", "01".repeat(32)), 7]]),
+		);
+		assert_eq!(decode("Option<u32>", Some(1u32).encode()), json!(1));
+		assert_eq!(decode("Option<bool>", Some(false).encode()), json!(false));
+		assert_eq!(decode("Option<u32>", None::<u32>.encode()), Value::Null);
+		assert_eq!(decode("bool", true.encode()), json!(true));
+		assert_eq!(decode("Vec<u8>", b"abc".to_vec().encode()), json!("0x616263"));
+		assert_eq!(decode("String", "abc".to_string().encode()), json!("abc"));
+		assert_eq!(
+			decode("DispatchInfo", (10u32, 1u8, true).encode()),
+			json!({ "weight": 10, "class": "Operational", "pays_fee": true }),
+		);
+		assert_eq!(
+			decode("DispatchError", (3u8, 2u8, 1u8).encode()),
+			json!({ "Module": { "index": 2, "error": 1 } }),
+		);
+		assert_eq!(decode("DispatchResult", vec![0u8]), json!({ "Ok": null }));
+		assert_eq!(
+			decode("Timepoint<BlockNumber>", (5u32, 2u32).encode()),
+			json!({ "height": 5, "index": 2 }),
+		);
+		assert_eq!(decode("Address", vec![0xfc, 0x00, 0x01]), json!({ "Index": 256 }));
+		assert_eq!(decode("CheckEra", vec![0]), json!("Immortal"));
+		// `Era::mortal(64, 42)`
+		assert_eq!(decode("CheckEra", vec![0xa5, 0x02]), json!({ "Mortal": { "period": 64, "phase": 42 } }));
+		assert_eq!(
+			decode("ChargeAssetTxPayment", (Compact(5u128), Some(1u32)).encode()),
+			json!({ "tip": 5, "asset_id": 1 }),
+		);
+	}
+
+	#[test]
+	fn should_fail_on_unknown_or_invalid_values() {
+		let registry = TypeRegistry::default();
+
+		assert!(matches_unknown(registry.decode("Unknown", &mut &[0u8][..]), "Unknown"));
+		assert!(registry.decode("u32", &mut &[0u8][..]).is_err());
+		assert!(registry.decode("Vec<u32>", &mut &Compact(1000u32).encode()[..]).is_err());
+		assert!(registry.decode("CheckEra", &mut &[0x10, 0x00][..]).is_err());
+		match registry.decode("Call", &mut &[0u8, 0u8][..]) {
+			Err(Error::UnknownCall { module: 0, call: 0 }) => (),
+			other => panic!("Unexpected result {:?}", other),
+		}
+
+		let mut registry = TypeRegistry::empty();
+		registry.alias("Loop", "Vec<Loop>");
+		assert!(registry.decode("Loop", &mut &[4u8; 64][..]).is_err());
+	}
+
+	fn matches_unknown(result: Result<Value>, name: &str) -> bool {
+		match result {
+			Err(Error::UnknownType(ref unknown)) => unknown == name,
+			_ => false,
+		}
+	}
+}
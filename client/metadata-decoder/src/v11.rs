@@ -0,0 +1,62 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Version 11 of the runtime metadata, still returned by runtimes which predate the
+//! storage bounds of version 12.
+//!
+//! `frame-metadata` can only decode the latest version, so the parts of version 11 which
+//! differ from it are redefined here.
+
+use codec::{Decode, Encode};
+use frame_metadata::{
+	ErrorMetadata, EventMetadata, ExtrinsicMetadata, FunctionMetadata, ModuleConstantMetadata,
+	StorageEntryModifier, StorageEntryType,
+};
+
+/// The metadata of a runtime.
+#[derive(Encode, Decode)]
+pub struct RuntimeMetadataV11 {
+	pub modules: Vec<ModuleMetadata>,
+	pub extrinsic: ExtrinsicMetadata,
+}
+
+/// All metadata about a runtime module.
+#[derive(Encode, Decode)]
+pub struct ModuleMetadata {
+	pub name: String,
+	pub storage: Option<StorageMetadata>,
+	pub calls: Option<Vec<FunctionMetadata>>,
+	pub event: Option<Vec<EventMetadata>>,
+	pub constants: Vec<ModuleConstantMetadata>,
+	pub errors: Vec<ErrorMetadata>,
+}
+
+/// All metadata of the storage of a module.
+#[derive(Encode, Decode)]
+pub struct StorageMetadata {
+	pub prefix: String,
+	pub entries: Vec<StorageEntryMetadata>,
+}
+
+/// All the metadata about one storage entry, without the bound of version 12.
+#[derive(Encode, Decode)]
+pub struct StorageEntryMetadata {
+	pub name: String,
+	pub modifier: StorageEntryModifier,
+	pub ty: StorageEntryType,
+	pub default: Vec<u8>,
+	pub documentation: Vec<String>,
+}
@@ -28,6 +28,7 @@ use crate::service::{ExHashT, TransactionPool};
 use bitflags::bitflags;
 use sp_consensus::{block_validation::BlockAnnounceValidator, import_queue::ImportQueue};
 use sp_runtime::traits::{Block as BlockT};
+use prometheus_endpoint::Registry;
 use libp2p::identity::{Keypair, ed25519};
 use libp2p::wasm_ext;
 use libp2p::{PeerId, Multiaddr, multiaddr};
@@ -87,6 +88,9 @@ pub struct Params<B: BlockT, S, H: ExHashT> {
 
 	/// Type to check incoming block announcements.
 	pub block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
+
+	/// Registry for recording Prometheus metrics to. `None` if metrics are disabled.
+	pub metrics_registry: Option<Registry>,
 }
 
 bitflags! {
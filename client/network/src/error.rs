@@ -30,6 +30,8 @@ pub enum Error {
 	Io(std::io::Error),
 	/// Client error
 	Client(sp_blockchain::Error),
+	/// Error registering the metrics.
+	Prometheus(prometheus_endpoint::PrometheusError),
 	/// The same bootnode (based on address) is registered with two different peer ids.
 	#[display(
 		fmt = "The same bootnode (`{}`) is registered with two different peer ids: `{}` and `{}`",
@@ -59,6 +61,7 @@ impl std::error::Error for Error {
 		match self {
 			Error::Io(ref err) => Some(err),
 			Error::Client(ref err) => Some(err),
+			Error::Prometheus(ref err) => Some(err),
 			Error::DuplicateBootnode { .. } => None,
 		}
 	}
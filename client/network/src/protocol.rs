@@ -52,8 +52,11 @@ use crate::chain::{Client, FinalityProofProvider};
 use sc_client_api::{FetchChecker, ChangesProof, StorageProof};
 use crate::error;
 use util::LruHashSet;
+use metrics::Metrics;
+use prometheus_endpoint::Registry;
 
 mod legacy_proto;
+mod metrics;
 mod util;
 
 pub mod message;
@@ -359,6 +362,7 @@ impl<'a, B: BlockT + 'a, H: ExHashT + 'a> Context<B> for ProtocolContext<'a, B,
 		send_message::<B> (
 			self.behaviour,
 			&mut self.context_data.stats,
+			self.context_data.metrics.as_ref(),
 			&who,
 			GenericMessage::ChainSpecific(message)
 		)
@@ -370,6 +374,7 @@ struct ContextData<B: BlockT, H: ExHashT> {
 	// All connected peers
 	peers: HashMap<PeerId, Peer<B, H>>,
 	stats: HashMap<&'static str, PacketStats>,
+	metrics: Option<Metrics>,
 	pub chain: Arc<dyn Client<B>>,
 }
 
@@ -403,7 +408,8 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 		finality_proof_request_builder: Option<BoxFinalityProofRequestBuilder<B>>,
 		protocol_id: ProtocolId,
 		peerset_config: sc_peerset::PeersetConfig,
-		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>
+		block_announce_validator: Box<dyn BlockAnnounceValidator<B> + Send>,
+		metrics_registry: Option<&Registry>,
 	) -> error::Result<(Protocol<B, S, H>, sc_peerset::PeersetHandle)> {
 		let info = chain.info();
 		let sync = ChainSync::new(
@@ -435,6 +441,10 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 			context_data: ContextData {
 				peers: HashMap::new(),
 				stats: HashMap::new(),
+				metrics: match metrics_registry {
+					Some(registry) => Some(Metrics::register(registry)?),
+					None => None,
+				},
 				chain,
 			},
 			light_dispatch: LightDispatch::new(checker),
@@ -572,7 +582,15 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 		who: PeerId,
 		data: BytesMut,
 	) -> CustomMessageOutcome<B> {
+		let peer = who.clone();
+		sc_tracing::logging::with_log_context("peer", peer, || self.handle_custom_message(who, data))
+	}
 
+	fn handle_custom_message(
+		&mut self,
+		who: PeerId,
+		data: BytesMut,
+	) -> CustomMessageOutcome<B> {
 		let message = match <Message<B> as Decode>::decode(&mut &data[..]) {
 			Ok(message) => message,
 			Err(err) => {
@@ -585,6 +603,9 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 		let mut stats = self.context_data.stats.entry(message.id()).or_default();
 		stats.bytes_in += data.len() as u64;
 		stats.count_in += 1;
+		if let Some(metrics) = &self.context_data.metrics {
+			metrics.report_message(false, &message, data.len());
+		}
 
 		match message {
 			GenericMessage::Status(s) => return self.on_status_message(who, s),
@@ -675,6 +696,7 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 		send_request::<B, H>(
 			&mut self.behaviour,
 			&mut self.context_data.stats,
+			self.context_data.metrics.as_ref(),
 			&mut self.context_data.peers,
 			who,
 			message,
@@ -685,6 +707,7 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 		send_message::<B>(
 			&mut self.behaviour,
 			&mut self.context_data.stats,
+			self.context_data.metrics.as_ref(),
 			who,
 			message,
 		);
@@ -883,6 +906,9 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 	/// > **Note**: This method normally doesn't have to be called except for testing purposes.
 	pub fn tick(&mut self) {
 		self.maintain_peers();
+		if let Some(metrics) = &self.context_data.metrics {
+			metrics.report_sync_peers(self.sync.peer_states().map(|state| state.name()));
+		}
 		self.light_dispatch.maintain_peers(LightDispatchIn {
 			behaviour: &mut self.behaviour,
 			peerset: self.peerset_handle.clone(),
@@ -1179,6 +1205,7 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 				send_message::<B> (
 					&mut self.behaviour,
 					&mut self.context_data.stats,
+					self.context_data.metrics.as_ref(),
 					&who,
 					GenericMessage::Transactions(to_send)
 				)
@@ -1255,6 +1282,7 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 				send_message::<B> (
 					&mut self.behaviour,
 					&mut self.context_data.stats,
+					self.context_data.metrics.as_ref(),
 					&who,
 					message,
 				)
@@ -1441,6 +1469,7 @@ impl<B: BlockT, S: NetworkSpecialization<B>, H: ExHashT> Protocol<B, S, H> {
 					send_request(
 						&mut self.behaviour,
 						&mut self.context_data.stats,
+						self.context_data.metrics.as_ref(),
 						&mut self.context_data.peers,
 						&id,
 						msg
@@ -1821,6 +1850,7 @@ pub enum CustomMessageOutcome<B: BlockT> {
 fn send_request<B: BlockT, H: ExHashT>(
 	behaviour: &mut LegacyProto<Substream<StreamMuxerBox>>,
 	stats: &mut HashMap<&'static str, PacketStats>,
+	metrics: Option<&Metrics>,
 	peers: &mut HashMap<PeerId, Peer<B, H>>,
 	who: &PeerId,
 	mut message: Message<B>,
@@ -1836,12 +1866,13 @@ fn send_request<B: BlockT, H: ExHashT>(
 			peer.block_request = Some((time::Instant::now(), r.clone()));
 		}
 	}
-	send_message::<B>(behaviour, stats, who, message)
+	send_message::<B>(behaviour, stats, metrics, who, message)
 }
 
 fn send_message<B: BlockT>(
 	behaviour: &mut LegacyProto<Substream<StreamMuxerBox>>,
 	stats: &mut HashMap<&'static str, PacketStats>,
+	metrics: Option<&Metrics>,
 	who: &PeerId,
 	message: Message<B>,
 ) {
@@ -1849,6 +1880,9 @@ fn send_message<B: BlockT>(
 	let mut stats = stats.entry(message.id()).or_default();
 	stats.bytes_out += encoded.len() as u64;
 	stats.count_out += 1;
+	if let Some(metrics) = metrics {
+		metrics.report_message(true, &message, encoded.len());
+	}
 	behaviour.send_packet(who, encoded);
 }
 
@@ -1903,6 +1937,7 @@ Protocol<B, S, H> {
 			send_request(
 				&mut self.behaviour,
 				&mut self.context_data.stats,
+				self.context_data.metrics.as_ref(),
 				&mut self.context_data.peers,
 				&id,
 				GenericMessage::BlockRequest(r)
@@ -1912,6 +1947,7 @@ Protocol<B, S, H> {
 			send_request(
 				&mut self.behaviour,
 				&mut self.context_data.stats,
+				self.context_data.metrics.as_ref(),
 				&mut self.context_data.peers,
 				&id,
 				GenericMessage::BlockRequest(r)
@@ -1921,6 +1957,7 @@ Protocol<B, S, H> {
 			send_request(
 				&mut self.behaviour,
 				&mut self.context_data.stats,
+				self.context_data.metrics.as_ref(),
 				&mut self.context_data.peers,
 				&id,
 				GenericMessage::FinalityProofRequest(r))
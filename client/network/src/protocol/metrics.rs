@@ -0,0 +1,95 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the protocol.
+
+use prometheus_endpoint::{register, CounterVec, GaugeVec, Opts, PrometheusError, Registry};
+use sp_runtime::ConsensusEngineId;
+use super::message::generic::Message as GenericMessage;
+use super::sync::PEER_SYNC_STATES;
+
+/// Metrics of the network traffic and of the sync peers.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	/// Bytes of the messages sent and received, by direction and protocol.
+	network_bytes: CounterVec,
+	/// Number of sync peers, by state.
+	sync_peers: GaugeVec,
+}
+
+impl Metrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Metrics {
+			network_bytes: register(CounterVec::new(
+				Opts::new(
+					"substrate_sub_libp2p_network_bytes_total",
+					"Bytes of the messages sent and received on the substrate protocol",
+				),
+				&["direction", "protocol"],
+			)?, registry)?,
+			sync_peers: register(GaugeVec::new(
+				Opts::new("substrate_sync_peers", "Number of peers we sync with, by sync state"),
+				&["state"],
+			)?, registry)?,
+		})
+	}
+
+	/// Account for a message of `len` bytes, sent if `outbound` and received otherwise.
+	///
+	/// Notifications are accounted to their protocol, all other messages to their kind.
+	pub(crate) fn report_message<Header, Hash, Number, Extrinsic>(
+		&self,
+		outbound: bool,
+		message: &GenericMessage<Header, Hash, Number, Extrinsic>,
+		len: usize,
+	) {
+		let direction = if outbound { "out" } else { "in" };
+		match message {
+			GenericMessage::Consensus(msg) => self.network_bytes
+				.with_label_values(&[direction, &engine_label(&msg.engine_id)])
+				.inc_by(len as u64),
+			GenericMessage::ConsensusBatch(msgs) if !msgs.is_empty() => {
+				// The batch is encoded as a whole, so its size is split evenly.
+				let share = (len / msgs.len()) as u64;
+				for msg in msgs {
+					self.network_bytes
+						.with_label_values(&[direction, &engine_label(&msg.engine_id)])
+						.inc_by(share);
+				}
+			},
+			_ => self.network_bytes
+				.with_label_values(&[direction, message.id()])
+				.inc_by(len as u64),
+		}
+	}
+
+	/// Set the number of sync peers in each state.
+	pub(crate) fn report_sync_peers<'a>(&self, states: impl Iterator<Item = &'a str>) {
+		let mut counts = PEER_SYNC_STATES.iter().map(|s| (*s, 0u64)).collect::<Vec<_>>();
+		for state in states {
+			if let Some(count) = counts.iter_mut().find(|(s, _)| *s == state) {
+				count.1 += 1;
+			}
+		}
+		for (state, count) in counts {
+			self.sync_peers.with_label_values(&[state]).set(count as f64);
+		}
+	}
+}
+
+fn engine_label(engine_id: &ConsensusEngineId) -> String {
+	String::from_utf8_lossy(&engine_id[..]).into_owned()
+}
@@ -203,8 +203,30 @@ impl<B: BlockT> PeerSyncState<B> {
 			false
 		}
 	}
+
+	/// The name of the state, one of `PEER_SYNC_STATES`.
+	pub fn name(&self) -> &'static str {
+		match self {
+			PeerSyncState::Available => "available",
+			PeerSyncState::AncestorSearch(..) => "ancestor_search",
+			PeerSyncState::DownloadingNew(_) => "downloading_new",
+			PeerSyncState::DownloadingStale(_) => "downloading_stale",
+			PeerSyncState::DownloadingJustification(_) => "downloading_justification",
+			PeerSyncState::DownloadingFinalityProof(_) => "downloading_finality_proof",
+		}
+	}
 }
 
+/// The names of all states a sync peer can be in.
+pub const PEER_SYNC_STATES: &[&str] = &[
+	"available",
+	"ancestor_search",
+	"downloading_new",
+	"downloading_stale",
+	"downloading_justification",
+	"downloading_finality_proof",
+];
+
 /// Reported sync state.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum SyncState {
@@ -328,6 +350,11 @@ impl<B: BlockT> ChainSync<B> {
 		self.peers.get(who).map(|p| PeerInfo { best_hash: p.best_hash, best_number: p.best_number })
 	}
 
+	/// Returns the state of each peer we sync with.
+	pub fn peer_states(&self) -> impl Iterator<Item = &PeerSyncState<B>> {
+		self.peers.values().map(|p| &p.state)
+	}
+
 	/// Returns the current sync status.
 	pub fn status(&self) -> Status<B> {
 		let best_seen = self.peers.values().max_by_key(|p| p.best_number).map(|p| p.best_number);
@@ -18,12 +18,14 @@
 
 pub mod error;
 pub mod hash;
+pub mod status;
 
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use sp_core::Bytes;
 use sp_transaction_pool::TransactionStatus;
 use self::error::{FutureResult, Result};
+use self::status::{ExtrinsicStatus, TransactionStatusInfo};
 
 pub use self::gen_client::Client as AuthorClient;
 
@@ -74,6 +76,14 @@ pub trait AuthorApi<Hash, BlockHash> {
 		bytes_or_hash: Vec<hash::ExtrinsicOrHash<Hash>>
 	) -> Result<Vec<Hash>>;
 
+	/// Returns the last known status of the extrinsic with given hash.
+	///
+	/// Included extrinsics are looked up in the transaction index, if the node keeps one,
+	/// other extrinsics are only known while they are in the pool or shortly after they
+	/// left it.
+	#[rpc(name = "author_transactionStatus")]
+	fn transaction_status(&self, hash: Hash) -> Result<Option<TransactionStatusInfo<Hash, BlockHash>>>;
+
 	/// Submit an extrinsic to watch.
 	///
 	/// See [`TransactionStatus`](sp_transaction_pool::TransactionStatus) for details on transaction
@@ -89,6 +99,33 @@ pub trait AuthorApi<Hash, BlockHash> {
 		bytes: Bytes
 	);
 
+	/// Watch an extrinsic that has already been submitted.
+	///
+	/// Starts with the current status of the extrinsic, followed by its status updates.
+	/// See [`ExtrinsicStatus`](self::status::ExtrinsicStatus) for how they differ from
+	/// the ones of `author_submitAndWatchExtrinsic`.
+	#[pubsub(
+		subscription = "author_extrinsicStatus",
+		subscribe,
+		name = "author_watchExtrinsicByHash"
+	)]
+	fn watch_extrinsic_by_hash(&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ExtrinsicStatus<Hash, BlockHash>>,
+		hash: Hash
+	);
+
+	/// Unsubscribe from watching an extrinsic by hash.
+	#[pubsub(
+		subscription = "author_extrinsicStatus",
+		unsubscribe,
+		name = "author_unwatchExtrinsicByHash"
+	)]
+	fn unwatch_extrinsic_by_hash(&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId
+	) -> Result<bool>;
+
 	/// Unsubscribe from extrinsic watching.
 	#[pubsub(
 		subscription = "author_extrinsicUpdate",
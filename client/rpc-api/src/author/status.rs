@@ -0,0 +1,119 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types of the transaction status query and of the watch by hash subscription.
+
+use serde::{Serialize, Deserialize};
+use sp_transaction_pool::TransactionStatus;
+
+/// Status of a transaction, as reported by `author_transactionStatus` and
+/// `author_watchExtrinsicByHash`.
+///
+/// Unlike the [`TransactionStatus`] of `author_submitAndWatchExtrinsic`, which reports
+/// the inclusion in a block as `finalized` (see #4438), `InBlock` is reported as `inBlock`
+/// and `finalized` is only reported once the including block is finalized.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtrinsicStatus<Hash, BlockHash> {
+	/// Transaction is part of the future queue.
+	Future,
+	/// Transaction is part of the ready queue.
+	Ready,
+	/// The transaction has been broadcast to the given peers.
+	Broadcast(Vec<String>),
+	/// Transaction has been included in block with given hash.
+	InBlock(BlockHash),
+	/// The block the transaction has been included in has been finalized.
+	Finalized(BlockHash),
+	/// Transaction has been replaced in the pool, by another transaction
+	/// that provides the same tags. (e.g. same (sender, nonce)).
+	Usurped(Hash),
+	/// Transaction has been dropped from the pool because of the limit.
+	Dropped,
+	/// Transaction is no longer valid in the current state.
+	Invalid,
+}
+
+impl<Hash, BlockHash> From<TransactionStatus<Hash, BlockHash>> for ExtrinsicStatus<Hash, BlockHash> {
+	fn from(status: TransactionStatus<Hash, BlockHash>) -> Self {
+		match status {
+			TransactionStatus::Future => ExtrinsicStatus::Future,
+			TransactionStatus::Ready => ExtrinsicStatus::Ready,
+			TransactionStatus::Broadcast(peers) => ExtrinsicStatus::Broadcast(peers),
+			TransactionStatus::InBlock(block) => ExtrinsicStatus::InBlock(block),
+			TransactionStatus::Finalized(block) => ExtrinsicStatus::Finalized(block),
+			TransactionStatus::Usurped(hash) => ExtrinsicStatus::Usurped(hash),
+			TransactionStatus::Dropped => ExtrinsicStatus::Dropped,
+			TransactionStatus::Invalid => ExtrinsicStatus::Invalid,
+		}
+	}
+}
+
+/// Last known status of a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusInfo<Hash, BlockHash> {
+	/// The status of the transaction.
+	pub status: ExtrinsicStatus<Hash, BlockHash>,
+	/// Position of the transaction in the canonical chain, if it has been included
+	/// and the node keeps a transaction index.
+	pub inclusion: Option<Inclusion<BlockHash>>,
+}
+
+/// Position of a transaction in the canonical chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Inclusion<BlockHash> {
+	/// Hash of the block including the transaction.
+	pub block_hash: BlockHash,
+	/// Index of the transaction among the extrinsics of the block.
+	pub index: u32,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn should_serialize_transaction_status_info() {
+		let info = TransactionStatusInfo::<u32, u32> {
+			status: TransactionStatus::Finalized(5).into(),
+			inclusion: Some(Inclusion { block_hash: 5, index: 2 }),
+		};
+		assert_eq!(
+			serde_json::to_value(&info).unwrap(),
+			json!({ "status": { "finalized": 5 }, "inclusion": { "blockHash": 5, "index": 2 } }),
+		);
+
+		let info = TransactionStatusInfo::<u32, u32> { status: ExtrinsicStatus::Ready, inclusion: None };
+		assert_eq!(
+			serde_json::to_value(&info).unwrap(),
+			json!({ "status": "ready", "inclusion": null }),
+		);
+	}
+
+	#[test]
+	fn should_only_report_finalized_blocks_as_finalized() {
+		let in_block = TransactionStatus::<u32, u32>::InBlock(5);
+		// the status of `author_submitAndWatchExtrinsic` is kept as it is, see #4438.
+		assert_eq!(serde_json::to_value(&in_block).unwrap(), json!({ "finalized": 5 }));
+		assert_eq!(
+			serde_json::to_value(&ExtrinsicStatus::from(in_block)).unwrap(),
+			json!({ "inBlock": 5 }),
+		);
+	}
+}
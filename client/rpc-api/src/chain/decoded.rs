@@ -0,0 +1,159 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types of blocks and events decoded using the runtime metadata.
+//!
+//! Decoded values are accompanied by their encoding, so that clients can verify the
+//! decoding or fall back to their own if a value could not be decoded.
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sp_core::Bytes;
+
+/// A block with decoded extrinsics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedBlock<Header> {
+	/// The block header.
+	pub header: Header,
+	/// Spec version of the runtime whose metadata was used for decoding.
+	pub spec_version: u32,
+	/// The extrinsics of the block body.
+	pub extrinsics: Vec<DecodedExtrinsic>,
+}
+
+/// An extrinsic with its decoding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedExtrinsic {
+	/// The encoded extrinsic.
+	pub encoded: Bytes,
+	/// The decoded extrinsic, `None` if it could not be decoded.
+	pub decoded: Option<ExtrinsicDetails>,
+	/// Why the extrinsic could not be decoded.
+	pub error: Option<String>,
+}
+
+/// The parts of a decoded extrinsic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicDetails {
+	/// The extrinsic format version.
+	pub version: u8,
+	/// The signature of signed extrinsics.
+	pub signature: Option<ExtrinsicSignature>,
+	/// The call with its module, function and arguments.
+	pub call: Value,
+}
+
+/// The signature of a decoded signed extrinsic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtrinsicSignature {
+	/// The address of the signer.
+	pub address: Value,
+	/// The signature.
+	pub signature: Value,
+	/// The signed extensions, in the order they are encoded in.
+	pub signed_extensions: Vec<SignedExtension>,
+}
+
+/// The decoded payload of a signed extension.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedExtension {
+	/// Identifier of the signed extension.
+	pub identifier: String,
+	/// The decoded payload.
+	pub value: Value,
+}
+
+/// The decoded events of a block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvents<Hash> {
+	/// Spec version of the runtime whose metadata was used for decoding.
+	pub spec_version: u32,
+	/// The encoded value of the `System::Events` storage entry.
+	pub encoded: Bytes,
+	/// The events, up to the first one which could not be decoded.
+	pub events: Vec<DecodedEvent<Hash>>,
+	/// Why not all events could be decoded.
+	pub error: Option<String>,
+}
+
+/// A decoded event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedEvent<Hash> {
+	/// Index of the extrinsic which deposited the event, `None` if it was deposited
+	/// during block finalization.
+	pub extrinsic_index: Option<u32>,
+	/// Name of the pallet which deposited the event.
+	pub pallet: String,
+	/// Name of the event.
+	pub event: String,
+	/// The decoded arguments of the event.
+	pub args: Vec<Value>,
+	/// The topics of the event.
+	pub topics: Vec<Hash>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn should_serialize_decoded_extrinsics() {
+		let extrinsics = vec![
+			DecodedExtrinsic {
+				encoded: Bytes(vec![1, 2]),
+				decoded: Some(ExtrinsicDetails {
+					version: 4,
+					signature: Some(ExtrinsicSignature {
+						address: json!({ "Index": 1 }),
+						signature: json!({ "Ed25519": "0x00" }),
+						signed_extensions: vec![SignedExtension { identifier: "CheckNonce".into(), value: json!(2) }],
+					}),
+					call: json!({ "module": "System", "function": "remark", "args": [] }),
+				}),
+				error: None,
+			},
+			DecodedExtrinsic { encoded: Bytes(vec![3]), decoded: None, error: Some("Invalid".into()) },
+		];
+
+		assert_eq!(
+			serde_json::to_value(&extrinsics).unwrap(),
+			json!([
+				{
+					"encoded": "0x0102",
+					"decoded": {
+						"version": 4,
+						"signature": {
+							"address": { "Index": 1 },
+							"signature": { "Ed25519": "0x00" },
+							"signedExtensions": [{ "identifier": "CheckNonce", "value": 2 }],
+						},
+						"call": { "module": "System", "function": "remark", "args": [] },
+					},
+					"error": null,
+				},
+				{ "encoded": "0x03", "decoded": null, "error": "Invalid" },
+			]),
+		);
+	}
+}
@@ -32,6 +32,21 @@ pub enum Error {
 	/// Client error.
 	#[display(fmt="Client error: {}", _0)]
 	Client(Box<dyn std::error::Error + Send>),
+	/// There is no active follow-chain subscription with the given id in this session.
+	#[display(fmt="Unknown follow-chain subscription")]
+	UnknownSubscription,
+	/// The node does not maintain an event index.
+	#[display(fmt="Event index is not available, start a full node with `--index-events` to enable it")]
+	EventIndexUnavailable,
+	/// Invalid events query.
+	#[display(fmt="Invalid events query: {}", _0)]
+	#[from(ignore)]
+	InvalidEventQuery(String),
+	/// The node can not decode blocks and events.
+	#[display(fmt="Decoding blocks and events is only supported by full nodes")]
+	DecodingUnavailable,
+	/// Call to an unsafe RPC was denied.
+	UnsafeRpcCalled(crate::policy::UnsafeRpcError),
 	/// Other error type.
 	Other(String),
 }
@@ -40,6 +55,7 @@ impl std::error::Error for Error {
 	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
 		match self {
 			Error::Client(ref err) => Some(&**err),
+			Error::UnsafeRpcCalled(ref err) => Some(err),
 			_ => None,
 		}
 	}
@@ -56,6 +72,27 @@ impl From<Error> for rpc::Error {
 				message,
 				data: None,
 			},
+			Error::UnknownSubscription => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 2),
+				message: format!("{}", e),
+				data: None,
+			},
+			Error::EventIndexUnavailable => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 3),
+				message: format!("{}", e),
+				data: None,
+			},
+			Error::InvalidEventQuery(_) => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 4),
+				message: format!("{}", e),
+				data: None,
+			},
+			Error::DecodingUnavailable => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 5),
+				message: format!("{}", e),
+				data: None,
+			},
+			Error::UnsafeRpcCalled(e) => e.into(),
 			e => errors::internal(e),
 		}
 	}
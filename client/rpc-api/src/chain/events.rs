@@ -0,0 +1,104 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types of the indexed events query.
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+/// Filter of the `chain_queryEvents` query. Events have to match all given fields.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EventFilter<Hash> {
+	/// Name of the pallet which deposited the event.
+	pub pallet: Option<String>,
+	/// Name of the event, requires `pallet` to be set.
+	pub event: Option<String>,
+	/// A topic of the event.
+	pub topic: Option<Hash>,
+}
+
+/// An event of a canonical block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent<Number, Hash> {
+	/// Number of the block.
+	pub block_number: Number,
+	/// Hash of the block.
+	pub block_hash: Hash,
+	/// Index of the event among the events of the block.
+	pub event_index: u32,
+	/// Index of the extrinsic which deposited the event, `None` if it was deposited
+	/// during block finalization.
+	pub extrinsic_index: Option<u32>,
+	/// Name of the pallet which deposited the event.
+	pub pallet: String,
+	/// Name of the event.
+	pub event: String,
+	/// The decoded arguments of the event.
+	pub args: Vec<Value>,
+	/// The topics of the event.
+	pub topics: Vec<Hash>,
+}
+
+/// A page of the `chain_queryEvents` results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage<Number, Hash> {
+	/// The matching events, ordered by block number and event index.
+	pub events: Vec<IndexedEvent<Number, Hash>>,
+	/// The block to continue the query from if the queried range has not been scanned
+	/// completely, either because the page is full or blocks are not indexed yet.
+	pub next: Option<Number>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn should_serialize_indexed_events() {
+		let page = EventPage {
+			events: vec![IndexedEvent {
+				block_number: 5u32,
+				block_hash: 1u32,
+				event_index: 2,
+				extrinsic_index: Some(1),
+				pallet: "Balances".into(),
+				event: "Transfer".into(),
+				args: vec![json!(10)],
+				topics: vec![],
+			}],
+			next: Some(6),
+		};
+
+		assert_eq!(
+			serde_json::to_string(&page).unwrap(),
+			r#"{"events":[{"blockNumber":5,"blockHash":1,"eventIndex":2,"extrinsicIndex":1,"#.to_owned() +
+				r#""pallet":"Balances","event":"Transfer","args":[10],"topics":[]}],"next":6}"#,
+		);
+	}
+
+	#[test]
+	fn should_deserialize_event_filters() {
+		assert_eq!(
+			serde_json::from_str::<EventFilter<u32>>(r#"{"pallet":"Balances","topic":3}"#).unwrap(),
+			EventFilter { pallet: Some("Balances".into()), event: None, topic: Some(3) },
+		);
+		assert!(serde_json::from_str::<EventFilter<u32>>(r#"{"module":"Balances"}"#).is_err());
+	}
+}
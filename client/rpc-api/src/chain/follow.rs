@@ -0,0 +1,91 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Events of the follow-chain subscription.
+
+use serde::{Serialize, Deserialize};
+
+/// An event of the `chain_followChain` subscription.
+///
+/// Every block reported in `Initialized` or `NewBlock` stays pinned, i.e. its state
+/// can still be queried, until it is released with `chain_unpin` or the subscription
+/// ends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "event")]
+pub enum FollowEvent<Hash> {
+	/// The first event of the subscription, reporting the last finalized block.
+	///
+	/// It is followed by a `NewBlock` for every known non-finalized block and by
+	/// the current best block.
+	#[serde(rename_all = "camelCase")]
+	Initialized {
+		/// The last finalized block.
+		finalized_block_hash: Hash,
+	},
+	/// A new block has been imported. Its parent has always been reported before.
+	#[serde(rename_all = "camelCase")]
+	NewBlock {
+		/// The imported block.
+		block_hash: Hash,
+		/// The parent of the imported block.
+		parent_block_hash: Hash,
+	},
+	/// The best block has changed to a block reported before.
+	#[serde(rename_all = "camelCase")]
+	BestBlockChanged {
+		/// The new best block.
+		best_block_hash: Hash,
+	},
+	/// Blocks have been finalized.
+	#[serde(rename_all = "camelCase")]
+	Finalized {
+		/// The newly finalized blocks, ordered by block number.
+		finalized_block_hashes: Vec<Hash>,
+		/// The reported blocks that are not descendants of the last finalized block
+		/// and thus will never be finalized.
+		pruned_block_hashes: Vec<Hash>,
+	},
+	/// The subscription has been closed by the node, e.g. because too many blocks
+	/// are pinned. No further events are sent and all pins are released.
+	Stop,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_follow_events() {
+		assert_eq!(
+			serde_json::to_string(&FollowEvent::NewBlock {
+				block_hash: 2u32,
+				parent_block_hash: 1u32,
+			}).unwrap(),
+			r#"{"event":"newBlock","blockHash":2,"parentBlockHash":1}"#,
+		);
+		assert_eq!(
+			serde_json::to_string(&FollowEvent::Finalized {
+				finalized_block_hashes: vec![1u32, 2],
+				pruned_block_hashes: vec![3],
+			}).unwrap(),
+			r#"{"event":"finalized","finalizedBlockHashes":[1,2],"prunedBlockHashes":[3]}"#,
+		);
+		assert_eq!(
+			serde_json::to_string(&FollowEvent::<u32>::Stop).unwrap(),
+			r#"{"event":"stop"}"#,
+		);
+	}
+}
@@ -16,14 +16,20 @@
 
 //! Substrate blockchain API.
 
+pub mod decoded;
 pub mod error;
+pub mod events;
+pub mod follow;
 
 use jsonrpc_core::Result as RpcResult;
 use jsonrpc_core::futures::Future;
 use jsonrpc_derive::rpc;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use sp_rpc::{number::NumberOrHex, list::ListOrValue};
+use self::decoded::{DecodedBlock, DecodedEvents};
 use self::error::{FutureResult, Result};
+use self::events::{EventFilter, EventPage};
+use self::follow::FollowEvent;
 
 pub use self::gen_client::Client as ChainClient;
 
@@ -41,6 +47,18 @@ pub trait ChainApi<Number, Hash, Header, SignedBlock> {
 	#[rpc(name = "chain_getBlock")]
 	fn block(&self, hash: Option<Hash>) -> FutureResult<Option<SignedBlock>>;
 
+	/// Get a block with its extrinsics decoded using the metadata of the block's runtime.
+	///
+	/// By default returns the best block.
+	#[rpc(name = "chain_getDecodedBlock")]
+	fn decoded_block(&self, hash: Option<Hash>) -> Result<Option<DecodedBlock<Header>>>;
+
+	/// Get the events of a block decoded using the metadata of the block's runtime.
+	///
+	/// By default returns the events of the best block.
+	#[rpc(name = "chain_getDecodedEvents")]
+	fn decoded_events(&self, hash: Option<Hash>) -> Result<Option<DecodedEvents<Hash>>>;
+
 	/// Get hash of the n-th block in the canon chain.
 	///
 	/// By default returns latest block hash.
@@ -50,6 +68,18 @@ pub trait ChainApi<Number, Hash, Header, SignedBlock> {
 		hash: Option<ListOrValue<NumberOrHex<Number>>>,
 	) -> Result<ListOrValue<Option<Hash>>>;
 
+	/// Query the indexed events of the canon chain blocks in the range `from..=to`.
+	///
+	/// By default the range ends at the best block. Results are paged, a page ends at
+	/// a block boundary and its `next` field is the block to continue the query from.
+	#[rpc(name = "chain_queryEvents")]
+	fn query_events(
+		&self,
+		filter: EventFilter<Hash>,
+		from: NumberOrHex<Number>,
+		to: Option<NumberOrHex<Number>>,
+	) -> Result<EventPage<Number, Hash>>;
+
 	/// Get hash of the last finalized block in the canon chain.
 	#[rpc(name = "chain_getFinalizedHead", alias("chain_getFinalisedHead"))]
 	fn finalized_head(&self) -> Result<Hash>;
@@ -97,4 +127,29 @@ pub trait ChainApi<Number, Hash, Header, SignedBlock> {
 		metadata: Option<Self::Metadata>,
 		id: SubscriptionId,
 	) -> RpcResult<bool>;
+
+	/// Follow the chain: new blocks, best block changes, finalization and pruned forks.
+	///
+	/// Reported blocks are kept pinned until they are released with `chain_unpin`. This call is
+	/// unsafe.
+	#[pubsub(subscription = "chain_followEvent", subscribe, name = "chain_followChain")]
+	fn follow_chain(&self, metadata: Self::Metadata, subscriber: Subscriber<FollowEvent<Hash>>);
+
+	/// Unsubscribe from follow-chain subscription, releasing all its pinned blocks.
+	#[pubsub(subscription = "chain_followEvent", unsubscribe, name = "chain_unfollowChain")]
+	fn unfollow_chain(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> RpcResult<bool>;
+
+	/// Release blocks pinned by the follow-chain subscription with the given id, which must
+	/// have been opened in the same session. This call is unsafe.
+	#[rpc(meta, name = "chain_unpin")]
+	fn unpin(
+		&self,
+		metadata: Self::Metadata,
+		subscription: u64,
+		hashes: Vec<Hash>,
+	) -> Result<()>;
 }
@@ -0,0 +1,60 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Policy of the RPC methods considered unsafe.
+//!
+//! Unsafe methods give access to the keys, the peers or the local storage of the node,
+//! and are denied on interfaces that are not trusted.
+
+use jsonrpc_core as rpc;
+
+/// Whether the RPC methods considered unsafe are denied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenyUnsafe {
+	/// Unsafe methods are denied.
+	Yes,
+	/// All methods are allowed.
+	No,
+}
+
+impl DenyUnsafe {
+	/// Returns an error if the RPC methods considered unsafe are denied.
+	///
+	/// Unsafe methods call this before doing anything else.
+	pub fn check_if_safe(self) -> Result<(), UnsafeRpcError> {
+		match self {
+			DenyUnsafe::Yes => Err(UnsafeRpcError),
+			DenyUnsafe::No => Ok(()),
+		}
+	}
+}
+
+/// An unsafe RPC method has been called on an interface that denies them.
+#[derive(Debug, derive_more::Display)]
+#[display(fmt = "RPC call is unsafe to be called externally")]
+pub struct UnsafeRpcError;
+
+impl std::error::Error for UnsafeRpcError {}
+
+impl From<UnsafeRpcError> for rpc::Error {
+	fn from(e: UnsafeRpcError) -> Self {
+		rpc::Error {
+			code: rpc::ErrorCode::MethodNotFound,
+			message: format!("{}", e),
+			data: Some("Use `--rpc-methods unsafe` to allow this call on a public interface.".into()),
+		}
+	}
+}
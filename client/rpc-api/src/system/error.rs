@@ -30,6 +30,11 @@ pub enum Error {
 	NotHealthy(Health),
 	/// Peer argument is malformatted.
 	MalformattedPeerArg(String),
+	/// Call to an unsafe RPC was denied.
+	UnsafeRpcCalled(crate::policy::UnsafeRpcError),
+	/// Log filter directives are invalid.
+	#[from(ignore)]
+	InvalidLogDirective(String),
 }
 
 impl std::error::Error for Error {}
@@ -49,7 +54,13 @@ impl From<Error> for rpc::Error {
 				code :rpc::ErrorCode::ServerError(BASE_ERROR + 2),
 				message: e.clone(),
 				data: None,
-			}
+			},
+			Error::UnsafeRpcCalled(e) => e.into(),
+			Error::InvalidLogDirective(ref e) => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 3),
+				message: e.clone(),
+				data: None,
+			},
 		}
 	}
 }
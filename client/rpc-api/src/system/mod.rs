@@ -57,14 +57,16 @@ pub trait SystemApi<Hash, Number> {
 
 	/// Returns currently connected peers
 	#[rpc(name = "system_peers", returns = "Vec<PeerInfo<Hash, Number>>")]
-	fn system_peers(&self) -> Receiver<Vec<PeerInfo<Hash, Number>>>;
+	fn system_peers(&self)
+		-> Compat<BoxFuture<'static, Result<Vec<PeerInfo<Hash, Number>>, jsonrpc_core::Error>>>;
 
 	/// Returns current state of the network.
 	///
 	/// **Warning**: This API is not stable.
 	// TODO: make this stable and move structs https://github.com/paritytech/substrate/issues/1890
 	#[rpc(name = "system_networkState", returns = "jsonrpc_core::Value")]
-	fn system_network_state(&self) -> Receiver<jsonrpc_core::Value>;
+	fn system_network_state(&self)
+		-> Compat<BoxFuture<'static, Result<jsonrpc_core::Value, jsonrpc_core::Error>>>;
 
 	/// Adds a reserved peer. Returns the empty string or an error. The string
 	/// parameter should encode a `p2p` multiaddr.
@@ -84,4 +86,15 @@ pub trait SystemApi<Hash, Number> {
 	/// Returns the roles the node is running as.
 	#[rpc(name = "system_nodeRoles", returns = "Vec<NodeRole>")]
 	fn system_node_roles(&self) -> Receiver<Vec<NodeRole>>;
+
+	/// Adds the supplied directives to the current log filter.
+	///
+	/// The syntax is identical to the `--log` CLI option, e.g. `sync=debug,afg=trace`.
+	/// Later directives override the level of earlier ones for the same target.
+	#[rpc(name = "system_addLogFilter")]
+	fn system_add_log_filter(&self, directives: String) -> SystemResult<()>;
+
+	/// Resets the log filter to the one the node has been started with.
+	#[rpc(name = "system_resetLogFilter")]
+	fn system_reset_log_filter(&self) -> SystemResult<()>;
 }
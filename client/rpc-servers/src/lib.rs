@@ -18,27 +18,34 @@
 
 #![warn(missing_docs)]
 
+mod middleware;
+#[cfg(not(target_os = "unknown"))]
+mod ws_proxy;
+
 use std::io;
-use jsonrpc_core::IoHandlerExtension;
+use jsonrpc_core::{IoHandlerExtension, MetaIoHandler};
 use log::error;
 use pubsub::PubSubMetadata;
 
-/// Maximal payload accepted by RPC servers.
+/// Default maximal payload accepted by RPC servers.
 const MAX_PAYLOAD: usize = 15 * 1024 * 1024;
 
 /// Default maximum number of connections for WS RPC servers.
 const WS_MAX_CONNECTIONS: usize = 100;
 
 /// The RPC IoHandler containing all requested APIs.
-pub type RpcHandler<T> = pubsub::PubSubHandler<T>;
+pub type RpcHandler<T> = pubsub::PubSubHandler<T, RpcMiddleware>;
 
 pub use self::inner::*;
+pub use self::middleware::RpcMiddleware;
+pub use ipnet::IpNet;
 
 /// Construct rpc `IoHandler`
 pub fn rpc_handler<M: PubSubMetadata>(
-	extension: impl IoHandlerExtension<M>
+	extension: impl IoHandlerExtension<M>,
+	middleware: RpcMiddleware,
 ) -> RpcHandler<M> {
-	let mut io = pubsub::PubSubHandler::default();
+	let mut io = pubsub::PubSubHandler::new(MetaIoHandler::with_middleware(middleware));
 	extension.augment(&mut io);
 
 	// add an endpoint to list all available methods.
@@ -62,54 +69,98 @@ mod inner {
 
 	/// Type alias for http server
 	pub type HttpServer = http::Server;
-	/// Type alias for ws server
-	pub type WsServer = ws::Server;
+
+	/// WS server, optionally behind a proxy filtering the IP addresses of peers.
+	pub struct WsServer {
+		_server: ws::Server,
+		_proxy: Option<ws_proxy::WsProxy>,
+	}
 
 	/// Start HTTP server listening on given address.
 	///
+	/// Requests bigger than `max_payload` bytes (15 MiB if `None`) are rejected.
+	///
 	/// **Note**: Only available if `not(target_os = "unknown")`.
 	pub fn start_http<M: pubsub::PubSubMetadata + Default>(
 		addr: &std::net::SocketAddr,
 		cors: Option<&Vec<String>>,
+		max_payload: Option<usize>,
 		io: RpcHandler<M>,
 	) -> io::Result<http::Server> {
 		http::ServerBuilder::new(io)
 			.threads(4)
 			.health_api(("/health", "system_health"))
-			.allowed_hosts(hosts_filtering(cors.is_some()))
+			.allowed_hosts(hosts_filtering(cors.is_some(), None))
 			.rest_api(if cors.is_some() {
 				http::RestApi::Secure
 			} else {
 				http::RestApi::Unsecure
 			})
 			.cors(map_cors::<http::AccessControlAllowOrigin>(cors))
-			.max_request_body_size(MAX_PAYLOAD)
+			.max_request_body_size(max_payload.unwrap_or(MAX_PAYLOAD))
 			.start_http(addr)
 	}
 
 	/// Start WS server listening on given address.
 	///
+	/// Messages bigger than `max_payload` bytes (15 MiB if `None`) are rejected. If
+	/// `allowed_ips` is set, only peers with an IP address within one of the given
+	/// networks can connect.
+	///
 	/// **Note**: Only available if `not(target_os = "unknown")`.
 	pub fn start_ws<M: pubsub::PubSubMetadata + From<jsonrpc_core::futures::sync::mpsc::Sender<String>>> (
 		addr: &std::net::SocketAddr,
 		max_connections: Option<usize>,
 		cors: Option<&Vec<String>>,
+		max_payload: Option<usize>,
+		allowed_ips: Option<&Vec<IpNet>>,
 		io: RpcHandler<M>,
-	) -> io::Result<ws::Server> {
-		ws::ServerBuilder::with_meta_extractor(io, |context: &ws::RequestContext| context.sender().into())
-			.max_payload(MAX_PAYLOAD)
+	) -> io::Result<WsServer> {
+		let builder = ws::ServerBuilder::with_meta_extractor(io, |context: &ws::RequestContext| context.sender().into())
+			.max_payload(max_payload.unwrap_or(MAX_PAYLOAD))
 			.max_connections(max_connections.unwrap_or(WS_MAX_CONNECTIONS))
-			.allowed_origins(map_cors(cors))
-			.allowed_hosts(hosts_filtering(cors.is_some()))
-			.start(addr)
-			.map_err(|err| match err {
-				ws::Error::Io(io) => io,
-				ws::Error::ConnectionClosed => io::ErrorKind::BrokenPipe.into(),
-				e => {
-					error!("{}", e);
-					io::ErrorKind::Other.into()
-				}
-			})
+			.allowed_origins(map_cors(cors));
+
+		let allowed_ips = match allowed_ips {
+			Some(allowed_ips) => allowed_ips,
+			None => return builder
+				.allowed_hosts(hosts_filtering(cors.is_some(), None))
+				.start(addr)
+				.map(|server| WsServer { _server: server, _proxy: None })
+				.map_err(map_ws_error),
+		};
+
+		// The proxy takes over the public address and forwards allowed peers to
+		// the server listening on a random loopback port.
+		let listener = std::net::TcpListener::bind(addr)?;
+		let public_addr = listener.local_addr()?;
+		let local_addr = std::net::SocketAddr::new(match public_addr {
+			std::net::SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+			std::net::SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+		}, 0);
+		let server = builder
+			.allowed_hosts(hosts_filtering(cors.is_some(), Some(&public_addr)))
+			.start(&local_addr)
+			.map_err(map_ws_error)?;
+		let proxy = ws_proxy::WsProxy::start(
+			listener,
+			*server.addr(),
+			allowed_ips.clone(),
+			max_connections.unwrap_or(WS_MAX_CONNECTIONS),
+		)?;
+
+		Ok(WsServer { _server: server, _proxy: Some(proxy) })
+	}
+
+	fn map_ws_error(err: ws::Error) -> io::Error {
+		match err {
+			ws::Error::Io(io) => io,
+			ws::Error::ConnectionClosed => io::ErrorKind::BrokenPipe.into(),
+			e => {
+				error!("{}", e);
+				io::ErrorKind::Other.into()
+			}
+		}
 	}
 
 	fn map_cors<T: for<'a> From<&'a str>>(
@@ -118,12 +169,20 @@ mod inner {
 		cors.map(|x| x.iter().map(AsRef::as_ref).map(Into::into).collect::<Vec<_>>()).into()
 	}
 
-	fn hosts_filtering(enable: bool) -> http::DomainsValidation<http::Host> {
+	fn hosts_filtering(
+		enable: bool,
+		public_addr: Option<&std::net::SocketAddr>,
+	) -> http::DomainsValidation<http::Host> {
 		if enable {
 			// NOTE The listening address is whitelisted by default.
 			// Setting an empty vector here enables the validation
-			// and allows only the listening address.
-			http::DomainsValidation::AllowOnly(vec![])
+			// and allows only the listening address, or the address
+			// of the proxy forwarding to it.
+			http::DomainsValidation::AllowOnly(public_addr.map_or_else(Vec::new, |addr| vec![
+				addr.to_string().as_str().into(),
+				format!("localhost:{}", addr.port()).as_str().into(),
+				format!("127.0.0.1:{}", addr.port()).as_str().into(),
+			]))
 		} else {
 			http::DomainsValidation::Disabled
 		}
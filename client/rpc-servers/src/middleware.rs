@@ -0,0 +1,297 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Middleware enforcing per-connection rate limits and response size limits.
+
+use std::{
+	cell::RefCell,
+	collections::{HashMap, hash_map::Entry},
+	sync::{Arc, Weak, atomic::{AtomicUsize, Ordering}},
+	time::{Duration, Instant},
+};
+use jsonrpc_core::{
+	Call, Error, ErrorCode, Middleware, MethodCall, Output, Success,
+	futures::{Future, future::{self, Either}, task, task_local},
+	middleware::{NoopCallFuture, NoopFuture},
+};
+use parking_lot::Mutex;
+use pubsub::{PubSubMetadata, Session};
+
+/// Error code returned when the response exceeds the configured maximum size.
+const RESPONSE_TOO_BIG: i64 = -32008;
+/// Error code returned when a connection exceeds its call rate limit.
+const TOO_MANY_REQUESTS: i64 = -32009;
+
+/// RPC middleware limiting the rate of calls and the size of responses.
+///
+/// The rate limit applies per connection. Connections with sessions (WS) are identified by
+/// their session, the others (HTTP) by the task serving them. Calls made outside of any
+/// task share a single, server-wide limit.
+#[derive(Clone, Default)]
+pub struct RpcMiddleware {
+	max_response_size: Option<usize>,
+	rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RpcMiddleware {
+	/// Create a new middleware.
+	///
+	/// `max_response_size` is in bytes, `None` values disable the respective limit.
+	pub fn new(max_response_size: Option<usize>, max_calls_per_second: Option<u32>) -> Self {
+		RpcMiddleware {
+			max_response_size,
+			rate_limiter: max_calls_per_second.map(|max_calls_per_second| Arc::new(RateLimiter {
+				max_calls_per_second,
+				windows: Mutex::new(HashMap::new()),
+			})),
+		}
+	}
+}
+
+impl<M: PubSubMetadata> Middleware<M> for RpcMiddleware {
+	type Future = NoopFuture;
+	type CallFuture = NoopCallFuture;
+
+	fn on_call<F, X>(&self, call: Call, meta: M, next: F) -> Either<Self::CallFuture, X>
+	where
+		F: Fn(Call, M) -> X + Send + Sync,
+		X: Future<Item = Option<Output>, Error = ()> + Send + 'static,
+	{
+		if let Some(ref rate_limiter) = self.rate_limiter {
+			if !RateLimiter::try_call(rate_limiter, meta.session()) {
+				let output = match call {
+					Call::MethodCall(MethodCall { id, jsonrpc, .. }) =>
+						Some(Output::from(Err(too_many_requests()), id, jsonrpc)),
+					Call::Notification(_) => None,
+					Call::Invalid { id } => Some(Output::from(Err(Error::invalid_request()), id, None)),
+				};
+				return Either::A(Box::new(future::ok(output)));
+			}
+		}
+
+		match self.max_response_size {
+			Some(max_response_size) => Either::A(Box::new(
+				next(call, meta).map(move |output| output.map(|output| limit_size(output, max_response_size)))
+			)),
+			None => Either::B(next(call, meta)),
+		}
+	}
+}
+
+/// Replaces a successful output with an error if its result is bigger than `max_size` bytes.
+fn limit_size(output: Output, max_size: usize) -> Output {
+	match output {
+		Output::Success(Success { id, jsonrpc, result }) => {
+			let size = serde_json::to_vec(&result).map(|encoded| encoded.len()).unwrap_or(0);
+			if size > max_size {
+				Output::from(Err(Error {
+					code: ErrorCode::ServerError(RESPONSE_TOO_BIG),
+					message: "Response is too big".into(),
+					data: Some(format!(
+						"Exceeded max limit of {} bytes, use `--rpc-max-response-size` to increase it",
+						max_size,
+					).into()),
+				}), id, jsonrpc)
+			} else {
+				Output::Success(Success { id, jsonrpc, result })
+			}
+		},
+		failure => failure,
+	}
+}
+
+fn too_many_requests() -> Error {
+	Error {
+		code: ErrorCode::ServerError(TOO_MANY_REQUESTS),
+		message: "Too many requests".into(),
+		data: None,
+	}
+}
+
+/// Source of the ids of connections without a session.
+static NEXT_CONNECTION_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A connection without a session, alive as long as the task serving it.
+struct Connection {
+	id: usize,
+	/// Run when the task serving the connection ends.
+	on_close: RefCell<Vec<Box<dyn FnOnce() + Send>>>,
+}
+
+impl Drop for Connection {
+	fn drop(&mut self) {
+		for on_close in self.on_close.get_mut().drain(..) {
+			on_close();
+		}
+	}
+}
+
+task_local! {
+	static CONNECTION: Connection = Connection {
+		id: NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+		on_close: RefCell::new(Vec::new()),
+	}
+}
+
+/// Identifies the connection a call is made on.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Key {
+	/// Address of the session, unique while the session is alive.
+	Session(usize),
+	/// Id of a connection without a session.
+	Connection(usize),
+	/// Calls which can't be attributed to a connection.
+	Shared,
+}
+
+/// Counts the calls of every connection within the current one second window.
+struct RateLimiter {
+	max_calls_per_second: u32,
+	windows: Mutex<HashMap<Key, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+	/// Registers a call of the given session, returns `false` if it exceeds the limit.
+	fn try_call(this: &Arc<Self>, session: Option<Arc<Session>>) -> bool {
+		let key = match session.as_ref() {
+			Some(session) => Key::Session(&**session as *const Session as usize),
+			// every HTTP connection is served by its own task.
+			None if task::is_in_task() => CONNECTION.with(|connection| Key::Connection(connection.id)),
+			None => Key::Shared,
+		};
+		let now = Instant::now();
+		let mut windows = this.windows.lock();
+		let (start, calls) = match windows.entry(key) {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => {
+				let limiter: Weak<Self> = Arc::downgrade(this);
+				let forget = move || if let Some(limiter) = limiter.upgrade() {
+					limiter.windows.lock().remove(&key);
+				};
+				match (key, session.as_ref()) {
+					(Key::Session(_), Some(session)) => session.on_drop(forget),
+					(Key::Connection(_), _) => CONNECTION.with(|connection|
+						connection.on_close.borrow_mut().push(Box::new(forget))
+					),
+					_ => {},
+				}
+				entry.insert((now, 0))
+			},
+		};
+
+		if now.duration_since(*start) >= Duration::from_secs(1) {
+			*start = now;
+			*calls = 0;
+		}
+		if *calls >= this.max_calls_per_second {
+			return false;
+		}
+		*calls += 1;
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpc_core::{MetaIoHandler, Metadata, Value, futures::sync::mpsc};
+
+	/// Runs `f` within a task, as calls made on an HTTP connection are.
+	fn in_task<R>(f: impl FnOnce() -> R) -> R {
+		future::lazy(|| Ok::<_, ()>(f())).wait().unwrap()
+	}
+
+	#[derive(Clone, Default)]
+	struct Meta(Option<Arc<Session>>);
+
+	impl Metadata for Meta {}
+
+	impl PubSubMetadata for Meta {
+		fn session(&self) -> Option<Arc<Session>> {
+			self.0.clone()
+		}
+	}
+
+	fn handler(middleware: RpcMiddleware) -> MetaIoHandler<Meta, RpcMiddleware> {
+		let mut io = MetaIoHandler::with_middleware(middleware);
+		io.add_method("hello", |_| Ok(Value::String("hello world".into())));
+		io
+	}
+
+	fn session() -> Meta {
+		let (tx, _rx) = mpsc::channel(1);
+		Meta(Some(Arc::new(Session::new(tx))))
+	}
+
+	const REQUEST: &str = r#"{"jsonrpc":"2.0","method":"hello","params":[],"id":1}"#;
+	const SUCCESS: &str = r#"{"jsonrpc":"2.0","result":"hello world","id":1}"#;
+	const TOO_MANY: &str = r#"{"jsonrpc":"2.0","error":{"code":-32009,"message":"Too many requests"},"id":1}"#;
+
+	#[test]
+	fn should_limit_calls_per_connection() {
+		let middleware = RpcMiddleware::new(None, Some(2));
+		let io = handler(middleware.clone());
+		let (first, second) = (session(), session());
+
+		for _ in 0..2 {
+			assert_eq!(io.handle_request_sync(REQUEST, first.clone()), Some(SUCCESS.into()));
+		}
+		assert_eq!(io.handle_request_sync(REQUEST, first.clone()), Some(TOO_MANY.into()));
+		assert_eq!(io.handle_request_sync(REQUEST, second.clone()), Some(SUCCESS.into()));
+
+		let limiter = middleware.rate_limiter.unwrap();
+		assert_eq!(limiter.windows.lock().len(), 2);
+		drop(first);
+		assert_eq!(limiter.windows.lock().len(), 1);
+	}
+
+	#[test]
+	fn should_limit_calls_per_task_without_session() {
+		let middleware = RpcMiddleware::new(None, Some(2));
+		let io = handler(middleware.clone());
+		let limiter = middleware.rate_limiter.clone().unwrap();
+
+		in_task(|| {
+			for _ in 0..2 {
+				assert_eq!(io.handle_request_sync(REQUEST, Meta(None)), Some(SUCCESS.into()));
+			}
+			assert_eq!(io.handle_request_sync(REQUEST, Meta(None)), Some(TOO_MANY.into()));
+			assert_eq!(limiter.windows.lock().len(), 1);
+		});
+		// the window is forgotten with the task, another connection has its own limit.
+		assert_eq!(limiter.windows.lock().len(), 0);
+		in_task(|| assert_eq!(io.handle_request_sync(REQUEST, Meta(None)), Some(SUCCESS.into())));
+	}
+
+	#[test]
+	fn should_share_limit_outside_of_tasks() {
+		let io = handler(RpcMiddleware::new(None, Some(1)));
+
+		assert_eq!(io.handle_request_sync(REQUEST, Meta(None)), Some(SUCCESS.into()));
+		assert_eq!(io.handle_request_sync(REQUEST, Meta(None)), Some(TOO_MANY.into()));
+	}
+
+	#[test]
+	fn should_reject_too_big_responses() {
+		let io = handler(RpcMiddleware::new(Some(10), None));
+
+		let response = io.handle_request_sync(REQUEST, Meta(None)).unwrap();
+		assert!(response.contains(r#""code":-32008"#), "{}", response);
+
+		let io = handler(RpcMiddleware::new(Some(13), None));
+		assert_eq!(io.handle_request_sync(REQUEST, Meta(None)), Some(SUCCESS.into()));
+	}
+}
@@ -0,0 +1,233 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! TCP proxy restricting the peers allowed to connect to the WS server.
+//!
+//! The WS server does not expose the address of its peers, so the public address is
+//! served by this proxy which only forwards connections from allowed IP addresses
+//! to the WS server listening on a loopback address. Every forwarded connection takes
+//! two threads, so the number of connections forwarded at once is capped.
+
+use std::{
+	io, thread,
+	net::{IpAddr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream},
+	sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}},
+};
+use ipnet::IpNet;
+use log::{debug, warn};
+
+/// Proxy forwarding connections of allowed peers. Stops accepting connections on drop.
+pub struct WsProxy {
+	local_addr: SocketAddr,
+	closed: Arc<AtomicBool>,
+}
+
+impl WsProxy {
+	/// Start forwarding connections accepted by `listener` to `target` if the IP address
+	/// of the peer is within one of the `allowed` networks, and fewer than `max_connections`
+	/// connections are being forwarded already.
+	pub fn start(
+		listener: TcpListener,
+		target: SocketAddr,
+		allowed: Vec<IpNet>,
+		max_connections: usize,
+	) -> io::Result<Self> {
+		let local_addr = listener.local_addr()?;
+		let closed = Arc::new(AtomicBool::new(false));
+		let closed2 = closed.clone();
+		let active = Arc::new(AtomicUsize::new(0));
+
+		thread::Builder::new()
+			.name("ws-rpc-proxy".into())
+			.spawn(move || for stream in listener.incoming() {
+				if closed2.load(Ordering::SeqCst) {
+					break;
+				}
+
+				let inbound = match stream {
+					Ok(stream) => stream,
+					Err(err) => {
+						warn!("Failed to accept WS RPC connection: {}", err);
+						continue;
+					},
+				};
+				match inbound.peer_addr() {
+					Ok(peer) if is_allowed(&allowed, peer.ip()) => {
+						if active.load(Ordering::SeqCst) >= max_connections {
+							warn!("Refused WS RPC connection from {}: too many connections", peer);
+							continue;
+						}
+						if let Err(err) = forward(inbound, target, ConnectionGuard::new(&active)) {
+							warn!("Failed to forward WS RPC connection from {}: {}", peer, err);
+						}
+					},
+					Ok(peer) => debug!("Refused WS RPC connection from {}", peer),
+					Err(err) => debug!("Refused WS RPC connection from unknown peer: {}", err),
+				}
+			})?;
+
+		Ok(WsProxy { local_addr, closed })
+	}
+}
+
+impl Drop for WsProxy {
+	fn drop(&mut self) {
+		self.closed.store(true, Ordering::SeqCst);
+		// Wake up the accepting thread so it notices the proxy is closed.
+		let mut addr = self.local_addr;
+		if addr.ip().is_unspecified() {
+			addr.set_ip(match addr.ip() {
+				IpAddr::V4(_) => [127, 0, 0, 1].into(),
+				IpAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+			});
+		}
+		let _ = TcpStream::connect(addr);
+	}
+}
+
+/// Whether `ip` is within one of the `allowed` networks.
+///
+/// IPv4-mapped IPv6 addresses, as reported for IPv4 peers of an IPv6 listener, are
+/// matched as IPv4 addresses.
+fn is_allowed(allowed: &[IpNet], ip: IpAddr) -> bool {
+	let ip = match ip {
+		IpAddr::V6(v6) if v6.segments()[..6] == [0, 0, 0, 0, 0, 0xffff] =>
+			v6.to_ipv4().map(IpAddr::V4).unwrap_or(ip),
+		ip => ip,
+	};
+	allowed.iter().any(|net| net.contains(&ip))
+}
+
+/// Counts a forwarded connection as active until the last of its threads drops it.
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+	fn new(active: &Arc<AtomicUsize>) -> Arc<Self> {
+		active.fetch_add(1, Ordering::SeqCst);
+		Arc::new(ConnectionGuard(active.clone()))
+	}
+}
+
+impl Drop for ConnectionGuard {
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::SeqCst);
+	}
+}
+
+/// Connect to `target` and copy data in both directions until either side closes.
+fn forward(inbound: TcpStream, target: SocketAddr, guard: Arc<ConnectionGuard>) -> io::Result<()> {
+	let outbound = TcpStream::connect(target)?;
+	let (mut inbound_read, mut outbound_write) = (inbound.try_clone()?, outbound.try_clone()?);
+	let (mut outbound_read, mut inbound_write) = (outbound, inbound);
+
+	let guard2 = guard.clone();
+	thread::Builder::new().name("ws-rpc-proxy-conn".into()).spawn(move || {
+		let _guard = guard2;
+		let _ = io::copy(&mut inbound_read, &mut outbound_write);
+		let _ = outbound_write.shutdown(Shutdown::Both);
+		let _ = inbound_read.shutdown(Shutdown::Both);
+	})?;
+	thread::Builder::new().name("ws-rpc-proxy-conn".into()).spawn(move || {
+		let _guard = guard;
+		let _ = io::copy(&mut outbound_read, &mut inbound_write);
+		let _ = inbound_write.shutdown(Shutdown::Both);
+		let _ = outbound_read.shutdown(Shutdown::Both);
+	})?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{Read, Write};
+
+	#[test]
+	fn should_match_allowed_networks() {
+		let allowed = vec!["10.0.0.0/8".parse().unwrap(), "::1/128".parse().unwrap()];
+
+		assert!(is_allowed(&allowed, [10, 1, 2, 3].into()));
+		assert!(is_allowed(&allowed, Ipv6Addr::LOCALHOST.into()));
+		assert!(is_allowed(&allowed, "::ffff:10.0.0.1".parse().unwrap()));
+		assert!(!is_allowed(&allowed, [127, 0, 0, 1].into()));
+		assert!(!is_allowed(&allowed, "::ffff:192.168.0.1".parse().unwrap()));
+	}
+
+	fn echo_server() -> SocketAddr {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = listener.local_addr().unwrap();
+		thread::spawn(move || for stream in listener.incoming() {
+			let mut stream = stream.unwrap();
+			let mut writer = stream.try_clone().unwrap();
+			let _ = io::copy(&mut stream, &mut writer);
+		});
+		addr
+	}
+
+	fn proxy(allowed: &str, max_connections: usize) -> (WsProxy, SocketAddr) {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr = listener.local_addr().unwrap();
+		let allowed = vec![allowed.parse().unwrap()];
+		(WsProxy::start(listener, echo_server(), allowed, max_connections).unwrap(), addr)
+	}
+
+	fn ping(stream: &mut TcpStream) -> bool {
+		let _ = stream.write_all(b"ping");
+		let mut buf = [0; 4];
+		stream.read_exact(&mut buf).is_ok() && &buf == b"ping"
+	}
+
+	#[test]
+	fn should_forward_allowed_connections() {
+		let (_proxy, addr) = proxy("127.0.0.0/8", 100);
+		let mut stream = TcpStream::connect(addr).unwrap();
+
+		assert!(ping(&mut stream));
+	}
+
+	#[test]
+	fn should_refuse_other_connections() {
+		let (_proxy, addr) = proxy("10.0.0.0/8", 100);
+		let mut stream = TcpStream::connect(addr).unwrap();
+
+		let _ = stream.write_all(b"ping");
+		let mut buf = Vec::new();
+		assert_eq!(stream.read_to_end(&mut buf).unwrap_or(0), 0);
+	}
+
+	#[test]
+	fn should_refuse_connections_over_the_limit() {
+		let (_proxy, addr) = proxy("127.0.0.0/8", 1);
+		let mut first = TcpStream::connect(addr).unwrap();
+		assert!(ping(&mut first));
+
+		let mut second = TcpStream::connect(addr).unwrap();
+		assert!(!ping(&mut second));
+
+		// closing the first connection frees its slot.
+		first.shutdown(Shutdown::Both).unwrap();
+		drop(first);
+		let mut third = None;
+		for _ in 0..50 {
+			let mut stream = TcpStream::connect(addr).unwrap();
+			if ping(&mut stream) {
+				third = Some(stream);
+				break;
+			}
+			thread::sleep(std::time::Duration::from_millis(20));
+		}
+		assert!(third.is_some());
+	}
+}
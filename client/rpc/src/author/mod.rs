@@ -22,21 +22,21 @@ mod tests;
 use std::{sync::Arc, convert::TryInto};
 use log::warn;
 
-use sc_client::Client;
+use sc_client::{Client, BlockchainEvents};
 use sp_blockchain::Error as ClientError;
 
 use rpc::futures::{
 	Sink, Future,
 	future::result,
 };
-use futures::{StreamExt as _, compat::Compat};
+use futures::{Stream, StreamExt as _, compat::Compat, stream};
 use futures::future::{ready, FutureExt, TryFutureExt};
-use sc_rpc_api::Subscriptions;
+use sc_rpc_api::{DenyUnsafe, Subscriptions};
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use codec::{Encode, Decode};
 use sp_core::{Bytes, traits::BareCryptoStorePtr};
 use sp_api::ProvideRuntimeApi;
-use sp_runtime::{generic, traits};
+use sp_runtime::{generic, traits::{self, Header as _, NumberFor}};
 use sp_transaction_pool::{
 	TransactionPool, InPoolTransaction, TransactionStatus,
 	BlockHash, TxHash, TransactionFor, error::IntoPoolError,
@@ -46,6 +46,17 @@ use sp_session::SessionKeys;
 /// Re-export the API for backward compatibility.
 pub use sc_rpc_api::author::*;
 use self::error::{Error, FutureResult, Result};
+use self::status::{ExtrinsicStatus, Inclusion, TransactionStatusInfo};
+
+/// Index of the extrinsics included in blocks.
+pub trait TransactionIndex<Block: traits::Block>: Send + Sync {
+	/// Returns the number and hash of all indexed blocks including the extrinsic with
+	/// given hash, together with the index of the extrinsic in the block.
+	fn inclusions(&self, hash: &Block::Hash) -> Vec<(NumberFor<Block>, Block::Hash, u32)>;
+}
+
+/// Stream of the status updates of a transaction.
+type StatusStream<P> = Box<dyn Stream<Item = TransactionStatus<TxHash<P>, BlockHash<P>>> + Send + Unpin>;
 
 /// Authoring API
 pub struct Author<B, E, P, Block: traits::Block, RA> {
@@ -57,6 +68,10 @@ pub struct Author<B, E, P, Block: traits::Block, RA> {
 	subscriptions: Subscriptions,
 	/// The key store.
 	keystore: BareCryptoStorePtr,
+	/// Whether to deny unsafe calls
+	deny_unsafe: DenyUnsafe,
+	/// Index of the included extrinsics, if the node keeps one.
+	transaction_index: Option<Arc<dyn TransactionIndex<Block>>>,
 }
 
 impl<B, E, P, Block: traits::Block, RA> Author<B, E, P, Block, RA> {
@@ -66,13 +81,76 @@ impl<B, E, P, Block: traits::Block, RA> Author<B, E, P, Block, RA> {
 		pool: Arc<P>,
 		subscriptions: Subscriptions,
 		keystore: BareCryptoStorePtr,
+		deny_unsafe: DenyUnsafe,
+		transaction_index: Option<Arc<dyn TransactionIndex<Block>>>,
 	) -> Self {
 		Author {
 			client,
 			pool,
 			subscriptions,
 			keystore,
+			deny_unsafe,
+			transaction_index,
+		}
+	}
+}
+
+impl<B, E, P, RA> Author<B, E, P, <P as TransactionPool>::Block, RA>
+where
+	B: sc_client_api::backend::Backend<<P as TransactionPool>::Block> + Send + Sync + 'static,
+	E: sc_client::CallExecutor<<P as TransactionPool>::Block> + Send + Sync + 'static,
+	P: TransactionPool<Hash = BlockHash<P>> + Sync + Send + 'static,
+	RA: Send + Sync + 'static,
+{
+	/// Status of the extrinsic with given hash if it is included in the canonical chain,
+	/// together with the number of the including block and the position in it.
+	fn canonical_status(
+		&self,
+		hash: &TxHash<P>,
+	) -> Result<Option<(
+		NumberFor<P::Block>,
+		TransactionStatus<TxHash<P>, BlockHash<P>>,
+		Inclusion<BlockHash<P>>,
+	)>> {
+		let index = match self.transaction_index {
+			Some(ref index) => index,
+			None => return Ok(None),
+		};
+
+		for (number, block_hash, position) in index.inclusions(hash) {
+			let canon = self.client.block_hash(number).map_err(|e| Error::Client(Box::new(e)))?;
+			if canon != Some(block_hash) {
+				continue;
+			}
+
+			let status = if number <= self.client.chain_info().finalized_number {
+				TransactionStatus::Finalized(block_hash)
+			} else {
+				TransactionStatus::InBlock(block_hash)
+			};
+			return Ok(Some((number, status, Inclusion { block_hash, index: position })));
 		}
+
+		Ok(None)
+	}
+
+	/// Stream of the status updates of a transaction included in the canonical chain,
+	/// which reports `Finalized` once the including block is finalized.
+	fn finality_updates(
+		&self,
+		number: NumberFor<P::Block>,
+		block_hash: BlockHash<P>,
+	) -> StatusStream<P> {
+		let client = self.client.clone();
+		let updates = self.client.finality_notification_stream()
+			.filter(move |notification| ready(*notification.header.number() >= number))
+			.take(1)
+			.filter_map(move |_| ready(match client.block_hash(number) {
+				// the block might have been retracted in the meantime
+				Ok(Some(canon)) if canon == block_hash => Some(TransactionStatus::Finalized(block_hash)),
+				_ => None,
+			}));
+		Box::new(Box::pin(updates))
 	}
 }
 
@@ -81,7 +159,7 @@ impl<B, E, P, RA> AuthorApi<TxHash<P>, BlockHash<P>>
 where
 	B: sc_client_api::backend::Backend<<P as TransactionPool>::Block> + Send + Sync + 'static,
 	E: sc_client::CallExecutor<<P as TransactionPool>::Block> + Send + Sync + 'static,
-	P: TransactionPool + Sync + Send + 'static,
+	P: TransactionPool<Hash = BlockHash<P>> + Sync + Send + 'static,
 	P::Block: traits::Block,
 	P::Error: 'static,
 	RA: Send + Sync + 'static,
@@ -97,6 +175,8 @@ where
 		suri: String,
 		public: Bytes,
 	) -> Result<()> {
+		self.deny_unsafe.check_if_safe()?;
+
 		let key_type = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
 		let mut keystore = self.keystore.write();
 		keystore.insert_unknown(key_type, &suri, &public[..])
@@ -105,6 +185,8 @@ where
 	}
 
 	fn rotate_keys(&self) -> Result<Bytes> {
+		self.deny_unsafe.check_if_safe()?;
+
 		let best_block_hash = self.client.chain_info().best_hash;
 		self.client.runtime_api().generate_session_keys(
 			&generic::BlockId::Hash(best_block_hash),
@@ -113,6 +195,8 @@ where
 	}
 
 	fn has_session_keys(&self, session_keys: Bytes) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
 		let best_block_hash = self.client.chain_info().best_hash;
 		let keys = self.client.runtime_api().decode_session_keys(
 			&generic::BlockId::Hash(best_block_hash),
@@ -124,6 +208,8 @@ where
 	}
 
 	fn has_key(&self, public_key: Bytes, key_type: String) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
 		let key_type = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
 		Ok(self.keystore.read().has_keys(&[(public_key.to_vec(), key_type)]))
 	}
@@ -151,6 +237,8 @@ where
 		&self,
 		bytes_or_hash: Vec<hash::ExtrinsicOrHash<TxHash<P>>>,
 	) -> Result<Vec<TxHash<P>>> {
+		self.deny_unsafe.check_if_safe()?;
+
 		let hashes = bytes_or_hash.into_iter()
 			.map(|x| match x {
 				hash::ExtrinsicOrHash::Hash(h) => Ok(h),
@@ -170,6 +258,18 @@ where
 		)
 	}
 
+	fn transaction_status(
+		&self,
+		hash: TxHash<P>,
+	) -> Result<Option<TransactionStatusInfo<TxHash<P>, BlockHash<P>>>> {
+		Ok(match self.canonical_status(&hash)? {
+			Some((_, status, inclusion)) =>
+				Some(TransactionStatusInfo { status: status.into(), inclusion: Some(inclusion) }),
+			None => self.pool.transaction_status(&hash)
+				.map(|status| TransactionStatusInfo { status: status.into(), inclusion: None }),
+		})
+	}
+
 	fn watch_extrinsic(&self,
 		_metadata: Self::Metadata,
 		subscriber: Subscriber<TransactionStatus<TxHash<P>, BlockHash<P>>>,
@@ -192,8 +292,12 @@ where
 		let subscriptions = self.subscriptions.clone();
 		let future = ready(submit())
 			.and_then(|res| res)
-			// convert the watcher into a `Stream`
-			.map(|res| res.map(|stream| stream.map(|v| Ok::<_, ()>(Ok(v)))))
+			// convert the watcher into a `Stream`, `Finalized` can't be told apart from
+			// `InBlock` by the subscribers, see #4438
+			.map(|res| res.map(|stream| stream
+				.filter(|status| ready(!matches_finalized(status)))
+				.map(|v| Ok::<_, ()>(Ok(v)))
+			))
 			// now handle the import result,
 			// start a new subscrition
 			.map(move |result| match result {
@@ -219,7 +323,67 @@ where
 		}
 	}
 
+	fn watch_extrinsic_by_hash(&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<ExtrinsicStatus<TxHash<P>, BlockHash<P>>>,
+		hash: TxHash<P>,
+	) {
+		// start watching before looking up the current status, so no update is missed
+		let watcher = self.pool.watch(&hash);
+		let canonical = match self.canonical_status(&hash) {
+			Ok(canonical) => canonical,
+			Err(err) => {
+				let _ = subscriber.reject(err.into());
+				return;
+			},
+		};
+
+		let updates: StatusStream<P> = match (canonical, watcher) {
+			(Some((_, status, _)), _) if matches_finalized(&status) =>
+				Box::new(stream::iter(Some(status))),
+			(Some((_, status, _)), Some(watcher)) =>
+				Box::new(stream::iter(Some(status)).chain(watcher)),
+			(Some((number, status, _)), None) => {
+				let finality = match status {
+					TransactionStatus::InBlock(block_hash) => self.finality_updates(number, block_hash),
+					_ => Box::new(stream::empty()),
+				};
+				Box::new(stream::iter(Some(status)).chain(finality))
+			},
+			(None, Some(watcher)) =>
+				Box::new(stream::iter(self.pool.transaction_status(&hash)).chain(watcher)),
+			(None, None) => match self.pool.transaction_status(&hash) {
+				// the transaction left the pool, there won't be any updates
+				Some(status) => Box::new(stream::iter(Some(status))),
+				None => {
+					let _ = subscriber.reject(Error::UnknownTransaction.into());
+					return;
+				},
+			},
+		};
+
+		let updates = updates.map(|v| Ok::<_, ()>(Ok(ExtrinsicStatus::from(v))));
+		self.subscriptions.add(subscriber, move |sink| {
+			sink
+				.sink_map_err(|_| unimplemented!())
+				.send_all(Compat::new(updates))
+				.map(|_| ())
+		});
+	}
+
 	fn unwatch_extrinsic(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
 		Ok(self.subscriptions.cancel(id))
 	}
+
+	fn unwatch_extrinsic_by_hash(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
+
+/// Whether the given status is `Finalized`.
+fn matches_finalized<H, BH>(status: &TransactionStatus<H, BH>) -> bool {
+	match status {
+		TransactionStatus::Finalized(_) => true,
+		_ => false,
+	}
 }
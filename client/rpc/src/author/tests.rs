@@ -64,6 +64,7 @@ impl Default for TestSetup {
 		let pool = Arc::new(BasicPool::new(
 			Default::default(),
 			Arc::new(FullChainApi::new(client.clone())),
+			None,
 		));
 		TestSetup {
 			runtime: runtime::Runtime::new().expect("Failed to create runtime in test setup"),
@@ -74,13 +75,31 @@ impl Default for TestSetup {
 	}
 }
 
+/// Transaction index including every transaction in the genesis block.
+struct GenesisIndex(H256);
+
+impl TransactionIndex<Block> for GenesisIndex {
+	fn inclusions(&self, _hash: &H256) -> Vec<(u64, H256, u32)> {
+		vec![(0, self.0, 0)]
+	}
+}
+
 impl TestSetup {
 	fn author(&self) -> Author<Backend, Executor, FullTransactionPool, Block, RuntimeApi> {
+		self.author_with_policy(DenyUnsafe::No)
+	}
+
+	fn author_with_policy(
+		&self,
+		deny_unsafe: DenyUnsafe,
+	) -> Author<Backend, Executor, FullTransactionPool, Block, RuntimeApi> {
 		Author {
 			client: self.client.clone(),
 			pool: self.pool.clone(),
 			subscriptions: Subscriptions::new(Arc::new(self.runtime.executor())),
 			keystore: self.keystore.clone(),
+			deny_unsafe,
+			transaction_index: None,
 		}
 	}
 }
@@ -151,6 +170,108 @@ fn should_watch_extrinsic() {
 	);
 }
 
+#[test]
+fn should_watch_extrinsic_by_hash() {
+	//given
+	let mut setup = TestSetup::default();
+	let p = setup.author();
+	let hash = AuthorApi::submit_extrinsic(&p, uxt(AccountKeyring::Alice, 0).encode().into()).wait().unwrap();
+
+	let (subscriber, id_rx, data) = jsonrpc_pubsub::typed::Subscriber::new_test("test");
+
+	// when
+	p.watch_extrinsic_by_hash(Default::default(), subscriber, hash);
+
+	// then
+	assert_eq!(setup.runtime.block_on(id_rx), Ok(Ok(1.into())));
+	let replacement = {
+		let tx = Transfer {
+			amount: 5,
+			nonce: 0,
+			from: AccountKeyring::Alice.into(),
+			to: Default::default(),
+		};
+		tx.into_signed_tx()
+	};
+	AuthorApi::submit_extrinsic(&p, replacement.encode().into()).wait().unwrap();
+	let (res, data) = setup.runtime.block_on(data.into_future()).unwrap();
+	assert_eq!(
+		res,
+		Some(r#"{"jsonrpc":"2.0","method":"test","params":{"result":"ready","subscription":1}}"#.into())
+	);
+	let h = blake2_256(&replacement.encode());
+	assert_eq!(
+		setup.runtime.block_on(data.into_future()).unwrap().0,
+		Some(format!(r#"{{"jsonrpc":"2.0","method":"test","params":{{"result":{{"usurped":"0x{}"}},"subscription":1}}}}"#, HexDisplay::from(&h)))
+	);
+	assert_matches!(
+		p.transaction_status(hash),
+		Ok(Some(TransactionStatusInfo { status: ExtrinsicStatus::Usurped(usurper), inclusion: None }))
+			if usurper == h.into()
+	);
+}
+
+#[test]
+fn should_reject_watching_unknown_extrinsic() {
+	//given
+	let mut setup = TestSetup::default();
+	let p = setup.author();
+
+	let (subscriber, id_rx, _data) = jsonrpc_pubsub::typed::Subscriber::new_test("test");
+
+	// when
+	p.watch_extrinsic_by_hash(Default::default(), subscriber, H256::repeat_byte(1));
+
+	// then
+	let res = setup.runtime.block_on(id_rx).unwrap();
+	assert!(res.is_err(), "Expected the unknown transaction to be rejected.");
+}
+
+#[test]
+fn should_return_transaction_status() {
+	let p = TestSetup::default().author();
+
+	let hash = AuthorApi::submit_extrinsic(&p, uxt(AccountKeyring::Alice, 0).encode().into()).wait().unwrap();
+
+	assert_eq!(
+		p.transaction_status(hash).unwrap(),
+		Some(TransactionStatusInfo { status: ExtrinsicStatus::Ready, inclusion: None }),
+	);
+	assert_eq!(p.transaction_status(H256::repeat_byte(1)).unwrap(), None);
+}
+
+#[test]
+fn should_return_status_of_indexed_transaction() {
+	//given
+	let mut setup = TestSetup::default();
+	let genesis_hash = setup.client.chain_info().genesis_hash;
+	let mut p = setup.author();
+	p.transaction_index = Some(Arc::new(GenesisIndex(genesis_hash)));
+	let hash = H256::repeat_byte(1);
+
+	// when
+	let status = p.transaction_status(hash).unwrap();
+	let (subscriber, id_rx, data) = jsonrpc_pubsub::typed::Subscriber::new_test("test");
+	p.watch_extrinsic_by_hash(Default::default(), subscriber, hash);
+
+	// then
+	assert_eq!(
+		status,
+		Some(TransactionStatusInfo {
+			status: ExtrinsicStatus::Finalized(genesis_hash),
+			inclusion: Some(Inclusion { block_hash: genesis_hash, index: 0 }),
+		}),
+	);
+	assert_eq!(setup.runtime.block_on(id_rx), Ok(Ok(1.into())));
+	assert_eq!(
+		setup.runtime.block_on(data.into_future()).unwrap().0,
+		Some(format!(
+			r#"{{"jsonrpc":"2.0","method":"test","params":{{"result":{{"finalized":"0x{}"}},"subscription":1}}}}"#,
+			HexDisplay::from(&genesis_hash.0),
+		))
+	);
+}
+
 #[test]
 fn should_return_watch_validation_error() {
 	//given
@@ -221,6 +342,25 @@ fn should_insert_key() {
 	assert_eq!(key_pair.public(), store_key_pair.public());
 }
 
+#[test]
+fn should_deny_unsafe_calls() {
+	let setup = TestSetup::default();
+	let p = setup.author_with_policy(DenyUnsafe::Yes);
+
+	let suri = "//Alice";
+	let key_pair = ed25519::Pair::from_string(suri, None).expect("Generates keypair");
+	assert_matches!(
+		p.insert_key(
+			String::from_utf8(ED25519.0.to_vec()).expect("Keytype is a valid string"),
+			suri.to_string(),
+			key_pair.public().0.to_vec().into(),
+		),
+		Err(Error::UnsafeRpcCalled(_))
+	);
+	assert_matches!(p.rotate_keys(), Err(Error::UnsafeRpcCalled(_)));
+	assert!(setup.keystore.read().ed25519_key_pair(ED25519, &key_pair.public()).is_none());
+}
+
 #[test]
 fn should_rotate_keys() {
 	let setup = TestSetup::default();
@@ -0,0 +1,514 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Follow-chain subscription.
+//!
+//! Reports the block tree as it evolves and keeps every reported block pinned in the
+//! backend, so that its state can still be queried, until the subscriber releases it.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak, atomic::{AtomicUsize, Ordering}};
+use std::task::Poll;
+use futures::{future, stream as stream03, StreamExt, TryStreamExt};
+use log::{debug, warn};
+use parking_lot::Mutex;
+use rpc::futures::{Future, Sink};
+
+use sc_rpc_api::Subscriptions;
+use sc_client::{Client, BlockchainEvents};
+use sc_client_api::{BlockImportNotification, FinalityNotification};
+use jsonrpc_pubsub::{typed::Subscriber, Session, SubscriptionId};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header, NumberFor},
+};
+
+use super::{client_err, error::{Error, Result}, FollowEvent};
+
+/// Maximal number of blocks a single subscription may keep pinned.
+///
+/// A subscriber that doesn't release blocks in time is stopped.
+const MAX_PINNED_BLOCKS: usize = 512;
+
+/// Maximal number of blocks all the subscriptions together may keep pinned.
+///
+/// The subscriber whose block would exceed it is stopped.
+const MAX_TOTAL_PINNED_BLOCKS: usize = 4096;
+
+/// Backend block pinning, as an object so that pins can be released on drop.
+trait PinBlocks<Block: BlockT>: Send + Sync {
+	fn pin_block(&self, hash: &Block::Hash) -> sp_blockchain::Result<()>;
+	fn unpin_block(&self, hash: &Block::Hash);
+}
+
+impl<B, E, Block, RA> PinBlocks<Block> for Client<B, E, Block, RA> where
+	Block: BlockT + 'static,
+	B: sc_client_api::backend::Backend<Block> + Send + Sync + 'static,
+	E: sc_client::CallExecutor<Block> + Send + Sync + 'static,
+	RA: Send + Sync + 'static,
+{
+	fn pin_block(&self, hash: &Block::Hash) -> sp_blockchain::Result<()> {
+		Client::pin_block(self, hash)
+	}
+
+	fn unpin_block(&self, hash: &Block::Hash) {
+		Client::unpin_block(self, hash)
+	}
+}
+
+/// Blocks pinned by a follow-chain subscription.
+///
+/// All the blocks that are still pinned are released when this is dropped.
+struct PinnedBlocks<Block: BlockT> {
+	client: Arc<dyn PinBlocks<Block>>,
+	hashes: HashSet<Block::Hash>,
+	/// Number of blocks pinned by all the subscriptions.
+	total: Arc<AtomicUsize>,
+}
+
+impl<Block: BlockT> PinnedBlocks<Block> {
+	/// Whether no more blocks may be pinned.
+	fn is_full(&self) -> bool {
+		self.hashes.len() >= MAX_PINNED_BLOCKS ||
+			self.total.load(Ordering::Acquire) >= MAX_TOTAL_PINNED_BLOCKS
+	}
+
+	/// Pin the given block. Returns false if it isn't pinned, either because the pinned
+	/// blocks limit is reached or because its state is already gone.
+	fn pin(&mut self, hash: Block::Hash) -> bool {
+		if self.hashes.contains(&hash) {
+			return true;
+		}
+		if self.hashes.len() >= MAX_PINNED_BLOCKS {
+			return false;
+		}
+		if self.total.fetch_add(1, Ordering::AcqRel) >= MAX_TOTAL_PINNED_BLOCKS {
+			self.total.fetch_sub(1, Ordering::AcqRel);
+			return false;
+		}
+		match self.client.pin_block(&hash) {
+			Ok(()) => {
+				self.hashes.insert(hash);
+				true
+			},
+			Err(e) => {
+				debug!("Failed to pin block {:?}: {:?}", hash, e);
+				self.total.fetch_sub(1, Ordering::AcqRel);
+				false
+			},
+		}
+	}
+
+	/// Release the given blocks. Fails without releasing anything if one of them isn't pinned.
+	fn unpin(&mut self, hashes: &[Block::Hash]) -> Result<()> {
+		if let Some(hash) = hashes.iter().find(|hash| !self.hashes.contains(hash)) {
+			return Err(Error::Other(format!("Block {:?} is not pinned", hash)));
+		}
+		for hash in hashes {
+			if self.hashes.remove(hash) {
+				self.client.unpin_block(hash);
+				self.total.fetch_sub(1, Ordering::AcqRel);
+			}
+		}
+		Ok(())
+	}
+
+	/// Release all the pinned blocks.
+	fn clear(&mut self) {
+		for hash in self.hashes.drain() {
+			self.client.unpin_block(&hash);
+			self.total.fetch_sub(1, Ordering::AcqRel);
+		}
+	}
+}
+
+impl<Block: BlockT> Drop for PinnedBlocks<Block> {
+	fn drop(&mut self) {
+		self.clear();
+	}
+}
+
+/// An active follow-chain subscription.
+struct Follower<Block: BlockT> {
+	/// The session the subscription was opened in, the only one allowed to manage it.
+	session: Option<Weak<Session>>,
+	pinned: Weak<Mutex<PinnedBlocks<Block>>>,
+}
+
+impl<Block: BlockT> Follower<Block> {
+	fn is_owned_by(&self, session: Option<Arc<Session>>) -> bool {
+		match (self.session.as_ref(), session.as_ref()) {
+			(Some(owner), Some(session)) => Weak::ptr_eq(owner, &Arc::downgrade(session)),
+			(None, None) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Pinned blocks of the active follow-chain subscriptions, by subscription id.
+///
+/// The subscription task owns its pinned blocks, so they are released as soon as
+/// the task ends, whatever the reason.
+pub(super) struct Followers<Block: BlockT> {
+	followers: Mutex<HashMap<SubscriptionId, Follower<Block>>>,
+	/// Number of blocks pinned by all the subscriptions.
+	total_pinned: Arc<AtomicUsize>,
+}
+
+impl<Block: BlockT> Followers<Block> {
+	pub(super) fn new() -> Self {
+		Followers {
+			followers: Mutex::new(HashMap::new()),
+			total_pinned: Arc::new(AtomicUsize::new(0)),
+		}
+	}
+
+	fn insert(
+		&self,
+		id: SubscriptionId,
+		session: Option<Arc<Session>>,
+		pinned: &Arc<Mutex<PinnedBlocks<Block>>>,
+	) {
+		let mut followers = self.followers.lock();
+		followers.retain(|_, follower| follower.pinned.upgrade().is_some());
+		followers.insert(id, Follower {
+			session: session.as_ref().map(Arc::downgrade),
+			pinned: Arc::downgrade(pinned),
+		});
+	}
+
+	/// Release the given blocks pinned by the subscription `id` opened in `session`.
+	pub(super) fn unpin(
+		&self,
+		session: Option<Arc<Session>>,
+		id: &SubscriptionId,
+		hashes: &[Block::Hash],
+	) -> Result<()> {
+		let pinned = self.followers.lock().get(id)
+			.filter(|follower| follower.is_owned_by(session))
+			.and_then(|follower| follower.pinned.upgrade());
+		pinned.ok_or(Error::UnknownSubscription)?.lock().unpin(hashes)
+	}
+
+	/// Whether the subscription `id` has been opened in `session`.
+	pub(super) fn owns(&self, session: Option<Arc<Session>>, id: &SubscriptionId) -> bool {
+		self.followers.lock().get(id).map_or(false, |follower| follower.is_owned_by(session))
+	}
+
+	/// Forget the subscription `id`, releasing its pinned blocks right away.
+	pub(super) fn remove(&self, id: &SubscriptionId) {
+		let pinned = self.followers.lock().remove(id).and_then(|follower| follower.pinned.upgrade());
+		if let Some(pinned) = pinned {
+			pinned.lock().clear();
+		}
+	}
+}
+
+/// A block notification of the client.
+enum Notification<Block: BlockT> {
+	Import(BlockImportNotification<Block>),
+	Finality(FinalityNotification<Block>),
+}
+
+/// Tracks the blocks reported to a follow-chain subscriber.
+struct ChainFollower<B, E, Block: BlockT, RA> {
+	client: Arc<Client<B, E, Block, RA>>,
+	pinned: Arc<Mutex<PinnedBlocks<Block>>>,
+	/// Reported blocks that are neither finalized nor pruned, with their number and parent.
+	live: HashMap<Block::Hash, (NumberFor<Block>, Block::Hash)>,
+	/// Hash and number of the last reported finalized block.
+	finalized: (Block::Hash, NumberFor<Block>),
+	/// Last reported best block.
+	best: Block::Hash,
+	/// Whether the `Stop` event has been sent.
+	stopped: bool,
+}
+
+impl<B, E, Block, RA> ChainFollower<B, E, Block, RA> where
+	Block: BlockT + 'static,
+	B: sc_client_api::backend::Backend<Block> + Send + Sync + 'static,
+	E: sc_client::CallExecutor<Block> + Send + Sync + 'static,
+{
+	/// Create a follower, returning it along with the events describing the current chain.
+	fn new(
+		client: Arc<Client<B, E, Block, RA>>,
+		pinned: Arc<Mutex<PinnedBlocks<Block>>>,
+	) -> Result<(Self, Vec<FollowEvent<Block::Hash>>)> {
+		let info = client.chain_info();
+		let mut follower = ChainFollower {
+			client,
+			pinned,
+			live: HashMap::new(),
+			finalized: (info.finalized_hash, info.finalized_number),
+			best: info.finalized_hash,
+			stopped: false,
+		};
+
+		let mut events = Vec::new();
+		let pinned = {
+			let mut pinned = follower.pinned.lock();
+			!pinned.is_full() && pinned.pin(info.finalized_hash)
+		};
+		if !pinned {
+			follower.stop(&mut events);
+			return Ok((follower, events));
+		}
+		events.push(FollowEvent::Initialized { finalized_block_hash: info.finalized_hash });
+
+		// light clients don't track the leaves, only the best chain is reported to them.
+		let leaves = follower.client.leaves().unwrap_or_else(|_| vec![info.best_hash]);
+		let mut blocks = Vec::new();
+		for leaf in leaves {
+			let mut branch = Vec::new();
+			let mut header = follower.header(leaf)?;
+			while *header.number() > info.finalized_number {
+				let hash = header.hash();
+				let parent = *header.parent_hash();
+				branch.push((*header.number(), hash, parent));
+				header = follower.header(parent)?;
+			}
+			// skip the forks of finalized blocks.
+			if header.hash() == info.finalized_hash {
+				blocks.extend(branch);
+			}
+		}
+		blocks.sort();
+		blocks.dedup();
+		for (number, hash, parent) in blocks {
+			follower.report_block(hash, number, parent, &mut events);
+			if follower.stopped {
+				return Ok((follower, events));
+			}
+		}
+
+		follower.report_best(info.best_hash, &mut events);
+		Ok((follower, events))
+	}
+
+	fn header(&self, hash: Block::Hash) -> Result<Block::Header> {
+		self.client.header(&BlockId::Hash(hash))
+			.map_err(client_err)?
+			.ok_or_else(|| Error::Other(format!("Header of {:?} is missing", hash)))
+	}
+
+	/// Process a client notification, returning the events to report.
+	fn on_notification(&mut self, notification: Notification<Block>) -> Vec<FollowEvent<Block::Hash>> {
+		let mut events = Vec::new();
+		if self.stopped {
+			return events;
+		}
+
+		match notification {
+			Notification::Import(notification) => {
+				let header = notification.header;
+				let reported = self.report_block(
+					notification.hash,
+					*header.number(),
+					*header.parent_hash(),
+					&mut events,
+				);
+				if reported && notification.is_new_best {
+					self.report_best(notification.hash, &mut events);
+				}
+			},
+			Notification::Finality(notification) => {
+				if let Err(e) = self.report_finalized(notification.hash, notification.header, &mut events) {
+					warn!("Failed to report finalized block {:?}: {:?}", notification.hash, e);
+				}
+			},
+		}
+		events
+	}
+
+	/// Report a new block, unless it is already reported, its parent is unknown or it can't be
+	/// pinned. The subscription is stopped if no more blocks may be pinned.
+	///
+	/// Returns whether the block is reported.
+	fn report_block(
+		&mut self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+		parent: Block::Hash,
+		events: &mut Vec<FollowEvent<Block::Hash>>,
+	) -> bool {
+		if hash == self.finalized.0 || self.live.contains_key(&hash) {
+			return true;
+		}
+		// children of pruned, unknown or unpinnable blocks are never reported either.
+		if parent != self.finalized.0 && !self.live.contains_key(&parent) {
+			return false;
+		}
+		if self.pinned.lock().is_full() {
+			self.stop(events);
+			return false;
+		}
+		// the state of the block is already gone.
+		if !self.pinned.lock().pin(hash) {
+			return false;
+		}
+		self.live.insert(hash, (number, parent));
+		events.push(FollowEvent::NewBlock { block_hash: hash, parent_block_hash: parent });
+		true
+	}
+
+	/// Report the best block if it has changed and is already reported.
+	fn report_best(&mut self, hash: Block::Hash, events: &mut Vec<FollowEvent<Block::Hash>>) {
+		if hash != self.best && (hash == self.finalized.0 || self.live.contains_key(&hash)) {
+			self.best = hash;
+			events.push(FollowEvent::BestBlockChanged { best_block_hash: hash });
+		}
+	}
+
+	/// Report the finalization of a block along with the forks it prunes.
+	fn report_finalized(
+		&mut self,
+		hash: Block::Hash,
+		header: Block::Header,
+		events: &mut Vec<FollowEvent<Block::Hash>>,
+	) -> Result<()> {
+		let number = *header.number();
+		if number <= self.finalized.1 {
+			return Ok(());
+		}
+
+		// the finality notification may precede the import notifications of the block
+		// and its ancestors, so they are reported first.
+		let mut unreported = Vec::new();
+		let mut current = header;
+		while current.hash() != self.finalized.0 && !self.live.contains_key(&current.hash()) {
+			if *current.number() <= self.finalized.1 {
+				return Err(Error::Other(format!("Block {:?} is not a descendant of the last finalized block", hash)));
+			}
+			let parent = *current.parent_hash();
+			unreported.push((current.hash(), *current.number(), parent));
+			current = self.header(parent)?;
+		}
+		for (hash, number, parent) in unreported.into_iter().rev() {
+			// the finalized chain can't be reported without this block.
+			if !self.report_block(hash, number, parent, events) {
+				if !self.stopped {
+					self.stop(events);
+				}
+				return Ok(());
+			}
+		}
+
+		let mut finalized = Vec::new();
+		let mut current = hash;
+		while current != self.finalized.0 {
+			let (_, parent) = self.live.remove(&current)
+				.expect("the route to the last finalized block has been reported above; qed");
+			finalized.push(current);
+			current = parent;
+		}
+		finalized.reverse();
+		self.finalized = (hash, number);
+
+		let live = &self.live;
+		let is_descendant = |block: &Block::Hash| {
+			let mut current = *block;
+			loop {
+				if current == hash {
+					return true;
+				}
+				match live.get(&current) {
+					Some((_, parent)) => current = *parent,
+					None => return false,
+				}
+			}
+		};
+		let pruned = live.keys().filter(|block| !is_descendant(block)).cloned().collect::<Vec<_>>();
+		for block in &pruned {
+			self.live.remove(block);
+		}
+
+		events.push(FollowEvent::Finalized {
+			finalized_block_hashes: finalized,
+			pruned_block_hashes: pruned,
+		});
+
+		// finalizing a block outside of the best chain resets the best block.
+		let best_hash = self.client.chain_info().best_hash;
+		self.report_best(best_hash, events);
+		Ok(())
+	}
+
+	/// Stop the subscription, releasing all the pinned blocks.
+	fn stop(&mut self, events: &mut Vec<FollowEvent<Block::Hash>>) {
+		self.stopped = true;
+		self.pinned.lock().clear();
+		events.push(FollowEvent::Stop);
+	}
+}
+
+/// Subscribe to the evolution of the chain.
+pub(super) fn follow_chain<B, E, Block, RA>(
+	client: &Arc<Client<B, E, Block, RA>>,
+	subscriptions: &Subscriptions,
+	followers: &Followers<Block>,
+	session: Option<Arc<Session>>,
+	subscriber: Subscriber<FollowEvent<Block::Hash>>,
+) where
+	Block: BlockT + 'static,
+	B: sc_client_api::backend::Backend<Block> + Send + Sync + 'static,
+	E: sc_client::CallExecutor<Block> + Send + Sync + 'static,
+	RA: Send + Sync + 'static,
+{
+	let pinned = Arc::new(Mutex::new(PinnedBlocks {
+		client: client.clone() as Arc<dyn PinBlocks<Block>>,
+		hashes: HashSet::new(),
+		total: followers.total_pinned.clone(),
+	}));
+	let id = subscriptions.add(subscriber, |sink| {
+		// subscribe before reading the chain, so that no block is missed.
+		let mut imports = client.import_notification_stream();
+		let mut finality = client.finality_notification_stream();
+		// the pending import notifications are processed first, so that blocks are
+		// reported before a concurrent finalization prunes their fork.
+		let notifications = stream03::poll_fn(move |cx| {
+			match imports.poll_next_unpin(cx) {
+				Poll::Ready(notification) => Poll::Ready(notification.map(Notification::Import)),
+				Poll::Pending => finality.poll_next_unpin(cx).map(|n| n.map(Notification::Finality)),
+			}
+		});
+
+		let (follower, initial) = match ChainFollower::new(client.clone(), pinned.clone()) {
+			Ok((follower, events)) => (Some(follower), events.into_iter().map(Ok).collect()),
+			Err(e) => (None, vec![Err(e.into())]),
+		};
+
+		let events = notifications
+			.scan(follower, |follower, notification| future::ready(match follower {
+				Some(follower) if !follower.stopped => Some(follower.on_notification(notification)),
+				_ => None,
+			}))
+			.map(|events| stream03::iter(events.into_iter().map(Ok)))
+			.flatten();
+
+		sink
+			.sink_map_err(|e| warn!("Error sending notifications: {:?}", e))
+			.send_all(
+				stream03::iter(initial)
+					.chain(events)
+					.map(Ok::<_, ()>)
+					.compat()
+			)
+			// we ignore the resulting Stream (if the first stream is over we are unsubscribed)
+			.map(|_| ())
+	});
+
+	followers.insert(id, session, &pinned);
+}
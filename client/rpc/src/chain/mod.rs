@@ -18,6 +18,7 @@
 
 mod chain_full;
 mod chain_light;
+mod follow;
 
 #[cfg(test)]
 mod tests;
@@ -30,12 +31,12 @@ use rpc::{
 	futures::{stream, Future, Sink, Stream},
 };
 
-use sc_rpc_api::Subscriptions;
+use sc_rpc_api::{DenyUnsafe, Subscriptions};
 use sc_client::{
 	self, Client, BlockchainEvents,
 	light::{fetcher::Fetcher, blockchain::RemoteBlockchain},
 };
-use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, PubSubMetadata};
 use sp_rpc::{number::NumberOrHex, list::ListOrValue};
 use sp_runtime::{
 	generic::{BlockId, SignedBlock},
@@ -45,6 +46,31 @@ use sp_runtime::{
 use self::error::{Result, Error, FutureResult};
 
 pub use sc_rpc_api::chain::*;
+use sc_rpc_api::chain::{
+	decoded::{DecodedBlock, DecodedEvents},
+	events::{EventFilter, EventPage},
+	follow::FollowEvent,
+};
+
+/// Index of the events of the canon chain blocks.
+pub trait EventIndex<Block: BlockT>: Send + Sync {
+	/// Query the events matching `filter` in the blocks `from..=to`.
+	fn query_events(
+		&self,
+		filter: &EventFilter<Block::Hash>,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> Result<EventPage<NumberFor<Block>, Block::Hash>>;
+}
+
+/// Decoding of blocks and events using the metadata of the runtime.
+pub trait RuntimeDecoder<Block: BlockT>: Send + Sync {
+	/// Decode the extrinsics of the given block, `None` if the block is unknown.
+	fn decode_block(&self, hash: &Block::Hash) -> Result<Option<DecodedBlock<Block::Header>>>;
+
+	/// Decode the events of the given block, `None` if the block is unknown.
+	fn decode_events(&self, hash: &Block::Hash) -> Result<Option<DecodedEvents<Block::Hash>>>;
+}
 
 /// Blockchain backend API
 trait ChainBackend<B, E, Block: BlockT, RA>: Send + Sync + 'static
@@ -149,9 +175,14 @@ trait ChainBackend<B, E, Block: BlockT, RA>: Send + Sync + 'static
 }
 
 /// Create new state API that works on full node.
+///
+/// Following the chain is refused if `deny_unsafe` is set.
 pub fn new_full<B, E, Block: BlockT, RA>(
 	client: Arc<Client<B, E, Block, RA>>,
 	subscriptions: Subscriptions,
+	event_index: Option<Arc<dyn EventIndex<Block>>>,
+	decoder: Option<Arc<dyn RuntimeDecoder<Block>>>,
+	deny_unsafe: DenyUnsafe,
 ) -> Chain<B, E, Block, RA>
 	where
 		Block: BlockT + 'static,
@@ -161,15 +192,22 @@ pub fn new_full<B, E, Block: BlockT, RA>(
 {
 	Chain {
 		backend: Box::new(self::chain_full::FullChain::new(client, subscriptions)),
+		followers: follow::Followers::new(),
+		event_index,
+		decoder,
+		deny_unsafe,
 	}
 }
 
 /// Create new state API that works on light node.
+///
+/// Following the chain is refused if `deny_unsafe` is set.
 pub fn new_light<B, E, Block: BlockT, RA, F: Fetcher<Block>>(
 	client: Arc<Client<B, E, Block, RA>>,
 	subscriptions: Subscriptions,
 	remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
 	fetcher: Arc<F>,
+	deny_unsafe: DenyUnsafe,
 ) -> Chain<B, E, Block, RA>
 	where
 		Block: BlockT + 'static,
@@ -185,12 +223,20 @@ pub fn new_light<B, E, Block: BlockT, RA, F: Fetcher<Block>>(
 			remote_blockchain,
 			fetcher,
 		)),
+		followers: follow::Followers::new(),
+		event_index: None,
+		decoder: None,
+		deny_unsafe,
 	}
 }
 
 /// Chain API with subscriptions support.
 pub struct Chain<B, E, Block: BlockT, RA> {
 	backend: Box<dyn ChainBackend<B, E, Block, RA>>,
+	followers: follow::Followers<Block>,
+	event_index: Option<Arc<dyn EventIndex<Block>>>,
+	decoder: Option<Arc<dyn RuntimeDecoder<Block>>>,
+	deny_unsafe: DenyUnsafe,
 }
 
 impl<B, E, Block, RA> ChainApi<NumberFor<Block>, Block::Hash, Block::Header, SignedBlock<Block>> for Chain<B, E, Block, RA> where
@@ -210,6 +256,16 @@ impl<B, E, Block, RA> ChainApi<NumberFor<Block>, Block::Hash, Block::Header, Sig
 		self.backend.block(hash)
 	}
 
+	fn decoded_block(&self, hash: Option<Block::Hash>) -> Result<Option<DecodedBlock<Block::Header>>> {
+		let decoder = self.decoder.as_ref().ok_or(Error::DecodingUnavailable)?;
+		decoder.decode_block(&self.backend.unwrap_or_best(hash))
+	}
+
+	fn decoded_events(&self, hash: Option<Block::Hash>) -> Result<Option<DecodedEvents<Block::Hash>>> {
+		let decoder = self.decoder.as_ref().ok_or(Error::DecodingUnavailable)?;
+		decoder.decode_events(&self.backend.unwrap_or_best(hash))
+	}
+
 	fn block_hash(
 		&self,
 		number: Option<ListOrValue<NumberOrHex<NumberFor<Block>>>>
@@ -225,6 +281,21 @@ impl<B, E, Block, RA> ChainApi<NumberFor<Block>, Block::Hash, Block::Header, Sig
 		}
 	}
 
+	fn query_events(
+		&self,
+		filter: EventFilter<Block::Hash>,
+		from: NumberOrHex<NumberFor<Block>>,
+		to: Option<NumberOrHex<NumberFor<Block>>>,
+	) -> Result<EventPage<NumberFor<Block>, Block::Hash>> {
+		let event_index = self.event_index.as_ref().ok_or(Error::EventIndexUnavailable)?;
+		let from = from.to_number()?;
+		let to = match to {
+			Some(to) => to.to_number()?,
+			None => self.backend.client().chain_info().best_number,
+		};
+		event_index.query_events(&filter, from, to)
+	}
+
 	fn finalized_head(&self) -> Result<Block::Hash> {
 		self.backend.finalized_head()
 	}
@@ -244,6 +315,41 @@ impl<B, E, Block, RA> ChainApi<NumberFor<Block>, Block::Hash, Block::Header, Sig
 	fn unsubscribe_finalized_heads(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
 		self.backend.unsubscribe_finalized_heads(metadata, id)
 	}
+
+	fn follow_chain(&self, metadata: Self::Metadata, subscriber: Subscriber<FollowEvent<Block::Hash>>) {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			let _ = subscriber.reject(Error::from(err).into());
+			return;
+		}
+		follow::follow_chain(
+			self.backend.client(),
+			self.backend.subscriptions(),
+			&self.followers,
+			metadata.session(),
+			subscriber,
+		)
+	}
+
+	fn unfollow_chain(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> RpcResult<bool> {
+		// without metadata, the subscription is ended by its own session closing.
+		if let Some(metadata) = metadata {
+			if !self.followers.owns(metadata.session(), &id) {
+				return Ok(false);
+			}
+		}
+		self.followers.remove(&id);
+		Ok(self.backend.subscriptions().cancel(id))
+	}
+
+	fn unpin(
+		&self,
+		metadata: Self::Metadata,
+		subscription: u64,
+		hashes: Vec<Block::Hash>,
+	) -> Result<()> {
+		self.deny_unsafe.check_if_safe()?;
+		self.followers.unpin(metadata.session(), &SubscriptionId::Number(subscription), &hashes)
+	}
 }
 
 /// Subscribe to new headers.
@@ -18,7 +18,7 @@ use super::*;
 use assert_matches::assert_matches;
 use substrate_test_runtime_client::{
 	prelude::*,
-	sp_consensus::BlockOrigin,
+	sp_consensus::{BlockOrigin, BlockImport, BlockImportParams, ForkChoiceStrategy},
 	runtime::{H256, Block, Header},
 };
 use sp_rpc::list::ListOrValue;
@@ -29,7 +29,7 @@ fn should_return_header() {
 	let remote = core.executor();
 
 	let client = Arc::new(substrate_test_runtime_client::new());
-	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)));
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
 
 	assert_matches!(
 		api.header(Some(client.genesis_hash()).into()).wait(),
@@ -64,7 +64,7 @@ fn should_return_a_block() {
 	let remote = core.executor();
 
 	let mut client = Arc::new(substrate_test_runtime_client::new());
-	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)));
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
 
 	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
 	let block_hash = block.hash();
@@ -118,7 +118,7 @@ fn should_return_block_hash() {
 	let remote = core.executor();
 
 	let mut client = Arc::new(substrate_test_runtime_client::new());
-	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)));
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
 
 	assert_matches!(
 		api.block_hash(None.into()),
@@ -165,7 +165,7 @@ fn should_return_finalized_hash() {
 	let remote = core.executor();
 
 	let mut client = Arc::new(substrate_test_runtime_client::new());
-	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)));
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
 
 	assert_matches!(
 		api.finalized_head(),
@@ -197,7 +197,7 @@ fn should_notify_about_latest_block() {
 
 	{
 		let mut client = Arc::new(substrate_test_runtime_client::new());
-		let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)));
+		let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
 
 		api.subscribe_new_heads(Default::default(), subscriber);
 
@@ -226,7 +226,7 @@ fn should_notify_about_finalized_block() {
 
 	{
 		let mut client = Arc::new(substrate_test_runtime_client::new());
-		let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)));
+		let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
 
 		api.subscribe_finalized_heads(Default::default(), subscriber);
 
@@ -247,3 +247,207 @@ fn should_notify_about_finalized_block() {
 	// no more notifications on this channel
 	assert_eq!(core.block_on(next.into_future()).unwrap().0, None);
 }
+
+#[test]
+fn should_follow_chain_and_release_pinned_blocks() {
+	let mut core = ::tokio::runtime::Runtime::new().unwrap();
+	let remote = core.executor();
+	let (subscriber, id, transport) = Subscriber::new_test("test");
+
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
+	let (_, session) = crate::metadata::Metadata::new_test();
+	let (_, other_session) = crate::metadata::Metadata::new_test();
+
+	api.follow_chain(session.clone(), subscriber);
+
+	// assert id assigned
+	assert_eq!(core.block_on(id), Ok(Ok(SubscriptionId::Number(1))));
+	let id = SubscriptionId::Number(1);
+
+	// assert the finalized block is reported right at the start.
+	let (notification, _next) = core.block_on(transport.into_future()).unwrap();
+	assert!(notification.unwrap().contains(r#""event":"initialized""#));
+
+	assert_matches!(
+		api.unpin(session.clone(), 2, vec![genesis_hash]),
+		Err(Error::UnknownSubscription)
+	);
+	// only the session which opened the subscription may manage it.
+	assert_matches!(
+		api.unpin(other_session.clone(), 1, vec![genesis_hash]),
+		Err(Error::UnknownSubscription)
+	);
+	assert_matches!(api.unfollow_chain(Some(other_session), id.clone()), Ok(false));
+
+	assert_matches!(api.unpin(session.clone(), 1, vec![genesis_hash]), Ok(()));
+	// blocks can only be released once.
+	assert_matches!(api.unpin(session.clone(), 1, vec![genesis_hash]), Err(Error::Other(_)));
+
+	assert_matches!(api.unfollow_chain(Some(session.clone()), id), Ok(true));
+	assert_matches!(api.unpin(session, 1, vec![]), Err(Error::UnknownSubscription));
+}
+
+#[test]
+fn should_deny_following_chain_if_unsafe() {
+	let mut core = ::tokio::runtime::Runtime::new().unwrap();
+	let remote = core.executor();
+	let (subscriber, id, _transport) = Subscriber::new_test("test");
+
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::Yes);
+	let (_, session) = crate::metadata::Metadata::new_test();
+
+	api.follow_chain(session.clone(), subscriber);
+	assert_matches!(core.block_on(id), Ok(Err(_)));
+	assert_matches!(
+		api.unpin(session, 1, vec![genesis_hash]),
+		Err(Error::UnsafeRpcCalled(_))
+	);
+}
+
+/// Import a block that doesn't change the state, without executing it.
+fn import_header(client: &mut Arc<TestClient>, parent_hash: H256, number: u64, fork: u8) -> H256 {
+	let parent = client.header(&BlockId::hash(parent_hash)).unwrap().unwrap();
+	let header = Header {
+		parent_hash,
+		number,
+		state_root: parent.state_root,
+		extrinsics_root: H256::repeat_byte(fork),
+		digest: Default::default(),
+	};
+	let hash = header.hash();
+	let import = BlockImportParams {
+		origin: BlockOrigin::Own,
+		header,
+		justification: None,
+		post_digests: vec![],
+		body: None,
+		storage_changes: Some(Default::default()),
+		finalized: false,
+		auxiliary: Vec::new(),
+		intermediates: Default::default(),
+		fork_choice: Some(ForkChoiceStrategy::LongestChain),
+		allow_missing_state: false,
+		import_existing: false,
+	};
+	client.import_block(import, Default::default()).unwrap();
+	hash
+}
+
+#[test]
+fn should_notify_about_followed_chain() {
+	let mut core = ::tokio::runtime::Runtime::new().unwrap();
+	let remote = core.executor();
+	let (subscriber, id, transport) = Subscriber::new_test("test");
+
+	let mut client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(remote)), None, None, DenyUnsafe::No);
+	let (_, session) = crate::metadata::Metadata::new_test();
+
+	api.follow_chain(session.clone(), subscriber);
+
+	// assert id assigned
+	assert_eq!(core.block_on(id), Ok(Ok(SubscriptionId::Number(1))));
+
+	let mut transport = Some(transport);
+	let mut expect = |events: Vec<FollowEvent<H256>>| for event in events {
+		let (notification, next) = core.block_on(transport.take().unwrap().into_future()).unwrap();
+		let notification: serde_json::Value = serde_json::from_str(&notification.unwrap()).unwrap();
+		assert_eq!(notification["params"]["result"], serde_json::to_value(&event).unwrap());
+		transport = Some(next);
+	};
+
+	let a1 = import_header(&mut client, genesis_hash, 1, 1);
+	let a2 = import_header(&mut client, a1, 2, 1);
+	let b1 = import_header(&mut client, genesis_hash, 1, 2);
+	// the blocks are pinned once reported, so that finalizing doesn't discard them before.
+	expect(vec![
+		FollowEvent::Initialized { finalized_block_hash: genesis_hash },
+		FollowEvent::NewBlock { block_hash: a1, parent_block_hash: genesis_hash },
+		FollowEvent::BestBlockChanged { best_block_hash: a1 },
+		FollowEvent::NewBlock { block_hash: a2, parent_block_hash: a1 },
+		FollowEvent::BestBlockChanged { best_block_hash: a2 },
+		FollowEvent::NewBlock { block_hash: b1, parent_block_hash: genesis_hash },
+	]);
+
+	client.finalize_block(BlockId::hash(a1), None).unwrap();
+	client.finalize_block(BlockId::hash(a2), None).unwrap();
+	expect(vec![
+		FollowEvent::Finalized { finalized_block_hashes: vec![a1], pruned_block_hashes: vec![b1] },
+		FollowEvent::Finalized { finalized_block_hashes: vec![a2], pruned_block_hashes: vec![] },
+	]);
+
+	// the subscription ends when unfollowing the chain.
+	assert_matches!(api.unfollow_chain(Some(session), SubscriptionId::Number(1)), Ok(true));
+	assert_eq!(core.block_on(transport.unwrap().into_future()).unwrap().0, None);
+}
+
+#[test]
+fn should_query_event_index() {
+	struct RangeIndex;
+
+	impl EventIndex<Block> for RangeIndex {
+		fn query_events(
+			&self,
+			filter: &EventFilter<H256>,
+			from: u64,
+			to: u64,
+		) -> Result<EventPage<u64, H256>> {
+			assert_eq!(filter.pallet.as_ref().map(|pallet| &pallet[..]), Some("Balances"));
+			Ok(EventPage { events: vec![], next: if from < to { Some(to) } else { None } })
+		}
+	}
+
+	let core = ::tokio::runtime::Runtime::new().unwrap();
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let filter = || EventFilter { pallet: Some("Balances".into()), ..Default::default() };
+
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(core.executor())), None, None, DenyUnsafe::No);
+	assert_matches!(api.query_events(filter(), 0u64.into(), None), Err(Error::EventIndexUnavailable));
+
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(core.executor())), Some(Arc::new(RangeIndex)), None, DenyUnsafe::No);
+	assert_matches!(api.query_events(filter(), 0u64.into(), None), Ok(EventPage { next: None, .. }));
+	assert_matches!(
+		api.query_events(filter(), 0u64.into(), Some(5u64.into())),
+		Ok(EventPage { next: Some(5), .. })
+	);
+}
+
+#[test]
+fn should_decode_blocks_and_events() {
+	struct HeaderDecoder(Arc<TestClient>);
+
+	impl RuntimeDecoder<Block> for HeaderDecoder {
+		fn decode_block(&self, hash: &H256) -> Result<Option<DecodedBlock<Header>>> {
+			Ok(self.0.header(&BlockId::Hash(*hash)).unwrap().map(|header| DecodedBlock {
+				header,
+				spec_version: 1,
+				extrinsics: vec![],
+			}))
+		}
+
+		fn decode_events(&self, _hash: &H256) -> Result<Option<DecodedEvents<H256>>> {
+			Ok(None)
+		}
+	}
+
+	let core = ::tokio::runtime::Runtime::new().unwrap();
+	let client = Arc::new(substrate_test_runtime_client::new());
+
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(core.executor())), None, None, DenyUnsafe::No);
+	assert_matches!(api.decoded_block(None), Err(Error::DecodingUnavailable));
+	assert_matches!(api.decoded_events(None), Err(Error::DecodingUnavailable));
+
+	let decoder = Arc::new(HeaderDecoder(client.clone()));
+	let api = new_full(client.clone(), Subscriptions::new(Arc::new(core.executor())), None, Some(decoder), DenyUnsafe::No);
+	assert_matches!(
+		api.decoded_block(None),
+		Ok(Some(ref block)) if block.header.hash() == client.genesis_hash()
+	);
+	assert_matches!(api.decoded_block(Some(H256::repeat_byte(1))), Ok(None));
+	assert_matches!(api.decoded_events(None), Ok(None));
+}
@@ -42,6 +42,10 @@ impl Default for Status {
 }
 
 fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
+	api_with_policy(sync, DenyUnsafe::No)
+}
+
+fn api_with_policy<T: Into<Option<Status>>>(sync: T, deny_unsafe: DenyUnsafe) -> System<Block> {
 	let status = sync.into().unwrap_or_default();
 	let should_have_peers = !status.is_dev;
 	let (tx, rx) = mpsc::unbounded();
@@ -105,7 +109,7 @@ fn api<T: Into<Option<Status>>>(sync: T) -> System<Block> {
 		impl_version: "0.2.0".into(),
 		chain_name: "testchain".into(),
 		properties: Default::default(),
-	}, tx)
+	}, tx, deny_unsafe)
 }
 
 fn wait_receiver<T>(rx: Receiver<T>) -> T {
@@ -113,6 +117,11 @@ fn wait_receiver<T>(rx: Receiver<T>) -> T {
 	runtime.block_on(rx).unwrap()
 }
 
+fn wait_future<T: Send + 'static>(fut: Compat<BoxFuture<'static, rpc::Result<T>>>) -> T {
+	let mut runtime = tokio::runtime::current_thread::Runtime::new().unwrap();
+	runtime.block_on(fut).unwrap()
+}
+
 #[test]
 fn system_name_works() {
 	assert_eq!(
@@ -203,7 +212,7 @@ fn system_health() {
 fn system_peers() {
 	let peer_id = PeerId::random();
 	assert_eq!(
-		wait_receiver(api(Status {
+		wait_future(api(Status {
 			peer_id: peer_id.clone(),
 			peers: 1,
 			is_syncing: false,
@@ -221,7 +230,7 @@ fn system_peers() {
 
 #[test]
 fn system_network_state() {
-	let res = wait_receiver(api(None).system_network_state());
+	let res = wait_future(api(None).system_network_state());
 	assert_eq!(
 		serde_json::from_value::<sc_network::NetworkState>(res).unwrap(),
 		sc_network::NetworkState {
@@ -268,3 +277,30 @@ fn system_network_remove_reserved() {
 	assert_eq!(runtime.block_on(good_fut), Ok(()));
 	assert!(runtime.block_on(bad_fut).is_err());
 }
+
+#[test]
+fn system_unsafe_calls_are_denied() {
+	let mut runtime = tokio::runtime::current_thread::Runtime::new().unwrap();
+	let api = api_with_policy(None, DenyUnsafe::Yes);
+
+	let peer_id = "QmSk5HQbn6LhUwDiNMseVUjuRYhEtYj4aUZ6WfWoGURpdV";
+	assert_matches!(
+		runtime.block_on(api.system_peers()),
+		Err(ref err) if err.code == rpc::ErrorCode::MethodNotFound
+	);
+	assert!(runtime.block_on(api.system_network_state()).is_err());
+	assert!(runtime.block_on(api.system_remove_reserved_peer(peer_id.into())).is_err());
+	assert_matches!(api.system_add_log_filter("sync=trace".into()), Err(Error::UnsafeRpcCalled(_)));
+	assert_matches!(api.system_reset_log_filter(), Err(Error::UnsafeRpcCalled(_)));
+	assert_eq!(api.system_name().unwrap(), "testclient".to_owned());
+}
+
+#[test]
+fn system_log_filter() {
+	assert_matches!(api(None).system_add_log_filter("sync=trace,afg=debug".into()), Ok(()));
+	assert_matches!(
+		api(None).system_add_log_filter("sync=loud".into()),
+		Err(Error::InvalidLogDirective(ref e)) if e.contains("sync=loud")
+	);
+	assert_matches!(api(None).system_reset_log_filter(), Ok(()));
+}
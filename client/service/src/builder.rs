@@ -16,7 +16,11 @@
 
 use crate::{Service, NetworkStatus, NetworkState, error::Error, DEFAULT_PROTOCOL_ID};
 use crate::{SpawnTaskHandle, start_rpc_servers, build_network_future, TransactionPoolAdapter};
-use crate::status_sinks;
+use crate::{health, status_sinks};
+use crate::event_index::{EventIndex, EventIndexer};
+use crate::runtime_decoder::RuntimeDecoder;
+use crate::transaction_index::{TransactionIndex, TransactionIndexer};
+use crate::metrics::ServiceMetrics;
 use crate::config::{Configuration, DatabaseConfig, KeystoreConfig};
 use sc_client_api::{
 	self,
@@ -53,6 +57,7 @@ use sc_telemetry::{telemetry, SUBSTRATE_INFO};
 use sp_transaction_pool::MaintainedTransactionPool;
 use sp_blockchain;
 use grafana_data_source::{self, record_metrics};
+use prometheus_endpoint::Registry;
 
 /// Aggregator for the components required to build a service.
 ///
@@ -219,6 +224,7 @@ fn new_full_parts<TBl, TRtApi, TExecDisp, TGen, TCSExt>(
 			fork_blocks,
 			bad_blocks,
 			extensions,
+			config.prometheus_config.as_ref().map(|config| config.registry.clone()),
 		)?
 	};
 
@@ -333,6 +339,7 @@ where TGen: RuntimeGenesis, TCSExt: Extension {
 			backend.clone(),
 			config.expect_chain_spec(),
 			executor,
+			config.prometheus_config.as_ref().map(|config| config.registry.clone()),
 		)?);
 
 		Ok(ServiceBuilder {
@@ -600,6 +607,7 @@ impl<TBl, TRtApi, TGen, TCSExt, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TNetP, TEx
 			sc_transaction_pool::txpool::Options,
 			Arc<TCl>,
 			Option<TFchr>,
+			Option<&Registry>,
 		) -> Result<UExPool, Error>
 	) -> Result<ServiceBuilder<TBl, TRtApi, TGen, TCSExt, TCl, TFchr, TSc, TImpQu, TFprb, TFpp,
 		TNetP, UExPool, TRpc, Backend>, Error>
@@ -608,6 +616,7 @@ impl<TBl, TRtApi, TGen, TCSExt, TCl, TFchr, TSc, TImpQu, TFprb, TFpp, TNetP, TEx
 			self.config.transaction_pool.clone(),
 			self.client.clone(),
 			self.fetcher.clone(),
+			self.config.prometheus_config.as_ref().map(|config| &config.registry),
 		)?;
 
 		Ok(ServiceBuilder {
@@ -852,6 +861,7 @@ ServiceBuilder<
 			protocol_id,
 			specialization: network_protocol,
 			block_announce_validator,
+			metrics_registry: config.prometheus_config.as_ref().map(|config| config.registry.clone()),
 		};
 
 		let has_bootnodes = !network_params.network_config.boot_nodes.is_empty();
@@ -871,6 +881,40 @@ ServiceBuilder<
 			_ => None,
 		};
 
+		let event_index = match (config.event_index, backend.event_index_storage()) {
+			(true, Some(storage)) if !config.roles.is_light() => {
+				let event_index = Arc::new(EventIndex::new(storage));
+				let indexer = EventIndexer::new(client.clone(), event_index.clone());
+				let _ = to_spawn_tx.unbounded_send((
+					Box::pin(select(Box::pin(indexer.run()), exit.clone()).map(drop)),
+					From::from("event-indexer")
+				));
+				Some(event_index)
+			},
+			(true, _) => {
+				warn!("Event index disabled, due to lack of event index storage support in backend.");
+				None
+			},
+			_ => None,
+		};
+
+		let transaction_index = match (config.transaction_index, backend.transaction_index_storage()) {
+			(true, Some(storage)) if !config.roles.is_light() => {
+				let transaction_index = Arc::new(TransactionIndex::new(storage));
+				let indexer = TransactionIndexer::new(client.clone(), transaction_index.clone());
+				let _ = to_spawn_tx.unbounded_send((
+					Box::pin(select(Box::pin(indexer.run()), exit.clone()).map(drop)),
+					From::from("transaction-indexer")
+				));
+				Some(transaction_index)
+			},
+			(true, _) => {
+				warn!("Transaction index disabled, due to lack of transaction index storage support in backend.");
+				None
+			},
+			_ => None,
+		};
+
 		{
 			// block notifications
 			let txpool = Arc::downgrade(&transaction_pool);
@@ -915,6 +959,22 @@ ServiceBuilder<
 			));
 		}
 
+		{
+			// finality notifications
+			let txpool = Arc::downgrade(&transaction_pool);
+			let events = client.finality_notification_stream()
+				.for_each(move |notification| {
+					if let Some(txpool) = txpool.upgrade() {
+						txpool.on_block_finalized(notification.hash);
+					}
+					ready(())
+				});
+			let _ = to_spawn_tx.unbounded_send((
+				Box::pin(select(events, exit.clone()).map(drop)),
+				From::from("txpool-finality-notif")
+			));
+		}
+
 		{
 			// extrinsic notifications
 			let network = Arc::downgrade(&network);
@@ -938,6 +998,11 @@ ServiceBuilder<
 			));
 		}
 
+		let metrics = match config.prometheus_config.as_ref() {
+			Some(config) => Some(ServiceMetrics::register(&config.registry)?),
+			None => None,
+		};
+
 		// Periodically notify the telemetry.
 		let transaction_pool_ = transaction_pool.clone();
 		let client_ = client.clone();
@@ -955,6 +1020,13 @@ ServiceBuilder<
 			let bandwidth_download = net_status.average_download_per_sec;
 			let bandwidth_upload = net_status.average_upload_per_sec;
 
+			if let Some(metrics) = metrics.as_ref() {
+				metrics.report_txpool(&txpool_status);
+				if let Some(usage) = info.usage.as_ref() {
+					metrics.report_usage(usage);
+				}
+			}
+
 			// get cpu usage and memory usage of this process
 			let (cpu_usage, memory) = if let Some(self_pid) = self_pid {
 				if sys.refresh_process(self_pid) {
@@ -1024,7 +1096,8 @@ ServiceBuilder<
 
 		// RPC
 		let (system_rpc_tx, system_rpc_rx) = mpsc::unbounded();
-		let gen_handler = || {
+		let runtime_decoder = Arc::new(RuntimeDecoder::new(client.clone()));
+		let gen_handler = |deny_unsafe: sc_rpc::DenyUnsafe| {
 			use sc_rpc::{chain, state, author, system, offchain};
 
 			let system_info = sc_rpc::system::SystemInfo {
@@ -1046,7 +1119,8 @@ ServiceBuilder<
 					client.clone(),
 					subscriptions.clone(),
 					remote_backend.clone(),
-					on_demand.clone()
+					on_demand.clone(),
+					deny_unsafe,
 				);
 				let state = sc_rpc::state::new_light(
 					client.clone(),
@@ -1058,7 +1132,13 @@ ServiceBuilder<
 
 			} else {
 				// Full nodes
-				let chain = sc_rpc::chain::new_full(client.clone(), subscriptions.clone());
+				let chain = sc_rpc::chain::new_full(
+					client.clone(),
+					subscriptions.clone(),
+					event_index.clone().map(|index| index as Arc<dyn chain::EventIndex<_>>),
+					Some(runtime_decoder.clone() as Arc<dyn chain::RuntimeDecoder<_>>),
+					deny_unsafe,
+				);
 				let state = sc_rpc::state::new_full(client.clone(), subscriptions.clone());
 				(chain, state)
 			};
@@ -1068,12 +1148,18 @@ ServiceBuilder<
 				transaction_pool.clone(),
 				subscriptions,
 				keystore.clone(),
+				deny_unsafe,
+				transaction_index.clone().map(|index| index as Arc<dyn author::TransactionIndex<_>>),
+			);
+			let system = system::System::new(system_info, system_rpc_tx.clone(), deny_unsafe);
+			let middleware = sc_rpc_server::RpcMiddleware::new(
+				config.rpc_max_response_size,
+				config.rpc_rate_limit,
 			);
-			let system = system::System::new(system_info, system_rpc_tx.clone());
 
 			match offchain_storage.clone() {
 				Some(storage) => {
-					let offchain = sc_rpc::offchain::Offchain::new(storage);
+					let offchain = sc_rpc::offchain::Offchain::new(storage, deny_unsafe);
 					sc_rpc_server::rpc_handler((
 						state::StateApi::to_delegate(state),
 						chain::ChainApi::to_delegate(chain),
@@ -1081,7 +1167,7 @@ ServiceBuilder<
 						author::AuthorApi::to_delegate(author),
 						system::SystemApi::to_delegate(system),
 						rpc_extensions.clone(),
-					))
+					), middleware)
 				},
 				None => sc_rpc_server::rpc_handler((
 					state::StateApi::to_delegate(state),
@@ -1089,10 +1175,11 @@ ServiceBuilder<
 					author::AuthorApi::to_delegate(author),
 					system::SystemApi::to_delegate(system),
 					rpc_extensions.clone(),
-				))
+				), middleware)
 			}
 		};
-		let rpc_handlers = gen_handler();
+		// The in-process handlers are only reachable by the node itself.
+		let rpc_handlers = gen_handler(sc_rpc::DenyUnsafe::No);
 		let rpc = start_rpc_servers(&config, gen_handler)?;
 
 
@@ -1163,6 +1250,61 @@ ServiceBuilder<
 			let _ = to_spawn_tx.unbounded_send((Box::pin(future), From::from("grafana-server")));
 		}
 
+		// Health check endpoint, served by the Prometheus endpoint
+		let health_handler = match config.health_config.as_ref() {
+			Some(_) if config.prometheus_config.is_none() => {
+				return Err(
+					"The health check endpoint requires the Prometheus endpoint, which is disabled".into()
+				);
+			},
+			Some(health_config) => {
+				let state = health::HealthState::default();
+				let state_ = state.clone();
+				let client_ = client.clone();
+				let (health_tx, health_rx) = mpsc::unbounded::<(NetworkStatus<_>, NetworkState)>();
+				network_status_sinks.lock().push(health::STATUS_INTERVAL, health_tx);
+				let status_task = health_rx.for_each(move |(net_status, _)| {
+					let info = client_.chain_info();
+					state_.update(health::HealthStatus {
+						peers: net_status.num_connected_peers,
+						is_syncing: net_status.sync_state == sc_network::SyncState::Downloading,
+						should_have_peers: has_bootnodes,
+						best_number: info.best_number.saturated_into::<u64>(),
+						best_hash: format!("{:?}", info.best_hash),
+						finalized_number: info.finalized_number.saturated_into::<u64>(),
+						finalized_hash: format!("{:?}", info.finalized_hash),
+						best_seen_block: net_status.best_seen_block.map(|number| number.saturated_into::<u64>()),
+					});
+					ready(())
+				});
+				let _ = to_spawn_tx.unbounded_send((
+					Box::pin(select(status_task, exit.clone()).map(drop)),
+					From::from("health-status")
+				));
+
+				Some(health::handler(state, health_config.readiness.clone()))
+			},
+			None => None,
+		};
+
+		// Prometheus endpoint
+		if let Some(config) = config.prometheus_config.as_ref() {
+			let future = select(
+				prometheus_endpoint::init_prometheus_with_handler(
+					config.port,
+					config.registry.clone(),
+					health_handler,
+				)
+					.map(|result| if let Err(e) = result {
+						error!("Prometheus endpoint failed: {}", e);
+					})
+					.boxed(),
+				exit.clone()
+			).map(drop);
+
+			let _ = to_spawn_tx.unbounded_send((Box::pin(future), From::from("prometheus-endpoint")));
+		}
+
 		// Instrumentation
 		if let Some(tracing_targets) = config.tracing_targets.as_ref() {
 			let subscriber = sc_tracing::ProfilingSubscriber::new(
@@ -1197,6 +1339,7 @@ ServiceBuilder<
 			_offchain_workers: offchain_workers,
 			_telemetry_on_connect_sinks: telemetry_connection_sinks.clone(),
 			keystore,
+			prometheus_registry: config.prometheus_config.as_ref().map(|config| config.registry.clone()),
 			marker: PhantomData::<TBl>,
 		})
 	}
@@ -20,13 +20,15 @@ pub use sc_client::ExecutionStrategies;
 pub use sc_client_db::{kvdb::KeyValueDB, PruningMode};
 pub use sc_network::config::{ExtTransport, NetworkConfiguration, Roles};
 pub use sc_executor::WasmExecutionMethod;
+pub use sc_rpc_server::IpNet;
 
-use std::{future::Future, path::{PathBuf, Path}, pin::Pin, net::SocketAddr, sync::Arc};
+use std::{future::Future, path::{PathBuf, Path}, pin::Pin, net::SocketAddr, sync::Arc, time::Duration};
 pub use sc_transaction_pool::txpool::Options as TransactionPoolOptions;
 use sc_chain_spec::{ChainSpec, NoExtension};
 use sp_core::crypto::Protected;
 use target_info::Target;
 use sc_telemetry::TelemetryEndpoints;
+use prometheus_endpoint::Registry;
 
 /// Executable version. Used to pass version information from the root crate.
 #[derive(Clone)]
@@ -93,8 +95,22 @@ pub struct Configuration<G, E = NoExtension> {
 	pub rpc_ws_max_connections: Option<usize>,
 	/// CORS settings for HTTP & WS servers. `None` if all origins are allowed.
 	pub rpc_cors: Option<Vec<String>>,
+	/// RPC methods to expose on the HTTP & WS servers.
+	pub rpc_methods: RpcMethods,
+	/// Maximum size of RPC requests in bytes. `None` if default.
+	pub rpc_max_payload: Option<usize>,
+	/// Maximum size of RPC responses in bytes. `None` if unlimited.
+	pub rpc_max_response_size: Option<usize>,
+	/// Maximum number of RPC calls per second and connection. `None` if unlimited.
+	pub rpc_rate_limit: Option<u32>,
+	/// Networks allowed to connect to the WebSockets RPC server. `None` if all peers are allowed.
+	pub rpc_ws_allowed_ips: Option<Vec<IpNet>>,
 	/// Grafana data source http port. `None` if disabled.
 	pub grafana_port: Option<SocketAddr>,
+	/// Prometheus endpoint configuration. `None` if disabled.
+	pub prometheus_config: Option<PrometheusConfig>,
+	/// Health check endpoint configuration. `None` if disabled.
+	pub health_config: Option<HealthConfig>,
 	/// Telemetry service URL. `None` if disabled.
 	pub telemetry_endpoints: Option<TelemetryEndpoints>,
 	/// External WASM transport for the telemetry. If `Some`, when connection to a telemetry
@@ -104,6 +120,10 @@ pub struct Configuration<G, E = NoExtension> {
 	pub default_heap_pages: Option<u64>,
 	/// Should offchain workers be executed.
 	pub offchain_worker: bool,
+	/// Should the events of imported blocks be indexed. Only supported by full nodes.
+	pub event_index: bool,
+	/// Should the blocks including extrinsics be indexed. Only supported by full nodes.
+	pub transaction_index: bool,
 	/// Sentry mode is enabled, the node's role is AUTHORITY but it should not
 	/// actively participate in consensus (i.e. no keystores should be passed to
 	/// consensus modules).
@@ -150,6 +170,56 @@ impl KeystoreConfig {
 	}
 }
 
+/// Configuration of the Prometheus endpoint.
+#[derive(Clone, Debug)]
+pub struct PrometheusConfig {
+	/// Address to listen on.
+	pub port: SocketAddr,
+	/// The registry which all subsystems register their metrics with.
+	pub registry: Registry,
+}
+
+impl PrometheusConfig {
+	/// Create a new config using the default registry.
+	pub fn new_with_default_registry(port: SocketAddr) -> Self {
+		Self { port, registry: Registry::new() }
+	}
+}
+
+/// Configuration of the health check endpoint, served by the Prometheus endpoint.
+///
+/// Building a service with it fails if the Prometheus endpoint is disabled.
+#[derive(Clone, Debug)]
+pub struct HealthConfig {
+	/// The criteria for the node to be reported as ready.
+	pub readiness: ReadinessCriteria,
+}
+
+/// Criteria for the node to be reported as ready by the health check endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadinessCriteria {
+	/// Maximal number of blocks the best block may be behind the best block seen on the network.
+	pub max_blocks_behind: u64,
+	/// Minimal number of connected peers, only checked if the node should have peers.
+	pub min_peers: usize,
+	/// Maximal number of blocks the finalized block may be behind the best block.
+	pub max_finality_lag: Option<u64>,
+	/// Maximal time the best block may not advance while the node is behind the network,
+	/// after which the import is considered stalled.
+	pub max_import_stall: Option<Duration>,
+}
+
+impl Default for ReadinessCriteria {
+	fn default() -> Self {
+		ReadinessCriteria {
+			max_blocks_behind: 10,
+			min_peers: 1,
+			max_finality_lag: None,
+			max_import_stall: Some(Duration::from_secs(60)),
+		}
+	}
+}
+
 /// Configuration of the database of the client.
 #[derive(Clone)]
 pub enum DatabaseConfig {
@@ -165,6 +235,24 @@ pub enum DatabaseConfig {
 	Custom(Arc<dyn KeyValueDB>),
 }
 
+/// RPC methods exposed by the HTTP & WS servers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RpcMethods {
+	/// Expose every RPC method if the server listens on a loopback address,
+	/// only the safe ones otherwise.
+	Auto,
+	/// Expose only the safe RPC methods.
+	Safe,
+	/// Expose every RPC method, even the unsafe ones.
+	Unsafe,
+}
+
+impl Default for RpcMethods {
+	fn default() -> RpcMethods {
+		RpcMethods::Auto
+	}
+}
+
 impl<G, E> Default for Configuration<G, E> {
 	/// Create a default config
 	fn default() -> Self {
@@ -190,11 +278,20 @@ impl<G, E> Default for Configuration<G, E> {
 			rpc_ws: None,
 			rpc_ws_max_connections: None,
 			rpc_cors: Some(vec![]),
+			rpc_methods: Default::default(),
+			rpc_max_payload: None,
+			rpc_max_response_size: None,
+			rpc_rate_limit: None,
+			rpc_ws_allowed_ips: None,
 			grafana_port: None,
+			prometheus_config: None,
+			health_config: None,
 			telemetry_endpoints: None,
 			telemetry_external_transport: None,
 			default_heap_pages: None,
 			offchain_worker: Default::default(),
+			event_index: false,
+			transaction_index: false,
 			sentry_mode: false,
 			force_authoring: false,
 			disable_grandpa: false,
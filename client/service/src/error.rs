@@ -37,6 +37,8 @@ pub enum Error {
 	Network(sc_network::error::Error),
 	/// Keystore error.
 	Keystore(sc_keystore::Error),
+	/// Error registering the metrics.
+	Prometheus(prometheus_endpoint::PrometheusError),
 	/// Best chain selection strategy is missing.
 	#[display(fmt="Best chain selection strategy (SelectChain) is not provided.")]
 	SelectChainRequired,
@@ -61,6 +63,7 @@ impl std::error::Error for Error {
 			Error::Consensus(ref err) => Some(err),
 			Error::Network(ref err) => Some(err),
 			Error::Keystore(ref err) => Some(err),
+			Error::Prometheus(ref err) => Some(err),
 			_ => None,
 		}
 	}
@@ -0,0 +1,677 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Index of the events deposited by the blocks of the canon chain.
+//!
+//! The indexer follows the canon chain block by block, decodes the `System::Events`
+//! storage value of every block using the runtime metadata and stores the events in
+//! the event index storage of the backend. Besides the events of every block, the
+//! index keeps lookup tables from pallets, events and topics to the blocks depositing
+//! them, so queries only need to read the matching blocks. Every event has its own key
+//! in the lookup tables, so indexing a block never rewrites the entries of other blocks.
+//!
+//! Events can only be indexed for blocks with available state. Blocks whose state has
+//! been pruned before they were indexed are skipped and the index then starts at the
+//! last finalized block. Blocks whose events can't be decoded are indexed without events.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	marker::PhantomData,
+	sync::Arc,
+	time::Duration,
+};
+
+use codec::{Decode, Encode};
+use futures::{channel::mpsc, future::ready, prelude::*, stream};
+use log::{debug, warn};
+use sc_client::{Client, CallExecutor, BlockchainEvents};
+use sc_client_api::backend::{self, IndexStorage};
+use sc_metadata_decoder::Phase;
+use sc_rpc::chain::{
+	error::{Error as RpcError, Result as RpcResult},
+	events::{EventFilter, EventPage, IndexedEvent},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, SaturatedConversion},
+};
+
+use crate::runtime_decoder::{system_events_key, MetadataCache};
+
+/// Number of blocks sharing a lookup table entry.
+const BUCKET_SIZE: u64 = 256;
+/// A page of query results ends at the first block boundary after this many events.
+const MAX_PAGE_EVENTS: usize = 1000;
+/// Maximal number of blocks scanned for a single page of query results.
+const MAX_SCANNED_BLOCKS: u64 = 10_000;
+/// Maximal number of blocks indexed at once.
+const BATCH_SIZE: u64 = 256;
+/// Interval of indexing new blocks independently of block notifications, which are
+/// not sent during major sync.
+const TICK_INTERVAL: Duration = Duration::from_secs(6);
+
+/// The first indexed block.
+const META_FIRST: &[u8] = b"meta:first";
+/// The next block to index.
+const META_NEXT: &[u8] = b"meta:next";
+/// The last block whose forks have been removed from the index.
+const META_PRUNED: &[u8] = b"meta:pruned";
+
+/// An event as stored in the index.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct StoredEvent<Hash> {
+	/// Index of the extrinsic depositing the event, `None` during block finalization.
+	pub extrinsic_index: Option<u32>,
+	/// Name of the pallet.
+	pub pallet: String,
+	/// Name of the event.
+	pub event: String,
+	/// The JSON encoded arguments.
+	pub args: String,
+	/// Topics of the event.
+	pub topics: Vec<Hash>,
+}
+
+/// A lookup table of the index.
+enum Lookup<'a, Hash> {
+	Pallet(&'a str),
+	Event(&'a str, &'a str),
+	Topic(&'a Hash),
+}
+
+impl<'a, Hash: Encode> Lookup<'a, Hash> {
+	/// Common prefix of the keys of the table entries of the blocks in the given bucket.
+	fn prefix(&self, bucket: u64) -> Vec<u8> {
+		match self {
+			Lookup::Pallet(pallet) => (b'p', pallet, bucket).encode(),
+			Lookup::Event(pallet, event) => (b'v', pallet, event, bucket).encode(),
+			Lookup::Topic(topic) => (b't', topic, bucket).encode(),
+		}
+	}
+
+	/// Key of the given table entry.
+	fn key(&self, entry: &LookupEntry<Hash>) -> Vec<u8> {
+		let mut key = self.prefix(entry.0 / BUCKET_SIZE);
+		entry.encode_to(&mut key);
+		key
+	}
+}
+
+/// The lookup tables referencing the given event.
+fn lookups<Hash>(event: &StoredEvent<Hash>) -> Vec<Lookup<Hash>> {
+	let mut lookups = vec![Lookup::Pallet(&event.pallet), Lookup::Event(&event.pallet, &event.event)];
+	lookups.extend(event.topics.iter().map(Lookup::Topic));
+	lookups
+}
+
+/// Key of the hash of the canon block with the given number.
+fn canon_key(number: u64) -> Vec<u8> {
+	(b'c', number).encode()
+}
+
+/// Key of the hashes of all indexed blocks with the given number.
+fn blocks_key(number: u64) -> Vec<u8> {
+	(b'n', number).encode()
+}
+
+/// Key of the events of a block.
+fn events_key<Hash: Encode>(number: u64, hash: &Hash) -> Vec<u8> {
+	(b'e', number, hash).encode()
+}
+
+/// Entry of a lookup table: block number, block hash and index of the event.
+type LookupEntry<Hash> = (u64, Hash, u32);
+
+/// The event index, stored in an `IndexStorage`.
+pub struct EventIndex<Block: BlockT> {
+	storage: Arc<dyn IndexStorage>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block: BlockT> EventIndex<Block> {
+	/// Create an event index stored in `storage`.
+	pub fn new(storage: Arc<dyn IndexStorage>) -> Self {
+		EventIndex { storage, _marker: PhantomData }
+	}
+
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		self.storage.get(key).and_then(|value| T::decode(&mut &value[..]).ok())
+	}
+
+	/// The first indexed block and the next block to index, `None` until indexing starts.
+	pub fn bounds(&self) -> Option<(u64, u64)> {
+		Some((self.get(META_FIRST)?, self.get(META_NEXT)?))
+	}
+
+	/// Hash of the canon block with the given number at the time it was indexed.
+	pub fn canon_hash(&self, number: u64) -> Option<Block::Hash> {
+		self.get(&canon_key(number))
+	}
+
+	/// The entries of the given lookup table in `bucket`.
+	fn lookup_entries(&self, lookup: &Lookup<Block::Hash>, bucket: u64) -> Vec<LookupEntry<Block::Hash>> {
+		let prefix = lookup.prefix(bucket);
+		self.storage.keys_with_prefix(&prefix).into_iter()
+			.filter_map(|key| Decode::decode(&mut &key[prefix.len()..]).ok())
+			.collect()
+	}
+
+	/// The events of the given block.
+	pub fn block_events(&self, number: u64, hash: &Block::Hash) -> Option<Vec<StoredEvent<Block::Hash>>> {
+		self.get(&events_key(number, hash))
+	}
+
+	/// Start a batch of changes to the index.
+	pub fn batch(&self) -> Batch<Block> {
+		Batch { index: self, changes: HashMap::new() }
+	}
+
+	/// Query the events of the canon blocks `from..=to` matching `filter`.
+	pub fn query(
+		&self,
+		filter: &EventFilter<Block::Hash>,
+		from: u64,
+		to: u64,
+	) -> RpcResult<EventPage<NumberFor<Block>, Block::Hash>> {
+		if filter.event.is_some() && filter.pallet.is_none() {
+			return Err(RpcError::InvalidEventQuery("filtering by `event` requires a `pallet`".into()));
+		}
+		if from > to {
+			return Err(RpcError::InvalidEventQuery(format!("`from` #{} is above `to` #{}", from, to)));
+		}
+
+		let page = |events, next: Option<u64>| EventPage {
+			events,
+			next: next.map(|next| next.saturated_into()),
+		};
+		let (first, next) = match self.bounds() {
+			Some(bounds) => bounds,
+			None => return Ok(page(Vec::new(), Some(from))),
+		};
+		if from < first {
+			return Err(RpcError::InvalidEventQuery(format!("blocks below #{} are not indexed", first)));
+		}
+		if from >= next {
+			return Ok(page(Vec::new(), Some(from)));
+		}
+
+		let lookup = match (filter.topic.as_ref(), filter.pallet.as_ref(), filter.event.as_ref()) {
+			(Some(topic), _, _) => Some(Lookup::Topic(topic)),
+			(None, Some(pallet), Some(event)) => Some(Lookup::Event(pallet, event)),
+			(None, Some(pallet), None) => Some(Lookup::Pallet(pallet)),
+			(None, None, _) => None,
+		};
+		let indexed_to = to.min(next - 1);
+		let mut events = Vec::new();
+		let mut number = from;
+		while number <= indexed_to {
+			if number - from >= MAX_SCANNED_BLOCKS {
+				return Ok(page(events, Some(number)));
+			}
+
+			let bucket = number / BUCKET_SIZE;
+			let end = ((bucket + 1) * BUCKET_SIZE - 1).min(indexed_to).min(from + MAX_SCANNED_BLOCKS - 1);
+			// The blocks to read and the indices of their candidate events, all if `None`.
+			let blocks: Vec<(u64, Block::Hash, Option<Vec<u32>>)> = match lookup {
+				Some(ref lookup) => {
+					let mut candidates = BTreeMap::<_, Vec<_>>::new();
+					for (n, hash, index) in self.lookup_entries(lookup, bucket) {
+						if n >= number && n <= end {
+							candidates.entry((n, hash)).or_default().push(index);
+						}
+					}
+					candidates.into_iter()
+						.filter(|((n, hash), _)| self.canon_hash(*n).as_ref() == Some(hash))
+						.map(|((n, hash), mut indices)| {
+							indices.sort();
+							indices.dedup();
+							(n, hash, Some(indices))
+						})
+						.collect()
+				},
+				None => (number..=end)
+					.filter_map(|n| self.canon_hash(n).map(|hash| (n, hash, None)))
+					.collect(),
+			};
+
+			for (n, hash, indices) in blocks {
+				let stored = self.block_events(n, &hash).unwrap_or_default();
+				let candidates: Box<dyn Iterator<Item = u32>> = match indices {
+					Some(indices) => Box::new(indices.into_iter()),
+					None => Box::new(0..stored.len() as u32),
+				};
+				for index in candidates {
+					let event = match stored.get(index as usize) {
+						Some(event) if matches(filter, event) => event,
+						_ => continue,
+					};
+					events.push(IndexedEvent {
+						block_number: n.saturated_into(),
+						block_hash: hash,
+						event_index: index,
+						extrinsic_index: event.extrinsic_index,
+						pallet: event.pallet.clone(),
+						event: event.event.clone(),
+						args: serde_json::from_str(&event.args).unwrap_or_default(),
+						topics: event.topics.clone(),
+					});
+				}
+
+				if events.len() >= MAX_PAGE_EVENTS && n < to {
+					return Ok(page(events, Some(n + 1)));
+				}
+			}
+			number = end + 1;
+		}
+
+		Ok(page(events, if indexed_to < to { Some(indexed_to + 1) } else { None }))
+	}
+}
+
+/// Whether `event` matches all fields of `filter`.
+fn matches<Hash: PartialEq>(filter: &EventFilter<Hash>, event: &StoredEvent<Hash>) -> bool {
+	filter.pallet.as_ref().map_or(true, |pallet| pallet == &event.pallet)
+		&& filter.event.as_ref().map_or(true, |name| name == &event.event)
+		&& filter.topic.as_ref().map_or(true, |topic| event.topics.contains(topic))
+}
+
+impl<Block: BlockT> sc_rpc::chain::EventIndex<Block> for EventIndex<Block> {
+	fn query_events(
+		&self,
+		filter: &EventFilter<Block::Hash>,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> RpcResult<EventPage<NumberFor<Block>, Block::Hash>> {
+		self.query(filter, from.saturated_into::<u64>(), to.saturated_into::<u64>())
+	}
+}
+
+/// Changes to the event index, written atomically on commit.
+pub struct Batch<'a, Block: BlockT> {
+	index: &'a EventIndex<Block>,
+	changes: HashMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a, Block: BlockT> Batch<'a, Block> {
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		match self.changes.get(key) {
+			Some(value) => value.as_ref().and_then(|value| T::decode(&mut &value[..]).ok()),
+			None => self.index.get(key),
+		}
+	}
+
+	fn put<T: Encode>(&mut self, key: Vec<u8>, value: &T) {
+		self.changes.insert(key, Some(value.encode()));
+	}
+
+	/// Set the first indexed block and the next block to index.
+	pub fn set_bounds(&mut self, first: u64, next: u64) {
+		self.put(META_FIRST.to_vec(), &first);
+		self.put(META_NEXT.to_vec(), &next);
+	}
+
+	/// Add the events of a block, making it the canon block of its number.
+	pub fn insert_block(&mut self, number: u64, hash: Block::Hash, events: Vec<StoredEvent<Block::Hash>>) {
+		self.put(canon_key(number), &hash);
+
+		let mut blocks: Vec<Block::Hash> = self.get(&blocks_key(number)).unwrap_or_default();
+		if blocks.contains(&hash) {
+			// The block has been retracted and is canon again.
+			return;
+		}
+		blocks.push(hash);
+		self.put(blocks_key(number), &blocks);
+
+		for (index, event) in events.iter().enumerate() {
+			let entry = (number, hash, index as u32);
+			for lookup in lookups(event) {
+				self.changes.insert(lookup.key(&entry), Some(Vec::new()));
+			}
+		}
+		self.put(events_key(number, &hash), &events);
+	}
+
+	/// Remove all blocks which are not canon from the index, up to block `number`.
+	///
+	/// Must only be called with finalized blocks, whose canon block does not change anymore.
+	pub fn prune_forks(&mut self, number: u64) {
+		let first = match self.get::<u64>(META_FIRST) {
+			Some(first) => first,
+			None => return,
+		};
+		let from = self.get::<u64>(META_PRUNED).map_or(first, |pruned| first.max(pruned + 1));
+		for n in from..=number {
+			let canon = self.get::<Block::Hash>(&canon_key(n));
+			let blocks: Vec<Block::Hash> = self.get(&blocks_key(n)).unwrap_or_default();
+			for hash in blocks.iter().filter(|hash| Some(*hash) != canon.as_ref()) {
+				self.remove_block(n, hash);
+			}
+			if blocks.len() > 1 {
+				self.put(blocks_key(n), &canon.into_iter().collect::<Vec<_>>());
+			}
+		}
+		if from <= number {
+			self.put(META_PRUNED.to_vec(), &number);
+		}
+	}
+
+	fn remove_block(&mut self, number: u64, hash: &Block::Hash) {
+		let key = events_key(number, hash);
+		let events: Vec<StoredEvent<Block::Hash>> = self.get(&key).unwrap_or_default();
+		self.changes.insert(key, None);
+
+		for (index, event) in events.iter().enumerate() {
+			let entry = (number, *hash, index as u32);
+			for lookup in lookups(event) {
+				self.changes.insert(lookup.key(&entry), None);
+			}
+		}
+	}
+
+	/// Write the changes to the index storage.
+	pub fn commit(self) -> sp_blockchain::Result<()> {
+		self.index.storage.commit(self.changes.into_iter().collect())
+	}
+}
+
+/// Indexes the events of the canon chain.
+pub struct EventIndexer<B, E, Block: BlockT, RA> {
+	client: Arc<Client<B, E, Block, RA>>,
+	index: Arc<EventIndex<Block>>,
+	metadata: MetadataCache<B, E, Block, RA>,
+}
+
+impl<B, E, Block, RA> EventIndexer<B, E, Block, RA> where
+	B: backend::Backend<Block>,
+	E: CallExecutor<Block> + Send + Sync,
+	Block: BlockT,
+	Client<B, E, Block, RA>: ProvideRuntimeApi<Block>,
+	<Client<B, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
+		sp_api::Metadata<Block, Error = sp_blockchain::Error>,
+{
+	/// Create an indexer of the events of `client`'s blocks.
+	pub fn new(client: Arc<Client<B, E, Block, RA>>, index: Arc<EventIndex<Block>>) -> Self {
+		EventIndexer { metadata: MetadataCache::new(client.clone()), client, index }
+	}
+
+	/// Index the next batch of canon blocks, returns whether the index is up to date.
+	pub fn index_batch(&mut self) -> sp_blockchain::Result<bool> {
+		let info = self.client.chain_info();
+		let best = info.best_number.saturated_into::<u64>();
+		let finalized = info.finalized_number.saturated_into::<u64>();
+
+		let index = self.index.clone();
+		let mut batch = index.batch();
+		let (mut first, mut next) = match self.index.bounds() {
+			Some(bounds) => bounds,
+			None => {
+				let genesis_state = self.client.state_at(&BlockId::Number(0u32.into())).is_ok();
+				let first = if genesis_state { 0 } else { finalized };
+				(first, first)
+			},
+		};
+
+		// Blocks retracted by a reorg have to be indexed again.
+		while next > first {
+			let canon = self.client.block_hash((next - 1).saturated_into())?;
+			if canon.is_some() && canon == self.index.canon_hash(next - 1) {
+				break;
+			}
+			next -= 1;
+		}
+
+		let end = best.min(next.saturating_add(BATCH_SIZE - 1));
+		while next <= end {
+			let hash = match self.client.block_hash(next.saturated_into())? {
+				Some(hash) => hash,
+				None => break,
+			};
+			let id = BlockId::Hash(hash);
+			if self.client.state_at(&id).is_err() {
+				let resume = finalized.max(next + 1);
+				warn!(
+					"State of block #{} has been pruned, events of blocks #{}..#{} are not indexed",
+					next, next, resume - 1,
+				);
+				first = resume;
+				next = resume;
+				continue;
+			}
+
+			let events = self.decode_events(&id).unwrap_or_else(|err| {
+				// Decoding is deterministic, so the block is indexed without its events
+				// instead of retrying it forever.
+				warn!("Failed to decode events of block #{}, they are not indexed: {}", next, err);
+				Vec::new()
+			});
+			batch.insert_block(next, hash, events);
+			next += 1;
+		}
+
+		batch.set_bounds(first, next);
+		batch.prune_forks(finalized.min(next.saturating_sub(1)));
+		batch.commit()?;
+
+		debug!(target: "event-index", "Indexed events up to block #{}", next.saturating_sub(1));
+		Ok(next > best)
+	}
+
+	/// Decode the events deposited by the given block.
+	fn decode_events(&self, id: &BlockId<Block>) -> Result<Vec<StoredEvent<Block::Hash>>, String> {
+		let encoded = match self.client.storage(id, &system_events_key()).map_err(|e| e.to_string())? {
+			Some(encoded) => encoded.0,
+			None => return Ok(Vec::new()),
+		};
+		let (_, metadata) = self.metadata.metadata(id)?;
+
+		let mut events = Vec::new();
+		for record in metadata.decode_events::<Block::Hash>(&encoded).map_err(|e| e.to_string())? {
+			let record = match record {
+				Ok(record) => record,
+				Err(err) => {
+					// Following events can not be decoded either, keep the decoded ones.
+					warn!("Failed to decode event #{} of block {}: {}", events.len(), id, err);
+					break;
+				},
+			};
+			events.push(StoredEvent {
+				extrinsic_index: match record.phase {
+					Phase::ApplyExtrinsic(index) => Some(index),
+					Phase::Finalization => None,
+				},
+				pallet: record.module,
+				event: record.event,
+				args: serde_json::Value::Array(record.arguments).to_string(),
+				topics: record.topics,
+			});
+		}
+		Ok(events)
+	}
+
+	/// Keep indexing new blocks, whenever blocks are imported or finalized or periodically.
+	pub fn run(mut self) -> impl Future<Output = ()> {
+		let (retry_tx, retry_rx) = mpsc::unbounded();
+		let _ = retry_tx.unbounded_send(());
+
+		let imports = self.client.import_notification_stream().map(drop);
+		let finality = self.client.finality_notification_stream().map(drop);
+		let ticks = stream::unfold((), |()| futures_timer::Delay::new(TICK_INTERVAL).map(|()| Some(((), ()))));
+
+		stream::select(stream::select(imports, finality), stream::select(ticks, retry_rx))
+			.for_each(move |()| {
+				match self.index_batch() {
+					Ok(true) => (),
+					// Continue with the next batch after other tasks had the chance to run.
+					Ok(false) => { let _ = retry_tx.unbounded_send(()); },
+					Err(err) => warn!("Failed to index events: {}", err),
+				}
+				ready(())
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parking_lot::Mutex;
+	use substrate_test_runtime_client::runtime::{Block, H256};
+
+	#[derive(Default)]
+	struct MemoryStorage(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl IndexStorage for MemoryStorage {
+		fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+			self.0.lock().get(key).cloned()
+		}
+
+		fn keys_with_prefix(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+			self.0.lock().keys().filter(|key| key.starts_with(prefix)).cloned().collect()
+		}
+
+		fn commit(&self, changes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> sp_blockchain::Result<()> {
+			let mut storage = self.0.lock();
+			for (key, value) in changes {
+				match value {
+					Some(value) => storage.insert(key, value),
+					None => storage.remove(&key),
+				};
+			}
+			Ok(())
+		}
+	}
+
+	fn event(pallet: &str, event: &str, topics: Vec<H256>) -> StoredEvent<H256> {
+		StoredEvent {
+			extrinsic_index: Some(1),
+			pallet: pallet.into(),
+			event: event.into(),
+			args: "[1]".into(),
+			topics,
+		}
+	}
+
+	fn hash(n: u64) -> H256 {
+		H256::from_low_u64_be(n)
+	}
+
+	/// Index with the blocks `1..=blocks`, block `n` having hash `n`.
+	fn index(blocks: u64, events: impl Fn(u64) -> Vec<StoredEvent<H256>>) -> EventIndex<Block> {
+		let index = EventIndex::new(Arc::new(MemoryStorage::default()));
+		let mut batch = index.batch();
+		for n in 1..=blocks {
+			batch.insert_block(n, hash(n), events(n));
+		}
+		batch.set_bounds(1, blocks + 1);
+		batch.commit().unwrap();
+		index
+	}
+
+	fn found(page: &EventPage<u64, H256>) -> Vec<(u64, u32, &str)> {
+		page.events.iter().map(|e| (e.block_number, e.event_index, &e.event[..])).collect()
+	}
+
+	fn filter(pallet: Option<&str>, event: Option<&str>, topic: Option<H256>) -> EventFilter<H256> {
+		EventFilter { pallet: pallet.map(Into::into), event: event.map(Into::into), topic }
+	}
+
+	#[test]
+	fn should_query_events() {
+		let topic = H256::repeat_byte(1);
+		let index = index(600, |n| match n {
+			5 => vec![event("Balances", "Transfer", vec![]), event("System", "ExtrinsicSuccess", vec![])],
+			300 => vec![event("Balances", "Endowed", vec![topic]), event("Balances", "Transfer", vec![topic])],
+			_ => vec![],
+		});
+
+		let page = index.query(&filter(Some("Balances"), None, None), 1, 600).unwrap();
+		assert_eq!(found(&page), vec![(5, 0, "Transfer"), (300, 0, "Endowed"), (300, 1, "Transfer")]);
+		assert_eq!(page.next, None);
+		assert_eq!(page.events[0].args, vec![serde_json::json!(1)]);
+		assert_eq!(page.events[0].block_hash, hash(5));
+
+		let page = index.query(&filter(Some("Balances"), Some("Transfer"), None), 1, 600).unwrap();
+		assert_eq!(found(&page), vec![(5, 0, "Transfer"), (300, 1, "Transfer")]);
+
+		let page = index.query(&filter(Some("Balances"), Some("Transfer"), Some(topic)), 1, 600).unwrap();
+		assert_eq!(found(&page), vec![(300, 1, "Transfer")]);
+
+		let page = index.query(&filter(None, None, None), 5, 299).unwrap();
+		assert_eq!(found(&page), vec![(5, 0, "Transfer"), (5, 1, "ExtrinsicSuccess")]);
+
+		// Blocks which are not indexed yet are reported as the continuation.
+		let page = index.query(&filter(None, None, Some(topic)), 300, 700).unwrap();
+		assert_eq!(found(&page), vec![(300, 0, "Endowed"), (300, 1, "Transfer")]);
+		assert_eq!(page.next, Some(601));
+	}
+
+	#[test]
+	fn should_reject_invalid_queries() {
+		let index = index(10, |_| vec![]);
+
+		assert!(index.query(&filter(None, Some("Transfer"), None), 1, 10).is_err());
+		assert!(index.query(&filter(None, None, None), 5, 4).is_err());
+		assert!(index.query(&filter(None, None, None), 0, 10).is_err());
+	}
+
+	#[test]
+	fn should_page_results() {
+		let index = index(20_100, |n| match n {
+			2 | 3 | 4 => (0..600).map(|_| event("Balances", "Transfer", vec![])).collect(),
+			_ => vec![],
+		});
+
+		let page = index.query(&filter(Some("Balances"), None, None), 1, 20_100).unwrap();
+		assert_eq!(page.events.len(), 1200);
+		assert_eq!(page.next, Some(4));
+
+		let page = index.query(&filter(Some("Balances"), None, None), 4, 20_100).unwrap();
+		assert_eq!(page.events.len(), 600);
+		assert_eq!(page.next, Some(10_004));
+
+		let page = index.query(&filter(Some("Balances"), None, None), 10_004, 20_100).unwrap();
+		assert_eq!(page.events.len(), 0);
+		assert_eq!(page.next, Some(20_004));
+
+		let page = index.query(&filter(Some("Balances"), None, None), 20_004, 20_100).unwrap();
+		assert_eq!(page.next, None);
+	}
+
+	#[test]
+	fn should_ignore_retracted_blocks_and_prune_forks() {
+		let index = index(3, |_| vec![event("Balances", "Transfer", vec![])]);
+		let fork = H256::repeat_byte(2);
+
+		// A reorg replaces block 3.
+		let mut batch = index.batch();
+		batch.insert_block(3, fork, vec![event("Balances", "Endowed", vec![])]);
+		batch.commit().unwrap();
+
+		let page = index.query(&filter(Some("Balances"), None, None), 1, 3).unwrap();
+		assert_eq!(found(&page), vec![(1, 0, "Transfer"), (2, 0, "Transfer"), (3, 0, "Endowed")]);
+		assert!(index.block_events(3, &hash(3)).is_some());
+
+		let mut batch = index.batch();
+		batch.prune_forks(3);
+		batch.commit().unwrap();
+
+		assert_eq!(index.block_events(3, &hash(3)), None);
+		assert_eq!(index.block_events(3, &fork), Some(vec![event("Balances", "Endowed", vec![])]));
+		let mut entries = index.lookup_entries(&Lookup::Pallet("Balances"), 0);
+		entries.sort();
+		assert_eq!(entries, vec![(1, hash(1), 0), (2, hash(2), 0), (3, fork, 0)]);
+	}
+}
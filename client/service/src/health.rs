@@ -0,0 +1,263 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! HTTP endpoints reporting the health of the node, e.g. for the probes of orchestrators.
+//! They are served by the Prometheus endpoint.
+//!
+//! - `/health` reports whether the node is alive, i.e. whether its status is still updated.
+//! - `/ready` reports whether the node satisfies the configured [`ReadinessCriteria`].
+//!
+//! Both respond with `200 OK` or `503 Service Unavailable` and a JSON object describing the
+//! status of the node, including why it is not healthy or ready.
+
+use std::{sync::Arc, time::{Duration, Instant}};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::ReadinessCriteria;
+
+/// Interval at which the status of the node is updated.
+pub(crate) const STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Time after which the node is considered dead if its status has not been updated.
+const MAX_STATUS_AGE: Duration = Duration::from_secs(30);
+
+/// The status of the node, built from the network status and the client info.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HealthStatus {
+	/// Number of connected peers.
+	pub peers: usize,
+	/// Whether the node is syncing.
+	pub is_syncing: bool,
+	/// Whether the node should be connected to peers.
+	pub should_have_peers: bool,
+	/// Number of the best block.
+	pub best_number: u64,
+	/// Hash of the best block.
+	pub best_hash: String,
+	/// Number of the last finalized block.
+	pub finalized_number: u64,
+	/// Hash of the last finalized block.
+	pub finalized_hash: String,
+	/// Number of the best block seen on the network.
+	pub best_seen_block: Option<u64>,
+}
+
+struct Reported {
+	status: HealthStatus,
+	/// When the status has been reported.
+	updated: Instant,
+	/// When the best block number has last changed.
+	best_changed: Instant,
+}
+
+/// The last reported status of the node, shared with the endpoint.
+#[derive(Clone, Default)]
+pub(crate) struct HealthState(Arc<RwLock<Option<Reported>>>);
+
+impl HealthState {
+	/// Report the current status of the node.
+	pub fn update(&self, status: HealthStatus) {
+		self.update_at(status, Instant::now())
+	}
+
+	fn update_at(&self, status: HealthStatus, now: Instant) {
+		let mut reported = self.0.write();
+		let best_changed = match *reported {
+			Some(ref reported) if reported.status.best_number == status.best_number => reported.best_changed,
+			_ => now,
+		};
+		*reported = Some(Reported { status, updated: now, best_changed });
+	}
+
+	/// Whether the node is alive, with the reasons if it isn't.
+	fn health(&self, now: Instant) -> (Option<HealthStatus>, Vec<String>) {
+		let reported = self.0.read();
+		let reported = match *reported {
+			Some(ref reported) => reported,
+			None => return (None, vec!["The status of the node has not been reported yet".into()]),
+		};
+
+		let mut reasons = Vec::new();
+		let age = now.saturating_duration_since(reported.updated);
+		if age > MAX_STATUS_AGE {
+			reasons.push(format!("The status of the node has not been updated for {}s", age.as_secs()));
+		}
+		(Some(reported.status.clone()), reasons)
+	}
+
+	/// Whether the node satisfies the criteria, with the reasons if it doesn't.
+	fn readiness(&self, criteria: &ReadinessCriteria, now: Instant) -> (Option<HealthStatus>, Vec<String>) {
+		let (status, mut reasons) = self.health(now);
+		let status = match status {
+			Some(status) => status,
+			None => return (None, reasons),
+		};
+		let best_changed = self.0.read().as_ref().map_or(now, |reported| reported.best_changed);
+
+		if status.should_have_peers && status.peers < criteria.min_peers {
+			reasons.push(format!("Connected to {} peers, at least {} are required", status.peers, criteria.min_peers));
+		}
+
+		let best_seen = status.best_seen_block.unwrap_or(status.best_number);
+		let behind = best_seen.saturating_sub(status.best_number);
+		if behind > criteria.max_blocks_behind {
+			reasons.push(format!("The best block is {} blocks behind the network", behind));
+		}
+
+		let finality_lag = status.best_number.saturating_sub(status.finalized_number);
+		if criteria.max_finality_lag.map_or(false, |max_finality_lag| finality_lag > max_finality_lag) {
+			reasons.push(format!("The finalized block is {} blocks behind the best block", finality_lag));
+		}
+
+		let stalled = now.saturating_duration_since(best_changed);
+		if behind > 0 && criteria.max_import_stall.map_or(false, |max_import_stall| stalled > max_import_stall) {
+			reasons.push(format!("No block has been imported for {}s", stalled.as_secs()));
+		}
+
+		(Some(status), reasons)
+	}
+}
+
+/// Build the response to a request of `path`: whether it succeeded, and the JSON body.
+///
+/// Returns `None` for unknown paths.
+fn respond(path: &str, state: &HealthState, criteria: &ReadinessCriteria, now: Instant) -> Option<(bool, Value)> {
+	let (key, (status, reasons)) = match path {
+		"/health" => ("healthy", state.health(now)),
+		"/ready" => ("ready", state.readiness(criteria, now)),
+		_ => return None,
+	};
+
+	let success = reasons.is_empty();
+	let mut body = match status.map(serde_json::to_value) {
+		Some(Ok(Value::Object(body))) => body,
+		_ => Default::default(),
+	};
+	body.insert(key.into(), success.into());
+	body.insert("reasons".into(), reasons.into());
+	Some((success, Value::Object(body)))
+}
+
+/// Handler serving `/health` and `/ready` from the Prometheus endpoint, reporting the status of
+/// `state`.
+pub(crate) fn handler(state: HealthState, criteria: ReadinessCriteria) -> prometheus_endpoint::Handler {
+	Arc::new(move |path: &str| respond(path, &state, &criteria, Instant::now()).map(|(success, body)| {
+		prometheus_endpoint::Response {
+			status: if success { 200 } else { 503 },
+			content_type: "application/json",
+			body: body.to_string(),
+		}
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn status(peers: usize, best_number: u64, finalized_number: u64, best_seen_block: Option<u64>) -> HealthStatus {
+		HealthStatus {
+			peers,
+			is_syncing: false,
+			should_have_peers: true,
+			best_number,
+			best_hash: "0x01".into(),
+			finalized_number,
+			finalized_hash: "0x02".into(),
+			best_seen_block,
+		}
+	}
+
+	#[test]
+	fn reports_health_and_readiness_with_reasons() {
+		let state = HealthState::default();
+		let criteria = ReadinessCriteria {
+			max_blocks_behind: 5,
+			min_peers: 2,
+			max_finality_lag: Some(10),
+			max_import_stall: Some(Duration::from_secs(60)),
+		};
+		let start = Instant::now();
+
+		let (healthy, body) = respond("/health", &state, &criteria, start).unwrap();
+		assert!(!healthy);
+		assert_eq!(body, json!({
+			"healthy": false,
+			"reasons": ["The status of the node has not been reported yet"],
+		}));
+		assert!(respond("/metrics", &state, &criteria, start).is_none());
+
+		let handler = handler(state.clone(), criteria.clone());
+		assert_eq!(handler("/health").map(|response| response.status), Some(503));
+		assert!(handler("/metrics").is_none());
+
+		state.update_at(status(3, 100, 95, Some(103)), start);
+		let (ready, body) = respond("/ready", &state, &criteria, start).unwrap();
+		assert!(ready);
+		assert_eq!(body, json!({
+			"peers": 3,
+			"isSyncing": false,
+			"shouldHavePeers": true,
+			"bestNumber": 100,
+			"bestHash": "0x01",
+			"finalizedNumber": 95,
+			"finalizedHash": "0x02",
+			"bestSeenBlock": 103,
+			"ready": true,
+			"reasons": [],
+		}));
+
+		state.update_at(status(1, 100, 80, Some(110)), start + Duration::from_secs(5));
+		let (ready, body) = respond("/ready", &state, &criteria, start + Duration::from_secs(65)).unwrap();
+		assert!(!ready);
+		assert_eq!(body["reasons"], json!([
+			"The status of the node has not been updated for 60s",
+			"Connected to 1 peers, at least 2 are required",
+			"The best block is 10 blocks behind the network",
+			"The finalized block is 20 blocks behind the best block",
+			"No block has been imported for 65s",
+		]));
+		let (healthy, _) = respond("/health", &state, &criteria, start + Duration::from_secs(65)).unwrap();
+		assert!(!healthy);
+	}
+
+	#[test]
+	fn import_stall_is_measured_from_the_last_best_block_change() {
+		let state = HealthState::default();
+		let criteria = ReadinessCriteria { max_blocks_behind: 100, ..Default::default() };
+		let start = Instant::now();
+
+		state.update_at(status(3, 100, 100, Some(102)), start);
+		state.update_at(status(3, 101, 100, Some(102)), start + Duration::from_secs(50));
+		state.update_at(status(3, 101, 100, Some(102)), start + Duration::from_secs(100));
+		let (ready, _) = respond("/ready", &state, &criteria, start + Duration::from_secs(100)).unwrap();
+		assert!(ready);
+
+		state.update_at(status(3, 101, 100, Some(102)), start + Duration::from_secs(115));
+		let (ready, body) = respond("/ready", &state, &criteria, start + Duration::from_secs(115)).unwrap();
+		assert!(!ready);
+		assert_eq!(body["reasons"], json!(["No block has been imported for 65s"]));
+
+		// Not stalled without blocks to import.
+		state.update_at(status(3, 101, 100, Some(101)), start + Duration::from_secs(120));
+		let (ready, _) = respond("/ready", &state, &criteria, start + Duration::from_secs(120)).unwrap();
+		assert!(ready);
+	}
+}
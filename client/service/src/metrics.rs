@@ -0,0 +1,69 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics which the service samples periodically.
+
+use prometheus_endpoint::{register, CounterVec, GaugeVec, Opts, PrometheusError, Registry};
+use sc_client_api::UsageInfo;
+use sp_transaction_pool::PoolStatus;
+
+/// Metrics of the transaction pool and the database, sampled by the informant task.
+pub(crate) struct ServiceMetrics {
+	txpool_transactions: GaugeVec,
+	state_cache_reads: CounterVec,
+	cache_size: GaugeVec,
+}
+
+impl ServiceMetrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(ServiceMetrics {
+			txpool_transactions: register(GaugeVec::new(
+				Opts::new(
+					"substrate_sub_txpool_transactions",
+					"Number of transactions in the transaction pool, by queue",
+				),
+				&["queue"],
+			)?, registry)?,
+			state_cache_reads: register(CounterVec::new(
+				Opts::new(
+					"substrate_state_reads_total",
+					"Number of state reads, by whether they were answered by the state cache",
+				),
+				&["result"],
+			)?, registry)?,
+			cache_size: register(GaugeVec::new(
+				Opts::new("substrate_cache_size_bytes", "Memory used by the caches, in bytes"),
+				&["cache"],
+			)?, registry)?,
+		})
+	}
+
+	/// Record the current status of the transaction pool.
+	pub(crate) fn report_txpool(&self, status: &PoolStatus) {
+		self.txpool_transactions.with_label_values(&["ready"]).set(status.ready as f64);
+		self.txpool_transactions.with_label_values(&["future"]).set(status.future as f64);
+	}
+
+	/// Record the database usage since the previous report.
+	pub(crate) fn report_usage(&self, usage: &UsageInfo) {
+		let hits = usage.io.state_reads_cache;
+		let misses = usage.io.state_reads.saturating_sub(hits);
+		self.state_cache_reads.with_label_values(&["hit"]).inc_by(hits);
+		self.state_cache_reads.with_label_values(&["miss"]).inc_by(misses);
+		self.cache_size.with_label_values(&["state"]).set(usage.memory.state_cache as f64);
+		self.cache_size.with_label_values(&["database"]).set(usage.memory.database_cache as f64);
+	}
+}
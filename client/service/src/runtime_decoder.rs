@@ -0,0 +1,205 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of blocks and events using the metadata of the runtime of the block.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use codec::Encode;
+use log::warn;
+use parking_lot::Mutex;
+use sc_client::{Client, CallExecutor};
+use sc_client_api::backend;
+use sc_metadata_decoder::{Extrinsic, Metadata, Phase, TypeRegistry};
+use sc_rpc::chain::{
+	decoded::{
+		DecodedBlock, DecodedEvent, DecodedEvents, DecodedExtrinsic, ExtrinsicDetails, ExtrinsicSignature,
+		SignedExtension,
+	},
+	error::{Error as RpcError, Result as RpcResult},
+};
+use sp_api::{Metadata as _, ProvideRuntimeApi};
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Maximal number of runtime versions whose metadata is kept.
+const MAX_CACHED_METADATA: usize = 4;
+
+/// Storage key of the `System::Events` storage value.
+pub(crate) fn system_events_key() -> StorageKey {
+	let mut key = twox_128(b"System").to_vec();
+	key.extend_from_slice(&twox_128(b"Events"));
+	StorageKey(key)
+}
+
+/// Decoded runtime metadata, cached by the spec version of the runtime.
+///
+/// The metadata is queried from the runtime of the block and decoded according to its
+/// version, so blocks of runtimes returning version 11 and 12 of the metadata are supported.
+pub struct MetadataCache<B, E, Block: BlockT, RA> {
+	client: Arc<Client<B, E, Block, RA>>,
+	/// Metadata of the most recently used runtimes, the most recent one last.
+	cache: Mutex<VecDeque<(u32, Arc<Metadata>)>>,
+}
+
+impl<B, E, Block, RA> MetadataCache<B, E, Block, RA> where
+	B: backend::Backend<Block>,
+	E: CallExecutor<Block> + Send + Sync,
+	Block: BlockT,
+	Client<B, E, Block, RA>: ProvideRuntimeApi<Block>,
+	<Client<B, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
+		sp_api::Metadata<Block, Error = sp_blockchain::Error>,
+{
+	/// Create a cache of the metadata of `client`'s runtimes.
+	pub fn new(client: Arc<Client<B, E, Block, RA>>) -> Self {
+		MetadataCache { client, cache: Mutex::new(VecDeque::new()) }
+	}
+
+	/// The spec version and metadata of the runtime of the given block.
+	pub fn metadata(&self, id: &BlockId<Block>) -> Result<(u32, Arc<Metadata>), String> {
+		let spec_version = self.client.runtime_version_at(id).map_err(|e| e.to_string())?.spec_version;
+		{
+			let mut cache = self.cache.lock();
+			if let Some(position) = cache.iter().position(|(version, _)| *version == spec_version) {
+				let entry = cache.remove(position).expect("position is in bounds; qed");
+				let metadata = entry.1.clone();
+				cache.push_back(entry);
+				return Ok((spec_version, metadata));
+			}
+		}
+
+		let encoded = self.client.runtime_api().metadata(id).map_err(|e| e.to_string())?;
+		let metadata = Arc::new(Metadata::new(&encoded, TypeRegistry::default()).map_err(|e| e.to_string())?);
+
+		let mut cache = self.cache.lock();
+		if cache.len() >= MAX_CACHED_METADATA {
+			cache.pop_front();
+		}
+		cache.push_back((spec_version, metadata.clone()));
+		Ok((spec_version, metadata))
+	}
+}
+
+/// Decodes the blocks and events of a client for the chain RPC.
+pub struct RuntimeDecoder<B, E, Block: BlockT, RA> {
+	client: Arc<Client<B, E, Block, RA>>,
+	metadata: MetadataCache<B, E, Block, RA>,
+}
+
+impl<B, E, Block, RA> RuntimeDecoder<B, E, Block, RA> where
+	B: backend::Backend<Block>,
+	E: CallExecutor<Block> + Send + Sync,
+	Block: BlockT,
+	Client<B, E, Block, RA>: ProvideRuntimeApi<Block>,
+	<Client<B, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
+		sp_api::Metadata<Block, Error = sp_blockchain::Error>,
+{
+	/// Create a decoder of `client`'s blocks and events.
+	pub fn new(client: Arc<Client<B, E, Block, RA>>) -> Self {
+		RuntimeDecoder { metadata: MetadataCache::new(client.clone()), client }
+	}
+}
+
+impl<B, E, Block, RA> sc_rpc::chain::RuntimeDecoder<Block> for RuntimeDecoder<B, E, Block, RA> where
+	B: backend::Backend<Block> + Send + Sync,
+	E: CallExecutor<Block> + Send + Sync,
+	Block: BlockT,
+	RA: Send + Sync,
+	Client<B, E, Block, RA>: ProvideRuntimeApi<Block>,
+	<Client<B, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
+		sp_api::Metadata<Block, Error = sp_blockchain::Error>,
+{
+	fn decode_block(&self, hash: &Block::Hash) -> RpcResult<Option<DecodedBlock<Block::Header>>> {
+		let id = BlockId::Hash(*hash);
+		let header = self.client.header(&id).map_err(client_err)?;
+		let body = self.client.body(&id).map_err(client_err)?;
+		let (header, body) = match (header, body) {
+			(Some(header), Some(body)) => (header, body),
+			_ => return Ok(None),
+		};
+		let (spec_version, metadata) = self.metadata.metadata(&id).map_err(RpcError::Other)?;
+
+		let extrinsics = body.into_iter().enumerate().map(|(index, extrinsic)| {
+			let encoded = extrinsic.encode();
+			match metadata.decode_extrinsic(&encoded) {
+				Ok(decoded) => DecodedExtrinsic {
+					encoded: encoded.into(),
+					decoded: Some(extrinsic_details(decoded)),
+					error: None,
+				},
+				Err(err) => {
+					warn!("Failed to decode extrinsic #{} of block {}: {}", index, hash, err);
+					DecodedExtrinsic { encoded: encoded.into(), decoded: None, error: Some(err.to_string()) }
+				},
+			}
+		}).collect();
+
+		Ok(Some(DecodedBlock { header, spec_version, extrinsics }))
+	}
+
+	fn decode_events(&self, hash: &Block::Hash) -> RpcResult<Option<DecodedEvents<Block::Hash>>> {
+		let id = BlockId::Hash(*hash);
+		if self.client.header(&id).map_err(client_err)?.is_none() {
+			return Ok(None);
+		}
+		let encoded = self.client.storage(&id, &system_events_key())
+			.map_err(client_err)?
+			.map(|data| data.0)
+			.unwrap_or_default();
+		let (spec_version, metadata) = self.metadata.metadata(&id).map_err(RpcError::Other)?;
+
+		let mut events = Vec::new();
+		let mut error = None;
+		if !encoded.is_empty() {
+			let records = metadata.decode_events::<Block::Hash>(&encoded).map_err(|e| RpcError::Other(e.to_string()))?;
+			for record in records {
+				match record {
+					Ok(record) => events.push(DecodedEvent {
+						extrinsic_index: match record.phase {
+							Phase::ApplyExtrinsic(index) => Some(index),
+							Phase::Finalization => None,
+						},
+						pallet: record.module,
+						event: record.event,
+						args: record.arguments,
+						topics: record.topics,
+					}),
+					Err(err) => error = Some(err.to_string()),
+				}
+			}
+		}
+
+		Ok(Some(DecodedEvents { spec_version, encoded: encoded.into(), events, error }))
+	}
+}
+
+fn extrinsic_details(extrinsic: Extrinsic) -> ExtrinsicDetails {
+	ExtrinsicDetails {
+		version: extrinsic.version,
+		signature: extrinsic.signature.map(|signature| ExtrinsicSignature {
+			address: signature.address,
+			signature: signature.signature,
+			signed_extensions: signature.extensions.into_iter()
+				.map(|(identifier, value)| SignedExtension { identifier, value })
+				.collect(),
+		}),
+		call: extrinsic.call,
+	}
+}
+
+fn client_err(err: sp_blockchain::Error) -> RpcError {
+	RpcError::Client(Box::new(err))
+}
@@ -0,0 +1,303 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Index of the blocks including extrinsics.
+//!
+//! The indexer follows the canon chain block by block and stores the number and hash
+//! of the including block and the position in it for the hash of every extrinsic.
+//! Only block bodies are read, so blocks with pruned state are indexed as well.
+//!
+//! Entries of retracted blocks are kept in the index, lookups have to check whether
+//! the including block is still canon.
+
+use std::{collections::HashMap, marker::PhantomData, sync::Arc, time::Duration};
+
+use codec::{Decode, Encode};
+use futures::{channel::mpsc, future::ready, prelude::*, stream};
+use log::{debug, warn};
+use sc_client::{Client, CallExecutor, BlockchainEvents};
+use sc_client_api::backend::{self, IndexStorage};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, NumberFor, SaturatedConversion},
+};
+
+/// Maximal number of blocks indexed at once.
+const BATCH_SIZE: u64 = 1024;
+/// Interval of indexing new blocks independently of block notifications, which are
+/// not sent during major sync.
+const TICK_INTERVAL: Duration = Duration::from_secs(6);
+
+/// The next block to index.
+const META_NEXT: &[u8] = b"meta:next";
+
+/// Key of the hash of the canon block with the given number.
+fn canon_key(number: u64) -> Vec<u8> {
+	(b'c', number).encode()
+}
+
+/// Key of the blocks including the extrinsic with the given hash.
+fn inclusions_key<Hash: Encode>(hash: &Hash) -> Vec<u8> {
+	(b'x', hash).encode()
+}
+
+/// An entry of the index: block number, block hash and index of the extrinsic.
+type Inclusion<Hash> = (u64, Hash, u32);
+
+/// The transaction index, stored in an `IndexStorage`.
+pub struct TransactionIndex<Block: BlockT> {
+	storage: Arc<dyn IndexStorage>,
+	_marker: PhantomData<Block>,
+}
+
+impl<Block: BlockT> TransactionIndex<Block> {
+	/// Create a transaction index stored in `storage`.
+	pub fn new(storage: Arc<dyn IndexStorage>) -> Self {
+		TransactionIndex { storage, _marker: PhantomData }
+	}
+
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		self.storage.get(key).and_then(|value| T::decode(&mut &value[..]).ok())
+	}
+
+	/// The next block to index.
+	pub fn next(&self) -> u64 {
+		self.get(META_NEXT).unwrap_or(0)
+	}
+
+	/// Hash of the canon block with the given number at the time it was indexed.
+	pub fn canon_hash(&self, number: u64) -> Option<Block::Hash> {
+		self.get(&canon_key(number))
+	}
+
+	/// All indexed blocks including the extrinsic with the given hash.
+	pub fn inclusions(&self, hash: &Block::Hash) -> Vec<Inclusion<Block::Hash>> {
+		self.get(&inclusions_key(hash)).unwrap_or_default()
+	}
+
+	/// Start a batch of changes to the index.
+	pub fn batch(&self) -> Batch<Block> {
+		Batch { index: self, changes: Default::default() }
+	}
+}
+
+impl<Block: BlockT> sc_rpc::author::TransactionIndex<Block> for TransactionIndex<Block> {
+	fn inclusions(&self, hash: &Block::Hash) -> Vec<(NumberFor<Block>, Block::Hash, u32)> {
+		TransactionIndex::inclusions(self, hash).into_iter()
+			.map(|(number, block_hash, index)| (number.saturated_into(), block_hash, index))
+			.collect()
+	}
+}
+
+/// Changes to the transaction index, written atomically on commit.
+pub struct Batch<'a, Block: BlockT> {
+	index: &'a TransactionIndex<Block>,
+	changes: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl<'a, Block: BlockT> Batch<'a, Block> {
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		match self.changes.get(key) {
+			Some(value) => T::decode(&mut &value[..]).ok(),
+			None => self.index.get(key),
+		}
+	}
+
+	fn put<T: Encode>(&mut self, key: Vec<u8>, value: &T) {
+		self.changes.insert(key, value.encode());
+	}
+
+	/// Set the next block to index.
+	pub fn set_next(&mut self, next: u64) {
+		self.put(META_NEXT.to_vec(), &next);
+	}
+
+	/// Add the extrinsics of a block, making it the canon block of its number.
+	pub fn insert_block(&mut self, number: u64, hash: Block::Hash, extrinsics: Vec<Block::Hash>) {
+		self.put(canon_key(number), &hash);
+
+		for (index, extrinsic) in extrinsics.iter().enumerate() {
+			let key = inclusions_key(extrinsic);
+			let mut entries: Vec<Inclusion<Block::Hash>> = self.get(&key).unwrap_or_default();
+			let entry = (number, hash, index as u32);
+			// The block might have been retracted and be canon again.
+			if !entries.contains(&entry) {
+				entries.push(entry);
+				self.put(key, &entries);
+			}
+		}
+	}
+
+	/// Write the changes to the index storage.
+	pub fn commit(self) -> sp_blockchain::Result<()> {
+		self.index.storage.commit(self.changes.into_iter().map(|(key, value)| (key, Some(value))).collect())
+	}
+}
+
+/// Indexes the extrinsics of the canon chain.
+pub struct TransactionIndexer<B, E, Block: BlockT, RA> {
+	client: Arc<Client<B, E, Block, RA>>,
+	index: Arc<TransactionIndex<Block>>,
+}
+
+impl<B, E, Block, RA> TransactionIndexer<B, E, Block, RA> where
+	B: backend::Backend<Block>,
+	E: CallExecutor<Block> + Send + Sync,
+	Block: BlockT,
+{
+	/// Create an indexer of the extrinsics of `client`'s blocks.
+	pub fn new(client: Arc<Client<B, E, Block, RA>>, index: Arc<TransactionIndex<Block>>) -> Self {
+		TransactionIndexer { client, index }
+	}
+
+	/// Index the next batch of canon blocks, returns whether the index is up to date.
+	pub fn index_batch(&mut self) -> sp_blockchain::Result<bool> {
+		let best = self.client.chain_info().best_number.saturated_into::<u64>();
+		let mut next = self.index.next();
+
+		// Blocks retracted by a reorg have to be indexed again.
+		while next > 0 {
+			let canon = self.client.block_hash((next - 1).saturated_into())?;
+			if canon.is_some() && canon == self.index.canon_hash(next - 1) {
+				break;
+			}
+			next -= 1;
+		}
+
+		let index = self.index.clone();
+		let mut batch = index.batch();
+		let end = best.min(next.saturating_add(BATCH_SIZE - 1));
+		while next <= end {
+			let hash = match self.client.block_hash(next.saturated_into())? {
+				Some(hash) => hash,
+				None => break,
+			};
+			let extrinsics = match self.client.body(&BlockId::Hash(hash))? {
+				Some(body) => body.iter()
+					.map(|extrinsic| extrinsic.using_encoded(<<Block::Header as HeaderT>::Hashing as HashT>::hash))
+					.collect(),
+				None => {
+					warn!("Body of block #{} is not available, its extrinsics are not indexed", next);
+					Vec::new()
+				},
+			};
+			batch.insert_block(next, hash, extrinsics);
+			next += 1;
+		}
+
+		batch.set_next(next);
+		batch.commit()?;
+
+		debug!(target: "transaction-index", "Indexed extrinsics up to block #{}", next.saturating_sub(1));
+		Ok(next > best)
+	}
+
+	/// Keep indexing new blocks, whenever blocks are imported or periodically.
+	pub fn run(mut self) -> impl Future<Output = ()> {
+		let (retry_tx, retry_rx) = mpsc::unbounded();
+		let _ = retry_tx.unbounded_send(());
+
+		let imports = self.client.import_notification_stream().map(drop);
+		let ticks = stream::unfold((), |()| futures_timer::Delay::new(TICK_INTERVAL).map(|()| Some(((), ()))));
+
+		stream::select(imports, stream::select(ticks, retry_rx))
+			.for_each(move |()| {
+				match self.index_batch() {
+					Ok(true) => (),
+					// Continue with the next batch after other tasks had the chance to run.
+					Ok(false) => { let _ = retry_tx.unbounded_send(()); },
+					Err(err) => warn!("Failed to index extrinsics: {}", err),
+				}
+				ready(())
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parking_lot::Mutex;
+	use substrate_test_runtime_client::runtime::{Block, H256};
+
+	#[derive(Default)]
+	struct MemoryStorage(Mutex<HashMap<Vec<u8>, Vec<u8>>>);
+
+	impl IndexStorage for MemoryStorage {
+		fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+			self.0.lock().get(key).cloned()
+		}
+
+		fn keys_with_prefix(&self, prefix: &[u8]) -> Vec<Vec<u8>> {
+			self.0.lock().keys().filter(|key| key.starts_with(prefix)).cloned().collect()
+		}
+
+		fn commit(&self, changes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> sp_blockchain::Result<()> {
+			let mut storage = self.0.lock();
+			for (key, value) in changes {
+				match value {
+					Some(value) => storage.insert(key, value),
+					None => storage.remove(&key),
+				};
+			}
+			Ok(())
+		}
+	}
+
+	fn hash(n: u64) -> H256 {
+		H256::from_low_u64_be(n)
+	}
+
+	#[test]
+	fn should_index_extrinsics_of_blocks() {
+		let index = TransactionIndex::<Block>::new(Arc::new(MemoryStorage::default()));
+		let (xt1, xt2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		let fork = H256::repeat_byte(3);
+
+		let mut batch = index.batch();
+		batch.insert_block(1, hash(1), vec![xt1]);
+		batch.insert_block(2, hash(2), vec![xt2, xt1]);
+		batch.set_next(3);
+		batch.commit().unwrap();
+
+		assert_eq!(index.next(), 3);
+		assert_eq!(index.canon_hash(2), Some(hash(2)));
+		assert_eq!(index.inclusions(&xt1), vec![(1, hash(1), 0), (2, hash(2), 1)]);
+		assert_eq!(index.inclusions(&xt2), vec![(2, hash(2), 0)]);
+
+		// A reorg replaces block 2, which later becomes canon again.
+		let mut batch = index.batch();
+		batch.insert_block(2, fork, vec![xt2]);
+		batch.commit().unwrap();
+		let mut batch = index.batch();
+		batch.insert_block(2, hash(2), vec![xt2, xt1]);
+		batch.commit().unwrap();
+
+		assert_eq!(index.canon_hash(2), Some(hash(2)));
+		assert_eq!(index.inclusions(&xt2), vec![(2, hash(2), 0), (2, fork, 0)]);
+		assert_eq!(index.inclusions(&H256::repeat_byte(4)), vec![]);
+	}
+
+	#[test]
+	fn should_index_genesis_block() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let index = Arc::new(TransactionIndex::<Block>::new(Arc::new(MemoryStorage::default())));
+		let mut indexer = TransactionIndexer::new(client.clone(), index.clone());
+
+		assert!(indexer.index_batch().unwrap());
+		assert_eq!(index.next(), 1);
+		assert_eq!(index.canon_hash(0), Some(client.chain_info().genesis_hash));
+	}
+}
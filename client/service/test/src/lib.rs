@@ -198,11 +198,20 @@ fn node_config<G, E: Clone> (
 		rpc_ws: None,
 		rpc_ws_max_connections: None,
 		rpc_cors: None,
+		rpc_methods: Default::default(),
+		rpc_max_payload: None,
+		rpc_max_response_size: None,
+		rpc_rate_limit: None,
+		rpc_ws_allowed_ips: None,
 		grafana_port: None,
+		prometheus_config: None,
+		health_config: None,
 		telemetry_endpoints: None,
 		telemetry_external_transport: None,
 		default_heap_pages: None,
 		offchain_worker: false,
+		event_index: false,
+		transaction_index: false,
 		sentry_mode: false,
 		force_authoring: false,
 		disable_grandpa: false,
@@ -61,6 +61,7 @@ use sp_api::{
 	CallApiAtParams,
 };
 use sc_block_builder::BlockBuilderApi;
+use prometheus_endpoint::Registry;
 
 pub use sc_client_api::{
 	backend::{
@@ -83,6 +84,7 @@ use crate::{
 	call_executor::LocalCallExecutor,
 	light::{call_executor::prove_execution, fetcher::ChangesProof},
 	in_mem, genesis, cht, block_rules::{BlockRules, LookupResult as BlockLookupResult},
+	metrics::{Metrics, origin_label},
 };
 
 /// Substrate Client
@@ -96,6 +98,7 @@ pub struct Client<B, E, Block, RA> where Block: BlockT {
 	importing_block: RwLock<Option<Block::Hash>>,
 	block_rules: BlockRules<Block>,
 	execution_extensions: ExecutionExtensions<Block>,
+	metrics: Option<Metrics>,
 	_phantom: PhantomData<RA>,
 }
 
@@ -207,6 +210,7 @@ pub fn new_with_backend<B, E, Block, S, RA>(
 		Default::default(),
 		Default::default(),
 		extensions,
+		None,
 	)
 }
 
@@ -231,6 +235,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		fork_blocks: ForkBlocks<Block>,
 		bad_blocks: BadBlocks<Block>,
 		execution_extensions: ExecutionExtensions<Block>,
+		prometheus_registry: Option<Registry>,
 	) -> sp_blockchain::Result<Self> {
 		if backend.blockchain().header(BlockId::Number(Zero::zero()))?.is_none() {
 			let genesis_storage = build_genesis_storage.build_storage()?;
@@ -260,6 +265,11 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			importing_block: Default::default(),
 			block_rules: BlockRules::new(fork_blocks, bad_blocks),
 			execution_extensions,
+			metrics: match prometheus_registry {
+				Some(registry) => Some(Metrics::register(&registry)
+					.map_err(|e| Error::Msg(format!("Failed to register client metrics: {}", e)))?),
+				None => None,
+			},
 			_phantom: Default::default(),
 		})
 	}
@@ -274,6 +284,16 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		self.backend.state_at(*block)
 	}
 
+	/// Keep the state of the given block from being pruned until `unpin_block` is called.
+	pub fn pin_block(&self, hash: &Block::Hash) -> sp_blockchain::Result<()> {
+		self.backend.pin_block(hash)
+	}
+
+	/// Release a pin previously taken with `pin_block`.
+	pub fn unpin_block(&self, hash: &Block::Hash) {
+		self.backend.unpin_block(hash)
+	}
+
 	/// Given a `BlockId` and a key prefix, return the matching storage keys in that block.
 	pub fn storage_keys(&self, id: &BlockId<Block>, key_prefix: &StorageKey) -> sp_blockchain::Result<Vec<StorageKey>> {
 		let keys = self.state_at(id)?.keys(&key_prefix.0).into_iter().map(StorageKey).collect();
@@ -1305,6 +1325,11 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		self.backend.blockchain().info()
 	}
 
+	/// Get the hashes of all the leaves of the block tree.
+	pub fn leaves(&self) -> sp_blockchain::Result<Vec<Block::Hash>> {
+		self.backend.blockchain().leaves()
+	}
+
 	/// Get block status.
 	pub fn block_status(&self, id: &BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
 		// this can probably be implemented more efficiently
@@ -1599,18 +1624,40 @@ impl<B, E, Block, RA> sp_consensus::BlockImport<Block> for &Client<B, E, Block,
 		mut import_block: BlockImportParams<Block, backend::TransactionFor<B, Block>>,
 		new_cache: HashMap<CacheKeyId, Vec<u8>>,
 	) -> Result<ImportResult, Self::Error> {
-		if let Some(res) = self.prepare_block_storage_changes(&mut import_block).map_err(|e| {
-			warn!("Block prepare storage changes error:\n{:?}", e);
-			ConsensusError::ClientImport(e.to_string())
-		})? {
-			return Ok(res)
-		}
+		let number = *import_block.header.number();
+		let span = tracing::span!(
+			target: sc_tracing::BLOCK_IMPORT_TARGET,
+			tracing::Level::INFO,
+			"import_block",
+			number = %number,
+			hash = ?import_block.post_header().hash(),
+		);
+		let _enter = span.enter();
+		sc_tracing::logging::with_log_context("block", number, || {
+			if let Some(res) = self.prepare_block_storage_changes(&mut import_block).map_err(|e| {
+				warn!("Block prepare storage changes error:\n{:?}", e);
+				ConsensusError::ClientImport(e.to_string())
+			})? {
+				return Ok(res)
+			}
 
-		self.lock_import_and_run(|operation| {
-			self.apply_block(operation, import_block, new_cache)
-		}).map_err(|e| {
-			warn!("Block import error:\n{:?}", e);
-			ConsensusError::ClientImport(e.to_string()).into()
+			let timer = self.metrics.as_ref().map(|metrics| metrics.block_import_time
+				.with_label_values(&[origin_label(import_block.origin)])
+				.start_timer()
+			);
+			let result = self.lock_import_and_run(|operation| {
+				self.apply_block(operation, import_block, new_cache)
+			}).map_err(|e| {
+				warn!("Block import error:\n{:?}", e);
+				ConsensusError::ClientImport(e.to_string()).into()
+			});
+			// Only successful imports are timed.
+			match (timer, &result) {
+				(Some(timer), Ok(ImportResult::Imported(_))) => timer.observe_duration(),
+				(Some(timer), _) => timer.stop_and_discard(),
+				(None, _) => {},
+			}
+			result
 		})
 	}
 
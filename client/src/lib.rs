@@ -84,6 +84,7 @@ pub mod leaves;
 mod call_executor;
 mod client;
 mod block_rules;
+mod metrics;
 
 pub use sc_client_api::{
 	blockchain,
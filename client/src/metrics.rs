@@ -0,0 +1,54 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the client.
+
+use prometheus_endpoint::{
+	register, HistogramOpts, HistogramVec, PrometheusError, Registry, DEFAULT_BUCKETS,
+};
+use sp_consensus::BlockOrigin;
+
+/// Metrics of block import.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+	pub(crate) block_import_time: HistogramVec,
+}
+
+impl Metrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Metrics {
+			block_import_time: register(HistogramVec::new(
+				HistogramOpts::new(
+					"substrate_block_import_time",
+					"Time taken to import a block, in seconds",
+				).buckets(DEFAULT_BUCKETS.to_vec()),
+				&["origin"],
+			)?, registry)?,
+		})
+	}
+}
+
+/// The label of a block origin.
+pub(crate) fn origin_label(origin: BlockOrigin) -> &'static str {
+	match origin {
+		BlockOrigin::Genesis => "genesis",
+		BlockOrigin::NetworkInitialSync => "network_initial_sync",
+		BlockOrigin::NetworkBroadcast => "network_broadcast",
+		BlockOrigin::ConsensusBroadcast => "consensus_broadcast",
+		BlockOrigin::Own => "own",
+		BlockOrigin::File => "file",
+	}
+}
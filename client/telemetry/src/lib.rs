@@ -32,6 +32,20 @@
 //!
 //! > **Note**: Cloning the [`Telemetry`] and polling from multiple clones has an unspecified behaviour.
 //!
+//! # Endpoints
+//!
+//! The kind of an endpoint is selected by the scheme of its URL:
+//!
+//! - `ws://` and `wss://` URLs, as well as multiaddresses, are telemetry servers reached over
+//!   WebSocket.
+//! - `tcp://host:port` is a server reached over TCP, which receives one JSON object per line.
+//! - `file://path` is a file receiving one JSON object per line. The file is rotated once it
+//!   grows larger than `max_size` bytes, keeping `max_files` rotated files, both can be given as
+//!   query parameters, e.g. `file:///var/log/telemetry.json?max_size=1048576&max_files=3`.
+//! - `stdout://` is the standard output, receiving one JSON object per line.
+//!
+//! Each endpoint has its own maximum verbosity level.
+//!
 //! # Example
 //!
 //! ```no_run
@@ -63,7 +77,7 @@ use libp2p::{Multiaddr, wasm_ext};
 use log::{error, warn};
 use parking_lot::Mutex;
 use serde::{Serialize, Deserialize};
-use std::{pin::Pin, sync::Arc, task::{Context, Poll}, time::{Duration, Instant}};
+use std::{path::PathBuf, pin::Pin, sync::Arc, task::{Context, Poll}, time::{Duration, Instant}};
 
 pub use libp2p::wasm_ext::ExtTransport;
 pub use slog_scope::with_logger;
@@ -72,9 +86,15 @@ pub use slog;
 mod async_record;
 mod worker;
 
+/// Size in bytes after which telemetry files are rotated by default.
+const DEFAULT_FILE_MAX_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Number of rotated telemetry files which are kept by default.
+const DEFAULT_FILE_MAX_FILES: usize = 5;
+
 /// Configuration for telemetry.
 pub struct TelemetryConfig {
-	/// Collection of telemetry WebSocket servers with a corresponding verbosity level.
+	/// Collection of telemetry endpoints with a corresponding verbosity level.
 	pub endpoints: TelemetryEndpoints,
 
 	/// Optional external implementation of a libp2p transport. Used in WASM contexts where we need
@@ -90,10 +110,11 @@ pub struct TelemetryConfig {
 	pub wasm_external_transport: Option<wasm_ext::ExtTransport>,
 }
 
-/// List of telemetry servers we want to talk to. Contains the URL of the server, and the
+/// List of telemetry endpoints we want to send to. Contains the URL of the endpoint, and the
 /// maximum verbosity level.
 ///
-/// The URL string can be either a URL or a multiaddress.
+/// The URL string can be either a URL or a multiaddress, see the crate root documentation for
+/// the supported schemes.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TelemetryEndpoints(Vec<(String, u8)>);
 
@@ -150,8 +171,8 @@ pub fn init_telemetry(config: TelemetryConfig) -> Telemetry {
 	// Build the list of telemetry endpoints.
 	let mut endpoints = Vec::new();
 	for &(ref url, verbosity) in &config.endpoints.0 {
-		match url_to_multiaddr(url) {
-			Ok(addr) => endpoints.push((addr, verbosity)),
+		match parse_endpoint(url) {
+			Ok(endpoint) => endpoints.push((endpoint, verbosity)),
 			Err(err) => warn!(target: "telemetry", "Invalid telemetry URL {}: {}", url, err),
 		}
 	}
@@ -270,6 +291,53 @@ impl slog::Drain for TelemetryDrain {
 	}
 }
 
+/// Parses the URL of a telemetry endpoint.
+fn parse_endpoint(url: &str) -> Result<worker::Endpoint, String> {
+	if url == "stdout://" {
+		return Ok(worker::Endpoint::Stdout);
+	}
+
+	if url.starts_with("tcp://") {
+		let address = &url["tcp://".len()..];
+		let (host, port) = match address.rfind(':') {
+			Some(pos) => (&address[..pos], &address[pos + 1..]),
+			None => return Err("Missing port".into()),
+		};
+		let port = port.parse::<u16>().map_err(|err| format!("Invalid port: {}", err))?;
+		let host = host.trim_start_matches('[').trim_end_matches(']');
+		let addr = match host.parse::<std::net::IpAddr>() {
+			Ok(std::net::IpAddr::V4(ip)) => format!("/ip4/{}/tcp/{}", ip, port),
+			Ok(std::net::IpAddr::V6(ip)) => format!("/ip6/{}/tcp/{}", ip, port),
+			Err(_) if !host.is_empty() => format!("/dns4/{}/tcp/{}", host, port),
+			Err(_) => return Err("Missing host".into()),
+		};
+		return addr.parse().map(worker::Endpoint::Tcp).map_err(|err| err.to_string());
+	}
+
+	if url.starts_with("file://") {
+		let mut parts = url["file://".len()..].splitn(2, '?');
+		let path = parts.next().unwrap_or_default();
+		if path.is_empty() {
+			return Err("Missing path".into());
+		}
+		let mut max_size = DEFAULT_FILE_MAX_SIZE;
+		let mut max_files = DEFAULT_FILE_MAX_FILES;
+		for param in parts.next().into_iter().flat_map(|query| query.split('&')) {
+			let mut param = param.splitn(2, '=');
+			match (param.next(), param.next()) {
+				(Some("max_size"), Some(value)) =>
+					max_size = value.parse().map_err(|err| format!("Invalid max_size: {}", err))?,
+				(Some("max_files"), Some(value)) =>
+					max_files = value.parse().map_err(|err| format!("Invalid max_files: {}", err))?,
+				(name, _) => return Err(format!("Unknown parameter {:?}", name.unwrap_or_default())),
+			}
+		}
+		return Ok(worker::Endpoint::File { path: PathBuf::from(path), max_size, max_files });
+	}
+
+	url_to_multiaddr(url).map(worker::Endpoint::WebSocket).map_err(|err| err.to_string())
+}
+
 /// Parses a WebSocket URL into a libp2p `Multiaddr`.
 fn url_to_multiaddr(url: &str) -> Result<Multiaddr, libp2p::multiaddr::Error> {
 	// First, assume that we have a `Multiaddr`.
@@ -299,3 +367,54 @@ macro_rules! telemetry {
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use worker::Endpoint;
+
+	#[test]
+	fn parses_endpoints() {
+		assert_eq!(
+			parse_endpoint("wss://telemetry.polkadot.io/submit/"),
+			Ok(Endpoint::WebSocket("/dns4/telemetry.polkadot.io/tcp/443/x-parity-wss/%2Fsubmit%2F".parse().unwrap())),
+		);
+		assert_eq!(
+			parse_endpoint("/ip4/127.0.0.1/tcp/8000/ws"),
+			Ok(Endpoint::WebSocket("/ip4/127.0.0.1/tcp/8000/ws".parse().unwrap())),
+		);
+		assert_eq!(
+			parse_endpoint("tcp://127.0.0.1:9000"),
+			Ok(Endpoint::Tcp("/ip4/127.0.0.1/tcp/9000".parse().unwrap())),
+		);
+		assert_eq!(parse_endpoint("tcp://[::1]:9000"), Ok(Endpoint::Tcp("/ip6/::1/tcp/9000".parse().unwrap())));
+		assert_eq!(
+			parse_endpoint("tcp://telemetry.local:9000"),
+			Ok(Endpoint::Tcp("/dns4/telemetry.local/tcp/9000".parse().unwrap())),
+		);
+		assert_eq!(parse_endpoint("stdout://"), Ok(Endpoint::Stdout));
+		assert_eq!(
+			parse_endpoint("file:///var/log/telemetry.json"),
+			Ok(Endpoint::File {
+				path: "/var/log/telemetry.json".into(),
+				max_size: DEFAULT_FILE_MAX_SIZE,
+				max_files: DEFAULT_FILE_MAX_FILES,
+			}),
+		);
+		assert_eq!(
+			parse_endpoint("file://telemetry.json?max_size=1024&max_files=0"),
+			Ok(Endpoint::File { path: "telemetry.json".into(), max_size: 1024, max_files: 0 }),
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_endpoints() {
+		assert!(parse_endpoint("tcp://127.0.0.1").is_err());
+		assert!(parse_endpoint("tcp://:9000").is_err());
+		assert!(parse_endpoint("tcp://127.0.0.1:port").is_err());
+		assert!(parse_endpoint("file://").is_err());
+		assert!(parse_endpoint("file://telemetry.json?max_size=large").is_err());
+		assert!(parse_endpoint("file://telemetry.json?rotate=1").is_err());
+		assert!(parse_endpoint("telemetry").is_err());
+	}
+}
@@ -19,7 +19,7 @@
 //! # Usage
 //!
 //! - Create a `TelemetryWorker` with `TelemetryWorker::new`.
-//! - Send messages to the telemetry with `TelemetryWorker::send_message`. Messages will only be
+//! - Send messages to the telemetry with `TelemetryWorker::log`. Messages will only be
 //!   sent to the appropriate targets. Messages may be ignored if the target happens to be
 //!   temporarily unreachable.
 //! - You must appropriately poll the worker with `TelemetryWorker::poll`. Polling will/may produce
@@ -31,8 +31,9 @@ use futures::{prelude::*, ready};
 use libp2p::{core::transport::OptionalTransport, Multiaddr, Transport, wasm_ext};
 use log::{trace, warn, error};
 use slog::Drain;
-use std::{io, pin::Pin, task::Context, task::Poll, time};
+use std::{io, path::PathBuf, pin::Pin, task::Context, task::Poll, time};
 
+mod local;
 mod node;
 
 /// Timeout after which a connection attempt is considered failed. Includes the WebSocket HTTP
@@ -47,17 +48,41 @@ pub enum TelemetryWorkerEvent {
 	Connected,
 }
 
+/// Where the telemetry messages are sent to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Endpoint {
+	/// A telemetry server reached over WebSocket.
+	WebSocket(Multiaddr),
+	/// A server reached over TCP, receiving one JSON object per line.
+	Tcp(Multiaddr),
+	/// A file receiving one JSON object per line.
+	File {
+		/// Path of the file.
+		path: PathBuf,
+		/// Size in bytes after which the file is rotated.
+		max_size: u64,
+		/// Number of rotated files which are kept.
+		max_files: usize,
+	},
+	/// The standard output, receiving one JSON object per line.
+	Stdout,
+}
+
 /// Telemetry processing machine.
 #[derive(Debug)]
 pub struct TelemetryWorker {
 	/// List of nodes with their maximum verbosity level.
-	nodes: Vec<(node::Node<WsTrans>, u8)>,
+	nodes: Vec<(node::Node<NodeTrans>, u8)>,
+	/// List of local sinks with their maximum verbosity level.
+	local_sinks: Vec<(local::LocalSink, u8)>,
+	/// True if the `Connected` event has been generated for the local sinks.
+	local_sinks_connected: bool,
 }
 
 trait StreamAndSink<I>: Stream + Sink<I> {}
 impl<T: ?Sized + Stream + Sink<I>, I> StreamAndSink<I> for T {}
 
-type WsTrans = libp2p::core::transport::boxed::Boxed<
+type NodeTrans = libp2p::core::transport::boxed::Boxed<
 	Pin<Box<dyn StreamAndSink<
 		BytesMut,
 		Item = Result<BytesMut, io::Error>,
@@ -73,7 +98,7 @@ impl TelemetryWorker {
 	/// to the telemetry, only the targets whose verbosity is higher than the verbosity of the
 	/// message will receive it.
 	pub fn new(
-		endpoints: impl IntoIterator<Item = (Multiaddr, u8)>,
+		endpoints: impl IntoIterator<Item = (Endpoint, u8)>,
 		wasm_external_transport: impl Into<Option<wasm_ext::ExtTransport>>
 	) -> Result<Self, io::Error> {
 		let transport = match wasm_external_transport.into() {
@@ -111,16 +136,49 @@ impl TelemetryWorker {
 			})
 			.boxed();
 
+		// TCP is not available in WASM contexts.
+		#[cfg(not(target_os = "unknown"))]
+		let tcp_transport = Some(libp2p::dns::DnsConfig::new(libp2p::tcp::TcpConfig::new())?
+			.timeout(CONNECT_TIMEOUT)
+			.map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+			.map(|out, _| Box::pin(LineSink::from(out)) as Pin<Box<_>>)
+			.boxed());
+		#[cfg(target_os = "unknown")]
+		let tcp_transport: Option<NodeTrans> = None;
+
+		let mut nodes = Vec::new();
+		let mut local_sinks = Vec::new();
+		for (endpoint, verbosity) in endpoints {
+			match endpoint {
+				Endpoint::WebSocket(addr) => nodes.push((node::Node::new(transport.clone(), addr), verbosity)),
+				Endpoint::Tcp(addr) => match tcp_transport {
+					Some(ref tcp_transport) =>
+						nodes.push((node::Node::new(tcp_transport.clone(), addr), verbosity)),
+					None => warn!(target: "telemetry", "TCP telemetry endpoints are not supported, ignoring {}", addr),
+				},
+				Endpoint::File { path, max_size, max_files } => local_sinks.push((
+					local::LocalSink::File(local::RotatingFile::new(path, max_size, max_files)),
+					verbosity,
+				)),
+				Endpoint::Stdout => local_sinks.push((local::LocalSink::Stdout, verbosity)),
+			}
+		}
+
 		Ok(TelemetryWorker {
-			nodes: endpoints.into_iter().map(|(addr, verbosity)| {
-				let node = node::Node::new(transport.clone(), addr);
-				(node, verbosity)
-			}).collect()
+			nodes,
+			local_sinks,
+			local_sinks_connected: false,
 		})
 	}
 
 	/// Polls the worker for events that happened.
 	pub fn poll(&mut self, cx: &mut Context) -> Poll<TelemetryWorkerEvent> {
+		// Local sinks are always available, they are reported as connected once.
+		if !self.local_sinks_connected && !self.local_sinks.is_empty() {
+			self.local_sinks_connected = true;
+			return Poll::Ready(TelemetryWorkerEvent::Connected);
+		}
+
 		for (node, _) in &mut self.nodes {
 			loop {
 				match node::Node::poll(Pin::new(node), cx) {
@@ -149,8 +207,12 @@ impl TelemetryWorker {
 			}
 		};
 
-		// None of the nodes want that verbosity, so just return without doing any serialization.
-		if self.nodes.iter().all(|(_, node_max_verbosity)| msg_verbosity > *node_max_verbosity) {
+		// None of the endpoints want that verbosity, so just return without doing any
+		// serialization.
+		let max_verbosity = self.nodes.iter().map(|(_, verbosity)| *verbosity)
+			.chain(self.local_sinks.iter().map(|(_, verbosity)| *verbosity))
+			.max();
+		if max_verbosity.map_or(true, |max_verbosity| msg_verbosity > max_verbosity) {
 			trace!(
 				target: "telemetry",
 				"Skipping log entry because verbosity {:?} is too high for all endpoints",
@@ -159,13 +221,19 @@ impl TelemetryWorker {
 			return Ok(())
 		}
 
-		// Turn the message into JSON.
+		// Turn the message into JSON, terminated by a newline.
 		let serialized = {
 			let mut out = Vec::new();
 			slog_json::Json::default(&mut out).log(record, values).map_err(|_| ())?;
 			out
 		};
 
+		self.send(msg_verbosity, &serialized);
+		Ok(())
+	}
+
+	/// Sends a serialized message to the endpoints whose verbosity is at least `msg_verbosity`.
+	fn send(&mut self, msg_verbosity: u8, serialized: &[u8]) {
 		for (node, node_max_verbosity) in &mut self.nodes {
 			if msg_verbosity > *node_max_verbosity {
 				trace!(target: "telemetry", "Skipping {:?} for log entry with verbosity {:?}",
@@ -174,10 +242,18 @@ impl TelemetryWorker {
 			}
 
 			// `send_message` returns an error if we're not connected, which we silently ignore.
-			let _ = node.send_message(&serialized.clone()[..]);
+			let _ = node.send_message(serialized);
 		}
 
-		Ok(())
+		for (sink, sink_max_verbosity) in &mut self.local_sinks {
+			if msg_verbosity > *sink_max_verbosity {
+				continue;
+			}
+
+			if let Err(err) = sink.write(serialized) {
+				warn!(target: "telemetry", "Failed to write telemetry to {:?}: {}", sink, err);
+			}
+		}
 	}
 }
 
@@ -257,3 +333,119 @@ impl<T: AsyncWrite> Sink<BytesMut> for StreamSink<T> {
 		AsyncWrite::poll_close(this.0, cx)
 	}
 }
+
+/// Wraps around an `AsyncWrite` and implements `Sink`. Contrary to `StreamSink`, each item may be
+/// written in multiple calls to `write`, which is what stream sockets need.
+#[pin_project::pin_project]
+struct LineSink<T>(#[pin] T, BytesMut);
+
+impl<T> From<T> for LineSink<T> {
+	fn from(inner: T) -> LineSink<T> {
+		LineSink(inner, BytesMut::new())
+	}
+}
+
+impl<T: AsyncRead> Stream for LineSink<T> {
+	type Item = Result<BytesMut, io::Error>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+		let this = self.project();
+		let mut buf = [0; 128];
+		match ready!(AsyncRead::poll_read(this.0, cx, &mut buf)) {
+			Ok(0) => Poll::Ready(None),
+			Ok(n) => Poll::Ready(Some(Ok(buf[..n].into()))),
+			Err(err) => Poll::Ready(Some(Err(err))),
+		}
+	}
+}
+
+impl<T: AsyncWrite> LineSink<T> {
+	fn poll_flush_buffer(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), io::Error>> {
+		let mut this = self.project();
+
+		while !this.1.is_empty() {
+			let written = ready!(this.0.as_mut().poll_write(cx, &this.1[..]))?;
+			if written == 0 {
+				return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+			}
+			let _ = this.1.split_to(written);
+		}
+
+		Poll::Ready(Ok(()))
+	}
+}
+
+impl<T: AsyncWrite> Sink<BytesMut> for LineSink<T> {
+	type Error = io::Error;
+
+	fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+		ready!(LineSink::poll_flush_buffer(self, cx))?;
+		Poll::Ready(Ok(()))
+	}
+
+	fn start_send(self: Pin<&mut Self>, item: BytesMut) -> Result<(), Self::Error> {
+		self.project().1.extend_from_slice(&item);
+		Ok(())
+	}
+
+	fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+		ready!(self.as_mut().poll_flush_buffer(cx))?;
+		let this = self.project();
+		AsyncWrite::poll_flush(this.0, cx)
+	}
+
+	fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+		ready!(self.as_mut().poll_flush_buffer(cx))?;
+		let this = self.project();
+		AsyncWrite::poll_close(this.0, cx)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{BufRead, BufReader};
+
+	#[test]
+	fn sends_to_tcp_endpoints_by_verbosity() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let addr: Multiaddr = format!("/ip4/127.0.0.1/tcp/{}", listener.local_addr().unwrap().port())
+			.parse()
+			.unwrap();
+		let mut worker = TelemetryWorker::new(vec![(Endpoint::Tcp(addr), 5)], None).unwrap();
+
+		futures::executor::block_on(future::poll_fn(|cx| worker.poll(cx)));
+		worker.send(6, b"{\"msg\":\"verbose\"}\n");
+		worker.send(5, b"{\"msg\":\"hello\"}\n");
+		futures::executor::block_on(future::poll_fn(|cx| {
+			let _ = worker.poll(cx);
+			Poll::Ready(())
+		}));
+
+		let (stream, _) = listener.accept().unwrap();
+		let mut line = String::new();
+		BufReader::new(stream).read_line(&mut line).unwrap();
+		assert_eq!(line, "{\"msg\":\"hello\"}\n");
+	}
+
+	#[test]
+	fn sends_to_local_endpoints_by_verbosity() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("telemetry.json");
+		let file = |path: &PathBuf| Endpoint::File { path: path.clone(), max_size: 1024, max_files: 1 };
+		let mut worker = TelemetryWorker::new(vec![(file(&path), 0), (file(&path.with_extension("log")), 9)], None)
+			.unwrap();
+
+		match futures::executor::block_on(future::poll_fn(|cx| worker.poll(cx))) {
+			TelemetryWorkerEvent::Connected => (),
+		}
+		worker.send(0, b"{\"msg\":\"info\"}\n");
+		worker.send(9, b"{\"msg\":\"debug\"}\n");
+
+		assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"msg\":\"info\"}\n");
+		assert_eq!(
+			std::fs::read_to_string(path.with_extension("log")).unwrap(),
+			"{\"msg\":\"info\"}\n{\"msg\":\"debug\"}\n",
+		);
+	}
+}
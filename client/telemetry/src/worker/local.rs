@@ -0,0 +1,159 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Contains the sinks writing telemetry messages locally, into a file or to the standard output.
+//!
+//! Messages are written as they are received, one JSON object per line.
+
+use std::{fs::{self, File, OpenOptions}, io::{self, Write}, path::{Path, PathBuf}};
+
+/// A sink writing telemetry messages locally.
+#[derive(Debug)]
+pub enum LocalSink {
+	/// Writes into a file, which is rotated once it grows too large.
+	File(RotatingFile),
+	/// Writes to the standard output.
+	Stdout,
+}
+
+impl LocalSink {
+	/// Writes a message, which must end with a newline.
+	pub fn write(&mut self, message: &[u8]) -> io::Result<()> {
+		match self {
+			LocalSink::File(file) => file.write(message),
+			LocalSink::Stdout => {
+				let stdout = io::stdout();
+				let mut stdout = stdout.lock();
+				stdout.write_all(message)?;
+				stdout.flush()
+			},
+		}
+	}
+}
+
+/// A file which is rotated once writing a message would make it larger than `max_size`.
+///
+/// The rotated files get the suffixes `.1` (the most recent one) to `.{max_files}`, older
+/// ones are removed.
+#[derive(Debug)]
+pub struct RotatingFile {
+	path: PathBuf,
+	max_size: u64,
+	max_files: usize,
+	/// The opened file with its current size, `None` until the first message is written.
+	file: Option<(File, u64)>,
+}
+
+impl RotatingFile {
+	/// Creates a sink appending to the file at `path`, the file is opened when the first
+	/// message is written.
+	pub fn new(path: PathBuf, max_size: u64, max_files: usize) -> Self {
+		RotatingFile { path, max_size, max_files, file: None }
+	}
+
+	fn write(&mut self, message: &[u8]) -> io::Result<()> {
+		let size = match self.file {
+			Some((_, size)) => size,
+			None => self.path.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+		};
+		if size > 0 && size + message.len() as u64 > self.max_size {
+			self.file = None;
+			self.rotate()?;
+		}
+
+		if self.file.is_none() {
+			if let Some(parent) = self.path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+			let size = file.metadata()?.len();
+			self.file = Some((file, size));
+		}
+
+		let (file, size) = self.file.as_mut().expect("the file has been opened above; qed");
+		file.write_all(message)?;
+		*size += message.len() as u64;
+		Ok(())
+	}
+
+	fn rotate(&self) -> io::Result<()> {
+		if self.max_files == 0 {
+			return fs::remove_file(&self.path);
+		}
+
+		let rotated = |index: usize| {
+			let mut path = self.path.clone().into_os_string();
+			path.push(format!(".{}", index));
+			PathBuf::from(path)
+		};
+		remove_if_exists(&rotated(self.max_files))?;
+		for index in (1..self.max_files).rev() {
+			rename_if_exists(&rotated(index), &rotated(index + 1))?;
+		}
+		fs::rename(&self.path, rotated(1))
+	}
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+	match fs::remove_file(path) {
+		Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+		result => result,
+	}
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+	match fs::rename(from, to) {
+		Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+		result => result,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rotates_files() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("telemetry").join("log.json");
+		let mut sink = LocalSink::File(RotatingFile::new(path.clone(), 20, 2));
+
+		for message in &["{\"msg\":\"a\"}\n", "{\"msg\":\"b\"}\n", "{\"msg\":\"c\"}\n", "{\"msg\":\"d\"}\n"] {
+			sink.write(message.as_bytes()).unwrap();
+		}
+
+		let read = |name: &str| fs::read_to_string(dir.path().join("telemetry").join(name)).unwrap();
+		assert_eq!(read("log.json"), "{\"msg\":\"d\"}\n");
+		assert_eq!(read("log.json.1"), "{\"msg\":\"c\"}\n");
+		assert_eq!(read("log.json.2"), "{\"msg\":\"b\"}\n");
+		assert!(!dir.path().join("telemetry").join("log.json.3").exists());
+	}
+
+	#[test]
+	fn appends_to_existing_files() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("log.json");
+		fs::write(&path, "{\"msg\":\"a\"}\n").unwrap();
+
+		let mut sink = LocalSink::File(RotatingFile::new(path.clone(), 1024, 0));
+		sink.write(b"{\"msg\":\"b\"}\n").unwrap();
+		assert_eq!(fs::read_to_string(&path).unwrap(), "{\"msg\":\"a\"}\n{\"msg\":\"b\"}\n");
+
+		let mut sink = LocalSink::File(RotatingFile::new(path.clone(), 30, 0));
+		sink.write(b"{\"msg\":\"c\"}\n").unwrap();
+		assert_eq!(fs::read_to_string(&path).unwrap(), "{\"msg\":\"c\"}\n");
+	}
+}
@@ -34,15 +34,29 @@
 //! let span = tracing::span!(tracing::Level::INFO, "my_span_name", my_number = 10, a_key = "a value");
 //! let _guard = span.enter();
 //! ```
-//! Currently we provide `Log` (default), `Telemetry` and `Grafana` variants for `Receiver`
+//! Currently we provide `Log` (default), `Telemetry`, `Grafana` and `ChromeTrace` variants for
+//! `Receiver`
+//!
+//! Spans and events of the runtime are received as well, also when it is executed in Wasm.
+//! They are entered through the `wasm_tracing` host functions of `sp_io`, which create spans
+//! with the target and name `wasm_tracing` and put the actual ones into fields.
+//!
+//! The `ChromeTrace` receiver writes the spans closed during a block import into a file per
+//! block, in the trace event format of Chrome's `about:tracing`.
+
+pub mod logging;
 
 use std::collections::HashMap;
 use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, ThreadId};
 use std::time::{Duration, Instant};
 
 use parking_lot::Mutex;
 use serde::ser::{Serialize, Serializer, SerializeMap};
+use serde_json::json;
 use slog::{SerdeValue, Value};
 use tracing_core::{
 	event::Event,
@@ -56,6 +70,20 @@ use tracing_core::{
 use grafana_data_source::{self, record_metrics};
 use sc_telemetry::{telemetry, SUBSTRATE_INFO};
 
+/// Target and name of the spans and events entered by the runtime through `sp_io::wasm_tracing`.
+pub const WASM_TRACE_IDENTIFIER: &str = "wasm_tracing";
+
+/// Field of the runtime's spans and events holding their actual target.
+const WASM_TARGET_KEY: &str = "wasm_target";
+
+/// Field of the runtime's spans holding their actual name.
+const WASM_NAME_KEY: &str = "wasm_name";
+
+/// Target of the span covering the import of a block.
+///
+/// The span should record the `number` and `hash` of the block.
+pub const BLOCK_IMPORT_TARGET: &str = "block_import";
+
 /// Used to configure how to receive the metrics
 #[derive(Debug, Clone)]
 pub enum TracingReceiver {
@@ -65,6 +93,8 @@ pub enum TracingReceiver {
 	Telemetry,
 	/// Output to Grafana
 	Grafana,
+	/// Output Chrome trace event files into the given directory, one per imported block
+	ChromeTrace(PathBuf),
 }
 
 impl Default for TracingReceiver {
@@ -76,8 +106,8 @@ impl Default for TracingReceiver {
 #[derive(Debug)]
 struct SpanDatum {
 	id: u64,
-	name: &'static str,
-	target: &'static str,
+	name: String,
+	target: String,
 	level: Level,
 	line: u32,
 	start_time: Instant,
@@ -101,11 +131,27 @@ impl Visit for Visitor {
 		self.record_debug(field, &value)
 	}
 
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.0.push((field.name().to_string(), value.to_string()));
+	}
+
 	fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
 		self.0.push((field.name().to_string(), format!("{:?}",value)));
 	}
 }
 
+impl Visitor {
+	/// Remove the value of the field with the given name.
+	fn take(&mut self, name: &str) -> Option<String> {
+		let position = self.0.iter().position(|(k, _)| k == name)?;
+		Some(self.0.remove(position).1)
+	}
+
+	fn get(&self, name: &str) -> Option<&str> {
+		self.0.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+	}
+}
+
 impl Serialize for Visitor {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 		where S: Serializer,
@@ -146,12 +192,27 @@ impl Value for Visitor {
 	}
 }
 
+#[derive(Debug)]
+struct EventDatum {
+	target: String,
+	level: Level,
+	time: Instant,
+	values: Visitor,
+}
+
+/// The spans and events recorded on a thread during a block import.
+struct ChromeTrace {
+	start_time: Instant,
+	events: Vec<serde_json::Value>,
+}
+
 /// Responsible for assigning ids to new spans, which are not re-used.
 pub struct ProfilingSubscriber {
 	next_id: AtomicU64,
 	targets: Vec<(String, Level)>,
 	receiver: TracingReceiver,
 	span_data: Mutex<HashMap<u64, SpanDatum>>,
+	chrome_traces: Mutex<HashMap<ThreadId, ChromeTrace>>,
 }
 
 impl ProfilingSubscriber {
@@ -165,7 +226,25 @@ impl ProfilingSubscriber {
 			targets,
 			receiver,
 			span_data: Mutex::new(HashMap::new()),
+			chrome_traces: Mutex::new(HashMap::new()),
+		}
+	}
+
+	fn check_target(&self, target: &str, level: &Level) -> bool {
+		if let TracingReceiver::ChromeTrace(_) = self.receiver {
+			if target == BLOCK_IMPORT_TARGET {
+				return true;
+			}
+		}
+		for t in &self.targets {
+			if target.starts_with(t.0.as_str()) && level <= &t.1 {
+				log::debug!("Enabled target: {}, level: {}", target, level);
+				return true;
+			} else {
+				log::debug!("Disabled target: {}, level: {}", target, level);
+			}
 		}
+		false
 	}
 }
 
@@ -188,27 +267,30 @@ fn parse_target(s: &str) -> (String, Level) {
 
 impl Subscriber for ProfilingSubscriber {
 	fn enabled(&self, metadata: &Metadata<'_>) -> bool {
-		for t in &self.targets {
-			if metadata.target().starts_with(t.0.as_str()) && metadata.level() <= &t.1 {
-				log::debug!("Enabled target: {}, level: {}", metadata.target(), metadata.level());
-				return true;
-			} else {
-				log::debug!("Disabled target: {}, level: {}", metadata.target(), metadata.level());
-			}
-		}
-		false
+		// The actual target of the runtime's spans and events is only known once they are
+		// created, they are filtered when they are sent.
+		metadata.target() == WASM_TRACE_IDENTIFIER || self.check_target(metadata.target(), metadata.level())
 	}
 
 	fn new_span(&self, attrs: &Attributes<'_>) -> Id {
 		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
 		let mut values = Visitor(Vec::new());
 		attrs.record(&mut values);
+		let metadata = attrs.metadata();
+		let (name, target) = if metadata.target() == WASM_TRACE_IDENTIFIER {
+			(
+				values.take(WASM_NAME_KEY).unwrap_or_else(|| metadata.name().to_owned()),
+				values.take(WASM_TARGET_KEY).unwrap_or_else(|| metadata.target().to_owned()),
+			)
+		} else {
+			(metadata.name().to_owned(), metadata.target().to_owned())
+		};
 		let span_datum = SpanDatum {
 			id,
-			name: attrs.metadata().name(),
-			target: attrs.metadata().target(),
-			level: attrs.metadata().level().clone(),
-			line: attrs.metadata().line().unwrap_or(0),
+			name,
+			target,
+			level: metadata.level().clone(),
+			line: metadata.line().unwrap_or(0),
 			start_time: Instant::now(),
 			overall_time: Duration::from_nanos(0),
 			values,
@@ -221,13 +303,39 @@ impl Subscriber for ProfilingSubscriber {
 
 	fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
 
-	fn event(&self, _event: &Event<'_>) {}
+	fn event(&self, event: &Event<'_>) {
+		let mut values = Visitor(Vec::new());
+		event.record(&mut values);
+		let metadata = event.metadata();
+		let target = if metadata.target() == WASM_TRACE_IDENTIFIER {
+			values.take(WASM_TARGET_KEY).unwrap_or_else(|| metadata.target().to_owned())
+		} else {
+			metadata.target().to_owned()
+		};
+		if !self.check_target(&target, metadata.level()) {
+			return;
+		}
+
+		let event_datum = EventDatum { target, level: metadata.level().clone(), time: Instant::now(), values };
+		match self.receiver {
+			TracingReceiver::Log => print_event_log(event_datum),
+			TracingReceiver::Telemetry => send_event_telemetry(event_datum),
+			TracingReceiver::Grafana => {},
+			TracingReceiver::ChromeTrace(_) => self.record_chrome_event(event_datum),
+		}
+	}
 
 	fn enter(&self, span: &Id) {
 		let mut span_data = self.span_data.lock();
 		let start_time = Instant::now();
 		if let Some(mut s) = span_data.get_mut(&span.into_u64()) {
 			s.start_time = start_time;
+			if let TracingReceiver::ChromeTrace(_) = self.receiver {
+				if s.target == BLOCK_IMPORT_TARGET {
+					self.chrome_traces.lock().entry(thread::current().id())
+						.or_insert_with(|| ChromeTrace { start_time, events: Vec::new() });
+				}
+			}
 		} else {
 			log::warn!("Tried to enter span {:?} that has already been closed!", span);
 		}
@@ -244,7 +352,9 @@ impl Subscriber for ProfilingSubscriber {
 	fn try_close(&self, span: Id) -> bool {
 		let mut span_data = self.span_data.lock();
 		if let Some(data) = span_data.remove(&span.into_u64()) {
-			self.send_span(data);
+			if self.check_target(&data.target, &data.level) {
+				self.send_span(data);
+			}
 		};
 		true
 	}
@@ -256,8 +366,71 @@ impl ProfilingSubscriber {
 			TracingReceiver::Log => print_log(span_datum),
 			TracingReceiver::Telemetry => send_telemetry(span_datum),
 			TracingReceiver::Grafana => send_grafana(span_datum),
+			TracingReceiver::ChromeTrace(ref dir) => self.record_chrome_span(dir, span_datum),
+		}
+	}
+
+	/// Add the span to the trace of the block being imported on this thread.
+	///
+	/// The trace is written once the span of the block import itself is closed, spans
+	/// closed outside of block imports are dropped.
+	fn record_chrome_span(&self, dir: &Path, span_datum: SpanDatum) {
+		let mut chrome_traces = self.chrome_traces.lock();
+		let thread = thread::current().id();
+		let trace = match chrome_traces.get_mut(&thread) {
+			Some(trace) => trace,
+			None => return,
+		};
+		trace.events.push(json!({
+			"name": span_datum.name,
+			"cat": span_datum.target,
+			"ph": "X",
+			"ts": micros(span_datum.start_time.saturating_duration_since(trace.start_time)),
+			"dur": micros(span_datum.overall_time),
+			"pid": std::process::id(),
+			"tid": 1,
+			"args": span_datum.values,
+		}));
+
+		if span_datum.target == BLOCK_IMPORT_TARGET {
+			let trace = chrome_traces.remove(&thread).expect("trace of the thread exists; qed");
+			let file_name = format!(
+				"block-{}-{}.json",
+				span_datum.values.get("number").unwrap_or("unknown"),
+				span_datum.values.get("hash").unwrap_or("unknown"),
+			);
+			if let Err(e) = write_chrome_trace(dir, &file_name, trace) {
+				log::warn!("Unable to write trace of block import to {}: {}", dir.display(), e);
+			}
 		}
 	}
+
+	fn record_chrome_event(&self, event_datum: EventDatum) {
+		if let Some(trace) = self.chrome_traces.lock().get_mut(&thread::current().id()) {
+			let mut values = event_datum.values;
+			let message = values.take("message").unwrap_or_default();
+			trace.events.push(json!({
+				"name": message,
+				"cat": event_datum.target,
+				"ph": "i",
+				"s": "t",
+				"ts": micros(event_datum.time.saturating_duration_since(trace.start_time)),
+				"pid": std::process::id(),
+				"tid": 1,
+				"args": values,
+			}));
+		}
+	}
+}
+
+fn micros(duration: Duration) -> f64 {
+	duration.as_nanos() as f64 / 1_000.0
+}
+
+fn write_chrome_trace(dir: &Path, file_name: &str, trace: ChromeTrace) -> std::io::Result<()> {
+	fs::create_dir_all(dir)?;
+	let trace = json!({ "traceEvents": trace.events, "displayTimeUnit": "ns" });
+	fs::write(dir.join(file_name), trace.to_string())
 }
 
 fn print_log(span_datum: SpanDatum) {
@@ -281,6 +454,18 @@ fn print_log(span_datum: SpanDatum) {
 	}
 }
 
+fn print_event_log(event_datum: EventDatum) {
+	log::info!("TRACING: event {} {}: {}", event_datum.level, event_datum.target, event_datum.values);
+}
+
+fn send_event_telemetry(event_datum: EventDatum) {
+	telemetry!(SUBSTRATE_INFO; "tracing.event";
+		"target" => event_datum.target,
+		"level" => event_datum.level.to_string(),
+		"values" => event_datum.values
+	);
+}
+
 fn send_telemetry(span_datum: SpanDatum) {
 	telemetry!(SUBSTRATE_INFO; "tracing.profiling";
 		"name" => span_datum.name,
@@ -297,3 +482,59 @@ fn send_grafana(span_datum: SpanDatum) {
 		log::warn!("Unable to send metrics to grafana: {:?}", e);
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn read_trace(dir: &Path) -> Vec<serde_json::Value> {
+		let files = fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect::<Vec<_>>();
+		assert_eq!(files.len(), 1);
+		assert_eq!(files[0].file_name().unwrap(), "block-5-0x01.json");
+		let trace: serde_json::Value = serde_json::from_slice(&fs::read(&files[0]).unwrap()).unwrap();
+		trace["traceEvents"].as_array().unwrap().clone()
+	}
+
+	#[test]
+	fn should_write_chrome_trace_of_block_import() {
+		let dir = tempfile::tempdir().unwrap();
+		let receiver = TracingReceiver::ChromeTrace(dir.path().to_owned());
+		let subscriber = ProfilingSubscriber::new(receiver, "pallet_balances,frame_system=info");
+
+		tracing::subscriber::with_default(subscriber, || {
+			// Closed outside of a block import, dropped.
+			tracing::span!(target: "pallet_balances", tracing::Level::DEBUG, "on_initialize").in_scope(|| {});
+
+			let import = tracing::span!(target: BLOCK_IMPORT_TARGET, tracing::Level::INFO, "import_block", number = 5, hash = "0x01");
+			import.in_scope(|| {
+				tracing::span!(target: "pallet_balances", tracing::Level::DEBUG, "transfer").in_scope(|| {
+					tracing::event!(target: WASM_TRACE_IDENTIFIER, tracing::Level::INFO, wasm_target = "pallet_balances", "Transferred");
+				});
+				tracing::span!(
+					target: WASM_TRACE_IDENTIFIER,
+					tracing::Level::DEBUG,
+					WASM_TRACE_IDENTIFIER,
+					wasm_target = "pallet_balances",
+					wasm_name = "set_balance",
+					who = 1,
+				).in_scope(|| {});
+				// Below the level of the target.
+				tracing::span!(target: "frame_system", tracing::Level::DEBUG, "deposit_event").in_scope(|| {});
+			});
+		});
+
+		let events = read_trace(dir.path());
+		let names = events.iter()
+			.map(|event| (event["cat"].as_str().unwrap(), event["name"].as_str().unwrap(), event["ph"].as_str().unwrap()))
+			.collect::<Vec<_>>();
+		assert_eq!(names, vec![
+			("pallet_balances", "Transferred", "i"),
+			("pallet_balances", "transfer", "X"),
+			("pallet_balances", "set_balance", "X"),
+			(BLOCK_IMPORT_TARGET, "import_block", "X"),
+		]);
+		assert_eq!(events[2]["args"], json!({ "who": "1" }));
+		assert_eq!(events[3]["args"], json!({ "number": "5", "hash": "0x01" }));
+		assert!(events[1]["ts"].as_f64().unwrap() <= events[0]["ts"].as_f64().unwrap());
+	}
+}
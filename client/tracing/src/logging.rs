@@ -0,0 +1,287 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The global logger of the node.
+//!
+//! Records are filtered by directives in the syntax of `RUST_LOG`, e.g. `sync=debug,info`.
+//! Directives can be added while the node is running, later directives override the
+//! level of earlier ones for the same target.
+//!
+//! Besides the human readable output formatted by the caller, records can be written as
+//! JSON objects, one per line. JSON records include the context fields set by the
+//! logging thread with [`with_log_context`], like the number of the block being
+//! imported or the peer whose message is being handled.
+
+use std::{cell::RefCell, fmt, io::Write, sync::atomic::{AtomicBool, Ordering}};
+
+use env_logger::filter::{Builder as FilterBuilder, Filter};
+use lazy_static::lazy_static;
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::RwLock;
+use serde_json::{Map, Value};
+
+/// Module of the host functions logging the records of the runtime.
+const RUNTIME_MODULE: &str = "sp_io";
+
+/// The directives of the log filter.
+struct Directives {
+	/// The directives the logger has been initialized with.
+	initial: Vec<String>,
+	/// The directives added since.
+	added: Vec<String>,
+	filter: Filter,
+}
+
+impl Directives {
+	fn rebuild(&mut self) {
+		let mut builder = FilterBuilder::new();
+		for directives in self.initial.iter().chain(self.added.iter()) {
+			builder.parse(directives);
+		}
+		self.filter = builder.build();
+		log::set_max_level(self.filter.filter());
+	}
+}
+
+lazy_static! {
+	static ref DIRECTIVES: RwLock<Directives> = RwLock::new(Directives {
+		initial: Vec::new(),
+		added: Vec::new(),
+		filter: FilterBuilder::new().build(),
+	});
+}
+
+/// Whether context fields are collected, only the JSON output includes them.
+static CONTEXT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+	static CONTEXT: RefCell<Vec<(&'static str, String)>> = RefCell::new(Vec::new());
+}
+
+/// Removes the innermost context field of the thread when dropped, also on panics.
+struct ContextGuard;
+
+impl Drop for ContextGuard {
+	fn drop(&mut self) {
+		CONTEXT.with(|context| { context.borrow_mut().pop(); });
+	}
+}
+
+/// Run `f` with the field `key` set to `value` for the records it logs on this thread.
+///
+/// The value is only formatted if the log output includes context fields.
+pub fn with_log_context<R>(key: &'static str, value: impl fmt::Display, f: impl FnOnce() -> R) -> R {
+	if !CONTEXT_ENABLED.load(Ordering::Relaxed) {
+		return f();
+	}
+
+	CONTEXT.with(|context| context.borrow_mut().push((key, value.to_string())));
+	let _guard = ContextGuard;
+	f()
+}
+
+/// Format of the log output.
+pub enum LogFormat {
+	/// Records formatted by the given function.
+	Text(Box<dyn Fn(&Record) -> String + Send + Sync>),
+	/// A JSON object per record.
+	Json,
+}
+
+struct Logger {
+	format: LogFormat,
+}
+
+impl Log for Logger {
+	fn enabled(&self, metadata: &Metadata) -> bool {
+		DIRECTIVES.read().filter.enabled(metadata)
+	}
+
+	fn log(&self, record: &Record) {
+		if !DIRECTIVES.read().filter.matches(record) {
+			return;
+		}
+
+		let output = match self.format {
+			LogFormat::Text(ref format) => format(record),
+			LogFormat::Json => format_json(record),
+		};
+		let stderr = std::io::stderr();
+		let _ = writeln!(stderr.lock(), "{}", output);
+	}
+
+	fn flush(&self) {
+		let _ = std::io::stderr().flush();
+	}
+}
+
+/// Install the global logger, filtering records by the given directives.
+///
+/// Each of the `directives` is a comma separated list of directives, applied in order.
+pub fn init_logger(directives: Vec<String>, format: LogFormat) -> Result<(), log::SetLoggerError> {
+	if let LogFormat::Json = format {
+		CONTEXT_ENABLED.store(true, Ordering::Relaxed);
+	}
+
+	{
+		let mut current = DIRECTIVES.write();
+		current.initial = directives;
+		current.added.clear();
+		current.rebuild();
+	}
+	log::set_boxed_logger(Box::new(Logger { format }))
+}
+
+/// Add directives to the log filter, e.g. `sync=trace,afg=debug`.
+///
+/// Returns an error without changing the filter if the directives are invalid.
+pub fn add_directives(directives: &str) -> Result<(), String> {
+	validate(directives)?;
+
+	let mut current = DIRECTIVES.write();
+	current.added.push(directives.to_owned());
+	current.rebuild();
+	Ok(())
+}
+
+/// Reset the log filter to the directives the logger has been initialized with.
+pub fn reset_log_filter() {
+	let mut current = DIRECTIVES.write();
+	current.added.clear();
+	current.rebuild();
+}
+
+/// Check the syntax of the directives, `env_logger` ignores invalid ones with a warning.
+fn validate(directives: &str) -> Result<(), String> {
+	let invalid = |directive: &str| Err(format!("Invalid log directive `{}`", directive));
+
+	let mut parts = directives.split('/');
+	let targets = parts.next().unwrap_or_default();
+	if parts.count() > 1 {
+		return invalid(directives);
+	}
+
+	for directive in targets.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+		let mut parts = directive.split('=');
+		let target = parts.next().unwrap_or_default().trim();
+		let level = parts.next().map(str::trim);
+		if target.is_empty() || parts.next().is_some() {
+			return invalid(directive);
+		}
+		match level {
+			Some(level) if !level.is_empty() && level.parse::<LevelFilter>().is_err() => return invalid(directive),
+			_ => (),
+		}
+	}
+	Ok(())
+}
+
+/// Format a record as a JSON object, including the context fields of the current thread.
+pub fn format_json(record: &Record) -> String {
+	let mut object = Map::new();
+	object.insert(
+		"timestamp".into(),
+		chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true).into(),
+	);
+	object.insert("level".into(), record.level().to_string().into());
+	object.insert("target".into(), record.target().into());
+	if let Some(thread) = std::thread::current().name() {
+		object.insert("thread".into(), thread.into());
+	}
+	if record.module_path().map_or(false, |module| module.starts_with(RUNTIME_MODULE)) {
+		object.insert("runtime".into(), true.into());
+	}
+	CONTEXT.with(|context| for (key, value) in context.borrow().iter() {
+		object.insert((*key).into(), value.clone().into());
+	});
+	object.insert("message".into(), record.args().to_string().into());
+
+	Value::Object(object).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_validate_directives() {
+		assert!(validate("sync=trace,afg=debug").is_ok());
+		assert!(validate("info").is_ok());
+		assert!(validate("sync=trace/import").is_ok());
+		assert!(validate("sync=loud").is_err());
+		assert!(validate("sync=trace=debug").is_err());
+		assert!(validate("=debug").is_err());
+		assert!(validate("sync/a/b").is_err());
+	}
+
+	#[test]
+	fn should_add_and_reset_directives() {
+		let enabled = |target: &str, level: log::Level| {
+			DIRECTIVES.read().filter.enabled(&Metadata::builder().target(target).level(level).build())
+		};
+		{
+			let mut current = DIRECTIVES.write();
+			current.initial = vec!["info,sync=warn".into(), "afg=debug".into()];
+			current.rebuild();
+		}
+		assert!(!enabled("sync", log::Level::Info));
+		assert!(enabled("afg", log::Level::Debug));
+		assert!(enabled("babe", log::Level::Info));
+
+		add_directives("sync=debug").unwrap();
+		assert!(enabled("sync", log::Level::Debug));
+		assert!(add_directives("sync=loud").is_err());
+		add_directives("sync::import=trace").unwrap();
+		assert!(enabled("sync::import", log::Level::Trace));
+		assert!(!enabled("sync", log::Level::Trace));
+
+		reset_log_filter();
+		assert!(!enabled("sync", log::Level::Info));
+		assert!(enabled("afg", log::Level::Debug));
+	}
+
+	#[test]
+	fn should_format_records_as_json() {
+		CONTEXT_ENABLED.store(true, Ordering::Relaxed);
+		let json = with_log_context("block", 5, || with_log_context("peer", "QmPeer", || {
+			format_json(&Record::builder()
+				.args(format_args!("Imported #{}", 5))
+				.level(log::Level::Info)
+				.target("sync")
+				.module_path(Some("sc_client"))
+				.build())
+		}));
+
+		let value: Value = serde_json::from_str(&json).unwrap();
+		assert_eq!(value["level"], "INFO");
+		assert_eq!(value["target"], "sync");
+		assert_eq!(value["block"], "5");
+		assert_eq!(value["peer"], "QmPeer");
+		assert_eq!(value["message"], "Imported #5");
+		assert!(value.get("runtime").is_none());
+		CONTEXT.with(|context| assert!(context.borrow().is_empty()));
+
+		let json = format_json(&Record::builder()
+			.args(format_args!("Hello"))
+			.level(log::Level::Debug)
+			.target("runtime")
+			.module_path(Some("sp_io::logging"))
+			.build());
+		let value: Value = serde_json::from_str(&json).unwrap();
+		assert_eq!(value["runtime"], true);
+		assert!(value.get("block").is_none());
+	}
+}
@@ -16,29 +16,66 @@
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::{
-	collections::HashMap,
+	collections::{HashMap, VecDeque},
 	fmt,
 	hash,
 };
 use serde::Serialize;
 use crate::watcher;
 use sp_runtime::traits;
+use sp_transaction_pool::TransactionStatus;
 use log::{debug, trace, warn};
 
+/// Maximal number of blocks for which included transactions are kept awaiting finality.
+const MAX_FINALITY_WATCHERS: usize = 512;
+/// Maximal number of transactions that left the pool for which the last status is kept.
+const MAX_FINISHED_STATUSES: usize = 4096;
+/// Number of recently finalized blocks remembered, in case transactions are pruned late.
+const MAX_FINALIZED_BLOCKS: usize = 64;
+
 /// Extrinsic pool default listener.
 pub struct Listener<H: hash::Hash + Eq, H2> {
-	watchers: HashMap<H, watcher::Sender<H, H2>>
+	watchers: HashMap<H, watcher::Sender<H, H2>>,
+	/// Last known status of transactions in the pool and of recently finished ones.
+	statuses: HashMap<H, TransactionStatus<H, H2>>,
+	/// Transactions that will not receive any further status updates, oldest first.
+	finished: VecDeque<H>,
+	/// Transactions included in not yet finalized blocks, oldest block first.
+	finality_watchers: VecDeque<(H2, Vec<H>)>,
+	/// Recently finalized blocks, oldest first.
+	finalized_blocks: VecDeque<H2>,
 }
 
 impl<H: hash::Hash + Eq, H2> Default for Listener<H, H2> {
 	fn default() -> Self {
 		Listener {
 			watchers: Default::default(),
+			statuses: Default::default(),
+			finished: Default::default(),
+			finality_watchers: Default::default(),
+			finalized_blocks: Default::default(),
 		}
 	}
 }
 
-impl<H: hash::Hash + traits::Member + Serialize, H2: Clone + fmt::Debug> Listener<H, H2> {
+/// Returns true if no further status updates are expected after given status.
+fn is_final<H, H2>(status: &TransactionStatus<H, H2>) -> bool {
+	match status {
+		TransactionStatus::Future
+		| TransactionStatus::Ready
+		| TransactionStatus::Broadcast(_)
+		| TransactionStatus::InBlock(_) => false,
+		TransactionStatus::Finalized(_)
+		| TransactionStatus::Usurped(_)
+		| TransactionStatus::Dropped
+		| TransactionStatus::Invalid => true,
+	}
+}
+
+impl<
+	H: hash::Hash + traits::Member + Serialize,
+	H2: Clone + PartialEq + fmt::Debug,
+> Listener<H, H2> {
 	fn fire<F>(&mut self, hash: &H, fun: F) where F: FnOnce(&mut watcher::Sender<H, H2>) {
 		let clean = if let Some(h) = self.watchers.get_mut(hash) {
 			fun(h);
@@ -60,17 +97,54 @@ impl<H: hash::Hash + traits::Member + Serialize, H2: Clone + fmt::Debug> Listene
 		sender.new_watcher(hash)
 	}
 
+	/// Returns the last known status of given transaction.
+	pub fn status(&self, hash: &H) -> Option<TransactionStatus<H, H2>> {
+		self.statuses.get(hash).cloned()
+	}
+
+	/// Returns true if given transaction is included in a block that is awaiting finality.
+	pub fn is_awaiting_finality(&self, hash: &H) -> bool {
+		match self.statuses.get(hash) {
+			Some(TransactionStatus::InBlock(block)) =>
+				self.finality_watchers.iter().any(|(b, _)| b == block),
+			_ => false,
+		}
+	}
+
+	fn set_status(&mut self, hash: &H, status: TransactionStatus<H, H2>) {
+		let finished = is_final(&status);
+		self.statuses.insert(hash.clone(), status);
+		if finished {
+			self.finish(hash);
+		}
+	}
+
+	fn finish(&mut self, hash: &H) {
+		self.finished.push_back(hash.clone());
+		while self.finished.len() > MAX_FINISHED_STATUSES {
+			if let Some(old) = self.finished.pop_front() {
+				// the transaction might have been re-submitted in the meantime
+				if self.statuses.get(&old).map_or(false, is_final) {
+					self.statuses.remove(&old);
+				}
+			}
+		}
+	}
+
 	/// Notify the listeners about extrinsic broadcast.
 	pub fn broadcasted(&mut self, hash: &H, peers: Vec<String>) {
 		trace!(target: "txpool", "[{:?}] Broadcasted", hash);
+		self.set_status(hash, TransactionStatus::Broadcast(peers.clone()));
 		self.fire(hash, |watcher| watcher.broadcast(peers));
 	}
 
 	/// New transaction was added to the ready pool or promoted from the future pool.
 	pub fn ready(&mut self, tx: &H, old: Option<&H>) {
 		trace!(target: "txpool", "[{:?}] Ready (replaced: {:?})", tx, old);
+		self.set_status(tx, TransactionStatus::Ready);
 		self.fire(tx, |watcher| watcher.ready());
 		if let Some(old) = old {
+			self.set_status(old, TransactionStatus::Usurped(tx.clone()));
 			self.fire(old, |watcher| watcher.usurped(tx.clone()));
 		}
 	}
@@ -78,12 +152,17 @@ impl<H: hash::Hash + traits::Member + Serialize, H2: Clone + fmt::Debug> Listene
 	/// New transaction was added to the future pool.
 	pub fn future(&mut self, tx: &H) {
 		trace!(target: "txpool", "[{:?}] Future", tx);
+		self.set_status(tx, TransactionStatus::Future);
 		self.fire(tx, |watcher| watcher.future());
 	}
 
 	/// Transaction was dropped from the pool because of the limit.
 	pub fn dropped(&mut self, tx: &H, by: Option<&H>) {
 		trace!(target: "txpool", "[{:?}] Dropped (replaced by {:?})", tx, by);
+		self.set_status(tx, match by {
+			Some(t) => TransactionStatus::Usurped(t.clone()),
+			None => TransactionStatus::Dropped,
+		});
 		self.fire(tx, |watcher| match by {
 			Some(t) => watcher.usurped(t.clone()),
 			None => watcher.dropped(),
@@ -97,12 +176,69 @@ impl<H: hash::Hash + traits::Member + Serialize, H2: Clone + fmt::Debug> Listene
 		} else {
 			debug!(target: "txpool", "Extrinsic invalid: {:?}", tx);
 		}
+		self.set_status(tx, TransactionStatus::Invalid);
 		self.fire(tx, |watcher| watcher.invalid());
 	}
 
 	/// Transaction was pruned from the pool.
 	pub fn pruned(&mut self, header_hash: H2, tx: &H) {
 		debug!(target: "txpool", "[{:?}] Pruned at {:?}", tx, header_hash);
-		self.fire(tx, |watcher| watcher.in_block(header_hash))
+		// the same transaction might be reported more than once for a single block
+		match self.statuses.get(tx) {
+			Some(TransactionStatus::InBlock(block)) | Some(TransactionStatus::Finalized(block))
+				if *block == header_hash => return,
+			_ => (),
+		}
+		self.set_status(tx, TransactionStatus::InBlock(header_hash.clone()));
+		self.fire(tx, |watcher| watcher.in_block(header_hash.clone()));
+
+		// the block might have been finalized before the pool got to prune it
+		if self.finalized_blocks.contains(&header_hash) {
+			self.set_status(tx, TransactionStatus::Finalized(header_hash.clone()));
+			self.fire(tx, |watcher| watcher.finalized(header_hash));
+			return;
+		}
+
+		match self.finality_watchers.iter_mut().find(|(block, _)| *block == header_hash) {
+			Some((_, txs)) => txs.push(tx.clone()),
+			None => self.finality_watchers.push_back((header_hash, vec![tx.clone()])),
+		}
+
+		while self.finality_watchers.len() > MAX_FINALITY_WATCHERS {
+			if let Some((block, txs)) = self.finality_watchers.pop_front() {
+				debug!(target: "txpool", "Stopped waiting for finality of {:?}", block);
+				for tx in txs {
+					if self.statuses.get(&tx) == Some(&TransactionStatus::InBlock(block.clone())) {
+						// dropping the sender terminates the streams
+						self.watchers.remove(&tx);
+						self.finish(&tx);
+					}
+				}
+			}
+		}
+	}
+
+	/// Block with given hash was finalized.
+	pub fn finalized(&mut self, block: H2) {
+		self.finalized_blocks.push_back(block.clone());
+		if self.finalized_blocks.len() > MAX_FINALIZED_BLOCKS {
+			self.finalized_blocks.pop_front();
+		}
+
+		let position = self.finality_watchers.iter().position(|(b, _)| *b == block);
+		let txs = match position.and_then(|p| self.finality_watchers.remove(p)) {
+			Some((_, txs)) => txs,
+			None => return,
+		};
+
+		for tx in txs {
+			// skip transactions that have been retracted and re-imported in the meantime
+			if self.statuses.get(&tx) != Some(&TransactionStatus::InBlock(block.clone())) {
+				continue;
+			}
+			trace!(target: "txpool", "[{:?}] Finalized in {:?}", tx, block);
+			self.set_status(&tx, TransactionStatus::Finalized(block.clone()));
+			self.fire(&tx, |watcher| watcher.finalized(block.clone()));
+		}
 	}
 }
@@ -33,9 +33,10 @@ use sp_runtime::{
 	traits::{self, SaturatedConversion},
 	transaction_validity::{TransactionValidity, TransactionTag as Tag, TransactionValidityError},
 };
-use sp_transaction_pool::{error, PoolStatus};
+use sp_transaction_pool::{error, PoolStatus, TransactionStatus};
 
 use crate::validated_pool::{ValidatedPool, ValidatedTransaction};
+use prometheus_endpoint::Histogram;
 
 /// Modification notification event stream type;
 pub type EventStream<H> = mpsc::UnboundedReceiver<H>;
@@ -120,6 +121,7 @@ impl Default for Options {
 /// Extrinsics pool that performs validation.
 pub struct Pool<B: ChainApi> {
 	validated_pool: Arc<ValidatedPool<B>>,
+	validation_time: Option<Histogram>,
 }
 
 impl<B: ChainApi> parity_util_mem::MallocSizeOf for Pool<B>
@@ -135,8 +137,19 @@ where
 impl<B: ChainApi> Pool<B> {
 	/// Create a new transaction pool.
 	pub fn new(options: Options, api: Arc<B>) -> Self {
+		Self::with_validation_time(options, api, None)
+	}
+
+	/// Create a new transaction pool which observes the time taken to validate each transaction
+	/// in `validation_time`.
+	pub fn with_validation_time(
+		options: Options,
+		api: Arc<B>,
+		validation_time: Option<Histogram>,
+	) -> Self {
 		Pool {
 			validated_pool: Arc::new(ValidatedPool::new(options, api)),
+			validation_time,
 		}
 	}
 
@@ -350,6 +363,24 @@ impl<B: ChainApi> Pool<B> {
 		self.validated_pool.on_broadcasted(propagated)
 	}
 
+	/// Invoked when a block is finalized.
+	pub fn on_block_finalized(&self, block_hash: BlockHash<B>) {
+		self.validated_pool.on_block_finalized(block_hash)
+	}
+
+	/// Returns the last known status of the transaction with given hash.
+	pub fn transaction_status(
+		&self,
+		hash: &ExHash<B>,
+	) -> Option<TransactionStatus<ExHash<B>, BlockHash<B>>> {
+		self.validated_pool.transaction_status(hash)
+	}
+
+	/// Starts to watch the progress of a transaction that is already known to the pool.
+	pub fn watch(&self, hash: &ExHash<B>) -> Option<Watcher<ExHash<B>, BlockHash<B>>> {
+		self.validated_pool.watch(hash)
+	}
+
 	/// Remove invalid transactions from the pool.
 	pub fn remove_invalid(&self, hashes: &[ExHash<B>]) -> Vec<TransactionFor<B>> {
 		self.validated_pool.remove_invalid(hashes)
@@ -417,7 +448,9 @@ impl<B: ChainApi> Pool<B> {
 			)
 		}
 
+		let timer = self.validation_time.as_ref().map(|histogram| histogram.start_timer());
 		let validation_result = self.validated_pool.api().validate_transaction(block_id, xt.clone()).await;
+		drop(timer);
 
 		let status = match validation_result {
 			Ok(status) => status,
@@ -463,6 +496,7 @@ impl<B: ChainApi> Clone for Pool<B> {
 	fn clone(&self) -> Self {
 		Self {
 			validated_pool: self.validated_pool.clone(),
+			validation_time: self.validation_time.clone(),
 		}
 	}
 }
@@ -476,7 +510,6 @@ mod tests {
 	use parking_lot::Mutex;
 	use futures::executor::block_on;
 	use super::*;
-	use sp_transaction_pool::TransactionStatus;
 	use sp_runtime::transaction_validity::{ValidTransaction, InvalidTransaction};
 	use codec::Encode;
 	use substrate_test_runtime::{Block, Extrinsic, Transfer, H256, AccountId};
@@ -817,11 +850,13 @@ mod tests {
 			block_on(pool.prune_tags(&BlockId::Number(2), vec![vec![0u8]], vec![])).unwrap();
 			assert_eq!(pool.status().ready, 0);
 			assert_eq!(pool.status().future, 0);
+			pool.on_block_finalized(H256::from_low_u64_be(2).into());
 
 			// then
 			let mut stream = futures::executor::block_on_stream(watcher.into_stream());
 			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 			assert_eq!(stream.next(), Some(TransactionStatus::InBlock(H256::from_low_u64_be(2).into())));
+			assert_eq!(stream.next(), Some(TransactionStatus::Finalized(H256::from_low_u64_be(2).into())));
 			assert_eq!(stream.next(), None);
 		}
 
@@ -842,14 +877,80 @@ mod tests {
 			block_on(pool.prune_tags(&BlockId::Number(2), vec![vec![0u8]], vec![2u64])).unwrap();
 			assert_eq!(pool.status().ready, 0);
 			assert_eq!(pool.status().future, 0);
+			pool.on_block_finalized(H256::from_low_u64_be(2).into());
 
 			// then
 			let mut stream = futures::executor::block_on_stream(watcher.into_stream());
 			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
 			assert_eq!(stream.next(), Some(TransactionStatus::InBlock(H256::from_low_u64_be(2).into())));
+			assert_eq!(stream.next(), Some(TransactionStatus::Finalized(H256::from_low_u64_be(2).into())));
+			assert_eq!(stream.next(), None);
+		}
+
+		#[test]
+		fn should_report_status_and_watch_included_transaction() {
+			// given
+			let pool = pool();
+			let hash = block_on(pool.submit_one(&BlockId::Number(0), uxt(Transfer {
+				from: AccountId::from_h256(H256::from_low_u64_be(1)),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce: 0,
+			}))).unwrap();
+			assert_eq!(pool.transaction_status(&hash), Some(TransactionStatus::Ready));
+			let ready_watcher = pool.watch(&hash).unwrap();
+
+			// when
+			block_on(pool.prune_tags(&BlockId::Number(2), vec![vec![0u8]], vec![])).unwrap();
+			let block_hash: BlockHash<TestApi> = H256::from_low_u64_be(2).into();
+			assert_eq!(pool.transaction_status(&hash), Some(TransactionStatus::InBlock(block_hash)));
+			let in_block_watcher = pool.watch(&hash).unwrap();
+			pool.on_block_finalized(block_hash);
+
+			// then
+			assert_eq!(pool.transaction_status(&hash), Some(TransactionStatus::Finalized(block_hash)));
+			assert!(pool.watch(&hash).is_none());
+			let mut stream = futures::executor::block_on_stream(ready_watcher.into_stream());
+			assert_eq!(stream.next(), Some(TransactionStatus::InBlock(block_hash)));
+			assert_eq!(stream.next(), Some(TransactionStatus::Finalized(block_hash)));
+			assert_eq!(stream.next(), None);
+			let mut stream = futures::executor::block_on_stream(in_block_watcher.into_stream());
+			assert_eq!(stream.next(), Some(TransactionStatus::Finalized(block_hash)));
 			assert_eq!(stream.next(), None);
 		}
 
+		#[test]
+		fn should_trigger_finalized_when_pruned_after_finalization() {
+			// given
+			let pool = pool();
+			let watcher = block_on(pool.submit_and_watch(&BlockId::Number(0), uxt(Transfer {
+				from: AccountId::from_h256(H256::from_low_u64_be(1)),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce: 0,
+			}))).unwrap();
+
+			// when
+			pool.on_block_finalized(H256::from_low_u64_be(2).into());
+			block_on(pool.prune_tags(&BlockId::Number(2), vec![vec![0u8]], vec![])).unwrap();
+
+			// then
+			let mut stream = futures::executor::block_on_stream(watcher.into_stream());
+			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+			assert_eq!(stream.next(), Some(TransactionStatus::InBlock(H256::from_low_u64_be(2).into())));
+			assert_eq!(stream.next(), Some(TransactionStatus::Finalized(H256::from_low_u64_be(2).into())));
+			assert_eq!(stream.next(), None);
+		}
+
+		#[test]
+		fn should_not_watch_unknown_transaction() {
+			let pool = pool();
+			let hash = 42u64;
+
+			assert_eq!(pool.transaction_status(&hash), None);
+			assert!(pool.watch(&hash).is_none());
+		}
+
 		#[test]
 		fn should_trigger_future_and_ready_after_promoted() {
 			// given
@@ -36,7 +36,7 @@ use sp_runtime::{
 	traits::{self, SaturatedConversion},
 	transaction_validity::TransactionTag as Tag,
 };
-use sp_transaction_pool::{error, PoolStatus};
+use sp_transaction_pool::{error, PoolStatus, TransactionStatus};
 
 use crate::base_pool::PruneStatus;
 use crate::pool::{EventStream, Options, ChainApi, BlockHash, ExHash, ExtrinsicFor, TransactionFor};
@@ -482,6 +482,35 @@ impl<B: ChainApi> ValidatedPool<B> {
 		}
 	}
 
+	/// Invoked when a block is finalized.
+	pub fn on_block_finalized(&self, block_hash: BlockHash<B>) {
+		self.listener.write().finalized(block_hash);
+	}
+
+	/// Returns the last known status of the transaction with given hash.
+	pub fn transaction_status(
+		&self,
+		hash: &ExHash<B>,
+	) -> Option<TransactionStatus<ExHash<B>, BlockHash<B>>> {
+		self.listener.read().status(hash)
+	}
+
+	/// Starts to watch the progress of a transaction that is already known to the pool.
+	///
+	/// Returns `None` if the transaction is neither in the pool nor awaiting finality.
+	pub fn watch(&self, hash: &ExHash<B>) -> Option<Watcher<ExHash<B>, BlockHash<B>>> {
+		// hold the pool lock, so the transaction can't leave it before the watcher is created
+		let pool = self.pool.read();
+		let mut listener = self.listener.write();
+		let known = pool.by_hashes(&[hash.clone()]).pop().and_then(|tx| tx).is_some()
+			|| listener.is_awaiting_finality(hash);
+		if known {
+			Some(listener.create_watcher(hash.clone()))
+		} else {
+			None
+		}
+	}
+
 	/// Remove a subtree of transactions from the pool and mark them invalid.
 	///
 	/// The transactions passed as an argument will be additionally banned
@@ -527,7 +556,7 @@ fn fire_events<H, H2, Ex>(
 	imported: &base::Imported<H, Ex>,
 ) where
 	H: hash::Hash + Eq + traits::Member + Serialize,
-	H2: Clone + fmt::Debug,
+	H2: Clone + PartialEq + fmt::Debug,
 {
 	match *imported {
 		base::Imported::Ready { ref promoted, ref failed, ref removed, ref hash } => {
@@ -91,6 +91,11 @@ impl<H: Clone, H2: Clone> Sender<H, H2> {
 	/// Extrinsic has been included in block with given hash.
 	pub fn in_block(&mut self, hash: H2) {
 		self.send(TransactionStatus::InBlock(hash));
+	}
+
+	/// The block this extrinsic was included in has been finalized.
+	pub fn finalized(&mut self, hash: H2) {
+		self.send(TransactionStatus::Finalized(hash));
 		self.finalized = true;
 	}
 
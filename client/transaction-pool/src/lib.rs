@@ -31,6 +31,7 @@ pub use crate::api::{FullChainApi, LightChainApi};
 use std::{collections::HashMap, sync::Arc, pin::Pin, time::Instant};
 use futures::{Future, FutureExt, future::ready};
 use parking_lot::Mutex;
+use prometheus_endpoint::{Histogram, HistogramOpts, PrometheusError, Registry};
 
 use sp_runtime::{
 	generic::BlockId,
@@ -39,7 +40,7 @@ use sp_runtime::{
 use sp_transaction_pool::{
 	TransactionPool, PoolStatus, ImportNotificationStream,
 	TxHash, TransactionFor, TransactionStatusStreamFor, BlockHash,
-	MaintainedTransactionPool, PoolFuture,
+	MaintainedTransactionPool, PoolFuture, TransactionStatus,
 };
 
 /// Basic implementation of transaction pool that can be customized by providing PoolApi.
@@ -88,11 +89,14 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 		PoolApi: sc_transaction_graph::ChainApi<Block=Block, Hash=Block::Hash>,
 {
 	/// Create new basic transaction pool with provided api.
+	///
+	/// The time taken to validate transactions is recorded in `prometheus`, if given.
 	pub fn new(
 		options: sc_transaction_graph::Options,
 		pool_api: Arc<PoolApi>,
+		prometheus: Option<&Registry>,
 	) -> Self {
-		Self::with_revalidation_type(options, pool_api, RevalidationType::Full)
+		Self::with_revalidation_type(options, pool_api, prometheus, RevalidationType::Full)
 	}
 
 	/// Create new basic transaction pool with provided api and custom
@@ -100,12 +104,21 @@ impl<PoolApi, Block> BasicPool<PoolApi, Block>
 	pub fn with_revalidation_type(
 		options: sc_transaction_graph::Options,
 		pool_api: Arc<PoolApi>,
+		prometheus: Option<&Registry>,
 		revalidation_type: RevalidationType,
 	) -> Self {
 		let cloned_api = pool_api.clone();
+		let validation_time = prometheus.and_then(|registry| register_validation_time(registry)
+			.map_err(|e| log::warn!("Failed to register transaction pool metrics: {}", e))
+			.ok()
+		);
 		BasicPool {
 			api: cloned_api,
-			pool: Arc::new(sc_transaction_graph::Pool::new(options, pool_api)),
+			pool: Arc::new(sc_transaction_graph::Pool::with_validation_time(
+				options,
+				pool_api,
+				validation_time,
+			)),
 			revalidation_strategy: Arc::new(Mutex::new(
 				match revalidation_type {
 					RevalidationType::Light => RevalidationStrategy::Light(RevalidationStatus::NotScheduled),
@@ -198,6 +211,24 @@ impl<PoolApi, Block> TransactionPool for BasicPool<PoolApi, Block>
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>> {
 		self.pool.ready_transaction(hash)
 	}
+
+	fn transaction_status(
+		&self,
+		hash: &TxHash<Self>,
+	) -> Option<TransactionStatus<TxHash<Self>, BlockHash<Self>>> {
+		self.pool.transaction_status(hash)
+	}
+
+	fn watch(&self, hash: &TxHash<Self>) -> Option<Box<TransactionStatusStreamFor<Self>>> {
+		self.pool.watch(hash).map(|watcher| Box::new(watcher.into_stream()) as _)
+	}
+}
+
+fn register_validation_time(registry: &Registry) -> Result<Histogram, PrometheusError> {
+	prometheus_endpoint::register(Histogram::with_opts(HistogramOpts::new(
+		"substrate_sub_txpool_validation_time",
+		"Time taken to validate a transaction, in seconds",
+	))?, registry)
 }
 
 #[cfg_attr(test, derive(Debug))]
@@ -364,4 +395,8 @@ where
 			revalidation_strategy.lock().clear();
 		}.boxed()
 	}
+
+	fn on_block_finalized(&self, block: BlockHash<Self>) {
+		self.pool.on_block_finalized(block)
+	}
 }
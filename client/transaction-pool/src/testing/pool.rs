@@ -32,7 +32,7 @@ fn pool() -> Pool<TestApi> {
 }
 
 fn maintained_pool() -> BasicPool<TestApi, Block> {
-	BasicPool::new(Default::default(), std::sync::Arc::new(TestApi::with_alice_nonce(209)))
+	BasicPool::new(Default::default(), std::sync::Arc::new(TestApi::with_alice_nonce(209)), None)
 }
 
 #[test]
@@ -162,6 +162,33 @@ fn should_prune_old_during_maintenance() {
 	assert_eq!(pool.status().ready, 0);
 }
 
+#[test]
+fn should_report_finalized_transaction_after_maintenance() {
+	let xt = uxt(Alice, 209);
+
+	let pool = maintained_pool();
+
+	let watcher = block_on(pool.submit_and_watch(&BlockId::number(0), xt.clone())).expect("1. Imported");
+	let hash = pool.hash_of(&xt);
+	assert_eq!(pool.transaction_status(&hash), Some(TransactionStatus::Ready));
+
+	pool.api.push_block(1, vec![xt.clone()]);
+	block_on(pool.maintain(&BlockId::number(1), &[]));
+	// the test api reports a default hash for blocks imported by number
+	let block_hash = Hash::default();
+	assert_eq!(pool.transaction_status(&hash), Some(TransactionStatus::InBlock(block_hash)));
+
+	pool.on_block_finalized(block_hash);
+	assert_eq!(pool.transaction_status(&hash), Some(TransactionStatus::Finalized(block_hash)));
+	assert!(pool.watch(&hash).is_none());
+
+	let mut stream = futures::executor::block_on_stream(watcher);
+	assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+	assert_eq!(stream.next(), Some(TransactionStatus::InBlock(block_hash)));
+	assert_eq!(stream.next(), Some(TransactionStatus::Finalized(block_hash)));
+	assert_eq!(stream.next(), None);
+}
+
 #[test]
 fn should_revalidate_during_maintenance() {
 	let xt1 = uxt(Alice, 209);
@@ -225,4 +252,23 @@ fn can_track_heap_size() {
 	block_on(pool.submit_one(&BlockId::number(0), uxt(Alice, 212))).expect("1. Imported");
 
 	assert!(parity_util_mem::malloc_size(&pool) > 3000);
-}
\ No newline at end of file
+}
+
+#[test]
+fn should_record_validation_time() {
+	let registry = prometheus_endpoint::Registry::new();
+	let pool = BasicPool::new(
+		Default::default(),
+		std::sync::Arc::new(TestApi::with_alice_nonce(209)),
+		Some(&registry),
+	);
+	block_on(pool.submit_one(&BlockId::number(0), uxt(Alice, 209))).expect("1. Imported");
+	block_on(pool.submit_one(&BlockId::number(0), uxt(Alice, 210))).expect("2. Imported");
+
+	let families = registry.gather();
+	assert_eq!(families.len(), 1);
+	let count = families[0].samples.iter()
+		.find(|s| s.name == "substrate_sub_txpool_validation_time_count")
+		.expect("histograms have a count");
+	assert_eq!(count.value, 2.0);
+}
@@ -20,12 +20,24 @@
 //!
 //! ## Overview
 //!
-//! The Assets module provides functionality for asset management of fungible asset classes
-//! with a fixed supply, including:
+//! The Assets module provides functionality for asset management of fungible asset classes,
+//! including:
 //!
 //! * Asset Issuance
 //! * Asset Transfer
 //! * Asset Destruction
+//! * Asset Minting and Burning by a privileged issuer and admin
+//! * Freezing of accounts and of whole asset classes
+//! * Delegated transfers through approvals
+//! * Asset Metadata
+//!
+//! Every asset class has its own minimum balance (existential deposit): an account holding less
+//! than that amount of the asset is removed and the dust is either swept along with the transfer
+//! that caused it or burnt.
+//!
+//! The module implements the multi-asset traits in
+//! [`frame_support::traits::fungibles`](../frame_support/traits/fungibles/index.html), so it can
+//! be used by any module which is generic over a set of fungible assets.
 //!
 //! To use it in your runtime, you need to implement the assets [`Trait`](./trait.Trait.html).
 //!
@@ -39,6 +51,13 @@
 //! * **Asset destruction:** The process of an account removing its entire holding of an asset.
 //! * **Fungible asset:** An asset whose units are interchangeable.
 //! * **Non-fungible asset:** An asset for which each unit has unique characteristics.
+//! * **Minimum balance:** The smallest non-zero balance an account may hold of a given asset.
+//! * **Owner:** The account which may change the team and the metadata of an asset class.
+//! * **Issuer:** The account which may mint new units of an asset class.
+//! * **Admin:** The account which may burn units of an asset class from any account, and thaw
+//!   frozen accounts.
+//! * **Freezer:** The account which may freeze accounts or a whole asset class.
+//! * **Approval:** An amount which a delegate may transfer out of the account of an owner.
 //!
 //! ### Goals
 //!
@@ -48,16 +67,27 @@
 //! * Move assets between accounts.
 //! * Remove an account's balance of an asset when requested by that account's owner and update
 //!   the asset's total supply.
+//! * Let a team of privileged accounts manage the supply and the liquidity of an asset.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! * `issue` - Issues the total supply of a new fungible asset to the account of the caller of the function.
+//! * `create` - Creates a new fungible asset class with no supply and a given admin.
 //! * `transfer` - Transfers an `amount` of units of fungible asset `id` from the balance of
 //! the function caller's account (`origin`) to a `target` account.
 //! * `destroy` - Destroys the entire holding of a fungible asset `id` associated with the account
 //! that called the function.
+//! * `mint` - Mints new units of an asset into an account; callable by the issuer.
+//! * `burn` - Burns units of an asset from an account; callable by the admin.
+//! * `freeze` / `thaw` - Stops or restarts an account from sending an asset.
+//! * `freeze_asset` / `thaw_asset` - Stops or restarts all transfers of an asset.
+//! * `transfer_ownership` / `set_team` - Changes the privileged accounts of an asset.
+//! * `set_metadata` - Sets the name, symbol and decimals of an asset.
+//! * `approve_transfer` / `cancel_approval` / `transfer_approved` - Delegated transfers.
+//! * `migrate_accounts` - Moves balances kept in the storage layout used before asset classes had
+//! details into the current one.
 //!
 //! Please refer to the [`Call`](./enum.Call.html) enum and its associated variants for documentation on each function.
 //!
@@ -85,14 +115,14 @@
 //!
 //! ```rust,ignore
 //! use pallet_assets as assets;
-//! use frame_support::{decl_module, dispatch, ensure};
+//! use frame_support::{decl_module, dispatch, ensure, traits::fungibles::Mutate};
 //! use frame_system::{self as system, ensure_signed};
 //!
 //! pub trait Trait: assets::Trait { }
 //!
 //! decl_module! {
 //! 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-//! 		pub fn issue_token_airdrop(origin) -> dispatch::DispatchResult {
+//! 		pub fn issue_token_airdrop(origin, asset_id: T::AssetId) -> dispatch::DispatchResult {
 //! 			let sender = ensure_signed(origin).map_err(|e| e.as_str())?;
 //!
 //! 			const ACCOUNT_ALICE: u64 = 1;
@@ -102,14 +132,10 @@
 //!
 //! 			ensure!(!COUNT_AIRDROP_RECIPIENTS.is_zero(), "Divide by zero error.");
 //!
-//! 			let asset_id = Self::next_asset_id();
-//!
-//! 			<NextAssetId<T>>::mutate(|asset_id| *asset_id += 1);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_ALICE), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<Balances<T>>::insert((asset_id, &ACCOUNT_BOB), TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS);
-//! 			<TotalSupply<T>>::insert(asset_id, TOKENS_FIXED_SUPPLY);
+//! 			let amount = TOKENS_FIXED_SUPPLY / COUNT_AIRDROP_RECIPIENTS;
+//! 			<assets::Module<T>>::mint_into(asset_id, &ACCOUNT_ALICE, amount)?;
+//! 			<assets::Module<T>>::mint_into(asset_id, &ACCOUNT_BOB, amount)?;
 //!
-//! 			Self::deposit_event(RawEvent::Issued(asset_id, sender, TOKENS_FIXED_SUPPLY));
 //! 			Ok(())
 //! 		}
 //! 	}
@@ -132,10 +158,24 @@
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{Parameter, decl_module, decl_event, decl_storage, decl_error, ensure};
-use sp_runtime::traits::{Member, SimpleArithmetic, Zero, StaticLookup};
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure,
+	weights::{Weight, FunctionOf, DispatchClass},
+	traits::{
+		Get, LockIdentifier, WithdrawReason, WithdrawReasons, ExistenceRequirement,
+		fungibles::{Inspect, Mutate, Transfer, Reserve, Lock},
+	},
+};
+use sp_runtime::{
+	RuntimeDebug, DispatchResult, DispatchError,
+	traits::{Member, SimpleArithmetic, Zero, StaticLookup, CheckedAdd, CheckedSub, Saturating},
+};
 use frame_system::{self as system, ensure_signed};
-use sp_runtime::traits::One;
+use sp_runtime::traits::{One, MaybeSerializeDeserialize};
+
+mod migration;
 
 /// The module configuration trait.
 pub trait Trait: frame_system::Trait {
@@ -143,10 +183,67 @@ pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// The units in which we record balances.
-	type Balance: Member + Parameter + SimpleArithmetic + Default + Copy;
+	type Balance: Member + Parameter + SimpleArithmetic + Default + Copy + MaybeSerializeDeserialize;
 
 	/// The arithmetic type of asset identifier.
 	type AssetId: Parameter + SimpleArithmetic + Default + Copy;
+
+	/// The maximum length of the name or the symbol of an asset.
+	type StringLimit: Get<u32>;
+}
+
+/// The details of an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetDetails<Balance, AccountId> {
+	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts, and the metadata.
+	pub owner: AccountId,
+	/// Can mint tokens.
+	pub issuer: AccountId,
+	/// Can thaw tokens and burn tokens from any account.
+	pub admin: AccountId,
+	/// Can freeze tokens.
+	pub freezer: AccountId,
+	/// The total supply across all accounts.
+	pub supply: Balance,
+	/// The balance below which an account is removed and its dust burnt.
+	pub min_balance: Balance,
+	/// The total number of accounts holding this asset.
+	pub accounts: u32,
+	/// Whether all transfers of this asset are currently suspended.
+	pub is_frozen: bool,
+}
+
+/// The balance of an account in a single asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetBalance<Balance> {
+	/// The free balance, which may be transferred unless it is locked or the account frozen.
+	pub balance: Balance,
+	/// The balance set aside through the `Reserve` trait.
+	pub reserved: Balance,
+	/// Whether the account may no longer send any of its free balance.
+	pub is_frozen: bool,
+}
+
+/// The human-readable description of an asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct AssetMetadata {
+	/// The user friendly name of this asset.
+	pub name: Vec<u8>,
+	/// The ticker symbol of this asset.
+	pub symbol: Vec<u8>,
+	/// The number of decimals this asset uses to represent one unit.
+	pub decimals: u8,
+}
+
+/// A liquidity restriction on the balance of an account in a single asset class.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct BalanceLock<Balance> {
+	/// An identifier for this lock. Only one lock may be in existence for each identifier.
+	pub id: LockIdentifier,
+	/// The amount which the free balance may not drop below when this lock is in effect.
+	pub amount: Balance,
+	/// The reasons for which this lock applies.
+	pub reasons: WithdrawReasons,
 }
 
 decl_module! {
@@ -157,44 +254,343 @@ decl_module! {
 		/// Issue a new class of fungible assets. There are, and will only ever be, `total`
 		/// such assets and they'll all belong to the `origin` initially. It will have an
 		/// identifier `AssetId` instance: this will be specified in the `Issued` event.
+		///
+		/// The `origin` becomes the owner, issuer, admin and freezer of the asset, and its
+		/// minimum balance is a single unit.
 		fn issue(origin, #[compact] total: T::Balance) {
 			let origin = ensure_signed(origin)?;
 
 			let id = Self::next_asset_id();
 			<NextAssetId<T>>::mutate(|id| *id += One::one());
 
-			<Balances<T>>::insert((id, &origin), total);
-			<TotalSupply<T>>::insert(id, total);
+			let mut details = AssetDetails {
+				owner: origin.clone(),
+				issuer: origin.clone(),
+				admin: origin.clone(),
+				freezer: origin.clone(),
+				supply: total,
+				min_balance: One::one(),
+				accounts: 0,
+				is_frozen: false,
+			};
+			Self::write_account(id, &origin, &mut details, AssetBalance {
+				balance: total,
+				.. Default::default()
+			});
+			<Asset<T>>::insert(id, details);
 
 			Self::deposit_event(RawEvent::Issued(id, origin, total));
 		}
 
+		/// Create a new class of fungible assets with no supply. Its identifier will be specified
+		/// in the `Created` event.
+		///
+		/// The `origin` becomes the owner of the asset and `admin` becomes its issuer, admin and
+		/// freezer. Accounts holding less than `min_balance` of the asset are removed.
+		fn create(origin,
+			admin: <T::Lookup as StaticLookup>::Source,
+			#[compact] min_balance: T::Balance
+		) {
+			let owner = ensure_signed(origin)?;
+			let admin = T::Lookup::lookup(admin)?;
+			ensure!(!min_balance.is_zero(), Error::<T>::MinBalanceZero);
+
+			let id = Self::next_asset_id();
+			<NextAssetId<T>>::mutate(|id| *id += One::one());
+
+			<Asset<T>>::insert(id, AssetDetails {
+				owner: owner.clone(),
+				issuer: admin.clone(),
+				admin: admin.clone(),
+				freezer: admin.clone(),
+				supply: Zero::zero(),
+				min_balance,
+				accounts: 0,
+				is_frozen: false,
+			});
+
+			Self::deposit_event(RawEvent::Created(id, owner, admin));
+		}
+
 		/// Move some assets from one holder to another.
+		///
+		/// If the holder would be left with less than the minimum balance of the asset, the
+		/// remainder is transferred too.
 		fn transfer(origin,
 			#[compact] id: T::AssetId,
 			target: <T::Lookup as StaticLookup>::Source,
 			#[compact] amount: T::Balance
 		) {
 			let origin = ensure_signed(origin)?;
-			let origin_account = (id, origin.clone());
-			let origin_balance = <Balances<T>>::get(&origin_account);
 			let target = T::Lookup::lookup(target)?;
-			ensure!(!amount.is_zero(), Error::<T>::AmountZero);
-			ensure!(origin_balance >= amount, Error::<T>::BalanceLow);
 
-			Self::deposit_event(RawEvent::Transferred(id, origin, target.clone(), amount));
-			<Balances<T>>::insert(origin_account, origin_balance - amount);
-			<Balances<T>>::mutate((id, target), |balance| *balance += amount);
+			let moved = Self::do_transfer(id, &origin, &target, amount, ExistenceRequirement::AllowDeath)?;
+			Self::deposit_event(RawEvent::Transferred(id, origin, target, moved));
 		}
 
 		/// Destroy any assets of `id` owned by `origin`.
 		fn destroy(origin, #[compact] id: T::AssetId) {
 			let origin = ensure_signed(origin)?;
-			let balance = <Balances<T>>::take((id, &origin));
+			let balance = Self::balance(id, origin.clone());
 			ensure!(!balance.is_zero(), Error::<T>::BalanceZero);
 
-			<TotalSupply<T>>::mutate(id, |total_supply| *total_supply -= balance);
-			Self::deposit_event(RawEvent::Destroyed(id, origin, balance));
+			let burned = Self::do_burn(id, &origin, balance, WithdrawReasons::all())?;
+			Self::deposit_event(RawEvent::Destroyed(id, origin, burned));
+		}
+
+		/// Mint `amount` of asset `id` into the account of `beneficiary`.
+		///
+		/// The `origin` must be the issuer of the asset.
+		fn mint(origin,
+			#[compact] id: T::AssetId,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.issuer, Error::<T>::NoPermission);
+
+			Self::do_mint(id, &beneficiary, amount)?;
+			Self::deposit_event(RawEvent::Issued(id, beneficiary, amount));
+		}
+
+		/// Burn up to `amount` of asset `id` from the account of `who`.
+		///
+		/// The `origin` must be the admin of the asset.
+		fn burn(origin,
+			#[compact] id: T::AssetId,
+			who: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+
+			let amount = amount.min(Self::balance(id, who.clone()));
+			ensure!(!amount.is_zero(), Error::<T>::BalanceZero);
+
+			let burned = Self::do_burn(id, &who, amount, WithdrawReasons::all())?;
+			Self::deposit_event(RawEvent::Burned(id, who, burned));
+		}
+
+		/// Disallow further transfers of asset `id` from the account of `who`, which must hold some
+		/// of the asset.
+		///
+		/// The `origin` must be the freezer of the asset.
+		fn freeze(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.freezer, Error::<T>::NoPermission);
+			ensure!(<Account<T>>::contains_key(id, &who), Error::<T>::BalanceZero);
+
+			let mut account = <Account<T>>::get(id, &who);
+			account.is_frozen = true;
+			Self::write_account(id, &who, &mut details, account);
+			<Asset<T>>::insert(id, details);
+
+			Self::deposit_event(RawEvent::Frozen(id, who));
+		}
+
+		/// Allow transfers of asset `id` from the account of `who` again.
+		///
+		/// The `origin` must be the admin of the asset.
+		fn thaw(origin, #[compact] id: T::AssetId, who: <T::Lookup as StaticLookup>::Source) {
+			let origin = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+
+			let mut account = <Account<T>>::get(id, &who);
+			account.is_frozen = false;
+			Self::write_account(id, &who, &mut details, account);
+			<Asset<T>>::insert(id, details);
+
+			Self::deposit_event(RawEvent::Thawed(id, who));
+		}
+
+		/// Disallow all transfers of asset `id`.
+		///
+		/// The `origin` must be the freezer of the asset.
+		fn freeze_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.freezer, Error::<T>::NoPermission);
+			details.is_frozen = true;
+			<Asset<T>>::insert(id, details);
+
+			Self::deposit_event(RawEvent::AssetFrozen(id));
+		}
+
+		/// Allow transfers of asset `id` again.
+		///
+		/// The `origin` must be the admin of the asset.
+		fn thaw_asset(origin, #[compact] id: T::AssetId) {
+			let origin = ensure_signed(origin)?;
+
+			let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.admin, Error::<T>::NoPermission);
+			details.is_frozen = false;
+			<Asset<T>>::insert(id, details);
+
+			Self::deposit_event(RawEvent::AssetThawed(id));
+		}
+
+		/// Change the owner of asset `id`.
+		///
+		/// The `origin` must be the owner of the asset.
+		fn transfer_ownership(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source
+		) {
+			let origin = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+
+			let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.owner, Error::<T>::NoPermission);
+			details.owner = owner.clone();
+			<Asset<T>>::insert(id, details);
+
+			Self::deposit_event(RawEvent::OwnerChanged(id, owner));
+		}
+
+		/// Change the issuer, admin and freezer of asset `id`.
+		///
+		/// The `origin` must be the owner of the asset.
+		fn set_team(origin,
+			#[compact] id: T::AssetId,
+			issuer: <T::Lookup as StaticLookup>::Source,
+			admin: <T::Lookup as StaticLookup>::Source,
+			freezer: <T::Lookup as StaticLookup>::Source
+		) {
+			let origin = ensure_signed(origin)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let freezer = T::Lookup::lookup(freezer)?;
+
+			let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.owner, Error::<T>::NoPermission);
+			details.issuer = issuer.clone();
+			details.admin = admin.clone();
+			details.freezer = freezer.clone();
+			<Asset<T>>::insert(id, details);
+
+			Self::deposit_event(RawEvent::TeamChanged(id, issuer, admin, freezer));
+		}
+
+		/// Set the name, symbol and number of decimals of asset `id`.
+		///
+		/// The `origin` must be the owner of the asset. `name` and `symbol` may be no longer than
+		/// `StringLimit`.
+		fn set_metadata(origin,
+			#[compact] id: T::AssetId,
+			name: Vec<u8>,
+			symbol: Vec<u8>,
+			decimals: u8
+		) {
+			let origin = ensure_signed(origin)?;
+
+			let limit = T::StringLimit::get() as usize;
+			ensure!(name.len() <= limit, Error::<T>::BadMetadata);
+			ensure!(symbol.len() <= limit, Error::<T>::BadMetadata);
+
+			let details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+			ensure!(origin == details.owner, Error::<T>::NoPermission);
+
+			<Metadata<T>>::insert(id, AssetMetadata { name: name.clone(), symbol: symbol.clone(), decimals });
+			Self::deposit_event(RawEvent::MetadataSet(id, name, symbol, decimals));
+		}
+
+		/// Allow `delegate` to transfer up to `amount` of asset `id` out of the account of
+		/// `origin`, on top of any amount already approved.
+		fn approve_transfer(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(<Asset<T>>::contains_key(id), Error::<T>::Unknown);
+
+			let approved = <Approvals<T>>::get((id, &owner), &delegate)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::Overflow)?;
+			<Approvals<T>>::insert((id, &owner), &delegate, approved);
+
+			Self::deposit_event(RawEvent::ApprovedTransfer(id, owner, delegate, approved));
+		}
+
+		/// Cancel any approval for `delegate` to transfer asset `id` out of the account of
+		/// `origin`.
+		fn cancel_approval(origin,
+			#[compact] id: T::AssetId,
+			delegate: <T::Lookup as StaticLookup>::Source
+		) {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(<Approvals<T>>::contains_key((id, &owner), &delegate), Error::<T>::Unapproved);
+
+			<Approvals<T>>::remove((id, &owner), &delegate);
+			Self::deposit_event(RawEvent::ApprovalCancelled(id, owner, delegate));
+		}
+
+		/// Transfer `amount` of asset `id` from the account of `owner` to `destination`, using an
+		/// approval previously given to `origin` by `owner`.
+		fn transfer_approved(origin,
+			#[compact] id: T::AssetId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[compact] amount: T::Balance
+		) {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+
+			let approved = <Approvals<T>>::get((id, &owner), &delegate);
+			let remaining = approved.checked_sub(&amount).ok_or(Error::<T>::Unapproved)?;
+
+			Self::do_transfer(id, &owner, &destination, amount, ExistenceRequirement::KeepAlive)?;
+
+			if remaining.is_zero() {
+				<Approvals<T>>::remove((id, &owner), &delegate);
+			} else {
+				<Approvals<T>>::insert((id, &owner), &delegate, remaining);
+			}
+
+			Self::deposit_event(RawEvent::TransferredApproved(id, owner, delegate, destination, amount));
+		}
+
+		/// Move the balances of asset `id` held by `accounts` out of the storage layout used before
+		/// asset classes had details.
+		///
+		/// The old balances are keyed by a hash of the asset and the account, so they can't be found
+		/// on-chain and have to be given. An account without an old balance is skipped.
+		#[weight = FunctionOf(
+			|args: (&T::AssetId, &Vec<T::AccountId>)| (args.1.len() as Weight).saturating_mul(10_000),
+			DispatchClass::Normal,
+			true
+		)]
+		fn migrate_accounts(origin, #[compact] id: T::AssetId, accounts: Vec<T::AccountId>) {
+			ensure_signed(origin)?;
+			ensure!(Self::asset(id).is_some(), Error::<T>::Unknown);
+
+			for who in accounts.iter() {
+				migration::migrate_account::<T>(id, who);
+			}
+		}
+
+		fn on_initialize() {
+			if !IsUpgraded::get() {
+				IsUpgraded::put(true);
+				migration::upgrade::<T>();
+			}
 		}
 	}
 }
@@ -211,6 +607,30 @@ decl_event! {
 		Transferred(AssetId, AccountId, AccountId, Balance),
 		/// Some assets were destroyed.
 		Destroyed(AssetId, AccountId, Balance),
+		/// An asset class was created (asset_id, owner, admin).
+		Created(AssetId, AccountId, AccountId),
+		/// Some assets were burnt by the admin.
+		Burned(AssetId, AccountId, Balance),
+		/// The team of an asset changed (asset_id, issuer, admin, freezer).
+		TeamChanged(AssetId, AccountId, AccountId, AccountId),
+		/// The owner of an asset changed.
+		OwnerChanged(AssetId, AccountId),
+		/// Some account was frozen.
+		Frozen(AssetId, AccountId),
+		/// Some account was thawed.
+		Thawed(AssetId, AccountId),
+		/// Some asset class was frozen.
+		AssetFrozen(AssetId),
+		/// Some asset class was thawed.
+		AssetThawed(AssetId),
+		/// The metadata of an asset was set (asset_id, name, symbol, decimals).
+		MetadataSet(AssetId, Vec<u8>, Vec<u8>, u8),
+		/// A transfer was approved (asset_id, owner, delegate, total approved amount).
+		ApprovedTransfer(AssetId, AccountId, AccountId, Balance),
+		/// An approval was cancelled (asset_id, owner, delegate).
+		ApprovalCancelled(AssetId, AccountId, AccountId),
+		/// An approved transfer was executed (asset_id, owner, delegate, destination, amount).
+		TransferredApproved(AssetId, AccountId, AccountId, AccountId, Balance),
 	}
 }
 
@@ -222,17 +642,50 @@ decl_error! {
 		BalanceLow,
 		/// Balance should be non-zero
 		BalanceZero,
+		/// The given asset ID is unknown.
+		Unknown,
+		/// The signing account has no permission to do the operation.
+		NoPermission,
+		/// The origin account or the asset is frozen.
+		Frozen,
+		/// The resulting balance would be below the minimum balance of the asset.
+		BelowMinimum,
+		/// The minimum balance of an asset should be non-zero.
+		MinBalanceZero,
+		/// The operation would reap an account which must be kept alive.
+		WouldDie,
+		/// An arithmetic overflow occurred.
+		Overflow,
+		/// The account liquidity restrictions prevent withdrawal.
+		LiquidityRestrictions,
+		/// The name or symbol is too long.
+		BadMetadata,
+		/// No approval exists that would allow the transfer.
+		Unapproved,
 	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Assets {
-		/// The number of units of assets held by any given account.
-		Balances: map hasher(blake2_256) (T::AssetId, T::AccountId) => T::Balance;
+		/// Details of an asset.
+		Asset get(fn asset): map hasher(blake2_256) T::AssetId
+			=> Option<AssetDetails<T::Balance, T::AccountId>>;
+		/// The holdings of a specific account for a specific asset.
+		Account: double_map hasher(blake2_256) T::AssetId, hasher(blake2_256) T::AccountId
+			=> AssetBalance<T::Balance>;
+		/// Any liquidity locks on the holdings of a specific account for a specific asset.
+		Locks get(fn locks): double_map hasher(blake2_256) T::AssetId, hasher(blake2_256) T::AccountId
+			=> Vec<BalanceLock<T::Balance>>;
+		/// Approved balance transfers, keyed by asset and owner, then by delegate.
+		Approvals: double_map hasher(blake2_256) (T::AssetId, T::AccountId), hasher(blake2_256) T::AccountId
+			=> T::Balance;
+		/// The metadata of an asset.
+		Metadata get(fn metadata): map hasher(blake2_256) T::AssetId => AssetMetadata;
 		/// The next asset identifier up for grabs.
 		NextAssetId get(fn next_asset_id): T::AssetId;
-		/// The total unit supply of an asset.
-		TotalSupply: map hasher(blake2_256) T::AssetId => T::Balance;
+		/// True if the total supplies of the assets issued before asset classes had details have
+		/// been turned into details.
+		IsUpgraded: bool;
 	}
 }
 
@@ -242,12 +695,380 @@ impl<T: Trait> Module<T> {
 
 	/// Get the asset `id` balance of `who`.
 	pub fn balance(id: T::AssetId, who: T::AccountId) -> T::Balance {
-		<Balances<T>>::get((id, who))
+		<Account<T>>::get(id, who).balance
 	}
 
 	/// Get the total supply of an asset `id`.
 	pub fn total_supply(id: T::AssetId) -> T::Balance {
-		<TotalSupply<T>>::get(id)
+		Self::asset(id).map(|d| d.supply).unwrap_or_else(Zero::zero)
+	}
+
+	/// Get the amount of asset `id` which `delegate` may transfer out of the account of `owner`.
+	pub fn allowance(id: T::AssetId, owner: &T::AccountId, delegate: &T::AccountId) -> T::Balance {
+		<Approvals<T>>::get((id, owner), delegate)
+	}
+
+	/// Return `Ok` iff the locks on the asset `id` balance of `who` allow it to drop to
+	/// `new_balance` for the given `reasons`.
+	pub fn ensure_can_withdraw(
+		id: T::AssetId,
+		who: &T::AccountId,
+		reasons: WithdrawReasons,
+		new_balance: T::Balance,
+	) -> DispatchResult {
+		if Self::locks(id, who)
+			.into_iter()
+			.all(|l| new_balance >= l.amount || !l.reasons.intersects(reasons))
+		{
+			Ok(())
+		} else {
+			Err(Error::<T>::LiquidityRestrictions)?
+		}
+	}
+
+	// Private mutables
+
+	/// Write the `account` of `who`, removing it if it holds nothing and keeping the number of
+	/// accounts in `details` up to date. The caller is expected to write `details` back.
+	fn write_account(
+		id: T::AssetId,
+		who: &T::AccountId,
+		details: &mut AssetDetails<T::Balance, T::AccountId>,
+		account: AssetBalance<T::Balance>,
+	) {
+		let existed = <Account<T>>::contains_key(id, who);
+		if account.balance.is_zero() && account.reserved.is_zero() && !account.is_frozen {
+			if existed {
+				details.accounts = details.accounts.saturating_sub(1);
+				<Account<T>>::remove(id, who);
+			}
+		} else {
+			if !existed {
+				details.accounts = details.accounts.saturating_add(1);
+			}
+			<Account<T>>::insert(id, who, account);
+		}
+	}
+
+	/// Compute the new free balance of `who` after withdrawing `amount` of asset `id`, together
+	/// with the amount actually withdrawn once any dust has been swept along.
+	fn prep_withdraw(
+		id: T::AssetId,
+		details: &AssetDetails<T::Balance, T::AccountId>,
+		who: &T::AccountId,
+		account: &AssetBalance<T::Balance>,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<(T::Balance, T::Balance), DispatchError> {
+		let mut remaining = account.balance.checked_sub(&amount).ok_or(Error::<T>::BalanceLow)?;
+		let mut actual = amount;
+		if remaining < details.min_balance {
+			if liveness == ExistenceRequirement::KeepAlive && account.reserved.is_zero() {
+				Err(Error::<T>::WouldDie)?
+			}
+			actual = account.balance;
+			remaining = Zero::zero();
+		}
+		Self::ensure_can_withdraw(id, who, reasons, remaining)?;
+		Ok((remaining, actual))
+	}
+
+	/// Compute the new free balance of `who` after reserving `amount` of asset `id`.
+	///
+	/// The free balance may not be left below the minimum balance unless it is emptied entirely,
+	/// otherwise the account would hold dust which is never swept.
+	fn prep_reserve(
+		id: T::AssetId,
+		details: &AssetDetails<T::Balance, T::AccountId>,
+		who: &T::AccountId,
+		account: &AssetBalance<T::Balance>,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		let new_balance = account.balance.checked_sub(&amount).ok_or(Error::<T>::BalanceLow)?;
+		ensure!(
+			new_balance.is_zero() || new_balance >= details.min_balance,
+			Error::<T>::BelowMinimum
+		);
+		Self::ensure_can_withdraw(id, who, WithdrawReason::Reserve.into(), new_balance)?;
+		Ok(new_balance)
+	}
+
+	/// Increase the free balance of `who` by `amount` of asset `id`, and the supply accordingly.
+	fn do_mint(id: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+		let mut account = <Account<T>>::get(id, who);
+
+		account.balance = account.balance.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+		ensure!(account.balance >= details.min_balance, Error::<T>::BelowMinimum);
+		details.supply = details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+
+		Self::write_account(id, who, &mut details, account);
+		<Asset<T>>::insert(id, details);
+		Ok(())
+	}
+
+	/// Decrease the free balance of `who` by `amount` of asset `id`, and the supply accordingly.
+	/// Any dust left behind is burnt too. Returns the amount actually burnt.
+	fn do_burn(
+		id: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> Result<T::Balance, DispatchError> {
+		let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+		let mut account = <Account<T>>::get(id, who);
+
+		let (remaining, actual) = Self::prep_withdraw(
+			id, &details, who, &account, amount, reasons, ExistenceRequirement::AllowDeath,
+		)?;
+		account.balance = remaining;
+		details.supply = details.supply.saturating_sub(actual);
+
+		Self::write_account(id, who, &mut details, account);
+		<Asset<T>>::insert(id, details);
+		Ok(actual)
+	}
+
+	/// Move `amount` of asset `id` from `source` to `dest`. Returns the amount actually moved.
+	fn do_transfer(
+		id: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		liveness: ExistenceRequirement,
+	) -> Result<T::Balance, DispatchError> {
+		ensure!(!amount.is_zero(), Error::<T>::AmountZero);
+		let mut details = Self::asset(id).ok_or(Error::<T>::Unknown)?;
+		let mut source_account = <Account<T>>::get(id, source);
+		ensure!(source_account.balance >= amount, Error::<T>::BalanceLow);
+		ensure!(!details.is_frozen && !source_account.is_frozen, Error::<T>::Frozen);
+
+		let (remaining, actual) = Self::prep_withdraw(
+			id, &details, source, &source_account, amount, WithdrawReason::Transfer.into(), liveness,
+		)?;
+		if source == dest {
+			return Ok(actual);
+		}
+
+		let mut dest_account = <Account<T>>::get(id, dest);
+		dest_account.balance = dest_account.balance.checked_add(&actual).ok_or(Error::<T>::Overflow)?;
+		ensure!(dest_account.balance >= details.min_balance, Error::<T>::BelowMinimum);
+		source_account.balance = remaining;
+
+		Self::write_account(id, source, &mut details, source_account);
+		Self::write_account(id, dest, &mut details, dest_account);
+		<Asset<T>>::insert(id, details);
+		Ok(actual)
+	}
+
+	fn update_locks(id: T::AssetId, who: &T::AccountId, locks: Vec<BalanceLock<T::Balance>>) {
+		if locks.is_empty() {
+			<Locks<T>>::remove(id, who);
+		} else {
+			<Locks<T>>::insert(id, who, locks);
+		}
+	}
+}
+
+impl<T: Trait> Inspect<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: T::AssetId) -> T::Balance {
+		Self::total_supply(asset)
+	}
+
+	fn minimum_balance(asset: T::AssetId) -> T::Balance {
+		Self::asset(asset).map(|d| d.min_balance).unwrap_or_else(Zero::zero)
+	}
+
+	fn balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<Account<T>>::get(asset, who).balance
+	}
+
+	fn can_deposit(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let details = Self::asset(asset).ok_or(Error::<T>::Unknown)?;
+		details.supply.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+		let new_balance = <Account<T>>::get(asset, who).balance
+			.checked_add(&amount)
+			.ok_or(Error::<T>::Overflow)?;
+		ensure!(new_balance >= details.min_balance, Error::<T>::BelowMinimum);
+		Ok(())
+	}
+
+	fn can_withdraw(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> DispatchResult {
+		let details = Self::asset(asset).ok_or(Error::<T>::Unknown)?;
+		let account = <Account<T>>::get(asset, who);
+		if reasons.contains(WithdrawReason::Transfer) {
+			ensure!(!details.is_frozen && !account.is_frozen, Error::<T>::Frozen);
+		}
+		Self::prep_withdraw(asset, &details, who, &account, amount, reasons, liveness).map(|_| ())
+	}
+}
+
+impl<T: Trait> Mutate<T::AccountId> for Module<T> {
+	fn mint_into(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		Self::do_mint(asset, who, amount)
+	}
+
+	fn burn_from(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		Self::do_burn(asset, who, amount, WithdrawReasons::all())
+	}
+}
+
+impl<T: Trait> Transfer<T::AccountId> for Module<T> {
+	fn transfer(
+		asset: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		liveness: ExistenceRequirement,
+	) -> Result<T::Balance, DispatchError> {
+		Self::do_transfer(asset, source, dest, amount, liveness)
+	}
+}
+
+impl<T: Trait> Reserve<T::AccountId> for Module<T> {
+	fn reserved_balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		<Account<T>>::get(asset, who).reserved
+	}
+
+	fn can_reserve(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> bool {
+		Self::asset(asset).map_or(false, |details| {
+			let account = <Account<T>>::get(asset, who);
+			Self::prep_reserve(asset, &details, who, &account, amount).is_ok()
+		})
+	}
+
+	fn reserve(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let mut details = Self::asset(asset).ok_or(Error::<T>::Unknown)?;
+		let mut account = <Account<T>>::get(asset, who);
+
+		account.balance = Self::prep_reserve(asset, &details, who, &account, amount)?;
+		account.reserved = account.reserved.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+
+		Self::write_account(asset, who, &mut details, account);
+		<Asset<T>>::insert(asset, details);
+		Ok(())
+	}
+
+	fn unreserve(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let mut details = match Self::asset(asset) {
+			Some(details) => details,
+			None => return amount,
+		};
+		let mut account = <Account<T>>::get(asset, who);
+
+		let actual = amount.min(account.reserved);
+		account.reserved -= actual;
+		account.balance = account.balance.saturating_add(actual);
+
+		Self::write_account(asset, who, &mut details, account);
+		<Asset<T>>::insert(asset, details);
+		amount - actual
+	}
+
+	fn slash_reserved(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let mut details = match Self::asset(asset) {
+			Some(details) => details,
+			None => return amount,
+		};
+		let mut account = <Account<T>>::get(asset, who);
+
+		let actual = amount.min(account.reserved);
+		account.reserved -= actual;
+		details.supply = details.supply.saturating_sub(actual);
+
+		Self::write_account(asset, who, &mut details, account);
+		<Asset<T>>::insert(asset, details);
+		amount - actual
+	}
+
+	fn repatriate_reserved(
+		asset: T::AssetId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+	) -> Result<T::Balance, DispatchError> {
+		if slashed == beneficiary {
+			return Ok(<Self as Reserve<_>>::unreserve(asset, slashed, amount));
+		}
+		let mut details = Self::asset(asset).ok_or(Error::<T>::Unknown)?;
+		let mut from_account = <Account<T>>::get(asset, slashed);
+		let mut to_account = <Account<T>>::get(asset, beneficiary);
+
+		let actual = amount.min(from_account.reserved);
+		to_account.balance = to_account.balance.checked_add(&actual).ok_or(Error::<T>::Overflow)?;
+		ensure!(to_account.balance >= details.min_balance, Error::<T>::BelowMinimum);
+		from_account.reserved -= actual;
+
+		Self::write_account(asset, slashed, &mut details, from_account);
+		Self::write_account(asset, beneficiary, &mut details, to_account);
+		<Asset<T>>::insert(asset, details);
+		Ok(amount - actual)
+	}
+}
+
+impl<T: Trait> Lock<T::AccountId> for Module<T> {
+	fn set_lock(
+		id: LockIdentifier,
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) {
+		let mut new_lock = Some(BalanceLock { id, amount, reasons });
+		let mut locks = Self::locks(asset, who)
+			.into_iter()
+			.filter_map(|l| if l.id == id { new_lock.take() } else { Some(l) })
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			locks.push(lock)
+		}
+		Self::update_locks(asset, who, locks);
+	}
+
+	fn extend_lock(
+		id: LockIdentifier,
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) {
+		let mut new_lock = Some(BalanceLock { id, amount, reasons });
+		let mut locks = Self::locks(asset, who)
+			.into_iter()
+			.filter_map(|l| if l.id == id {
+				new_lock.take().map(|nl| BalanceLock {
+					id: l.id,
+					amount: l.amount.max(nl.amount),
+					reasons: l.reasons | nl.reasons,
+				})
+			} else {
+				Some(l)
+			})
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			locks.push(lock)
+		}
+		Self::update_locks(asset, who, locks);
+	}
+
+	fn remove_lock(id: LockIdentifier, asset: T::AssetId, who: &T::AccountId) {
+		let mut locks = Self::locks(asset, who);
+		locks.retain(|l| l.id != id);
+		Self::update_locks(asset, who, locks);
 	}
 }
 
@@ -256,10 +1077,11 @@ mod tests {
 	use super::*;
 
 	use frame_support::{impl_outer_origin, assert_ok, assert_noop, parameter_types, weights::Weight};
+	use frame_support::traits::{WithdrawReason, fungibles::{Inspect, Mutate, Transfer, Reserve, Lock}};
 	use sp_core::H256;
 	// The testing primitives are very useful for avoiding having to work with signatures
 	// or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
-	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+	use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup, OnInitialize}, testing::Header};
 
 	impl_outer_origin! {
 		pub enum Origin for Test  where system = frame_system {}
@@ -275,6 +1097,7 @@ mod tests {
 		pub const MaximumBlockWeight: Weight = 1024;
 		pub const MaximumBlockLength: u32 = 2 * 1024;
 		pub const AvailableBlockRatio: Perbill = Perbill::one();
+		pub const StringLimit: u32 = 8;
 	}
 	impl frame_system::Trait for Test {
 		type Origin = Origin;
@@ -298,6 +1121,7 @@ mod tests {
 		type Event = ();
 		type Balance = u64;
 		type AssetId = u32;
+		type StringLimit = StringLimit;
 	}
 	type Assets = Module<Test>;
 
@@ -392,4 +1216,243 @@ mod tests {
 			assert_noop!(Assets::destroy(Origin::signed(2), 0), Error::<Test>::BalanceZero);
 		});
 	}
+
+	#[test]
+	fn issue_makes_issuer_the_team() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			let details = Assets::asset(0).unwrap();
+			assert_eq!((details.owner, details.issuer, details.admin, details.freezer), (1, 1, 1, 1));
+			assert_eq!(details.supply, 100);
+			assert_eq!(details.accounts, 1);
+		});
+	}
+
+	#[test]
+	fn minting_and_burning_requires_the_team() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::create(Origin::signed(1), 2, 10));
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 3, 100), Error::<Test>::NoPermission);
+			assert_ok!(Assets::mint(Origin::signed(2), 0, 3, 100));
+			assert_eq!(Assets::balance(0, 3), 100);
+			assert_eq!(Assets::total_supply(0), 100);
+
+			assert_noop!(Assets::burn(Origin::signed(1), 0, 3, 50), Error::<Test>::NoPermission);
+			assert_ok!(Assets::burn(Origin::signed(2), 0, 3, 50));
+			assert_eq!(Assets::balance(0, 3), 50);
+			assert_eq!(Assets::total_supply(0), 50);
+
+			// Burning more than the balance only burns what is there.
+			assert_ok!(Assets::burn(Origin::signed(2), 0, 3, 500));
+			assert_eq!(Assets::balance(0, 3), 0);
+			assert_eq!(Assets::total_supply(0), 0);
+			assert_eq!(Assets::asset(0).unwrap().accounts, 0);
+		});
+	}
+
+	#[test]
+	fn unknown_asset_should_not_work() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 100), Error::<Test>::Unknown);
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 1), Error::<Test>::Unknown);
+			assert_noop!(Assets::create(Origin::signed(1), 1, 0), Error::<Test>::MinBalanceZero);
+		});
+	}
+
+	#[test]
+	fn min_balance_should_be_respected() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::create(Origin::signed(1), 1, 10));
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 9), Error::<Test>::BelowMinimum);
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+			// Recipient would end up below the minimum.
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 9), Error::<Test>::BelowMinimum);
+
+			// Sender would be left with dust, which is swept along.
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 91));
+			assert_eq!(Assets::balance(0, 1), 0);
+			assert_eq!(Assets::balance(0, 2), 100);
+			assert_eq!(Assets::total_supply(0), 100);
+			assert_eq!(Assets::asset(0).unwrap().accounts, 1);
+
+			// Unless the sender must be kept alive.
+			assert_noop!(
+				<Assets as Transfer<_>>::transfer(0, &2, &3, 95, ExistenceRequirement::KeepAlive),
+				Error::<Test>::WouldDie
+			);
+
+			// Burning from the trait also burns the dust.
+			assert_eq!(<Assets as Mutate<_>>::burn_from(0, &2, 95), Ok(100));
+			assert_eq!(Assets::total_supply(0), 0);
+		});
+	}
+
+	#[test]
+	fn freezing_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+
+			assert_noop!(Assets::freeze(Origin::signed(2), 0, 1), Error::<Test>::NoPermission);
+			assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+			assert_noop!(Assets::transfer(Origin::signed(2), 0, 1, 10), Error::<Test>::Frozen);
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+			assert_ok!(Assets::thaw(Origin::signed(1), 0, 2));
+			assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 10));
+
+			assert_ok!(Assets::freeze_asset(Origin::signed(1), 0));
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 10), Error::<Test>::Frozen);
+			assert_ok!(Assets::thaw_asset(Origin::signed(1), 0));
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 10));
+		});
+	}
+
+	#[test]
+	fn freezing_an_account_without_balance_should_not_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(Assets::freeze(Origin::signed(1), 0, 2), Error::<Test>::BalanceZero);
+			assert_eq!(Assets::asset(0).unwrap().accounts, 1);
+		});
+	}
+
+	#[test]
+	fn old_storage_layout_should_be_migrated() {
+		new_test_ext().execute_with(|| {
+			// two assets issued with the old layout, the first one transferred around.
+			<NextAssetId<Test>>::put(2);
+			migration::OldTotalSupply::<Test>::insert(0, 100);
+			migration::OldTotalSupply::<Test>::insert(1, 50);
+			migration::OldBalances::<Test>::insert((0, 1), 60);
+			migration::OldBalances::<Test>::insert((0, 2), 40);
+			migration::OldBalances::<Test>::insert((1, 1), 50);
+
+			Assets::on_initialize(1);
+			let details = Assets::asset(0).unwrap();
+			assert_eq!((details.owner, details.supply, details.min_balance, details.accounts), (0, 100, 1, 0));
+			assert_eq!(Assets::total_supply(1), 50);
+			assert!(!migration::OldTotalSupply::<Test>::contains_key(0));
+
+			assert_noop!(Assets::migrate_accounts(Origin::signed(3), 2, vec![1]), Error::<Test>::Unknown);
+			assert_ok!(Assets::migrate_accounts(Origin::signed(3), 0, vec![1, 2, 3]));
+			assert_eq!(Assets::balance(0, 1), 60);
+			assert_eq!(Assets::balance(0, 2), 40);
+			assert_eq!(Assets::asset(0).unwrap().accounts, 2);
+			assert_eq!(Assets::balance(1, 1), 0);
+			assert!(migration::OldBalances::<Test>::contains_key((1, 1)));
+
+			// migrating twice does not credit twice.
+			assert_ok!(Assets::migrate_accounts(Origin::signed(3), 0, vec![1]));
+			assert_eq!(Assets::balance(0, 1), 60);
+			assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 40));
+			assert_eq!(Assets::balance(0, 1), 100);
+			assert_eq!(Assets::asset(0).unwrap().accounts, 1);
+		});
+	}
+
+	#[test]
+	fn changing_team_and_owner_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_ok!(Assets::set_team(Origin::signed(1), 0, 2, 3, 4));
+			assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 10), Error::<Test>::NoPermission);
+			assert_ok!(Assets::mint(Origin::signed(2), 0, 1, 10));
+			assert_ok!(Assets::freeze(Origin::signed(4), 0, 1));
+			assert_ok!(Assets::thaw(Origin::signed(3), 0, 1));
+
+			assert_ok!(Assets::transfer_ownership(Origin::signed(1), 0, 5));
+			assert_noop!(Assets::set_team(Origin::signed(1), 0, 1, 1, 1), Error::<Test>::NoPermission);
+			assert_ok!(Assets::set_team(Origin::signed(5), 0, 1, 1, 1));
+		});
+	}
+
+	#[test]
+	fn set_metadata_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(2), 0, b"Token".to_vec(), b"TKN".to_vec(), 12),
+				Error::<Test>::NoPermission
+			);
+			assert_noop!(
+				Assets::set_metadata(Origin::signed(1), 0, b"Long Token Name".to_vec(), b"TKN".to_vec(), 12),
+				Error::<Test>::BadMetadata
+			);
+			assert_ok!(Assets::set_metadata(Origin::signed(1), 0, b"Token".to_vec(), b"TKN".to_vec(), 12));
+			assert_eq!(Assets::metadata(0), AssetMetadata {
+				name: b"Token".to_vec(),
+				symbol: b"TKN".to_vec(),
+				decimals: 12,
+			});
+		});
+	}
+
+	#[test]
+	fn approvals_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 10), Error::<Test>::Unapproved);
+
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 20));
+			assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 2, 30));
+			assert_eq!(Assets::allowance(0, &1, &2), 50);
+
+			assert_noop!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 60), Error::<Test>::Unapproved);
+			assert_ok!(Assets::transfer_approved(Origin::signed(2), 0, 1, 3, 40));
+			assert_eq!(Assets::balance(0, 1), 60);
+			assert_eq!(Assets::balance(0, 3), 40);
+			assert_eq!(Assets::allowance(0, &1, &2), 10);
+
+			assert_ok!(Assets::cancel_approval(Origin::signed(1), 0, 2));
+			assert_eq!(Assets::allowance(0, &1, &2), 0);
+			assert_noop!(Assets::cancel_approval(Origin::signed(1), 0, 2), Error::<Test>::Unapproved);
+		});
+	}
+
+	#[test]
+	fn reserve_and_lock_traits_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::issue(Origin::signed(1), 100));
+			assert_eq!(<Assets as Inspect<_>>::minimum_balance(0), 1);
+
+			assert_ok!(<Assets as Reserve<_>>::reserve(0, &1, 40));
+			assert_eq!(Assets::balance(0, 1), 60);
+			assert_eq!(<Assets as Reserve<_>>::reserved_balance(0, &1), 40);
+			assert_noop!(<Assets as Reserve<_>>::reserve(0, &1, 61), Error::<Test>::BalanceLow);
+
+			assert_eq!(<Assets as Reserve<_>>::repatriate_reserved(0, &1, &2, 30), Ok(0));
+			assert_eq!(Assets::balance(0, 2), 30);
+			assert_eq!(<Assets as Reserve<_>>::slash_reserved(0, &1, 20), 10);
+			assert_eq!(Assets::total_supply(0), 90);
+			assert_eq!(<Assets as Reserve<_>>::unreserve(0, &1, 10), 10);
+
+			<Assets as Lock<_>>::set_lock(*b"testlock", 0, &1, 50, WithdrawReason::Transfer.into());
+			assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 20), Error::<Test>::LiquidityRestrictions);
+			assert_ok!(<Assets as Reserve<_>>::reserve(0, &1, 20));
+			assert_eq!(<Assets as Reserve<_>>::unreserve(0, &1, 20), 0);
+			<Assets as Lock<_>>::remove_lock(*b"testlock", 0, &1);
+			assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 20));
+		});
+	}
+
+	#[test]
+	fn reserve_should_not_leave_dust() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Assets::create(Origin::signed(1), 1, 10));
+			assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+			assert!(!<Assets as Reserve<_>>::can_reserve(0, &1, 95));
+			assert_noop!(<Assets as Reserve<_>>::reserve(0, &1, 95), Error::<Test>::BelowMinimum);
+
+			assert!(<Assets as Reserve<_>>::can_reserve(0, &1, 90));
+			assert_ok!(<Assets as Reserve<_>>::reserve(0, &1, 90));
+			assert_eq!(Assets::balance(0, 1), 10);
+
+			// Reserving everything that is left does not leave any dust behind.
+			assert_ok!(<Assets as Reserve<_>>::reserve(0, &1, 10));
+			assert_eq!(Assets::balance(0, 1), 0);
+			assert_eq!(<Assets as Reserve<_>>::reserved_balance(0, &1), 100);
+		});
+	}
 }
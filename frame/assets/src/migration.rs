@@ -0,0 +1,91 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations from the layout which only kept balances and total supplies.
+
+use crate::{Module, Trait, Asset, Account, AssetDetails, AssetBalance};
+use frame_support::{StorageMap, StorageDoubleMap, Blake2_256, storage::generator};
+use sp_runtime::traits::{Zero, One, Saturating};
+use sp_std::marker::PhantomData;
+
+// the `Balances` storage item as it was laid out before asset details were introduced.
+pub(crate) struct OldBalances<T>(PhantomData<T>);
+
+impl<T: Trait> generator::StorageMap<(T::AssetId, T::AccountId), T::Balance> for OldBalances<T> {
+	type Query = Option<T::Balance>;
+	type Hasher = Blake2_256;
+	fn module_prefix() -> &'static [u8] { b"Assets" }
+	fn storage_prefix() -> &'static [u8] { b"Balances" }
+	fn from_optional_value_to_query(v: Option<T::Balance>) -> Self::Query { v }
+	fn from_query_to_optional_value(v: Self::Query) -> Option<T::Balance> { v }
+}
+
+// the `TotalSupply` storage item as it was laid out before asset details were introduced.
+pub(crate) struct OldTotalSupply<T>(PhantomData<T>);
+
+impl<T: Trait> generator::StorageMap<T::AssetId, T::Balance> for OldTotalSupply<T> {
+	type Query = Option<T::Balance>;
+	type Hasher = Blake2_256;
+	fn module_prefix() -> &'static [u8] { b"Assets" }
+	fn storage_prefix() -> &'static [u8] { b"TotalSupply" }
+	fn from_optional_value_to_query(v: Option<T::Balance>) -> Self::Query { v }
+	fn from_query_to_optional_value(v: Self::Query) -> Option<T::Balance> { v }
+}
+
+/// Turn the total supply of every asset issued so far into the details of that asset.
+///
+/// Assets used to have no owner, issuer, admin or freezer, so these are all set to the default
+/// account. Their minimum balance is a single unit, as with `issue`. The balances can't be moved
+/// here as they are keyed by a hash of the asset and the account; see `migrate_account`.
+pub fn upgrade<T: Trait>() {
+	sp_runtime::print("Upgrading assets...");
+	let mut id = T::AssetId::zero();
+	while id < <Module<T>>::next_asset_id() {
+		if let Some(supply) = OldTotalSupply::<T>::take(id) {
+			if !<Asset<T>>::contains_key(id) {
+				<Asset<T>>::insert(id, AssetDetails {
+					owner: Default::default(),
+					issuer: Default::default(),
+					admin: Default::default(),
+					freezer: Default::default(),
+					supply,
+					min_balance: One::one(),
+					accounts: 0,
+					is_frozen: false,
+				});
+			}
+		}
+		id += One::one();
+	}
+}
+
+/// Move the balance of `who` in asset `id` out of the old `Balances` storage item, returning
+/// whether there was anything to move.
+pub fn migrate_account<T: Trait>(id: T::AssetId, who: &T::AccountId) -> bool {
+	let mut details = match <Asset<T>>::get(id) {
+		Some(details) => details,
+		None => return false,
+	};
+	let balance = match OldBalances::<T>::take((id, who.clone())) {
+		Some(balance) if !balance.is_zero() => balance,
+		_ => return false,
+	};
+	let mut account: AssetBalance<T::Balance> = <Account<T>>::get(id, who);
+	account.balance = account.balance.saturating_add(balance);
+	<Module<T>>::write_account(id, who, &mut details, account);
+	<Asset<T>>::insert(id, details);
+	true
+}
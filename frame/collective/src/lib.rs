@@ -19,6 +19,11 @@
 //!
 //! The membership can be provided in one of two ways: either directly, using the Root-dispatchable
 //! function `set_members`, or indirectly, through implementing the `ChangeMembers`
+//!
+//! A motion has a voting period of `MotionDuration` blocks. Once it is over, or once the outcome
+//! is decided, anyone may `close` the motion. Members who have not voted by then are counted as
+//! voting the same way as the prime member, or against the motion if the prime member voted
+//! against it, abstained, or there is no prime member.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![recursion_limit="128"]
@@ -30,8 +35,8 @@ use sp_runtime::traits::{Hash, EnsureOrigin};
 use frame_support::weights::SimpleDispatchInfo;
 use frame_support::{
 	dispatch::{Dispatchable, Parameter}, codec::{Encode, Decode},
-	traits::{ChangeMembers, InitializeMembers}, decl_module, decl_event,
-	decl_storage, decl_error, ensure,
+	traits::{ChangeMembers, InitializeMembers, Get}, decl_module, decl_event, BoundedVec,
+	decl_storage, decl_error, ensure, storage::StoragePrefixedMap,
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
 
@@ -53,6 +58,12 @@ pub trait Trait<I=DefaultInstance>: frame_system::Trait {
 
 	/// The outer event type.
 	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The time-out for council motions.
+	type MotionDuration: Get<Self::BlockNumber>;
+
+	/// Maximum number of proposals allowed to be active in parallel.
+	type MaxProposals: Get<u32>;
 }
 
 /// Origin for the collective module.
@@ -71,7 +82,7 @@ pub type Origin<T, I=DefaultInstance> = RawOrigin<<T as frame_system::Trait>::Ac
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 /// Info for keeping track of a motion being voted on.
-pub struct Votes<AccountId> {
+pub struct Votes<AccountId, BlockNumber> {
 	/// The proposal's unique index.
 	index: ProposalIndex,
 	/// The number of approval votes that are needed to pass the motion.
@@ -80,22 +91,32 @@ pub struct Votes<AccountId> {
 	ayes: Vec<AccountId>,
 	/// The current set of voters that rejected it.
 	nays: Vec<AccountId>,
+	/// The hard end time of this vote.
+	end: BlockNumber,
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait<I>, I: Instance=DefaultInstance> as Collective {
 		/// The hashes of the active proposals.
-		pub Proposals get(fn proposals): Vec<T::Hash>;
+		pub Proposals get(fn proposals): BoundedVec<T::Hash, T::MaxProposals>;
 		/// Actual proposal for a given hash, if it's current.
 		pub ProposalOf get(fn proposal_of):
 			map hasher(blake2_256) T::Hash => Option<<T as Trait<I>>::Proposal>;
 		/// Votes on a given proposal, if it is ongoing.
 		pub Voting get(fn voting):
-			map hasher(blake2_256) T::Hash => Option<Votes<T::AccountId>>;
+			map hasher(blake2_256) T::Hash => Option<Votes<T::AccountId, T::BlockNumber>>;
 		/// Proposals so far.
 		pub ProposalCount get(fn proposal_count): u32;
 		/// The current members of the collective. This is stored sorted (just by value).
 		pub Members get(fn members): Vec<T::AccountId>;
+		/// The member who provides the default vote for any other members that do not vote before
+		/// the timeout. If None, then no member has that privilege.
+		pub Prime get(fn prime): Option<T::AccountId>;
+
+		/// True if network has been upgraded to store an end block for each motion.
+		///
+		/// True for new networks.
+		IsUpgraded build(|_: &GenesisConfig<T, I>| true): bool;
 	}
 	add_extra_genesis {
 		config(phantom): sp_std::marker::PhantomData<I>;
@@ -123,6 +144,9 @@ decl_event! {
 		Executed(Hash, bool),
 		/// A single member did some action; `bool` is true if returned without error.
 		MemberExecuted(Hash, bool),
+		/// A proposal was closed after its duration was up, leaving a tally (yes votes and no
+		/// votes given respectively as `MemberCount`, abstentions counted as per the prime).
+		Closed(Hash, MemberCount, MemberCount),
 	}
 }
 
@@ -140,6 +164,10 @@ decl_error! {
 		DuplicateVote,
 		/// Members are already initialized!
 		AlreadyInitialized,
+		/// The close call is made too early, before the end of the voting.
+		TooEarly,
+		/// There can only be a maximum of `MaxProposals` active proposals.
+		TooManyProposals,
 	}
 }
 
@@ -152,12 +180,22 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_initialize() {
+			if !IsUpgraded::<I>::get() {
+				IsUpgraded::<I>::put(true);
+				Self::do_upgrade();
+			}
+		}
+
 		/// Set the collective's membership manually to `new_members`. Be nice to the chain and
 		/// provide it pre-sorted.
 		///
+		/// `prime` becomes the prime member; it should be one of `new_members`, otherwise no prime
+		/// member is set.
+		///
 		/// Requires root origin.
 		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
-		fn set_members(origin, new_members: Vec<T::AccountId>) {
+		fn set_members(origin, new_members: Vec<T::AccountId>, prime: Option<T::AccountId>) {
 			ensure_root(origin)?;
 			let mut new_members = new_members;
 			new_members.sort();
@@ -165,6 +203,7 @@ decl_module! {
 				<Self as ChangeMembers<T::AccountId>>::set_members_sorted(&new_members[..], m);
 				*m = new_members;
 			});
+			<Self as ChangeMembers<T::AccountId>>::set_prime(prime);
 		}
 
 		/// Dispatch a proposal from a member using the `Member` origin.
@@ -198,11 +237,15 @@ decl_module! {
 				let ok = proposal.dispatch(RawOrigin::Members(1, seats).into()).is_ok();
 				Self::deposit_event(RawEvent::Executed(proposal_hash, ok));
 			} else {
+				let mut proposals = Self::proposals();
+				proposals.try_push(proposal_hash).map_err(|_| Error::<T, I>::TooManyProposals)?;
+
 				let index = Self::proposal_count();
 				<ProposalCount<I>>::mutate(|i| *i += 1);
-				<Proposals<T, I>>::mutate(|proposals| proposals.push(proposal_hash));
+				<Proposals<T, I>>::put(proposals);
 				<ProposalOf<T, I>>::insert(proposal_hash, *proposal);
-				let votes = Votes { index, threshold, ayes: vec![who.clone()], nays: vec![] };
+				let end = <frame_system::Module<T>>::block_number() + T::MotionDuration::get();
+				let votes = Votes { index, threshold, ayes: vec![who.clone()], nays: vec![], end };
 				<Voting<T, I>>::insert(proposal_hash, votes);
 
 				Self::deposit_event(RawEvent::Proposed(who, index, proposal_hash, threshold));
@@ -251,30 +294,70 @@ decl_module! {
 			let seats = Self::members().len() as MemberCount;
 			let approved = yes_votes >= voting.threshold;
 			let disapproved = seats.saturating_sub(no_votes) < voting.threshold;
-			if approved || disapproved {
-				if approved {
-					Self::deposit_event(RawEvent::Approved(proposal));
-
-					// execute motion, assuming it exists.
-					if let Some(p) = <ProposalOf<T, I>>::take(&proposal) {
-						let origin = RawOrigin::Members(voting.threshold, seats).into();
-						let ok = p.dispatch(origin).is_ok();
-						Self::deposit_event(RawEvent::Executed(proposal, ok));
-					}
-				} else {
-					// disapproved
-					<ProposalOf<T, I>>::remove(&proposal);
-					Self::deposit_event(RawEvent::Disapproved(proposal));
-				}
-
-				// remove vote
-				<Voting<T, I>>::remove(&proposal);
-				<Proposals<T, I>>::mutate(|proposals| proposals.retain(|h| h != &proposal));
+			if approved {
+				Self::do_approve_proposal(seats, voting, proposal);
+			} else if disapproved {
+				Self::do_disapprove_proposal(proposal);
 			} else {
 				// update voting
 				<Voting<T, I>>::insert(&proposal, voting);
 			}
 		}
+
+		/// Close a vote that is either approved, disapproved or whose voting period has ended.
+		///
+		/// Once the voting period has ended, members who have not voted are counted as voting the
+		/// same way as the prime member if the prime member voted in favour, and against the
+		/// motion otherwise.
+		///
+		/// May be called by any signed account.
+		///
+		/// # <weight>
+		/// - Bounded storage reads and writes.
+		/// - Will be slightly heavier if the proposal is approved and executed.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(200_000)]
+		fn close(origin, proposal: T::Hash, #[compact] index: ProposalIndex) {
+			let _ = ensure_signed(origin)?;
+
+			let voting = Self::voting(&proposal).ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(voting.index == index, Error::<T, I>::WrongIndex);
+
+			let mut no_votes = voting.nays.len() as MemberCount;
+			let mut yes_votes = voting.ayes.len() as MemberCount;
+			let seats = Self::members().len() as MemberCount;
+			let approved = yes_votes >= voting.threshold;
+			let disapproved = seats.saturating_sub(no_votes) < voting.threshold;
+			if approved {
+				Self::deposit_event(RawEvent::Closed(proposal, yes_votes, no_votes));
+				Self::do_approve_proposal(seats, voting, proposal);
+				return Ok(());
+			} else if disapproved {
+				Self::deposit_event(RawEvent::Closed(proposal, yes_votes, no_votes));
+				Self::do_disapprove_proposal(proposal);
+				return Ok(());
+			}
+
+			// Only allow actual closing of the proposal after the voting period has ended.
+			ensure!(<frame_system::Module<T>>::block_number() >= voting.end, Error::<T, I>::TooEarly);
+
+			// default to true only if there's a prime and they voted in favour.
+			let default = Self::prime().map_or(false, |who| voting.ayes.iter().any(|a| a == &who));
+
+			let abstentions = seats.saturating_sub(yes_votes.saturating_add(no_votes));
+			if default {
+				yes_votes = yes_votes.saturating_add(abstentions);
+			} else {
+				no_votes = no_votes.saturating_add(abstentions);
+			}
+
+			Self::deposit_event(RawEvent::Closed(proposal, yes_votes, no_votes));
+			if yes_votes >= voting.threshold {
+				Self::do_approve_proposal(seats, voting, proposal);
+			} else {
+				Self::do_disapprove_proposal(proposal);
+			}
+		}
 	}
 }
 
@@ -282,6 +365,56 @@ impl<T: Trait<I>, I: Instance> Module<T, I> {
 	pub fn is_member(who: &T::AccountId) -> bool {
 		Self::members().contains(who)
 	}
+
+	/// Migrate ongoing motions to carry an end block, which is `MotionDuration` from now.
+	fn do_upgrade() {
+		#[derive(Decode)]
+		struct OldVotes<AccountId> {
+			index: ProposalIndex,
+			threshold: MemberCount,
+			ayes: Vec<AccountId>,
+			nays: Vec<AccountId>,
+		}
+
+		let end = <frame_system::Module<T>>::block_number() + T::MotionDuration::get();
+		let res = <Module<T, I> as Store>::Voting::translate_values(|old: OldVotes<T::AccountId>|
+			Votes { index: old.index, threshold: old.threshold, ayes: old.ayes, nays: old.nays, end }
+		);
+		if res.is_err() {
+			frame_support::print("Encountered error in migration of Collective::Voting map.");
+		}
+	}
+
+	/// Emit `Approved`, execute the motion and remove it.
+	fn do_approve_proposal(
+		seats: MemberCount,
+		voting: Votes<T::AccountId, T::BlockNumber>,
+		proposal: T::Hash,
+	) {
+		Self::deposit_event(RawEvent::Approved(proposal));
+
+		// execute motion, assuming it exists.
+		if let Some(p) = <ProposalOf<T, I>>::take(&proposal) {
+			let origin = RawOrigin::Members(voting.threshold, seats).into();
+			let ok = p.dispatch(origin).is_ok();
+			Self::deposit_event(RawEvent::Executed(proposal, ok));
+		}
+
+		Self::remove_proposal(proposal)
+	}
+
+	/// Emit `Disapproved` and remove the motion.
+	fn do_disapprove_proposal(proposal: T::Hash) {
+		<ProposalOf<T, I>>::remove(&proposal);
+		Self::deposit_event(RawEvent::Disapproved(proposal));
+		Self::remove_proposal(proposal)
+	}
+
+	/// Remove the votes on a motion and the motion itself from the active proposals.
+	fn remove_proposal(proposal: T::Hash) {
+		<Voting<T, I>>::remove(&proposal);
+		<Proposals<T, I>>::mutate(|proposals| proposals.retain(|h| h != &proposal));
+	}
 }
 
 impl<T: Trait<I>, I: Instance> ChangeMembers<T::AccountId> for Module<T, I> {
@@ -303,6 +436,16 @@ impl<T: Trait<I>, I: Instance> ChangeMembers<T::AccountId> for Module<T, I> {
 			);
 		}
 		<Members<T, I>>::put(new);
+		if Self::prime().map_or(false, |p| outgoing.binary_search(&p).is_ok()) {
+			Prime::<T, I>::kill();
+		}
+	}
+
+	fn set_prime(prime: Option<T::AccountId>) {
+		match prime {
+			Some(p) if Self::is_member(&p) => Prime::<T, I>::put(p),
+			_ => Prime::<T, I>::kill(),
+		}
 	}
 }
 
@@ -434,15 +577,23 @@ mod tests {
 		type Version = ();
 		type ModuleToIndex = ();
 	}
+	parameter_types! {
+		pub const MotionDuration: u64 = 3;
+		pub const MaxProposals: u32 = 100;
+	}
 	impl Trait<Instance1> for Test {
 		type Origin = Origin;
 		type Proposal = Call;
 		type Event = Event;
+		type MotionDuration = MotionDuration;
+		type MaxProposals = MaxProposals;
 	}
 	impl Trait for Test {
 		type Origin = Origin;
 		type Proposal = Call;
 		type Event = Event;
+		type MotionDuration = MotionDuration;
+		type MaxProposals = MaxProposals;
 	}
 
 	pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -483,6 +634,133 @@ mod tests {
 		Call::System(frame_system::Call::remark(value.encode()))
 	}
 
+	fn record(event: Event) -> EventRecord<Event, H256> {
+		EventRecord { phase: Phase::Finalization, event, topics: vec![] }
+	}
+
+	#[test]
+	fn close_works() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+
+			System::set_block_number(3);
+			assert_noop!(
+				Collective::close(Origin::signed(4), hash.clone(), 0),
+				Error::<Test, Instance1>::TooEarly
+			);
+
+			System::set_block_number(4);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+
+			let record = |event| record(Event::collective_Instance1(event));
+			assert_eq!(System::events(), vec![
+				record(RawEvent::Proposed(1, 0, hash.clone(), 3)),
+				record(RawEvent::Voted(2, hash.clone(), true, 2, 0)),
+				record(RawEvent::Closed(hash.clone(), 2, 1)),
+				record(RawEvent::Disapproved(hash.clone()))
+			]);
+			assert_eq!(Collective::proposals(), vec![]);
+			assert_eq!(Collective::voting(&hash), None);
+		});
+	}
+
+	#[test]
+	fn close_with_prime_works() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(3)));
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+
+			System::set_block_number(4);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+
+			let record = |event| record(Event::collective_Instance1(event));
+			assert_eq!(System::events(), vec![
+				record(RawEvent::Proposed(1, 0, hash.clone(), 3)),
+				record(RawEvent::Voted(2, hash.clone(), true, 2, 0)),
+				record(RawEvent::Closed(hash.clone(), 2, 1)),
+				record(RawEvent::Disapproved(hash.clone()))
+			]);
+		});
+	}
+
+	#[test]
+	fn close_with_voting_prime_works() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(1)));
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+
+			System::set_block_number(4);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+
+			let record = |event| record(Event::collective_Instance1(event));
+			assert_eq!(System::events(), vec![
+				record(RawEvent::Proposed(1, 0, hash.clone(), 3)),
+				record(RawEvent::Voted(2, hash.clone(), true, 2, 0)),
+				record(RawEvent::Closed(hash.clone(), 3, 0)),
+				record(RawEvent::Approved(hash.clone())),
+				record(RawEvent::Executed(hash.clone(), false))
+			]);
+		});
+	}
+
+	#[test]
+	fn close_of_decided_motion_works_before_end() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+			// With only two members left, the threshold can no longer be reached.
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2], None));
+
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+			assert_noop!(
+				Collective::close(Origin::signed(4), hash.clone(), 0),
+				Error::<Test, Instance1>::ProposalMissing
+			);
+
+			let record = |event| record(Event::collective_Instance1(event));
+			assert_eq!(System::events(), vec![
+				record(RawEvent::Proposed(1, 0, hash.clone(), 3)),
+				record(RawEvent::Voted(2, hash.clone(), true, 2, 0)),
+				record(RawEvent::Closed(hash.clone(), 2, 0)),
+				record(RawEvent::Disapproved(hash.clone()))
+			]);
+		});
+	}
+
+	#[test]
+	fn prime_must_be_a_member() {
+		make_ext().execute_with(|| {
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(4)));
+			assert_eq!(Collective::prime(), None);
+
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(3)));
+			assert_eq!(Collective::prime(), Some(3));
+
+			// The prime is cleared once they are no longer a member.
+			Collective::change_members_sorted(&[4], &[3], &[1, 2, 4]);
+			assert_eq!(Collective::prime(), None);
+		});
+	}
+
 	#[test]
 	fn removal_of_old_voters_votes_works() {
 		make_ext().execute_with(|| {
@@ -493,12 +771,12 @@ mod tests {
 			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 3, ayes: vec![1, 2], nays: vec![] })
+				Some(Votes { index: 0, threshold: 3, ayes: vec![1, 2], nays: vec![], end: 4 })
 			);
 			Collective::change_members_sorted(&[4], &[1], &[2, 3, 4]);
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 3, ayes: vec![2], nays: vec![] })
+				Some(Votes { index: 0, threshold: 3, ayes: vec![2], nays: vec![], end: 4 })
 			);
 
 			let proposal = make_proposal(69);
@@ -507,12 +785,12 @@ mod tests {
 			assert_ok!(Collective::vote(Origin::signed(3), hash.clone(), 1, false));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![3] })
+				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![3], end: 4 })
 			);
 			Collective::change_members_sorted(&[], &[3], &[2, 4]);
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![] })
+				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![], end: 4 })
 			);
 		});
 	}
@@ -527,12 +805,12 @@ mod tests {
 			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 3, ayes: vec![1, 2], nays: vec![] })
+				Some(Votes { index: 0, threshold: 3, ayes: vec![1, 2], nays: vec![], end: 4 })
 			);
-			assert_ok!(Collective::set_members(Origin::ROOT, vec![2, 3, 4]));
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![2, 3, 4], None));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 3, ayes: vec![2], nays: vec![] })
+				Some(Votes { index: 0, threshold: 3, ayes: vec![2], nays: vec![], end: 4 })
 			);
 
 			let proposal = make_proposal(69);
@@ -541,16 +819,31 @@ mod tests {
 			assert_ok!(Collective::vote(Origin::signed(3), hash.clone(), 1, false));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![3] })
+				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![3], end: 4 })
 			);
-			assert_ok!(Collective::set_members(Origin::ROOT, vec![2, 4]));
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![2, 4], None));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![] })
+				Some(Votes { index: 1, threshold: 2, ayes: vec![2], nays: vec![], end: 4 })
 			);
 		});
 	}
 
+	#[test]
+	fn limit_active_proposals() {
+		make_ext().execute_with(|| {
+			for i in 0..MaxProposals::get() {
+				let proposal = make_proposal(i as u64);
+				assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal)));
+			}
+			let proposal = make_proposal(MaxProposals::get() as u64);
+			assert_noop!(
+				Collective::propose(Origin::signed(1), 3, Box::new(proposal)),
+				Error::<Test, Instance1>::TooManyProposals
+			);
+		})
+	}
+
 	#[test]
 	fn propose_works() {
 		make_ext().execute_with(|| {
@@ -562,7 +855,7 @@ mod tests {
 			assert_eq!(Collective::proposal_of(&hash), Some(proposal));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 3, ayes: vec![1], nays: vec![] })
+				Some(Votes { index: 0, threshold: 3, ayes: vec![1], nays: vec![], end: 4 })
 			);
 
 			assert_eq!(System::events(), vec![
@@ -629,7 +922,7 @@ mod tests {
 			assert_ok!(Collective::propose(Origin::signed(1), 2, Box::new(proposal.clone())));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 2, ayes: vec![1], nays: vec![] })
+				Some(Votes { index: 0, threshold: 2, ayes: vec![1], nays: vec![], end: 4 })
 			);
 			assert_noop!(
 				Collective::vote(Origin::signed(1), hash.clone(), 0, true),
@@ -638,7 +931,7 @@ mod tests {
 			assert_ok!(Collective::vote(Origin::signed(1), hash.clone(), 0, false));
 			assert_eq!(
 				Collective::voting(&hash),
-				Some(Votes { index: 0, threshold: 2, ayes: vec![], nays: vec![1] })
+				Some(Votes { index: 0, threshold: 2, ayes: vec![], nays: vec![1], end: 4 })
 			);
 			assert_noop!(
 				Collective::vote(Origin::signed(1), hash.clone(), 0, false),
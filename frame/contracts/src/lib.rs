@@ -113,7 +113,7 @@ use sp_std::{prelude::*, marker::PhantomData, fmt::Debug};
 use codec::{Codec, Encode, Decode};
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{Hash, StaticLookup, Zero, MaybeSerializeDeserialize, Member, SignedExtension},
+	traits::{Hash, StaticLookup, Zero, MaybeSerializeDeserialize, Member, SignedExtension, GetTip},
 	transaction_validity::{
 		ValidTransaction, InvalidTransaction, TransactionValidity, TransactionValidityError,
 	},
@@ -1097,6 +1097,8 @@ impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckBlockGasLimit<T> {
 	}
 }
 
+impl<T: Trait + Send + Sync, Balance> GetTip<Balance> for CheckBlockGasLimit<T> {}
+
 impl<T: Trait + Send + Sync> SignedExtension for CheckBlockGasLimit<T> {
 	const IDENTIFIER: &'static str = "CheckBlockGasLimit";
 	type AccountId = T::AccountId;
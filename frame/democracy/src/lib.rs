@@ -19,15 +19,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
-use sp_std::{result, convert::TryFrom};
 use sp_runtime::{
-	RuntimeDebug, DispatchResult,
-	traits::{Zero, Bounded, CheckedMul, CheckedDiv, EnsureOrigin, Hash, Dispatchable, Saturating},
+	DispatchResult, DispatchError,
+	traits::{Zero, Bounded, EnsureOrigin, Hash, Dispatchable, Saturating},
 };
-use codec::{Ref, Encode, Decode, Input, Output};
+use codec::{Ref, Decode};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
-	weights::SimpleDispatchInfo,
+	dispatch::DispatchResultWithPostInfo,
+	weights::{SimpleDispatchInfo, Weight, WeighData, ClassifyDispatch, PaysFee, DispatchClass},
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
 		OnReapAccount, OnUnbalanced
@@ -36,7 +36,14 @@ use frame_support::{
 use frame_system::{self as system, ensure_signed, ensure_root};
 
 mod vote_threshold;
+mod vote;
+mod conviction;
+mod types;
+mod migration;
 pub use vote_threshold::{Approved, VoteThreshold};
+pub use vote::{Vote, AccountVote, Voting, PriorLock};
+pub use conviction::Conviction;
+pub use types::{ReferendumInfo, ReferendumStatus, Tally, UnvoteScope, Delegations};
 
 const DEMOCRACY_ID: LockIdentifier = *b"democrac";
 
@@ -46,131 +53,6 @@ pub type PropIndex = u32;
 /// A referendum index.
 pub type ReferendumIndex = u32;
 
-/// A value denoting the strength of conviction of a vote.
-#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
-pub enum Conviction {
-	/// 0.1x votes, unlocked.
-	None,
-	/// 1x votes, locked for an enactment period following a successful vote.
-	Locked1x,
-	/// 2x votes, locked for 2x enactment periods following a successful vote.
-	Locked2x,
-	/// 3x votes, locked for 4x...
-	Locked3x,
-	/// 4x votes, locked for 8x...
-	Locked4x,
-	/// 5x votes, locked for 16x...
-	Locked5x,
-	/// 6x votes, locked for 32x...
-	Locked6x,
-}
-
-impl Default for Conviction {
-	fn default() -> Self {
-		Conviction::None
-	}
-}
-
-impl From<Conviction> for u8 {
-	fn from(c: Conviction) -> u8 {
-		match c {
-			Conviction::None => 0,
-			Conviction::Locked1x => 1,
-			Conviction::Locked2x => 2,
-			Conviction::Locked3x => 3,
-			Conviction::Locked4x => 4,
-			Conviction::Locked5x => 5,
-			Conviction::Locked6x => 6,
-		}
-	}
-}
-
-impl TryFrom<u8> for Conviction {
-	type Error = ();
-	fn try_from(i: u8) -> result::Result<Conviction, ()> {
-		Ok(match i {
-			0 => Conviction::None,
-			1 => Conviction::Locked1x,
-			2 => Conviction::Locked2x,
-			3 => Conviction::Locked3x,
-			4 => Conviction::Locked4x,
-			5 => Conviction::Locked5x,
-			6 => Conviction::Locked6x,
-			_ => return Err(()),
-		})
-	}
-}
-
-impl Conviction {
-	/// The amount of time (in number of periods) that our conviction implies a successful voter's
-	/// balance should be locked for.
-	fn lock_periods(self) -> u32 {
-		match self {
-			Conviction::None => 0,
-			Conviction::Locked1x => 1,
-			Conviction::Locked2x => 2,
-			Conviction::Locked3x => 4,
-			Conviction::Locked4x => 8,
-			Conviction::Locked5x => 16,
-			Conviction::Locked6x => 32,
-		}
-	}
-
-	/// The votes of a voter of the given `balance` with our conviction.
-	fn votes<
-		B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded
-	>(self, balance: B) -> (B, B) {
-		match self {
-			Conviction::None => {
-				let r = balance.checked_div(&10u8.into()).unwrap_or_else(Zero::zero);
-				(r, r)
-			}
-			x => (
-				balance.checked_mul(&u8::from(x).into()).unwrap_or_else(B::max_value),
-				balance,
-			)
-		}
-	}
-}
-
-impl Bounded for Conviction {
-	fn min_value() -> Self {
-		Conviction::None
-	}
-
-	fn max_value() -> Self {
-		Conviction::Locked6x
-	}
-}
-
-const MAX_RECURSION_LIMIT: u32 = 16;
-
-/// A number of lock periods, plus a vote, one way or the other.
-#[derive(Copy, Clone, Eq, PartialEq, Default, RuntimeDebug)]
-pub struct Vote {
-	pub aye: bool,
-	pub conviction: Conviction,
-}
-
-impl Encode for Vote {
-	fn encode_to<T: Output>(&self, output: &mut T) {
-		output.push_byte(u8::from(self.conviction) | if self.aye { 0b1000_0000 } else { 0 });
-	}
-}
-
-impl codec::EncodeLike for Vote {}
-
-impl Decode for Vote {
-	fn decode<I: Input>(input: &mut I) -> core::result::Result<Self, codec::Error> {
-		let b = input.read_byte()?;
-		Ok(Vote {
-			aye: (b & 0b1000_0000) == 0b1000_0000,
-			conviction: Conviction::try_from(b & 0b0111_1111)
-				.map_err(|_| codec::Error::from("Invalid conviction"))?,
-		})
-	}
-}
-
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> =
 <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
@@ -233,30 +115,40 @@ pub trait Trait: frame_system::Trait + Sized {
 
 	/// Handler for the unbalanced reduction when slashing a preimage deposit.
 	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
-}
 
-/// Info regarding an ongoing referendum.
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct ReferendumInfo<BlockNumber: Parameter, Hash: Parameter> {
-	/// When voting on this referendum will end.
-	end: BlockNumber,
-	/// The hash of the proposal being voted on.
-	proposal_hash: Hash,
-	/// The thresholding mechanism to determine whether it passed.
-	threshold: VoteThreshold,
-	/// The delay (in blocks) to wait after a successful referendum before deploying.
-	delay: BlockNumber,
+	/// Origin from which a proposal may be blacklisted.
+	type BlacklistOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The maximum number of referenda an account may have votes recorded on at once.
+	type MaxVotes: Get<u32>;
+
+	/// The maximum number of accounts in a chain of delegations, including the account voting
+	/// directly at its end.
+	type MaxDelegationDepth: Get<u32>;
 }
 
-impl<BlockNumber: Parameter, Hash: Parameter> ReferendumInfo<BlockNumber, Hash> {
-	/// Create a new instance.
-	pub fn new(
-		end: BlockNumber,
-		proposal_hash: Hash,
-		threshold: VoteThreshold,
-		delay: BlockNumber
-	) -> Self {
-		ReferendumInfo { end, proposal_hash, threshold, delay }
+/// The weight of `delegate` and `undelegate`, which walk a delegation chain and update the tally
+/// of every referendum its end has voted on.
+///
+/// This is the worst case, given `MaxDelegationDepth` and `MaxVotes`. The unused part is refunded.
+struct DelegationWeight<T>(sp_std::marker::PhantomData<T>);
+
+impl<T> DelegationWeight<T> {
+	fn new() -> Self { Self(Default::default()) }
+}
+impl<T: Trait, Args> WeighData<Args> for DelegationWeight<T> {
+	fn weigh_data(&self, _: Args) -> Weight {
+		Module::<T>::delegation_weight(T::MaxDelegationDepth::get(), T::MaxVotes::get())
+	}
+}
+impl<T, Args> ClassifyDispatch<Args> for DelegationWeight<T> {
+	fn classify_dispatch(&self, _: Args) -> DispatchClass {
+		DispatchClass::Normal
+	}
+}
+impl<T, Args> PaysFee<Args> for DelegationWeight<T> {
+	fn pays_fee(&self, _: Args) -> bool {
+		true
 	}
 }
 
@@ -283,30 +175,22 @@ decl_storage! {
 		/// Information concerning any given referendum.
 		pub ReferendumInfoOf get(fn referendum_info):
 			map hasher(blake2_256) ReferendumIndex
-			=> Option<ReferendumInfo<T::BlockNumber, T::Hash>>;
+			=> Option<ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>>;
 		/// Queue of successful referenda to be dispatched. Stored ordered by block number.
 		pub DispatchQueue get(fn dispatch_queue): Vec<(T::BlockNumber, T::Hash, ReferendumIndex)>;
 
-		/// Get the voters for the current proposal.
-		pub VotersFor get(fn voters_for):
-			map hasher(blake2_256) ReferendumIndex => Vec<T::AccountId>;
-
-		/// Get the vote in a given referendum of a particular voter. The result is meaningful only
-		/// if `voters_for` includes the voter when called with the referendum (you'll get the
-		/// default `Vote` value otherwise). If you don't want to check `voters_for`, then you can
-		/// also check for simple existence with `VoteOf::contains_key` first.
-		pub VoteOf get(fn vote_of): map hasher(blake2_256) (ReferendumIndex, T::AccountId) => Vote;
+		/// What each account is doing: either voting directly, along with the delegations it has
+		/// received, or delegating its votes to another account.
+		pub VotingOf get(fn voting_of):
+			map hasher(twox_64_concat) T::AccountId => Voting<BalanceOf<T>, T::AccountId, T::BlockNumber>;
 
 		/// Who is able to vote for whom. Value is the fund-holding account, key is the
 		/// vote-transaction-sending account.
 		pub Proxy get(fn proxy): map hasher(blake2_256) T::AccountId => Option<T::AccountId>;
 
-		/// Get the account (and lock periods) to which another account is delegating vote.
-		pub Delegations get(fn delegations):
-			linked_map hasher(blake2_256) T::AccountId => (T::AccountId, Conviction);
-
-		/// Accounts for which there are locks in action which may be removed at some point in the
-		/// future. The value is the block number at which the lock expires and may be removed.
+		/// Accounts for which there are legacy, indefinite locks in action which may be removed at
+		/// some point in the future. The value is the block number at which the lock expires and
+		/// may be removed. Locks taken by voting and delegating are tracked in `VotingOf` instead.
 		pub Locks get(locks): map hasher(blake2_256) T::AccountId => Option<T::BlockNumber>;
 
 		/// True if the last referendum tabled was submitted externally. False if it was a public
@@ -320,12 +204,21 @@ decl_storage! {
 		pub NextExternal: Option<(T::Hash, VoteThreshold)>;
 
 		/// A record of who vetoed what. Maps proposal hash to a possible existent block number
-		/// (until when it may not be resubmitted) and who vetoed it.
-		pub Blacklist get(fn blacklist):
+		/// (until when it may not be resubmitted) and who vetoed it. Proposals blacklisted through
+		/// `blacklist` are banned until the maximum block number, i.e. permanently.
+		pub Blacklist get(fn blacklist_of):
 			map hasher(blake2_256) T::Hash => Option<(T::BlockNumber, Vec<T::AccountId>)>;
 
 		/// Record of all proposals that have been subject to emergency cancellation.
 		pub Cancellations: map hasher(blake2_256) T::Hash => bool;
+
+		/// True if the network has been upgraded to store `Delegations` in an iterable map.
+		///
+		/// True for new networks.
+		IsUpgraded build(|_: &GenesisConfig| true): bool;
+
+		/// The version of the storage layout, used to perform storage migrations.
+		StorageVersion build(|_: &GenesisConfig| migration::CURRENT_VERSION): migration::VersionNumber;
 	}
 }
 
@@ -370,6 +263,10 @@ decl_event! {
 		PreimageReaped(Hash, AccountId, Balance, AccountId),
 		/// An account has been unlocked successfully.
 		Unlocked(AccountId),
+		/// A proposal preimage was removed after its referendum failed (the deposit was returned).
+		PreimageRefunded(Hash, AccountId, Balance),
+		/// A proposal hash has been blacklisted permanently.
+		Blacklisted(Hash),
 	}
 }
 
@@ -423,6 +320,29 @@ decl_error! {
 		NotLocked,
 		/// The lock on the account to be unlocked has not yet expired.
 		NotExpired,
+		/// The account is already delegating.
+		AlreadyDelegating,
+		/// Too high a balance was provided that the account cannot afford.
+		InsufficientFunds,
+		/// The given account did not vote on the referendum.
+		NotVoter,
+		/// The actor has no permission to conduct the action.
+		NoPermission,
+		/// The account currently has votes attached to it and the operation cannot succeed until
+		/// these are removed, through `remove_vote`.
+		VotesExist,
+		/// Delegation to oneself, directly or through a chain of delegations, makes no sense.
+		Nonsense,
+		/// The account has votes recorded on `MaxVotes` referenda already.
+		MaxVotesReached,
+		/// The delegation would make a chain of more than `MaxDelegationDepth` accounts.
+		DelegationTooDeep,
+		/// The account has received delegations, so it cannot delegate itself.
+		DelegationsReceived,
+		/// An unexpected integer overflow occurred.
+		Overflow,
+		/// An unexpected integer underflow occurred.
+		Underflow,
 	}
 }
 
@@ -469,6 +389,12 @@ decl_module! {
 		) {
 			let who = ensure_signed(origin)?;
 			ensure!(value >= T::MinimumDeposit::get(), Error::<T>::ValueLow);
+			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+				ensure!(
+					<frame_system::Module<T>>::block_number() >= until,
+					Error::<T>::ProposalBlacklisted,
+				);
+			}
 			T::Currency::reserve(&who, value)?;
 
 			let index = Self::public_prop_count();
@@ -497,36 +423,67 @@ decl_module! {
 			<DepositOf<T>>::insert(proposal, deposit);
 		}
 
-		/// Vote in a referendum. If `vote.is_aye()`, the vote is to enact the proposal;
-		/// otherwise it is a vote to keep the status quo.
+		/// Vote in a referendum. A standard vote puts `balance` behind one side with some
+		/// conviction; a split vote puts different amounts behind each side, without conviction.
+		///
+		/// Voting again on the same referendum replaces the previous vote. The voted balance is
+		/// locked until the vote is removed; see `remove_vote`.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - O(R) where R is the number of referenda the voter has voted on.
 		/// - One DB change, one DB entry.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
 		fn vote(origin,
 			#[compact] ref_index: ReferendumIndex,
-			vote: Vote
+			vote: AccountVote<BalanceOf<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::do_vote(who, ref_index, vote)
+			Self::try_vote(&who, ref_index, vote)
 		}
 
-		/// Vote in a referendum on behalf of a stash. If `vote.is_aye()`, the vote is to enact
-		/// the proposal; otherwise it is a vote to keep the status quo.
+		/// Vote in a referendum on behalf of a stash. Otherwise the same as `vote`.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - O(R) where R is the number of referenda the voter has voted on.
 		/// - One DB change, one DB entry.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
 		fn proxy_vote(origin,
 			#[compact] ref_index: ReferendumIndex,
-			vote: Vote
+			vote: AccountVote<BalanceOf<T>>,
 		) -> DispatchResult {
 			let who = Self::proxy(ensure_signed(origin)?).ok_or(Error::<T>::NotProxy)?;
-			Self::do_vote(who, ref_index, vote)
+			Self::try_vote(&who, ref_index, vote)
+		}
+
+		/// Remove a vote for a referendum.
+		///
+		/// If the referendum is ongoing, the vote is removed from its tally and may then be cast
+		/// again. If the referendum has finished and the vote was on the winning side with some
+		/// conviction, the voted balance stays locked until the conviction's lock period has
+		/// passed. If the referendum was cancelled, the vote is simply forgotten.
+		///
+		/// # <weight>
+		/// - O(R) where R is the number of referenda the voter has voted on.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		fn remove_vote(origin, index: ReferendumIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::try_remove_vote(&who, index, UnvoteScope::Any)
+		}
+
+		/// Remove a vote of `target` for a referendum which has finished or was cancelled, and
+		/// whose lock (if any) has expired, so that the target's lock may be reduced.
+		///
+		/// # <weight>
+		/// - O(R) where R is the number of referenda the target has voted on.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		fn remove_other_vote(origin, target: T::AccountId, index: ReferendumIndex) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::try_remove_vote(&target, index, UnvoteScope::OnlyExpired)?;
+			Ok(())
 		}
 
 		/// Schedule an emergency cancellation of a referendum. Cannot happen twice to the same
@@ -535,8 +492,8 @@ decl_module! {
 		fn emergency_cancel(origin, ref_index: ReferendumIndex) {
 			T::CancellationOrigin::ensure_origin(origin)?;
 
-			let info = Self::referendum_info(ref_index).ok_or(Error::<T>::BadIndex)?;
-			let h = info.proposal_hash;
+			let status = Self::referendum_status(ref_index)?;
+			let h = status.proposal_hash;
 			ensure!(!<Cancellations<T>>::contains_key(h), Error::<T>::AlreadyCanceled);
 
 			<Cancellations<T>>::insert(h, true);
@@ -561,22 +518,32 @@ decl_module! {
 		/// Schedule a majority-carries referendum to be tabled next once it is legal to schedule
 		/// an external referendum.
 		///
-		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
-		/// pre-scheduled `external_propose` call.
+		/// Unlike `external_propose`, it may replace a pre-scheduled `external_propose` call.
 		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
 		fn external_propose_majority(origin, proposal_hash: T::Hash) {
 			T::ExternalMajorityOrigin::ensure_origin(origin)?;
+			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+				ensure!(
+					<frame_system::Module<T>>::block_number() >= until,
+					Error::<T>::ProposalBlacklisted,
+				);
+			}
 			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SimpleMajority));
 		}
 
 		/// Schedule a negative-turnout-bias referendum to be tabled next once it is legal to
 		/// schedule an external referendum.
 		///
-		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
-		/// pre-scheduled `external_propose` call.
+		/// Unlike `external_propose`, it may replace a pre-scheduled `external_propose` call.
 		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
 		fn external_propose_default(origin, proposal_hash: T::Hash) {
 			T::ExternalDefaultOrigin::ensure_origin(origin)?;
+			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+				ensure!(
+					<frame_system::Module<T>>::block_number() >= until,
+					Error::<T>::ProposalBlacklisted,
+				);
+			}
 			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityAgainst));
 		}
 
@@ -629,12 +596,60 @@ decl_module! {
 
 			existing_vetoers.insert(insert_position, who.clone());
 			let until = <frame_system::Module<T>>::block_number() + T::CooloffPeriod::get();
+			// A veto never shortens an existing ban.
+			let until = <Blacklist<T>>::get(&proposal_hash)
+				.map_or(until, |(existing, _)| existing.max(until));
 			<Blacklist<T>>::insert(&proposal_hash, (until, existing_vetoers));
 
 			Self::deposit_event(RawEvent::Vetoed(who, proposal_hash, until));
 			<NextExternal<T>>::kill();
 		}
 
+		/// Permanently blacklist a proposal hash so that it can never be proposed again.
+		///
+		/// Any public proposal with the hash is removed and its deposits are slashed, a matching
+		/// external proposal is cancelled and, if `maybe_ref_index` is given and that referendum
+		/// is ongoing with the same proposal hash, the referendum is cancelled too.
+		///
+		/// The dispatch origin of this call must be `BlacklistOrigin`.
+		#[weight = SimpleDispatchInfo::FixedOperational(5_000_000)]
+		fn blacklist(origin, proposal_hash: T::Hash, maybe_ref_index: Option<ReferendumIndex>) {
+			T::BlacklistOrigin::ensure_origin(origin)?;
+
+			let permanent = (T::BlockNumber::max_value(), Vec::<T::AccountId>::new());
+			<Blacklist<T>>::insert(&proposal_hash, permanent);
+
+			// Remove any public proposals of it, slashing their deposits.
+			let mut public_props = Self::public_props();
+			public_props.retain(|&(prop_index, ref h, _)| if h == &proposal_hash {
+				if let Some((deposit, depositors)) = <DepositOf<T>>::take(prop_index) {
+					for who in depositors.iter() {
+						T::Slash::on_unbalanced(T::Currency::slash_reserved(who, deposit).0);
+					}
+				}
+				false
+			} else {
+				true
+			});
+			<PublicProps<T>>::put(public_props);
+
+			// Remove the queued external proposal, if it's this one.
+			if <NextExternal<T>>::get().map_or(false, |(h, _)| h == proposal_hash) {
+				<NextExternal<T>>::kill();
+			}
+
+			// Cancel the referendum, if it's this one.
+			if let Some(ref_index) = maybe_ref_index {
+				if let Ok(status) = Self::referendum_status(ref_index) {
+					if status.proposal_hash == proposal_hash {
+						Self::internal_cancel_referendum(ref_index);
+					}
+				}
+			}
+
+			Self::deposit_event(RawEvent::Blacklisted(proposal_hash));
+		}
+
 		/// Remove a referendum.
 		#[weight = SimpleDispatchInfo::FixedOperational(10_000)]
 		fn cancel_referendum(origin, #[compact] ref_index: ReferendumIndex) {
@@ -654,6 +669,8 @@ decl_module! {
 		}
 
 		fn on_initialize(n: T::BlockNumber) {
+			migration::perform_migrations::<T>();
+
 			if let Err(e) = Self::begin_block(n) {
 				sp_runtime::print(e);
 			}
@@ -697,47 +714,49 @@ decl_module! {
 			<Proxy<T>>::remove(proxy);
 		}
 
-		/// Delegate vote.
+		/// Delegate `balance` of the sender's funds to vote on behalf of `to` with `conviction`.
+		///
+		/// The delegated votes are accumulated directly into `to`'s voting record and are counted
+		/// in any referendum on which `to` votes, now or later. If `to` is itself delegating, they
+		/// are passed along the chain of delegations, together with any delegations the sender
+		/// has received, to the first account which votes directly. Each delegator's balance is
+		/// weighted by its own conviction.
+		///
+		/// The sender must not currently be voting on any referendum, nor delegating, and the
+		/// delegation must not form a cycle.
 		///
 		/// # <weight>
-		/// - One extra DB entry.
+		/// - O(D + R) where D is the length of the delegation chain above `to` and R is the number
+		///   of referenda its end has voted on, bounded by `MaxDelegationDepth` and `MaxVotes`.
+		/// - Only the weight of the chain actually walked is charged.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
-		pub fn delegate(origin, to: T::AccountId, conviction: Conviction) {
+		#[weight = DelegationWeight::<T>::new()]
+		pub fn delegate(origin,
+			to: T::AccountId,
+			conviction: Conviction,
+			balance: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			<Delegations<T>>::insert(&who, (&to, conviction));
-			// Currency is locked indefinitely as long as it's delegated.
-			T::Currency::extend_lock(
-				DEMOCRACY_ID,
-				&who,
-				Bounded::max_value(),
-				WithdrawReason::Transfer.into()
-			);
-			Locks::<T>::remove(&who);
-			Self::deposit_event(RawEvent::Delegated(who, to));
+			let (depth, votes) = Self::try_delegate(who, to, conviction, balance)?;
+			Ok(Some(Self::delegation_weight(depth, votes)).into())
 		}
 
 		/// Undelegate vote.
 		///
+		/// The delegated balance remains locked for the lock period of the conviction with which
+		/// it was delegated.
+		///
 		/// # <weight>
-		/// - O(1).
+		/// - O(D + R) where D is the length of the delegation chain above the delegate and R is
+		///   the number of referenda its end has voted on, bounded by `MaxDelegationDepth` and
+		///   `MaxVotes`.
+		/// - Only the weight of the chain actually walked is charged.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
-		fn undelegate(origin) {
+		#[weight = DelegationWeight::<T>::new()]
+		fn undelegate(origin) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			ensure!(<Delegations<T>>::contains_key(&who), Error::<T>::NotDelegated);
-			let (_, conviction) = <Delegations<T>>::take(&who);
-			// Indefinite lock is reduced to the maximum voting lock that could be possible.
-			let now = <frame_system::Module<T>>::block_number();
-			let locked_until = now + T::EnactmentPeriod::get() * conviction.lock_periods().into();
-			Locks::<T>::insert(&who, locked_until);
-			T::Currency::set_lock(
-				DEMOCRACY_ID,
-				&who,
-				Bounded::max_value(),
-				WithdrawReason::Transfer.into(),
-			);
-			Self::deposit_event(RawEvent::Undelegated(who));
+			let (depth, votes) = Self::try_undelegate(who)?;
+			Ok(Some(Self::delegation_weight(depth, votes)).into())
 		}
 
 		/// Veto and blacklist the proposal hash. Must be from Root origin.
@@ -788,6 +807,9 @@ decl_module! {
 		/// This will only work after `VotingPeriod` blocks from the time that the preimage was
 		/// noted, if it's the same account doing it. If it's a different account, then it'll only
 		/// work an additional `EnactmentPeriod` later.
+		///
+		/// The preimage of a proposal is removed and its deposit returned automatically once its
+		/// referendum is enacted or fails, so this is only needed for preimages which go unused.
 		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
 		fn reap_preimage(origin, proposal_hash: T::Hash) {
 			let who = ensure_signed(origin)?;
@@ -807,15 +829,21 @@ decl_module! {
 			Self::deposit_event(RawEvent::PreimageReaped(proposal_hash, old, deposit, who));
 		}
 
+		/// Unlock tokens of `target` that are no longer needed for voting, delegating or a legacy
+		/// lock which has expired.
 		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
 		fn unlock(origin, target: T::AccountId) {
 			ensure_signed(origin)?;
 
-			let expiry = Locks::<T>::get(&target).ok_or(Error::<T>::NotLocked)?;
-			ensure!(expiry <= system::Module::<T>::block_number(), Error::<T>::NotExpired);
+			let now = system::Module::<T>::block_number();
+			if let Some(expiry) = Locks::<T>::get(&target) {
+				ensure!(expiry <= now, Error::<T>::NotExpired);
+				Locks::<T>::remove(&target);
+			} else {
+				ensure!(<VotingOf<T>>::contains_key(&target), Error::<T>::NotLocked);
+			}
 
-			T::Currency::remove_lock(DEMOCRACY_ID, &target);
-			Locks::<T>::remove(&target);
+			Self::update_lock(&target);
 			Self::deposit_event(RawEvent::Unlocked(target));
 		}
 	}
@@ -832,103 +860,36 @@ impl<T: Trait> Module<T> {
 
 	/// Return true if `ref_index` is an on-going referendum.
 	pub fn is_active_referendum(ref_index: ReferendumIndex) -> bool {
-		<ReferendumInfoOf<T>>::contains_key(ref_index)
+		Self::referendum_status(ref_index).is_ok()
 	}
 
 	/// Get all referenda currently active.
 	pub fn active_referenda()
-		-> Vec<(ReferendumIndex, ReferendumInfo<T::BlockNumber, T::Hash>)>
+		-> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)>
 	{
 		let next = Self::lowest_unbaked();
 		let last = Self::referendum_count();
 		(next..last).into_iter()
-			.filter_map(|i| Self::referendum_info(i).map(|info| (i, info)))
+			.filter_map(|i| Self::referendum_status(i).ok().map(|status| (i, status)))
 			.collect()
 	}
 
 	/// Get all referenda ready for tally at block `n`.
 	pub fn maturing_referenda_at(
 		n: T::BlockNumber
-	) -> Vec<(ReferendumIndex, ReferendumInfo<T::BlockNumber, T::Hash>)> {
-		let next = Self::lowest_unbaked();
-		let last = Self::referendum_count();
-		(next..last).into_iter()
-			.filter_map(|i| Self::referendum_info(i).map(|info| (i, info)))
-			.filter(|&(_, ref info)| info.end == n)
+	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)> {
+		Self::active_referenda()
+			.into_iter()
+			.filter(|&(_, ref status)| status.end == n)
 			.collect()
 	}
 
-	/// Get the voters for the current proposal.
-	pub fn tally(ref_index: ReferendumIndex) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
-		let (approve, against, capital):
-			(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) = Self::voters_for(ref_index)
-				.iter()
-				.map(|voter| (
-					T::Currency::total_balance(voter), Self::vote_of((ref_index, voter.clone()))
-				))
-				.map(|(balance, Vote { aye, conviction })| {
-					let (votes, turnout) = conviction.votes(balance);
-					if aye {
-						(votes, Zero::zero(), turnout)
-					} else {
-						(Zero::zero(), votes, turnout)
-					}
-				}).fold(
-					(Zero::zero(), Zero::zero(), Zero::zero()),
-					|(a, b, c), (d, e, f)| (a + d, b + e, c + f)
-				);
-		let (del_approve, del_against, del_capital) = Self::tally_delegation(ref_index);
-		(approve + del_approve, against + del_against, capital + del_capital)
-	}
-
-	/// Get the delegated voters for the current proposal.
-	/// I think this goes into a worker once https://github.com/paritytech/substrate/issues/1458 is
-	/// done.
-	fn tally_delegation(ref_index: ReferendumIndex) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
-		Self::voters_for(ref_index).iter().fold(
-			(Zero::zero(), Zero::zero(), Zero::zero()),
-			|(approve_acc, against_acc, turnout_acc), voter| {
-				let Vote { aye, conviction } = Self::vote_of((ref_index, voter.clone()));
-				let (votes, turnout) = Self::delegated_votes(
-					ref_index,
-					voter.clone(),
-					conviction,
-					MAX_RECURSION_LIMIT
-				);
-				if aye {
-					(approve_acc + votes, against_acc, turnout_acc + turnout)
-				} else {
-					(approve_acc, against_acc + votes, turnout_acc + turnout)
-				}
-			}
-		)
-	}
-
-	fn delegated_votes(
-		ref_index: ReferendumIndex,
-		to: T::AccountId,
-		parent_conviction: Conviction,
-		recursion_limit: u32,
-	) -> (BalanceOf<T>, BalanceOf<T>) {
-		if recursion_limit == 0 { return (Zero::zero(), Zero::zero()); }
-		<Delegations<T>>::enumerate()
-			.filter(|(delegator, (delegate, _))|
-				*delegate == to && !<VoteOf<T>>::contains_key(&(ref_index, delegator.clone()))
-			).fold(
-				(Zero::zero(), Zero::zero()),
-				|(votes_acc, turnout_acc), (delegator, (_delegate, max_conviction))| {
-					let conviction = Conviction::min(parent_conviction, max_conviction);
-					let balance = T::Currency::total_balance(&delegator);
-					let (votes, turnout) = conviction.votes(balance);
-					let (del_votes, del_turnout) = Self::delegated_votes(
-						ref_index,
-						delegator,
-						conviction,
-						recursion_limit - 1
-					);
-					(votes_acc + votes + del_votes, turnout_acc + turnout + del_turnout)
-				}
-			)
+	/// Get the current tally of an ongoing referendum; `None` if `ref_index` isn't an ongoing
+	/// referendum.
+	///
+	/// Tallies are kept up to date as votes and delegations change, so this is O(1).
+	pub fn tally(ref_index: ReferendumIndex) -> Option<Tally<BalanceOf<T>>> {
+		Self::referendum_status(ref_index).ok().map(|status| status.tally)
 	}
 
 	// Exposed mutables.
@@ -960,16 +921,298 @@ impl<T: Trait> Module<T> {
 
 	// private.
 
+	/// Ok if the given referendum is active, Err otherwise.
+	fn ensure_ongoing(r: ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>)
+		-> Result<ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>, DispatchError>
+	{
+		match r {
+			ReferendumInfo::Ongoing(s) => Ok(s),
+			_ => Err(Error::<T>::ReferendumInvalid.into()),
+		}
+	}
+
+	/// The status of the given referendum, if it is ongoing.
+	fn referendum_status(ref_index: ReferendumIndex)
+		-> Result<ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>, DispatchError>
+	{
+		let info = <ReferendumInfoOf<T>>::get(ref_index)
+			.ok_or(Error::<T>::ReferendumInvalid)?;
+		Self::ensure_ongoing(info)
+	}
+
 	/// Actually enact a vote, if legit.
-	fn do_vote(who: T::AccountId, ref_index: ReferendumIndex, vote: Vote) -> DispatchResult {
-		ensure!(Self::is_active_referendum(ref_index), Error::<T>::ReferendumInvalid);
-		if !<VoteOf<T>>::contains_key((ref_index, &who)) {
-			<VotersFor<T>>::append_or_insert(ref_index, &[&who][..]);
+	fn try_vote(
+		who: &T::AccountId,
+		ref_index: ReferendumIndex,
+		vote: AccountVote<BalanceOf<T>>,
+	) -> DispatchResult {
+		let mut status = Self::referendum_status(ref_index)?;
+		ensure!(vote.balance() <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+		let mut voting = <VotingOf<T>>::get(who);
+		match voting {
+			Voting::Direct { ref mut votes, delegations, .. } => {
+				match votes.binary_search_by_key(&ref_index, |i| i.0) {
+					Ok(i) => {
+						// Shouldn't be possible to fail, but we handle it gracefully.
+						status.tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
+						if let Some(approve) = votes[i].1.as_standard() {
+							status.tally.reduce(approve, delegations);
+						}
+						votes[i].1 = vote;
+					}
+					Err(i) => {
+						ensure!(
+							votes.len() < T::MaxVotes::get() as usize,
+							Error::<T>::MaxVotesReached,
+						);
+						votes.insert(i, (ref_index, vote));
+					}
+				}
+				// Shouldn't be possible to fail, but we handle it gracefully.
+				status.tally.add(vote).ok_or(Error::<T>::Overflow)?;
+				if let Some(approve) = vote.as_standard() {
+					status.tally.increase(approve, delegations);
+				}
+			}
+			Voting::Delegating { .. } => Err(Error::<T>::AlreadyDelegating)?,
+		}
+		// Extend the lock to `balance` (rather than setting it) since we don't know what other
+		// votes are in place.
+		T::Currency::extend_lock(
+			DEMOCRACY_ID,
+			who,
+			vote.balance(),
+			WithdrawReason::Transfer.into()
+		);
+		<VotingOf<T>>::insert(who, voting);
+		<ReferendumInfoOf<T>>::insert(ref_index, ReferendumInfo::Ongoing(status));
+		Ok(())
+	}
+
+	/// Remove the account's vote for the given referendum if possible. This is possible when:
+	/// - The referendum has not finished.
+	/// - The referendum has finished and the voter lost their direction.
+	/// - The referendum has finished and the voter's lock period is up.
+	/// - The referendum was cancelled.
+	///
+	/// This will generally be combined with a call to `unlock`.
+	fn try_remove_vote(
+		who: &T::AccountId,
+		ref_index: ReferendumIndex,
+		scope: UnvoteScope,
+	) -> DispatchResult {
+		let info = <ReferendumInfoOf<T>>::get(ref_index);
+		let mut voting = <VotingOf<T>>::get(who);
+		if let Voting::Direct { ref mut votes, delegations, ref mut prior } = voting {
+			let i = votes.binary_search_by_key(&ref_index, |i| i.0)
+				.map_err(|_| Error::<T>::NotVoter)?;
+			match info {
+				Some(ReferendumInfo::Ongoing(mut status)) => {
+					ensure!(scope == UnvoteScope::Any, Error::<T>::NoPermission);
+					// Shouldn't be possible to fail, but we handle it gracefully.
+					status.tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
+					if let Some(approve) = votes[i].1.as_standard() {
+						status.tally.reduce(approve, delegations);
+					}
+					<ReferendumInfoOf<T>>::insert(ref_index, ReferendumInfo::Ongoing(status));
+				}
+				Some(ReferendumInfo::Finished { end, approved }) =>
+					if let Some((lock_periods, balance)) = votes[i].1.locked_if(approved) {
+						let unlock_at = end + T::EnactmentPeriod::get() * lock_periods.into();
+						let now = system::Module::<T>::block_number();
+						if now < unlock_at {
+							ensure!(scope == UnvoteScope::Any, Error::<T>::NoPermission);
+							prior.accumulate(unlock_at, balance)
+						}
+					},
+				None => {} // Referendum was cancelled.
+			}
+			votes.remove(i);
 		}
-		<VoteOf<T>>::insert((ref_index, &who), vote);
+		<VotingOf<T>>::insert(who, voting);
 		Ok(())
 	}
 
+	/// The weight of walking a delegation chain of `depth` accounts whose end has voted on `votes`
+	/// referenda.
+	fn delegation_weight(depth: u32, votes: u32) -> Weight {
+		(100_000 as Weight)
+			.saturating_add((50_000 as Weight).saturating_mul(depth.into()))
+			.saturating_add((20_000 as Weight).saturating_mul(votes.into()))
+	}
+
+	/// Add `amount` of delegated votes and capital to `who`'s record. If `who` is voting directly,
+	/// the tally of every ongoing referendum on which it has a standard vote is increased; if it
+	/// is delegating, the amount is passed on along its delegation chain.
+	///
+	/// Returns the number of accounts in the chain and the number of votes of its end.
+	fn increase_upstream_delegation(who: &T::AccountId, amount: Delegations<BalanceOf<T>>) -> (u32, u32) {
+		let (mut depth, mut end_votes) = (0, 0);
+		let mut next = Some(who.clone());
+		while let Some(who) = next.take() {
+			depth += 1;
+			next = <VotingOf<T>>::mutate(&who, |voting| match voting {
+				Voting::Delegating { delegations, target, .. } => {
+					*delegations = delegations.saturating_add(amount);
+					Some(target.clone())
+				}
+				Voting::Direct { votes, delegations, .. } => {
+					*delegations = delegations.saturating_add(amount);
+					end_votes = votes.len() as u32;
+					for &(ref_index, account_vote) in votes.iter() {
+						if let AccountVote::Standard { vote, .. } = account_vote {
+							<ReferendumInfoOf<T>>::mutate(ref_index, |maybe_info|
+								if let Some(ReferendumInfo::Ongoing(ref mut status)) = maybe_info {
+									status.tally.increase(vote.aye, amount);
+								}
+							);
+						}
+					}
+					None
+				}
+			});
+		}
+		(depth, end_votes)
+	}
+
+	/// Remove `amount` of delegated votes and capital from `who`'s record, undoing
+	/// `increase_upstream_delegation`.
+	///
+	/// Returns the number of accounts in the chain and the number of votes of its end.
+	fn reduce_upstream_delegation(who: &T::AccountId, amount: Delegations<BalanceOf<T>>) -> (u32, u32) {
+		let (mut depth, mut end_votes) = (0, 0);
+		let mut next = Some(who.clone());
+		while let Some(who) = next.take() {
+			depth += 1;
+			next = <VotingOf<T>>::mutate(&who, |voting| match voting {
+				Voting::Delegating { delegations, target, .. } => {
+					*delegations = delegations.saturating_sub(amount);
+					Some(target.clone())
+				}
+				Voting::Direct { votes, delegations, .. } => {
+					*delegations = delegations.saturating_sub(amount);
+					end_votes = votes.len() as u32;
+					for &(ref_index, account_vote) in votes.iter() {
+						if let AccountVote::Standard { vote, .. } = account_vote {
+							<ReferendumInfoOf<T>>::mutate(ref_index, |maybe_info|
+								if let Some(ReferendumInfo::Ongoing(ref mut status)) = maybe_info {
+									status.tally.reduce(vote.aye, amount);
+								}
+							);
+						}
+					}
+					None
+				}
+			});
+		}
+		(depth, end_votes)
+	}
+
+	/// Check that `who` may delegate to `target`: it must not be part of a cycle then, and the
+	/// chain of delegations starting at `who` must not exceed `MaxDelegationDepth` accounts.
+	fn ensure_can_delegate_to(who: &T::AccountId, target: &T::AccountId) -> DispatchResult {
+		let mut next = target.clone();
+		let mut depth = 1;
+		loop {
+			ensure!(&next != who, Error::<T>::Nonsense);
+			depth += 1;
+			ensure!(depth <= T::MaxDelegationDepth::get(), Error::<T>::DelegationTooDeep);
+			match <VotingOf<T>>::get(&next) {
+				Voting::Delegating { target, .. } => next = target,
+				Voting::Direct { .. } => return Ok(()),
+			}
+		}
+	}
+
+	/// Attempt to delegate `balance` times `conviction` of voting power from `who` to `target`.
+	///
+	/// Only accounts which have not received any delegations may delegate, so that chains only
+	/// grow at their start and their length is checked against `MaxDelegationDepth` here.
+	///
+	/// Returns the number of accounts in the chain above `who` and the number of votes of its end.
+	fn try_delegate(
+		who: T::AccountId,
+		target: T::AccountId,
+		conviction: Conviction,
+		balance: BalanceOf<T>,
+	) -> Result<(u32, u32), DispatchError> {
+		Self::ensure_can_delegate_to(&who, &target)?;
+		ensure!(!balance.is_zero(), Error::<T>::InsufficientFunds);
+		ensure!(balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
+		let prior = match <VotingOf<T>>::get(&who) {
+			Voting::Delegating { .. } => Err(Error::<T>::AlreadyDelegating)?,
+			Voting::Direct { votes, delegations, prior } => {
+				// here we just ensure that we're currently idling with no votes recorded.
+				ensure!(votes.is_empty(), Error::<T>::VotesExist);
+				// delegators always delegate some capital, so none have delegated to us.
+				ensure!(delegations.capital.is_zero(), Error::<T>::DelegationsReceived);
+				prior
+			}
+		};
+		<VotingOf<T>>::insert(&who, Voting::Delegating {
+			balance,
+			target: target.clone(),
+			conviction,
+			delegations: Default::default(),
+			prior,
+		});
+		let walked = Self::increase_upstream_delegation(&target, conviction.votes(balance));
+		// Extend the lock to `balance` (rather than setting it) since we don't know what other
+		// votes are in place.
+		T::Currency::extend_lock(
+			DEMOCRACY_ID,
+			&who,
+			balance,
+			WithdrawReason::Transfer.into()
+		);
+		Self::deposit_event(RawEvent::Delegated(who, target));
+		Ok(walked)
+	}
+
+	/// Attempt to end the current delegation.
+	///
+	/// Returns the number of accounts in the chain above `who` and the number of votes of its end.
+	fn try_undelegate(who: T::AccountId) -> Result<(u32, u32), DispatchError> {
+		let (voting, walked) = match <VotingOf<T>>::get(&who) {
+			Voting::Delegating { balance, target, conviction, delegations, mut prior } => {
+				// remove our votes, and those delegated to us, from our current target.
+				let walked = Self::reduce_upstream_delegation(
+					&target,
+					conviction.votes(balance).saturating_add(delegations),
+				);
+				let now = system::Module::<T>::block_number();
+				let lock_periods = conviction.lock_periods().into();
+				prior.accumulate(now + T::EnactmentPeriod::get() * lock_periods, balance);
+				let mut voting = Voting::default();
+				voting.set_common(delegations, prior);
+				(voting, walked)
+			}
+			Voting::Direct { .. } => Err(Error::<T>::NotDelegated)?,
+		};
+		<VotingOf<T>>::insert(&who, voting);
+		Self::deposit_event(RawEvent::Undelegated(who));
+		Ok(walked)
+	}
+
+	/// Rejig the lock on an account. It will never get more stringent (since that would indicate
+	/// a security hole) but may be reduced from what they are currently.
+	fn update_lock(who: &T::AccountId) {
+		// A legacy lock which has not yet been unlocked still holds the whole balance.
+		if Locks::<T>::contains_key(who) {
+			return
+		}
+		let now = system::Module::<T>::block_number();
+		let lock_needed = <VotingOf<T>>::mutate(who, |voting| {
+			voting.rejig(now);
+			voting.locked_balance()
+		});
+		if lock_needed.is_zero() {
+			T::Currency::remove_lock(DEMOCRACY_ID, who);
+		} else {
+			T::Currency::set_lock(DEMOCRACY_ID, who, lock_needed, WithdrawReason::Transfer.into());
+		}
+	}
+
 	/// Start a referendum
 	fn inject_referendum(
 		end: T::BlockNumber,
@@ -979,7 +1222,7 @@ impl<T: Trait> Module<T> {
 	) -> ReferendumIndex {
 		let ref_index = Self::referendum_count();
 		ReferendumCount::put(ref_index + 1);
-		let item = ReferendumInfo { end, proposal_hash, threshold, delay };
+		let item = ReferendumInfo::new(end, proposal_hash, threshold, delay);
 		<ReferendumInfoOf<T>>::insert(ref_index, item);
 		Self::deposit_event(RawEvent::Started(ref_index, threshold));
 		ref_index
@@ -988,8 +1231,12 @@ impl<T: Trait> Module<T> {
 	/// Remove all info on a referendum.
 	fn clear_referendum(ref_index: ReferendumIndex) {
 		<ReferendumInfoOf<T>>::remove(ref_index);
+		Self::note_referendum_closed(ref_index);
+	}
 
-
+	/// Move `LowestUnbaked` past `ref_index` (and any other referenda which are no longer
+	/// ongoing) if it was pointing at it.
+	fn note_referendum_closed(ref_index: ReferendumIndex) {
 		LowestUnbaked::mutate(|i| if *i == ref_index {
 			*i += 1;
 			let end = ReferendumCount::get();
@@ -997,10 +1244,6 @@ impl<T: Trait> Module<T> {
 				*i += 1;
 			}
 		});
-		<VotersFor<T>>::remove(ref_index);
-		for v in Self::voters_for(ref_index) {
-			<VoteOf<T>>::remove((ref_index, v));
-		}
 	}
 
 	/// Enact a proposal from a referendum.
@@ -1025,6 +1268,26 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// Remove the preimage of a proposal which failed its referendum and return the deposit to
+	/// whoever noted it, unless the proposal is still pending elsewhere.
+	fn refund_preimage(proposal_hash: T::Hash) {
+		if Self::is_pending(&proposal_hash) {
+			return
+		}
+		if let Some((_, who, amount, _)) = <Preimages<T>>::take(&proposal_hash) {
+			let _ = T::Currency::unreserve(&who, amount);
+			Self::deposit_event(RawEvent::PreimageRefunded(proposal_hash, who, amount));
+		}
+	}
+
+	/// Return true if `proposal_hash` is proposed, up for referendum or queued for enactment.
+	fn is_pending(proposal_hash: &T::Hash) -> bool {
+		Self::public_props().iter().any(|p| &p.1 == proposal_hash)
+			|| <NextExternal<T>>::get().map_or(false, |(h, _)| &h == proposal_hash)
+			|| Self::active_referenda().iter().any(|(_, s)| &s.proposal_hash == proposal_hash)
+			|| Self::dispatch_queue().iter().any(|item| &item.1 == proposal_hash)
+	}
+
 	/// Table the next waiting proposal for a vote.
 	fn launch_next(now: T::BlockNumber) -> DispatchResult {
 		if LastTabledWasExternal::take() {
@@ -1085,43 +1348,27 @@ impl<T: Trait> Module<T> {
 	fn bake_referendum(
 		now: T::BlockNumber,
 		index: ReferendumIndex,
-		info: ReferendumInfo<T::BlockNumber, T::Hash>
+		status: ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>,
 	) -> DispatchResult {
-		let (approve, against, capital) = Self::tally(index);
 		let total_issuance = T::Currency::total_issuance();
-		let approved = info.threshold.approved(approve, against, capital, total_issuance);
-
-		// Logic defined in https://www.slideshare.net/gavofyork/governance-in-polkadot-poc3
-		// Essentially, we extend the lock-period of the coins behind the winning votes to be the
-		// vote strength times the public delay period from now.
-		for (a, Vote { conviction, .. }) in Self::voters_for(index).into_iter()
-			.map(|a| (a.clone(), Self::vote_of((index, a))))
-			// ^^^ defensive only: all items come from `voters`; for an item to be in `voters`
-			// there must be a vote registered; qed
-			.filter(|&(_, vote)| vote.aye == approved)  // Just the winning coins
-		{
-			// now plus: the base lock period multiplied by the number of periods this voter
-			// offered to lock should they win...
-			let locked_until = now + T::EnactmentPeriod::get() * conviction.lock_periods().into();
-			Locks::<T>::insert(&a, locked_until);
-			// ...extend their bondage until at least then.
-			T::Currency::extend_lock(
-				DEMOCRACY_ID,
-				&a,
-				Bounded::max_value(),
-				WithdrawReason::Transfer.into()
-			);
-
-		}
-
-		Self::clear_referendum(index);
+		let approved = status.threshold.approved(
+			status.tally.ayes,
+			status.tally.nays,
+			status.tally.turnout,
+			total_issuance,
+		);
+
+		// Votes on the winning side remain locked according to their conviction until the voter
+		// removes them; see `try_remove_vote`.
+		<ReferendumInfoOf<T>>::insert(index, ReferendumInfo::Finished { approved, end: now });
+		Self::note_referendum_closed(index);
 
 		if approved {
 			Self::deposit_event(RawEvent::Passed(index));
-			if info.delay.is_zero() {
-				let _ = Self::enact_proposal(info.proposal_hash, index);
+			if status.delay.is_zero() {
+				let _ = Self::enact_proposal(status.proposal_hash, index);
 			} else {
-				let item = (now + info.delay,info.proposal_hash, index);
+				let item = (now + status.delay, status.proposal_hash, index);
 				<DispatchQueue<T>>::mutate(|queue| {
 					let pos = queue.binary_search_by_key(&item.0, |x| x.0).unwrap_or_else(|e| e);
 					queue.insert(pos, item);
@@ -1129,6 +1376,7 @@ impl<T: Trait> Module<T> {
 			}
 		} else {
 			Self::deposit_event(RawEvent::NotPassed(index));
+			Self::refund_preimage(status.proposal_hash);
 		}
 
 		Ok(())
@@ -1144,8 +1392,8 @@ impl<T: Trait> Module<T> {
 		}
 
 		// tally up votes for any expiring referenda.
-		for (index, info) in Self::maturing_referenda_at(now).into_iter() {
-			Self::bake_referendum(now, index, info)?;
+		for (index, status) in Self::maturing_referenda_at(now).into_iter() {
+			Self::bake_referendum(now, index, status)?;
 		}
 
 		let queue = <DispatchQueue<T>>::get();
@@ -1182,14 +1430,35 @@ mod tests {
 		testing::Header, Perbill,
 	};
 	use pallet_balances::{BalanceLock, Error as BalancesError};
-	use frame_system::EnsureSignedBy;
+	use frame_system::{EnsureSignedBy, EnsureRoot};
 	use sp_storage::Storage;
+	use codec::Encode;
 
 	const AYE: Vote = Vote{ aye: true, conviction: Conviction::None };
 	const NAY: Vote = Vote{ aye: false, conviction: Conviction::None };
 	const BIG_AYE: Vote = Vote{ aye: true, conviction: Conviction::Locked1x };
 	const BIG_NAY: Vote = Vote{ aye: false, conviction: Conviction::Locked1x };
 
+	fn aye(who: u64) -> AccountVote<u64> {
+		AccountVote::Standard { vote: AYE, balance: Balances::free_balance(&who) }
+	}
+
+	fn nay(who: u64) -> AccountVote<u64> {
+		AccountVote::Standard { vote: NAY, balance: Balances::free_balance(&who) }
+	}
+
+	fn big_aye(who: u64) -> AccountVote<u64> {
+		AccountVote::Standard { vote: BIG_AYE, balance: Balances::free_balance(&who) }
+	}
+
+	fn big_nay(who: u64) -> AccountVote<u64> {
+		AccountVote::Standard { vote: BIG_NAY, balance: Balances::free_balance(&who) }
+	}
+
+	fn tally(r: ReferendumIndex) -> Tally<u64> {
+		Democracy::tally(r).unwrap()
+	}
+
 	impl_outer_origin! {
 		pub enum Origin for Test  where system = frame_system {}
 	}
@@ -1249,6 +1518,8 @@ mod tests {
 		pub const MinimumDeposit: u64 = 1;
 		pub const EnactmentPeriod: u64 = 2;
 		pub const CooloffPeriod: u64 = 2;
+		pub const MaxVotes: u32 = 3;
+		pub const MaxDelegationDepth: u32 = 3;
 	}
 	ord_parameter_types! {
 		pub const One: u64 = 1;
@@ -1288,9 +1559,13 @@ mod tests {
 		type CooloffPeriod = CooloffPeriod;
 		type PreimageByteDeposit = PreimageByteDeposit;
 		type Slash = ();
+		type BlacklistOrigin = EnsureRoot<u64>;
+		type MaxVotes = MaxVotes;
+		type MaxDelegationDepth = MaxDelegationDepth;
 	}
 
 	fn new_test_ext() -> sp_io::TestExternalities {
+		PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 0);
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 		pallet_balances::GenesisConfig::<Test>{
 			balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
@@ -1417,7 +1692,7 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 			next_block();
 			next_block();
@@ -1444,7 +1719,7 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 			assert_eq!(Balances::reserved_balance(6), 12);
 
@@ -1457,6 +1732,49 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn preimage_deposit_should_be_returned_when_referendum_fails() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_hash_and_note(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, nay(1)));
+			assert_eq!(Balances::reserved_balance(6), 12);
+
+			next_block();
+			next_block();
+
+			assert_eq!(Balances::free_balance(42), 0);
+			assert!(!<Preimages<Test>>::contains_key(set_balance_proposal_hash(2)));
+			assert_eq!(Balances::reserved_balance(6), 0);
+			assert_eq!(Balances::free_balance(6), 60);
+		});
+	}
+
+	#[test]
+	fn preimage_of_failed_referendum_should_be_kept_while_pending() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			let h = set_balance_proposal_hash_and_note(2);
+			let r = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+			// The same proposal is also up for another referendum.
+			Democracy::inject_referendum(4, h, VoteThreshold::SuperMajorityApprove, 0);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, nay(1)));
+
+			next_block();
+			next_block();
+
+			assert_eq!(Balances::reserved_balance(6), 12);
+			assert!(<Preimages<Test>>::contains_key(h));
+		});
+	}
+
 	#[test]
 	fn preimage_deposit_should_be_reapable_earlier_by_owner() {
 		new_test_ext().execute_with(|| {
@@ -1520,7 +1838,7 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				1
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 			assert_noop!(
 				Democracy::note_imminent_preimage(Origin::signed(7), set_balance_proposal(2)),
@@ -1544,7 +1862,7 @@ mod tests {
 			System::set_block_number(1);
 			let h = set_balance_proposal_hash_and_note(2);
 			let r = Democracy::inject_referendum(3, h, VoteThreshold::SuperMajorityApprove, 1);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 			next_block();
 			next_block();
 			// now imminent.
@@ -1567,12 +1885,13 @@ mod tests {
 			// both waiting: external goes first.
 			assert_eq!(
 				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 4,
 					proposal_hash: set_balance_proposal_hash_and_note(1),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 			// replenish external
 			assert_ok!(Democracy::external_propose(
@@ -1585,12 +1904,13 @@ mod tests {
 			// both waiting: public goes next.
 			assert_eq!(
 				Democracy::referendum_info(1),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 6,
 					proposal_hash: set_balance_proposal_hash_and_note(2),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 			// don't replenish public
 
@@ -1599,12 +1919,13 @@ mod tests {
 			// it's external "turn" again, though since public is empty that doesn't really matter
 			assert_eq!(
 				Democracy::referendum_info(2),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 8,
 					proposal_hash: set_balance_proposal_hash_and_note(3),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 			// replenish external
 			assert_ok!(Democracy::external_propose(
@@ -1617,12 +1938,13 @@ mod tests {
 			// external goes again because there's no public waiting.
 			assert_eq!(
 				Democracy::referendum_info(3),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 10,
 					proposal_hash: set_balance_proposal_hash_and_note(5),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 			// replenish both
 			assert_ok!(Democracy::external_propose(
@@ -1636,12 +1958,13 @@ mod tests {
 			// public goes now since external went last time.
 			assert_eq!(
 				Democracy::referendum_info(4),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 12,
 					proposal_hash: set_balance_proposal_hash_and_note(4),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 			// replenish public again
 			assert_ok!(propose_set_balance_and_note(6, 6, 2));
@@ -1654,12 +1977,13 @@ mod tests {
 			// public goes again now since there's no external waiting.
 			assert_eq!(
 				Democracy::referendum_info(5),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 14,
 					proposal_hash: set_balance_proposal_hash_and_note(6),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 		});
 	}
@@ -1754,6 +2078,71 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn blacklisting_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let hash = set_balance_proposal_hash(2);
+
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(propose_set_balance_and_note(1, 4, 4));
+			assert_ok!(Democracy::second(Origin::signed(2), 0));
+
+			assert_noop!(Democracy::blacklist(Origin::signed(1), hash.clone(), None), BadOrigin);
+			assert_ok!(Democracy::blacklist(Origin::ROOT, hash, None));
+
+			// The matching public proposal is gone and its deposits slashed.
+			assert_eq!(Democracy::public_props().len(), 1);
+			assert_eq!(Democracy::public_props()[0].1, set_balance_proposal_hash(4));
+			assert_eq!(Democracy::deposit_of(0), None);
+			assert_eq!(Balances::reserved_balance(1), 4);
+			assert_eq!(Balances::free_balance(1), 4);
+			assert_eq!(Balances::free_balance(2), 18);
+			assert_noop!(propose_set_balance(1, 2, 2), Error::<Test>::ProposalBlacklisted);
+
+			// The ban is permanent.
+			fast_forward_to(100);
+			assert_noop!(propose_set_balance(1, 2, 2), Error::<Test>::ProposalBlacklisted);
+			assert_noop!(
+				Democracy::external_propose(Origin::signed(2), hash),
+				Error::<Test>::ProposalBlacklisted,
+			);
+		});
+	}
+
+	#[test]
+	fn blacklisting_should_cancel_external_and_referendum() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let hash = set_balance_proposal_hash_and_note(2);
+			let other = set_balance_proposal_hash_and_note(3);
+			assert_ok!(Democracy::external_propose(Origin::signed(2), hash));
+			let r = Democracy::inject_referendum(2, hash, VoteThreshold::SuperMajorityApprove, 0);
+			let o = Democracy::inject_referendum(2, other, VoteThreshold::SuperMajorityApprove, 0);
+
+			// A referendum index of another proposal is left alone.
+			assert_ok!(Democracy::blacklist(Origin::ROOT, hash, Some(o)));
+			assert!(!<NextExternal<Test>>::exists());
+			assert!(Democracy::is_active_referendum(r));
+			assert!(Democracy::is_active_referendum(o));
+
+			assert_ok!(Democracy::blacklist(Origin::ROOT, hash, Some(r)));
+			assert_eq!(Democracy::referendum_info(r), None);
+			assert!(Democracy::is_active_referendum(o));
+
+			// None of the external proposal calls can bring it back.
+			assert_noop!(
+				Democracy::external_propose_majority(Origin::signed(3), hash),
+				Error::<Test>::ProposalBlacklisted,
+			);
+			assert_noop!(
+				Democracy::external_propose_default(Origin::signed(1), hash),
+				Error::<Test>::ProposalBlacklisted,
+			);
+			assert_eq!(Democracy::blacklist_of(hash).map(|b| b.0), Some(u64::max_value()));
+		});
+	}
+
 	#[test]
 	fn external_referendum_works() {
 		new_test_ext().execute_with(|| {
@@ -1776,12 +2165,13 @@ mod tests {
 			fast_forward_to(2);
 			assert_eq!(
 				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 4,
 					proposal_hash: set_balance_proposal_hash(2),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 		});
 	}
@@ -1804,12 +2194,13 @@ mod tests {
 			fast_forward_to(2);
 			assert_eq!(
 				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 4,
 					proposal_hash: set_balance_proposal_hash(2),
 					threshold: VoteThreshold::SimpleMajority,
 					delay: 2,
-				})
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 		});
 	}
@@ -1832,12 +2223,13 @@ mod tests {
 			fast_forward_to(2);
 			assert_eq!(
 				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 4,
 					proposal_hash: set_balance_proposal_hash(2),
 					threshold: VoteThreshold::SuperMajorityAgainst,
 					delay: 2,
-				})
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 		});
 	}
@@ -1856,12 +2248,13 @@ mod tests {
 			assert_ok!(Democracy::fast_track(Origin::signed(5), h, 0, 0));
 			assert_eq!(
 				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 1,
 					proposal_hash: set_balance_proposal_hash_and_note(2),
 					threshold: VoteThreshold::SimpleMajority,
 					delay: 0,
-				})
+					tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				}))
 			);
 		});
 	}
@@ -1906,31 +2299,32 @@ mod tests {
 			fast_forward_to(2);
 
 			let r = 0;
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 			assert_eq!(Democracy::referendum_count(), 1);
 			assert_eq!(
 				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
+				Some(ReferendumInfo::Ongoing(ReferendumStatus {
 					end: 4,
 					proposal_hash: set_balance_proposal_hash_and_note(2),
 					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+					delay: 2,
+					tally: Tally { ayes: 1, nays: 0, turnout: 10 },
+				}))
 			);
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
 
 			fast_forward_to(3);
 
 			// referendum still running
-			assert!(Democracy::referendum_info(0).is_some());
+			assert!(Democracy::is_active_referendum(0));
 
 			// referendum runs during 2 and 3, ends @ start of 4.
 			fast_forward_to(4);
 
-			assert!(Democracy::referendum_info(0).is_none());
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo::Finished { approved: true, end: 4 })
+			);
 			assert_eq!(Democracy::dispatch_queue(), vec![
 				(6, set_balance_proposal_hash_and_note(2), 0)
 			]);
@@ -1951,7 +2345,7 @@ mod tests {
 			// start of 2 => next referendum scheduled.
 			fast_forward_to(2);
 
-			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, aye(1)));
 
 			fast_forward_to(4);
 
@@ -2004,11 +2398,9 @@ mod tests {
 			fast_forward_to(2);
 			let r = 0;
 			assert_ok!(Democracy::set_proxy(Origin::signed(1), 10));
-			assert_ok!(Democracy::proxy_vote(Origin::signed(10), r, AYE));
+			assert_ok!(Democracy::proxy_vote(Origin::signed(10), r, aye(1)));
 
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
+			assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			fast_forward_to(6);
 			assert_eq!(Balances::free_balance(42), 2);
@@ -2025,14 +2417,16 @@ mod tests {
 			fast_forward_to(2);
 
 			// Delegate vote.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20));
 
 			let r = 0;
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 			// Delegated vote is counted.
-			assert_eq!(Democracy::tally(r), (3, 0, 3));
+			assert_eq!(tally(r), Tally { ayes: 121, nays: 0, turnout: 30 });
+
+			// Delegating while the delegate has already voted counts too.
+			assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::Locked1x, 30));
+			assert_eq!(tally(r), Tally { ayes: 151, nays: 0, turnout: 60 });
 
 			fast_forward_to(6);
 
@@ -2041,34 +2435,61 @@ mod tests {
 	}
 
 	#[test]
-	fn single_proposal_should_work_with_cyclic_delegation() {
+	fn delegation_chains_should_accumulate() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
 
 			assert_ok!(propose_set_balance_and_note(1, 2, 1));
 
 			fast_forward_to(2);
-
-			// Check behavior with cycle.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
-			assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::max_value()));
-			assert_ok!(Democracy::delegate(Origin::signed(1), 3, Conviction::max_value()));
 			let r = 0;
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
-			assert_eq!(Democracy::voters_for(r), vec![1]);
 
-			// Delegated vote is counted.
-			assert_eq!(Democracy::tally(r), (6, 0, 6));
+			// 3 delegates to 2, which delegates to 1.
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20));
+			assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::Locked1x, 30));
+			assert_eq!(Democracy::voting_of(2).delegations(), Delegations { votes: 30, capital: 30 });
+			assert_eq!(Democracy::voting_of(1).delegations(), Delegations { votes: 150, capital: 50 });
+
+			// Each delegator's balance counts with its own conviction.
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+			assert_eq!(tally(r), Tally { ayes: 151, nays: 0, turnout: 60 });
+
+			// 2 leaves the chain, taking 3's delegation with it...
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
+
+			// ...which is counted with 2's own vote.
+			assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
+			assert_eq!(tally(r), Tally { ayes: 1, nays: 32, turnout: 60 });
 
 			fast_forward_to(6);
 
-			assert_eq!(Balances::free_balance(42), 2);
+			assert_eq!(Balances::free_balance(42), 0);
+		});
+	}
+
+	#[test]
+	fn cyclic_delegation_should_fail() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Democracy::delegate(Origin::signed(1), 1, Conviction::max_value(), 10),
+				Error::<Test>::Nonsense
+			);
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20));
+			assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::max_value(), 30));
+			assert_noop!(
+				Democracy::delegate(Origin::signed(1), 3, Conviction::max_value(), 10),
+				Error::<Test>::Nonsense
+			);
+			assert_noop!(
+				Democracy::delegate(Origin::signed(3), 1, Conviction::max_value(), 30),
+				Error::<Test>::AlreadyDelegating
+			);
 		});
 	}
 
 	#[test]
-	/// If transactor already voted, delegated vote is overwriten.
-	fn single_proposal_should_work_with_vote_and_delegation() {
+	fn delegating_with_votes_should_fail() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
 
@@ -2077,15 +2498,19 @@ mod tests {
 			fast_forward_to(2);
 
 			let r = 0;
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
-			// Vote.
-			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
-			// Delegate vote.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
-			assert_eq!(Democracy::voters_for(r), vec![1, 2]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			// Delegated vote is not counted.
-			assert_eq!(Democracy::tally(r), (3, 0, 3));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+			// Can't delegate while voting.
+			assert_noop!(
+				Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20),
+				Error::<Test>::VotesExist
+			);
+			assert_eq!(tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
+
+			// Removing the vote allows delegating it instead.
+			assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20));
+			assert_eq!(tally(r), Tally { ayes: 121, nays: 0, turnout: 30 });
 
 			fast_forward_to(6);
 
@@ -2101,19 +2526,18 @@ mod tests {
 			assert_ok!(propose_set_balance_and_note(1, 2, 1));
 
 			// Delegate and undelegate vote.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20));
 			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_noop!(Democracy::undelegate(Origin::signed(2)), Error::<Test>::NotDelegated);
 
 			fast_forward_to(2);
 			let r = 0;
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 			assert_eq!(Democracy::referendum_count(), 1);
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
 
 			// Delegated vote is not counted.
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
+			assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			fast_forward_to(6);
 
@@ -2122,8 +2546,7 @@ mod tests {
 	}
 
 	#[test]
-	/// If transactor voted, delegated vote is overwriten.
-	fn single_proposal_should_work_with_delegation_and_vote() {
+	fn voting_while_delegating_should_fail() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
 
@@ -2132,20 +2555,22 @@ mod tests {
 			fast_forward_to(2);
 			let r = 0;
 
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 			// Delegate vote.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
-
-			// Vote.
-			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value(), 20));
 
-			assert_eq!(Democracy::referendum_count(), 1);
-			assert_eq!(Democracy::voters_for(r), vec![1, 2]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
+			// Can't vote directly while delegating.
+			assert_noop!(
+				Democracy::vote(Origin::signed(2), r, aye(2)),
+				Error::<Test>::AlreadyDelegating
+			);
+			assert_eq!(tally(r), Tally { ayes: 121, nays: 0, turnout: 30 });
 
-			// Delegated vote is not counted.
-			assert_eq!(Democracy::tally(r), (3, 0, 3));
+			// After undelegating, the vote counts directly.
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+			assert_eq!(tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
 
 			fast_forward_to(6);
 
@@ -2153,6 +2578,69 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn split_vote_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_hash_and_note(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			let v = AccountVote::Split { aye: 40, nay: 20 };
+			assert_noop!(Democracy::vote(Origin::signed(5), r, v), Error::<Test>::InsufficientFunds);
+			let v = AccountVote::Split { aye: 30, nay: 20 };
+			assert_ok!(Democracy::vote(Origin::signed(5), r, v));
+
+			// Split votes carry no conviction.
+			assert_eq!(tally(r), Tally { ayes: 3, nays: 2, turnout: 50 });
+
+			// Delegations only count for standard votes.
+			assert_ok!(Democracy::delegate(Origin::signed(1), 5, Conviction::Locked1x, 10));
+			assert_eq!(tally(r), Tally { ayes: 3, nays: 2, turnout: 50 });
+			assert_eq!(Balances::locks(5)[0].amount, 50);
+		});
+	}
+
+	#[test]
+	fn remove_vote_should_update_tally() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_hash_and_note(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
+			assert_eq!(tally(r), Tally { ayes: 1, nays: 2, turnout: 30 });
+
+			assert_noop!(Democracy::remove_vote(Origin::signed(3), r), Error::<Test>::NotVoter);
+			// Others can't remove a vote on an ongoing referendum.
+			assert_noop!(
+				Democracy::remove_other_vote(Origin::signed(3), 1, r),
+				Error::<Test>::NoPermission
+			);
+
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_eq!(tally(r), Tally { ayes: 0, nays: 2, turnout: 20 });
+
+			// The vote can be cast again, and voting again replaces it.
+			assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+			assert_eq!(tally(r), Tally { ayes: 10, nays: 2, turnout: 30 });
+			assert_ok!(Democracy::vote(Origin::signed(1), r, big_nay(1)));
+			assert_eq!(tally(r), Tally { ayes: 0, nays: 12, turnout: 30 });
+
+			// Removing a vote on a cancelled referendum just forgets it.
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r.into()));
+			assert_ok!(Democracy::remove_other_vote(Origin::signed(3), 1, r));
+			assert_ok!(Democracy::unlock(Origin::signed(3), 1));
+			assert_eq!(Balances::locks(1), vec![]);
+		});
+	}
+
 	#[test]
 	fn deposit_for_proposals_should_be_taken() {
 		new_test_ext().execute_with(|| {
@@ -2217,11 +2705,11 @@ mod tests {
 			assert_ok!(propose_set_balance_and_note(1, 4, 4));
 			assert_ok!(propose_set_balance_and_note(1, 3, 3));
 			fast_forward_to(2);
-			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, aye(1)));
 			fast_forward_to(4);
-			assert_ok!(Democracy::vote(Origin::signed(1), 1, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), 1, aye(1)));
 			fast_forward_to(6);
-			assert_ok!(Democracy::vote(Origin::signed(1), 2, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), 2, aye(1)));
 		});
 	}
 
@@ -2242,18 +2730,14 @@ mod tests {
 				0
 			);
 
-			assert_ok!(Democracy::vote(Origin::signed(1), r2, AYE));
-			assert_eq!(Democracy::voters_for(r2), vec![1]);
-			assert_eq!(Democracy::vote_of((r2, 1)), AYE);
-			assert_eq!(Democracy::tally(r2), (1, 0, 1));
+			assert_ok!(Democracy::vote(Origin::signed(1), r2, aye(1)));
+			assert_eq!(tally(r2), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			next_block();
 			assert_eq!(Balances::free_balance(42), 2);
 
-			assert_ok!(Democracy::vote(Origin::signed(1), r1, AYE));
-			assert_eq!(Democracy::voters_for(r1), vec![1]);
-			assert_eq!(Democracy::vote_of((r1, 1)), AYE);
-			assert_eq!(Democracy::tally(r1), (1, 0, 1));
+			assert_ok!(Democracy::vote(Origin::signed(1), r1, aye(1)));
+			assert_eq!(tally(r1), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			next_block();
 			assert_eq!(Balances::free_balance(42), 3);
@@ -2270,11 +2754,9 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
+			assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			next_block();
 			next_block();
@@ -2293,7 +2775,7 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r.into()));
 
 			next_block();
@@ -2313,11 +2795,9 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, NAY));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, nay(1)));
 
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), NAY);
-			assert_eq!(Democracy::tally(r), (0, 1, 1));
+			assert_eq!(tally(r), Tally { ayes: 0, nays: 1, turnout: 10 });
 
 			next_block();
 			next_block();
@@ -2337,14 +2817,14 @@ mod tests {
 				0
 			);
 
-			assert_ok!(Democracy::vote(Origin::signed(1), r, BIG_AYE));
-			assert_ok!(Democracy::vote(Origin::signed(2), r, BIG_NAY));
-			assert_ok!(Democracy::vote(Origin::signed(3), r, BIG_NAY));
-			assert_ok!(Democracy::vote(Origin::signed(4), r, BIG_AYE));
-			assert_ok!(Democracy::vote(Origin::signed(5), r, BIG_NAY));
-			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, big_nay(2)));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, big_nay(3)));
+			assert_ok!(Democracy::vote(Origin::signed(4), r, big_aye(4)));
+			assert_ok!(Democracy::vote(Origin::signed(5), r, big_nay(5)));
+			assert_ok!(Democracy::vote(Origin::signed(6), r, big_aye(6)));
 
-			assert_eq!(Democracy::tally(r), (110, 100, 210));
+			assert_eq!(tally(r), Tally { ayes: 110, nays: 100, turnout: 210 });
 
 			next_block();
 			next_block();
@@ -2363,14 +2843,14 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				1
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
-			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
-			assert_ok!(Democracy::vote(Origin::signed(3), r, AYE));
-			assert_ok!(Democracy::vote(Origin::signed(4), r, AYE));
-			assert_ok!(Democracy::vote(Origin::signed(5), r, AYE));
-			assert_ok!(Democracy::vote(Origin::signed(6), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, aye(3)));
+			assert_ok!(Democracy::vote(Origin::signed(4), r, aye(4)));
+			assert_ok!(Democracy::vote(Origin::signed(5), r, aye(5)));
+			assert_ok!(Democracy::vote(Origin::signed(6), r, aye(6)));
 
-			assert_eq!(Democracy::tally(r), (21, 0, 21));
+			assert_eq!(tally(r), Tally { ayes: 21, nays: 0, turnout: 210 });
 
 			next_block();
 			assert_eq!(Balances::free_balance(42), 0);
@@ -2391,10 +2871,10 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(5), r, BIG_NAY));
-			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_AYE));
+			assert_ok!(Democracy::vote(Origin::signed(5), r, big_nay(5)));
+			assert_ok!(Democracy::vote(Origin::signed(6), r, big_aye(6)));
 
-			assert_eq!(Democracy::tally(r), (60, 50, 110));
+			assert_eq!(tally(r), Tally { ayes: 60, nays: 50, turnout: 110 });
 
 			next_block();
 			next_block();
@@ -2416,11 +2896,11 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(4), r, BIG_AYE));
-			assert_ok!(Democracy::vote(Origin::signed(5), r, BIG_NAY));
-			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_AYE));
+			assert_ok!(Democracy::vote(Origin::signed(4), r, big_aye(4)));
+			assert_ok!(Democracy::vote(Origin::signed(5), r, big_nay(5)));
+			assert_ok!(Democracy::vote(Origin::signed(6), r, big_aye(6)));
 
-			assert_eq!(Democracy::tally(r), (100, 50, 150));
+			assert_eq!(tally(r), Tally { ayes: 100, nays: 50, turnout: 150 });
 
 			next_block();
 			next_block();
@@ -2429,6 +2909,14 @@ mod tests {
 		});
 	}
 
+	fn the_lock(amount: u64) -> BalanceLock<u64> {
+		BalanceLock {
+			id: DEMOCRACY_ID,
+			amount,
+			reasons: pallet_balances::Reasons::Misc,
+		}
+	}
+
 	#[test]
 	fn lock_voting_should_work() {
 		new_test_ext().execute_with(|| {
@@ -2439,73 +2927,75 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, Vote {
-				aye: false,
-				conviction: Conviction::Locked5x
-			}));
-			assert_ok!(Democracy::vote(Origin::signed(2), r, Vote {
-				aye: true,
-				conviction: Conviction::Locked4x
-			}));
-			assert_ok!(Democracy::vote(Origin::signed(3), r, Vote {
-				aye: true,
-				conviction: Conviction::Locked3x
-			}));
-			assert_ok!(Democracy::vote(Origin::signed(4), r, Vote {
-				aye: true,
-				conviction: Conviction::Locked2x
-			}));
-			assert_ok!(Democracy::vote(Origin::signed(5), r, Vote {
-				aye: false,
-				conviction: Conviction::Locked1x
-			}));
-
-			assert_eq!(Democracy::tally(r), (250, 100, 150));
+			let vote = |aye, conviction, balance| AccountVote::Standard {
+				vote: Vote { aye, conviction },
+				balance,
+			};
+			assert_ok!(Democracy::vote(Origin::signed(1), r, vote(false, Conviction::Locked5x, 10)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, vote(true, Conviction::Locked4x, 20)));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, vote(true, Conviction::Locked3x, 30)));
+			assert_ok!(Democracy::vote(Origin::signed(4), r, vote(true, Conviction::Locked2x, 40)));
+			assert_ok!(Democracy::vote(Origin::signed(5), r, vote(false, Conviction::Locked1x, 50)));
+
+			assert_eq!(tally(r), Tally { ayes: 250, nays: 100, turnout: 150 });
+
+			// All balances are currently locked.
+			for i in 1..=5 {
+				assert_eq!(Balances::locks(i), vec![the_lock(i * 10)]);
+			}
 
 			fast_forward_to(2);
 
+			assert_eq!(Balances::free_balance(42), 2);
+
+			// Referendum passed; 1 and 5 didn't get their way and can now reduce and unlock.
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_ok!(Democracy::unlock(Origin::signed(1), 1));
+			// Anyone can reduce the votes of those who lost.
+			assert_ok!(Democracy::remove_other_vote(Origin::signed(1), 5, r));
+			assert_ok!(Democracy::unlock(Origin::signed(1), 5));
 			assert_eq!(Balances::locks(1), vec![]);
-			assert_eq!(Balances::locks(2), vec![BalanceLock {
-				id: DEMOCRACY_ID,
-				amount: u64::max_value(),
-				reasons: pallet_balances::Reasons::Misc,
-			}]);
-			assert_eq!(Democracy::locks(2), Some(18));
-			assert_eq!(Balances::locks(3), vec![BalanceLock {
-				id: DEMOCRACY_ID,
-				amount: u64::max_value(),
-				reasons: pallet_balances::Reasons::Misc,
-			}]);
-			assert_eq!(Democracy::locks(3), Some(10));
-			assert_eq!(Balances::locks(4), vec![BalanceLock {
-				id: DEMOCRACY_ID,
-				amount: u64::max_value(),
-				reasons: pallet_balances::Reasons::Misc,
-			}]);
-			assert_eq!(Democracy::locks(4), Some(6));
 			assert_eq!(Balances::locks(5), vec![]);
 
-			assert_eq!(Balances::free_balance(42), 2);
-
-			assert_noop!(Democracy::unlock(Origin::signed(1), 1), Error::<Test>::NotLocked);
+			// The winners' locks only expire after their conviction's lock period; until then
+			// they can remove their votes but not their locks.
+			assert_noop!(
+				Democracy::remove_other_vote(Origin::signed(1), 4, r),
+				Error::<Test>::NoPermission
+			);
+			assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+			assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+			assert_eq!(Balances::locks(2), vec![the_lock(20)]);
 
 			fast_forward_to(5);
-			assert_noop!(Democracy::unlock(Origin::signed(1), 4), Error::<Test>::NotExpired);
+			assert_noop!(
+				Democracy::remove_other_vote(Origin::signed(1), 4, r),
+				Error::<Test>::NoPermission
+			);
 			fast_forward_to(6);
+			assert_ok!(Democracy::remove_other_vote(Origin::signed(1), 4, r));
 			assert_ok!(Democracy::unlock(Origin::signed(1), 4));
-			assert_noop!(Democracy::unlock(Origin::signed(1), 4), Error::<Test>::NotLocked);
+			assert_eq!(Balances::locks(4), vec![]);
 
 			fast_forward_to(9);
-			assert_noop!(Democracy::unlock(Origin::signed(1), 3), Error::<Test>::NotExpired);
+			assert_noop!(
+				Democracy::remove_other_vote(Origin::signed(1), 3, r),
+				Error::<Test>::NoPermission
+			);
 			fast_forward_to(10);
+			assert_ok!(Democracy::remove_other_vote(Origin::signed(1), 3, r));
 			assert_ok!(Democracy::unlock(Origin::signed(1), 3));
-			assert_noop!(Democracy::unlock(Origin::signed(1), 3), Error::<Test>::NotLocked);
+			assert_eq!(Balances::locks(3), vec![]);
 
 			fast_forward_to(17);
-			assert_noop!(Democracy::unlock(Origin::signed(1), 2), Error::<Test>::NotExpired);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2), vec![the_lock(20)]);
 			fast_forward_to(18);
 			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
-			assert_noop!(Democracy::unlock(Origin::signed(1), 2), Error::<Test>::NotLocked);
+			assert_eq!(Balances::locks(2), vec![]);
+
+			// Accounts which never voted have nothing to unlock.
+			assert_noop!(Democracy::unlock(Origin::signed(1), 6), Error::<Test>::NotLocked);
 		});
 	}
 
@@ -2519,30 +3009,88 @@ mod tests {
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
-			assert_ok!(Democracy::vote(Origin::signed(1), r, Vote {
-				aye: false,
-				conviction: Conviction::Locked5x
-			}));
-			assert_ok!(Democracy::vote(Origin::signed(2), r, Vote {
-				aye: true,
-				conviction: Conviction::Locked4x
-			}));
-			assert_ok!(Democracy::vote(Origin::signed(3), r, Vote {
-				aye: true,
-				conviction: Conviction::Locked3x
-			}));
-			assert_ok!(Democracy::delegate(Origin::signed(4), 2, Conviction::Locked2x));
-			assert_ok!(Democracy::vote(Origin::signed(5), r, Vote {
-				aye: false,
-				conviction: Conviction::Locked1x
-			}));
-
-			assert_eq!(Democracy::tally(r), (250, 100, 150));
+			let vote = |aye, conviction, balance| AccountVote::Standard {
+				vote: Vote { aye, conviction },
+				balance,
+			};
+			assert_ok!(Democracy::vote(Origin::signed(1), r, vote(false, Conviction::Locked5x, 10)));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, vote(true, Conviction::Locked4x, 20)));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, vote(true, Conviction::Locked3x, 30)));
+			assert_ok!(Democracy::delegate(Origin::signed(4), 2, Conviction::Locked2x, 40));
+			assert_ok!(Democracy::vote(Origin::signed(5), r, vote(false, Conviction::Locked1x, 50)));
+
+			assert_eq!(tally(r), Tally { ayes: 250, nays: 100, turnout: 150 });
 
 			next_block();
 			next_block();
 
 			assert_eq!(Balances::free_balance(42), 2);
+
+			// The delegated balance stays locked while delegating, and for the lock period of
+			// its conviction once undelegated.
+			assert_ok!(Democracy::unlock(Origin::signed(1), 4));
+			assert_eq!(Balances::locks(4), vec![the_lock(40)]);
+			assert_ok!(Democracy::undelegate(Origin::signed(4)));
+			fast_forward_to(6);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 4));
+			assert_eq!(Balances::locks(4), vec![the_lock(40)]);
+			fast_forward_to(7);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 4));
+			assert_eq!(Balances::locks(4), vec![]);
+		});
+	}
+
+	#[test]
+	fn votes_and_delegations_via_migration_should_work() {
+		use migration::{OldReferendumInfo, OldVotersFor, OldVoteOf, OldDelegations};
+		use frame_support::storage::{StorageMap as _, unhashed};
+
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			StorageVersion::put(0);
+			ReferendumCount::put(2);
+			let h = set_balance_proposal_hash_and_note(2);
+			let old_info = OldReferendumInfo {
+				end: 2u64,
+				proposal_hash: h,
+				threshold: VoteThreshold::SuperMajorityApprove,
+				delay: 0u64,
+			};
+			unhashed::put(&<ReferendumInfoOf<Test>>::hashed_key_for(1), &old_info);
+			OldVotersFor::<Test>::insert(1, vec![1, 3]);
+			OldVoteOf::<Test>::insert((1, 1), AYE);
+			OldVoteOf::<Test>::insert((1, 3), BIG_NAY);
+			// 4 delegates through 2 to 1.
+			OldDelegations::<Test>::insert(2, (1, Conviction::Locked1x));
+			OldDelegations::<Test>::insert(4, (2, Conviction::None));
+			// 3 delegated but also voted.
+			OldDelegations::<Test>::insert(3, (1, Conviction::Locked6x));
+			// A vote on a referendum which was cancelled.
+			OldVotersFor::<Test>::insert(0, vec![5]);
+			OldVoteOf::<Test>::insert((0, 5), AYE);
+
+			migration::perform_migrations::<Test>();
+
+			assert_eq!(StorageVersion::get(), migration::CURRENT_VERSION);
+			assert_eq!(tally(1), Tally { ayes: 25, nays: 30, turnout: 100 });
+			assert_eq!(Democracy::voting_of(4), Voting::Delegating {
+				balance: 40,
+				target: 2,
+				conviction: Conviction::None,
+				delegations: Default::default(),
+				prior: Default::default(),
+			});
+			assert_eq!(Democracy::voting_of(1).delegations(), Delegations { votes: 24, capital: 60 });
+			assert_eq!(Balances::locks(1), vec![the_lock(10)]);
+			// 3's delegation became a legacy lock.
+			assert_eq!(Democracy::locks(3), Some(65));
+			assert_eq!(Democracy::voting_of(5), Voting::default());
+			assert!(OldVoteOf::<Test>::get((0, 5)).is_none());
+			assert!(OldDelegations::<Test>::get(2).is_none());
+
+			// Migration only happens once.
+			migration::perform_migrations::<Test>();
+			assert_eq!(tally(1), Tally { ayes: 25, nays: 30, turnout: 100 });
 		});
 	}
 }
@@ -0,0 +1,232 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations for pallet-democracy.
+
+use crate::{
+	Module, Trait, Store, BalanceOf, ReferendumIndex, ReferendumInfo, ReferendumStatus, Vote,
+	AccountVote, Voting, Conviction, VoteThreshold, Locks, DEMOCRACY_ID,
+};
+use frame_support::{
+	Blake2_256, Twox64Concat, StorageValue,
+	storage::{generator, unhashed, migration::take_linked_map, StorageMap, IterableStorageMap},
+	traits::{Currency, LockableCurrency, WithdrawReason, Get},
+};
+use sp_runtime::traits::Zero;
+use codec::{Encode, Decode};
+use sp_std::{vec::Vec, marker::PhantomData, collections::btree_map::BTreeMap};
+
+/// Indicator of a version of a storage layout.
+pub type VersionNumber = u32;
+
+// the current expected version of the storage
+pub const CURRENT_VERSION: VersionNumber = 1;
+
+// the `ReferendumInfoOf` value as it was laid out before v1, i.e. without a tally.
+#[derive(Encode, Decode)]
+pub(crate) struct OldReferendumInfo<BlockNumber, Hash> {
+	pub(crate) end: BlockNumber,
+	pub(crate) proposal_hash: Hash,
+	pub(crate) threshold: VoteThreshold,
+	pub(crate) delay: BlockNumber,
+}
+
+// the `VotersFor` storage item as it was laid out before v1.
+pub(crate) struct OldVotersFor<T>(PhantomData<T>);
+
+impl<T: Trait> generator::StorageMap<ReferendumIndex, Vec<T::AccountId>> for OldVotersFor<T> {
+	type Query = Vec<T::AccountId>;
+	type Hasher = Blake2_256;
+	fn module_prefix() -> &'static [u8] { b"Democracy" }
+	fn storage_prefix() -> &'static [u8] { b"VotersFor" }
+	fn from_optional_value_to_query(v: Option<Vec<T::AccountId>>) -> Self::Query {
+		v.unwrap_or_default()
+	}
+	fn from_query_to_optional_value(v: Self::Query) -> Option<Vec<T::AccountId>> { Some(v) }
+}
+
+// the `VoteOf` storage item as it was laid out before v1.
+pub(crate) struct OldVoteOf<T>(PhantomData<T>);
+
+impl<T: Trait> generator::StorageMap<(ReferendumIndex, T::AccountId), Vote> for OldVoteOf<T> {
+	type Query = Option<Vote>;
+	type Hasher = Blake2_256;
+	fn module_prefix() -> &'static [u8] { b"Democracy" }
+	fn storage_prefix() -> &'static [u8] { b"VoteOf" }
+	fn from_optional_value_to_query(v: Option<Vote>) -> Self::Query { v }
+	fn from_query_to_optional_value(v: Self::Query) -> Option<Vote> { v }
+}
+
+// the `Delegations` storage item as it was laid out before v1.
+pub(crate) struct OldDelegations<T>(PhantomData<T>);
+
+impl<T: Trait> generator::StorageMap<T::AccountId, (T::AccountId, Conviction)>
+	for OldDelegations<T>
+{
+	type Query = Option<(T::AccountId, Conviction)>;
+	type Hasher = Twox64Concat;
+	fn module_prefix() -> &'static [u8] { b"Democracy" }
+	fn storage_prefix() -> &'static [u8] { b"Delegations" }
+	fn from_optional_value_to_query(v: Option<(T::AccountId, Conviction)>) -> Self::Query { v }
+	fn from_query_to_optional_value(v: Self::Query) -> Option<(T::AccountId, Conviction)> { v }
+}
+
+// move `Delegations` out of the linked map it used to be stored in.
+fn upgrade_delegations<T: Trait>() {
+	let delegations = take_linked_map::<T::AccountId, (T::AccountId, Conviction), Blake2_256>(
+		b"Democracy",
+		b"Delegations",
+	);
+	for (delegator, delegation) in delegations {
+		OldDelegations::<T>::insert(delegator, delegation);
+	}
+}
+
+// migrate storage from v0 to v1.
+//
+// this moves the votes of ongoing referenda out of `VotersFor` and `VoteOf` into `VotingOf`,
+// keeping a running tally on each referendum, and turns the entries of `Delegations` into
+// `Voting::Delegating` records which are accumulated into their target's votes. The voted and
+// delegated balance is the voter's free balance at the time of the migration.
+fn to_v1<T: Trait>(version: &mut VersionNumber) {
+	if *version != 0 { return }
+	*version += 1;
+
+	let now = <frame_system::Module<T>>::block_number();
+	for index in <Module<T>>::lowest_unbaked()..<Module<T>>::referendum_count() {
+		let voters = OldVotersFor::<T>::take(index);
+		let key = <Module<T> as Store>::ReferendumInfoOf::hashed_key_for(index);
+		let old = unhashed::get::<OldReferendumInfo<T::BlockNumber, T::Hash>>(&key);
+		let mut status = match old {
+			Some(old) => ReferendumStatus {
+				end: old.end,
+				proposal_hash: old.proposal_hash,
+				threshold: old.threshold,
+				delay: old.delay,
+				tally: Default::default(),
+			},
+			// cancelled; forget about any votes.
+			None => {
+				for voter in voters {
+					OldVoteOf::<T>::remove((index, voter));
+				}
+				continue
+			}
+		};
+		for voter in voters {
+			let vote = match OldVoteOf::<T>::take((index, &voter)) {
+				Some(vote) => vote,
+				None => continue,
+			};
+			let balance = T::Currency::free_balance(&voter);
+			let account_vote = AccountVote::Standard { vote, balance };
+			let votes_count = match <Module<T> as Store>::VotingOf::get(&voter) {
+				Voting::Direct { votes, .. } => votes.len(),
+				Voting::Delegating { .. } => 0,
+			};
+			if votes_count >= T::MaxVotes::get() as usize {
+				frame_support::print("Too many votes when migrating a democracy vote; vote dropped.");
+				continue
+			}
+			if status.tally.add(account_vote).is_none() {
+				frame_support::print("Overflow when migrating a democracy vote; vote dropped.");
+				continue
+			}
+			// referenda are visited in order, so each voter's votes remain sorted.
+			<Module<T> as Store>::VotingOf::mutate(&voter, |voting| {
+				if let Voting::Direct { votes, .. } = voting {
+					votes.push((index, account_vote));
+				}
+			});
+			T::Currency::extend_lock(DEMOCRACY_ID, &voter, balance, WithdrawReason::Transfer.into());
+		}
+		<Module<T> as Store>::ReferendumInfoOf::insert(index, ReferendumInfo::Ongoing(status));
+	}
+
+	// an account may only delegate while nobody delegates to it, so each chain is migrated from
+	// its end: every delegation is moved over before any of the delegations made to its delegator.
+	let mut pending: BTreeMap<_, _> = OldDelegations::<T>::drain().collect();
+	while let Some(first) = pending.keys().next().cloned() {
+		let mut chain = sp_std::vec![first];
+		loop {
+			let next = match pending.get(&chain[chain.len() - 1]) {
+				Some((target, _)) if pending.contains_key(target) && !chain.contains(target) =>
+					target.clone(),
+				_ => break,
+			};
+			chain.push(next);
+		}
+		for who in chain.into_iter().rev() {
+			if let Some((target, conviction)) = pending.remove(&who) {
+				migrate_delegation::<T>(who, target, conviction, now);
+			}
+		}
+	}
+
+	frame_support::print("Finished migrating Democracy storage to v1.");
+}
+
+// turn a single old delegation into a `Voting::Delegating` record, falling back to a legacy lock
+// if the delegation is no longer allowed.
+fn migrate_delegation<T: Trait>(
+	who: T::AccountId,
+	target: T::AccountId,
+	conviction: Conviction,
+	now: T::BlockNumber,
+) {
+	let voting = <Module<T> as Store>::VotingOf::get(&who);
+	let balance: BalanceOf<T> = T::Currency::free_balance(&who);
+	match voting {
+		Voting::Direct { ref votes, delegations, prior }
+			if votes.is_empty()
+				&& delegations.capital.is_zero()
+				&& !balance.is_zero()
+				&& <Module<T>>::ensure_can_delegate_to(&who, &target).is_ok() =>
+		{
+			<Module<T> as Store>::VotingOf::insert(&who, Voting::Delegating {
+				balance,
+				target: target.clone(),
+				conviction,
+				delegations,
+				prior,
+			});
+			<Module<T>>::increase_upstream_delegation(&target, conviction.votes(balance));
+		}
+		_ => {
+			// the account also voted directly, has received delegations already, or its
+			// delegation would close a cycle or make too long a chain; none of these are
+			// allowed any more. the delegation ends here and its indefinite lock becomes a
+			// legacy lock which expires as if it were undelegated now.
+			let lock_periods = conviction.lock_periods().into();
+			Locks::<T>::insert(&who, now + T::EnactmentPeriod::get() * lock_periods);
+		}
+	}
+}
+
+/// Perform all necessary storage migrations to get storage into the expected state for current
+/// logic. No-op if fully upgraded.
+pub(crate) fn perform_migrations<T: Trait>() {
+	if !<Module<T> as Store>::IsUpgraded::get() {
+		<Module<T> as Store>::IsUpgraded::put(true);
+		upgrade_delegations::<T>();
+	}
+
+	<Module<T> as Store>::StorageVersion::mutate(|version| {
+		if *version == CURRENT_VERSION { return }
+
+		to_v1::<T>(version);
+	});
+}
@@ -83,9 +83,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
-use sp_runtime::{print, DispatchResult, DispatchError, traits::{Zero, StaticLookup, Convert}};
+use sp_runtime::{print, DispatchResult, DispatchError, traits::{Zero, StaticLookup, Convert, Saturating}};
 use frame_support::{
 	decl_storage, decl_event, ensure, decl_module, decl_error, weights::SimpleDispatchInfo,
+	Blake2_256, BoundedVec, storage::migration::take_linked_map,
 	traits::{
 		Currency, Get, LockableCurrency, LockIdentifier, ReservableCurrency, WithdrawReasons,
 		ChangeMembers, OnUnbalanced, WithdrawReason, Contains
@@ -144,6 +145,9 @@ pub trait Trait: frame_system::Trait {
 	/// round will happen. If set to zero, no elections are ever triggered and the module will
 	/// be in passive mode.
 	type TermDuration: Get<Self::BlockNumber>;
+
+	/// Maximum number of candidates in a single election round.
+	type MaxCandidates: Get<u32>;
 }
 
 decl_storage! {
@@ -157,13 +161,17 @@ decl_storage! {
 		pub ElectionRounds get(fn election_rounds): u32 = Zero::zero();
 
 		/// Votes of a particular voter, with the round index of the votes.
-		pub VotesOf get(fn votes_of): linked_map hasher(blake2_256) T::AccountId => Vec<T::AccountId>;
+		pub VotesOf get(fn votes_of): map hasher(twox_64_concat) T::AccountId => Vec<T::AccountId>;
 		/// Locked stake of a voter.
 		pub StakeOf get(fn stake_of): map hasher(blake2_256) T::AccountId => BalanceOf<T>;
 
 		/// The present candidate list. Sorted based on account-id. A current member or a runner can
 		/// never enter this vector and is always implicitly assumed to be a candidate.
-		pub Candidates get(fn candidates): Vec<T::AccountId>;
+		pub Candidates get(fn candidates): BoundedVec<T::AccountId, T::MaxCandidates>;
+
+		/// True if the network has been upgraded to store `VotesOf` in an iterable map. New
+		/// networks start out upgraded.
+		IsUpgraded build(|_| true): bool;
 	}
 }
 
@@ -198,6 +206,8 @@ decl_error! {
 		InvalidOrigin,
 		/// Not a member.
 		NotMember,
+		/// There can only be a maximum of `MaxCandidates` candidates.
+		TooManyCandidates,
 	}
 }
 
@@ -350,10 +360,13 @@ decl_module! {
 			ensure!(!Self::is_member(&who), Error::<T>::MemberSubmit);
 			ensure!(!Self::is_runner(&who), Error::<T>::RunnerSubmit);
 
+			let mut candidates = Self::candidates();
+			candidates.try_insert(index, who.clone()).map_err(|_| Error::<T>::TooManyCandidates)?;
+
 			T::Currency::reserve(&who, T::CandidacyBond::get())
 				.map_err(|_| Error::<T>::InsufficientCandidateFunds)?;
 
-			<Candidates<T>>::mutate(|c| c.insert(index, who));
+			<Candidates<T>>::put(candidates);
 		}
 
 		/// Renounce one's intention to be a candidate for the next election round. 3 potential
@@ -442,6 +455,11 @@ decl_module! {
 
 		/// What to do at the end of each block. Checks if an election needs to happen or not.
 		fn on_initialize(n: T::BlockNumber) {
+			if !IsUpgraded::get() {
+				IsUpgraded::put(true);
+				Self::do_upgrade();
+			}
+
 			if let Err(e) = Self::end_block(n) {
 				print("Guru meditation");
 				print(e);
@@ -472,6 +490,17 @@ decl_event!(
 );
 
 impl<T: Trait> Module<T> {
+	/// Move `VotesOf` out of the linked map it used to be stored in.
+	fn do_upgrade() {
+		let votes = take_linked_map::<T::AccountId, Vec<T::AccountId>, Blake2_256>(
+			b"PhragmenElection",
+			b"VotesOf",
+		);
+		for (voter, targets) in votes {
+			<VotesOf<T>>::insert(voter, targets);
+		}
+	}
+
 	/// Attempts to remove a member `who`. If a runner up exists, it is used as the replacement.
 	/// Otherwise, `Ok(false)` is returned to signal the caller.
 	///
@@ -624,7 +653,7 @@ impl<T: Trait> Module<T> {
 		let desired_runners_up = Self::desired_runners_up() as usize;
 		let num_to_elect = desired_runners_up + desired_seats;
 
-		let mut candidates = Self::candidates();
+		let mut candidates = Self::candidates().into_inner();
 		// candidates who explicitly called `submit_candidacy`. Only these folks are at the risk of
 		// losing their bond.
 		let exposed_candidates = candidates.clone();
@@ -634,14 +663,14 @@ impl<T: Trait> Module<T> {
 		// previous runners_up are also always candidates for the next round.
 		candidates.append(&mut Self::runners_up_ids());
 
-		let voters_and_votes = <VotesOf<T>>::enumerate()
+		let voters_and_votes = <VotesOf<T>>::iter()
 			.map(|(v, i)| (v, i))
 			.collect::<Vec<(T::AccountId, Vec<T::AccountId>)>>();
 		let maybe_phragmen_result = sp_phragmen::elect::<_, _, _, T::CurrencyToVote>(
 			num_to_elect,
 			0,
 			candidates,
-			voters_and_votes,
+			voters_and_votes.clone(),
 			Self::locked_stake_of,
 		);
 
@@ -709,6 +738,22 @@ impl<T: Trait> Module<T> {
 				.map(|(r, _)| r.clone())
 				.collect::<Vec<T::AccountId>>();
 
+			// the prime is the new member backed by the most stake. Since new_members_ids is
+			// sorted, ties go to the highest account id.
+			let mut prime_votes = new_members_ids
+				.iter()
+				.map(|m| (m, BalanceOf::<T>::zero()))
+				.collect::<Vec<_>>();
+			for (voter, votes) in voters_and_votes.iter() {
+				let stake = Self::locked_stake_of(voter);
+				for vote in votes.iter() {
+					if let Ok(i) = prime_votes.binary_search_by_key(&vote, |&(m, _)| m) {
+						prime_votes[i].1 = prime_votes[i].1.saturating_add(stake);
+					}
+				}
+			}
+			let prime = prime_votes.into_iter().max_by_key(|&(_, s)| s).map(|(m, _)| m.clone());
+
 			// report member changes. We compute diff because we need the outgoing list.
 			let (incoming, outgoing) = T::ChangeMembers::compute_members_diff(
 				&new_members_ids,
@@ -719,6 +764,7 @@ impl<T: Trait> Module<T> {
 				&outgoing.clone(),
 				&new_members_ids,
 			);
+			T::ChangeMembers::set_prime(prime);
 
 			// outgoing candidates lose their bond.
 			let mut to_burn_bond = outgoing.to_vec();
@@ -776,7 +822,7 @@ impl<T: Trait> Contains<T::AccountId> for Module<T> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use std::cell::RefCell;
+	use std::{cell::RefCell, convert::TryFrom};
 	use frame_support::{assert_ok, assert_noop, parameter_types, weights::Weight};
 	use substrate_test_utils::assert_eq_uvec;
 	use sp_core::H256;
@@ -860,8 +906,13 @@ mod tests {
 		fn get() -> u64 { TERM_DURATION.with(|v| *v.borrow()) }
 	}
 
+	parameter_types! {
+		pub const MaxCandidates: u32 = 5;
+	}
+
 	thread_local! {
 		pub static MEMBERS: RefCell<Vec<u64>> = RefCell::new(vec![]);
+		pub static PRIME: RefCell<Option<u64>> = RefCell::new(None);
 	}
 
 	pub struct TestChangeMembers;
@@ -896,6 +947,11 @@ mod tests {
 			assert_eq!(old_plus_incoming, new_plus_outgoing);
 
 			MEMBERS.with(|m| *m.borrow_mut() = new.to_vec());
+			PRIME.with(|p| *p.borrow_mut() = None);
+		}
+
+		fn set_prime(who: Option<u64>) {
+			PRIME.with(|p| *p.borrow_mut() = who);
 		}
 	}
 
@@ -918,6 +974,7 @@ mod tests {
 		type CandidacyBond = CandidacyBond;
 		type VotingBond = VotingBond;
 		type TermDuration = TermDuration;
+		type MaxCandidates = MaxCandidates;
 		type DesiredMembers = DesiredMembers;
 		type DesiredRunnersUp = DesiredRunnersUp;
 		type LoserCandidate = ();
@@ -936,7 +993,7 @@ mod tests {
 		{
 			System: system::{Module, Call, Event},
 			Balances: pallet_balances::{Module, Call, Event<T>, Config<T>},
-			Elections: elections::{Module, Call, Event<T>},
+			Elections: elections::{Module, Call, Event<T>, Config},
 		}
 	);
 
@@ -986,12 +1043,15 @@ mod tests {
 						(6, 60 * self.balance_factor)
 					],
 				}),
+				elections: Some(Default::default()),
 			}.build_storage().unwrap().into()
 		}
 	}
 
 	fn all_voters() -> Vec<u64> {
-		<VotesOf<Test>>::enumerate().map(|(v, _)| v).collect::<Vec<u64>>()
+		let mut voters = <VotesOf<Test>>::iter().map(|(v, _)| v).collect::<Vec<u64>>();
+		voters.sort();
+		voters
 	}
 
 	fn balances(who: &u64) -> (u64, u64) {
@@ -1024,6 +1084,21 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn new_chains_start_out_upgraded() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert!(IsUpgraded::get());
+
+			assert_ok!(Elections::submit_candidacy(Origin::signed(5)));
+			assert_ok!(Elections::vote(Origin::signed(2), vec![5], 20));
+
+			// The upgrade must not run over votes which are already stored in the new format.
+			<Elections as sp_runtime::traits::OnInitialize<u64>>::on_initialize(1);
+			assert_eq!(all_voters(), vec![2]);
+			assert_eq!(Elections::votes_of(&2), vec![5]);
+		});
+	}
+
 	#[test]
 	fn term_duration_zero_is_passive() {
 		ExtBuilder::default()
@@ -1076,6 +1151,21 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn candidates_are_limited() {
+		ExtBuilder::default().build().execute_with(|| {
+			for i in 1..=MaxCandidates::get() as u64 {
+				assert_ok!(Elections::submit_candidacy(Origin::signed(i)));
+			}
+			assert_noop!(
+				Elections::submit_candidacy(Origin::signed(6)),
+				Error::<Test>::TooManyCandidates,
+			);
+			assert_eq!(balances(&6), (60, 0));
+			assert_eq!(Elections::candidates(), vec![1, 2, 3, 4, 5]);
+		});
+	}
+
 	#[test]
 	fn simple_candidate_submission_with_no_votes_should_work() {
 		ExtBuilder::default().build().execute_with(|| {
@@ -1547,6 +1637,28 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn prime_works() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(Elections::submit_candidacy(Origin::signed(3)));
+			assert_ok!(Elections::submit_candidacy(Origin::signed(4)));
+			assert_ok!(Elections::submit_candidacy(Origin::signed(5)));
+
+			assert_ok!(Elections::vote(Origin::signed(1), vec![4, 3], 10));
+			assert_ok!(Elections::vote(Origin::signed(2), vec![4], 20));
+			assert_ok!(Elections::vote(Origin::signed(3), vec![3], 30));
+			assert_ok!(Elections::vote(Origin::signed(4), vec![4], 40));
+			assert_ok!(Elections::vote(Origin::signed(5), vec![5], 50));
+
+			System::set_block_number(5);
+			assert_ok!(Elections::end_block(System::block_number()));
+
+			assert_eq!(Elections::members_ids(), vec![4, 5]);
+			// 4 is backed by 70 and 5 by 50.
+			assert_eq!(PRIME.with(|p| *p.borrow()), Some(4));
+		});
+	}
+
 	#[test]
 	fn phragmen_should_not_self_vote() {
 		ExtBuilder::default().build().execute_with(|| {
@@ -2079,7 +2191,7 @@ mod tests {
 	#[test]
 	fn behavior_with_dupe_candidate() {
 		ExtBuilder::default().desired_runners_up(2).build().execute_with(|| {
-			<Candidates<Test>>::put(vec![1, 1, 2, 3, 4]);
+			<Candidates<Test>>::put(BoundedVec::try_from(vec![1, 1, 2, 3, 4]).unwrap());
 
 			assert_ok!(Elections::vote(Origin::signed(5), vec![1], 50));
 			assert_ok!(Elections::vote(Origin::signed(4), vec![4], 40));
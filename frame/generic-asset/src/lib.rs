@@ -167,6 +167,7 @@ use frame_support::{
 	traits::{
 		Currency, ExistenceRequirement, Imbalance, LockIdentifier, LockableCurrency, ReservableCurrency,
 		SignedImbalance, UpdateBalanceOutcome, WithdrawReason, WithdrawReasons, TryDrop,
+		fungibles,
 	},
 	Parameter, StorageMap,
 };
@@ -883,6 +884,125 @@ impl<T: Trait> Module<T> {
 	}
 }
 
+impl<T: Trait> fungibles::Inspect<T::AccountId> for Module<T> {
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn total_issuance(asset: T::AssetId) -> T::Balance {
+		<TotalIssuance<T>>::get(asset)
+	}
+
+	fn minimum_balance(_asset: T::AssetId) -> T::Balance {
+		Zero::zero()
+	}
+
+	fn balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::free_balance(&asset, who)
+	}
+
+	fn can_deposit(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		<TotalIssuance<T>>::get(asset).checked_add(&amount).ok_or(Error::<T>::TotalMintingOverflow)?;
+		Self::free_balance(&asset, who).checked_add(&amount).ok_or(Error::<T>::FreeMintingOverflow)?;
+		Ok(())
+	}
+
+	fn can_withdraw(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+		_liveness: ExistenceRequirement,
+	) -> DispatchResult {
+		let new_balance = Self::free_balance(&asset, who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::InsufficientBalance)?;
+		Self::ensure_can_withdraw(&asset, who, amount, reasons, new_balance)
+	}
+}
+
+impl<T: Trait> fungibles::Mutate<T::AccountId> for Module<T> {
+	fn mint_into(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		let new_total_issuance = <TotalIssuance<T>>::get(asset)
+			.checked_add(&amount)
+			.ok_or(Error::<T>::TotalMintingOverflow)?;
+		let new_balance = Self::free_balance(&asset, who)
+			.checked_add(&amount)
+			.ok_or(Error::<T>::FreeMintingOverflow)?;
+
+		<TotalIssuance<T>>::insert(asset, new_total_issuance);
+		Self::set_free_balance(&asset, who, new_balance);
+		Ok(())
+	}
+
+	fn burn_from(
+		asset: T::AssetId,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> result::Result<T::Balance, DispatchError> {
+		let new_balance = Self::free_balance(&asset, who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::FreeBurningUnderflow)?;
+		Self::ensure_can_withdraw(&asset, who, amount, WithdrawReasons::all(), new_balance)?;
+		let new_total_issuance = <TotalIssuance<T>>::get(asset)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::TotalBurningUnderflow)?;
+
+		<TotalIssuance<T>>::insert(asset, new_total_issuance);
+		Self::set_free_balance(&asset, who, new_balance);
+		Ok(amount)
+	}
+}
+
+impl<T: Trait> fungibles::Transfer<T::AccountId> for Module<T> {
+	fn transfer(
+		asset: T::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: T::Balance,
+		_liveness: ExistenceRequirement,
+	) -> result::Result<T::Balance, DispatchError> {
+		Self::make_transfer(&asset, source, dest, amount)?;
+		Ok(amount)
+	}
+}
+
+impl<T: Trait> fungibles::Reserve<T::AccountId> for Module<T> {
+	fn reserved_balance(asset: T::AssetId, who: &T::AccountId) -> T::Balance {
+		Self::reserved_balance(&asset, who)
+	}
+
+	fn can_reserve(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> bool {
+		Self::free_balance(&asset, who)
+			.checked_sub(&amount)
+			.map_or(false, |new_balance| Self::ensure_can_withdraw(
+				&asset, who, amount, WithdrawReason::Reserve.into(), new_balance,
+			).is_ok())
+	}
+
+	fn reserve(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		Self::reserve(&asset, who, amount)
+	}
+
+	fn unreserve(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		Self::unreserve(&asset, who, amount)
+	}
+
+	fn slash_reserved(asset: T::AssetId, who: &T::AccountId, amount: T::Balance) -> T::Balance {
+		let remaining = Self::slash_reserved(&asset, who, amount).unwrap_or_else(Zero::zero);
+		<TotalIssuance<T>>::mutate(asset, |issued| *issued = issued.saturating_sub(amount - remaining));
+		remaining
+	}
+
+	fn repatriate_reserved(
+		asset: T::AssetId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		amount: T::Balance,
+	) -> result::Result<T::Balance, DispatchError> {
+		Ok(Self::repatriate_reserved(&asset, slashed, beneficiary, amount))
+	}
+}
+
 pub trait AssetIdProvider {
 	type AssetId;
 	fn asset_id() -> Self::AssetId;
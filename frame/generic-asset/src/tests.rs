@@ -1213,3 +1213,32 @@ fn burn_should_raise_event() {
 		},
 	);
 }
+
+#[test]
+fn fungibles_traits_should_work() {
+	use frame_support::traits::fungibles::{Inspect, Mutate, Transfer, Reserve};
+
+	ExtBuilder::default().free_balance((16000, 1, 100)).build().execute_with(|| {
+		assert_eq!(<GenericAsset as Inspect<_>>::balance(16000, &1), 100);
+		assert_eq!(<GenericAsset as Inspect<_>>::minimum_balance(16000), 0);
+
+		assert_ok!(<GenericAsset as Mutate<_>>::mint_into(16000, &2, 50));
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 50);
+		assert_eq!(<GenericAsset as Inspect<_>>::total_issuance(16000), 150);
+
+		assert_eq!(<GenericAsset as Transfer<_>>::transfer(16000, &1, &2, 30, ExistenceRequirement::AllowDeath), Ok(30));
+		assert_eq!(GenericAsset::free_balance(&16000, &1), 70);
+		assert_eq!(GenericAsset::free_balance(&16000, &2), 80);
+
+		assert_ok!(<GenericAsset as Reserve<_>>::reserve(16000, &2, 40));
+		assert_eq!(<GenericAsset as Reserve<_>>::slash_reserved(16000, &2, 50), 10);
+		assert_eq!(<GenericAsset as Inspect<_>>::total_issuance(16000), 110);
+
+		assert_eq!(<GenericAsset as Mutate<_>>::burn_from(16000, &1, 70), Ok(70));
+		assert_eq!(<GenericAsset as Inspect<_>>::total_issuance(16000), 40);
+		assert_noop!(
+			<GenericAsset as Mutate<_>>::burn_from(16000, &1, 1),
+			Error::<Test>::FreeBurningUnderflow
+		);
+	});
+}
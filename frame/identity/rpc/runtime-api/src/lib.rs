@@ -0,0 +1,44 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for registrar tooling of the identity module.
+//!
+//! This API should be imported and implemented by the runtime of a node whose
+//! registrars want to discover the judgements they have been asked for, or whose
+//! wallets want to resolve display names to accounts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+
+/// An identifier for a single name registrar/identity verification service.
+pub type RegistrarIndex = u32;
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the judgement requests of identity registrars.
+	pub trait IdentityApi<AccountId, Balance> where
+		AccountId: codec::Codec,
+		Balance: codec::Codec,
+	{
+		/// Get the accounts that have requested a judgement from the registrar `reg_index`,
+		/// together with the fee each of them has reserved for it.
+		fn pending_judgements(reg_index: RegistrarIndex) -> Vec<(AccountId, Balance)>;
+
+		/// Get the account whose identity has the display name `name`, ignoring ASCII case. If
+		/// display names are not unique, this is the last account to have set the name.
+		fn account_by_display(name: Vec<u8>) -> Option<AccountId>;
+	}
+}
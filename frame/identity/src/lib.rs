@@ -47,6 +47,10 @@
 //! * `set_identity` - Set the associated identity of an account; a small deposit is reserved if not
 //!   already taken.
 //! * `set_subs` - Set the sub-accounts of an identity.
+//! * `add_sub` - Add a sub-account to an identity.
+//! * `rename_sub` - Rename a sub-account of an identity.
+//! * `remove_sub` - Remove a sub-account of an identity.
+//! * `quit_sub` - Stop being a sub-account of an identity; the deposit is paid to the sub-account.
 //! * `clear_identity` - Remove an account's associated identity; the deposit is returned.
 //! * `request_judgement` - Request a judgement from a registrar, paying a fee.
 //! * `cancel_request` - Cancel the previous request for a judgement.
@@ -59,12 +63,24 @@
 //! #### For super-users
 //! * `add_registrar` - Add a new registrar to the system.
 //! * `kill_identity` - Forcibly remove the associated identity; the deposit is lost.
+//! * `reindex_identities` - Index identities set before the indices of this module existed.
+//!
+//! ### Registrar tooling
+//!
+//! Outstanding judgement requests of each registrar are indexed so that they can be listed through
+//! [`Module::pending_judgements`](./struct.Module.html#method.pending_judgements), which is exposed
+//! to clients by the runtime API in `pallet-identity-rpc-runtime-api`, which can also resolve a
+//! display name to its account. The [`offchain`] module
+//! helps verify the claims made in an identity from an offchain worker before giving a judgement.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
+//! [`offchain`]: ./offchain/index.html
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod offchain;
+
 use sp_std::prelude::*;
 use sp_std::{fmt::Debug, ops::Add, iter::once};
 use enumflags2::BitFlags;
@@ -72,9 +88,10 @@ use codec::{Encode, Decode};
 use sp_runtime::{DispatchResult, RuntimeDebug};
 use sp_runtime::traits::{StaticLookup, EnsureOrigin, Zero, AppendZerosInput};
 use frame_support::{
-	decl_module, decl_event, decl_storage, ensure, decl_error,
+	decl_module, decl_event, decl_storage, ensure, decl_error, BoundedVec,
+	storage::IterableStorageDoubleMap,
 	traits::{Currency, ReservableCurrency, OnUnbalanced, Get},
-	weights::SimpleDispatchInfo,
+	weights::{SimpleDispatchInfo, Weight, WeighData, ClassifyDispatch, PaysFee, DispatchClass},
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
 
@@ -104,7 +121,7 @@ pub trait Trait: frame_system::Trait {
 
 	/// Maximum number of additional fields that may be stored in an ID. Needed to bound the I/O
 	/// required to access an identity, but can be pretty high.
-	type MaxAdditionalFields: Get<u32>;
+	type MaxAdditionalFields: Get<u32> + Clone + Eq + Debug;
 
 	/// What to do with slashed funds.
 	type Slashed: OnUnbalanced<NegativeImbalanceOf<Self>>;
@@ -114,6 +131,16 @@ pub trait Trait: frame_system::Trait {
 
 	/// The origin which may add or remove registrars. Root can always do this.
 	type RegistrarOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Whether raw display names must be unique (ignoring ASCII case).
+	///
+	/// An account can be looked up by its display name either way. If names are not unique, the
+	/// lookup gives the last account to have set the name.
+	///
+	/// NOTE: Display names are indexed whether or not this is enabled, but those of identities
+	/// set before the index was introduced must be added with `reindex_identities` before enabling
+	/// it.
+	type UniqueDisplayNames: Get<bool>;
 }
 
 /// Either underlying data blob if it is at most 32 bytes, or a hash of it. If the data is greater
@@ -275,12 +302,14 @@ impl Decode for IdentityFields {
 ///
 /// NOTE: This should be stored at the end of the storage item to facilitate the addition of extra
 /// fields in a backwards compatible way through a specialized `Decode` impl.
+///
+/// `FieldLimit` bounds the number of additional fields.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
 #[cfg_attr(test, derive(Default))]
-pub struct IdentityInfo {
+pub struct IdentityInfo<FieldLimit: Get<u32>> {
 	/// Additional fields of the identity that are not catered for with the struct's explicit
 	/// fields.
-	pub additional: Vec<(Data, Data)>,
+	pub additional: BoundedVec<(Data, Data), FieldLimit>,
 
 	/// A reasonable display name for the controller of the account. This should be whatever it is
 	/// that it is typically known as and should not be confusable with other entities, given
@@ -329,7 +358,8 @@ pub struct IdentityInfo {
 /// backwards compatible way through a specialized `Decode` impl.
 #[derive(Clone, Encode, Eq, PartialEq, RuntimeDebug)]
 pub struct Registration<
-	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq
+	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq,
+	FieldLimit: Get<u32>,
 > {
 	/// Judgements from the registrars on this identity. Stored ordered by `RegistrarIndex`. There
 	/// may be only a single judgement from each registrar.
@@ -339,12 +369,13 @@ pub struct Registration<
 	pub deposit: Balance,
 
 	/// Information on the identity.
-	pub info: IdentityInfo,
+	pub info: IdentityInfo<FieldLimit>,
 }
 
 impl <
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq + Zero + Add,
-> Registration<Balance> {
+	FieldLimit: Get<u32>,
+> Registration<Balance, FieldLimit> {
 	fn total_deposit(&self) -> Balance {
 		self.deposit + self.judgements.iter()
 			.map(|(_, ref j)| if let Judgement::FeePaid(fee) = j { *fee } else { Zero::zero() })
@@ -354,7 +385,8 @@ impl <
 
 impl<
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq,
-> Decode for Registration<Balance> {
+	FieldLimit: Get<u32>,
+> Decode for Registration<Balance, FieldLimit> {
 	fn decode<I: codec::Input>(input: &mut I) -> sp_std::result::Result<Self, codec::Error> {
 		let (judgements, deposit, info) = Decode::decode(&mut AppendZerosInput::new(input))?;
 		Ok(Self { judgements, deposit, info })
@@ -382,7 +414,7 @@ decl_storage! {
 	trait Store for Module<T: Trait> as Sudo {
 		/// Information that is pertinent to identify the entity behind an account.
 		pub IdentityOf get(fn identity):
-			map hasher(blake2_256) T::AccountId => Option<Registration<BalanceOf<T>>>;
+			map hasher(blake2_256) T::AccountId => Option<Registration<BalanceOf<T>, T::MaxAdditionalFields>>;
 
 		/// The super-identity of an alternative "sub" identity together with its name, within that
 		/// context. If the account is not some other account's sub-identity, then just `None`.
@@ -400,6 +432,17 @@ decl_storage! {
 		///
 		/// The index into this can be cast to `RegistrarIndex` to get a valid value.
 		pub Registrars get(fn registrars): Vec<Option<RegistrarInfo<BalanceOf<T>, T::AccountId>>>;
+
+		/// The judgement requests which a registrar has yet to act upon, together with the fee
+		/// reserved for each of them. Mirrors the `FeePaid` judgements in `IdentityOf`; those
+		/// requested before this index was introduced are added with `reindex_identities`.
+		pub PendingJudgements:
+			double_map hasher(twox_64_concat) RegistrarIndex, hasher(twox_64_concat) T::AccountId
+			=> Option<BalanceOf<T>>;
+
+		/// The account which goes by a display name, keyed by the lower-cased raw name. Those set
+		/// before this index was introduced are added with `reindex_identities`.
+		pub AccountOfDisplay: map hasher(blake2_256) Vec<u8> => Option<T::AccountId>;
 	}
 }
 
@@ -419,6 +462,13 @@ decl_event!(
 		JudgementGiven(AccountId, RegistrarIndex),
 		/// A registrar was added.
 		RegistrarAdded(RegistrarIndex),
+		/// A sub-identity was added to an identity and the deposit paid.
+		SubIdentityAdded(AccountId, AccountId, Balance),
+		/// A sub-identity was removed from an identity and the deposit freed.
+		SubIdentityRemoved(AccountId, AccountId, Balance),
+		/// A sub-identity was cleared, and the given deposit repatriated from the main identity
+		/// account to the sub-identity account.
+		SubIdentityRevoked(AccountId, AccountId, Balance),
 	}
 );
 
@@ -447,9 +497,38 @@ decl_error! {
 		InvalidIndex,
 		/// The target is invalid.
 		InvalidTarget,
-		/// Too many additional fields.
+		/// Too many additional fields. No longer returned, as the fields are bounded when decoded.
 		TooManyFields,
+		/// Account ID is already named.
+		AlreadyClaimed,
+		/// Sender is not a sub-account.
+		NotSub,
+		/// Sub-account isn't owned by sender.
+		NotOwned,
+		/// The display name is already taken by another account.
+		DisplayNameTaken,
+	}
 }
+
+/// The weight of a call going through a list of accounts, linear in the length of the list.
+struct PerAccount<AccountId>(Weight, sp_std::marker::PhantomData<AccountId>);
+impl<AccountId> PerAccount<AccountId> {
+	fn new(weight: Weight) -> Self { Self(weight, Default::default()) }
+}
+impl<AccountId> WeighData<(&Vec<AccountId>,)> for PerAccount<AccountId> {
+	fn weigh_data(&self, (accounts,): (&Vec<AccountId>,)) -> Weight {
+		self.0.saturating_mul(accounts.len() as Weight).saturating_add(10_000)
+	}
+}
+impl<AccountId> ClassifyDispatch<(&Vec<AccountId>,)> for PerAccount<AccountId> {
+	fn classify_dispatch(&self, _: (&Vec<AccountId>,)) -> DispatchClass {
+		DispatchClass::Normal
+	}
+}
+impl<AccountId> PaysFee<(&Vec<AccountId>,)> for PerAccount<AccountId> {
+	fn pays_fee(&self, _: (&Vec<AccountId>,)) -> bool {
+		true
+	}
 }
 
 decl_module! {
@@ -505,20 +584,28 @@ decl_module! {
 		/// - One event.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
-		fn set_identity(origin, info: IdentityInfo) {
+		fn set_identity(origin, info: IdentityInfo<T::MaxAdditionalFields>) {
 			let sender = ensure_signed(origin)?;
 			let extra_fields = info.additional.len() as u32;
-			ensure!(extra_fields <= T::MaxAdditionalFields::get(), Error::<T>::TooManyFields);
 			let fd = <BalanceOf<T>>::from(extra_fields) * T::FieldDeposit::get();
+			let display = Self::display_key(&info.display);
+			if T::UniqueDisplayNames::get() {
+				if let Some(ref name) = display {
+					ensure!(
+						<AccountOfDisplay<T>>::get(name).map_or(true, |owner| owner == sender),
+						Error::<T>::DisplayNameTaken,
+					);
+				}
+			}
 
-			let mut id = match <IdentityOf<T>>::get(&sender) {
+			let (mut id, old_display) = match <IdentityOf<T>>::get(&sender) {
 				Some(mut id) => {
 					// Only keep non-positive judgements.
 					id.judgements.retain(|j| j.1.is_sticky());
-					id.info = info;
-					id
+					let old_info = sp_std::mem::replace(&mut id.info, info);
+					(id, Some(old_info.display))
 				}
-				None => Registration { info, judgements: Vec::new(), deposit: Zero::zero() },
+				None => (Registration { info, judgements: Vec::new(), deposit: Zero::zero() }, None),
 			};
 
 			let old_deposit = id.deposit;
@@ -530,7 +617,13 @@ decl_module! {
 				let _ = T::Currency::unreserve(&sender, old_deposit - id.deposit);
 			}
 
+			if let Some(old_display) = old_display {
+				Self::remove_display(&sender, &old_display);
+			}
 			<IdentityOf<T>>::insert(&sender, id);
+			if let Some(name) = display {
+				<AccountOfDisplay<T>>::insert(name, &sender);
+			}
 			Self::deposit_event(RawEvent::IdentitySet(sender));
 		}
 
@@ -582,6 +675,121 @@ decl_module! {
 			}
 		}
 
+		/// Add the given account to the sender's subs.
+		///
+		/// Payment: `SubAccountDeposit` is reserved for the sub-account.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `sub`: the account to add; it must not be the sub-account of any identity yet.
+		/// - `data`: the name of the sub-account.
+		///
+		/// Emits `SubIdentityAdded` if successful.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` subs-count (hard- and deposit-bounded).
+		/// - One balance-reserve operation.
+		/// - Two storage mutations; one storage-exists.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
+		fn add_sub(origin, sub: <T::Lookup as StaticLookup>::Source, data: Data) {
+			let sender = ensure_signed(origin)?;
+			let sub = T::Lookup::lookup(sub)?;
+			ensure!(<IdentityOf<T>>::contains_key(&sender), Error::<T>::NoIdentity);
+			ensure!(!<SuperOf<T>>::contains_key(&sub), Error::<T>::AlreadyClaimed);
+
+			let (subs_deposit, mut sub_ids) = <SubsOf<T>>::get(&sender);
+			ensure!(sub_ids.len() < T::MaxSubAccounts::get() as usize, Error::<T>::TooManySubAccounts);
+			let deposit = T::SubAccountDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+
+			<SuperOf<T>>::insert(&sub, (sender.clone(), data));
+			sub_ids.push(sub.clone());
+			<SubsOf<T>>::insert(&sender, (subs_deposit + deposit, sub_ids));
+
+			Self::deposit_event(RawEvent::SubIdentityAdded(sub, sender, deposit));
+		}
+
+		/// Alter the associated name of the given sub-account.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// sub identity of `sub`.
+		///
+		/// - `sub`: the sub-account to rename.
+		/// - `data`: the new name of the sub-account.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One storage read and one storage mutation.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+		fn rename_sub(origin, sub: <T::Lookup as StaticLookup>::Source, data: Data) {
+			let sender = ensure_signed(origin)?;
+			let sub = T::Lookup::lookup(sub)?;
+			ensure!(<SuperOf<T>>::get(&sub).map_or(false, |x| x.0 == sender), Error::<T>::NotOwned);
+			<SuperOf<T>>::insert(&sub, (sender, data));
+		}
+
+		/// Remove the given account from the sender's subs.
+		///
+		/// Payment: The deposit reserved for the sub-account is returned to the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// sub identity of `sub`.
+		///
+		/// - `sub`: the sub-account to remove.
+		///
+		/// Emits `SubIdentityRemoved` if successful.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` subs-count (hard- and deposit-bounded).
+		/// - One balance-unreserve operation.
+		/// - Two storage mutations.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
+		fn remove_sub(origin, sub: <T::Lookup as StaticLookup>::Source) {
+			let sender = ensure_signed(origin)?;
+			ensure!(<IdentityOf<T>>::contains_key(&sender), Error::<T>::NoIdentity);
+			let sub = T::Lookup::lookup(sub)?;
+			ensure!(<SuperOf<T>>::get(&sub).map_or(false, |x| x.0 == sender), Error::<T>::NotOwned);
+
+			<SuperOf<T>>::remove(&sub);
+			let deposit = Self::release_sub(&sender, &sub);
+			let _ = T::Currency::unreserve(&sender, deposit);
+
+			Self::deposit_event(RawEvent::SubIdentityRemoved(sub, sender, deposit));
+		}
+
+		/// Remove the sender as a sub-account.
+		///
+		/// Payment: Balance reserved by the super-account for the sender is repatriated to the
+		/// sender, as compensation for having been claimed without consent.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must be a registered
+		/// sub-account of some identity.
+		///
+		/// Emits `SubIdentityRevoked` if successful.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` subs-count (hard- and deposit-bounded).
+		/// - One balance-repatriate operation.
+		/// - Two storage mutations.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
+		fn quit_sub(origin) {
+			let sender = ensure_signed(origin)?;
+			let (sup, _) = <SuperOf<T>>::take(&sender).ok_or(Error::<T>::NotSub)?;
+
+			let deposit = Self::release_sub(&sup, &sender);
+			let _ = T::Currency::repatriate_reserved(&sup, &sender, deposit);
+
+			Self::deposit_event(RawEvent::SubIdentityRevoked(sender, sup, deposit));
+		}
+
 		/// Clear an account's identity info and all sub-account and return all deposits.
 		///
 		/// Payment: All reserved balances on the account are returned.
@@ -602,11 +810,13 @@ decl_module! {
 			let sender = ensure_signed(origin)?;
 
 			let (subs_deposit, sub_ids) = <SubsOf<T>>::take(&sender);
-			let deposit = <IdentityOf<T>>::take(&sender).ok_or(Error::<T>::NotNamed)?.total_deposit()
-				+ subs_deposit;
+			let id = <IdentityOf<T>>::take(&sender).ok_or(Error::<T>::NotNamed)?;
+			let deposit = id.total_deposit() + subs_deposit;
 			for sub in sub_ids.iter() {
 				<SuperOf<T>>::remove(sub);
 			}
+			Self::remove_pending_judgements(&sender, &id);
+			Self::remove_display(&sender, &id.info.display);
 
 			let _ = T::Currency::unreserve(&sender, deposit.clone());
 
@@ -661,6 +871,7 @@ decl_module! {
 			T::Currency::reserve(&sender, registrar.fee)?;
 
 			<IdentityOf<T>>::insert(&sender, id);
+			<PendingJudgements<T>>::insert(reg_index, &sender, registrar.fee);
 
 			Self::deposit_event(RawEvent::JudgementRequested(sender, reg_index));
 		}
@@ -697,6 +908,7 @@ decl_module! {
 
 			let _ = T::Currency::unreserve(&sender, fee);
 			<IdentityOf<T>>::insert(&sender, id);
+			<PendingJudgements<T>>::remove(reg_index, &sender);
 
 			Self::deposit_event(RawEvent::JudgementUnrequested(sender, reg_index));
 		}
@@ -822,6 +1034,7 @@ decl_module! {
 				Ok(position) => {
 					if let Judgement::FeePaid(fee) = id.judgements[position].1 {
 						let _ = T::Currency::repatriate_reserved(&target, &sender, fee);
+						<PendingJudgements<T>>::remove(reg_index, &target);
 					}
 					id.judgements[position] = item
 				}
@@ -860,16 +1073,121 @@ decl_module! {
 			let target = T::Lookup::lookup(target)?;
 			// Grab their deposit (and check that they have one).
 			let (subs_deposit, sub_ids) = <SubsOf<T>>::take(&target);
-			let deposit = <IdentityOf<T>>::take(&target).ok_or(Error::<T>::NotNamed)?.total_deposit()
-				+ subs_deposit;
+			let id = <IdentityOf<T>>::take(&target).ok_or(Error::<T>::NotNamed)?;
+			let deposit = id.total_deposit() + subs_deposit;
 			for sub in sub_ids.iter() {
 				<SuperOf<T>>::remove(sub);
 			}
+			Self::remove_pending_judgements(&target, &id);
+			Self::remove_display(&target, &id.info.display);
 			// Slash their deposit from them.
 			T::Slashed::on_unbalanced(T::Currency::slash_reserved(&target, deposit).0);
 
 			Self::deposit_event(RawEvent::IdentityKilled(target, deposit));
 		}
+
+		/// Add the judgement requests and display names of existing identities to
+		/// `PendingJudgements` and `AccountOfDisplay`.
+		///
+		/// Identities are keyed by a hash of their account, so the accounts whose identities were
+		/// set before the index was introduced can't be found on-chain and have to be given. An
+		/// account without an identity is skipped and indexing an account twice is harmless. A
+		/// display name which is already taken stays with the account it is indexed for.
+		///
+		/// The dispatch origin for this call must be _Root_ or match `T::ForceOrigin`.
+		///
+		/// - `accounts`: the accounts whose identities are indexed.
+		///
+		/// # <weight>
+		/// - `O(A * R)` where `A` is the number of accounts.
+		/// - `2 * A` storage reads and at most `A * (R + 1)` storage writes.
+		/// # </weight>
+		#[weight = PerAccount::<T::AccountId>::new(50_000)]
+		fn reindex_identities(origin, accounts: Vec<T::AccountId>) {
+			T::ForceOrigin::try_origin(origin)
+				.map(|_| ())
+				.or_else(ensure_root)?;
+
+			for who in accounts.iter() {
+				if let Some(id) = <IdentityOf<T>>::get(who) {
+					for (reg_index, judgement) in id.judgements.iter() {
+						if let Judgement::FeePaid(fee) = judgement {
+							<PendingJudgements<T>>::insert(reg_index, who, fee);
+						}
+					}
+					if let Some(name) = Self::display_key(&id.info.display) {
+						if !<AccountOfDisplay<T>>::contains_key(&name) {
+							<AccountOfDisplay<T>>::insert(name, who);
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The accounts which have requested a judgement from the registrar `reg_index` and have yet to
+	/// receive it, together with the fee each of them has reserved for it.
+	pub fn pending_judgements(reg_index: RegistrarIndex) -> Vec<(T::AccountId, BalanceOf<T>)> {
+		<PendingJudgements<T>>::iter_prefix(reg_index).collect()
+	}
+
+	/// The account which goes by the display name `name`.
+	///
+	/// If display names are not unique, this is the last account to have set `name` as its display
+	/// name, as long as it still goes by it.
+	pub fn account_by_display(name: &[u8]) -> Option<T::AccountId> {
+		Self::display_key(&Data::Raw(name.to_vec())).and_then(<AccountOfDisplay<T>>::get)
+	}
+
+	/// The key under which the account with display name `display` is indexed, if `display` is
+	/// given in the raw.
+	fn display_key(display: &Data) -> Option<Vec<u8>> {
+		match display {
+			Data::Raw(ref name) if !name.is_empty() =>
+				Some(name.to_ascii_lowercase()),
+			_ => None,
+		}
+	}
+
+	/// Remove the display name of `who` from the index.
+	fn remove_display(who: &T::AccountId, display: &Data) {
+		if let Some(name) = Self::display_key(display) {
+			if <AccountOfDisplay<T>>::get(&name).as_ref() == Some(who) {
+				<AccountOfDisplay<T>>::remove(name);
+			}
+		}
+	}
+
+	/// Remove `sub` from the subs of `sup`, returning the part of the deposit that was held for it.
+	fn release_sub(sup: &T::AccountId, sub: &T::AccountId) -> BalanceOf<T> {
+		let (subs_deposit, mut sub_ids) = <SubsOf<T>>::get(sup);
+		sub_ids.retain(|x| x != sub);
+		// release everything with the last sub, in case the deposit per sub has changed since.
+		let deposit = if sub_ids.is_empty() {
+			subs_deposit
+		} else {
+			T::SubAccountDeposit::get().min(subs_deposit)
+		};
+		if sub_ids.is_empty() {
+			<SubsOf<T>>::remove(sup);
+		} else {
+			<SubsOf<T>>::insert(sup, (subs_deposit - deposit, sub_ids));
+		}
+		deposit
+	}
+
+	/// Remove the judgement requests of a registration which is going away from the index.
+	fn remove_pending_judgements(
+		who: &T::AccountId,
+		id: &Registration<BalanceOf<T>, T::MaxAdditionalFields>,
+	) {
+		for (reg_index, judgement) in id.judgements.iter() {
+			if judgement.has_deposit() {
+				<PendingJudgements<T>>::remove(reg_index, who);
+			}
+		}
 	}
 }
 
@@ -877,12 +1195,13 @@ decl_module! {
 mod tests {
 	use super::*;
 
+	use sp_std::convert::TryInto;
 	use sp_runtime::traits::BadOrigin;
 	use frame_support::{
 		assert_ok, assert_noop, impl_outer_origin, parameter_types, weights::Weight,
 		ord_parameter_types
 	};
-	use sp_core::H256;
+	use sp_core::{H256, offchain::{OffchainExt, testing}};
 	use frame_system::EnsureSignedBy;
 	// The testing primitives are very useful for avoiding having to work with signatures
 	// or public keys. `u64` is used as the `AccountId` and no `Signature`s are required.
@@ -943,6 +1262,7 @@ mod tests {
 		pub const SubAccountDeposit: u64 = 10;
 		pub const MaxSubAccounts: u32 = 2;
 		pub const MaxAdditionalFields: u32 = 2;
+		pub const UniqueDisplayNames: bool = true;
 	}
 	ord_parameter_types! {
 		pub const One: u64 = 1;
@@ -959,6 +1279,7 @@ mod tests {
 		type MaxAdditionalFields = MaxAdditionalFields;
 		type RegistrarOrigin = EnsureSignedBy<One, u64>;
 		type ForceOrigin = EnsureSignedBy<Two, u64>;
+		type UniqueDisplayNames = UniqueDisplayNames;
 	}
 	type System = frame_system::Module<Test>;
 	type Balances = pallet_balances::Module<Test>;
@@ -982,7 +1303,7 @@ mod tests {
 		t.into()
 	}
 
-	fn ten() -> IdentityInfo {
+	fn ten() -> IdentityInfo<MaxAdditionalFields> {
 		IdentityInfo {
 			display: Data::Raw(b"ten".to_vec()),
 			legal: Data::Raw(b"The Right Ordinal Ten, Esq.".to_vec()),
@@ -1019,13 +1340,14 @@ mod tests {
 			assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
 			assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
 			let mut three_fields = ten();
-			three_fields.additional.push(Default::default());
-			three_fields.additional.push(Default::default());
-			three_fields.additional.push(Default::default());
-			assert_noop!(
-				Identity::set_identity(Origin::signed(10), three_fields),
-				Error::<Test>::TooManyFields
-			);
+			assert_ok!(three_fields.additional.try_push(Default::default()));
+			assert_ok!(three_fields.additional.try_push(Default::default()));
+			assert!(three_fields.additional.try_push(Default::default()).is_err());
+			// Nor can an identity with too many additional fields be decoded: replace the empty
+			// `additional` of `ten()` (a single byte) with three fields.
+			let mut encoded = vec![(Data::None, Data::None); 3].encode();
+			encoded.extend(ten().encode().into_iter().skip(1));
+			assert!(IdentityInfo::<MaxAdditionalFields>::decode(&mut &encoded[..]).is_err());
 			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
 			assert_eq!(Identity::identity(10).unwrap().info, ten());
 			assert_eq!(Balances::free_balance(10), 90);
@@ -1128,6 +1450,112 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn adding_and_removing_subaccounts_should_work() {
+		new_test_ext().execute_with(|| {
+			let data = |x| Data::Raw(vec![x; 1]);
+			assert_noop!(Identity::add_sub(Origin::signed(10), 20, data(1)), Error::<Test>::NoIdentity);
+
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::add_sub(Origin::signed(10), 20, data(1)));
+			assert_eq!(Balances::free_balance(10), 80);
+			assert_eq!(Identity::subs(10), (10, vec![20]));
+			assert_eq!(Identity::super_of(20), Some((10, data(1))));
+			assert_noop!(Identity::add_sub(Origin::signed(10), 20, data(2)), Error::<Test>::AlreadyClaimed);
+
+			assert_ok!(Identity::add_sub(Origin::signed(10), 30, data(2)));
+			assert_eq!(Balances::free_balance(10), 70);
+			assert_eq!(Identity::subs(10), (20, vec![20, 30]));
+			assert_noop!(
+				Identity::add_sub(Origin::signed(10), 1, data(3)),
+				Error::<Test>::TooManySubAccounts
+			);
+
+			assert_noop!(Identity::rename_sub(Origin::signed(30), 20, data(3)), Error::<Test>::NotOwned);
+			assert_ok!(Identity::rename_sub(Origin::signed(10), 20, data(3)));
+			assert_eq!(Identity::super_of(20), Some((10, data(3))));
+
+			assert_ok!(Identity::remove_sub(Origin::signed(10), 20));
+			assert_eq!(Balances::free_balance(10), 80);
+			assert_eq!(Identity::subs(10), (10, vec![30]));
+			assert_eq!(Identity::super_of(20), None);
+			assert_noop!(Identity::remove_sub(Origin::signed(10), 20), Error::<Test>::NotOwned);
+
+			// The sub-account is paid the deposit held for it when quitting.
+			assert_ok!(Identity::quit_sub(Origin::signed(30)));
+			assert_eq!(Balances::free_balance(30), 110);
+			assert_eq!(Balances::free_balance(10), 80);
+			assert_eq!(Balances::reserved_balance(10), 10);
+			assert_eq!(Identity::subs(10), (0, vec![]));
+			assert_eq!(Identity::super_of(30), None);
+			assert_noop!(Identity::quit_sub(Origin::signed(30)), Error::<Test>::NotSub);
+		});
+	}
+
+	#[test]
+	fn display_names_should_be_unique() {
+		new_test_ext().execute_with(|| {
+			let named = |name: &[u8]| IdentityInfo {
+				display: Data::Raw(name.to_vec()),
+				.. Default::default()
+			};
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_eq!(Identity::account_by_display(b"ten"), Some(10));
+			assert_eq!(Identity::account_by_display(b"TEN"), Some(10));
+			assert_noop!(
+				Identity::set_identity(Origin::signed(20), named(b"Ten")),
+				Error::<Test>::DisplayNameTaken
+			);
+			// Setting the same name again is fine.
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+
+			// Renaming frees the old name.
+			assert_ok!(Identity::set_identity(Origin::signed(10), named(b"eleven")));
+			assert_eq!(Identity::account_by_display(b"ten"), None);
+			assert_eq!(Identity::account_by_display(b"eleven"), Some(10));
+			assert_ok!(Identity::set_identity(Origin::signed(20), named(b"Ten")));
+			assert_eq!(Identity::account_by_display(b"ten"), Some(20));
+
+			// Names which are only given as a hash are not indexed.
+			assert_ok!(Identity::set_identity(Origin::signed(30), named(b"")));
+			assert_ok!(Identity::set_identity(Origin::signed(30), IdentityInfo {
+				display: Data::BlakeTwo256([0; 32]),
+				.. Default::default()
+			}));
+			assert_eq!(Identity::account_by_display(b""), None);
+
+			assert_ok!(Identity::clear_identity(Origin::signed(20)));
+			assert_eq!(Identity::account_by_display(b"ten"), None);
+			assert_ok!(Identity::kill_identity(Origin::ROOT, 10));
+			assert_eq!(Identity::account_by_display(b"eleven"), None);
+		});
+	}
+
+	#[test]
+	fn failed_deposit_should_keep_display_name() {
+		new_test_ext().execute_with(|| {
+			let mut one = IdentityInfo { display: Data::Raw(b"one".to_vec()), .. Default::default() };
+			assert_ok!(Identity::set_identity(Origin::signed(1), one.clone()));
+			assert_eq!(Balances::free_balance(1), 0);
+
+			// The deposit of the extra field cannot be reserved.
+			one.display = Data::Raw(b"uno".to_vec());
+			assert_ok!(one.additional.try_push(Default::default()));
+			assert_noop!(
+				Identity::set_identity(Origin::signed(1), one),
+				pallet_balances::Error::<Test, _>::InsufficientBalance
+			);
+			assert_eq!(Identity::account_by_display(b"one"), Some(1));
+			assert_noop!(
+				Identity::set_identity(Origin::signed(2), IdentityInfo {
+					display: Data::Raw(b"One".to_vec()),
+					.. Default::default()
+				}),
+				Error::<Test>::DisplayNameTaken
+			);
+		});
+	}
+
 	#[test]
 	fn clearing_account_should_remove_subaccounts_and_refund() {
 		new_test_ext().execute_with(|| {
@@ -1206,7 +1634,7 @@ mod tests {
 				additional: vec![
 					(Data::Raw(b"number".to_vec()), Data::Raw(10u32.encode())),
 					(Data::Raw(b"text".to_vec()), Data::Raw(b"10".to_vec())),
-				], .. Default::default()
+				].try_into().unwrap(), .. Default::default()
 			}));
 			assert_eq!(Balances::free_balance(10), 70);
 		});
@@ -1224,4 +1652,226 @@ mod tests {
 			assert_ok!(Identity::set_account_id(Origin::signed(4), 0, 3));
 		});
 	}
+
+	#[test]
+	fn pending_judgements_should_be_indexed() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+			assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 4));
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::set_identity(Origin::signed(20), IdentityInfo {
+				display: Data::Raw(b"twenty".to_vec()),
+				.. ten()
+			}));
+
+			assert_ok!(Identity::request_judgement(Origin::signed(10), 0, 10));
+			assert_ok!(Identity::request_judgement(Origin::signed(20), 0, 10));
+			assert_ok!(Identity::request_judgement(Origin::signed(20), 1, 0));
+			let mut pending = Identity::pending_judgements(0);
+			pending.sort();
+			assert_eq!(pending, vec![(10, 10), (20, 10)]);
+			assert_eq!(Identity::pending_judgements(1), vec![(20, 0)]);
+
+			// Resetting the identity keeps the requests.
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_eq!(Identity::pending_judgements(0).len(), 2);
+
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
+			assert_eq!(Identity::pending_judgements(0), vec![(20, 10)]);
+			assert_ok!(Identity::cancel_request(Origin::signed(20), 0));
+			assert_eq!(Identity::pending_judgements(0), vec![]);
+
+			assert_ok!(Identity::clear_identity(Origin::signed(20)));
+			assert_eq!(Identity::pending_judgements(1), vec![]);
+
+			assert_ok!(Identity::request_judgement(Origin::signed(10), 1, 0));
+			assert_ok!(Identity::kill_identity(Origin::ROOT, 10));
+			assert_eq!(Identity::pending_judgements(1), vec![]);
+		});
+	}
+
+	#[test]
+	fn reindexing_should_restore_pending_judgements() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+			assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::request_judgement(Origin::signed(10), 0, 10));
+			// Judgements requested before the index was introduced.
+			<PendingJudgements<Test>>::remove_all();
+			assert_eq!(Identity::pending_judgements(0), vec![]);
+
+			assert_noop!(Identity::reindex_identities(Origin::signed(1), vec![10]), BadOrigin);
+			assert_ok!(Identity::reindex_identities(Origin::signed(2), vec![10, 20]));
+			assert_eq!(Identity::pending_judgements(0), vec![(10, 10)]);
+			assert_ok!(Identity::reindex_identities(Origin::ROOT, vec![10]));
+			assert_eq!(Identity::pending_judgements(0), vec![(10, 10)]);
+
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
+			assert_ok!(Identity::reindex_identities(Origin::ROOT, vec![10]));
+			assert_eq!(Identity::pending_judgements(0), vec![]);
+		});
+	}
+
+	#[test]
+	fn reindexing_should_restore_display_names() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::set_identity(Origin::signed(20), IdentityInfo {
+				display: Data::Raw(b"twenty".to_vec()),
+				.. Default::default()
+			}));
+			// Display names set before the index was introduced; two of them clash.
+			<AccountOfDisplay<Test>>::remove_all();
+			<IdentityOf<Test>>::insert(30, Registration {
+				judgements: vec![],
+				deposit: 10,
+				info: IdentityInfo { display: Data::Raw(b"TEN".to_vec()), .. Default::default() },
+			});
+			assert_eq!(Identity::account_by_display(b"ten"), None);
+
+			assert_ok!(Identity::reindex_identities(Origin::ROOT, vec![10, 20, 30]));
+			assert_eq!(Identity::account_by_display(b"ten"), Some(10));
+			assert_eq!(Identity::account_by_display(b"twenty"), Some(20));
+			assert_ok!(Identity::reindex_identities(Origin::ROOT, vec![30]));
+			assert_eq!(Identity::account_by_display(b"ten"), Some(10));
+		});
+	}
+
+	#[test]
+	fn offchain_verification_should_work() {
+		let mut t = new_test_ext();
+		let (offchain, state) = testing::TestOffchainExt::new();
+		t.register_extension(OffchainExt::new(offchain));
+		let sources = offchain::ProofSources {
+			email: "http://localhost:9933/email",
+			twitter: "http://localhost:9933/twitter",
+		};
+		t.execute_with(|| {
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+			assert_ok!(Identity::set_fee(Origin::signed(3), 0, 10));
+			assert_eq!(
+				offchain::verify::<Test>(&10, 0, sources, None),
+				Err(offchain::VerifyError::NoIdentity),
+			);
+			assert_eq!(
+				offchain::verify::<Test>(&10, 1, sources, None),
+				Err(offchain::VerifyError::InvalidIndex),
+			);
+
+			assert_ok!(Identity::set_identity(Origin::signed(10), IdentityInfo {
+				web: Data::Raw(b"ten.example".to_vec()),
+				twitter: Data::Raw(b"@ten".to_vec()),
+				.. ten()
+			}));
+			assert_ok!(Identity::request_judgement(Origin::signed(10), 0, 10));
+			assert_eq!(offchain::proof_of(&10u64), b"0x0a00000000000000".to_vec());
+
+			// With no fields set by the registrar, all claimed fields are checked.
+			state.write().expect_request(0, testing::PendingRequest {
+				method: "GET".into(),
+				uri: "https://ten.example/.well-known/substrate-identity".into(),
+				sent: true,
+				response: Some(b"identity: 0x0a00000000000000".to_vec()),
+				..Default::default()
+			});
+			state.write().expect_request(1, testing::PendingRequest {
+				method: "GET".into(),
+				uri: "http://localhost:9933/twitter/ten".into(),
+				sent: true,
+				response: Some(b"0x1400000000000000".to_vec()),
+				..Default::default()
+			});
+			let checks = offchain::verify::<Test>(&10, 0, sources, None).unwrap();
+			assert_eq!(checks, vec![(IdentityField::Web, true), (IdentityField::Twitter, false)]);
+			assert_eq!(offchain::judgement::<u64>(&checks), Some(Judgement::LowQuality));
+
+			// A field the registrar cares about but which isn't claimed can't be proven.
+			let fields = IdentityFields(IdentityField::Web | IdentityField::Email);
+			assert_ok!(Identity::set_fields(Origin::signed(3), 0, fields));
+			state.write().expect_request(0, testing::PendingRequest {
+				method: "GET".into(),
+				uri: "https://ten.example/.well-known/substrate-identity".into(),
+				sent: true,
+				response: Some(b"0x0a00000000000000".to_vec()),
+				..Default::default()
+			});
+			let checks = offchain::verify::<Test>(&10, 0, sources, None).unwrap();
+			assert_eq!(checks, vec![(IdentityField::Web, true), (IdentityField::Email, false)]);
+
+			let fields = IdentityFields(IdentityField::Web.into());
+			assert_ok!(Identity::set_fields(Origin::signed(3), 0, fields));
+			state.write().expect_request(0, testing::PendingRequest {
+				method: "GET".into(),
+				uri: "https://ten.example/.well-known/substrate-identity".into(),
+				sent: true,
+				response: Some(b"0x0a00000000000000".to_vec()),
+				..Default::default()
+			});
+			let checks = offchain::verify::<Test>(&10, 0, sources, None).unwrap();
+			let judgement = offchain::judgement(&checks).unwrap();
+			assert_eq!(judgement, Judgement::Reasonable);
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, judgement));
+			assert_eq!(Balances::free_balance(3), 20);
+			assert_eq!(Identity::pending_judgements(0), vec![]);
+		});
+	}
+
+	#[test]
+	fn offchain_verification_should_not_follow_reserved_characters() {
+		let mut t = new_test_ext();
+		let (offchain, state) = testing::TestOffchainExt::new();
+		t.register_extension(OffchainExt::new(offchain));
+		let sources = offchain::ProofSources {
+			email: "http://localhost:9933/email",
+			twitter: "http://localhost:9933/twitter",
+		};
+		t.execute_with(|| {
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+
+			// Anything but a bare hostname isn't fetched at all.
+			let webs: &[&[u8]] =
+				&[b"evil.example/ten", b"ten@evil.example", b"evil.example?", b"-ten.example"];
+			for web in webs {
+				assert_ok!(Identity::set_identity(Origin::signed(10), IdentityInfo {
+					web: Data::Raw(web.to_vec()),
+					.. Default::default()
+				}));
+				let checks = offchain::verify::<Test>(&10, 0, sources, None).unwrap();
+				assert_eq!(checks, vec![(IdentityField::Web, false)]);
+			}
+
+			// Nor is a handle which would resolve to another path.
+			assert_ok!(Identity::set_identity(Origin::signed(10), IdentityInfo {
+				twitter: Data::Raw(b"@..".to_vec()),
+				.. Default::default()
+			}));
+			let checks = offchain::verify::<Test>(&10, 0, sources, None).unwrap();
+			assert_eq!(checks, vec![(IdentityField::Twitter, false)]);
+
+			// Reserved characters in email addresses and handles are percent-encoded.
+			assert_ok!(Identity::set_identity(Origin::signed(10), IdentityInfo {
+				email: Data::Raw(b"ten@x/../admin?#".to_vec()),
+				twitter: Data::Raw(b"@../ten".to_vec()),
+				.. Default::default()
+			}));
+			state.write().expect_request(0, testing::PendingRequest {
+				method: "GET".into(),
+				uri: "http://localhost:9933/email/ten%40x%2F..%2Fadmin%3F%23".into(),
+				sent: true,
+				response: Some(b"0x0a00000000000000".to_vec()),
+				..Default::default()
+			});
+			state.write().expect_request(1, testing::PendingRequest {
+				method: "GET".into(),
+				uri: "http://localhost:9933/twitter/..%2Ften".into(),
+				sent: true,
+				response: Some(b"0x0a00000000000000".to_vec()),
+				..Default::default()
+			});
+			let checks = offchain::verify::<Test>(&10, 0, sources, None).unwrap();
+			assert_eq!(checks, vec![(IdentityField::Email, true), (IdentityField::Twitter, true)]);
+		});
+	}
 }
@@ -0,0 +1,245 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offchain verification of the fields claimed in an identity.
+//!
+//! A registrar can call [`verify`] from an offchain worker to fetch the proofs published for the
+//! web, email and twitter fields of an identity, turn the outcome into a judgement with
+//! [`judgement`] and submit it through `provide_judgement`.
+//!
+//! A proof is any document containing the output of [`proof_of`] for the account which the
+//! identity belongs to. It is fetched from:
+//!
+//! - web: `https://<web><WEB_PROOF_PATH>`, where the website is claimed by its bare hostname;
+//! - email: `<ProofSources::email>/<email>`, as published by a service which has confirmed that
+//!   the account holder controls the address;
+//! - twitter: `<ProofSources::twitter>/<handle>`, likewise for the handle without its `@`.
+//!
+//! Email addresses and handles are percent-encoded. Claims which can't be turned into such a URL
+//! are never proven.
+//!
+//! [`verify`]: ./fn.verify.html
+//! [`judgement`]: ./fn.judgement.html
+//! [`proof_of`]: ./fn.proof_of.html
+
+use sp_std::{prelude::*, fmt::Debug, str};
+use codec::{Encode, Decode};
+use sp_core::offchain::{Timestamp, HttpError};
+use sp_runtime::{RuntimeDebug, offchain::http};
+use crate::{Trait, Module, Data, IdentityField, RegistrarIndex, Judgement};
+
+/// The path under a claimed website at which its proof is published.
+pub const WEB_PROOF_PATH: &str = "/.well-known/substrate-identity";
+
+/// The maximum number of bytes of a response which are searched for a proof.
+pub const MAX_PROOF_LEN: usize = 4096;
+
+/// The fields which can be verified by fetching a proof.
+const VERIFIABLE: [IdentityField; 3] = [IdentityField::Web, IdentityField::Email, IdentityField::Twitter];
+
+/// The services publishing the proofs of fields which can't be checked at their source.
+#[derive(Clone, Copy, RuntimeDebug)]
+pub struct ProofSources<'a> {
+	/// Base URL of the service attesting email addresses.
+	pub email: &'a str,
+	/// Base URL of the service attesting twitter handles.
+	pub twitter: &'a str,
+}
+
+/// An error encountered while verifying an identity.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum VerifyError {
+	/// The account has no identity.
+	NoIdentity,
+	/// There is no registrar at the given index.
+	InvalidIndex,
+	/// Not all proofs were fetched before the deadline.
+	DeadlineReached,
+	/// Fetching a proof failed.
+	Http(http::Error),
+}
+
+impl From<HttpError> for VerifyError {
+	fn from(e: HttpError) -> Self {
+		match e {
+			HttpError::DeadlineReached => VerifyError::DeadlineReached,
+			_ => VerifyError::Http(http::Error::IoError),
+		}
+	}
+}
+
+/// The proof that an account controls some claimed field: its SCALE-encoded ID in lower-case hex,
+/// prefixed with `0x`.
+pub fn proof_of<AccountId: Encode>(who: &AccountId) -> Vec<u8> {
+	const HEX: &[u8; 16] = b"0123456789abcdef";
+	let mut proof = b"0x".to_vec();
+	who.using_encoded(|encoded| for byte in encoded {
+		proof.push(HEX[(byte >> 4) as usize]);
+		proof.push(HEX[(byte & 0xf) as usize]);
+	});
+	proof
+}
+
+/// Fetch the proofs of the verifiable fields of `who`'s identity that the registrar `reg_index`
+/// cares about, or of all those which the identity claims if the registrar has not set any
+/// fields. Returns whether each of them is proven, in the order web, email, twitter.
+///
+/// A field which the identity leaves empty, or only gives the hash of, can't be proven. All
+/// requests are made at once and must finish by `deadline`, if given.
+pub fn verify<T: Trait>(
+	who: &T::AccountId,
+	reg_index: RegistrarIndex,
+	sources: ProofSources,
+	deadline: Option<Timestamp>,
+) -> Result<Vec<(IdentityField, bool)>, VerifyError> {
+	let registrar = <Module<T>>::registrars().get(reg_index as usize).cloned()
+		.and_then(|r| r)
+		.ok_or(VerifyError::InvalidIndex)?;
+	let info = <Module<T>>::identity(who).ok_or(VerifyError::NoIdentity)?.info;
+
+	let mut fields = Vec::new();
+	for &field in VERIFIABLE.iter() {
+		let claim = match field {
+			IdentityField::Web => &info.web,
+			IdentityField::Email => &info.email,
+			_ => &info.twitter,
+		};
+		let wanted = if registrar.fields.0.is_empty() {
+			*claim != Data::None
+		} else {
+			registrar.fields.0.contains(field)
+		};
+		if wanted {
+			fields.push((field, proof_url(field, claim, sources)));
+		}
+	}
+
+	let mut pending = Vec::new();
+	for (_, url) in fields.iter() {
+		if let Some(url) = url {
+			let url = str::from_utf8(url).expect("the URL is made up of `str`s only; qed");
+			let mut request = http::Request::get(url);
+			if let Some(deadline) = deadline {
+				request = request.deadline(deadline);
+			}
+			pending.push(request.send()?);
+		}
+	}
+
+	let proof = proof_of(who);
+	let mut responses = http::PendingRequest::try_wait_all(pending, deadline).into_iter();
+	let mut checks = Vec::with_capacity(fields.len());
+	for (field, url) in fields {
+		if url.is_none() {
+			checks.push((field, false));
+			continue
+		}
+		let response = responses.next()
+			.expect("one response is returned for each request; qed")
+			.map_err(|_| VerifyError::DeadlineReached)?
+			.map_err(VerifyError::Http)?;
+		let mut body = response.body();
+		body.deadline(deadline);
+		let content = body.by_ref().take(MAX_PROOF_LEN).collect::<Vec<_>>();
+		if let Some(e) = body.error() {
+			return Err(e.clone().into())
+		}
+		let proven = response.code == 200 &&
+			content.windows(proof.len()).any(|w| w == &proof[..]);
+		checks.push((field, proven));
+	}
+	Ok(checks)
+}
+
+/// The judgement warranted by the outcome of [`verify`]: `Reasonable` if all checked fields are
+/// proven and `LowQuality` otherwise. `None` if no field was checked, in which case the registrar
+/// has to judge by other means.
+///
+/// [`verify`]: ./fn.verify.html
+pub fn judgement<Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq>(
+	checks: &[(IdentityField, bool)],
+) -> Option<Judgement<Balance>> {
+	if checks.is_empty() {
+		None
+	} else if checks.iter().all(|c| c.1) {
+		Some(Judgement::Reasonable)
+	} else {
+		Some(Judgement::LowQuality)
+	}
+}
+
+/// The URL at which the proof of `claim` for `field` is published, if there is one.
+///
+/// A website must be claimed by its bare hostname. Email addresses and twitter handles are
+/// percent-encoded into a single path segment, so that they can't point anywhere else on the
+/// attesting service.
+fn proof_url(field: IdentityField, claim: &Data, sources: ProofSources) -> Option<Vec<u8>> {
+	let claim = match claim {
+		Data::Raw(ref raw) if !raw.is_empty() => str::from_utf8(raw).ok()?,
+		_ => return None,
+	};
+	let mut url = Vec::new();
+	match field {
+		IdentityField::Web => {
+			if !is_hostname(claim) {
+				return None
+			}
+			url.extend_from_slice(b"https://");
+			url.extend_from_slice(claim.as_bytes());
+			url.extend_from_slice(WEB_PROOF_PATH.as_bytes());
+		}
+		IdentityField::Email => {
+			url.extend_from_slice(sources.email.as_bytes());
+			push_segment(&mut url, claim)?;
+		}
+		_ => {
+			url.extend_from_slice(sources.twitter.as_bytes());
+			push_segment(&mut url, claim.trim_start_matches('@'))?;
+		}
+	}
+	Some(url)
+}
+
+/// Whether `name` is a DNS hostname: dot-separated labels of ASCII letters, digits and hyphens,
+/// none of which starts or ends with a hyphen.
+fn is_hostname(name: &str) -> bool {
+	name.len() <= 253 && name.split('.').all(|label|
+		!label.is_empty() && label.len() <= 63 &&
+			!label.starts_with('-') && !label.ends_with('-') &&
+			label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+	)
+}
+
+/// Append `/` and `segment` to `url`, percent-encoding all but the unreserved characters of
+/// RFC 3986. Fails for a segment which would be resolved relative to the others.
+fn push_segment(url: &mut Vec<u8>, segment: &str) -> Option<()> {
+	const HEX: &[u8; 16] = b"0123456789ABCDEF";
+	if segment.is_empty() || segment == "." || segment == ".." {
+		return None
+	}
+	url.push(b'/');
+	for byte in segment.bytes() {
+		match byte {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => url.push(byte),
+			_ => {
+				url.push(b'%');
+				url.push(HEX[(byte >> 4) as usize]);
+				url.push(HEX[(byte & 0xf) as usize]);
+			}
+		}
+	}
+	Some(())
+}
@@ -192,6 +192,8 @@ pub struct StorageEntryMetadata {
 	pub modifier: StorageEntryModifier,
 	pub ty: StorageEntryType,
 	pub default: ByteGetter,
+	/// The maximum number of elements of a bounded collection value, if any.
+	pub bound: Option<BoundGetter>,
 	pub documentation: DecodeDifferentArray<&'static str, StringBuf>,
 }
 
@@ -267,6 +269,47 @@ impl sp_std::fmt::Debug for DefaultByteGetter {
 	}
 }
 
+/// A technical trait to store the lazily evaluated bound of a storage value as static dyn pointer.
+pub trait StorageBound: Send + Sync {
+	fn bound(&self) -> u32;
+}
+
+/// Wrapper over dyn pointer for accessing the bound of a storage value.
+#[derive(Clone)]
+pub struct StorageBoundGetter(pub &'static dyn StorageBound);
+
+/// Decode different for static lazy evaluated storage value bound.
+pub type BoundGetter = DecodeDifferent<StorageBoundGetter, u32>;
+
+impl Encode for StorageBoundGetter {
+	fn encode_to<W: Output>(&self, dest: &mut W) {
+		self.0.bound().encode_to(dest)
+	}
+}
+
+impl codec::EncodeLike for StorageBoundGetter {}
+
+impl PartialEq<StorageBoundGetter> for StorageBoundGetter {
+	fn eq(&self, other: &StorageBoundGetter) -> bool {
+		self.0.bound() == other.0.bound()
+	}
+}
+
+impl Eq for StorageBoundGetter { }
+
+#[cfg(feature = "std")]
+impl serde::Serialize for StorageBoundGetter {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+		self.0.bound().serialize(serializer)
+	}
+}
+
+impl sp_std::fmt::Debug for StorageBoundGetter {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		self.0.bound().fmt(f)
+	}
+}
+
 /// Hasher used by storage maps
 #[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Decode, Serialize))]
@@ -277,6 +320,7 @@ pub enum StorageHasher {
 	Twox128,
 	Twox256,
 	Twox64Concat,
+	Identity,
 }
 
 /// A storage entry type.
@@ -297,6 +341,11 @@ pub enum StorageEntryType {
 		value: DecodeDifferentStr,
 		key2_hasher: StorageHasher,
 	},
+	NMap {
+		keys: DecodeDifferentArray<&'static str, StringBuf>,
+		hashers: DecodeDifferentArray<StorageHasher>,
+		value: DecodeDifferentStr,
+	},
 }
 
 /// A storage entry modifier.
@@ -359,8 +408,10 @@ pub enum RuntimeMetadata {
 	V9(RuntimeMetadataDeprecated),
 	/// Version 10 for runtime metadata. No longer used.
 	V10(RuntimeMetadataDeprecated),
-	/// Version 11 for runtime metadata.
-	V11(RuntimeMetadataV11),
+	/// Version 11 for runtime metadata. No longer used.
+	V11(RuntimeMetadataDeprecated),
+	/// Version 12 for runtime metadata.
+	V12(RuntimeMetadataV12),
 }
 
 /// Enum that should fail.
@@ -382,9 +433,12 @@ impl Decode for RuntimeMetadataDeprecated {
 }
 
 /// The metadata of a runtime.
+///
+/// Compared to version 11, storage maps may use the `Identity` hasher, storage entries may be
+/// of the `NMap` type and storage entries expose the bound of their value.
 #[derive(Eq, Encode, PartialEq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Decode, Serialize))]
-pub struct RuntimeMetadataV11 {
+pub struct RuntimeMetadataV12 {
 	/// Metadata of all the modules.
 	pub modules: DecodeDifferentArray<ModuleMetadata>,
 	/// Metadata of the extrinsic.
@@ -392,7 +446,7 @@ pub struct RuntimeMetadataV11 {
 }
 
 /// The latest version of the metadata.
-pub type RuntimeMetadataLastVersion = RuntimeMetadataV11;
+pub type RuntimeMetadataLastVersion = RuntimeMetadataV12;
 
 /// All metadata about an runtime module.
 #[derive(Clone, PartialEq, Eq, Encode, RuntimeDebug)]
@@ -417,6 +471,6 @@ impl Into<sp_core::OpaqueMetadata> for RuntimeMetadataPrefixed {
 
 impl Into<RuntimeMetadataPrefixed> for RuntimeMetadataLastVersion {
 	fn into(self) -> RuntimeMetadataPrefixed {
-		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V11(self))
+		RuntimeMetadataPrefixed(META_RESERVED, RuntimeMetadata::V12(self))
 	}
 }
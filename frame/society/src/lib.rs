@@ -259,7 +259,9 @@ use sp_runtime::{Percent, ModuleId, RuntimeDebug,
 		TrailingZeroInput, CheckedSub, EnsureOrigin
 	}
 };
-use frame_support::{decl_error, decl_module, decl_storage, decl_event, ensure, dispatch::DispatchResult};
+use frame_support::{
+	decl_error, decl_module, decl_storage, decl_event, ensure, dispatch::DispatchResult, BoundedVec,
+};
 use frame_support::weights::SimpleDispatchInfo;
 use frame_support::traits::{
 	Currency, ReservableCurrency, Randomness, Get, ChangeMembers,
@@ -313,6 +315,9 @@ pub trait Trait<I=DefaultInstance>: system::Trait {
 
 	/// The number of blocks between membership challenges.
 	type ChallengePeriod: Get<Self::BlockNumber>;
+
+	/// The maximum number of bids in the queue.
+	type MaxBidCount: Get<u32>;
 }
 
 /// A vote by a member on a candidate application.
@@ -435,7 +440,7 @@ decl_storage! {
 		pub SuspendedMembers get(fn suspended_member): map hasher(blake2_256) T::AccountId => bool;
 
 		/// The current bids, stored ordered by the value of the bid.
-		Bids: Vec<Bid<T::AccountId, BalanceOf<T, I>>>;
+		Bids: BoundedVec<Bid<T::AccountId, BalanceOf<T, I>>, T::MaxBidCount>;
 
 		/// Members currently vouching or banned from vouching again
 		Vouching get(fn vouching): map hasher(blake2_256) T::AccountId => Option<VouchingStatus>;
@@ -491,6 +496,9 @@ decl_module! {
 		/// The number of blocks between membership challenges.
 		const ChallengePeriod: T::BlockNumber = T::ChallengePeriod::get();
 
+		/// The maximum number of bids in the queue.
+		const MaxBidCount: u32 = T::MaxBidCount::get();
+
 		// Used for handling module events.
 		fn deposit_event() = default;
 
@@ -514,16 +522,16 @@ decl_module! {
 		/// 	- One storage read to retrieve all members. O(M)
 		/// - Storage Writes:
 		/// 	- One storage mutate to add a new bid to the vector O(B) (TODO: possible optimization w/ read)
-		/// 	- Up to one storage removal if bid.len() > MAX_BID_COUNT. O(1)
+		/// 	- Up to one storage removal if bids.len() == MaxBidCount. O(1)
 		/// - Notable Computation:
 		/// 	- O(B + C + log M) search to check user is not already a part of society.
 		/// 	- O(log B) search to insert the new bid sorted.
 		/// - External Module Operations:
 		/// 	- One balance reserve operation. O(X)
-		/// 	- Up to one balance unreserve operation if bids.len() > MAX_BID_COUNT.
+		/// 	- Up to one balance unreserve operation if bids.len() == MaxBidCount.
 		/// - Events:
 		/// 	- One event for new bid.
-		/// 	- Up to one event for AutoUnbid if bid.len() > MAX_BID_COUNT.
+		/// 	- Up to one event for AutoUnbid if bids.len() == MaxBidCount.
 		///
 		/// Total Complexity: O(M + B + C + logM + logB + X)
 		/// # </weight>
@@ -622,17 +630,17 @@ decl_module! {
 		/// - Storage Writes:
 		/// 	- One storage write to insert vouching status to the member. O(1)
 		/// 	- One storage mutate to add a new bid to the vector O(B) (TODO: possible optimization w/ read)
-		/// 	- Up to one storage removal if bid.len() > MAX_BID_COUNT. O(1)
+		/// 	- Up to one storage removal if bids.len() == MaxBidCount. O(1)
 		/// - Notable Computation:
 		/// 	- O(log M) search to check sender is a member.
 		/// 	- O(B + C + log M) search to check user is not already a part of society.
 		/// 	- O(log B) search to insert the new bid sorted.
 		/// - External Module Operations:
 		/// 	- One balance reserve operation. O(X)
-		/// 	- Up to one balance unreserve operation if bids.len() > MAX_BID_COUNT.
+		/// 	- Up to one balance unreserve operation if bids.len() == MaxBidCount.
 		/// - Events:
 		/// 	- One event for vouch.
-		/// 	- Up to one event for AutoUnbid if bid.len() > MAX_BID_COUNT.
+		/// 	- Up to one event for AutoUnbid if bids.len() == MaxBidCount.
 		///
 		/// Total Complexity: O(M + B + C + logM + logB + X)
 		/// # </weight>
@@ -1162,65 +1170,54 @@ fn pick_usize<'a, R: RngCore>(rng: &mut R, max: usize) -> usize {
 
 impl<T: Trait<I>, I: Instance> Module<T, I> {
 	/// Puts a bid into storage ordered by smallest to largest value.
-	/// Allows a maximum of 1000 bids in queue, removing largest value people first.
+	/// Allows a maximum of `MaxBidCount` bids in queue, removing largest value people first.
 	fn put_bid(
-		mut bids: Vec<Bid<T::AccountId, BalanceOf<T, I>>>,
+		mut bids: BoundedVec<Bid<T::AccountId, BalanceOf<T, I>>, T::MaxBidCount>,
 		who: &T::AccountId,
 		value: BalanceOf<T, I>,
 		bid_kind: BidKind<T::AccountId, BalanceOf<T, I>>
 	) {
-		const MAX_BID_COUNT: usize = 1000;
-
-		match bids.binary_search_by(|bid| bid.value.cmp(&value)) {
+		let pos = match bids.binary_search_by(|bid| bid.value.cmp(&value)) {
 			// Insert new elements after the existing ones. This ensures new bids
 			// with the same bid value are further down the list than existing ones.
-			Ok(pos) => {
-				let different_bid = bids.iter()
-					// Easily extract the index we are on
-					.enumerate()
-					// Skip ahead to the suggested position
-					.skip(pos)
-					// Keep skipping ahead until the position changes
-					.skip_while(|(_, x)| x.value <= bids[pos].value)
-					// Get the element when things changed
-					.next();
-				// If the element is not at the end of the list, insert the new element
-				// in the spot.
-				if let Some((p, _)) = different_bid {
-					bids.insert(p, Bid {
-						value,
-						who: who.clone(),
-						kind: bid_kind,
-					});
-				// If the element is at the end of the list, push the element on the end.
-				} else {
-					bids.push(Bid {
-						value,
-						who: who.clone(),
-						kind: bid_kind,
-					});
-				}
-			},
-			Err(pos) => bids.insert(pos, Bid {
-				value,
-				who: who.clone(),
-				kind: bid_kind,
-			}),
-		}
-		// Keep it reasonably small.
-		if bids.len() > MAX_BID_COUNT {
-			let Bid { who: popped, kind, .. } = bids.pop().expect("b.len() > 1000; qed");
-			match kind {
-				BidKind::Deposit(deposit) => {
-					let _ = T::Currency::unreserve(&popped, deposit);
-				}
-				BidKind::Vouch(voucher, _) => {
-					<Vouching<T, I>>::remove(&voucher);
+			Ok(pos) => bids.iter()
+				// Easily extract the index we are on
+				.enumerate()
+				// Skip ahead to the suggested position
+				.skip(pos)
+				// Keep skipping ahead until the position changes
+				.skip_while(|(_, x)| x.value <= bids[pos].value)
+				// Get the element when things changed, or the end of the list
+				.next()
+				.map_or(bids.len(), |(p, _)| p),
+			Err(pos) => pos,
+		};
+		let mut bid = Some(Bid {
+			value,
+			who: who.clone(),
+			kind: bid_kind,
+		});
+
+		// Keep it reasonably small: if the queue is full, drop the largest bid, which may be the
+		// new one.
+		if bids.len() == T::MaxBidCount::get() as usize {
+			let largest = if pos == bids.len() { bid.take() } else { bids.pop() };
+			if let Some(Bid { who: popped, kind, .. }) = largest {
+				match kind {
+					BidKind::Deposit(deposit) => {
+						let _ = T::Currency::unreserve(&popped, deposit);
+					}
+					BidKind::Vouch(voucher, _) => {
+						<Vouching<T, I>>::remove(&voucher);
+					}
 				}
+				Self::deposit_event(RawEvent::AutoUnbid(popped));
 			}
-			Self::deposit_event(RawEvent::AutoUnbid(popped));
 		}
 
+		if let Some(bid) = bid {
+			bids.try_insert(pos, bid).expect("the largest bid was dropped if the queue was full; qed");
+		}
 		<Bids<T, I>>::put(bids);
 	}
 
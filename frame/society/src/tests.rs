@@ -777,6 +777,12 @@ fn max_limits_work() {
 		assert_eq!(bids[0], create_bid(100, 100, BidKind::Deposit(25)));
 		// Last bid is smallest number + 99 (1099)
 		assert_eq!(bids[999], create_bid(1099, 1099, BidKind::Deposit(25)));
+		// A new bid larger than all others is dropped straight away, with its deposit returned.
+		let _ = Balances::make_free_balance_be(&2000, 1000);
+		assert_ok!(Society::bid(Origin::signed(2000), 2000));
+		assert_eq!(<Bids<Test>>::get(), bids);
+		assert_eq!(Balances::free_balance(2000), 1000);
+		assert_eq!(Balances::reserved_balance(2000), 0);
 		// Rotate period
 		run_to_block(4);
 		// Max of 10 candidates
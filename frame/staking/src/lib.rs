@@ -258,7 +258,7 @@ pub mod inflation;
 use sp_std::{prelude::*, result};
 use codec::{HasCompact, Encode, Decode};
 use frame_support::{
-	decl_module, decl_event, decl_storage, ensure, decl_error,
+	decl_module, decl_event, decl_storage, ensure, decl_error, BoundedVec,
 	weights::SimpleDispatchInfo,
 	traits::{
 		Currency, LockIdentifier, LockableCurrency,
@@ -287,7 +287,6 @@ use sp_phragmen::ExtendedBalance;
 use frame_support::traits::OnReapAccount;
 
 const DEFAULT_MINIMUM_VALIDATOR_COUNT: u32 = 4;
-const MAX_NOMINATIONS: usize = 16;
 const MAX_UNLOCKING_CHUNKS: usize = 32;
 const STAKING_ID: LockIdentifier = *b"staking ";
 
@@ -496,10 +495,12 @@ impl<AccountId, Balance> StakingLedger<AccountId, Balance> where
 }
 
 /// A record of the nominations made by a specific account.
+///
+/// `TargetLimit` bounds the number of targets.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub struct Nominations<AccountId> {
+pub struct Nominations<AccountId, TargetLimit: Get<u32>> {
 	/// The targets of nomination.
-	pub targets: Vec<AccountId>,
+	pub targets: BoundedVec<AccountId, TargetLimit>,
 	/// The era the nominations were submitted.
 	pub submitted_in: EraIndex,
 	/// Whether the nominations have been suppressed.
@@ -552,6 +553,8 @@ type PositiveImbalanceOf<T> =
 type NegativeImbalanceOf<T> =
 	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
 type MomentOf<T> = <<T as Trait>::Time as Time>::Moment;
+pub type NominationsOf<T> =
+	Nominations<<T as frame_system::Trait>::AccountId, <T as Trait>::MaxNominations>;
 
 /// Means for interacting with a specialized version of the `session` trait.
 ///
@@ -637,6 +640,9 @@ pub trait Trait: frame_system::Trait {
 
 	/// The NPoS reward curve to use.
 	type RewardCurve: Get<&'static PiecewiseLinear<'static>>;
+
+	/// The maximum number of targets a nominator can have.
+	type MaxNominations: Get<u32>;
 }
 
 /// Mode of era-forcing.
@@ -683,14 +689,14 @@ decl_storage! {
 
 		/// The map from (wannabe) validator stash key to the preferences of that validator.
 		pub Validators get(fn validators):
-			linked_map hasher(blake2_256) T::AccountId => ValidatorPrefs;
+			map hasher(twox_64_concat) T::AccountId => ValidatorPrefs;
 
 		/// The map from nominator stash key to the set of stash keys of all validators to nominate.
 		///
 		/// NOTE: is private so that we can ensure upgraded before all typical accesses.
 		/// Direct storage APIs can still bypass this protection.
 		Nominators get(fn nominators):
-			linked_map hasher(blake2_256) T::AccountId => Option<Nominations<T::AccountId>>;
+			map hasher(twox_64_concat) T::AccountId => Option<NominationsOf<T>>;
 
 		/// Nominators for a particular account that is in action right now. You can't iterate
 		/// through validators here, but you can find them in the Session module.
@@ -849,6 +855,9 @@ decl_module! {
 		/// Number of eras that staked funds must remain bonded for.
 		const BondingDuration: EraIndex = T::BondingDuration::get();
 
+		/// The maximum number of targets a nominator can have.
+		const MaxNominations: u32 = T::MaxNominations::get();
+
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
@@ -1060,7 +1069,7 @@ decl_module! {
 		///
 		/// # <weight>
 		/// - The transaction's complexity is proportional to the size of `targets`,
-		/// which is capped at `MAX_NOMINATIONS`.
+		/// which is capped at `MaxNominations`.
 		/// - Both the reads and writes follow a similar pattern.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(750_000)]
@@ -1072,12 +1081,12 @@ decl_module! {
 			let stash = &ledger.stash;
 			ensure!(!targets.is_empty(), Error::<T>::EmptyTargets);
 			let targets = targets.into_iter()
-				.take(MAX_NOMINATIONS)
+				.take(T::MaxNominations::get() as usize)
 				.map(|t| T::Lookup::lookup(t))
 				.collect::<result::Result<Vec<T::AccountId>, _>>()?;
 
 			let nominations = Nominations {
-				targets,
+				targets: BoundedVec::truncate_from(targets),
 				submitted_in: Self::current_era(),
 				suppressed: false,
 			};
@@ -1480,14 +1489,14 @@ impl<T: Trait> Module<T> {
 	/// Assumes storage is coherent with the declaration.
 	fn select_validators() -> (BalanceOf<T>, Option<Vec<T::AccountId>>) {
 		let mut all_nominators: Vec<(T::AccountId, Vec<T::AccountId>)> = Vec::new();
-		let all_validator_candidates_iter = <Validators<T>>::enumerate();
+		let all_validator_candidates_iter = <Validators<T>>::iter();
 		let all_validators = all_validator_candidates_iter.map(|(who, _pref)| {
 			let self_vote = (who.clone(), vec![who.clone()]);
 			all_nominators.push(self_vote);
 			who
 		}).collect::<Vec<T::AccountId>>();
 
-		let nominator_votes = <Nominators<T>>::enumerate().map(|(nominator, nominations)| {
+		let nominator_votes = <Nominators<T>>::iter().map(|(nominator, nominations)| {
 			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
 
 			// Filter out nomination targets which were nominated before the most recent
@@ -1499,7 +1508,7 @@ impl<T: Trait> Module<T> {
 				)
 			});
 
-			(nominator, targets)
+			(nominator, targets.into_inner())
 		});
 		all_nominators.extend(nominator_votes);
 
@@ -20,20 +20,44 @@
 pub type VersionNumber = u32;
 
 // the current expected version of the storage
-pub const CURRENT_VERSION: VersionNumber = 2;
+pub const CURRENT_VERSION: VersionNumber = 3;
 
 /// The inner logic of migrations.
 #[cfg(any(test, feature = "migrate"))]
 pub mod inner {
-	use crate::{Store, Module, Trait};
-	use frame_support::{StorageLinkedMap, StoragePrefixedMap, StorageValue};
+	use crate::{Store, Module, Trait, NominationsOf, ValidatorPrefs};
+	use frame_support::{
+		StorageLinkedMap, StoragePrefixedMap, StorageValue, StorageMap, Blake2_256,
+		storage::{generator, migration::take_linked_map},
+	};
 	use codec::{Encode, Decode};
-	use sp_std::vec::Vec;
+	use sp_std::{vec::Vec, marker::PhantomData};
 	use super::{CURRENT_VERSION, VersionNumber};
 
 	// the minimum supported version of the migration logic.
 	const MIN_SUPPORTED_VERSION: VersionNumber = 0;
 
+	// the `Nominators` storage item as it was laid out before v3, i.e. as a `linked_map`.
+	pub(crate) struct OldNominators<T>(PhantomData<T>);
+
+	pub(crate) struct OldNominatorsKeyFormat;
+
+	impl generator::LinkedMapKeyFormat for OldNominatorsKeyFormat {
+		type Hasher = Blake2_256;
+		fn module_prefix() -> &'static [u8] { b"Staking" }
+		fn storage_prefix() -> &'static [u8] { b"Nominators" }
+		fn head_prefix() -> &'static [u8] { b"HeadOfNominators" }
+	}
+
+	impl<T: Trait> generator::StorageLinkedMap<T::AccountId, NominationsOf<T>>
+		for OldNominators<T>
+	{
+		type Query = Option<NominationsOf<T>>;
+		type KeyFormat = OldNominatorsKeyFormat;
+		fn from_optional_value_to_query(v: Option<NominationsOf<T>>) -> Self::Query { v }
+		fn from_query_to_optional_value(v: Self::Query) -> Option<NominationsOf<T>> { v }
+	}
+
 	// migrate storage from v0 to v1.
 	//
 	// this upgrades the `Nominators` linked_map value type from `Vec<T::AccountId>` to
@@ -43,10 +67,10 @@ pub mod inner {
 		*version += 1;
 
 		let now = <Module<T>>::current_era();
-		let res = <Module<T> as Store>::Nominators::translate::<T::AccountId, Vec<T::AccountId>, _, _>(
+		let res = OldNominators::<T>::translate::<T::AccountId, Vec<T::AccountId>, _, _>(
 			|key| key,
 			|targets| crate::Nominations {
-				targets,
+				targets: frame_support::BoundedVec::truncate_from(targets),
 				submitted_in: now,
 				suppressed: false,
 			},
@@ -111,6 +135,33 @@ pub mod inner {
 		}
 	}
 
+	// migrate storage from v2 to v3.
+	//
+	// this turns the `Validators` and `Nominators` linked_maps into maps hashed with
+	// `twox_64_concat`, which can be iterated without the linkage overhead.
+	pub fn to_v3<T: Trait>(version: &mut VersionNumber) {
+		if *version != 2 { return }
+		*version += 1;
+
+		let validators = take_linked_map::<T::AccountId, ValidatorPrefs, Blake2_256>(
+			b"Staking",
+			b"Validators",
+		);
+		for (stash, prefs) in validators {
+			<Module<T> as Store>::Validators::insert(stash, prefs);
+		}
+
+		let nominators = take_linked_map::<T::AccountId, NominationsOf<T>, Blake2_256>(
+			b"Staking",
+			b"Nominators",
+		);
+		for (stash, nominations) in nominators {
+			<Module<T> as Store>::Nominators::insert(stash, nominations);
+		}
+
+		frame_support::print("Finished migrating Staking storage to v3.");
+	}
+
 	pub(super) fn perform_migrations<T: Trait>() {
 		<Module<T> as Store>::StorageVersion::mutate(|version| {
 			if *version < MIN_SUPPORTED_VERSION {
@@ -124,6 +175,7 @@ pub mod inner {
 
 			to_v1::<T>(version);
 			to_v2::<T>(version);
+			to_v3::<T>(version);
 		});
 	}
 }
@@ -25,7 +25,7 @@ use sp_staking::{SessionIndex, offence::{OffenceDetails, OnOffenceHandler}};
 use sp_core::{H256, crypto::key_types};
 use sp_io;
 use frame_support::{
-	assert_ok, impl_outer_origin, parameter_types, StorageLinkedMap, StorageValue,
+	assert_ok, impl_outer_origin, parameter_types, IterableStorageMap, StorageValue,
 	traits::{Currency, Get, FindAuthor},
 	weights::Weight,
 };
@@ -201,6 +201,7 @@ parameter_types! {
 	pub const SessionsPerEra: SessionIndex = 3;
 	pub const BondingDuration: EraIndex = 3;
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &I_NPOS;
+	pub const MaxNominations: u32 = 16;
 }
 impl Trait for Test {
 	type Currency = pallet_balances::Module<Self>;
@@ -216,6 +217,7 @@ impl Trait for Test {
 	type BondingDuration = BondingDuration;
 	type SessionInterface = Self;
 	type RewardCurve = RewardCurve;
+	type MaxNominations = MaxNominations;
 }
 
 pub struct ExtBuilder {
@@ -374,7 +376,7 @@ pub fn check_exposure_all() {
 }
 
 pub fn check_nominator_all() {
-	<Nominators<Test>>::enumerate().for_each(|(acc, _)| check_nominator_exposure(acc));
+	<Nominators<Test>>::iter().for_each(|(acc, _)| check_nominator_exposure(acc));
 }
 
 /// Check for each selected validator: expo.total = Sum(expo.other) + expo.own
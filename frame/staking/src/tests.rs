@@ -79,7 +79,7 @@ fn basic_setup_works() {
 		assert_eq!(Staking::ledger(&1), None);
 
 		// ValidatorPrefs are default
-		assert_eq!(<Validators<Test>>::enumerate().collect::<Vec<_>>(), vec![
+		assert_eq!(<Validators<Test>>::iter().collect::<Vec<_>>(), vec![
 			(31, ValidatorPrefs::default()),
 			(21, ValidatorPrefs::default()),
 			(11, ValidatorPrefs::default())
@@ -127,10 +127,10 @@ fn change_controller_works() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_eq!(Staking::bonded(&11), Some(10));
 
-		assert!(<Validators<Test>>::enumerate().map(|(c, _)| c).collect::<Vec<u64>>().contains(&11));
+		assert!(<Validators<Test>>::iter().map(|(c, _)| c).collect::<Vec<u64>>().contains(&11));
 		// 10 can control 11 who is initially a validator.
 		assert_ok!(Staking::chill(Origin::signed(10)));
-		assert!(!<Validators<Test>>::enumerate().map(|(c, _)| c).collect::<Vec<u64>>().contains(&11));
+		assert!(!<Validators<Test>>::iter().map(|(c, _)| c).collect::<Vec<u64>>().contains(&11));
 
 		assert_ok!(Staking::set_controller(Origin::signed(11), 5));
 
@@ -2703,7 +2703,7 @@ fn slash_kicks_validators_not_nominators() {
 
 		// This is the best way to check that the validator was chilled; `get` will
 		// return default value.
-		for (stash, _) in <Staking as Store>::Validators::enumerate() {
+		for (stash, _) in <Staking as Store>::Validators::iter() {
 			assert!(stash != 11);
 		}
 
@@ -2766,6 +2766,34 @@ fn migration_v2() {
 	});
 }
 
+#[test]
+fn migration_v3() {
+	ExtBuilder::default().build().execute_with(|| {
+		use frame_support::StorageLinkedMap;
+		use crate::migration::inner::OldNominators;
+
+		// move the current nominators into the old linked map layout.
+		let nominators = <Staking as Store>::Nominators::drain().collect::<Vec<_>>();
+		assert!(!nominators.is_empty());
+		for (stash, nominations) in nominators.iter() {
+			OldNominators::<Test>::insert(stash, nominations);
+		}
+		assert_eq!(OldNominators::<Test>::enumerate().count(), nominators.len());
+
+		<Staking as Store>::StorageVersion::put(2);
+
+		// perform migration.
+		crate::migration::inner::to_v3::<Test>(&mut 2);
+
+		assert_eq!(OldNominators::<Test>::enumerate().count(), 0);
+		let mut migrated = <Staking as Store>::Nominators::iter().collect::<Vec<_>>();
+		migrated.sort_by_key(|(stash, _)| *stash);
+		let mut expected = nominators;
+		expected.sort_by_key(|(stash, _)| *stash);
+		assert_eq!(migrated, expected);
+	});
+}
+
 #[test]
 fn zero_slash_keeps_nominators() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -2791,7 +2819,7 @@ fn zero_slash_keeps_nominators() {
 
 		// This is the best way to check that the validator was chilled; `get` will
 		// return default value.
-		for (stash, _) in <Staking as Store>::Validators::enumerate() {
+		for (stash, _) in <Staking as Store>::Validators::iter() {
 			assert!(stash != 11);
 		}
 
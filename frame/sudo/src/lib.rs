@@ -86,7 +86,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
-use sp_runtime::{traits::{StaticLookup, Dispatchable}, DispatchError};
+use sp_runtime::traits::{StaticLookup, Dispatchable};
 
 use frame_support::{
 	Parameter, decl_module, decl_event, decl_storage, decl_error, ensure,
@@ -128,8 +128,7 @@ decl_module! {
 			let res = match proposal.dispatch(frame_system::RawOrigin::Root.into()) {
 				Ok(_) => true,
 				Err(e) => {
-					let e: DispatchError = e.into();
-					sp_runtime::print(e);
+					sp_runtime::print(e.error);
 					false
 				}
 			};
@@ -178,8 +177,7 @@ decl_module! {
 			let res = match proposal.dispatch(frame_system::RawOrigin::Signed(who).into()) {
 				Ok(_) => true,
 				Err(e) => {
-					let e: DispatchError = e.into();
-					sp_runtime::print(e);
+					sp_runtime::print(e.error);
 					false
 				}
 			};
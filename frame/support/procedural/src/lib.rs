@@ -77,6 +77,10 @@ use proc_macro::TokenStream;
 ///   `blake2_256` and `blake2_128_concat` are strong hasher. One should use another hasher
 ///   with care, see generator documentation.
 ///
+///   If `$hash` keeps the key in the final storage key (`blake2_128_concat`, `twox_64_concat` or
+///   `identity`) the map also implements
+///   [`IterableStorageMap`](../frame_support/storage/trait.IterableStorageMap.html).
+///
 ///   The generator is implemented with:
 ///   * `module_prefix`: $module_prefix
 ///   * `storage_prefix`: storage_name
@@ -87,7 +91,8 @@ use proc_macro::TokenStream;
 ///   twox128(module_prefix) ++ twox128(storage_prefix) ++ hasher(encode(key))
 ///   ```
 ///
-/// * Linked map: `Foo: linked_map hasher($hash) type => type`: Implements the
+/// * Linked map (deprecated, use an iterable map instead):
+///   `Foo: linked_map hasher($hash) type => type`: Implements the
 ///   [`StorageLinkedMap`](../frame_support/storage/trait.StorageLinkedMap.html) trait using the
 ///   [`StorageLinkedMap generator`](../frame_support/storage/generator/trait.StorageLinkedMap.html).
 ///   And [`StoragePrefixedMap`](../frame_support/storage/trait.StoragePrefixedMap.html).
@@ -134,6 +139,9 @@ use proc_macro::TokenStream;
 ///   `blake2_128_concat` must be used.
 ///   Otherwise, other items in storage with the same first key can be compromised.
 ///
+///   If both hashers keep the key in the final storage key the double map also implements
+///   [`IterableStorageDoubleMap`](../frame_support/storage/trait.IterableStorageDoubleMap.html).
+///
 ///   The generator is implemented with:
 ///   * `module_prefix`: $module_prefix
 ///   * `storage_prefix`: storage_name
@@ -145,8 +153,28 @@ use proc_macro::TokenStream;
 ///   Twox128(module_prefix) ++ Twox128(storage_prefix) ++ Hasher1(encode(key1)) ++ Hasher2(encode(key2))
 ///   ```
 ///
+/// * N-map: `Foo: nmap hasher($hash1) u32, hasher($hash2) u64, hasher($hash3) u8 => u32`:
+///   Implements the [`StorageNMap`](../frame_support/storage/trait.StorageNMap.html) trait using the
+///   [`StorageNMap generator`](../frame_support/storage/generator/trait.StorageNMap.html).
+///   And [`StoragePrefixedMap`](../frame_support/storage/trait.StoragePrefixedMap.html).
+///
+///   Any number of keys (up to 8) can be given, each with its own hasher. Keys are passed as a
+///   tuple, e.g. `Foo::get((1, 2, 3))`, and entries can be removed or iterated by any prefix of the
+///   keys, e.g. `Foo::remove_prefix((1,))`. The same care as for double maps must be taken when
+///   choosing the hashers.
+///
+///   If all hashers keep the key in the final storage key the N-map also implements
+///   [`IterableStorageNMap`](../frame_support/storage/trait.IterableStorageNMap.html).
+///
+///   Thus keys are stored at:
+///   ```nocompile
+///   Twox128(module_prefix) ++ Twox128(storage_prefix) ++ Hasher1(encode(key1)) ++ ... ++ HasherN(encode(keyN))
+///   ```
+///
 /// Supported hashers (ordered from least to best security):
 ///
+/// * `identity` - Just the unrefined key material. Use only if it is known to be a secure hash
+///   already.
 /// * `twox_64_concat` - TwoX with 64bit + key concatenated.
 /// * `twox_128` - TwoX with 128bit.
 /// * `twox_256` - TwoX with with 256bit.
@@ -167,6 +195,9 @@ use proc_macro::TokenStream;
 /// * `#type`: Storage type.
 /// * [optional] `#default`: Value returned when none.
 ///
+/// If the value type is a `BoundedVec` or a `BoundedBTreeMap`, its bound is exposed in the
+/// `bound` field of the storage entry metadata.
+///
 /// Storage items are accessible in multiple ways:
 ///
 /// * The structure: `Foo` or `Foo::<T>` depending if the value type is generic or not.
@@ -75,6 +75,21 @@ fn storage_line_metadata_type(scrate: &TokenStream, line: &StorageLineDefExt) ->
 				}
 			}
 		},
+		StorageLineTypeDef::NMap(map) => {
+			let keys = map.keys.iter()
+				.map(|key| clean_type_string(&quote!(#key).to_string()));
+			let hashers = map.hashers.iter()
+				.map(|hasher| hasher.into_metadata());
+			quote!{
+				#scrate::metadata::StorageEntryType::NMap {
+					keys: #scrate::metadata::DecodeDifferent::Encode(&[ #( #keys, )* ]),
+					hashers: #scrate::metadata::DecodeDifferent::Encode(&[
+						#( #scrate::metadata::#hashers, )*
+					]),
+					value: #scrate::metadata::DecodeDifferent::Encode(#value_type),
+				}
+			}
+		},
 	}
 }
 
@@ -152,9 +167,72 @@ fn default_byte_getter(
 	(struct_def, struct_instance)
 }
 
+/// Whether the value type is one of the bounded collections of `frame_support::storage`, in which
+/// case its bound is exposed in the metadata.
+fn is_bounded_collection(value_type: &syn::Type) -> bool {
+	match value_type {
+		syn::Type::Path(path) => path.path.segments.last()
+			.map_or(false, |s| s.ident == "BoundedVec" || s.ident == "BoundedBTreeMap"),
+		_ => false,
+	}
+}
+
+/// If the value type is a bounded collection, return the definition of the struct to lazily get
+/// its bound and an instance of it.
+fn bound_getter(
+	scrate: &TokenStream,
+	line: &StorageLineDefExt,
+	def: &DeclStorageDefExt,
+) -> Option<(TokenStream, TokenStream)> {
+	if !is_bounded_collection(&line.value_type) {
+		return None
+	}
+
+	let str_name = line.name.to_string();
+	let struct_name = syn::Ident::new(&("__GetBoundStruct".to_string() + &str_name), line.name.span());
+
+	let runtime_generic = &def.module_runtime_generic;
+	let runtime_trait = &def.module_runtime_trait;
+	let optional_instance_bound_optional_default = &def.optional_instance_bound_optional_default;
+	let optional_instance_bound = &def.optional_instance_bound;
+	let optional_instance = &def.optional_instance;
+	let optional_comma_instance = optional_instance.as_ref().map(|i| quote!(, #i));
+	let where_clause = &def.where_clause;
+
+	let value_type = &line.value_type;
+
+	let struct_def = quote! {
+		#[doc(hidden)]
+		pub struct #struct_name<
+			#runtime_generic, #optional_instance_bound_optional_default
+		>(pub #scrate::sp_std::marker::PhantomData<(#runtime_generic #optional_comma_instance)>);
+
+		impl<#runtime_generic: #runtime_trait, #optional_instance_bound>
+			#scrate::metadata::StorageBound
+			for #struct_name<#runtime_generic, #optional_instance>
+			#where_clause
+		{
+			fn bound(&self) -> u32 {
+				<#value_type as #scrate::storage::Bounded>::bound()
+			}
+		}
+
+		unsafe impl<#runtime_generic: #runtime_trait, #optional_instance_bound> Send
+			for #struct_name<#runtime_generic, #optional_instance> #where_clause {}
+
+		unsafe impl<#runtime_generic: #runtime_trait, #optional_instance_bound> Sync
+			for #struct_name<#runtime_generic, #optional_instance> #where_clause {}
+	};
+	let struct_instance = quote!(
+		#struct_name::<#runtime_generic, #optional_instance>(#scrate::sp_std::marker::PhantomData)
+	);
+
+	Some((struct_def, struct_instance))
+}
+
 pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStream {
 	let mut entries = TokenStream::new();
-	let mut default_byte_getter_struct_defs = TokenStream::new();
+	let mut getter_struct_defs = TokenStream::new();
 
 	for line in def.storage_lines.iter() {
 		let str_name = line.name.to_string();
@@ -172,6 +250,16 @@ pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStre
 			default_byte_getter_struct_instance,
 		) = default_byte_getter(scrate, line, def);
 
+		let bound = match bound_getter(scrate, line, def) {
+			Some((bound_getter_struct_def, bound_getter_struct_instance)) => {
+				getter_struct_defs.extend(bound_getter_struct_def);
+				quote!(Some(#scrate::metadata::DecodeDifferent::Encode(
+					#scrate::metadata::StorageBoundGetter(&#bound_getter_struct_instance)
+				)))
+			},
+			None => quote!(None),
+		};
+
 		let mut docs = TokenStream::new();
 		for attr in line.attrs.iter().filter_map(|v| v.parse_meta().ok()) {
 			if let syn::Meta::NameValue(meta) = attr {
@@ -190,11 +278,12 @@ pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStre
 				default: #scrate::metadata::DecodeDifferent::Encode(
 					#scrate::metadata::DefaultByteGetter(&#default_byte_getter_struct_instance)
 				),
+				bound: #bound,
 				documentation: #scrate::metadata::DecodeDifferent::Encode(&[ #docs ]),
 			},
 		};
 
-		default_byte_getter_struct_defs.extend(default_byte_getter_struct_def);
+		getter_struct_defs.extend(default_byte_getter_struct_def);
 		entries.extend(entry);
 	}
 
@@ -218,7 +307,7 @@ pub fn impl_metadata(scrate: &TokenStream, def: &DeclStorageDefExt) -> TokenStre
 	let where_clause = &def.where_clause;
 
 	quote!(
-		#default_byte_getter_struct_defs
+		#getter_struct_defs
 
 		impl#module_impl #module_struct #where_clause {
 			#[doc(hidden)]
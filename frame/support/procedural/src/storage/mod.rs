@@ -105,8 +105,12 @@ pub struct DeclStorageDefExt {
 impl From<DeclStorageDef> for DeclStorageDefExt {
 	fn from(mut def: DeclStorageDef) -> Self {
 		let storage_lines = def.storage_lines.drain(..).collect::<Vec<_>>();
+		let hidden_crate_name = def.hidden_crate.as_ref().map(|i| i.to_string())
+			.unwrap_or_else(|| "decl_storage".to_string());
+		let scrate = generate_crate_access(&hidden_crate_name, "frame-support");
+
 		let storage_lines = storage_lines.into_iter()
-			.map(|line| StorageLineDefExt::from_def(line, &def))
+			.map(|line| StorageLineDefExt::from_def(line, &def, &scrate))
 			.collect();
 
 		let (
@@ -226,7 +230,11 @@ pub struct StorageLineDefExt {
 }
 
 impl StorageLineDefExt {
-	fn from_def(storage_def: StorageLineDef, def: &DeclStorageDef) -> Self {
+	fn from_def(
+		storage_def: StorageLineDef,
+		def: &DeclStorageDef,
+		scrate: &proc_macro2::TokenStream,
+	) -> Self {
 		let is_generic = match &storage_def.storage_type {
 			StorageLineTypeDef::Simple(value) => {
 				ext::type_contains_ident(&value, &def.module_runtime_generic)
@@ -244,6 +252,10 @@ impl StorageLineDefExt {
 					|| ext::type_contains_ident(&map.key2, &def.module_runtime_generic)
 					|| ext::type_contains_ident(&map.value, &def.module_runtime_generic)
 			}
+			StorageLineTypeDef::NMap(map) => {
+				map.keys.iter().any(|key| ext::type_contains_ident(key, &def.module_runtime_generic))
+					|| ext::type_contains_ident(&map.value, &def.module_runtime_generic)
+			}
 		};
 
 		let query_type = match &storage_def.storage_type {
@@ -251,6 +263,7 @@ impl StorageLineDefExt {
 			StorageLineTypeDef::Map(map) => map.value.clone(),
 			StorageLineTypeDef::LinkedMap(map) => map.value.clone(),
 			StorageLineTypeDef::DoubleMap(map) => map.value.clone(),
+			StorageLineTypeDef::NMap(map) => map.value.clone(),
 		};
 		let is_option = ext::extract_type_option(&query_type).is_some();
 		let value_type = ext::extract_type_option(&query_type).unwrap_or(query_type.clone());
@@ -300,6 +313,10 @@ impl StorageLineDefExt {
 				let key2 = &map.key2;
 				quote!( StorageDoubleMap<#key1, #key2, #value_type> )
 			},
+			StorageLineTypeDef::NMap(map) => {
+				let key_generator = map.to_key_generator(scrate);
+				quote!( StorageNMap<#key_generator, #value_type> )
+			},
 		};
 
 		let storage_trait = quote!( storage::#storage_trait_trunkated );
@@ -338,6 +355,7 @@ pub enum StorageLineTypeDef {
 	Map(MapDef),
 	LinkedMap(MapDef),
 	DoubleMap(DoubleMapDef),
+	NMap(NMapDef),
 	Simple(syn::Type),
 }
 
@@ -357,6 +375,31 @@ pub struct DoubleMapDef {
 	pub value: syn::Type,
 }
 
+pub struct NMapDef {
+	pub hashers: Vec<HasherKind>,
+	pub keys: Vec<syn::Type>,
+	/// This is the query value not the inner value used in storage trait implementation.
+	pub value: syn::Type,
+}
+
+impl NMapDef {
+	/// The tuple of all key types, for example: `(u32, u64)`.
+	fn to_keys_tuple(&self) -> proc_macro2::TokenStream {
+		let keys = &self.keys;
+		quote!( ( #( #keys, )* ) )
+	}
+
+	/// The key generator used by the storage traits, for example:
+	/// `(Key<Twox64Concat, u32>, Key<Blake2_128Concat, u64>)`.
+	fn to_key_generator(&self, scrate: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+		let keys = self.hashers.iter().zip(self.keys.iter()).map(|(hasher, key)| {
+			let hasher = hasher.to_storage_hasher_struct();
+			quote!( #scrate::storage::key::Key<#scrate::#hasher, #key> )
+		});
+		quote!( ( #( #keys, )* ) )
+	}
+}
+
 pub struct ExtraGenesisLineDef {
 	attrs: Vec<syn::Attribute>,
 	name: syn::Ident,
@@ -372,6 +415,7 @@ pub enum HasherKind {
 	Twox256,
 	Twox128,
 	Twox64Concat,
+	Identity,
 }
 
 impl HasherKind {
@@ -383,6 +427,7 @@ impl HasherKind {
 			HasherKind::Twox256 => quote!( Twox256 ),
 			HasherKind::Twox128 => quote!( Twox128 ),
 			HasherKind::Twox64Concat => quote!( Twox64Concat ),
+			HasherKind::Identity => quote!( Identity ),
 		}
 	}
 
@@ -394,6 +439,7 @@ impl HasherKind {
 			HasherKind::Twox256 => quote!( StorageHasher::Twox256 ),
 			HasherKind::Twox128 => quote!( StorageHasher::Twox128 ),
 			HasherKind::Twox64Concat => quote!( StorageHasher::Twox64Concat ),
+			HasherKind::Identity => quote!( StorageHasher::Identity ),
 		}
 	}
 }
@@ -422,7 +468,11 @@ pub fn decl_storage_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 			StorageMap as _,
 			StorageLinkedMap as _,
 			StorageDoubleMap as _,
+			StorageNMap as _,
 			StoragePrefixedMap as _,
+			IterableStorageMap as _,
+			IterableStorageDoubleMap as _,
+			IterableStorageNMap as _,
 		};
 
 		#scrate_decl
@@ -29,12 +29,14 @@ mod keyword {
 	syn::custom_keyword!(map);
 	syn::custom_keyword!(linked_map);
 	syn::custom_keyword!(double_map);
+	syn::custom_keyword!(nmap);
 	syn::custom_keyword!(blake2_256);
 	syn::custom_keyword!(blake2_128);
 	syn::custom_keyword!(blake2_128_concat);
 	syn::custom_keyword!(twox_256);
 	syn::custom_keyword!(twox_128);
 	syn::custom_keyword!(twox_64_concat);
+	syn::custom_keyword!(identity);
 	syn::custom_keyword!(hasher);
 }
 
@@ -196,6 +198,7 @@ enum DeclStorageType {
 	Map(DeclStorageMap),
 	LinkedMap(DeclStorageLinkedMap),
 	DoubleMap(DeclStorageDoubleMap),
+	NMap(DeclStorageNMap),
 	Simple(syn::Type),
 }
 
@@ -207,6 +210,8 @@ impl syn::parse::Parse for DeclStorageType {
 			Ok(Self::LinkedMap(input.parse()?))
 		} else if input.peek(keyword::double_map) {
 			Ok(Self::DoubleMap(input.parse()?))
+		} else if input.peek(keyword::nmap) {
+			Ok(Self::NMap(input.parse()?))
 		} else {
 			Ok(Self::Simple(input.parse()?))
 		}
@@ -243,6 +248,20 @@ struct DeclStorageDoubleMap {
 	pub value: syn::Type,
 }
 
+#[derive(Parse, ToTokens, Debug)]
+struct DeclStorageNMap {
+	pub map_keyword: keyword::nmap,
+	pub keys: ext::PunctuatedTrailing<DeclStorageNMapKey, Token![,]>,
+	pub ass_keyword: Token![=>],
+	pub value: syn::Type,
+}
+
+#[derive(Parse, ToTokens, Debug)]
+struct DeclStorageNMapKey {
+	pub hasher: Opt<SetHasher>,
+	pub key: syn::Type,
+}
+
 #[derive(ToTokens, Debug)]
 enum Hasher {
 	Blake2_256(keyword::blake2_256),
@@ -251,6 +270,7 @@ enum Hasher {
 	Twox256(keyword::twox_256),
 	Twox128(keyword::twox_128),
 	Twox64Concat(keyword::twox_64_concat),
+	Identity(keyword::identity),
 }
 
 impl syn::parse::Parse for Hasher {
@@ -268,6 +288,8 @@ impl syn::parse::Parse for Hasher {
 			Ok(Self::Twox128(input.parse()?))
 		} else if lookahead.peek(keyword::twox_64_concat) {
 			Ok(Self::Twox64Concat(input.parse()?))
+		} else if lookahead.peek(keyword::identity) {
+			Ok(Self::Identity(input.parse()?))
 		} else {
 			Err(lookahead.error())
 		}
@@ -313,6 +335,7 @@ impl From<Hasher> for super::HasherKind {
 			Hasher::Twox256(_) => super::HasherKind::Twox256,
 			Hasher::Twox128(_) => super::HasherKind::Twox128,
 			Hasher::Twox64Concat(_) => super::HasherKind::Twox64Concat,
+			Hasher::Identity(_) => super::HasherKind::Identity,
 		}
 	}
 }
@@ -491,6 +514,21 @@ fn parse_storage_line_defs(
 					value: map.value,
 				}
 			),
+			DeclStorageType::NMap(map) => {
+				let mut hashers = Vec::new();
+				let mut keys = Vec::new();
+				for key in map.keys.inner {
+					hashers.push(key.hasher.inner.ok_or_else(no_hasher_error)?.into());
+					keys.push(key.key);
+				}
+				super::StorageLineTypeDef::NMap(
+					super::NMapDef {
+						hashers,
+						keys,
+						value: map.value,
+					}
+				)
+			},
 			DeclStorageType::Simple(expr) => super::StorageLineTypeDef::Simple(expr),
 		};
 
@@ -85,6 +85,19 @@
 //!	// same output to stdout, no overhead on WASM.
 //!	native::print!("My struct: {:?}", x);
 //! ```
+//!
+//! To profile runtime code, enter a tracing span with [`enter_span!`](../macro.enter_span.html).
+//! The span lasts until the end of the current scope. When running natively the span is
+//! created with `tracing` directly, in WASM it is entered through the `wasm_tracing` host
+//! functions. Either way it is received by the tracing subscriber of the node. Spans are only
+//! entered when the `with-tracing` feature is enabled, otherwise the macro expands to nothing.
+//!
+//! ```rust,no_run
+//!	fn expensive() {
+//!		frame_support::enter_span!("expensive");
+//!		// ...
+//!	}
+//! ```
 
 use sp_std::vec::Vec;
 use sp_std::fmt::{self, Debug};
@@ -136,6 +149,57 @@ macro_rules! runtime_print {
 	}
 }
 
+/// Enter a tracing span with the given name until the end of the current scope.
+///
+/// The target of the span is the module path of the caller.
+#[cfg(all(feature = "with-tracing", feature = "std"))]
+#[macro_export]
+macro_rules! enter_span {
+	( $name:expr ) => {
+		let __tracing_span__ = $crate::tracing::span!($crate::tracing::Level::DEBUG, $name);
+		let __tracing_guard__ = __tracing_span__.enter();
+	}
+}
+
+/// Enter a tracing span with the given name until the end of the current scope.
+///
+/// The target of the span is the module path of the caller.
+#[cfg(all(feature = "with-tracing", not(feature = "std")))]
+#[macro_export]
+macro_rules! enter_span {
+	( $name:expr ) => {
+		let __tracing_guard__ = $crate::debug::WasmSpan::enter(module_path!(), $name);
+	}
+}
+
+/// Enter a tracing span with the given name until the end of the current scope.
+///
+/// Does nothing unless the `with-tracing` feature is enabled.
+#[cfg(not(feature = "with-tracing"))]
+#[macro_export]
+macro_rules! enter_span {
+	( $name:expr ) => {}
+}
+
+/// A span entered through the `wasm_tracing` host functions, exited when dropped.
+#[cfg(all(feature = "with-tracing", not(feature = "std")))]
+pub struct WasmSpan(u64);
+
+#[cfg(all(feature = "with-tracing", not(feature = "std")))]
+impl WasmSpan {
+	/// Enter a span with the given target and name.
+	pub fn enter(target: &str, name: &str) -> Self {
+		WasmSpan(sp_io::wasm_tracing::enter_span(target, name))
+	}
+}
+
+#[cfg(all(feature = "with-tracing", not(feature = "std")))]
+impl Drop for WasmSpan {
+	fn drop(&mut self) {
+		sp_io::wasm_tracing::exit_span(self.0)
+	}
+}
+
 /// Print out the debuggable type.
 pub fn debug(data: &impl Debug) {
 	runtime_print!("{:?}", data);
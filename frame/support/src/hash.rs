@@ -28,6 +28,7 @@ pub trait Hashable: Sized {
 	fn twox_128(&self) -> [u8; 16];
 	fn twox_256(&self) -> [u8; 32];
 	fn twox_64_concat(&self) -> Vec<u8>;
+	fn identity(&self) -> Vec<u8>;
 }
 
 impl<T: Codec> Hashable for T {
@@ -49,6 +50,9 @@ impl<T: Codec> Hashable for T {
 	fn twox_64_concat(&self) -> Vec<u8> {
 		self.using_encoded(Twox64Concat::hash)
 	}
+	fn identity(&self) -> Vec<u8> {
+		self.encode()
+	}
 }
 
 /// Hasher to use to hash keys to insert to storage.
@@ -57,6 +61,31 @@ pub trait StorageHasher: 'static {
 	fn hash(x: &[u8]) -> Self::Output;
 }
 
+/// Hasher to use to hash keys to insert to storage, which also keeps the encoded key in the
+/// output so that it can be recovered.
+///
+/// Reversible hashers store the encoded key after the hash part.
+pub trait ReversibleStorageHasher: StorageHasher {
+	/// Split the hash part out of the input.
+	///
+	/// I.e. for input `&[hash ++ key ++ some]` returns `&[key ++ some]`
+	fn reverse(x: &[u8]) -> &[u8];
+}
+
+/// Store the key directly.
+pub struct Identity;
+impl StorageHasher for Identity {
+	type Output = Vec<u8>;
+	fn hash(x: &[u8]) -> Vec<u8> {
+		x.to_vec()
+	}
+}
+impl ReversibleStorageHasher for Identity {
+	fn reverse(x: &[u8]) -> &[u8] {
+		x
+	}
+}
+
 /// Hash storage keys with `concat(twox64(key), key)`
 pub struct Twox64Concat;
 impl StorageHasher for Twox64Concat {
@@ -69,6 +98,14 @@ impl StorageHasher for Twox64Concat {
 			.collect::<Vec<_>>()
 	}
 }
+impl ReversibleStorageHasher for Twox64Concat {
+	fn reverse(x: &[u8]) -> &[u8] {
+		if x.len() < 8 {
+			return &[]
+		}
+		&x[8..]
+	}
+}
 
 /// Hash storage keys with `concat(blake2_128(key), key)`
 pub struct Blake2_128Concat;
@@ -82,6 +119,14 @@ impl StorageHasher for Blake2_128Concat {
 			.collect::<Vec<_>>()
 	}
 }
+impl ReversibleStorageHasher for Blake2_128Concat {
+	fn reverse(x: &[u8]) -> &[u8] {
+		if x.len() < 16 {
+			return &[]
+		}
+		&x[16..]
+	}
+}
 
 /// Hash storage keys with blake2 128
 pub struct Blake2_128;
@@ -134,4 +179,11 @@ mod tests {
 		let r = Blake2_128Concat::hash(b"foo");
 		assert_eq!(r.split_at(16), (&blake2_128(b"foo")[..], &b"foo"[..]))
 	}
+
+	#[test]
+	fn test_reverse() {
+		assert_eq!(Identity::reverse(&Identity::hash(b"foo")), &b"foo"[..]);
+		assert_eq!(Twox64Concat::reverse(&Twox64Concat::hash(b"foo")), &b"foo"[..]);
+		assert_eq!(Blake2_128Concat::reverse(&Blake2_128Concat::hash(b"foo")), &b"foo"[..]);
+	}
 }
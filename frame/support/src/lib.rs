@@ -67,11 +67,13 @@ pub mod traits;
 pub mod weights;
 
 pub use self::hash::{
-	Twox256, Twox128, Blake2_256, Blake2_128, Twox64Concat, Blake2_128Concat, Hashable,
-	StorageHasher
+	Twox256, Twox128, Blake2_256, Blake2_128, Identity, Twox64Concat, Blake2_128Concat, Hashable,
+	StorageHasher, ReversibleStorageHasher
 };
 pub use self::storage::{
-	StorageValue, StorageMap, StorageLinkedMap, StorageDoubleMap, StoragePrefixedMap
+	StorageValue, StorageMap, StorageLinkedMap, StorageDoubleMap, StorageNMap, StoragePrefixedMap,
+	IterableStorageMap, IterableStorageDoubleMap, IterableStorageNMap,
+	bounded_vec::BoundedVec, bounded_btree_map::BoundedBTreeMap,
 };
 pub use self::dispatch::{Parameter, Callable, IsSubType};
 pub use sp_runtime::{self, ConsensusEngineId, print, traits::Printable};
@@ -91,6 +93,9 @@ pub use sp_runtime::{self, ConsensusEngineId, print, traits::Printable};
 ///   type Parameter = Argument;
 /// }
 /// ```
+///
+/// The generated types derive `Clone`, `Copy`, `PartialEq`, `Eq`, `Default` and `Debug`, so that
+/// they can be used as the bound of a `BoundedVec` inside types that derive these traits.
 #[macro_export]
 macro_rules! parameter_types {
 	(
@@ -99,6 +104,7 @@ macro_rules! parameter_types {
 		$( $rest:tt )*
 	) => (
 		$( #[ $attr ] )*
+		#[derive(Clone, Copy, PartialEq, Eq, Default, $crate::RuntimeDebug)]
 		$vis struct $name;
 		$crate::parameter_types!{IMPL $name , $type , $value}
 		$crate::parameter_types!{ $( $rest )* }
@@ -128,6 +134,7 @@ macro_rules! ord_parameter_types {
 		$( $rest:tt )*
 	) => (
 		$( #[ $attr ] )*
+		#[derive(Clone, Copy, PartialEq, Eq, Default, $crate::RuntimeDebug)]
 		$vis struct $name;
 		$crate::parameter_types!{IMPL $name , $type , $value}
 		$crate::ord_parameter_types!{IMPL $name , $type , $value}
@@ -203,12 +210,16 @@ macro_rules! assert_err {
 /// Panic if an expression doesn't evaluate to `Ok`.
 ///
 /// Used as `assert_ok!(expression_to_assert, expected_ok_expression)`,
-/// or `assert_ok!(expression_to_assert)` which would assert against `Ok(())`.
+/// or `assert_ok!(expression_to_assert)` which would assert against any `Ok(_)`.
 #[macro_export]
 #[cfg(feature = "std")]
 macro_rules! assert_ok {
 	( $x:expr $(,)? ) => {
-		assert_eq!($x, Ok(()));
+		let is = $x;
+		match is {
+			Ok(_) => (),
+			_ => assert!(false, "Expected Ok(_). Got {:#?}", is),
+		}
 	};
 	( $x:expr, $y:expr $(,)? ) => {
 		assert_eq!($x, Ok($y));
@@ -227,10 +238,10 @@ pub use serde::{Serialize, Deserialize};
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use codec::{Codec, EncodeLike};
+	use codec::{Codec, Encode, EncodeLike};
 	use frame_metadata::{
 		DecodeDifferent, StorageEntryMetadata, StorageMetadata, StorageEntryType,
-		StorageEntryModifier, DefaultByteGetter, StorageHasher,
+		StorageEntryModifier, DefaultByteGetter, StorageHasher, StorageBoundGetter,
 	};
 	use sp_std::marker::PhantomData;
 
@@ -271,9 +282,20 @@ mod tests {
 				=> Option<T::BlockNumber>;
 			pub AppendableDM:
 				double_map hasher(blake2_256) u32, hasher(blake2_256) T::BlockNumber => Vec<u32>;
+
+			pub GenericDataNM:
+				nmap hasher(blake2_128_concat) u32, hasher(identity) T::BlockNumber,
+					hasher(twox_64_concat) u16
+				=> Option<T::BlockNumber>;
+
+			pub BoundedData: BoundedVec<u32, MaxBoundedData>;
 		}
 	}
 
+	parameter_types! {
+		pub const MaxBoundedData: u32 = 4;
+	}
+
 	struct Test;
 	impl Trait for Test {
 		type BlockNumber = u32;
@@ -503,6 +525,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructData(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -517,6 +540,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructOptionLinkedMap(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -531,6 +555,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGenericData(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -545,6 +570,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGenericData2(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -554,6 +580,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGetterNoFnKeyword(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -569,6 +596,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructDataDM(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -584,6 +612,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGenericDataDM(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -599,6 +628,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGenericData2DM(PhantomData::<Test>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -614,6 +644,37 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGenericData2DM(PhantomData::<Test>))
 					),
+					bound: None,
+					documentation: DecodeDifferent::Encode(&[]),
+				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Encode("GenericDataNM"),
+					modifier: StorageEntryModifier::Optional,
+					ty: StorageEntryType::NMap {
+						keys: DecodeDifferent::Encode(&["u32", "T::BlockNumber", "u16"]),
+						hashers: DecodeDifferent::Encode(&[
+							StorageHasher::Blake2_128Concat,
+							StorageHasher::Identity,
+							StorageHasher::Twox64Concat,
+						]),
+						value: DecodeDifferent::Encode("T::BlockNumber"),
+					},
+					default: DecodeDifferent::Encode(
+						DefaultByteGetter(&__GetByteStructGenericDataNM(PhantomData::<Test>))
+					),
+					bound: None,
+					documentation: DecodeDifferent::Encode(&[]),
+				},
+				StorageEntryMetadata {
+					name: DecodeDifferent::Encode("BoundedData"),
+					modifier: StorageEntryModifier::Default,
+					ty: StorageEntryType::Plain(DecodeDifferent::Encode("BoundedVec<u32, MaxBoundedData>")),
+					default: DecodeDifferent::Encode(
+						DefaultByteGetter(&__GetByteStructBoundedData(PhantomData::<Test>))
+					),
+					bound: Some(DecodeDifferent::Encode(
+						StorageBoundGetter(&__GetBoundStructBoundedData(PhantomData::<Test>))
+					)),
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 			]
@@ -625,4 +686,16 @@ mod tests {
 		let metadata = Module::<Test>::storage_metadata();
 		pretty_assertions::assert_eq!(EXPECTED_METADATA, metadata);
 	}
+
+	#[test]
+	fn bounded_storage_metadata_exposes_the_bound() {
+		let entries = match Module::<Test>::storage_metadata().entries {
+			DecodeDifferent::Encode(entries) => entries,
+			DecodeDifferent::Decoded(_) => unreachable!(),
+		};
+		let bound = entries.iter()
+			.find(|entry| entry.name == DecodeDifferent::Encode("BoundedData"))
+			.and_then(|entry| entry.bound.clone());
+		assert_eq!(bound.encode(), Some(MaxBoundedData::get()).encode());
+	}
 }
@@ -18,7 +18,7 @@ pub use frame_metadata::{
 	DecodeDifferent, FnEncode, RuntimeMetadata, ModuleMetadata, RuntimeMetadataLastVersion,
 	DefaultByteGetter, RuntimeMetadataPrefixed, StorageEntryMetadata, StorageMetadata,
 	StorageEntryType, StorageEntryModifier, DefaultByte, StorageHasher, ModuleErrorMetadata,
-	ExtrinsicMetadata,
+	ExtrinsicMetadata, StorageBound, StorageBoundGetter,
 };
 
 /// Implements the metadata support for the given runtime and all its modules.
@@ -256,7 +256,7 @@ mod tests {
 	struct TestExtension;
 	impl sp_runtime::traits::SignedExtension for TestExtension {
 		type AccountId = u32;
-		type Call = u32;
+		type Call = ();
 		type AdditionalSigned = u32;
 		type DispatchInfo = ();
 		type Pre = ();
@@ -270,7 +270,7 @@ mod tests {
 	struct TestExtension2;
 	impl sp_runtime::traits::SignedExtension for TestExtension2 {
 		type AccountId = u32;
-		type Call = u32;
+		type Call = ();
 		type AdditionalSigned = u32;
 		type DispatchInfo = ();
 		type Pre = ();
@@ -570,6 +570,7 @@ mod tests {
 												)
 											)
 										),
+										bound: None,
 										documentation: DecodeDifferent::Encode(&[]),
 									}
 								]
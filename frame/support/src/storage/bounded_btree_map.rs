@@ -0,0 +1,200 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A map with a length bound, to be used in storage.
+//!
+//! A [`BoundedBTreeMap`](struct.BoundedBTreeMap.html) has the same encoding as a `BTreeMap`.
+
+use sp_std::{
+	borrow::Borrow, collections::btree_map::BTreeMap, convert::TryFrom, fmt, marker::PhantomData,
+	ops::Deref,
+};
+use codec::{Encode, Decode, EncodeLike, Input};
+use crate::{traits::Get, storage::Bounded};
+
+/// A `BTreeMap` which can never hold more than `S::get()` entries.
+///
+/// Inserting a new key or decoding a map beyond the bound fails. Read access is available through
+/// `Deref<Target = BTreeMap<K, V>>`, mutation only through the methods which check the bound.
+pub struct BoundedBTreeMap<K, V, S>(BTreeMap<K, V>, PhantomData<S>);
+
+impl<K: Ord, V, S: Get<u32>> BoundedBTreeMap<K, V, S> {
+	/// The maximum number of entries this map can hold.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Insert `value` under `key`, returning the value previously there.
+	///
+	/// Replacing the value of an existing key always succeeds; adding a new key fails if the map
+	/// is already full.
+	pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, ()> {
+		if self.0.len() < Self::bound() || self.0.contains_key(&key) {
+			Ok(self.0.insert(key, value))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<K: Ord, V, S> BoundedBTreeMap<K, V, S> {
+	/// Consume self and return the inner `BTreeMap`.
+	pub fn into_inner(self) -> BTreeMap<K, V> {
+		self.0
+	}
+
+	/// Remove `key` from the map, returning its value if it was there.
+	pub fn remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+		self.0.remove(key)
+	}
+
+	/// A mutable reference to the value under `key`, if any.
+	pub fn get_mut<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+		self.0.get_mut(key)
+	}
+}
+
+impl<K, V, S: Get<u32>> Bounded for BoundedBTreeMap<K, V, S> {
+	fn bound() -> u32 {
+		S::get()
+	}
+}
+
+impl<K: Ord, V, S: Get<u32>> TryFrom<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {
+	type Error = ();
+
+	fn try_from(map: BTreeMap<K, V>) -> Result<Self, Self::Error> {
+		if map.len() <= Self::bound() {
+			Ok(BoundedBTreeMap(map, PhantomData))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<K, V, S> From<BoundedBTreeMap<K, V, S>> for BTreeMap<K, V> {
+	fn from(map: BoundedBTreeMap<K, V, S>) -> Self {
+		map.0
+	}
+}
+
+impl<K: Ord, V, S> Default for BoundedBTreeMap<K, V, S> {
+	fn default() -> Self {
+		BoundedBTreeMap(BTreeMap::new(), PhantomData)
+	}
+}
+
+impl<K: Clone, V: Clone, S> Clone for BoundedBTreeMap<K, V, S> {
+	fn clone(&self) -> Self {
+		BoundedBTreeMap(self.0.clone(), PhantomData)
+	}
+}
+
+impl<K: PartialEq, V: PartialEq, S> PartialEq for BoundedBTreeMap<K, V, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<K: Eq, V: Eq, S> Eq for BoundedBTreeMap<K, V, S> {}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for BoundedBTreeMap<K, V, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<K, V, S> Deref for BoundedBTreeMap<K, V, S> {
+	type Target = BTreeMap<K, V>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<K, V, S> IntoIterator for BoundedBTreeMap<K, V, S> {
+	type Item = (K, V);
+	type IntoIter = sp_std::collections::btree_map::IntoIter<K, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<K: Encode, V: Encode, S> Encode for BoundedBTreeMap<K, V, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<K: Encode, V: Encode, S> EncodeLike for BoundedBTreeMap<K, V, S> {}
+impl<K: Encode, V: Encode, S> EncodeLike<BTreeMap<K, V>> for BoundedBTreeMap<K, V, S> {}
+
+impl<K: Decode + Ord, V: Decode, S: Get<u32>> Decode for BoundedBTreeMap<K, V, S> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let inner = BTreeMap::<K, V>::decode(input)?;
+		Self::try_from(inner).map_err(|_| "BoundedBTreeMap exceeds its limit".into())
+	}
+}
+
+impl<K, V, S> codec::DecodeLength for BoundedBTreeMap<K, V, S> {
+	fn len(self_encoded: &[u8]) -> Result<usize, codec::Error> {
+		<BTreeMap<K, V> as codec::DecodeLength>::len(self_encoded)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parameter_types;
+
+	parameter_types! {
+		pub const Three: u32 = 3;
+	}
+
+	type Bounded3 = BoundedBTreeMap<u32, u32, Three>;
+
+	fn map_of(len: u32) -> BTreeMap<u32, u32> {
+		(0..len).map(|i| (i, i)).collect()
+	}
+
+	#[test]
+	fn try_insert_respects_the_bound() {
+		let mut m = Bounded3::try_from(map_of(2)).unwrap();
+		assert_eq!(m.try_insert(2, 2), Ok(None));
+		assert_eq!(m.try_insert(3, 3), Err(()));
+		// Overwriting an existing key does not grow the map.
+		assert_eq!(m.try_insert(2, 20), Ok(Some(2)));
+
+		assert_eq!(m.remove(&0), Some(0));
+		assert_eq!(m.try_insert(3, 3), Ok(None));
+		assert_eq!(m.len(), Three::get() as usize);
+	}
+
+	#[test]
+	fn encodes_like_btree_map_and_decode_respects_the_bound() {
+		let m = Bounded3::try_from(map_of(3)).unwrap();
+		assert_eq!(m.encode(), map_of(3).encode());
+		assert_eq!(Bounded3::decode(&mut &m.encode()[..]), Ok(m));
+
+		assert!(Bounded3::try_from(map_of(4)).is_err());
+		assert!(Bounded3::decode(&mut &map_of(4).encode()[..]).is_err());
+	}
+}
@@ -0,0 +1,264 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A vector with a length bound, to be used in storage.
+//!
+//! A [`BoundedVec`](struct.BoundedVec.html) has the same encoding as a `Vec`, so an existing
+//! storage item can switch to it without a migration as long as the stored values respect the
+//! bound.
+
+use sp_std::{prelude::*, convert::TryFrom, fmt, marker::PhantomData, ops::Deref};
+use codec::{Encode, Decode, EncodeLike, Input};
+use crate::{traits::Get, storage::Bounded};
+
+/// A `Vec` which can never hold more than `S::get()` elements.
+///
+/// Pushing an element or decoding a vector beyond the bound fails. Read access is available
+/// through `Deref<Target = Vec<T>>`, mutation only through the methods which check the bound.
+pub struct BoundedVec<T, S>(Vec<T>, PhantomData<S>);
+
+impl<T, S: Get<u32>> BoundedVec<T, S> {
+	/// The maximum number of elements this vector can hold.
+	pub fn bound() -> usize {
+		S::get() as usize
+	}
+
+	/// Create from `v`, dropping any elements beyond the bound.
+	pub fn truncate_from(mut v: Vec<T>) -> Self {
+		v.truncate(Self::bound());
+		BoundedVec(v, PhantomData)
+	}
+
+	/// Append `element`, failing if the vector is already full.
+	pub fn try_push(&mut self, element: T) -> Result<(), ()> {
+		if self.0.len() < Self::bound() {
+			self.0.push(element);
+			Ok(())
+		} else {
+			Err(())
+		}
+	}
+
+	/// Insert `element` at `index`, failing if the vector is already full.
+	///
+	/// Panics if `index > len`.
+	pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), ()> {
+		if self.0.len() < Self::bound() {
+			self.0.insert(index, element);
+			Ok(())
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T, S> BoundedVec<T, S> {
+	/// Consume self and return the inner `Vec`.
+	pub fn into_inner(self) -> Vec<T> {
+		self.0
+	}
+
+	/// Remove and return the element at `index`, shifting all elements after it.
+	///
+	/// Panics if `index` is out of bounds.
+	pub fn remove(&mut self, index: usize) -> T {
+		self.0.remove(index)
+	}
+
+	/// Remove and return the element at `index`, replacing it with the last element.
+	///
+	/// Panics if `index` is out of bounds.
+	pub fn swap_remove(&mut self, index: usize) -> T {
+		self.0.swap_remove(index)
+	}
+
+	/// Remove and return the last element, if any.
+	pub fn pop(&mut self) -> Option<T> {
+		self.0.pop()
+	}
+
+	/// Shorten the vector to `len` elements, dropping the rest.
+	pub fn truncate(&mut self, len: usize) {
+		self.0.truncate(len)
+	}
+
+	/// Retain only the elements specified by the predicate.
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+		self.0.retain(f)
+	}
+
+	/// A mutable reference to the element at `index`, if any.
+	pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+		self.0.get_mut(index)
+	}
+
+	/// Sort the vector with the given comparator.
+	pub fn sort_by<F: FnMut(&T, &T) -> sp_std::cmp::Ordering>(&mut self, compare: F) {
+		self.0.sort_by(compare)
+	}
+}
+
+impl<T, S: Get<u32>> Bounded for BoundedVec<T, S> {
+	fn bound() -> u32 {
+		S::get()
+	}
+}
+
+impl<T, S: Get<u32>> TryFrom<Vec<T>> for BoundedVec<T, S> {
+	type Error = ();
+
+	fn try_from(v: Vec<T>) -> Result<Self, Self::Error> {
+		if v.len() <= Self::bound() {
+			Ok(BoundedVec(v, PhantomData))
+		} else {
+			Err(())
+		}
+	}
+}
+
+impl<T, S> From<BoundedVec<T, S>> for Vec<T> {
+	fn from(v: BoundedVec<T, S>) -> Self {
+		v.0
+	}
+}
+
+impl<T, S> Default for BoundedVec<T, S> {
+	fn default() -> Self {
+		BoundedVec(Vec::new(), PhantomData)
+	}
+}
+
+impl<T: Clone, S> Clone for BoundedVec<T, S> {
+	fn clone(&self) -> Self {
+		BoundedVec(self.0.clone(), PhantomData)
+	}
+}
+
+impl<T: PartialEq, S> PartialEq for BoundedVec<T, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl<T: PartialEq, S> PartialEq<Vec<T>> for BoundedVec<T, S> {
+	fn eq(&self, other: &Vec<T>) -> bool {
+		&self.0 == other
+	}
+}
+
+impl<T: Eq, S> Eq for BoundedVec<T, S> {}
+
+impl<T: fmt::Debug, S> fmt::Debug for BoundedVec<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<T, S> Deref for BoundedVec<T, S> {
+	type Target = Vec<T>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<T, S> IntoIterator for BoundedVec<T, S> {
+	type Item = T;
+	type IntoIter = sp_std::vec::IntoIter<T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<'a, T, S> IntoIterator for &'a BoundedVec<T, S> {
+	type Item = &'a T;
+	type IntoIter = sp_std::slice::Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter()
+	}
+}
+
+impl<T: Encode, S> Encode for BoundedVec<T, S> {
+	fn size_hint(&self) -> usize {
+		self.0.size_hint()
+	}
+
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		self.0.using_encoded(f)
+	}
+}
+
+impl<T: Encode, S> EncodeLike for BoundedVec<T, S> {}
+impl<T: Encode, S> EncodeLike<Vec<T>> for BoundedVec<T, S> {}
+
+impl<T: Decode, S: Get<u32>> Decode for BoundedVec<T, S> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let inner = Vec::<T>::decode(input)?;
+		Self::try_from(inner).map_err(|_| "BoundedVec exceeds its limit".into())
+	}
+}
+
+impl<T, S> codec::DecodeLength for BoundedVec<T, S> {
+	fn len(self_encoded: &[u8]) -> Result<usize, codec::Error> {
+		<Vec<T> as codec::DecodeLength>::len(self_encoded)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parameter_types;
+
+	parameter_types! {
+		pub const Seven: u32 = 7;
+	}
+
+	type Bounded7 = BoundedVec<u32, Seven>;
+
+	#[test]
+	fn try_push_and_try_insert_respect_the_bound() {
+		let mut v = Bounded7::try_from(vec![1, 2, 3, 4, 5, 6]).unwrap();
+		assert_eq!(v.try_insert(0, 0), Ok(()));
+		assert_eq!(v.try_push(7), Err(()));
+		assert_eq!(v.try_insert(3, 7), Err(()));
+		assert_eq!(v, vec![0, 1, 2, 3, 4, 5, 6]);
+
+		v.remove(0);
+		assert_eq!(v.try_push(7), Ok(()));
+		assert_eq!(v.into_inner(), vec![1, 2, 3, 4, 5, 6, 7]);
+	}
+
+	#[test]
+	fn try_from_respects_the_bound() {
+		assert_eq!(Bounded7::bound(), Seven::get() as usize);
+		assert!(Bounded7::try_from(vec![0; 7]).is_ok());
+		assert!(Bounded7::try_from(vec![0; 8]).is_err());
+		assert_eq!(Bounded7::truncate_from((0..10).collect()), vec![0, 1, 2, 3, 4, 5, 6]);
+	}
+
+	#[test]
+	fn encodes_like_vec_and_decode_respects_the_bound() {
+		let v = Bounded7::try_from(vec![1, 2, 3]).unwrap();
+		assert_eq!(v.encode(), vec![1u32, 2, 3].encode());
+		assert_eq!(Bounded7::decode(&mut &v.encode()[..]), Ok(v));
+		assert_eq!(<Bounded7 as codec::DecodeLength>::len(&vec![0u32; 7].encode()), Ok(7));
+
+		assert!(Bounded7::decode(&mut &vec![0u32; 7].encode()[..]).is_ok());
+		assert!(Bounded7::decode(&mut &vec![0u32; 8].encode()[..]).is_err());
+	}
+}
@@ -16,8 +16,12 @@
 
 use sp_std::prelude::*;
 use sp_std::borrow::Borrow;
-use codec::{Ref, FullCodec, FullEncode, Encode, EncodeLike, EncodeAppend};
-use crate::{storage::{self, unhashed}, hash::{StorageHasher, Twox128}, traits::Len};
+use codec::{Ref, FullCodec, FullEncode, Decode, Encode, EncodeLike, EncodeAppend};
+use crate::{
+	storage::{self, unhashed, generator::StorageMapIterator},
+	hash::{StorageHasher, Twox128, ReversibleStorageHasher},
+	traits::Len,
+};
 
 /// Generator for `StorageDoubleMap` used by `decl_storage`.
 ///
@@ -61,6 +65,21 @@ pub trait StorageDoubleMap<K1: FullEncode, K2: FullEncode, V: FullCodec> {
 	/// Convert a query to an optional value into storage.
 	fn from_query_to_optional_value(v: Self::Query) -> Option<V>;
 
+	/// Generate the prefix shared by the final keys of all elements of the map.
+	fn prefix_hash() -> Vec<u8> {
+		let module_prefix_hashed = Twox128::hash(Self::module_prefix());
+		let storage_prefix_hashed = Twox128::hash(Self::storage_prefix());
+
+		let mut result = Vec::with_capacity(
+			module_prefix_hashed.len() + storage_prefix_hashed.len()
+		);
+
+		result.extend_from_slice(&module_prefix_hashed[..]);
+		result.extend_from_slice(&storage_prefix_hashed[..]);
+
+		result
+	}
+
 	/// Generate the first part of the key used in top storage.
 	fn storage_double_map_final_key1<KArg1>(k1: KArg1) -> Vec<u8>
 	where
@@ -181,7 +200,7 @@ where
 		unhashed::kill_prefix(Self::storage_double_map_final_key1(k1).as_ref())
 	}
 
-	fn iter_prefix<KArg1>(k1: KArg1) -> storage::PrefixIterator<V>
+	fn iter_prefix_values<KArg1>(k1: KArg1) -> storage::PrefixIterator<V>
 		where KArg1: ?Sized + EncodeLike<K1>
 	{
 		let prefix = Self::storage_double_map_final_key1(k1);
@@ -278,6 +297,134 @@ where
 	}
 }
 
+/// Iterator over the `(key1, key2, value)` triples of a double map whose hashers are reversible.
+pub struct StorageDoubleMapIterator<K1, K2, V, Hasher1, Hasher2> {
+	prefix: Vec<u8>,
+	previous_key: Vec<u8>,
+	drain: bool,
+	_phantom: ::sp_std::marker::PhantomData<(K1, K2, V, Hasher1, Hasher2)>,
+}
+
+impl<
+	K1: Decode + Sized,
+	K2: Decode + Sized,
+	V: Decode + Sized,
+	Hasher1: ReversibleStorageHasher,
+	Hasher2: ReversibleStorageHasher,
+> Iterator for StorageDoubleMapIterator<K1, K2, V, Hasher1, Hasher2> {
+	type Item = (K1, K2, V);
+
+	fn next(&mut self) -> Option<(K1, K2, V)> {
+		loop {
+			let maybe_next = sp_io::storage::next_key(&self.previous_key)
+				.filter(|n| n.starts_with(&self.prefix));
+			break match maybe_next {
+				Some(next) => {
+					self.previous_key = next;
+					match unhashed::get::<V>(&self.previous_key) {
+						Some(value) => {
+							if self.drain {
+								unhashed::kill(&self.previous_key)
+							}
+							match decode_double_map_key::<K1, K2, Hasher1, Hasher2>(
+								&self.previous_key[self.prefix.len()..]
+							) {
+								Some((k1, k2)) => Some((k1, k2, value)),
+								None => continue,
+							}
+						}
+						None => continue,
+					}
+				}
+				None => None,
+			}
+		}
+	}
+}
+
+/// Decode both keys out of the hashed part of a double map final key.
+fn decode_double_map_key<K1, K2, Hasher1, Hasher2>(hashed_keys: &[u8]) -> Option<(K1, K2)>
+where
+	K1: Decode,
+	K2: Decode,
+	Hasher1: ReversibleStorageHasher,
+	Hasher2: ReversibleStorageHasher,
+{
+	let mut key_material = Hasher1::reverse(hashed_keys);
+	let k1 = K1::decode(&mut key_material).ok()?;
+	let mut key2_material = Hasher2::reverse(key_material);
+	let k2 = K2::decode(&mut key2_material).ok()?;
+	Some((k1, k2))
+}
+
+impl<
+	K1: FullCodec,
+	K2: FullCodec,
+	V: FullCodec,
+	G: StorageDoubleMap<K1, K2, V>,
+> storage::IterableStorageDoubleMap<K1, K2, V> for G where
+	G::Hasher1: ReversibleStorageHasher,
+	G::Hasher2: ReversibleStorageHasher
+{
+	type PrefixIterator = StorageMapIterator<K2, V, G::Hasher2>;
+	type Iterator = StorageDoubleMapIterator<K1, K2, V, G::Hasher1, G::Hasher2>;
+
+	fn iter_prefix(k1: impl EncodeLike<K1>) -> Self::PrefixIterator {
+		StorageMapIterator::new(G::storage_double_map_final_key1(k1), false)
+	}
+
+	fn drain_prefix(k1: impl EncodeLike<K1>) -> Self::PrefixIterator {
+		StorageMapIterator::new(G::storage_double_map_final_key1(k1), true)
+	}
+
+	fn iter() -> Self::Iterator {
+		let prefix = G::prefix_hash();
+		StorageDoubleMapIterator {
+			previous_key: prefix.clone(),
+			prefix,
+			drain: false,
+			_phantom: Default::default(),
+		}
+	}
+
+	fn drain() -> Self::Iterator {
+		let prefix = G::prefix_hash();
+		StorageDoubleMapIterator {
+			previous_key: prefix.clone(),
+			prefix,
+			drain: true,
+			_phantom: Default::default(),
+		}
+	}
+
+	fn translate<O: Decode, F: Fn(K1, K2, O) -> Option<V>>(f: F) {
+		let prefix = G::prefix_hash();
+		let mut previous_key = prefix.clone();
+		loop {
+			match sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix)) {
+				Some(next) => {
+					previous_key = next;
+					let value = match unhashed::get::<O>(&previous_key) {
+						Some(value) => value,
+						None => continue,
+					};
+					let (k1, k2) = match decode_double_map_key::<K1, K2, G::Hasher1, G::Hasher2>(
+						&previous_key[prefix.len()..]
+					) {
+						Some(keys) => keys,
+						None => continue,
+					};
+					match f(k1, k2, value) {
+						Some(new) => unhashed::put::<V>(&previous_key, &new),
+						None => unhashed::kill(&previous_key),
+					}
+				}
+				None => return,
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use sp_io::TestExternalities;
@@ -285,7 +432,7 @@ mod test {
 	use crate::hash::Twox128;
 
 	#[test]
-	fn iter_prefix_works() {
+	fn iter_prefix_values_works() {
 		TestExternalities::default().execute_with(|| {
 			struct MyStorage;
 			impl storage::generator::StorageDoubleMap<u64, u64, u64> for MyStorage {
@@ -303,8 +450,8 @@ mod test {
 			MyStorage::insert(2, 5, 9);
 			MyStorage::insert(2, 6, 10);
 
-			assert_eq!(MyStorage::iter_prefix(1).collect::<Vec<_>>(), vec![7, 8]);
-			assert_eq!(MyStorage::iter_prefix(2).collect::<Vec<_>>(), vec![10, 9]);
+			assert_eq!(MyStorage::iter_prefix_values(1).collect::<Vec<_>>(), vec![7, 8]);
+			assert_eq!(MyStorage::iter_prefix_values(2).collect::<Vec<_>>(), vec![10, 9]);
 		});
 	}
 }
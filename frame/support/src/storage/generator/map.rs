@@ -17,8 +17,12 @@
 #[cfg(not(feature = "std"))]
 use sp_std::prelude::*;
 use sp_std::borrow::Borrow;
-use codec::{FullCodec, FullEncode, Encode, EncodeLike, Ref, EncodeAppend};
-use crate::{storage::{self, unhashed}, hash::{StorageHasher, Twox128}, traits::Len};
+use codec::{FullCodec, FullEncode, Decode, Encode, EncodeLike, Ref, EncodeAppend};
+use crate::{
+	storage::{self, unhashed},
+	hash::{StorageHasher, Twox128, ReversibleStorageHasher},
+	traits::Len,
+};
 
 /// Generator for `StorageMap` used by `decl_storage`.
 ///
@@ -50,6 +54,21 @@ pub trait StorageMap<K: FullEncode, V: FullCodec> {
 	/// Convert a query to an optional value into storage.
 	fn from_query_to_optional_value(v: Self::Query) -> Option<V>;
 
+	/// Generate the prefix shared by the final keys of all elements of the map.
+	fn prefix_hash() -> Vec<u8> {
+		let module_prefix_hashed = Twox128::hash(Self::module_prefix());
+		let storage_prefix_hashed = Twox128::hash(Self::storage_prefix());
+
+		let mut result = Vec::with_capacity(
+			module_prefix_hashed.len() + storage_prefix_hashed.len()
+		);
+
+		result.extend_from_slice(&module_prefix_hashed[..]);
+		result.extend_from_slice(&storage_prefix_hashed[..]);
+
+		result
+	}
+
 	/// Generate the full key used in top storage.
 	fn storage_map_final_key<KeyArg>(key: KeyArg) -> Vec<u8>
 	where
@@ -71,6 +90,106 @@ pub trait StorageMap<K: FullEncode, V: FullCodec> {
 	}
 }
 
+/// Iterator over the `(key, value)` pairs of a map whose hasher is reversible.
+pub struct StorageMapIterator<K, V, Hasher> {
+	prefix: Vec<u8>,
+	previous_key: Vec<u8>,
+	drain: bool,
+	_phantom: ::sp_std::marker::PhantomData<(K, V, Hasher)>,
+}
+
+impl<K, V, Hasher> StorageMapIterator<K, V, Hasher> {
+	/// Create an iterator over all elements whose final key starts with `prefix`.
+	///
+	/// If `drain` is `true`, each element is removed from storage once it has been returned.
+	pub(crate) fn new(prefix: Vec<u8>, drain: bool) -> Self {
+		StorageMapIterator {
+			previous_key: prefix.clone(),
+			prefix,
+			drain,
+			_phantom: Default::default(),
+		}
+	}
+}
+
+impl<
+	K: Decode + Sized,
+	V: Decode + Sized,
+	Hasher: ReversibleStorageHasher
+> Iterator for StorageMapIterator<K, V, Hasher> {
+	type Item = (K, V);
+
+	fn next(&mut self) -> Option<(K, V)> {
+		loop {
+			let maybe_next = sp_io::storage::next_key(&self.previous_key)
+				.filter(|n| n.starts_with(&self.prefix));
+			break match maybe_next {
+				Some(next) => {
+					self.previous_key = next;
+					match unhashed::get::<V>(&self.previous_key) {
+						Some(value) => {
+							if self.drain {
+								unhashed::kill(&self.previous_key)
+							}
+							let mut key_material = Hasher::reverse(&self.previous_key[self.prefix.len()..]);
+							match K::decode(&mut key_material) {
+								Ok(key) => Some((key, value)),
+								Err(_) => continue,
+							}
+						}
+						None => continue,
+					}
+				}
+				None => None,
+			}
+		}
+	}
+}
+
+impl<
+	K: FullCodec,
+	V: FullCodec,
+	G: StorageMap<K, V>,
+> storage::IterableStorageMap<K, V> for G where
+	G::Hasher: ReversibleStorageHasher
+{
+	type Iterator = StorageMapIterator<K, V, G::Hasher>;
+
+	fn iter() -> Self::Iterator {
+		StorageMapIterator::new(G::prefix_hash(), false)
+	}
+
+	fn drain() -> Self::Iterator {
+		StorageMapIterator::new(G::prefix_hash(), true)
+	}
+
+	fn translate<O: Decode, F: Fn(K, O) -> Option<V>>(f: F) {
+		let prefix = G::prefix_hash();
+		let mut previous_key = prefix.clone();
+		loop {
+			match sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix)) {
+				Some(next) => {
+					previous_key = next;
+					let value = match unhashed::get::<O>(&previous_key) {
+						Some(value) => value,
+						None => continue,
+					};
+					let mut key_material = G::Hasher::reverse(&previous_key[prefix.len()..]);
+					let key = match K::decode(&mut key_material) {
+						Ok(key) => key,
+						Err(_) => continue,
+					};
+					match f(key, value) {
+						Some(new) => unhashed::put::<V>(&previous_key, &new),
+						None => unhashed::kill(&previous_key),
+					}
+				}
+				None => return,
+			}
+		}
+	}
+}
+
 impl<K: FullEncode, V: FullCodec, G: StorageMap<K, V>> storage::StorageMap<K, V> for G {
 	type Query = G::Query;
 
@@ -26,11 +26,13 @@
 mod linked_map;
 mod map;
 mod double_map;
+mod nmap;
 mod value;
 
 pub use linked_map::{StorageLinkedMap, Enumerator, Linkage, KeyFormat as LinkedMapKeyFormat};
-pub use map::StorageMap;
-pub use double_map::StorageDoubleMap;
+pub use map::{StorageMap, StorageMapIterator};
+pub use double_map::{StorageDoubleMap, StorageDoubleMapIterator};
+pub use nmap::{StorageNMap, StorageNMapIterator};
 pub use value::StorageValue;
 
 
@@ -39,7 +41,10 @@ pub use value::StorageValue;
 mod tests {
 	use sp_io::TestExternalities;
 	use codec::{Encode, Decode};
-	use crate::storage::{unhashed, generator::{StorageValue, StorageLinkedMap}};
+	use crate::storage::{
+		unhashed, IterableStorageMap, IterableStorageDoubleMap, IterableStorageNMap, StorageNMap,
+		generator::{StorageValue, StorageLinkedMap},
+	};
 
 	struct Runtime {}
 	pub trait Trait {
@@ -66,6 +71,15 @@ mod tests {
 		trait Store for Module<T: Trait> as Runtime {
 			Value get(fn value) config(): (u64, u64);
 			NumberMap: linked_map hasher(blake2_256) NumberNumber => u64;
+			IterableMap: map hasher(twox_64_concat) u32 => u64;
+			IterableDoubleMap:
+				double_map hasher(blake2_128_concat) u32, hasher(identity) u16 => u64;
+			TripleMap get(fn triple_map) config():
+				nmap hasher(blake2_128_concat) u32, hasher(twox_64_concat) u16, hasher(identity) u8
+				=> u64;
+			OpaqueTripleMap:
+				nmap hasher(blake2_256) u32, hasher(twox_64_concat) u16, hasher(blake2_128) u8
+				=> Option<u64>;
 		}
 	}
 
@@ -127,4 +141,166 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn iterable_map_works() {
+		let t = GenesisConfig::default().build_storage().unwrap();
+		TestExternalities::new(t).execute_with(|| {
+			for i in 0u32..10 {
+				IterableMap::insert(i, i as u64 * 10);
+			}
+
+			let mut elements = IterableMap::iter().collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(elements, (0u32..10).map(|i| (i, i as u64 * 10)).collect::<Vec<_>>());
+
+			// translate: remove odd keys and double the rest.
+			IterableMap::translate(|k: u32, v: u64| if k % 2 == 0 { Some(v * 2) } else { None });
+			let mut elements = IterableMap::iter().collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(
+				elements,
+				(0u32..10).filter(|i| i % 2 == 0).map(|i| (i, i as u64 * 20)).collect::<Vec<_>>(),
+			);
+
+			let mut drained = IterableMap::drain().collect::<Vec<_>>();
+			drained.sort();
+			assert_eq!(drained, elements);
+			assert_eq!(IterableMap::iter().count(), 0);
+		})
+	}
+
+	#[test]
+	fn iterable_double_map_works() {
+		let t = GenesisConfig::default().build_storage().unwrap();
+		TestExternalities::new(t).execute_with(|| {
+			for i in 0u32..3 {
+				for j in 0u16..3 {
+					IterableDoubleMap::insert(i, j, (i as u64) << 16 | j as u64);
+				}
+			}
+
+			let mut elements = IterableDoubleMap::iter().collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(elements.len(), 9);
+			assert_eq!(elements[4], (1, 1, 1 << 16 | 1));
+
+			let mut prefix = IterableDoubleMap::iter_prefix(2).collect::<Vec<_>>();
+			prefix.sort();
+			assert_eq!(prefix, vec![(0, 2 << 16), (1, 2 << 16 | 1), (2, 2 << 16 | 2)]);
+
+			let mut drained = IterableDoubleMap::drain_prefix(2).collect::<Vec<_>>();
+			drained.sort();
+			assert_eq!(drained, prefix);
+			assert_eq!(IterableDoubleMap::iter_prefix(2).count(), 0);
+			assert_eq!(IterableDoubleMap::iter().count(), 6);
+
+			IterableDoubleMap::translate(|k1: u32, _k2: u16, v: u64| {
+				if k1 == 0 { None } else { Some(v + 1) }
+			});
+			let mut elements = IterableDoubleMap::iter().collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(elements, vec![(1, 0, 1 << 16 | 1), (1, 1, 1 << 16 | 2), (1, 2, 1 << 16 | 3)]);
+
+			assert_eq!(IterableDoubleMap::drain().count(), 3);
+			assert_eq!(IterableDoubleMap::iter().count(), 0);
+		})
+	}
+
+	#[test]
+	fn take_linked_map_works() {
+		use crate::{hash::Blake2_256, storage::migration::take_linked_map};
+
+		let t = GenesisConfig::default().build_storage().unwrap();
+		TestExternalities::new(t).execute_with(|| {
+			for i in 0u32..10 {
+				NumberMap::insert(NumberNumber { a: i, b: i }, i as u64);
+			}
+			let expected = NumberMap::enumerate().collect::<Vec<_>>();
+
+			let taken = take_linked_map::<NumberNumber, u64, Blake2_256>(b"Runtime", b"NumberMap");
+			assert_eq!(taken, expected);
+			assert_eq!(NumberMap::enumerate().count(), 0);
+			assert!(!NumberMap::contains_key(NumberNumber { a: 0, b: 0 }));
+		})
+	}
+
+	#[test]
+	fn n_map_works() {
+		let t = GenesisConfig {
+			triple_map: vec![((1, 1, 1), 111), ((1, 2, 3), 123)],
+			..Default::default()
+		}.build_storage().unwrap();
+		TestExternalities::new(t).execute_with(|| {
+			assert_eq!(Module::<Runtime>::triple_map((1, 2, 3)), 123);
+			assert_eq!(TripleMap::get((&1, &1, &1)), 111);
+
+			for i in 0u32..2 {
+				for j in 0u16..2 {
+					for k in 0u8..2 {
+						TripleMap::insert((i, j, k), (i as u64) * 100 + (j as u64) * 10 + k as u64);
+					}
+				}
+			}
+			assert!(TripleMap::contains_key((1, 0, 1)));
+			assert_eq!(TripleMap::take((1, 0, 1)), 101);
+			assert!(!TripleMap::contains_key((1, 0, 1)));
+
+			TripleMap::mutate((0, 1, 1), |v| *v += 1000);
+			assert_eq!(TripleMap::get((0, 1, 1)), 1011);
+
+			TripleMap::swap((0, 1, 1), (1, 0, 1));
+			assert_eq!(TripleMap::get((1, 0, 1)), 1011);
+			assert!(!TripleMap::contains_key((0, 1, 1)));
+
+			let mut values = TripleMap::iter_prefix_values((1u32,)).collect::<Vec<_>>();
+			values.sort();
+			assert_eq!(values, vec![100, 110, 111, 123, 1011]);
+
+			let mut elements = TripleMap::iter_prefix((1u32, 1u16)).collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(elements, vec![((0,), 110), ((1,), 111)]);
+
+			let mut elements = TripleMap::iter_prefix((1u32,)).collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(elements, vec![((0, 0), 100), ((0, 1), 1011), ((1, 0), 110), ((1, 1), 111), ((2, 3), 123)]);
+
+			TripleMap::remove_prefix((1u32, 1u16));
+			assert_eq!(TripleMap::iter_prefix_values((1u32, 1u16)).count(), 0);
+
+			let mut drained = TripleMap::drain_prefix((1u32,)).collect::<Vec<_>>();
+			drained.sort();
+			assert_eq!(drained, vec![((0, 0), 100), ((0, 1), 1011), ((2, 3), 123)]);
+
+			TripleMap::translate(|(i, j, k): (u32, u16, u8), v: u64| {
+				if k == 0 { None } else { Some(v + (i as u64) + (j as u64)) }
+			});
+			let mut elements = TripleMap::iter().collect::<Vec<_>>();
+			elements.sort();
+			assert_eq!(elements, vec![((0, 0, 1), 1)]);
+
+			assert_eq!(TripleMap::drain().count(), 1);
+			assert_eq!(TripleMap::iter().count(), 0);
+		})
+	}
+
+	#[test]
+	fn n_map_without_reversible_hashers_works() {
+		let t = GenesisConfig::default().build_storage().unwrap();
+		TestExternalities::new(t).execute_with(|| {
+			OpaqueTripleMap::insert((1, 2, 3), 123);
+			OpaqueTripleMap::insert((1, 3, 3), 133);
+			OpaqueTripleMap::insert((2, 2, 3), 223);
+			assert_eq!(OpaqueTripleMap::get((1, 2, 3)), Some(123));
+			assert_eq!(OpaqueTripleMap::get((1, 2, 4)), None);
+
+			let mut values = OpaqueTripleMap::iter_prefix_values((1u32,)).collect::<Vec<_>>();
+			values.sort();
+			assert_eq!(values, vec![123, 133]);
+
+			OpaqueTripleMap::remove_prefix((1u32,));
+			assert_eq!(OpaqueTripleMap::iter_prefix_values((1u32,)).count(), 0);
+			assert_eq!(OpaqueTripleMap::get((2, 2, 3)), Some(223));
+		})
+	}
 }
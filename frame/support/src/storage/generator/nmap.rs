@@ -0,0 +1,302 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+#[cfg(not(feature = "std"))]
+use sp_std::prelude::*;
+use sp_std::borrow::Borrow;
+use codec::{FullCodec, Decode, EncodeLike};
+use crate::{
+	storage::{
+		self, unhashed,
+		key::{
+			KeyGenerator, ReversibleKeyGenerator, HasKeyPrefix, HasReversibleKeyPrefix,
+			EncodeLikeTuple, TupleToEncodedIter,
+		},
+	},
+	hash::{StorageHasher, Twox128},
+};
+
+/// Generator for `StorageNMap` used by `decl_storage`.
+///
+/// # Mapping of keys to a storage path
+///
+/// The storage key (i.e. the key under which the `Value` will be stored) is created from the
+/// module and storage prefixes followed by each part of the key hashed with its own hasher.
+///
+/// Thus value for (key1, key2, ..., keyN) is stored at:
+/// ```nocompile
+/// Twox128(module_prefix) ++ Twox128(storage_prefix)
+///     ++ Hasher1(encode(key1)) ++ Hasher2(encode(key2)) ++ ... ++ HasherN(encode(keyN))
+/// ```
+///
+/// # Warning
+///
+/// If a key part is not trusted (e.g. can be set by a user), a cryptographic `hasher` such as
+/// `blake2_128_concat` must be used for it. Otherwise, other items in storage sharing the
+/// preceding key parts can be compromised.
+pub trait StorageNMap<K: KeyGenerator, V: FullCodec> {
+	/// The type that get/take returns.
+	type Query;
+
+	/// Module prefix. Used for generating final key.
+	fn module_prefix() -> &'static [u8];
+
+	/// Storage prefix. Used for generating final key.
+	fn storage_prefix() -> &'static [u8];
+
+	/// Convert an optional value retrieved from storage to the type queried.
+	fn from_optional_value_to_query(v: Option<V>) -> Self::Query;
+
+	/// Convert a query to an optional value into storage.
+	fn from_query_to_optional_value(v: Self::Query) -> Option<V>;
+
+	/// Generate the prefix shared by the final keys of all elements of the map.
+	fn prefix_hash() -> Vec<u8> {
+		let module_prefix_hashed = Twox128::hash(Self::module_prefix());
+		let storage_prefix_hashed = Twox128::hash(Self::storage_prefix());
+
+		let mut result = Vec::with_capacity(
+			module_prefix_hashed.len() + storage_prefix_hashed.len()
+		);
+
+		result.extend_from_slice(&module_prefix_hashed[..]);
+		result.extend_from_slice(&storage_prefix_hashed[..]);
+
+		result
+	}
+
+	/// Generate the prefix shared by the final keys of all elements starting with `partial_key`.
+	fn storage_n_map_partial_key<KP>(partial_key: KP) -> Vec<u8> where K: HasKeyPrefix<KP> {
+		let mut final_key = Self::prefix_hash();
+		final_key.extend_from_slice(&K::partial_key(partial_key));
+		final_key
+	}
+
+	/// Generate the full key used in top storage.
+	fn storage_n_map_final_key<KArg>(key: KArg) -> Vec<u8>
+	where
+		KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+	{
+		let mut final_key = Self::prefix_hash();
+		final_key.extend_from_slice(&K::final_key(key));
+		final_key
+	}
+}
+
+impl<K: KeyGenerator, V: FullCodec, G: StorageNMap<K, V>> storage::StorageNMap<K, V> for G {
+	type Query = G::Query;
+
+	fn hashed_key_for<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> Vec<u8> {
+		Self::storage_n_map_final_key(key)
+	}
+
+	fn contains_key<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> bool {
+		unhashed::exists(&Self::storage_n_map_final_key(key))
+	}
+
+	fn get<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> Self::Query {
+		G::from_optional_value_to_query(unhashed::get(&Self::storage_n_map_final_key(key)))
+	}
+
+	fn take<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> Self::Query {
+		let final_key = Self::storage_n_map_final_key(key);
+
+		let value = unhashed::take(&final_key);
+		G::from_optional_value_to_query(value)
+	}
+
+	fn swap<KArg1, KArg2>(key1: KArg1, key2: KArg2)
+	where
+		KArg1: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+		KArg2: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+	{
+		let final_x_key = Self::storage_n_map_final_key(key1);
+		let final_y_key = Self::storage_n_map_final_key(key2);
+
+		let v1 = unhashed::get_raw(&final_x_key);
+		if let Some(val) = unhashed::get_raw(&final_y_key) {
+			unhashed::put_raw(&final_x_key, &val);
+		} else {
+			unhashed::kill(&final_x_key)
+		}
+		if let Some(val) = v1 {
+			unhashed::put_raw(&final_y_key, &val);
+		} else {
+			unhashed::kill(&final_y_key)
+		}
+	}
+
+	fn insert<KArg, VArg>(key: KArg, val: VArg)
+	where
+		KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+		VArg: EncodeLike<V>,
+	{
+		unhashed::put(&Self::storage_n_map_final_key(key), &val.borrow())
+	}
+
+	fn remove<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) {
+		unhashed::kill(&Self::storage_n_map_final_key(key))
+	}
+
+	fn remove_prefix<KP>(partial_key: KP) where K: HasKeyPrefix<KP> {
+		unhashed::kill_prefix(&Self::storage_n_map_partial_key(partial_key))
+	}
+
+	fn iter_prefix_values<KP>(partial_key: KP) -> storage::PrefixIterator<V>
+		where K: HasKeyPrefix<KP>
+	{
+		let prefix = Self::storage_n_map_partial_key(partial_key);
+		storage::PrefixIterator::<V> {
+			prefix: prefix.clone(),
+			previous_key: prefix,
+			phantom_data: Default::default(),
+		}
+	}
+
+	fn mutate<KArg, R, F>(key: KArg, f: F) -> R
+	where
+		KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+		F: FnOnce(&mut Self::Query) -> R,
+	{
+		let final_key = Self::storage_n_map_final_key(key);
+
+		let mut val = G::from_optional_value_to_query(unhashed::get(final_key.as_ref()));
+
+		let ret = f(&mut val);
+		match G::from_query_to_optional_value(val) {
+			Some(ref val) => unhashed::put(final_key.as_ref(), val),
+			None => unhashed::kill(final_key.as_ref()),
+		}
+		ret
+	}
+}
+
+/// Iterator over the `(key, value)` pairs of an N-map whose hashers are reversible.
+///
+/// `Key` is either the full key or the key parts following an iterated prefix.
+pub struct StorageNMapIterator<Key, V> {
+	prefix: Vec<u8>,
+	previous_key: Vec<u8>,
+	drain: bool,
+	decode_key: fn(&[u8]) -> Result<Key, codec::Error>,
+	_phantom: ::sp_std::marker::PhantomData<V>,
+}
+
+impl<Key, V> StorageNMapIterator<Key, V> {
+	fn new(prefix: Vec<u8>, drain: bool, decode_key: fn(&[u8]) -> Result<Key, codec::Error>) -> Self {
+		StorageNMapIterator {
+			previous_key: prefix.clone(),
+			prefix,
+			drain,
+			decode_key,
+			_phantom: Default::default(),
+		}
+	}
+}
+
+impl<Key, V: Decode> Iterator for StorageNMapIterator<Key, V> {
+	type Item = (Key, V);
+
+	fn next(&mut self) -> Option<(Key, V)> {
+		loop {
+			let maybe_next = sp_io::storage::next_key(&self.previous_key)
+				.filter(|n| n.starts_with(&self.prefix));
+			break match maybe_next {
+				Some(next) => {
+					self.previous_key = next;
+					match unhashed::get::<V>(&self.previous_key) {
+						Some(value) => {
+							if self.drain {
+								unhashed::kill(&self.previous_key)
+							}
+							match (self.decode_key)(&self.previous_key[self.prefix.len()..]) {
+								Ok(key) => Some((key, value)),
+								Err(_) => continue,
+							}
+						}
+						None => continue,
+					}
+				}
+				None => None,
+			}
+		}
+	}
+}
+
+fn decode_full_key<K: ReversibleKeyGenerator>(key_material: &[u8]) -> Result<K::Key, codec::Error> {
+	K::decode_final_key(key_material).map(|(key, _)| key)
+}
+
+impl<
+	K: ReversibleKeyGenerator,
+	V: FullCodec,
+	G: StorageNMap<K, V>,
+> storage::IterableStorageNMap<K, V> for G {
+	type Iterator = StorageNMapIterator<K::Key, V>;
+
+	fn iter_prefix<KP>(partial_key: KP) -> StorageNMapIterator<K::Suffix, V>
+		where K: HasReversibleKeyPrefix<KP>
+	{
+		StorageNMapIterator::new(
+			G::storage_n_map_partial_key(partial_key),
+			false,
+			K::decode_partial_key,
+		)
+	}
+
+	fn drain_prefix<KP>(partial_key: KP) -> StorageNMapIterator<K::Suffix, V>
+		where K: HasReversibleKeyPrefix<KP>
+	{
+		StorageNMapIterator::new(
+			G::storage_n_map_partial_key(partial_key),
+			true,
+			K::decode_partial_key,
+		)
+	}
+
+	fn iter() -> Self::Iterator {
+		StorageNMapIterator::new(G::prefix_hash(), false, decode_full_key::<K>)
+	}
+
+	fn drain() -> Self::Iterator {
+		StorageNMapIterator::new(G::prefix_hash(), true, decode_full_key::<K>)
+	}
+
+	fn translate<O: Decode, F: Fn(K::Key, O) -> Option<V>>(f: F) {
+		let prefix = G::prefix_hash();
+		let mut previous_key = prefix.clone();
+		loop {
+			match sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix)) {
+				Some(next) => {
+					previous_key = next;
+					let value = match unhashed::get::<O>(&previous_key) {
+						Some(value) => value,
+						None => continue,
+					};
+					let key = match decode_full_key::<K>(&previous_key[prefix.len()..]) {
+						Ok(key) => key,
+						Err(_) => continue,
+					};
+					match f(key, value) {
+						Some(new) => unhashed::put::<V>(&previous_key, &new),
+						None => unhashed::kill(&previous_key),
+					}
+				}
+				None => return,
+			}
+		}
+	}
+}
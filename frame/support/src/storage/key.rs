@@ -0,0 +1,241 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Key types for storage N-maps.
+//!
+//! The key of a `StorageNMap` is a tuple of [`Key`](struct.Key.html)s, each part of it being hashed
+//! with its own hasher. Implementations are provided for tuples of up to 8 keys.
+
+use sp_std::{prelude::*, vec, marker::PhantomData};
+use codec::{FullCodec, Encode, EncodeLike};
+use crate::hash::{StorageHasher, ReversibleStorageHasher};
+
+/// One part of the key of a storage N-map: a key of type `KeyType` hashed with `Hasher`.
+pub struct Key<Hasher, KeyType>(PhantomData<(Hasher, KeyType)>);
+
+/// A tuple of [`Key`](struct.Key.html)s which can be used as the key of a storage N-map.
+pub trait KeyGenerator {
+	/// The full key, a tuple of the types of all key parts.
+	type Key: FullCodec;
+
+	/// Hash each part of `key` with its hasher and concatenate the results.
+	fn final_key<KArg: EncodeLikeTuple<Self::Key> + TupleToEncodedIter>(key: KArg) -> Vec<u8>;
+}
+
+/// A [`KeyGenerator`](trait.KeyGenerator.html) whose hashers are all reversible, so that the full
+/// key can be decoded back from the final storage key.
+pub trait ReversibleKeyGenerator: KeyGenerator {
+	/// Decode the key out of the hashed key material, returning the unused remainder.
+	fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error>;
+}
+
+/// A [`KeyGenerator`](trait.KeyGenerator.html) whose first key parts can be given as `Prefix`, a
+/// tuple of their types.
+pub trait HasKeyPrefix<Prefix>: KeyGenerator {
+	/// The remaining key parts, as a tuple.
+	type Suffix;
+
+	/// Hash each part of `prefix` with its hasher and concatenate the results.
+	fn partial_key(prefix: Prefix) -> Vec<u8>;
+}
+
+/// A [`HasKeyPrefix`](trait.HasKeyPrefix.html) whose remaining key parts can be decoded back from
+/// the final storage key.
+pub trait HasReversibleKeyPrefix<Prefix>: ReversibleKeyGenerator + HasKeyPrefix<Prefix> {
+	/// Decode the remaining key parts out of the hashed key material following the prefix.
+	fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error>;
+}
+
+/// Marker for tuples whose elements each encode like the corresponding element of `T`.
+pub trait EncodeLikeTuple<T> {}
+
+impl<'a, T: EncodeLikeTuple<U>, U> EncodeLikeTuple<U> for &'a T {}
+
+/// A tuple whose elements can be encoded one by one.
+pub trait TupleToEncodedIter {
+	/// Encode each element of the tuple separately.
+	fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>>;
+}
+
+impl<'a, T: TupleToEncodedIter> TupleToEncodedIter for &'a T {
+	fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>> {
+		(**self).to_encoded_iter()
+	}
+}
+
+// Call `$m` for the given list of `(Hasher Key Arg)` identifiers and each of its suffixes.
+macro_rules! for_each_tuple {
+	($m:ident; $( $t:tt ),+) => {
+		$m!($( $t ),+);
+		for_each_tuple!(@rest $m; $( $t ),+);
+	};
+	(@rest $m:ident; $first:tt) => {};
+	(@rest $m:ident; $first:tt, $( $t:tt ),+) => {
+		for_each_tuple!($m; $( $t ),+);
+	};
+}
+
+macro_rules! impl_key_generator {
+	($( ($H:ident $K:ident $A:ident) ),+) => {
+		impl<$( $K: Encode, $A: EncodeLike<$K> ),+> EncodeLikeTuple<( $( $K, )+ )>
+			for ( $( $A, )+ )
+		{}
+
+		impl<$( $A: Encode ),+> TupleToEncodedIter for ( $( $A, )+ ) {
+			#[allow(non_snake_case)]
+			fn to_encoded_iter(&self) -> sp_std::vec::IntoIter<Vec<u8>> {
+				let ( $( $A, )+ ) = self;
+				vec![ $( $A.encode() ),+ ].into_iter()
+			}
+		}
+
+		impl<$( $H: StorageHasher, $K: FullCodec ),+> KeyGenerator for ( $( Key<$H, $K>, )+ ) {
+			type Key = ( $( $K, )+ );
+
+			fn final_key<KArg: EncodeLikeTuple<Self::Key> + TupleToEncodedIter>(
+				key: KArg,
+			) -> Vec<u8> {
+				let mut final_key = Vec::new();
+				let mut encoded = key.to_encoded_iter();
+				$(
+					let part = encoded.next()
+						.expect("`KArg` has as many elements as `Self::Key`; qed");
+					final_key.extend_from_slice(<$H as StorageHasher>::hash(&part).as_ref());
+				)+
+				final_key
+			}
+		}
+
+		impl<$( $H: ReversibleStorageHasher, $K: FullCodec ),+> ReversibleKeyGenerator
+			for ( $( Key<$H, $K>, )+ )
+		{
+			#[allow(non_snake_case)]
+			fn decode_final_key(key_material: &[u8]) -> Result<(Self::Key, &[u8]), codec::Error> {
+				let mut current = key_material;
+				$(
+					let mut material = <$H as ReversibleStorageHasher>::reverse(current);
+					let $K = <$K as codec::Decode>::decode(&mut material)?;
+					current = material;
+				)+
+				Ok((( $( $K, )+ ), current))
+			}
+		}
+
+		impl_key_prefixes!($( ($H $K $A) ),+);
+	};
+}
+
+// Implement `HasKeyPrefix` for every way of splitting the given key parts in a non empty prefix and
+// a non empty suffix.
+macro_rules! impl_key_prefixes {
+	($first:tt) => {};
+	($first:tt, $( $t:tt ),+) => {
+		impl_key_prefixes!(@split [$first] [$( $t ),+]);
+	};
+	(@split [$( $p:tt ),+] [$s:tt]) => {
+		impl_key_prefix!([$( $p ),+] [$s]);
+	};
+	(@split [$( $p:tt ),+] [$s0:tt, $( $s:tt ),+]) => {
+		impl_key_prefix!([$( $p ),+] [$s0, $( $s ),+]);
+		impl_key_prefixes!(@split [$( $p, )+ $s0] [$( $s ),+]);
+	};
+}
+
+macro_rules! impl_key_prefix {
+	(
+		[$( ($HP:ident $KP:ident $AP:ident) ),+]
+		[$( ($HS:ident $KS:ident $AS:ident) ),+]
+	) => {
+		impl<$( $HP: StorageHasher, $KP: FullCodec, )+ $( $HS: StorageHasher, $KS: FullCodec ),+>
+			HasKeyPrefix<( $( $KP, )+ )>
+			for ( $( Key<$HP, $KP>, )+ $( Key<$HS, $KS>, )+ )
+		{
+			type Suffix = ( $( $KS, )+ );
+
+			fn partial_key(prefix: ( $( $KP, )+ )) -> Vec<u8> {
+				<( $( Key<$HP, $KP>, )+ ) as KeyGenerator>::final_key(prefix)
+			}
+		}
+
+		impl<
+			$( $HP: ReversibleStorageHasher, $KP: FullCodec, )+
+			$( $HS: ReversibleStorageHasher, $KS: FullCodec ),+
+		>
+			HasReversibleKeyPrefix<( $( $KP, )+ )>
+			for ( $( Key<$HP, $KP>, )+ $( Key<$HS, $KS>, )+ )
+		{
+			fn decode_partial_key(key_material: &[u8]) -> Result<Self::Suffix, codec::Error> {
+				<( $( Key<$HS, $KS>, )+ ) as ReversibleKeyGenerator>::decode_final_key(key_material)
+					.map(|(suffix, _)| suffix)
+			}
+		}
+	};
+}
+
+for_each_tuple!(
+	impl_key_generator;
+	(H1 K1 A1), (H2 K2 A2), (H3 K3 A3), (H4 K4 A4),
+	(H5 K5 A5), (H6 K6 A6), (H7 K7 A7), (H8 K8 A8)
+);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::hash::{Blake2_128Concat, Twox64Concat, Identity};
+
+	type Keys = (Key<Blake2_128Concat, u32>, Key<Twox64Concat, u16>, Key<Identity, u8>);
+
+	#[test]
+	fn final_key_concatenates_hashed_parts() {
+		let final_key = Keys::final_key((1u32, 2u16, 3u8));
+		let expected = [
+			Blake2_128Concat::hash(&1u32.encode()),
+			Twox64Concat::hash(&2u16.encode()),
+			Identity::hash(&3u8.encode()),
+		].concat();
+		assert_eq!(final_key, expected);
+
+		// references encode like their values.
+		assert_eq!(Keys::final_key((&1u32, &2u16, &3u8)), expected);
+		assert_eq!(Keys::final_key(&(1u32, 2u16, 3u8)), expected);
+	}
+
+	#[test]
+	fn decode_final_key_works() {
+		let mut final_key = Keys::final_key((1u32, 2u16, 3u8));
+		final_key.extend_from_slice(b"rest");
+		assert_eq!(Keys::decode_final_key(&final_key).unwrap(), ((1, 2, 3), &b"rest"[..]));
+	}
+
+	#[test]
+	fn partial_keys_work() {
+		let final_key = Keys::final_key((1u32, 2u16, 3u8));
+
+		let prefix = <Keys as HasKeyPrefix<(u32,)>>::partial_key((1,));
+		assert!(final_key.starts_with(&prefix));
+		assert_eq!(
+			<Keys as HasReversibleKeyPrefix<(u32,)>>::decode_partial_key(&final_key[prefix.len()..]),
+			Ok((2, 3)),
+		);
+
+		let prefix = <Keys as HasKeyPrefix<(u32, u16)>>::partial_key((1, 2));
+		assert!(final_key.starts_with(&prefix));
+		assert_eq!(
+			<Keys as HasReversibleKeyPrefix<(u32, u16)>>::decode_partial_key(&final_key[prefix.len()..]),
+			Ok((3,)),
+		);
+	}
+}
@@ -0,0 +1,60 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for migrating storage items away from a previous layout.
+
+use sp_std::prelude::*;
+use codec::FullCodec;
+use crate::{
+	storage::{unhashed, generator::Linkage},
+	hash::{StorageHasher, Twox128},
+};
+
+/// Remove all elements of a (former) `linked_map` and return them in enumeration order.
+///
+/// `module` and `item` are the module prefix and the name the map was declared with in
+/// `decl_storage`, and `Hasher` is the hasher it used. The head of the map is removed as well, so
+/// that a new storage item can be declared under the same name.
+///
+/// Elements which are not reachable from the head of the map are removed without being returned.
+pub fn take_linked_map<K, V, Hasher>(module: &[u8], item: &[u8]) -> Vec<(K, V)>
+where
+	K: FullCodec,
+	V: FullCodec,
+	Hasher: StorageHasher,
+{
+	let prefix = [Twox128::hash(module), Twox128::hash(item)].concat();
+	let head_key = [
+		Twox128::hash(module),
+		Twox128::hash(&[&b"HeadOf"[..], item].concat()),
+	].concat();
+
+	let mut elements = Vec::new();
+	let mut next = unhashed::take::<K>(&head_key);
+	while let Some(key) = next {
+		let final_key = [&prefix[..], key.using_encoded(Hasher::hash).as_ref()].concat();
+		match unhashed::take::<(V, Linkage<K>)>(&final_key) {
+			Some((value, linkage)) => {
+				next = linkage.next;
+				elements.push((key, value));
+			}
+			None => break,
+		}
+	}
+	unhashed::kill_prefix(&prefix);
+
+	elements
+}
@@ -19,13 +19,31 @@
 use sp_std::{prelude::*, marker::PhantomData};
 use codec::{FullCodec, FullEncode, Encode, EncodeAppend, EncodeLike, Decode};
 use crate::{traits::Len, hash::{Twox128, StorageHasher}};
+use self::key::{
+	KeyGenerator, ReversibleKeyGenerator, HasKeyPrefix, HasReversibleKeyPrefix, EncodeLikeTuple,
+	TupleToEncodedIter,
+};
 
 pub mod unhashed;
 pub mod hashed;
 pub mod child;
+pub mod migration;
+pub mod key;
+pub mod bounded_vec;
+pub mod bounded_btree_map;
 #[doc(hidden)]
 pub mod generator;
 
+/// A collection type with a maximum number of elements.
+///
+/// `decl_storage` exposes this bound in the metadata of storage items whose value is a
+/// [`BoundedVec`](bounded_vec/struct.BoundedVec.html) or a
+/// [`BoundedBTreeMap`](bounded_btree_map/struct.BoundedBTreeMap.html).
+pub trait Bounded {
+	/// The maximum number of elements.
+	fn bound() -> u32;
+}
+
 /// A trait for working with macro-generated storage values under the substrate storage API.
 ///
 /// Details on implementation can be found at
@@ -182,12 +200,41 @@ pub trait StorageMap<K: FullEncode, V: FullCodec> {
 		where V: codec::DecodeLength + Len;
 }
 
+/// A strongly-typed map in storage whose keys and values can be iterated over.
+///
+/// This is implemented for any `StorageMap` whose hasher is a
+/// [`ReversibleStorageHasher`](../hash/trait.ReversibleStorageHasher.html), i.e. one which keeps
+/// the encoded key in the final storage key (`blake2_128_concat`, `twox_64_concat` or
+/// `identity`).
+pub trait IterableStorageMap<K: FullCodec, V: FullCodec>: StorageMap<K, V> {
+	/// The type that iterates over all `(key, value)`.
+	type Iterator: Iterator<Item = (K, V)>;
+
+	/// Enumerate all elements in the map in no particular order. If you alter the map while doing
+	/// this, you'll get undefined results.
+	fn iter() -> Self::Iterator;
+
+	/// Remove all elements from the map and iterate through them in no particular order. If you
+	/// add elements to the map while doing this, you'll get undefined results.
+	fn drain() -> Self::Iterator;
+
+	/// Translate the values of all elements by a function `f`, in the map in no particular order.
+	/// By returning `None` from `f` for an element, you'll remove it from the map.
+	///
+	/// Elements whose value cannot be decoded as `O` are left untouched.
+	fn translate<O: Decode, F: Fn(K, O) -> Option<V>>(f: F);
+}
+
 /// A strongly-typed linked map in storage.
 ///
 /// Similar to `StorageMap` but allows to enumerate other elements and doesn't implement append.
 ///
 /// Details on implementation can be found at
 /// [`generator::StorageLinkedMap`]
+///
+/// Deprecated: every insertion and removal costs extra reads and writes to maintain the linkage.
+/// Use a `map` with a reversible hasher and [`IterableStorageMap`](trait.IterableStorageMap.html)
+/// instead.
 pub trait StorageLinkedMap<K: FullCodec, V: FullCodec> {
 	/// The type that get/take return.
 	type Query;
@@ -306,7 +353,8 @@ pub trait StorageDoubleMap<K1: FullEncode, K2: FullEncode, V: FullCodec> {
 
 	fn remove_prefix<KArg1>(k1: KArg1) where KArg1: ?Sized + EncodeLike<K1>;
 
-	fn iter_prefix<KArg1>(k1: KArg1) -> PrefixIterator<V>
+	/// Iterate over the values of all entries with the given first key.
+	fn iter_prefix_values<KArg1>(k1: KArg1) -> PrefixIterator<V>
 		where KArg1: ?Sized + EncodeLike<K1>;
 
 	fn mutate<KArg1, KArg2, R, F>(k1: KArg1, k2: KArg2, f: F) -> R
@@ -357,6 +405,134 @@ pub trait StorageDoubleMap<K1: FullEncode, K2: FullEncode, V: FullCodec> {
 			V: codec::DecodeLength + Len;
 }
 
+/// A strongly-typed double map in storage whose keys and values can be iterated over.
+///
+/// This is implemented for any `StorageDoubleMap` whose hashers are both
+/// [`ReversibleStorageHasher`](../hash/trait.ReversibleStorageHasher.html)s.
+pub trait IterableStorageDoubleMap<
+	K1: FullCodec,
+	K2: FullCodec,
+	V: FullCodec
+>: StorageDoubleMap<K1, K2, V> {
+	/// The type that iterates over all `(key2, value)` with a given first key.
+	type PrefixIterator: Iterator<Item = (K2, V)>;
+
+	/// The type that iterates over all `(key1, key2, value)`.
+	type Iterator: Iterator<Item = (K1, K2, V)>;
+
+	/// Enumerate all elements in the map with first key `k1` in no particular order. If you add or
+	/// remove values whose first key is `k1` to the map while doing this, you'll get undefined
+	/// results.
+	fn iter_prefix(k1: impl EncodeLike<K1>) -> Self::PrefixIterator;
+
+	/// Remove all elements from the map with first key `k1` and iterate through them in no
+	/// particular order. If you add elements with first key `k1` to the map while doing this,
+	/// you'll get undefined results.
+	fn drain_prefix(k1: impl EncodeLike<K1>) -> Self::PrefixIterator;
+
+	/// Enumerate all elements in the map in no particular order. If you add or remove values to
+	/// the map while doing this, you'll get undefined results.
+	fn iter() -> Self::Iterator;
+
+	/// Remove all elements from the map and iterate through them in no particular order. If you
+	/// add elements to the map while doing this, you'll get undefined results.
+	fn drain() -> Self::Iterator;
+
+	/// Translate the values of all elements by a function `f`, in the map in no particular order.
+	/// By returning `None` from `f` for an element, you'll remove it from the map.
+	///
+	/// Elements whose value cannot be decoded as `O` are left untouched.
+	fn translate<O: Decode, F: Fn(K1, K2, O) -> Option<V>>(f: F);
+}
+
+/// A strongly-typed map in storage whose keys are made of an arbitrary number of parts, each
+/// hashed with its own hasher.
+///
+/// `K` is a tuple of [`key::Key`](key/struct.Key.html)s, and keys are given as tuples of the key
+/// parts. Elements can be accessed and removed by any prefix of the key parts.
+///
+/// Details on implementation can be found at
+/// [`generator::StorageNMap`]
+pub trait StorageNMap<K: KeyGenerator, V: FullCodec> {
+	/// The type that get/take returns.
+	type Query;
+
+	/// Get the storage key used to fetch a value corresponding to a specific key.
+	fn hashed_key_for<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> Vec<u8>;
+
+	/// Does the value (explicitly) exist in storage?
+	fn contains_key<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> bool;
+
+	/// Load the value associated with the given key from the map.
+	fn get<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> Self::Query;
+
+	/// Take the value under a key.
+	fn take<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg) -> Self::Query;
+
+	/// Swap the values of two keys.
+	fn swap<KArg1, KArg2>(key1: KArg1, key2: KArg2)
+	where
+		KArg1: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+		KArg2: EncodeLikeTuple<K::Key> + TupleToEncodedIter;
+
+	/// Store a value to be associated with the given key from the map.
+	fn insert<KArg, VArg>(key: KArg, val: VArg)
+	where
+		KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+		VArg: EncodeLike<V>;
+
+	/// Remove the value under a key.
+	fn remove<KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter>(key: KArg);
+
+	/// Remove all values whose key starts with the given key parts.
+	fn remove_prefix<KP>(partial_key: KP) where K: HasKeyPrefix<KP>;
+
+	/// Iterate over the values of all entries whose key starts with the given key parts.
+	fn iter_prefix_values<KP>(partial_key: KP) -> PrefixIterator<V> where K: HasKeyPrefix<KP>;
+
+	/// Mutate the value under a key.
+	fn mutate<KArg, R, F>(key: KArg, f: F) -> R
+	where
+		KArg: EncodeLikeTuple<K::Key> + TupleToEncodedIter,
+		F: FnOnce(&mut Self::Query) -> R;
+}
+
+/// A strongly-typed N-map in storage whose keys and values can be iterated over.
+///
+/// This is implemented for any `StorageNMap` whose hashers are all
+/// [`ReversibleStorageHasher`](../hash/trait.ReversibleStorageHasher.html)s.
+pub trait IterableStorageNMap<K: ReversibleKeyGenerator, V: FullCodec>: StorageNMap<K, V> {
+	/// The type that iterates over all `(key, value)`.
+	type Iterator: Iterator<Item = (K::Key, V)>;
+
+	/// Enumerate all elements in the map whose key starts with the given key parts, in no
+	/// particular order. The remaining key parts are returned along with each value. If you add or
+	/// remove values whose key starts with `partial_key` to the map while doing this, you'll get
+	/// undefined results.
+	fn iter_prefix<KP>(partial_key: KP) -> generator::StorageNMapIterator<K::Suffix, V>
+		where K: HasReversibleKeyPrefix<KP>;
+
+	/// Remove all elements from the map whose key starts with the given key parts and iterate
+	/// through them in no particular order. If you add elements whose key starts with
+	/// `partial_key` to the map while doing this, you'll get undefined results.
+	fn drain_prefix<KP>(partial_key: KP) -> generator::StorageNMapIterator<K::Suffix, V>
+		where K: HasReversibleKeyPrefix<KP>;
+
+	/// Enumerate all elements in the map in no particular order. If you add or remove values to
+	/// the map while doing this, you'll get undefined results.
+	fn iter() -> Self::Iterator;
+
+	/// Remove all elements from the map and iterate through them in no particular order. If you
+	/// add elements to the map while doing this, you'll get undefined results.
+	fn drain() -> Self::Iterator;
+
+	/// Translate the values of all elements by a function `f`, in the map in no particular order.
+	/// By returning `None` from `f` for an element, you'll remove it from the map.
+	///
+	/// Elements whose value cannot be decoded as `O` are left untouched.
+	fn translate<O: Decode, F: Fn(K::Key, O) -> Option<V>>(f: F);
+}
+
 /// Iterator for prefixed map.
 pub struct PrefixIterator<Value> {
 	prefix: Vec<u8>,
@@ -418,7 +594,7 @@ pub trait StoragePrefixedMap<Value: FullCodec> {
 	}
 
 	/// Iter over all value of the storage.
-	fn iter() -> PrefixIterator<Value> {
+	fn iter_values() -> PrefixIterator<Value> {
 		let prefix = Self::final_prefix();
 		PrefixIterator {
 			prefix: prefix.to_vec(),
@@ -513,26 +689,26 @@ mod test {
 			assert_eq!(MyStorage::final_prefix().to_vec(), k);
 
 			// test iteration
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![]);
 
 			unhashed::put(&[&k[..], &vec![1][..]].concat(), &1u64);
 			unhashed::put(&[&k[..], &vec![1, 1][..]].concat(), &2u64);
 			unhashed::put(&[&k[..], &vec![8][..]].concat(), &3u64);
 			unhashed::put(&[&k[..], &vec![10][..]].concat(), &4u64);
 
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
 
 			// test removal
 			MyStorage::remove_all();
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![]);
 
 			// test migration
 			unhashed::put(&[&k[..], &vec![1][..]].concat(), &1u32);
 			unhashed::put(&[&k[..], &vec![8][..]].concat(), &2u32);
 
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![]);
 			MyStorage::translate_values(|v: u32| v as u64).unwrap();
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![1, 2]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![1, 2]);
 			MyStorage::remove_all();
 
 			// test migration 2
@@ -542,9 +718,9 @@ mod test {
 			unhashed::put(&[&k[..], &vec![10][..]].concat(), &4u32);
 
 			// (contains some value that successfully decoded to u64)
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![1, 2, 3]);
 			assert_eq!(MyStorage::translate_values(|v: u128| v as u64), Err(2));
-			assert_eq!(MyStorage::iter().collect::<Vec<_>>(), vec![1, 3]);
+			assert_eq!(MyStorage::iter_values().collect::<Vec<_>>(), vec![1, 3]);
 			MyStorage::remove_all();
 
 			// test that other values are not modified.
@@ -616,10 +616,11 @@ pub trait VestingSchedule<AccountId> {
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &AccountId) -> <Self::Currency as Currency<AccountId>>::Balance;
 
-	/// Adds a vesting schedule to a given account.
+	/// Adds a vesting schedule to a given account. An account may have several vesting schedules
+	/// at once.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
-	/// and nothing is updated.
+	/// If the schedule cannot be added to the given account (see `can_add_vesting_schedule`), an
+	/// `Err` is returned and nothing is updated.
 	fn add_vesting_schedule(
 		who: &AccountId,
 		locked: <Self::Currency as Currency<AccountId>>::Balance,
@@ -627,8 +628,152 @@ pub trait VestingSchedule<AccountId> {
 		starting_block: Self::Moment,
 	) -> DispatchResult;
 
-	/// Remove a vesting schedule for a given account.
-	fn remove_vesting_schedule(who: &AccountId);
+	/// Checks whether `add_vesting_schedule` would succeed with the same arguments, without
+	/// changing anything.
+	fn can_add_vesting_schedule(
+		who: &AccountId,
+		locked: <Self::Currency as Currency<AccountId>>::Balance,
+		per_block: <Self::Currency as Currency<AccountId>>::Balance,
+		starting_block: Self::Moment,
+	) -> DispatchResult;
+
+	/// Remove the vesting schedule at `schedule_index` for a given account.
+	fn remove_vesting_schedule(who: &AccountId, schedule_index: u32) -> DispatchResult;
+}
+
+/// Abstractions over a set of fungible asset classes, each identified by an `AssetId`.
+///
+/// These are the multi-asset counterparts of `Currency`, `ReservableCurrency` and
+/// `LockableCurrency`. They are deliberately free of imbalance types so that any pallet storing
+/// balances keyed by asset can implement them, and so that consumers (treasury, transaction
+/// payment, ...) can work against any such pallet.
+pub mod fungibles {
+	use super::*;
+
+	/// Read-only access to the balances of a set of fungible asset classes.
+	pub trait Inspect<AccountId> {
+		/// The identifier of an asset class.
+		type AssetId: Parameter + Copy;
+
+		/// The balance of an account in any one asset class.
+		type Balance: SimpleArithmetic + FullCodec + Copy + MaybeSerializeDeserialize + Debug + Default;
+
+		/// The total amount of `asset` in existence.
+		fn total_issuance(asset: Self::AssetId) -> Self::Balance;
+
+		/// The minimum balance any single account may have in `asset`.
+		fn minimum_balance(asset: Self::AssetId) -> Self::Balance;
+
+		/// The amount of `asset` which `who` could spend, ignoring any locks.
+		fn balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+		/// Returns `Ok` iff `amount` of `asset` could be credited to `who`.
+		fn can_deposit(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Returns `Ok` iff `amount` of `asset` could be debited from `who` for the given
+		/// `reasons`, leaving the account alive or not according to `liveness`. This is a dry-run
+		/// of `Mutate::burn_from` and `Transfer::transfer`.
+		fn can_withdraw(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+			reasons: WithdrawReasons,
+			liveness: ExistenceRequirement,
+		) -> DispatchResult;
+	}
+
+	/// Minting and burning of a set of fungible asset classes.
+	pub trait Mutate<AccountId>: Inspect<AccountId> {
+		/// Increase the balance of `who` by exactly `amount` of `asset`, increasing the total
+		/// issuance accordingly.
+		fn mint_into(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Decrease the balance of `who` by at least `amount` of `asset`, decreasing the total
+		/// issuance accordingly. If the remaining balance would fall below the minimum balance,
+		/// the remainder is burnt too.
+		///
+		/// Returns the amount actually burnt.
+		fn burn_from(
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+		) -> result::Result<Self::Balance, DispatchError>;
+	}
+
+	/// Moving balances of a set of fungible asset classes between accounts.
+	pub trait Transfer<AccountId>: Inspect<AccountId> {
+		/// Transfer `amount` of `asset` from `source` to `dest`.
+		///
+		/// Returns the amount actually moved, which may be greater than `amount` if `liveness`
+		/// allows the account to be reaped and the remainder would be below the minimum balance.
+		fn transfer(
+			asset: Self::AssetId,
+			source: &AccountId,
+			dest: &AccountId,
+			amount: Self::Balance,
+			liveness: ExistenceRequirement,
+		) -> result::Result<Self::Balance, DispatchError>;
+	}
+
+	/// A set of fungible asset classes where funds can be reserved from the holder.
+	pub trait Reserve<AccountId>: Inspect<AccountId> {
+		/// The amount of `asset` reserved from `who`.
+		fn reserved_balance(asset: Self::AssetId, who: &AccountId) -> Self::Balance;
+
+		/// Same result as `reserve(asset, who, amount)` (but without the side-effects).
+		fn can_reserve(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> bool;
+
+		/// Move `amount` of `asset` from the free to the reserved balance of `who`.
+		///
+		/// If the free balance is lower than `amount`, or would be left non-zero but below the
+		/// minimum balance, then no funds will be moved and an `Err` will be returned.
+		fn reserve(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> DispatchResult;
+
+		/// Move up to `amount` of `asset` from the reserved to the free balance of `who`. This
+		/// cannot fail; the amount which could not be unreserved is returned.
+		fn unreserve(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+
+		/// Deduct up to `amount` of `asset` from the reserved balance of `who`, reducing the total
+		/// issuance accordingly. This cannot fail; the amount which could not be slashed is
+		/// returned.
+		fn slash_reserved(asset: Self::AssetId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+
+		/// Move up to `amount` of `asset` from the reserved balance of `slashed` to the free
+		/// balance of `beneficiary`.
+		///
+		/// Returns the amount which could not be moved.
+		fn repatriate_reserved(
+			asset: Self::AssetId,
+			slashed: &AccountId,
+			beneficiary: &AccountId,
+			amount: Self::Balance,
+		) -> result::Result<Self::Balance, DispatchError>;
+	}
+
+	/// A set of fungible asset classes whose account balances can have liquidity restrictions.
+	pub trait Lock<AccountId>: Inspect<AccountId> {
+		/// Create or replace the lock `id` on the `asset` balance of `who`.
+		fn set_lock(
+			id: LockIdentifier,
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+			reasons: WithdrawReasons,
+		);
+
+		/// Change the lock `id` on the `asset` balance of `who` so that it becomes less liquid in
+		/// all parameters, or create it if it does not exist.
+		fn extend_lock(
+			id: LockIdentifier,
+			asset: Self::AssetId,
+			who: &AccountId,
+			amount: Self::Balance,
+			reasons: WithdrawReasons,
+		);
+
+		/// Remove the lock `id` on the `asset` balance of `who`.
+		fn remove_lock(id: LockIdentifier, asset: Self::AssetId, who: &AccountId);
+	}
 }
 
 bitmask! {
@@ -738,6 +883,9 @@ pub trait ChangeMembers<AccountId: Clone + Ord> {
 		}
 		(incoming, outgoing)
 	}
+
+	/// Set the prime member. It must be one of the current members, or `None` for no prime.
+	fn set_prime(_prime: Option<AccountId>) {}
 }
 
 impl<T: Clone + Ord> ChangeMembers<T> for () {
@@ -108,6 +108,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[ " Hello, this is doc!" ]),
 				},
 				StorageEntryMetadata {
@@ -117,6 +118,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -126,6 +128,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -135,6 +138,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -144,6 +148,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -153,6 +158,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -162,6 +168,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETU32WITHCONFIG(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -171,6 +178,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETU32WITHCONFIG(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -180,6 +188,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -189,6 +198,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -198,6 +208,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETU32WITHCONFIGMYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -207,6 +218,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETU32WITHCONFIGMYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -216,6 +228,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETU32WITHCONFIGMYDEFOPT(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -225,6 +238,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGetU32WithBuilder(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -234,6 +248,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGetOptU32WithBuilderSome(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -243,6 +258,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGetOptU32WithBuilderNone(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -257,6 +273,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructMAPU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -271,6 +288,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBMAPU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -285,6 +303,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructMAPU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -299,6 +318,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBMAPU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -313,6 +333,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETMAPU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -327,6 +348,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETMAPU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -341,6 +363,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETMAPU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -355,6 +378,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETMAPU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -369,6 +393,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructLINKEDMAPU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -383,6 +408,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBLINKEDMAPU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -397,6 +423,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructGETLINKEDMAPU32(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -411,6 +438,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructPUBGETLINKEDMAPU32MYDEF(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -420,6 +448,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructCOMPLEXTYPE1(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -429,6 +458,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructCOMPLEXTYPE2(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 				StorageEntryMetadata {
@@ -438,6 +468,7 @@ mod tests {
 					default: DecodeDifferent::Encode(
 						DefaultByteGetter(&__GetByteStructCOMPLEXTYPE3(PhantomData::<TraitImpl>))
 					),
+					bound: None,
 					documentation: DecodeDifferent::Encode(&[]),
 				},
 			]
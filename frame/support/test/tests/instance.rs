@@ -410,6 +410,7 @@ const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
 						)
 					)
 				),
+				bound: None,
 				documentation: DecodeDifferent::Encode(&[]),
 			},
 			StorageEntryMetadata {
@@ -428,6 +429,7 @@ const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
 						)
 					)
 				),
+				bound: None,
 				documentation: DecodeDifferent::Encode(&[]),
 			},
 			StorageEntryMetadata {
@@ -446,6 +448,7 @@ const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
 						)
 					)
 				),
+				bound: None,
 				documentation: DecodeDifferent::Encode(&[]),
 			},
 			StorageEntryMetadata {
@@ -465,6 +468,7 @@ const EXPECTED_METADATA: StorageMetadata = StorageMetadata {
 						)
 					)
 				),
+				bound: None,
 				documentation: DecodeDifferent::Encode(&[]),
 			}
 		]
@@ -98,7 +98,7 @@ use sp_std::fmt::Debug;
 use sp_version::RuntimeVersion;
 use sp_runtime::{
 	RuntimeDebug,
-	generic::{self, Era}, Perbill, DispatchOutcome, DispatchError,
+	generic::{self, Era}, Perbill, DispatchOutcome, DispatchError, DispatchResult,
 	transaction_validity::{
 		ValidTransaction, TransactionPriority, TransactionLongevity, TransactionValidityError,
 		InvalidTransaction, TransactionValidity,
@@ -107,6 +107,7 @@ use sp_runtime::{
 		self, CheckEqual, SimpleArithmetic, Zero, SignedExtension, Lookup, LookupError,
 		SimpleBitOps, Hash, Member, MaybeDisplay, EnsureOrigin, BadOrigin, SaturatedConversion,
 		MaybeSerialize, MaybeSerializeDeserialize, MaybeMallocSizeOf, StaticLookup, One, Bounded,
+		Dispatchable, PostDispatchInfoOf, GetTip,
 	},
 };
 
@@ -114,7 +115,7 @@ use sp_core::{ChangesTrieConfiguration, storage::well_known_keys};
 use frame_support::{
 	decl_module, decl_event, decl_storage, decl_error, storage, Parameter,
 	traits::{Contains, Get, ModuleToIndex, OnReapAccount},
-	weights::{Weight, DispatchInfo, DispatchClass, SimpleDispatchInfo},
+	weights::{Weight, DispatchInfo, PostDispatchInfo, DispatchClass, SimpleDispatchInfo},
 };
 use codec::{Encode, Decode};
 
@@ -160,7 +161,7 @@ pub trait Trait: 'static + Eq + Clone {
 		+ Clone;
 
 	/// The aggregated `Call` type.
-	type Call: Debug;
+	type Call: Dispatchable + Debug;
 
 	/// Account index (aka nonce) type. This stores the number of previous transactions associated
 	/// with a sender account.
@@ -907,7 +908,7 @@ impl<T: Trait + Send + Sync> CheckWeight<T> {
 	///
 	/// Upon successes, it returns the new block weight as a `Result`.
 	fn check_weight(
-		info: <Self as SignedExtension>::DispatchInfo,
+		info: DispatchInfo,
 	) -> Result<Weight, TransactionValidityError> {
 		let current_weight = Module::<T>::all_extrinsics_weight();
 		let maximum_weight = T::MaximumBlockWeight::get();
@@ -925,7 +926,7 @@ impl<T: Trait + Send + Sync> CheckWeight<T> {
 	///
 	/// Upon successes, it returns the new block length as a `Result`.
 	fn check_block_length(
-		info: <Self as SignedExtension>::DispatchInfo,
+		info: DispatchInfo,
 		len: usize,
 	) -> Result<u32, TransactionValidityError> {
 		let current_len = Module::<T>::all_extrinsics_len();
@@ -941,7 +942,7 @@ impl<T: Trait + Send + Sync> CheckWeight<T> {
 	}
 
 	/// get the priority of an extrinsic denoted by `info`.
-	fn get_priority(info: <Self as SignedExtension>::DispatchInfo) -> TransactionPriority {
+	fn get_priority(info: DispatchInfo) -> TransactionPriority {
 		match info.class {
 			DispatchClass::Normal => info.weight.into(),
 			DispatchClass::Operational => Bounded::max_value()
@@ -954,7 +955,11 @@ impl<T: Trait + Send + Sync> CheckWeight<T> {
 	}
 }
 
-impl<T: Trait + Send + Sync> SignedExtension for CheckWeight<T> {
+impl<T: Trait + Send + Sync, Balance> GetTip<Balance> for CheckWeight<T> {}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckWeight<T> where
+	PostDispatchInfoOf<T::Call>: Into<PostDispatchInfo>,
+{
 	type AccountId = T::AccountId;
 	type Call = T::Call;
 	type AdditionalSigned = ();
@@ -998,6 +1003,28 @@ impl<T: Trait + Send + Sync> SignedExtension for CheckWeight<T> {
 
 		Ok(ValidTransaction { priority: Self::get_priority(info), ..Default::default() })
 	}
+
+	fn post_dispatch(
+		_pre: Self::Pre,
+		info: Self::DispatchInfo,
+		post_info: &PostDispatchInfoOf<T::Call>,
+		_len: usize,
+		_result: &DispatchResult,
+	) {
+		let post_info: PostDispatchInfo = (*post_info).into();
+		// Give back the weight which was reserved in `pre_dispatch` but not consumed. The same
+		// limit as in `check_weight` applies to both, so that nothing more than what was added
+		// is removed.
+		let limit = Self::get_dispatch_limit_ratio(info.class) * T::MaximumBlockWeight::get();
+		let added_weight = info.weight.min(limit);
+		let actual_weight = post_info.calc_actual_weight(&info).min(limit);
+		let unspent = added_weight.saturating_sub(actual_weight);
+		if unspent > 0 {
+			AllExtrinsicsWeight::mutate(|current_weight| {
+				*current_weight = Some(current_weight.unwrap_or_default().saturating_sub(unspent))
+			});
+		}
+	}
 }
 
 impl<T: Trait + Send + Sync> Debug for CheckWeight<T> {
@@ -1035,6 +1062,8 @@ impl<T: Trait> Debug for CheckNonce<T> {
 	}
 }
 
+impl<T: Trait, Balance> GetTip<Balance> for CheckNonce<T> {}
+
 impl<T: Trait> SignedExtension for CheckNonce<T> {
 	type AccountId = T::AccountId;
 	type Call = T::Call;
@@ -1120,6 +1149,8 @@ impl<T: Trait + Send + Sync> Debug for CheckEra<T> {
 	}
 }
 
+impl<T: Trait + Send + Sync, Balance> GetTip<Balance> for CheckEra<T> {}
+
 impl<T: Trait + Send + Sync> SignedExtension for CheckEra<T> {
 	type AccountId = T::AccountId;
 	type Call = T::Call;
@@ -1177,6 +1208,8 @@ impl<T: Trait + Send + Sync> CheckGenesis<T> {
 	}
 }
 
+impl<T: Trait + Send + Sync, Balance> GetTip<Balance> for CheckGenesis<T> {}
+
 impl<T: Trait + Send + Sync> SignedExtension for CheckGenesis<T> {
 	type AccountId = T::AccountId;
 	type Call = <T as Trait>::Call;
@@ -1213,6 +1246,8 @@ impl<T: Trait + Send + Sync> CheckVersion<T> {
 	}
 }
 
+impl<T: Trait + Send + Sync, Balance> GetTip<Balance> for CheckVersion<T> {}
+
 impl<T: Trait + Send + Sync> SignedExtension for CheckVersion<T> {
 	type AccountId = T::AccountId;
 	type Call = <T as Trait>::Call;
@@ -1256,6 +1291,20 @@ mod tests {
 	#[derive(Clone, Eq, PartialEq)]
 	pub struct Test;
 
+	#[derive(Clone, Debug)]
+	pub struct Call;
+
+	impl Dispatchable for Call {
+		type Origin = ();
+		type Trait = ();
+		type PostInfo = PostDispatchInfo;
+		fn dispatch(self, _origin: Self::Origin)
+			-> sp_runtime::DispatchResultWithInfo<Self::PostInfo>
+		{
+			panic!("Do not use dummy implementation for dispatch.");
+		}
+	}
+
 	parameter_types! {
 		pub const BlockHashCount: u64 = 10;
 		pub const MaximumBlockWeight: Weight = 1024;
@@ -1273,7 +1322,7 @@ mod tests {
 
 	impl Trait for Test {
 		type Origin = Origin;
-		type Call = ();
+		type Call = Call;
 		type Index = u64;
 		type BlockNumber = u64;
 		type Hash = H256;
@@ -1302,7 +1351,7 @@ mod tests {
 
 	type System = Module<Test>;
 
-	const CALL: &<Test as Trait>::Call = &();
+	const CALL: &<Test as Trait>::Call = &Call;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
 		GenesisConfig::default().build_storage::<Test>().unwrap().into()
@@ -1544,6 +1593,59 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn signed_ext_check_weight_refund_works() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo { weight: 512, ..Default::default() };
+			let post_info = PostDispatchInfo { actual_weight: Some(128) };
+			let len = 0_usize;
+
+			AllExtrinsicsWeight::put(256);
+
+			let pre = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, info, len).unwrap();
+			assert_eq!(System::all_extrinsics_weight(), 256 + 512);
+
+			CheckWeight::<Test>::post_dispatch(pre, info, &post_info, len, &Ok(()));
+			assert_eq!(System::all_extrinsics_weight(), 256 + 128);
+		})
+	}
+
+	#[test]
+	fn signed_ext_check_weight_actual_weight_higher_than_max_is_capped() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo { weight: 512, ..Default::default() };
+			let post_info = PostDispatchInfo { actual_weight: Some(700) };
+			let len = 0_usize;
+
+			AllExtrinsicsWeight::put(128);
+
+			let pre = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, info, len).unwrap();
+			assert_eq!(System::all_extrinsics_weight(), 128 + 512);
+
+			CheckWeight::<Test>::post_dispatch(pre, info, &post_info, len, &Ok(()));
+			assert_eq!(System::all_extrinsics_weight(), 128 + 512);
+		})
+	}
+
+	#[test]
+	fn signed_ext_check_weight_refund_is_bounded_by_the_limit() {
+		new_test_ext().execute_with(|| {
+			let info = DispatchInfo { weight: Weight::max_value(), ..Default::default() };
+			let post_info = PostDispatchInfo { actual_weight: Some(100) };
+			let len = 0_usize;
+			let normal_limit = normal_weight_limit();
+
+			AllExtrinsicsWeight::put(0);
+
+			let pre = CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, info, len).unwrap();
+			assert_eq!(System::all_extrinsics_weight(), normal_limit);
+
+			// only what was actually added is given back.
+			CheckWeight::<Test>::post_dispatch(pre, info, &post_info, len, &Ok(()));
+			assert_eq!(System::all_extrinsics_weight(), 100);
+		})
+	}
+
 	#[test]
 	fn signed_ext_check_weight_works_operational_tx() {
 		new_test_ext().execute_with(|| {
@@ -0,0 +1,686 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Asset Transaction Payment Module
+//!
+//! This module extends the [Transaction Payment module](../pallet_transaction_payment/index.html)
+//! so that transaction fees may be paid in assets other than the native currency.
+//!
+//! The fee is computed exactly as `ChargeTransactionPayment` does, in units of the native
+//! currency. If the sender chose to pay in some asset, that amount is converted through the
+//! configured [`AssetPrice`] source and withdrawn from the sender's balance of that asset. The
+//! withdrawn amount is handed to `OnAssetTransactionPayment` as an [`AssetCredit`], which knows the
+//! asset it is denominated in, so the handler can treat every asset differently.
+//!
+//! As with `ChargeTransactionPayment`, the fee is withdrawn before dispatch assuming the declared
+//! weight is used, and the part paying for unused weight is refunded after dispatch, in the
+//! currency or asset it was paid in.
+//!
+//! This module itself is an [`AssetPrice`] source: it stores a conversion rate for each asset,
+//! which can be changed by `RateOrigin`. Assets without a rate cannot be used to pay fees.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_storage, decl_module, decl_event,
+	traits::{
+		Currency, OnUnbalanced, ExistenceRequirement, WithdrawReason, WithdrawReasons, TryDrop,
+		fungibles::{Inspect, Mutate},
+	},
+	weights::{DispatchInfo, PostDispatchInfo, SimpleDispatchInfo},
+};
+use sp_runtime::{
+	RuntimeDebug, DispatchResult,
+	helpers_128bit::multiply_by_rational,
+	transaction_validity::{
+		TransactionPriority, ValidTransaction, InvalidTransaction, TransactionValidityError,
+		TransactionValidity,
+	},
+	traits::{
+		Zero, SignedExtension, SaturatedConversion, EnsureOrigin, Saturating, GetTip,
+		PostDispatchInfoOf,
+	},
+};
+use frame_system::{self as system};
+use pallet_transaction_payment::ChargeTransactionPayment;
+
+type BalanceOf<T> = <<T as pallet_transaction_payment::Trait>::Currency as Currency<
+	<T as frame_system::Trait>::AccountId,
+>>::Balance;
+type AssetIdOf<T> = <<T as Trait>::Assets as Inspect<<T as frame_system::Trait>::AccountId>>::AssetId;
+type AssetBalanceOf<T> = <<T as Trait>::Assets as Inspect<<T as frame_system::Trait>::AccountId>>::Balance;
+type AssetRateOf<T> = AssetRate<BalanceOf<T>, AssetBalanceOf<T>>;
+type NegativeImbalanceOf<T> = <<T as pallet_transaction_payment::Trait>::Currency as Currency<
+	<T as frame_system::Trait>::AccountId,
+>>::NegativeImbalance;
+
+/// The credit of an asset fee paid by a transaction sender.
+pub type AssetCreditOf<T> = AssetCredit<AssetIdOf<T>, AssetBalanceOf<T>>;
+
+pub trait Trait: pallet_transaction_payment::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The set of assets in which fees may be paid besides the native currency.
+	type Assets: Mutate<Self::AccountId>;
+
+	/// The source of the conversion rates from the native currency into assets.
+	type PriceSource: AssetPrice<AssetIdOf<Self>, BalanceOf<Self>, AssetBalanceOf<Self>>;
+
+	/// Handler for the fees paid in an asset.
+	type OnAssetTransactionPayment: OnUnbalanced<AssetCreditOf<Self>>;
+
+	/// The origin which may change the conversion rates stored by this module.
+	type RateOrigin: EnsureOrigin<Self::Origin>;
+}
+
+/// Something which can price an amount of the native currency in some asset.
+pub trait AssetPrice<AssetId, NativeBalance, AssetBalance> {
+	/// Convert `amount` of the native currency into an amount of `asset`, or `None` if `asset`
+	/// cannot currently be used to pay fees.
+	fn native_to_asset(asset: AssetId, amount: NativeBalance) -> Option<AssetBalance>;
+}
+
+impl<A, N, B> AssetPrice<A, N, B> for () {
+	fn native_to_asset(_: A, _: N) -> Option<B> { None }
+}
+
+/// A conversion rate: `native` units of the native currency are worth `asset` units of an asset.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct AssetRate<NativeBalance, AssetBalance> {
+	/// The amount of native currency.
+	pub native: NativeBalance,
+	/// The amount of the asset it is worth.
+	pub asset: AssetBalance,
+}
+
+/// An amount of some asset which was withdrawn from the sender of a transaction and is no longer
+/// accounted for in the total issuance of that asset.
+///
+/// Dropping it leaves the amount burnt; `Module::resolve_credit` credits it to an account instead.
+#[must_use]
+#[derive(RuntimeDebug, Eq, PartialEq)]
+pub struct AssetCredit<AssetId, Balance> {
+	asset: AssetId,
+	amount: Balance,
+}
+
+impl<AssetId: Copy, Balance: Copy + Zero + Saturating + PartialOrd> AssetCredit<AssetId, Balance> {
+	/// Create a new credit of `amount` of `asset`.
+	pub fn new(asset: AssetId, amount: Balance) -> Self {
+		AssetCredit { asset, amount }
+	}
+
+	/// The asset this credit is denominated in.
+	pub fn asset(&self) -> AssetId {
+		self.asset
+	}
+
+	/// The amount of the asset in this credit.
+	pub fn peek(&self) -> Balance {
+		self.amount
+	}
+
+	/// Consume `self` and return two credits of the same asset; the first is at most `amount`
+	/// and the second is the remainder.
+	pub fn split(self, amount: Balance) -> (Self, Self) {
+		let first = if amount < self.amount { amount } else { self.amount };
+		let second = self.amount.saturating_sub(first);
+		(Self::new(self.asset, first), Self::new(self.asset, second))
+	}
+}
+
+impl<AssetId, Balance: Zero> TryDrop for AssetCredit<AssetId, Balance> {
+	fn try_drop(self) -> Result<(), Self> {
+		if self.amount.is_zero() {
+			Ok(())
+		} else {
+			Err(self)
+		}
+	}
+}
+
+/// What was withdrawn from the sender of a transaction before it was dispatched.
+pub enum InitialPayment<T: Trait> {
+	/// Nothing was withdrawn, as the fee was zero.
+	Nothing,
+	/// The fee was paid in the native currency.
+	Native(NegativeImbalanceOf<T>),
+	/// The fee was paid in an asset.
+	Asset(AssetCreditOf<T>),
+}
+
+impl<T: Trait> Default for InitialPayment<T> {
+	fn default() -> Self {
+		InitialPayment::Nothing
+	}
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as AssetTxPayment {
+		/// The conversion rate from the native currency into each asset which may pay fees.
+		pub AssetRates get(fn asset_rate):
+			map hasher(blake2_256) AssetIdOf<T> => Option<AssetRateOf<T>>;
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where
+		AssetId = AssetIdOf<T>,
+		AssetRate = AssetRateOf<T>,
+	{
+		/// The conversion rate of an asset was set or, if `None`, removed.
+		AssetRateSet(AssetId, Option<AssetRate>),
+	}
+);
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		fn deposit_event() = default;
+
+		/// Set the conversion rate used to pay fees in `asset`, or disallow paying fees in `asset`
+		/// if `rate` is `None`.
+		///
+		/// The dispatch origin for this call must be `RateOrigin`.
+		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
+		fn set_rate(origin, asset: AssetIdOf<T>, rate: Option<AssetRateOf<T>>) -> DispatchResult {
+			T::RateOrigin::ensure_origin(origin)?;
+
+			match rate {
+				Some(r) => <AssetRates<T>>::insert(asset, r),
+				None => <AssetRates<T>>::remove(asset),
+			}
+
+			Self::deposit_event(RawEvent::AssetRateSet(asset, rate));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Deposit `credit` into the account of `who`, returning it if that is not possible.
+	pub fn resolve_credit(credit: AssetCreditOf<T>, who: &T::AccountId) -> Result<(), AssetCreditOf<T>> {
+		if credit.amount.is_zero() {
+			return Ok(());
+		}
+		T::Assets::mint_into(credit.asset, who, credit.amount).map_err(|_| credit)
+	}
+}
+
+impl<T: Trait> AssetPrice<AssetIdOf<T>, BalanceOf<T>, AssetBalanceOf<T>> for Module<T> {
+	fn native_to_asset(asset: AssetIdOf<T>, amount: BalanceOf<T>) -> Option<AssetBalanceOf<T>> {
+		let rate = Self::asset_rate(asset)?;
+		let native = rate.native.saturated_into::<u128>();
+		if native == 0 {
+			return None;
+		}
+		let amount = amount.saturated_into::<u128>();
+		let rate_asset = rate.asset.saturated_into::<u128>();
+		// Round up, so that paying in an asset is never cheaper than paying natively.
+		let asset_amount = match amount.checked_mul(rate_asset) {
+			Some(product) => product / native + if product % native == 0 { 0 } else { 1 },
+			None => multiply_by_rational(amount, rate_asset, native).ok()?,
+		};
+		Some(asset_amount.saturated_into())
+	}
+}
+
+/// Require the transactor pay for themselves, in the native currency or in an asset of their
+/// choice, and maybe include a tip to gain additional priority in the queue.
+///
+/// The tip is denominated in the native currency and converted along with the fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTxPayment<T: Trait + Send + Sync> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	asset_id: Option<AssetIdOf<T>>,
+}
+
+impl<T: Trait + Send + Sync> ChargeAssetTxPayment<T> where
+	BalanceOf<T>: Send + Sync,
+{
+	/// utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, asset_id: Option<AssetIdOf<T>>) -> Self {
+		Self { tip, asset_id }
+	}
+
+	/// Compute the fee of a transaction in `asset`, or `None` if fees cannot be paid in `asset`.
+	pub fn compute_asset_fee(
+		asset: AssetIdOf<T>,
+		len: u32,
+		info: DispatchInfo,
+		tip: BalanceOf<T>,
+	) -> Option<AssetBalanceOf<T>> {
+		let fee = ChargeTransactionPayment::<T>::compute_fee(len, info, tip);
+		T::PriceSource::native_to_asset(asset, fee)
+	}
+
+	fn withdraw_reasons(&self) -> WithdrawReasons {
+		if self.tip.is_zero() {
+			WithdrawReason::TransactionPayment.into()
+		} else {
+			WithdrawReason::TransactionPayment | WithdrawReason::Tip
+		}
+	}
+
+	/// Withdraw the full fee of a transaction from `who`, in the native currency or in the chosen
+	/// asset, assuming its declared weight is used.
+	///
+	/// Returns the fee in the native currency together with what was withdrawn.
+	fn withdraw_fee(
+		&self,
+		who: &T::AccountId,
+		info: DispatchInfo,
+		len: usize,
+	) -> Result<(BalanceOf<T>, InitialPayment<T>), TransactionValidityError> {
+		let fee = ChargeTransactionPayment::<T>::compute_fee(len as u32, info, self.tip);
+
+		// Only mess with balances if fee is not zero.
+		if fee.is_zero() {
+			return Ok((fee, InitialPayment::Nothing));
+		}
+
+		let reasons = self.withdraw_reasons();
+		match self.asset_id {
+			None => {
+				let imbalance = T::Currency::withdraw(
+					who,
+					fee,
+					reasons,
+					ExistenceRequirement::KeepAlive,
+				).map_err(|_| InvalidTransaction::Payment)?;
+				Ok((fee, InitialPayment::Native(imbalance)))
+			}
+			Some(asset) => {
+				let asset_fee = T::PriceSource::native_to_asset(asset, fee)
+					.ok_or(InvalidTransaction::Payment)?;
+				T::Assets::can_withdraw(asset, who, asset_fee, reasons, ExistenceRequirement::KeepAlive)
+					.map_err(|_| InvalidTransaction::Payment)?;
+				let paid = T::Assets::burn_from(asset, who, asset_fee)
+					.map_err(|_| InvalidTransaction::Payment)?;
+				Ok((fee, InitialPayment::Asset(AssetCredit::new(asset, paid))))
+			}
+		}
+	}
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeAssetTxPayment<{:?}, {:?}>", self.tip, self.asset_id)
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Trait + Send + Sync> GetTip<BalanceOf<T>> for ChargeAssetTxPayment<T> {
+	fn tip(&self) -> Option<BalanceOf<T>> {
+		Some(self.tip)
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeAssetTxPayment<T> where
+	BalanceOf<T>: Send + Sync,
+	AssetIdOf<T>: Send + Sync,
+	PostDispatchInfoOf<T::Call>: Into<PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+	type AccountId = T::AccountId;
+	type Call = T::Call;
+	type AdditionalSigned = ();
+	type DispatchInfo = DispatchInfo;
+	/// The tip, the sender and what was withdrawn from them before dispatch.
+	type Pre = (BalanceOf<T>, Self::AccountId, InitialPayment<T>);
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: Self::DispatchInfo,
+		len: usize,
+	) -> TransactionValidity {
+		// the payment is dropped here; changes made during validation are discarded anyway.
+		let (fee, _) = self.withdraw_fee(who, info, len)?;
+
+		let mut r = ValidTransaction::default();
+		// The priority is always based on the fee in the native currency, so that it does not
+		// depend on the asset chosen.
+		r.priority = fee.saturated_into::<TransactionPriority>();
+		Ok(r)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: Self::DispatchInfo,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let (_, payment) = self.withdraw_fee(who, info, len)?;
+		Ok((self.tip, who.clone(), payment))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: Self::DispatchInfo,
+		post_info: &PostDispatchInfoOf<T::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) {
+		let (tip, who, payment) = pre;
+		match payment {
+			InitialPayment::Nothing => {}
+			InitialPayment::Native(imbalance) => ChargeTransactionPayment::<T>::post_dispatch(
+				(tip, who, Some(imbalance)),
+				info,
+				post_info,
+				len,
+				result,
+			),
+			InitialPayment::Asset(paid) => {
+				let post_info = (*post_info).into();
+				let actual_fee = ChargeTransactionPayment::<T>::compute_actual_fee(
+					len as u32,
+					info,
+					&post_info,
+					tip,
+				);
+				// keep the whole fee if the asset can no longer be priced.
+				let actual_asset_fee = T::PriceSource::native_to_asset(paid.asset(), actual_fee)
+					.unwrap_or_else(|| paid.peek());
+				let (actual_payment, refund) = paid.split(actual_asset_fee);
+				if let Err(refund) = Module::<T>::resolve_credit(refund, &who) {
+					// the refund could not be deposited, so it is kept as part of the fee.
+					T::OnAssetTransactionPayment::on_unbalanced(refund);
+				}
+				T::OnAssetTransactionPayment::on_unbalanced(actual_payment);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{
+		parameter_types, impl_outer_origin, impl_outer_dispatch, assert_ok, assert_noop,
+		traits::Get,
+		weights::{DispatchClass, Weight},
+	};
+	use frame_system::EnsureRoot;
+	use sp_core::H256;
+	use sp_runtime::{
+		Perbill,
+		testing::Header,
+		traits::{BlakeTwo256, IdentityLookup, ConvertInto},
+	};
+	use pallet_balances::Call as BalancesCall;
+	use sp_std::cell::RefCell;
+
+	const CALL: &<Runtime as frame_system::Trait>::Call = &Call::Balances(BalancesCall::transfer(2, 69));
+	const ASSET: u32 = 16001;
+	const AUTHOR: u64 = 99;
+
+	impl_outer_dispatch! {
+		pub enum Call for Runtime where origin: Origin {
+			pallet_balances::Balances,
+			frame_system::System,
+		}
+	}
+
+	#[derive(Clone, PartialEq, Eq, Debug)]
+	pub struct Runtime;
+
+	use frame_system as system;
+	impl_outer_origin!{
+		pub enum Origin for Runtime {}
+	}
+
+	parameter_types! {
+		pub const BlockHashCount: u64 = 250;
+		pub const MaximumBlockWeight: Weight = 1024;
+		pub const MaximumBlockLength: u32 = 2 * 1024;
+		pub const AvailableBlockRatio: Perbill = Perbill::one();
+	}
+
+	impl frame_system::Trait for Runtime {
+		type Origin = Origin;
+		type Index = u64;
+		type BlockNumber = u64;
+		type Call = Call;
+		type Hash = H256;
+		type Hashing = BlakeTwo256;
+		type AccountId = u64;
+		type Lookup = IdentityLookup<Self::AccountId>;
+		type Header = Header;
+		type Event = ();
+		type BlockHashCount = BlockHashCount;
+		type MaximumBlockWeight = MaximumBlockWeight;
+		type MaximumBlockLength = MaximumBlockLength;
+		type AvailableBlockRatio = AvailableBlockRatio;
+		type Version = ();
+		type ModuleToIndex = ();
+	}
+
+	parameter_types! {
+		pub const CreationFee: u64 = 0;
+		pub const ExistentialDeposit: u64 = 0;
+	}
+
+	impl pallet_balances::Trait for Runtime {
+		type Balance = u64;
+		type OnReapAccount = System;
+		type OnNewAccount = ();
+		type Event = ();
+		type TransferPayment = ();
+		type DustRemoval = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type CreationFee = CreationFee;
+	}
+
+	impl pallet_generic_asset::Trait for Runtime {
+		type Balance = u64;
+		type AssetId = u32;
+		type Event = ();
+	}
+
+	thread_local! {
+		static TRANSACTION_BASE_FEE: RefCell<u64> = RefCell::new(5);
+	}
+
+	pub struct TransactionBaseFee;
+	impl Get<u64> for TransactionBaseFee {
+		fn get() -> u64 { TRANSACTION_BASE_FEE.with(|v| *v.borrow()) }
+	}
+
+	parameter_types! {
+		pub const TransactionByteFee: u64 = 1;
+	}
+
+	impl pallet_transaction_payment::Trait for Runtime {
+		type Currency = pallet_balances::Module<Runtime>;
+		type OnTransactionPayment = ();
+		type TransactionBaseFee = TransactionBaseFee;
+		type TransactionByteFee = TransactionByteFee;
+		type WeightToFee = ConvertInto;
+		type FeeMultiplierUpdate = ();
+	}
+
+	/// Hands all fees paid in assets to the block author.
+	pub struct ToAuthor;
+	impl OnUnbalanced<AssetCreditOf<Runtime>> for ToAuthor {
+		fn on_nonzero_unbalanced(credit: AssetCreditOf<Runtime>) {
+			let _ = AssetTxPayment::resolve_credit(credit, &AUTHOR);
+		}
+	}
+
+	impl Trait for Runtime {
+		type Event = ();
+		type Assets = GenericAsset;
+		type PriceSource = AssetTxPayment;
+		type OnAssetTransactionPayment = ToAuthor;
+		type RateOrigin = EnsureRoot<u64>;
+	}
+
+	type Balances = pallet_balances::Module<Runtime>;
+	type System = frame_system::Module<Runtime>;
+	type GenericAsset = pallet_generic_asset::Module<Runtime>;
+	type AssetTxPayment = Module<Runtime>;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(1, 100)],
+		}.assimilate_storage(&mut t).unwrap();
+		pallet_generic_asset::GenesisConfig::<Runtime> {
+			assets: vec![ASSET],
+			endowed_accounts: vec![2],
+			initial_balance: 100,
+			next_asset_id: 17000,
+			staking_asset_id: 16000,
+			spending_asset_id: ASSET,
+		}.assimilate_storage(&mut t).unwrap();
+		let mut ext: sp_io::TestExternalities = t.into();
+		ext.execute_with(|| <AssetRates<Runtime>>::insert(ASSET, AssetRate { native: 2, asset: 1 }));
+		ext
+	}
+
+	fn info_from_weight(w: Weight) -> DispatchInfo {
+		DispatchInfo { weight: w, pays_fee: true, ..Default::default() }
+	}
+
+	#[test]
+	fn paying_in_native_currency_works() {
+		new_test_ext().execute_with(|| {
+			let info = info_from_weight(5);
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, None)
+				.pre_dispatch(&1, CALL, info, 10)
+				.unwrap();
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 5 - 10);
+
+			let post_info = PostDispatchInfo { actual_weight: None };
+			ChargeAssetTxPayment::<Runtime>::post_dispatch(pre, info, &post_info, 10, &Ok(()));
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 5 - 10);
+		});
+	}
+
+	#[test]
+	fn paying_in_asset_works() {
+		new_test_ext().execute_with(|| {
+			// 5 base + 10 length + 5 weight + 4 tip = 24 native = 12 asset.
+			let info = info_from_weight(5);
+			let pre = ChargeAssetTxPayment::<Runtime>::from(4, Some(ASSET))
+				.pre_dispatch(&2, CALL, info, 10)
+				.unwrap();
+			assert_eq!(GenericAsset::free_balance(&ASSET, &2), 100 - 12);
+			assert_eq!(GenericAsset::total_issuance(ASSET), 100 - 12);
+
+			// the fee is handed over once the call has been dispatched.
+			let post_info = PostDispatchInfo { actual_weight: None };
+			ChargeAssetTxPayment::<Runtime>::post_dispatch(pre, info, &post_info, 10, &Ok(()));
+			assert_eq!(GenericAsset::free_balance(&ASSET, &2), 100 - 12);
+			assert_eq!(GenericAsset::free_balance(&ASSET, &AUTHOR), 12);
+			assert_eq!(GenericAsset::total_issuance(ASSET), 100);
+
+			// The conversion rounds up.
+			assert_eq!(
+				ChargeAssetTxPayment::<Runtime>::compute_asset_fee(ASSET, 10, info_from_weight(6), 0),
+				Some(11),
+			);
+		});
+	}
+
+	#[test]
+	fn unused_weight_is_refunded() {
+		new_test_ext().execute_with(|| {
+			// 5 base + 10 length + 20 weight = 35 native, of which only 5 weight is used.
+			let info = info_from_weight(20);
+			let post_info = PostDispatchInfo { actual_weight: Some(5) };
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, None)
+				.pre_dispatch(&1, CALL, info, 10)
+				.unwrap();
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 20);
+			ChargeAssetTxPayment::<Runtime>::post_dispatch(pre, info, &post_info, 10, &Ok(()));
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 5);
+
+			// 35 native = 18 asset up front, 20 native = 10 asset once refunded.
+			let pre = ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+				.pre_dispatch(&2, CALL, info, 10)
+				.unwrap();
+			assert_eq!(GenericAsset::free_balance(&ASSET, &2), 100 - 18);
+			ChargeAssetTxPayment::<Runtime>::post_dispatch(pre, info, &post_info, 10, &Ok(()));
+			assert_eq!(GenericAsset::free_balance(&ASSET, &2), 100 - 10);
+			assert_eq!(GenericAsset::free_balance(&ASSET, &AUTHOR), 10);
+			assert_eq!(GenericAsset::total_issuance(ASSET), 100);
+		});
+	}
+
+	#[test]
+	fn priority_is_based_on_native_fee() {
+		new_test_ext().execute_with(|| {
+			let native = ChargeAssetTxPayment::<Runtime>::from(0, None)
+				.validate(&1, CALL, info_from_weight(5), 10)
+				.unwrap();
+			let asset = ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+				.validate(&2, CALL, info_from_weight(5), 10)
+				.unwrap();
+			assert_eq!(native.priority, asset.priority);
+		});
+	}
+
+	#[test]
+	fn paying_in_unpriced_asset_fails() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				ChargeAssetTxPayment::<Runtime>::from(0, Some(16000))
+					.validate(&2, CALL, info_from_weight(5), 10),
+				TransactionValidityError::from(InvalidTransaction::Payment)
+			);
+			assert_noop!(
+				ChargeAssetTxPayment::<Runtime>::from(0, Some(ASSET))
+					.validate(&1, CALL, info_from_weight(5), 10),
+				TransactionValidityError::from(InvalidTransaction::Payment)
+			);
+		});
+	}
+
+	#[test]
+	fn free_transactions_do_not_touch_assets() {
+		new_test_ext().execute_with(|| {
+			let free = DispatchInfo { weight: 0, class: DispatchClass::Operational, pays_fee: false };
+			assert!(ChargeAssetTxPayment::<Runtime>::from(0, Some(16000)).validate(&3, CALL, free, 10).is_ok());
+		});
+	}
+
+	#[test]
+	fn set_rate_works() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				AssetTxPayment::set_rate(Origin::signed(1), ASSET, None),
+				sp_runtime::traits::BadOrigin
+			);
+			assert_ok!(AssetTxPayment::set_rate(Origin::ROOT, ASSET, Some(AssetRate { native: 1, asset: 3 })));
+			assert_eq!(AssetTxPayment::native_to_asset(ASSET, 10), Some(30));
+			assert_ok!(AssetTxPayment::set_rate(Origin::ROOT, ASSET, None));
+			assert_eq!(AssetTxPayment::native_to_asset(ASSET, 10), None);
+		});
+	}
+}
@@ -23,7 +23,7 @@ use frame_support::weights::{Weight, DispatchClass};
 use codec::{Encode, Codec, Decode};
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
-use sp_runtime::traits::{UniqueSaturatedInto, SaturatedConversion};
+use sp_runtime::traits::{UniqueSaturatedInto, SaturatedConversion, SimpleArithmetic};
 
 /// Some information related to a dispatchable that can be queried from the runtime.
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
@@ -73,12 +73,96 @@ impl CappedDispatchInfo {
 	}
 }
 
+/// The components of the fee of a dispatch, as computed by `ChargeTransactionPayment`.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct FeeDetails<Balance> {
+	/// The minimum fee for a transaction to be included in a block.
+	pub base_fee: Balance,
+	/// The fee for the encoded length of the transaction, before the fee multiplier.
+	pub len_fee: Balance,
+	/// The fee for the weight of the transaction, before the fee multiplier.
+	pub weight_fee: Balance,
+	/// The length and weight fees once the fee multiplier of the next block is applied.
+	pub adjusted_fee: Balance,
+	/// The tip included in the transaction.
+	pub tip: Balance,
+	/// What `ChargeTransactionPayment` actually took from the sender, including any refund made
+	/// after the dispatch, when the transaction was applied on top of the queried state.
+	///
+	/// Only present if a simulation was requested and the transaction could be applied.
+	pub actual_fee: Option<Balance>,
+}
+
+impl<Balance: SimpleArithmetic + Copy> FeeDetails<Balance> {
+	/// The total fee which the sender is expected to pay before any refund:
+	/// `base_fee + adjusted_fee + tip`.
+	pub fn final_fee(&self) -> Balance {
+		self.base_fee.saturating_add(self.adjusted_fee).saturating_add(self.tip)
+	}
+}
+
+/// A capped version of `FeeDetails`.
+///
+/// The `Balance` is capped (or expanded) to `u64` to avoid serde issues with `u128`.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CappedFeeDetails {
+	/// The minimum fee for a transaction to be included in a block.
+	pub base_fee: u64,
+	/// The fee for the encoded length of the transaction, before the fee multiplier.
+	pub len_fee: u64,
+	/// The fee for the weight of the transaction, before the fee multiplier.
+	pub weight_fee: u64,
+	/// The length and weight fees once the fee multiplier of the next block is applied.
+	pub adjusted_fee: u64,
+	/// The tip included in the transaction.
+	pub tip: u64,
+	/// The sum of `base_fee`, `adjusted_fee` and `tip`.
+	pub final_fee: u64,
+	/// What `ChargeTransactionPayment` actually took from the sender, if simulated.
+	pub actual_fee: Option<u64>,
+}
+
+impl CappedFeeDetails {
+	/// Create a new `CappedFeeDetails` from `FeeDetails`.
+	pub fn new<Balance: SimpleArithmetic + Copy + UniqueSaturatedInto<u64>>(
+		details: FeeDetails<Balance>,
+	) -> Self {
+		let final_fee = details.final_fee();
+		let FeeDetails {
+			base_fee,
+			len_fee,
+			weight_fee,
+			adjusted_fee,
+			tip,
+			actual_fee,
+		} = details;
+
+		Self {
+			base_fee: base_fee.saturated_into(),
+			len_fee: len_fee.saturated_into(),
+			weight_fee: weight_fee.saturated_into(),
+			adjusted_fee: adjusted_fee.saturated_into(),
+			tip: tip.saturated_into(),
+			final_fee: final_fee.saturated_into(),
+			actual_fee: actual_fee.map(SaturatedConversion::saturated_into),
+		}
+	}
+}
+
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait TransactionPaymentApi<Balance, Extrinsic> where
 		Balance: Codec,
 		Extrinsic: Codec,
 	{
 		fn query_info(uxt: Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance>;
+
+		/// Query the components of the fee of `uxt`. If `simulate` is true, `uxt` is also applied
+		/// on top of the current state to find out the fee which is actually taken.
+		fn query_fee_details(uxt: Extrinsic, len: u32, simulate: bool) -> FeeDetails<Balance>;
 	}
 }
 
@@ -103,4 +187,22 @@ mod tests {
 		// should not panic
 		serde_json::to_value(&info).unwrap();
 	}
+
+	#[test]
+	fn fee_details_should_serialize_properly_with_u64() {
+		let details = FeeDetails {
+			base_fee: 100_u64,
+			len_fee: 10,
+			weight_fee: 20,
+			adjusted_fee: 45,
+			tip: 5,
+			actual_fee: None,
+		};
+
+		let details = CappedFeeDetails::new(details);
+		assert_eq!(
+			serde_json::to_string(&details).unwrap(),
+			r#"{"baseFee":100,"lenFee":10,"weightFee":20,"adjustedFee":45,"tip":5,"finalFee":150,"actualFee":null}"#,
+		);
+	}
 }
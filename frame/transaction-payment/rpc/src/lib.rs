@@ -21,10 +21,10 @@ use codec::{Codec, Decode};
 use sp_blockchain::HeaderBackend;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use sp_runtime::{generic::BlockId, traits::{Block as BlockT, UniqueSaturatedInto}};
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, SimpleArithmetic, UniqueSaturatedInto}};
 use sp_api::ProvideRuntimeApi;
 use sp_core::Bytes;
-use pallet_transaction_payment_rpc_runtime_api::CappedDispatchInfo;
+use pallet_transaction_payment_rpc_runtime_api::{CappedDispatchInfo, CappedFeeDetails};
 pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
 pub use self::gen_client::Client as TransactionPaymentClient;
 
@@ -36,6 +36,18 @@ pub trait TransactionPaymentApi<BlockHash, Balance> {
 		encoded_xt: Bytes,
 		at: Option<BlockHash>
 	) -> Result<CappedDispatchInfo>;
+
+	/// Query the breakdown of the fee of `encoded_xt`.
+	///
+	/// If `simulate` is set, the extrinsic is also applied on top of the state of `at` (without
+	/// persisting anything) to report the fee which is actually charged, after any refund.
+	#[rpc(name = "payment_queryFeeDetails")]
+	fn query_fee_details(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<BlockHash>,
+		simulate: Option<bool>,
+	) -> Result<CappedFeeDetails>;
 }
 
 /// A struct that implements the [`TransactionPaymentApi`].
@@ -68,13 +80,26 @@ impl From<Error> for i64 {
 	}
 }
 
+impl<C, Block, Extrinsic> TransactionPayment<C, (Block, Extrinsic)> where
+	Block: BlockT,
+	C: HeaderBackend<Block>,
+{
+	/// The block to run the query at, defaulting to the best block.
+	fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+		BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		))
+	}
+}
+
 impl<C, Block, Balance, Extrinsic> TransactionPaymentApi<<Block as BlockT>::Hash, Balance>
 	for TransactionPayment<C, (Block, Extrinsic)>
 where
 	Block: BlockT,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
 	C::Api: TransactionPaymentRuntimeApi<Block, Balance, Extrinsic>,
-	Balance: Codec + UniqueSaturatedInto<u64>,
+	Balance: Codec + SimpleArithmetic + Copy + UniqueSaturatedInto<u64>,
 	Extrinsic: Codec + Send + Sync + 'static,
 {
 	fn query_info(
@@ -83,10 +108,7 @@ where
 		at: Option<<Block as BlockT>::Hash>
 	) -> Result<CappedDispatchInfo> {
 		let api = self.client.runtime_api();
-		let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash
-		));
+		let at = self.block_id(at);
 
 		let encoded_len = encoded_xt.len() as u32;
 
@@ -101,4 +123,27 @@ where
 			data: Some(format!("{:?}", e).into()),
 		}).map(CappedDispatchInfo::new)
 	}
+
+	fn query_fee_details(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+		simulate: Option<bool>,
+	) -> Result<CappedFeeDetails> {
+		let api = self.client.runtime_api();
+		let at = self.block_id(at);
+
+		let encoded_len = encoded_xt.len() as u32;
+
+		let uxt: Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to query fee details.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		api.query_fee_details(&at, uxt, encoded_len, simulate.unwrap_or(false)).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query fee details.".into(),
+			data: Some(format!("{:?}", e).into()),
+		}).map(CappedFeeDetails::new)
+	}
 }
@@ -35,18 +35,21 @@ use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use frame_support::{
 	decl_storage, decl_module,
-	traits::{Currency, Get, OnUnbalanced, ExistenceRequirement, WithdrawReason},
-	weights::{Weight, DispatchInfo, GetDispatchInfo},
+	traits::{Currency, Get, OnUnbalanced, ExistenceRequirement, WithdrawReason, Imbalance},
+	weights::{Weight, DispatchInfo, PostDispatchInfo, GetDispatchInfo},
 };
 use sp_runtime::{
-	Fixed64,
+	Fixed64, DispatchResult,
 	transaction_validity::{
 		TransactionPriority, ValidTransaction, InvalidTransaction, TransactionValidityError,
 		TransactionValidity,
 	},
-	traits::{Zero, Saturating, SignedExtension, SaturatedConversion, Convert},
+	traits::{
+		Zero, Saturating, SignedExtension, SaturatedConversion, Convert, Dispatchable,
+		PostDispatchInfoOf, GetTip,
+	},
 };
-use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+use pallet_transaction_payment_rpc_runtime_api::{RuntimeDispatchInfo, FeeDetails};
 
 type Multiplier = Fixed64;
 type BalanceOf<T> =
@@ -124,6 +127,65 @@ impl<T: Trait> Module<T> {
 
 		RuntimeDispatchInfo { weight, class, partial_fee }
 	}
+
+	/// Query the components of the fee of a given `unchecked_extrinsic` carrying `tip`.
+	///
+	/// Just like `query_info`, this cannot look into the signed extensions of the extrinsic. The
+	/// caller is responsible for extracting the `tip` out of them, see `GetTip`.
+	pub fn query_fee_details<Extrinsic: GetDispatchInfo>(
+		unchecked_extrinsic: Extrinsic,
+		len: u32,
+		tip: BalanceOf<T>,
+	) -> FeeDetails<BalanceOf<T>>
+	where
+		T: Send + Sync,
+		BalanceOf<T>: Send + Sync,
+	{
+		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
+		<ChargeTransactionPayment<T>>::compute_fee_details(len, dispatch_info, tip)
+	}
+
+	/// Find out the fee that is actually taken from `who` for `call` signed with the extensions
+	/// `extra`, by running it through the whole pipeline on top of the current state: all the
+	/// signed extensions are checked and executed, `call` is dispatched and `post_dispatch` is
+	/// executed.
+	///
+	/// A transaction that any of the signed extensions rejects reports that error, as it would
+	/// never be included and thus never pay a fee.
+	///
+	/// This mutates the state and must only be used when all changes are discarded afterwards,
+	/// e.g. from within a runtime API call. The result of the dispatch itself does not matter, as
+	/// the fee is paid regardless.
+	pub fn simulate_fee<Extra>(
+		who: T::AccountId,
+		extra: Extra,
+		call: T::Call,
+		len: u32,
+	) -> Result<BalanceOf<T>, TransactionValidityError>
+	where
+		Extra: SignedExtension<AccountId=T::AccountId, Call=T::Call, DispatchInfo=DispatchInfo>,
+		T::Call: Dispatchable<Origin=T::Origin> + GetDispatchInfo,
+	{
+		let info = call.get_dispatch_info();
+		let len = len as usize;
+
+		let before = T::Currency::free_balance(&who);
+		let pre = extra.pre_dispatch(&who, &call, info, len)?;
+		let withdrawn = before.saturating_sub(T::Currency::free_balance(&who));
+
+		let result = call.dispatch(frame_system::RawOrigin::Signed(who.clone()).into());
+		let post_info = match result {
+			Ok(post_info) => post_info,
+			Err(err) => err.post_info,
+		};
+		let result = result.map(|_| ()).map_err(|e| e.error);
+
+		let before = T::Currency::free_balance(&who);
+		Extra::post_dispatch(pre, info, &post_info, len, &result);
+		let refunded = T::Currency::free_balance(&who).saturating_sub(before);
+
+		Ok(withdrawn.saturating_sub(refunded))
+	}
 }
 
 /// Require the transactor pay for themselves and maybe include a tip to gain additional priority
@@ -137,6 +199,11 @@ impl<T: Trait + Send + Sync> ChargeTransactionPayment<T> {
 		Self(fee)
 	}
 
+	/// The tip included in the transaction.
+	pub fn tip(&self) -> BalanceOf<T> {
+		self.0
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	///
 	/// The final fee is composed of:
@@ -153,9 +220,24 @@ impl<T: Trait + Send + Sync> ChargeTransactionPayment<T> {
 	/// final_fee = base_fee + targeted_fee_adjustment(len_fee + weight_fee) + tip;
 	pub fn compute_fee(
 		len: u32,
-		info: <Self as SignedExtension>::DispatchInfo,
+		info: DispatchInfo,
 		tip: BalanceOf<T>,
 	) -> BalanceOf<T>
+	where
+		BalanceOf<T>: Sync + Send,
+	{
+		Self::compute_fee_details(len, info, tip).final_fee()
+	}
+
+	/// Compute the fee of a particular transaction, broken down into its components.
+	///
+	/// See `compute_fee` for the meaning of each of them. Transactions which do not pay fees only
+	/// have a `tip`.
+	pub fn compute_fee_details(
+		len: u32,
+		info: DispatchInfo,
+		tip: BalanceOf<T>,
+	) -> FeeDetails<BalanceOf<T>>
 	where
 		BalanceOf<T>: Sync + Send,
 	{
@@ -177,10 +259,66 @@ impl<T: Trait + Send + Sync> ChargeTransactionPayment<T> {
 			// adjusted_fee = adjustable_fee + (adjustable_fee * targeted_fee_adjustment)
 			let adjusted_fee = targeted_fee_adjustment.saturated_multiply_accumulate(adjustable_fee);
 
-			let base_fee = T::TransactionBaseFee::get();
-			base_fee.saturating_add(adjusted_fee).saturating_add(tip)
+			FeeDetails {
+				base_fee: T::TransactionBaseFee::get(),
+				len_fee,
+				weight_fee,
+				adjusted_fee,
+				tip,
+				actual_fee: None,
+			}
 		} else {
-			tip
+			FeeDetails { tip, ..Default::default() }
+		}
+	}
+
+	/// Compute the fee of a transaction once it has been dispatched, based on the weight it
+	/// actually consumed rather than the one declared up front.
+	pub fn compute_actual_fee(
+		len: u32,
+		info: DispatchInfo,
+		post_info: &PostDispatchInfo,
+		tip: BalanceOf<T>,
+	) -> BalanceOf<T>
+	where
+		BalanceOf<T>: Sync + Send,
+	{
+		let info = DispatchInfo { weight: post_info.calc_actual_weight(&info), ..info };
+		Self::compute_fee(len, info, tip)
+	}
+
+	/// Withdraw the full fee of a transaction from `who`, assuming its declared weight is used.
+	///
+	/// Returns the fee together with the withdrawn imbalance, if there was anything to withdraw.
+	fn withdraw_fee(
+		&self,
+		who: &T::AccountId,
+		info: DispatchInfo,
+		len: usize,
+	) -> Result<(BalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError>
+	where
+		BalanceOf<T>: Sync + Send,
+	{
+		let tip = self.0;
+		let fee = Self::compute_fee(len as u32, info, tip);
+
+		// Only mess with balances if fee is not zero.
+		if fee.is_zero() {
+			return Ok((fee, None));
+		}
+
+		match T::Currency::withdraw(
+			who,
+			fee,
+			if tip.is_zero() {
+				WithdrawReason::TransactionPayment.into()
+			} else {
+				WithdrawReason::TransactionPayment | WithdrawReason::Tip
+			},
+			ExistenceRequirement::KeepAlive,
+		) {
+			Ok(imbalance) => Ok((fee, Some(imbalance))),
+			Err(_) => Err(InvalidTransaction::Payment.into()),
 		}
 	}
 }
@@ -196,15 +334,23 @@ impl<T: Trait + Send + Sync> sp_std::fmt::Debug for ChargeTransactionPayment<T>
 	}
 }
 
-impl<T: Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T>
-	where BalanceOf<T>: Send + Sync
+impl<T: Trait + Send + Sync> GetTip<BalanceOf<T>> for ChargeTransactionPayment<T> {
+	fn tip(&self) -> Option<BalanceOf<T>> {
+		Some(self.0)
+	}
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T> where
+	BalanceOf<T>: Send + Sync,
+	PostDispatchInfoOf<T::Call>: Into<PostDispatchInfo>,
 {
 	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
 	type AccountId = T::AccountId;
 	type Call = T::Call;
 	type AdditionalSigned = ();
 	type DispatchInfo = DispatchInfo;
-	type Pre = ();
+	/// The tip, the sender and what was withdrawn from them before dispatch.
+	type Pre = (BalanceOf<T>, Self::AccountId, Option<NegativeImbalanceOf<T>>);
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> { Ok(()) }
 
 	fn validate(
@@ -214,26 +360,8 @@ impl<T: Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T>
 		info: Self::DispatchInfo,
 		len: usize,
 	) -> TransactionValidity {
-		// pay any fees.
-		let tip = self.0;
-		let fee = Self::compute_fee(len as u32, info, tip);
-		// Only mess with balances if fee is not zero.
-		if !fee.is_zero() {
-			let imbalance = match T::Currency::withdraw(
-				who,
-				fee,
-				if tip.is_zero() {
-					WithdrawReason::TransactionPayment.into()
-				} else {
-					WithdrawReason::TransactionPayment | WithdrawReason::Tip
-				},
-				ExistenceRequirement::KeepAlive,
-			) {
-				Ok(imbalance) => imbalance,
-				Err(_) => return InvalidTransaction::Payment.into(),
-			};
-			T::OnTransactionPayment::on_unbalanced(imbalance);
-		}
+		// the imbalance is dropped here; changes made during validation are discarded anyway.
+		let (fee, _) = self.withdraw_fee(who, info, len)?;
 
 		let mut r = ValidTransaction::default();
 		// NOTE: we probably want to maximize the _fee (of any type) per weight unit_ here, which
@@ -241,6 +369,42 @@ impl<T: Trait + Send + Sync> SignedExtension for ChargeTransactionPayment<T>
 		r.priority = fee.saturated_into::<TransactionPriority>();
 		Ok(r)
 	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		_call: &Self::Call,
+		info: Self::DispatchInfo,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let (_, imbalance) = self.withdraw_fee(who, info, len)?;
+		Ok((self.0, who.clone(), imbalance))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		info: Self::DispatchInfo,
+		post_info: &PostDispatchInfoOf<T::Call>,
+		len: usize,
+		_result: &DispatchResult,
+	) {
+		let (tip, who, imbalance) = pre;
+		if let Some(payed) = imbalance {
+			let post_info = (*post_info).into();
+			let actual_fee = Self::compute_actual_fee(len as u32, info, &post_info, tip);
+			let refund = payed.peek().saturating_sub(actual_fee);
+			let actual_payment = match T::Currency::deposit_into_existing(&who, refund) {
+				// the refund is never larger than what was payed up front.
+				Ok(refund_imbalance) => match payed.offset(refund_imbalance) {
+					Ok(actual_payment) => actual_payment,
+					Err(_) => return,
+				},
+				// the account was reaped in the meantime, keep the whole fee.
+				Err(_) => payed,
+			};
+			T::OnTransactionPayment::on_unbalanced(actual_payment);
+		}
+	}
 }
 
 #[cfg(test)]
@@ -259,7 +423,7 @@ mod tests {
 	};
 	use pallet_balances::Call as BalancesCall;
 	use sp_std::cell::RefCell;
-	use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+	use pallet_transaction_payment_rpc_runtime_api::{RuntimeDispatchInfo, FeeDetails};
 
 	const CALL: &<Runtime as frame_system::Trait>::Call = &Call::Balances(BalancesCall::transfer(2, 69));
 
@@ -437,6 +601,59 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn signed_extension_transaction_payment_refunds_unused_weight() {
+			ExtBuilder::default()
+			.balance_factor(10) // 100
+			.fees(5, 1, 1) // 5 fixed, 1 per byte, 1 per weight
+			.build()
+			.execute_with(||
+		{
+			let len = 10;
+			let info = info_from_weight(20);
+			let pre = ChargeTransactionPayment::<Runtime>::from(5 /* tipped */)
+				.pre_dispatch(&2, CALL, info, len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 20 - 5);
+
+			// only 5 of the 20 declared weight was used.
+			let post_info = PostDispatchInfo { actual_weight: Some(5) };
+			ChargeTransactionPayment::<Runtime>::post_dispatch(pre, info, &post_info, len, &Ok(()));
+			assert_eq!(Balances::free_balance(2), 200 - 5 - 10 - 5 - 5);
+
+			// no refund when the declared weight is used.
+			let pre = ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&1, CALL, info, len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 20);
+			let post_info = PostDispatchInfo { actual_weight: None };
+			ChargeTransactionPayment::<Runtime>::post_dispatch(pre, info, &post_info, len, &Ok(()));
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 20);
+		});
+	}
+
+	#[test]
+	fn signed_extension_transaction_payment_refund_is_bounded() {
+			ExtBuilder::default()
+			.balance_factor(10) // 100
+			.fees(5, 1, 1) // 5 fixed, 1 per byte, 1 per weight
+			.build()
+			.execute_with(||
+		{
+			let len = 10;
+			let info = info_from_weight(20);
+			let pre = ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&1, CALL, info, len)
+				.unwrap();
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 20);
+
+			// reporting more weight than declared does not charge more.
+			let post_info = PostDispatchInfo { actual_weight: Some(50) };
+			ChargeTransactionPayment::<Runtime>::post_dispatch(pre, info, &post_info, len, &Ok(()));
+			assert_eq!(Balances::free_balance(1), 100 - 5 - 10 - 20);
+		});
+	}
+
 	#[test]
 	fn signed_extension_transaction_payment_is_bounded() {
 			ExtBuilder::default()
@@ -553,6 +770,95 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn query_fee_details_works() {
+		let call = Call::Balances(BalancesCall::transfer(2, 69));
+		let origin = 111111;
+		let extra = ();
+		let xt = TestXt::new(call, Some((origin, extra))).unwrap();
+		let info  = xt.get_dispatch_info();
+		let ext = xt.encode();
+		let len = ext.len() as u32;
+		ExtBuilder::default()
+			.fees(5, 1, 2)
+			.build()
+			.execute_with(||
+		{
+			// all fees should be x1.5
+			NextFeeMultiplier::put(Fixed64::from_rational(1, 2));
+
+			let weight_fee = info.weight.min(MaximumBlockWeight::get()) as u64 * 2;
+			let details = TransactionPayment::query_fee_details(xt.clone(), len, 7);
+			assert_eq!(
+				details,
+				FeeDetails {
+					base_fee: 5,
+					len_fee: len as u64,
+					weight_fee,
+					adjusted_fee: (len as u64 + weight_fee) * 3 / 2,
+					tip: 7,
+					actual_fee: None,
+				},
+			);
+			// the breakdown adds up to what `query_info` reports, once the tip is removed.
+			assert_eq!(details.final_fee() - 7, TransactionPayment::query_info(xt, len).partial_fee);
+		});
+	}
+
+	#[test]
+	fn simulate_fee_works() {
+		ExtBuilder::default()
+			.balance_factor(1000)
+			.fees(5, 1, 1)
+			.build()
+			.execute_with(||
+		{
+			let call = Call::Balances(BalancesCall::transfer(2, 69));
+			let info = call.get_dispatch_info();
+			let len = 10;
+			let expected = ChargeTransactionPayment::<Runtime>::compute_fee(len, info, 3);
+
+			let extra = |tip| ChargeTransactionPayment::<Runtime>::from(tip);
+
+			assert_eq!(TransactionPayment::simulate_fee(1, extra(3), call, len), Ok(expected));
+			// the transfer has been dispatched as well.
+			assert_eq!(Balances::free_balance(2), 20000 + 69);
+			assert_eq!(Balances::free_balance(1), 10000 - 69 - expected);
+
+			// a failing dispatch still pays its fee.
+			let call = Call::Balances(BalancesCall::transfer(2, 100_000));
+			assert_eq!(TransactionPayment::simulate_fee(3, extra(0), call, len), Ok(expected - 3));
+			assert_eq!(Balances::free_balance(3), 30000 - (expected - 3));
+
+			// an account that cannot pay is rejected.
+			let call = Call::Balances(BalancesCall::transfer(2, 1));
+			assert_eq!(
+				TransactionPayment::simulate_fee(7, extra(0), call, len),
+				Err(InvalidTransaction::Payment.into()),
+			);
+
+			// so is a transaction that another signed extension rejects, before any fee is paid.
+			let call = Call::Balances(BalancesCall::transfer(2, 1));
+			let stale = (frame_system::CheckNonce::<Runtime>::from(0), extra(0));
+			System::inc_account_nonce(&4);
+			assert_eq!(
+				TransactionPayment::simulate_fee(4, stale, call, len),
+				Err(InvalidTransaction::Stale.into()),
+			);
+			assert_eq!(Balances::free_balance(4), 40000);
+		});
+	}
+
+	#[test]
+	fn tip_is_found_among_signed_extensions() {
+		let extra = (
+			frame_system::CheckNonce::<Runtime>::from(0),
+			ChargeTransactionPayment::<Runtime>::from(7),
+		);
+		assert_eq!(GetTip::<u64>::tip(&extra), Some(7));
+		assert_eq!(GetTip::<u64>::tip(&(frame_system::CheckNonce::<Runtime>::from(0),)), None);
+	}
+
 	#[test]
 	fn compute_fee_works_without_multiplier() {
 		ExtBuilder::default()
@@ -31,6 +31,17 @@
 //! By way of example, the Council could vote to fund the Treasury with a portion of the block
 //! reward and use the funds to pay developers.
 //!
+//! ### Spend tracks
+//!
+//! Besides `ApproveOrigin`, which may approve spends of any value, the runtime may configure a
+//! `SpendOrigin` mapping other origins (e.g. a technical committee or a simple council majority)
+//! to a spend track. Each track may approve spends up to a maximum total value per spend period,
+//! which is enforced by this module.
+//!
+//! Spends may also be paid out in assets other than the native currency, from the holdings of the
+//! treasury account in the `Assets` set of fungible asset classes. Such spends are paid out at the
+//! next spend period for which the treasury holds enough of the asset.
+//!
 //! ### Tipping
 //!
 //! A separate subsystem exists to allow for an agile "tipping" process, whereby a reward may be
@@ -43,6 +54,20 @@
 //! countdown period, the median of all declared tips is paid to the reported beneficiary, along
 //! with any finders fee, in case of a public (and bonded) original report.
 //!
+//! ### Bounties
+//!
+//! A bounty is a specific budget for some work, approved by the `ApproveOrigin` and funded from the
+//! pot in the same spend period mechanics as proposals. Once funded, a curator is proposed with a
+//! fee; after accepting (and placing a deposit proportional to the fee), the curator awards the
+//! bounty to a beneficiary once the work is done. The payout can be claimed after a delay. A
+//! curator must signal activity at least once per update period, otherwise anyone may unassign them
+//! and their deposit is slashed.
+//!
+//! The curator of an active bounty may split part of its funds off into child bounties, each with
+//! its own curator and beneficiary. Child bounty curators must signal activity within the same
+//! update period as bounty curators. A bounty cannot be awarded or closed while it has child
+//! bounties.
+//!
 //! ### Terminology
 //!
 //! - **Proposal:** A suggestion to allocate funds from the pot to a beneficiary.
@@ -63,6 +88,14 @@
 //! - **Finders Fee:** Some proportion of the tip amount that is paid to the reporter of the tip,
 //!   rather than the main beneficiary.
 //!
+//! Bounties protocol:
+//! - **Bounty:** A reward for a predefined body of work upon completion, funded from the pot.
+//! - **Curator:** An account managing a bounty and assessing the work, paid a fee for doing so.
+//! - **Curator Deposit:** Funds that a curator must lock when accepting a bounty, slashed if the
+//!   curator misbehaves or stays inactive.
+//! - **Child Bounty:** A bounty funded from the account of a parent bounty, managed by its own
+//!   curator under the parent curator's supervision.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -73,6 +106,8 @@
 //! - `configure` - Configure the module's proposal requirements.
 //! - `reject_proposal` - Reject a proposal, slashing the deposit.
 //! - `approve_proposal` - Accept the proposal, returning the deposit.
+//! - `spend_asset` - Approve a spend paid out in a non-native asset.
+//! - `void_asset_spend` - Void an asset spend which has not been paid out yet.
 //!
 //! Tipping protocol:
 //! - `report_awesome` - Report something worthy of a tip and register for a finders fee.
@@ -81,22 +116,47 @@
 //! - `tip` - Declare or redeclare an amount to tip for a particular reason.
 //! - `close_tip` - Close and pay out a tip.
 //!
+//! Bounties protocol:
+//! - `propose_bounty` - Propose a bounty and stake the required deposit.
+//! - `approve_bounty` - Accept a bounty for funding, returning the deposit once funded.
+//! - `propose_curator` - Assign a curator and their fee to a funded bounty.
+//! - `unassign_curator` - Unassign a curator, slashing their deposit where they are at fault.
+//! - `accept_curator` - Accept the role of curator, reserving the curator deposit.
+//! - `award_bounty` - Award an active bounty to a beneficiary.
+//! - `claim_bounty` - Pay out an awarded bounty after the payout delay.
+//! - `close_bounty` - Cancel a bounty, returning its funds to the pot.
+//! - `extend_bounty_expiry` - Signal curator activity, postponing the update deadline.
+//! - `add_child_bounty` - Fund a child bounty from an active bounty.
+//! - `propose_child_bounty_curator` - Assign a curator and their fee to a child bounty.
+//! - `accept_child_bounty_curator` - Accept the role of child bounty curator.
+//! - `unassign_child_bounty_curator` - Unassign a child bounty curator, slashing their deposit
+//!   where they are at fault.
+//! - `extend_child_bounty_expiry` - Signal child bounty curator activity, postponing the update
+//!   deadline.
+//! - `award_child_bounty` - Award a child bounty to a beneficiary.
+//! - `claim_child_bounty` - Pay out an awarded child bounty after the payout delay.
+//! - `close_child_bounty` - Cancel a child bounty, returning its funds to the parent bounty.
+//!
 //! ## GenesisConfig
 //!
 //! The Treasury module depends on the [`GenesisConfig`](./struct.GenesisConfig.html).
 
+#![recursion_limit="256"]
 #![cfg_attr(not(feature = "std"), no_std)]
 
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use sp_std::prelude::*;
-use frame_support::{decl_module, decl_storage, decl_event, ensure, print, decl_error, Parameter};
+use frame_support::{
+	decl_module, decl_storage, decl_event, ensure, print, decl_error, Parameter, BoundedVec,
+};
 use frame_support::traits::{
 	Currency, ExistenceRequirement, Get, Imbalance, OnUnbalanced, ExistenceRequirement::AllowDeath,
-	ReservableCurrency, WithdrawReason
+	ReservableCurrency, WithdrawReason, fungibles,
 };
-use sp_runtime::{Permill, ModuleId, Percent, RuntimeDebug, traits::{
-	Zero, EnsureOrigin, StaticLookup, AccountIdConversion, Saturating, Hash, BadOrigin
+use sp_runtime::{Permill, ModuleId, Percent, RuntimeDebug, DispatchError, traits::{
+	Zero, EnsureOrigin, StaticLookup, AccountIdConversion, Saturating, Hash, BadOrigin, Convert,
+	Bounded,
 }};
 use frame_support::{weights::SimpleDispatchInfo, traits::Contains};
 use codec::{Encode, Decode};
@@ -105,10 +165,15 @@ use frame_system::{self as system, ensure_signed};
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 type PositiveImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::PositiveImbalance;
 type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+type AssetIdOf<T> = <<T as Trait>::Assets as fungibles::Inspect<<T as frame_system::Trait>::AccountId>>::AssetId;
+type AssetBalanceOf<T> = <<T as Trait>::Assets as fungibles::Inspect<<T as frame_system::Trait>::AccountId>>::Balance;
 
 /// The treasury's module id, used for deriving its sovereign account ID.
 const MODULE_ID: ModuleId = ModuleId(*b"py/trsry");
 
+/// The maximum length of a tip reason or a bounty description.
+const MAX_SENSIBLE_REASON_LENGTH: usize = 16384;
+
 pub trait Trait: frame_system::Trait {
 	/// The staking balance.
 	type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
@@ -116,6 +181,20 @@ pub trait Trait: frame_system::Trait {
 	/// Origin from which approvals must come.
 	type ApproveOrigin: EnsureOrigin<Self::Origin>;
 
+	/// Origin from which spends of limited value may be approved. It yields the spend track of the
+	/// origin and the maximum total value which that track may approve per spend period.
+	type SpendOrigin: EnsureOrigin<Self::Origin, Success = (SpendTrackId, BalanceOf<Self>)>;
+
+	/// The set of fungible asset classes, besides `Currency`, in which spends may be paid out.
+	type Assets: fungibles::Transfer<Self::AccountId>;
+
+	/// The value of an amount of some asset in `Currency`, used to check asset spends against the
+	/// limits of the spend tracks.
+	type AssetValue: Convert<(AssetIdOf<Self>, AssetBalanceOf<Self>), BalanceOf<Self>>;
+
+	/// Maximum number of approved asset spends awaiting payout.
+	type MaxAssetSpends: Get<u32>;
+
 	/// Origin from which rejections must come.
 	type RejectOrigin: EnsureOrigin<Self::Origin>;
 
@@ -152,6 +231,25 @@ pub trait Trait: frame_system::Trait {
 
 	/// Percentage of spare funds (if any) that are burnt per spend period.
 	type Burn: Get<Permill>;
+
+	/// The amount held on deposit for placing a bounty proposal.
+	type BountyDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount held on deposit per byte within the bounty description.
+	type BountyDepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The delay period for which a bounty beneficiary needs to wait before claiming the payout.
+	type BountyDepositPayoutDelay: Get<Self::BlockNumber>;
+
+	/// The period within which a bounty curator must signal activity before anyone may unassign
+	/// them.
+	type BountyUpdatePeriod: Get<Self::BlockNumber>;
+
+	/// Percentage of the curator fee that will be reserved upfront as deposit for bounty curator.
+	type BountyCuratorDeposit: Get<Permill>;
+
+	/// Minimum value for a bounty or a child bounty.
+	type BountyValueMinimum: Get<BalanceOf<Self>>;
 }
 
 /// An index of a proposal. Just a `u32`.
@@ -171,6 +269,34 @@ pub struct Proposal<AccountId, Balance> {
 	bond: Balance,
 }
 
+/// An identifier of a spend track. Just a `u16`.
+pub type SpendTrackId = u16;
+
+/// An approved spend which is paid out in a non-native asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AssetSpend<AccountId, AssetId, AssetBalance, BlockNumber, Balance> {
+	/// The asset in which the payment is made.
+	asset_id: AssetId,
+	/// The amount of the asset to pay.
+	amount: AssetBalance,
+	/// The account to whom the payment should be made.
+	beneficiary: AccountId,
+	/// The spend track which approved the spend, together with the first block of the spend period
+	/// it was approved in and the value it counts for against the limit of the track.
+	track: Option<(SpendTrackId, BlockNumber, Balance)>,
+}
+
+/// An `AssetValue` for runtimes which have no price for their assets.
+///
+/// Any amount of any asset is valued above every spend track limit, so that asset spends can only
+/// be approved by `ApproveOrigin`.
+pub struct UnpricedAssets;
+impl<AssetId, AssetBalance, Balance: Bounded> Convert<(AssetId, AssetBalance), Balance> for UnpricedAssets {
+	fn convert(_: (AssetId, AssetBalance)) -> Balance {
+		Balance::max_value()
+	}
+}
+
 /// An open tipping "motion". Retains all details of a tip including information on the finder
 /// and the members who have voted.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
@@ -194,6 +320,102 @@ pub struct OpenTip<
 	tips: Vec<(AccountId, Balance)>,
 }
 
+/// An index of a bounty. Just a `u32`.
+pub type BountyIndex = u32;
+
+/// A bounty proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Bounty<AccountId, Balance, BlockNumber> {
+	/// The account proposing it.
+	proposer: AccountId,
+	/// The (total) amount that should be paid if the bounty is rewarded, including the curator fee.
+	value: Balance,
+	/// The curator fee. Included in `value`.
+	fee: Balance,
+	/// The amount held on deposit (reserved) by the curator.
+	curator_deposit: Balance,
+	/// The amount held on deposit (reserved) for making this proposal.
+	bond: Balance,
+	/// The status of this bounty.
+	status: BountyStatus<AccountId, BlockNumber>,
+}
+
+/// The status of a bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum BountyStatus<AccountId, BlockNumber> {
+	/// The bounty is proposed and waiting for approval.
+	Proposed,
+	/// The bounty is approved and waiting to be funded.
+	Approved,
+	/// The bounty is funded and waiting for a curator assignment.
+	Funded,
+	/// A curator has been proposed by the `ApproveOrigin`, waiting for acceptance from the
+	/// curator.
+	CuratorProposed {
+		/// The assigned curator of this bounty.
+		curator: AccountId,
+	},
+	/// The bounty is active and waiting to be awarded.
+	Active {
+		/// The curator of this bounty.
+		curator: AccountId,
+		/// An update from the curator is due by this block, else they are considered inactive.
+		update_due: BlockNumber,
+	},
+	/// The bounty is awarded and waiting to be claimed.
+	PendingPayout {
+		/// The curator of this bounty.
+		curator: AccountId,
+		/// The beneficiary of the bounty.
+		beneficiary: AccountId,
+		/// When the bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
+/// A child bounty, funded from the account of its parent bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ChildBounty<AccountId, Balance, BlockNumber> {
+	/// The (total) amount that should be paid if the child bounty is rewarded, including the
+	/// curator fee.
+	value: Balance,
+	/// The curator fee. Included in `value`.
+	fee: Balance,
+	/// The amount held on deposit (reserved) by the curator.
+	curator_deposit: Balance,
+	/// The status of this child bounty.
+	status: ChildBountyStatus<AccountId, BlockNumber>,
+}
+
+/// The status of a child bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ChildBountyStatus<AccountId, BlockNumber> {
+	/// The child bounty is funded and waiting for a curator assignment.
+	Added,
+	/// A curator has been proposed by the parent bounty curator, waiting for acceptance from the
+	/// curator.
+	CuratorProposed {
+		/// The assigned curator of this child bounty.
+		curator: AccountId,
+	},
+	/// The child bounty is active and waiting to be awarded.
+	Active {
+		/// The curator of this child bounty.
+		curator: AccountId,
+		/// An update from the curator is due by this block, else they are considered inactive.
+		update_due: BlockNumber,
+	},
+	/// The child bounty is awarded and waiting to be claimed.
+	PendingPayout {
+		/// The curator of this child bounty.
+		curator: AccountId,
+		/// The beneficiary of the child bounty.
+		beneficiary: AccountId,
+		/// When the child bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
 decl_storage! {
 	trait Store for Module<T: Trait> as Treasury {
 		/// Number of proposals that have been made.
@@ -205,6 +427,18 @@ decl_storage! {
 		/// Proposal indices that have been approved but not yet awarded.
 		Approvals get(fn approvals): Vec<ProposalIndex>;
 
+		/// The value approved through each spend track, along with the first block of the spend
+		/// period it was approved in.
+		pub SpendTrackUsage get(fn spend_track_usage):
+			map hasher(twox_64_concat) SpendTrackId => (T::BlockNumber, BalanceOf<T>);
+
+		/// Approved spends in non-native assets which have not yet been paid out.
+		pub AssetSpends get(fn asset_spends):
+			BoundedVec<
+				AssetSpend<T::AccountId, AssetIdOf<T>, AssetBalanceOf<T>, T::BlockNumber, BalanceOf<T>>,
+				T::MaxAssetSpends,
+			>;
+
 		/// Tips that are not yet completed. Keyed by the hash of `(reason, who)` from the value.
 		/// This has the insecure enumerable hash function since the key itself is already
 		/// guaranteed to be a secure hash.
@@ -214,6 +448,38 @@ decl_storage! {
 		/// Simple preimage lookup from the reason's hash to the original data. Again, has an
 		/// insecure enumerable hash since the key is guaranteed to be the result of a secure hash.
 		pub Reasons get(fn reasons): map hasher(twox_64_concat) T::Hash => Option<Vec<u8>>;
+
+		/// Number of bounty proposals that have been made.
+		pub BountyCount get(fn bounty_count): BountyIndex;
+
+		/// Bounties that have been made.
+		pub Bounties get(fn bounties):
+			map hasher(twox_64_concat) BountyIndex
+			=> Option<Bounty<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+		/// The description of each bounty.
+		pub BountyDescriptions get(fn bounty_descriptions):
+			map hasher(twox_64_concat) BountyIndex => Option<Vec<u8>>;
+
+		/// Bounty indices that have been approved but not yet funded.
+		pub BountyApprovals get(fn bounty_approvals): Vec<BountyIndex>;
+
+		/// Number of child bounties that have been added.
+		pub ChildBountyCount get(fn child_bounty_count): BountyIndex;
+
+		/// Child bounties that have been added, keyed by the parent bounty index and the child
+		/// bounty index.
+		pub ChildBounties get(fn child_bounties):
+			double_map hasher(twox_64_concat) BountyIndex, hasher(twox_64_concat) BountyIndex
+			=> Option<ChildBounty<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+		/// The description of each child bounty.
+		pub ChildBountyDescriptions get(fn child_bounty_descriptions):
+			map hasher(twox_64_concat) BountyIndex => Option<Vec<u8>>;
+
+		/// The number of open child bounties of each parent bounty.
+		pub ParentChildBounties get(fn parent_child_bounties):
+			map hasher(twox_64_concat) BountyIndex => u32;
 	}
 	add_extra_genesis {
 		build(|_config| {
@@ -232,6 +498,8 @@ decl_event!(
 		Balance = BalanceOf<T>,
 		<T as frame_system::Trait>::AccountId,
 		<T as frame_system::Trait>::Hash,
+		AssetId = AssetIdOf<T>,
+		AssetBalance = AssetBalanceOf<T>,
 	{
 		/// New proposal.
 		Proposed(ProposalIndex),
@@ -241,6 +509,12 @@ decl_event!(
 		Awarded(ProposalIndex, Balance, AccountId),
 		/// A proposal was rejected; funds were slashed.
 		Rejected(ProposalIndex, Balance),
+		/// A spend of some amount of an asset to a beneficiary was approved.
+		AssetSpendApproved(AssetId, AssetBalance, AccountId),
+		/// Some amount of an asset has been paid out to a beneficiary.
+		AssetAwarded(AssetId, AssetBalance, AccountId),
+		/// An approved asset spend has been voided before being paid out.
+		AssetSpendVoided(AssetId, AssetBalance, AccountId),
 		/// Some of our funds have been burnt.
 		Burnt(Balance),
 		/// Spending has finished; this is the amount that rolls over until next spend.
@@ -255,6 +529,30 @@ decl_event!(
 		TipClosed(Hash, AccountId, Balance),
 		/// A tip suggestion has been retracted.
 		TipRetracted(Hash),
+		/// New bounty proposal.
+		BountyProposed(BountyIndex),
+		/// A bounty proposal was rejected; funds were slashed.
+		BountyRejected(BountyIndex, Balance),
+		/// A bounty proposal is funded and became active.
+		BountyBecameActive(BountyIndex),
+		/// A bounty is awarded to a beneficiary.
+		BountyAwarded(BountyIndex, AccountId),
+		/// A bounty is claimed by beneficiary.
+		BountyClaimed(BountyIndex, Balance, AccountId),
+		/// A bounty is cancelled.
+		BountyCanceled(BountyIndex),
+		/// A bounty expiry is extended.
+		BountyExtended(BountyIndex),
+		/// A child bounty (second index) is added to a bounty (first index).
+		ChildBountyAdded(BountyIndex, BountyIndex),
+		/// A child bounty is awarded to a beneficiary.
+		ChildBountyAwarded(BountyIndex, BountyIndex, AccountId),
+		/// A child bounty is claimed by beneficiary.
+		ChildBountyClaimed(BountyIndex, BountyIndex, Balance, AccountId),
+		/// A child bounty is cancelled.
+		ChildBountyCanceled(BountyIndex, BountyIndex),
+		/// A child bounty expiry is extended.
+		ChildBountyExtended(BountyIndex, BountyIndex),
 	}
 );
 
@@ -275,8 +573,32 @@ decl_error! {
 		NotFinder,
 		/// The tip cannot be claimed/closed because there are not enough tippers yet.
 		StillOpen,
-		/// The tip cannot be claimed/closed because it's still in the countdown period.
+		/// The tip or bounty cannot be claimed/closed because it's still in the countdown period.
 		Premature,
+		/// No bounty or child bounty at that index.
+		InvalidIndex,
+		/// The bounty status is unexpected.
+		UnexpectedStatus,
+		/// Require bounty curator.
+		RequireCurator,
+		/// Invalid bounty value.
+		InvalidValue,
+		/// Invalid bounty fee.
+		InvalidFee,
+		/// A bounty payout is pending.
+		/// To cancel the bounty, you must unassign and slash the curator.
+		PendingPayout,
+		/// The bounty cannot be awarded or closed while it has open child bounties.
+		HasChildBounties,
+		/// The spend exceeds what the spend track of the origin may still approve in this spend
+		/// period.
+		SpendLimitExceeded,
+		/// There can only be a maximum of `MaxAssetSpends` asset spends awaiting payout.
+		TooManyAssetSpends,
+		/// The proposal has already been approved.
+		ProposalAlreadyApproved,
+		/// No asset spend awaiting payout at that index.
+		InvalidAssetSpendIndex,
 	}
 }
 
@@ -307,6 +629,27 @@ decl_module! {
 		/// The amount held on deposit per byte within the tip report reason.
 		const TipReportDepositPerByte: BalanceOf<T> = T::TipReportDepositPerByte::get();
 
+		/// The amount held on deposit for placing a bounty proposal.
+		const BountyDepositBase: BalanceOf<T> = T::BountyDepositBase::get();
+
+		/// The amount held on deposit per byte within the bounty description.
+		const BountyDepositPerByte: BalanceOf<T> = T::BountyDepositPerByte::get();
+
+		/// The delay period for which a bounty beneficiary needs to wait before claiming the payout.
+		const BountyDepositPayoutDelay: T::BlockNumber = T::BountyDepositPayoutDelay::get();
+
+		/// The period within which a bounty curator must signal activity.
+		const BountyUpdatePeriod: T::BlockNumber = T::BountyUpdatePeriod::get();
+
+		/// Percentage of the curator fee that will be reserved upfront as deposit for bounty curator.
+		const BountyCuratorDeposit: Permill = T::BountyCuratorDeposit::get();
+
+		/// Minimum value for a bounty or a child bounty.
+		const BountyValueMinimum: BalanceOf<T> = T::BountyValueMinimum::get();
+
+		/// Maximum number of approved asset spends awaiting payout.
+		const MaxAssetSpends: u32 = T::MaxAssetSpends::get();
+
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
@@ -362,20 +705,101 @@ decl_module! {
 		/// Approve a proposal. At a later time, the proposal will be allocated to the beneficiary
 		/// and the original deposit will be returned.
 		///
+		/// The dispatch origin for this call must be either `ApproveOrigin` or `SpendOrigin`. In
+		/// the latter case, the value of the proposal counts against the limit of the spend track.
+		///
 		/// # <weight>
 		/// - O(1).
 		/// - Limited storage reads.
-		/// - One DB change.
+		/// - Up to two DB changes.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
 		fn approve_proposal(origin, #[compact] proposal_id: ProposalIndex) {
-			T::ApproveOrigin::ensure_origin(origin)?;
+			let maybe_track = Self::ensure_spend_origin(origin)?;
 
-			ensure!(<Proposals<T>>::contains_key(proposal_id), Error::<T>::InvalidProposalIndex);
+			let proposal = <Proposals<T>>::get(proposal_id).ok_or(Error::<T>::InvalidProposalIndex)?;
+			ensure!(!Self::approvals().contains(&proposal_id), Error::<T>::ProposalAlreadyApproved);
+			if let Some((track, max)) = maybe_track {
+				Self::note_track_spend(track, max, proposal.value)?;
+			}
 
 			Approvals::mutate(|v| v.push(proposal_id));
 		}
 
+		/// Approve a spend of `amount` of the asset `asset_id` to `beneficiary`. It is paid out
+		/// from the treasury's holdings of the asset at the next spend period at which they are
+		/// sufficient.
+		///
+		/// The dispatch origin for this call must be either `ApproveOrigin` or `SpendOrigin`. In
+		/// the latter case, the value of the spend according to `AssetValue` counts against the
+		/// limit of the spend track.
+		///
+		/// Emits `AssetSpendApproved` if successful.
+		///
+		/// # <weight>
+		/// - O(S) where S is the number of pending asset spends, at most `MaxAssetSpends`.
+		/// - Up to two DB changes.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn spend_asset(
+			origin,
+			asset_id: AssetIdOf<T>,
+			amount: AssetBalanceOf<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source
+		) {
+			let maybe_track = Self::ensure_spend_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let mut spends = Self::asset_spends();
+			let mut spend = AssetSpend { asset_id, amount, beneficiary: beneficiary.clone(), track: None };
+			ensure!((spends.len() as u32) < T::MaxAssetSpends::get(), Error::<T>::TooManyAssetSpends);
+
+			if let Some((track, max)) = maybe_track {
+				let value = T::AssetValue::convert((asset_id, amount));
+				let period_start = Self::note_track_spend(track, max, value)?;
+				spend.track = Some((track, period_start, value));
+			}
+
+			spends.try_push(spend).map_err(|_| Error::<T>::TooManyAssetSpends)?;
+			<AssetSpends<T>>::put(spends);
+
+			Self::deposit_event(RawEvent::AssetSpendApproved(asset_id, amount, beneficiary));
+		}
+
+		/// Void an approved asset spend which has not been paid out yet, e.g. because the treasury
+		/// does not hold the asset. If it was approved through a spend track in the current spend
+		/// period, its value no longer counts against the limit of the track.
+		///
+		/// The dispatch origin for this call must be `RejectOrigin`.
+		///
+		/// - `index`: The index of the spend in `AssetSpends`.
+		///
+		/// Emits `AssetSpendVoided` if successful.
+		///
+		/// # <weight>
+		/// - O(S) where S is the number of pending asset spends, at most `MaxAssetSpends`.
+		/// - Up to two DB changes.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn void_asset_spend(origin, #[compact] index: u32) {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let mut spends = Self::asset_spends();
+			ensure!((index as usize) < spends.len(), Error::<T>::InvalidAssetSpendIndex);
+			let spend = spends.remove(index as usize);
+			<AssetSpends<T>>::put(spends);
+
+			if let Some((track, period_start, value)) = spend.track {
+				<SpendTrackUsage<T>>::mutate(track, |(usage_start, used)| if *usage_start == period_start {
+					*used = used.saturating_sub(value);
+				});
+			}
+
+			Self::deposit_event(RawEvent::AssetSpendVoided(spend.asset_id, spend.amount, spend.beneficiary));
+		}
+
 		/// Report something `reason` that deserves a tip and claim any eventual the finder's fee.
 		///
 		/// The dispatch origin for this call must be _Signed_.
@@ -399,7 +823,6 @@ decl_module! {
 		fn report_awesome(origin, reason: Vec<u8>, who: T::AccountId) {
 			let finder = ensure_signed(origin)?;
 
-			const MAX_SENSIBLE_REASON_LENGTH: usize = 16384;
 			ensure!(reason.len() <= MAX_SENSIBLE_REASON_LENGTH, Error::<T>::ReasonTooBig);
 
 			let reason_hash = T::Hashing::hash(&reason[..]);
@@ -525,21 +948,805 @@ decl_module! {
 		///   as the hash of the tuple of the original tip `reason` and the beneficiary account ID.
 		///
 		/// # <weight>
-		/// - `O(T)`
-		/// - One storage retrieval (codec `O(T)`) and two removals.
-		/// - Up to three balance operations.
+		/// - `O(T)`
+		/// - One storage retrieval (codec `O(T)`) and two removals.
+		/// - Up to three balance operations.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
+		fn close_tip(origin, hash: T::Hash) {
+			ensure_signed(origin)?;
+
+			let tip = Tips::<T>::get(hash).ok_or(Error::<T>::UnknownTip)?;
+			let n = tip.closes.as_ref().ok_or(Error::<T>::StillOpen)?;
+			ensure!(system::Module::<T>::block_number() >= *n, Error::<T>::Premature);
+			// closed.
+			Reasons::<T>::remove(&tip.reason);
+			Tips::<T>::remove(hash);
+			Self::payout_tip(tip);
+		}
+
+		/// Propose a new bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `BountyDepositBase` will be reserved from the origin account, as well as
+		/// `BountyDepositPerByte` for each byte in `description`. It will be unreserved upon
+		/// approval, or slashed when rejected.
+		///
+		/// - `value`: The total payment amount of this bounty, curator fee included.
+		/// - `description`: The description of this bounty.
+		///
+		/// Emits `BountyProposed` if successful.
+		///
+		/// # <weight>
+		/// - `O(D)` where `D` is the length of `description`.
+		/// - One balance operation.
+		/// - Three storage insertions.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn propose_bounty(origin, #[compact] value: BalanceOf<T>, description: Vec<u8>) {
+			let proposer = ensure_signed(origin)?;
+
+			ensure!(description.len() <= MAX_SENSIBLE_REASON_LENGTH, Error::<T>::ReasonTooBig);
+			ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+
+			let bond = T::BountyDepositBase::get()
+				+ T::BountyDepositPerByte::get() * (description.len() as u32).into();
+			T::Currency::reserve(&proposer, bond)
+				.map_err(|_| Error::<T>::InsufficientProposersBalance)?;
+
+			let index = Self::bounty_count();
+			BountyCount::put(index + 1);
+
+			let bounty = Bounty {
+				proposer,
+				value,
+				fee: Zero::zero(),
+				curator_deposit: Zero::zero(),
+				bond,
+				status: BountyStatus::Proposed,
+			};
+			Bounties::<T>::insert(index, bounty);
+			BountyDescriptions::insert(index, description);
+
+			Self::deposit_event(RawEvent::BountyProposed(index));
+		}
+
+		/// Approve a bounty proposal. At a later time, the bounty will be funded and become active
+		/// and the original deposit will be returned.
+		///
+		/// The dispatch origin for this call must be `ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - Two DB changes.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn approve_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let mut bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(bounty.status == BountyStatus::Proposed, Error::<T>::UnexpectedStatus);
+
+			bounty.status = BountyStatus::Approved;
+			Bounties::<T>::insert(bounty_id, bounty);
+			BountyApprovals::mutate(|v| v.push(bounty_id));
+		}
+
+		/// Assign a curator to a funded bounty.
+		///
+		/// The dispatch origin for this call must be `ApproveOrigin`.
+		///
+		/// - `bounty_id`: The bounty to assign a curator to; it must be funded, or have a curator
+		///   proposed who did not yet accept.
+		/// - `curator`: The account proposed as curator.
+		/// - `fee`: The fee paid to the curator upon payout. Must be less than the bounty value.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn propose_curator(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			curator: <T::Lookup as StaticLookup>::Source,
+			#[compact] fee: BalanceOf<T>,
+		) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			let curator = T::Lookup::lookup(curator)?;
+
+			let mut bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			match bounty.status {
+				BountyStatus::Funded | BountyStatus::CuratorProposed { .. } => {},
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+			ensure!(fee < bounty.value, Error::<T>::InvalidFee);
+
+			bounty.status = BountyStatus::CuratorProposed { curator };
+			bounty.fee = fee;
+			Bounties::<T>::insert(bounty_id, bounty);
+		}
+
+		/// Unassign the curator of a bounty.
+		///
+		/// The dispatch origin for this call may be either `RejectOrigin` or _Signed_:
+		/// - The curator itself may step down at any point before awarding the bounty; its
+		///   deposit is returned.
+		/// - Any account may unassign an active curator who has not signalled activity within
+		///   `BountyUpdatePeriod`; the curator deposit is slashed.
+		/// - `RejectOrigin` may unassign the curator at any point, including a pending payout; the
+		///   curator deposit is slashed.
+		///
+		/// The bounty goes back to the `Funded` state.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change, up to one balance operation.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn unassign_curator(origin, #[compact] bounty_id: BountyIndex) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+
+			let mut bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+
+			match bounty.status {
+				BountyStatus::Proposed | BountyStatus::Approved | BountyStatus::Funded => {
+					// No curator to unassign.
+					Err(Error::<T>::UnexpectedStatus)?
+				},
+				BountyStatus::CuratorProposed { ref curator } => {
+					// A curator who did not accept yet has no deposit at stake; `RejectOrigin` or
+					// the proposed curator itself may undo the proposal.
+					ensure!(maybe_sender.map_or(true, |sender| sender == *curator), BadOrigin);
+				},
+				BountyStatus::Active { ref curator, ref update_due } => {
+					match maybe_sender {
+						// `RejectOrigin` can always unassign and slash.
+						None => Self::slash_curator(curator, &mut bounty.curator_deposit),
+						Some(sender) if sender == *curator => {
+							// The curator is stepping down voluntarily.
+							let _ = T::Currency::unreserve(curator, bounty.curator_deposit);
+							bounty.curator_deposit = Zero::zero();
+						},
+						Some(_) => {
+							// Anyone else may only unassign an inactive curator.
+							let block_number = system::Module::<T>::block_number();
+							ensure!(*update_due < block_number, Error::<T>::Premature);
+							Self::slash_curator(curator, &mut bounty.curator_deposit);
+						},
+					}
+				},
+				BountyStatus::PendingPayout { ref curator, .. } => {
+					// Only `RejectOrigin` may prevent an awarded bounty from being paid out.
+					ensure!(maybe_sender.is_none(), BadOrigin);
+					Self::slash_curator(curator, &mut bounty.curator_deposit);
+				},
+			};
+
+			bounty.status = BountyStatus::Funded;
+			Bounties::<T>::insert(bounty_id, bounty);
+		}
+
+		/// Accept the curator role for a bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the proposed curator. A deposit of
+		/// `BountyCuratorDeposit` of the fee will be reserved from the curator.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change, one balance operation.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn accept_curator(origin, #[compact] bounty_id: BountyIndex) {
+			let signer = ensure_signed(origin)?;
+
+			let mut bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			match bounty.status {
+				BountyStatus::CuratorProposed { ref curator } => {
+					ensure!(signer == *curator, Error::<T>::RequireCurator);
+				},
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+
+			let deposit = T::BountyCuratorDeposit::get() * bounty.fee;
+			T::Currency::reserve(&signer, deposit)?;
+			bounty.curator_deposit = deposit;
+
+			let update_due = system::Module::<T>::block_number() + T::BountyUpdatePeriod::get();
+			bounty.status = BountyStatus::Active { curator: signer, update_due };
+			Bounties::<T>::insert(bounty_id, bounty);
+		}
+
+		/// Award an active bounty to a beneficiary. The beneficiary will be able to claim the funds
+		/// after `BountyDepositPayoutDelay`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the curator of this bounty, and
+		/// the bounty must not have any open child bounties.
+		///
+		/// - `bounty_id`: Bounty ID to award.
+		/// - `beneficiary`: The beneficiary account whom will receive the payout.
+		///
+		/// Emits `BountyAwarded` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn award_bounty(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) {
+			let signer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let mut bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			let curator = match bounty.status {
+				BountyStatus::Active { curator, .. } => curator,
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+			ensure!(signer == curator, Error::<T>::RequireCurator);
+			ensure!(Self::parent_child_bounties(bounty_id) == 0, Error::<T>::HasChildBounties);
+
+			let unlock_at = system::Module::<T>::block_number() + T::BountyDepositPayoutDelay::get();
+			bounty.status = BountyStatus::PendingPayout {
+				curator,
+				beneficiary: beneficiary.clone(),
+				unlock_at,
+			};
+			Bounties::<T>::insert(bounty_id, bounty);
+
+			Self::deposit_event(RawEvent::BountyAwarded(bounty_id, beneficiary));
+		}
+
+		/// Claim the payout of an awarded bounty after the payout delay.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// The curator is paid its fee and its deposit is returned; the beneficiary receives the
+		/// rest of the bounty funds.
+		///
+		/// - `bounty_id`: Bounty ID to claim.
+		///
+		/// Emits `BountyClaimed` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - Two DB removals, three balance operations.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn claim_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			ensure_signed(origin)?;
+
+			let bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			let (curator, beneficiary, unlock_at) = match bounty.status {
+				BountyStatus::PendingPayout { curator, beneficiary, unlock_at } =>
+					(curator, beneficiary, unlock_at),
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+			ensure!(system::Module::<T>::block_number() >= unlock_at, Error::<T>::Premature);
+
+			let bounty_account = Self::bounty_account_id(bounty_id);
+			let payout = Self::payout_funds(
+				&bounty_account,
+				&curator,
+				bounty.fee,
+				bounty.curator_deposit,
+				&beneficiary,
+			);
+
+			Bounties::<T>::remove(bounty_id);
+			BountyDescriptions::remove(bounty_id);
+
+			Self::deposit_event(RawEvent::BountyClaimed(bounty_id, payout, beneficiary));
+		}
+
+		/// Cancel a proposed or active bounty. All the funds will be sent to the treasury and the
+		/// curator deposit will be unreserved if possible.
+		///
+		/// The dispatch origin for this call must be `RejectOrigin`.
+		///
+		/// Only a bounty which is not approved-but-unfunded, not awarded and has no open child
+		/// bounties can be canceled. A proposed bounty has its bond slashed.
+		///
+		/// - `bounty_id`: Bounty ID to cancel.
+		///
+		/// Emits `BountyRejected` or `BountyCanceled` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - Two DB removals, up to two balance operations.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn close_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			T::RejectOrigin::ensure_origin(origin)?;
+
+			let bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(Self::parent_child_bounties(bounty_id) == 0, Error::<T>::HasChildBounties);
+			match bounty.status {
+				BountyStatus::Proposed => {
+					// The bounty was never funded; slash the proposer's bond.
+					Bounties::<T>::remove(bounty_id);
+					BountyDescriptions::remove(bounty_id);
+
+					let value = bounty.bond;
+					let imbalance = T::Currency::slash_reserved(&bounty.proposer, value).0;
+					T::ProposalRejection::on_unbalanced(imbalance);
+
+					Self::deposit_event(RawEvent::BountyRejected(bounty_id, value));
+					return Ok(());
+				},
+				BountyStatus::Approved => {
+					// Cannot cancel while it is waiting to be funded; the approvals queue does not
+					// support removal.
+					Err(Error::<T>::UnexpectedStatus)?
+				},
+				BountyStatus::Funded | BountyStatus::CuratorProposed { .. } => {
+					// Nothing extra to do besides returning the funds.
+				},
+				BountyStatus::Active { ref curator, .. } => {
+					// The curator is not at fault here, so their deposit is returned.
+					let _ = T::Currency::unreserve(curator, bounty.curator_deposit);
+				},
+				BountyStatus::PendingPayout { .. } => {
+					// The curator must be unassigned and slashed first.
+					Err(Error::<T>::PendingPayout)?
+				},
+			};
+
+			Bounties::<T>::remove(bounty_id);
+			BountyDescriptions::remove(bounty_id);
+
+			let bounty_account = Self::bounty_account_id(bounty_id);
+			let balance = T::Currency::free_balance(&bounty_account);
+			// Should not fail given the bounty account is not locked or reserved.
+			let _ = T::Currency::transfer(&bounty_account, &Self::account_id(), balance, AllowDeath);
+
+			Self::deposit_event(RawEvent::BountyCanceled(bounty_id));
+		}
+
+		/// Extend the expiry time of an active bounty, signalling that the curator is still
+		/// working on it.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the curator of this bounty.
+		///
+		/// - `bounty_id`: Bounty ID to extend.
+		/// - `_remark`: Additional information, e.g. a progress report.
+		///
+		/// Emits `BountyExtended` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn extend_bounty_expiry(origin, #[compact] bounty_id: BountyIndex, _remark: Vec<u8>) {
+			let signer = ensure_signed(origin)?;
+
+			let mut bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			match bounty.status {
+				BountyStatus::Active { ref curator, ref mut update_due } => {
+					ensure!(signer == *curator, Error::<T>::RequireCurator);
+					*update_due = (system::Module::<T>::block_number() + T::BountyUpdatePeriod::get())
+						.max(*update_due);
+				},
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			}
+			Bounties::<T>::insert(bounty_id, bounty);
+
+			Self::deposit_event(RawEvent::BountyExtended(bounty_id));
+		}
+
+		/// Add a child bounty to an active bounty, funded from the parent bounty's account.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the curator of the parent bounty.
+		///
+		/// - `parent_bounty_id`: The active bounty to fund the child bounty from.
+		/// - `value`: The total payment amount of this child bounty, curator fee included.
+		/// - `description`: The description of this child bounty.
+		///
+		/// Emits `ChildBountyAdded` if successful.
+		///
+		/// # <weight>
+		/// - `O(D)` where `D` is the length of `description`.
+		/// - One balance operation.
+		/// - Four storage changes.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn add_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] value: BalanceOf<T>,
+			description: Vec<u8>,
+		) {
+			let signer = ensure_signed(origin)?;
+
+			ensure!(description.len() <= MAX_SENSIBLE_REASON_LENGTH, Error::<T>::ReasonTooBig);
+			ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+
+			let parent_curator = Self::ensure_active_bounty(parent_bounty_id)?;
+			ensure!(signer == parent_curator, Error::<T>::RequireCurator);
+
+			let index = Self::child_bounty_count();
+			// The parent bounty account must stay alive for its own payout.
+			T::Currency::transfer(
+				&Self::bounty_account_id(parent_bounty_id),
+				&Self::child_bounty_account_id(index),
+				value,
+				ExistenceRequirement::KeepAlive,
+			).map_err(|_| Error::<T>::InvalidValue)?;
+
+			ChildBountyCount::put(index + 1);
+			ParentChildBounties::mutate(parent_bounty_id, |count| *count += 1);
+
+			let child_bounty = ChildBounty {
+				value,
+				fee: Zero::zero(),
+				curator_deposit: Zero::zero(),
+				status: ChildBountyStatus::Added,
+			};
+			ChildBounties::<T>::insert(parent_bounty_id, index, child_bounty);
+			ChildBountyDescriptions::insert(index, description);
+
+			Self::deposit_event(RawEvent::ChildBountyAdded(parent_bounty_id, index));
+		}
+
+		/// Assign a curator to a child bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the curator of the parent bounty,
+		/// which must be active.
+		///
+		/// - `fee`: The fee paid to the child bounty curator upon payout. Must be less than the
+		///   child bounty value.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn propose_child_bounty_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			curator: <T::Lookup as StaticLookup>::Source,
+			#[compact] fee: BalanceOf<T>,
+		) {
+			let signer = ensure_signed(origin)?;
+			let curator = T::Lookup::lookup(curator)?;
+
+			let parent_curator = Self::ensure_active_bounty(parent_bounty_id)?;
+			ensure!(signer == parent_curator, Error::<T>::RequireCurator);
+
+			let mut child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			match child_bounty.status {
+				ChildBountyStatus::Added | ChildBountyStatus::CuratorProposed { .. } => {},
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+			ensure!(fee < child_bounty.value, Error::<T>::InvalidFee);
+
+			child_bounty.status = ChildBountyStatus::CuratorProposed { curator };
+			child_bounty.fee = fee;
+			ChildBounties::<T>::insert(parent_bounty_id, child_bounty_id, child_bounty);
+		}
+
+		/// Accept the curator role for a child bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the proposed curator. A deposit of
+		/// `BountyCuratorDeposit` of the fee will be reserved from the curator.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change, one balance operation.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn accept_child_bounty_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let signer = ensure_signed(origin)?;
+
+			let mut child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			match child_bounty.status {
+				ChildBountyStatus::CuratorProposed { ref curator } => {
+					ensure!(signer == *curator, Error::<T>::RequireCurator);
+				},
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+
+			let deposit = T::BountyCuratorDeposit::get() * child_bounty.fee;
+			T::Currency::reserve(&signer, deposit)?;
+			child_bounty.curator_deposit = deposit;
+
+			let update_due = system::Module::<T>::block_number() + T::BountyUpdatePeriod::get();
+			child_bounty.status = ChildBountyStatus::Active { curator: signer, update_due };
+			ChildBounties::<T>::insert(parent_bounty_id, child_bounty_id, child_bounty);
+		}
+
+		/// Unassign the curator of a child bounty.
+		///
+		/// The dispatch origin for this call may be either `RejectOrigin` or _Signed_:
+		/// - The child bounty curator itself may step down at any point before awarding the child
+		///   bounty; its deposit is returned.
+		/// - The curator of the active parent bounty may unassign the child bounty curator before
+		///   the child bounty is awarded; the child bounty curator deposit is slashed.
+		/// - Any account may unassign an active curator who has not signalled activity within
+		///   `BountyUpdatePeriod`; the curator deposit is slashed.
+		/// - `RejectOrigin` may unassign the curator at any point, including a pending payout; the
+		///   curator deposit is slashed.
+		///
+		/// The child bounty goes back to the `Added` state.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change, up to one balance operation.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn unassign_child_bounty_curator(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
+
+			let mut child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			let is_parent_curator = match maybe_sender {
+				Some(ref sender) => Self::ensure_active_bounty(parent_bounty_id)
+					.map_or(false, |parent_curator| parent_curator == *sender),
+				None => false,
+			};
+
+			match child_bounty.status {
+				ChildBountyStatus::Added => {
+					// No curator to unassign.
+					Err(Error::<T>::UnexpectedStatus)?
+				},
+				ChildBountyStatus::CuratorProposed { ref curator } => {
+					// A curator who did not accept yet has no deposit at stake.
+					ensure!(
+						is_parent_curator || maybe_sender.as_ref().map_or(true, |sender| sender == curator),
+						BadOrigin,
+					);
+				},
+				ChildBountyStatus::Active { ref curator, ref update_due } => {
+					match maybe_sender {
+						Some(ref sender) if sender == curator => {
+							// The curator is stepping down voluntarily.
+							let _ = T::Currency::unreserve(curator, child_bounty.curator_deposit);
+							child_bounty.curator_deposit = Zero::zero();
+						},
+						Some(_) if !is_parent_curator => {
+							// Anyone else may only unassign an inactive curator.
+							let block_number = system::Module::<T>::block_number();
+							ensure!(*update_due < block_number, Error::<T>::Premature);
+							Self::slash_curator(curator, &mut child_bounty.curator_deposit);
+						},
+						// `RejectOrigin` and the parent bounty curator can always unassign and slash.
+						_ => Self::slash_curator(curator, &mut child_bounty.curator_deposit),
+					}
+				},
+				ChildBountyStatus::PendingPayout { ref curator, .. } => {
+					// Only `RejectOrigin` may prevent an awarded child bounty from being paid out.
+					ensure!(maybe_sender.is_none(), BadOrigin);
+					Self::slash_curator(curator, &mut child_bounty.curator_deposit);
+				},
+			};
+
+			child_bounty.status = ChildBountyStatus::Added;
+			ChildBounties::<T>::insert(parent_bounty_id, child_bounty_id, child_bounty);
+		}
+
+		/// Extend the expiry time of an active child bounty, signalling that the curator is still
+		/// working on it.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the curator of this child bounty.
+		///
+		/// - `_remark`: Additional information, e.g. a progress report.
+		///
+		/// Emits `ChildBountyExtended` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn extend_child_bounty_expiry(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			_remark: Vec<u8>,
+		) {
+			let signer = ensure_signed(origin)?;
+
+			let mut child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			match child_bounty.status {
+				ChildBountyStatus::Active { ref curator, ref mut update_due } => {
+					ensure!(signer == *curator, Error::<T>::RequireCurator);
+					*update_due = (system::Module::<T>::block_number() + T::BountyUpdatePeriod::get())
+						.max(*update_due);
+				},
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			}
+			ChildBounties::<T>::insert(parent_bounty_id, child_bounty_id, child_bounty);
+
+			Self::deposit_event(RawEvent::ChildBountyExtended(parent_bounty_id, child_bounty_id));
+		}
+
+		/// Award an active child bounty to a beneficiary. The beneficiary will be able to claim
+		/// the funds after `BountyDepositPayoutDelay`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the curator of this child bounty.
+		///
+		/// Emits `ChildBountyAwarded` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - One DB change.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn award_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) {
+			let signer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let mut child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			let curator = match child_bounty.status {
+				ChildBountyStatus::Active { curator, .. } => curator,
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+			ensure!(signer == curator, Error::<T>::RequireCurator);
+
+			let unlock_at = system::Module::<T>::block_number() + T::BountyDepositPayoutDelay::get();
+			child_bounty.status = ChildBountyStatus::PendingPayout {
+				curator,
+				beneficiary: beneficiary.clone(),
+				unlock_at,
+			};
+			ChildBounties::<T>::insert(parent_bounty_id, child_bounty_id, child_bounty);
+
+			Self::deposit_event(RawEvent::ChildBountyAwarded(
+				parent_bounty_id,
+				child_bounty_id,
+				beneficiary,
+			));
+		}
+
+		/// Claim the payout of an awarded child bounty after the payout delay.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `ChildBountyClaimed` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - Three DB changes, three balance operations.
+		/// - One event.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn claim_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			ensure_signed(origin)?;
+
+			let child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			let (curator, beneficiary, unlock_at) = match child_bounty.status {
+				ChildBountyStatus::PendingPayout { curator, beneficiary, unlock_at } =>
+					(curator, beneficiary, unlock_at),
+				_ => Err(Error::<T>::UnexpectedStatus)?,
+			};
+			ensure!(system::Module::<T>::block_number() >= unlock_at, Error::<T>::Premature);
+
+			let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+			let payout = Self::payout_funds(
+				&child_bounty_account,
+				&curator,
+				child_bounty.fee,
+				child_bounty.curator_deposit,
+				&beneficiary,
+			);
+
+			Self::remove_child_bounty(parent_bounty_id, child_bounty_id);
+
+			Self::deposit_event(RawEvent::ChildBountyClaimed(
+				parent_bounty_id,
+				child_bounty_id,
+				payout,
+				beneficiary,
+			));
+		}
+
+		/// Cancel a child bounty which has not been awarded yet. Its funds are returned to the
+		/// parent bounty and the child bounty curator deposit is unreserved.
+		///
+		/// The dispatch origin for this call must be either `RejectOrigin` or _Signed_ by the
+		/// curator of the parent bounty.
+		///
+		/// Emits `ChildBountyCanceled` if successful.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - Limited storage reads.
+		/// - Three DB changes, up to two balance operations.
+		/// - One event.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
-		fn close_tip(origin, hash: T::Hash) {
-			ensure_signed(origin)?;
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn close_child_bounty(
+			origin,
+			#[compact] parent_bounty_id: BountyIndex,
+			#[compact] child_bounty_id: BountyIndex,
+		) {
+			let maybe_sender = ensure_signed(origin.clone())
+				.map(Some)
+				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
 
-			let tip = Tips::<T>::get(hash).ok_or(Error::<T>::UnknownTip)?;
-			let n = tip.closes.as_ref().ok_or(Error::<T>::StillOpen)?;
-			ensure!(system::Module::<T>::block_number() >= *n, Error::<T>::Premature);
-			// closed.
-			Reasons::<T>::remove(&tip.reason);
-			Tips::<T>::remove(hash);
-			Self::payout_tip(tip);
+			if let Some(sender) = maybe_sender {
+				let parent_curator = Self::ensure_active_bounty(parent_bounty_id)?;
+				ensure!(sender == parent_curator, Error::<T>::RequireCurator);
+			}
+
+			let child_bounty = Self::child_bounties(parent_bounty_id, child_bounty_id)
+				.ok_or(Error::<T>::InvalidIndex)?;
+			match child_bounty.status {
+				ChildBountyStatus::Added | ChildBountyStatus::CuratorProposed { .. } => {},
+				ChildBountyStatus::Active { ref curator, .. } => {
+					let _ = T::Currency::unreserve(curator, child_bounty.curator_deposit);
+				},
+				ChildBountyStatus::PendingPayout { .. } => Err(Error::<T>::PendingPayout)?,
+			};
+
+			Self::remove_child_bounty(parent_bounty_id, child_bounty_id);
+
+			let child_bounty_account = Self::child_bounty_account_id(child_bounty_id);
+			let balance = T::Currency::free_balance(&child_bounty_account);
+			// Should not fail given the child bounty account is not locked or reserved.
+			let _ = T::Currency::transfer(
+				&child_bounty_account,
+				&Self::bounty_account_id(parent_bounty_id),
+				balance,
+				AllowDeath,
+			);
+
+			Self::deposit_event(RawEvent::ChildBountyCanceled(parent_bounty_id, child_bounty_id));
 		}
 
 		fn on_finalize(n: T::BlockNumber) {
@@ -562,11 +1769,96 @@ impl<T: Trait> Module<T> {
 		MODULE_ID.into_account()
 	}
 
+	/// Ensure `origin` may approve spends. Returns `None` for `ApproveOrigin`, which is not
+	/// limited, or the spend track and its limit for `SpendOrigin`.
+	fn ensure_spend_origin(
+		origin: T::Origin,
+	) -> Result<Option<(SpendTrackId, BalanceOf<T>)>, BadOrigin> {
+		T::ApproveOrigin::try_origin(origin)
+			.map(|_| None)
+			.or_else(|origin| T::SpendOrigin::ensure_origin(origin).map(Some))
+	}
+
+	/// Account for a spend of `value` approved through `track`, failing if that takes the total
+	/// approved through the track in the current spend period over `max`. Returns the first block
+	/// of the current spend period.
+	fn note_track_spend(
+		track: SpendTrackId,
+		max: BalanceOf<T>,
+		value: BalanceOf<T>,
+	) -> Result<T::BlockNumber, DispatchError> {
+		let now = system::Module::<T>::block_number();
+		let period_start = now - now % T::SpendPeriod::get();
+
+		let (usage_start, used) = Self::spend_track_usage(track);
+		let used = if usage_start == period_start { used } else { Zero::zero() };
+		let used = used.saturating_add(value);
+		ensure!(used <= max, Error::<T>::SpendLimitExceeded);
+
+		<SpendTrackUsage<T>>::insert(track, (period_start, used));
+		Ok(period_start)
+	}
+
 	/// The needed bond for a proposal whose spend is `value`.
 	fn calculate_bond(value: BalanceOf<T>) -> BalanceOf<T> {
 		T::ProposalBondMinimum::get().max(T::ProposalBond::get() * value)
 	}
 
+	/// The account ID of a bounty account.
+	pub fn bounty_account_id(id: BountyIndex) -> T::AccountId {
+		MODULE_ID.into_sub_account(("bt", id))
+	}
+
+	/// The account ID of a child bounty account.
+	pub fn child_bounty_account_id(id: BountyIndex) -> T::AccountId {
+		MODULE_ID.into_sub_account(("cb", id))
+	}
+
+	/// Slash the deposit of a (child) bounty curator.
+	fn slash_curator(curator: &T::AccountId, curator_deposit: &mut BalanceOf<T>) {
+		let imbalance = T::Currency::slash_reserved(curator, *curator_deposit).0;
+		T::ProposalRejection::on_unbalanced(imbalance);
+		*curator_deposit = Zero::zero();
+	}
+
+	/// Return the curator of the bounty `id` if it is active.
+	fn ensure_active_bounty(id: BountyIndex) -> Result<T::AccountId, Error<T>> {
+		let bounty = Self::bounties(id).ok_or(Error::<T>::InvalidIndex)?;
+		match bounty.status {
+			BountyStatus::Active { curator, .. } => Ok(curator),
+			_ => Err(Error::<T>::UnexpectedStatus),
+		}
+	}
+
+	/// Remove a child bounty and its description, and update the parent's child bounty count.
+	fn remove_child_bounty(parent_bounty_id: BountyIndex, child_bounty_id: BountyIndex) {
+		ChildBounties::<T>::remove(parent_bounty_id, child_bounty_id);
+		ChildBountyDescriptions::remove(child_bounty_id);
+		ParentChildBounties::mutate(parent_bounty_id, |count| *count = count.saturating_sub(1));
+	}
+
+	/// Pay out the funds held by the (child) bounty account `from`: the curator gets its fee and
+	/// deposit back, the beneficiary the rest. Returns the amount paid to the beneficiary.
+	///
+	/// Three balance operations.
+	fn payout_funds(
+		from: &T::AccountId,
+		curator: &T::AccountId,
+		fee: BalanceOf<T>,
+		curator_deposit: BalanceOf<T>,
+		beneficiary: &T::AccountId,
+	) -> BalanceOf<T> {
+		let _ = T::Currency::unreserve(curator, curator_deposit);
+
+		let balance = T::Currency::free_balance(from);
+		let fee = fee.min(balance);
+		let payout = balance.saturating_sub(fee);
+		// Best-effort only: the (child) bounty account is neither locked nor reserved.
+		let _ = T::Currency::transfer(from, curator, fee, AllowDeath);
+		let _ = T::Currency::transfer(from, beneficiary, payout, AllowDeath);
+		payout
+	}
+
 	/// Given a mutable reference to an `OpenTip`, insert the tip into it and check whether it
 	/// closes, if so, then deposit the relevant event and set closing accordingly.
 	///
@@ -670,6 +1962,59 @@ impl<T: Trait> Module<T> {
 			});
 		});
 
+		BountyApprovals::mutate(|v| {
+			v.retain(|&index| {
+				if let Some(mut bounty) = Self::bounties(index) {
+					if bounty.value <= budget_remaining {
+						budget_remaining -= bounty.value;
+
+						bounty.status = BountyStatus::Funded;
+
+						// return their deposit.
+						let _ = T::Currency::unreserve(&bounty.proposer, bounty.bond);
+
+						// fund the bounty account.
+						imbalance.subsume(T::Currency::deposit_creating(
+							&Self::bounty_account_id(index),
+							bounty.value,
+						));
+
+						Bounties::<T>::insert(index, bounty);
+						Self::deposit_event(RawEvent::BountyBecameActive(index));
+						false
+					} else {
+						missed_any = true;
+						true
+					}
+				} else {
+					false
+				}
+			});
+		});
+
+		// Asset spends are paid from the treasury's own holdings of each asset, so they neither
+		// draw from the budget nor hold back the burn. Those which cannot be paid yet are kept.
+		let account_id = Self::account_id();
+		<AssetSpends<T>>::mutate(|v| {
+			v.retain(|spend| {
+				let paid = <T::Assets as fungibles::Transfer<_>>::transfer(
+					spend.asset_id,
+					&account_id,
+					&spend.beneficiary,
+					spend.amount,
+					ExistenceRequirement::AllowDeath,
+				);
+				if paid.is_ok() {
+					Self::deposit_event(RawEvent::AssetAwarded(
+						spend.asset_id,
+						spend.amount,
+						spend.beneficiary.clone(),
+					));
+				}
+				paid.is_err()
+			});
+		});
+
 		if !missed_any {
 			// burn some proportion of the remaining budget if we run a surplus.
 			let burn = (T::Burn::get() * budget_remaining).min(budget_remaining);
@@ -721,7 +2066,7 @@ mod tests {
 	use super::*;
 
 	use frame_support::{assert_noop, assert_ok, impl_outer_origin, parameter_types, weights::Weight};
-	use frame_support::traits::Contains;
+	use frame_support::{traits::Contains, dispatch::Dispatchable};
 	use sp_core::H256;
 	use sp_runtime::{
 		traits::{BlakeTwo256, OnFinalize, IdentityLookup, BadOrigin}, testing::Header, Perbill
@@ -746,7 +2091,7 @@ mod tests {
 		type Call = ();
 		type Hash = H256;
 		type Hashing = BlakeTwo256;
-		type AccountId = u64;
+		type AccountId = u128;
 		type Lookup = IdentityLookup<Self::AccountId>;
 		type Header = Header;
 		type Event = ();
@@ -771,15 +2116,44 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type CreationFee = CreationFee;
 	}
+	parameter_types! {
+		pub const StringLimit: u32 = 50;
+	}
+	impl pallet_assets::Trait for Test {
+		type Event = ();
+		type Balance = u64;
+		type AssetId = u32;
+		type StringLimit = StringLimit;
+	}
 	pub struct TenToFourteen;
-	impl Contains<u64> for TenToFourteen {
-		fn contains(n: &u64) -> bool {
+	impl Contains<u128> for TenToFourteen {
+		fn contains(n: &u128) -> bool {
 			*n >= 10 && *n <= 14
 		}
-		fn sorted_members() -> Vec<u64> {
+		fn sorted_members() -> Vec<u128> {
 			vec![10, 11, 12, 13, 14]
 		}
 	}
+	/// Account 20 spends on track 0, limited to 10 per spend period; account 21 on track 1,
+	/// limited to 50.
+	pub struct TestSpendOrigin;
+	impl EnsureOrigin<Origin> for TestSpendOrigin {
+		type Success = (SpendTrackId, u64);
+		fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+			Into::<Result<frame_system::RawOrigin<u128>, Origin>>::into(o).and_then(|o| match o {
+				frame_system::RawOrigin::Signed(20) => Ok((0, 10)),
+				frame_system::RawOrigin::Signed(21) => Ok((1, 50)),
+				r => Err(Origin::from(r)),
+			})
+		}
+	}
+	/// Every unit of any asset is worth two units of the native currency.
+	pub struct TwoPerAsset;
+	impl Convert<(u32, u64), u64> for TwoPerAsset {
+		fn convert((_, amount): (u32, u64)) -> u64 {
+			amount * 2
+		}
+	}
 	parameter_types! {
 		pub const ProposalBond: Permill = Permill::from_percent(5);
 		pub const ProposalBondMinimum: u64 = 1;
@@ -789,11 +2163,22 @@ mod tests {
 		pub const TipFindersFee: Percent = Percent::from_percent(20);
 		pub const TipReportDepositBase: u64 = 1;
 		pub const TipReportDepositPerByte: u64 = 1;
+		pub const BountyDepositBase: u64 = 80;
+		pub const BountyDepositPerByte: u64 = 1;
+		pub const BountyDepositPayoutDelay: u64 = 3;
+		pub const BountyUpdatePeriod: u64 = 20;
+		pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+		pub const BountyValueMinimum: u64 = 1;
+		pub const MaxAssetSpends: u32 = 3;
 	}
 	impl Trait for Test {
 		type Currency = pallet_balances::Module<Test>;
-		type ApproveOrigin = frame_system::EnsureRoot<u64>;
-		type RejectOrigin = frame_system::EnsureRoot<u64>;
+		type ApproveOrigin = frame_system::EnsureRoot<u128>;
+		type RejectOrigin = frame_system::EnsureRoot<u128>;
+		type SpendOrigin = TestSpendOrigin;
+		type Assets = Assets;
+		type AssetValue = TwoPerAsset;
+		type MaxAssetSpends = MaxAssetSpends;
 		type Tippers = TenToFourteen;
 		type TipCountdown = TipCountdown;
 		type TipFindersFee = TipFindersFee;
@@ -805,10 +2190,17 @@ mod tests {
 		type ProposalBondMinimum = ProposalBondMinimum;
 		type SpendPeriod = SpendPeriod;
 		type Burn = Burn;
+		type BountyDepositBase = BountyDepositBase;
+		type BountyDepositPerByte = BountyDepositPerByte;
+		type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+		type BountyUpdatePeriod = BountyUpdatePeriod;
+		type BountyCuratorDeposit = BountyCuratorDeposit;
+		type BountyValueMinimum = BountyValueMinimum;
 	}
 	type System = frame_system::Module<Test>;
 	type Balances = pallet_balances::Module<Test>;
 	type Treasury = Module<Test>;
+	type Assets = pallet_assets::Module<Test>;
 
 	fn new_test_ext() -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
@@ -829,7 +2221,7 @@ mod tests {
 	}
 
 	fn tip_hash() -> H256 {
-		BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u64))
+		BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 3u128))
 	}
 
 	#[test]
@@ -878,7 +2270,7 @@ mod tests {
 			assert_ok!(Treasury::report_awesome(Origin::signed(0), b"awesome.dot".to_vec(), 0));
 			assert_eq!(Balances::reserved_balance(0), 12);
 			assert_eq!(Balances::free_balance(0), 88);
-			let h = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u64));
+			let h = BlakeTwo256::hash_of(&(BlakeTwo256::hash(b"awesome.dot"), 0u128));
 			assert_ok!(Treasury::tip(Origin::signed(10), h.clone(), 10));
 			assert_ok!(Treasury::tip(Origin::signed(11), h.clone(), 10));
 			assert_ok!(Treasury::tip(Origin::signed(12), h.clone(), 10));
@@ -1166,4 +2558,555 @@ mod tests {
 			assert_eq!(Balances::free_balance(3), 99); // Balance of `3` has changed
 		});
 	}
+
+	#[test]
+	fn propose_bounty_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&Treasury::account_id(), 101);
+			assert_eq!(Treasury::pot(), 100);
+
+			assert_ok!(Treasury::propose_bounty(Origin::signed(0), 10, b"1234567890".to_vec()));
+
+			let deposit = BountyDepositBase::get() + 10 * BountyDepositPerByte::get();
+			assert_eq!(Balances::reserved_balance(0), deposit);
+			assert_eq!(Balances::free_balance(0), 100 - deposit);
+
+			assert_eq!(Treasury::bounties(0).unwrap(), Bounty {
+				proposer: 0,
+				fee: 0,
+				curator_deposit: 0,
+				value: 10,
+				bond: deposit,
+				status: BountyStatus::Proposed,
+			});
+			assert_eq!(Treasury::bounty_descriptions(0).unwrap(), b"1234567890".to_vec());
+			assert_eq!(Treasury::bounty_count(), 1);
+		});
+	}
+
+	#[test]
+	fn propose_bounty_validation_works() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				Treasury::propose_bounty(Origin::signed(1), 10, vec![0; 20]),
+				Error::<Test>::InsufficientProposersBalance,
+			);
+			assert_noop!(
+				Treasury::propose_bounty(Origin::signed(0), 0, vec![]),
+				Error::<Test>::InvalidValue,
+			);
+			assert_noop!(
+				Treasury::propose_bounty(Origin::signed(0), 10, vec![0; MAX_SENSIBLE_REASON_LENGTH + 1]),
+				Error::<Test>::ReasonTooBig,
+			);
+		});
+	}
+
+	#[test]
+	fn close_proposed_bounty_slashes_the_bond() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Treasury::propose_bounty(Origin::signed(0), 10, b"12345".to_vec()));
+
+			assert_noop!(Treasury::close_bounty(Origin::signed(0), 0), BadOrigin);
+			assert_ok!(Treasury::close_bounty(Origin::ROOT, 0));
+
+			let deposit = BountyDepositBase::get() + 5 * BountyDepositPerByte::get();
+			assert_eq!(Balances::reserved_balance(0), 0);
+			assert_eq!(Balances::free_balance(0), 100 - deposit);
+			assert_eq!(Treasury::bounties(0), None);
+			assert_eq!(Treasury::bounty_descriptions(0), None);
+		});
+	}
+
+	#[test]
+	fn approve_bounty_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+			assert_noop!(Treasury::approve_bounty(Origin::ROOT, 0), Error::<Test>::InvalidIndex);
+			assert_ok!(Treasury::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+			assert_ok!(Treasury::approve_bounty(Origin::ROOT, 0));
+			assert_noop!(Treasury::approve_bounty(Origin::ROOT, 0), Error::<Test>::UnexpectedStatus);
+			assert_noop!(Treasury::close_bounty(Origin::ROOT, 0), Error::<Test>::UnexpectedStatus);
+
+			let deposit = BountyDepositBase::get() + 5 * BountyDepositPerByte::get();
+			assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Approved);
+			assert_eq!(Treasury::bounty_approvals(), vec![0]);
+			assert_eq!(Balances::reserved_balance(0), deposit);
+
+			<Treasury as OnFinalize<u64>>::on_finalize(2);
+
+			// The bond is returned and the bounty account funded from the pot, before the burn.
+			assert_eq!(Balances::reserved_balance(0), 0);
+			assert_eq!(Balances::free_balance(0), 100);
+			assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Funded);
+			assert_eq!(Treasury::bounty_approvals(), Vec::<BountyIndex>::new());
+			assert_eq!(Balances::free_balance(Treasury::bounty_account_id(0)), 50);
+			assert_eq!(Treasury::pot(), 25);
+		});
+	}
+
+	#[test]
+	fn approved_bounty_waits_for_enough_funds() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			Balances::make_free_balance_be(&Treasury::account_id(), 51);
+
+			assert_ok!(Treasury::propose_bounty(Origin::signed(0), 60, vec![]));
+			assert_ok!(Treasury::approve_bounty(Origin::ROOT, 0));
+
+			<Treasury as OnFinalize<u64>>::on_finalize(2);
+
+			// Nothing is burnt while an approval is pending.
+			assert_eq!(Treasury::pot(), 50);
+			assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Approved);
+			assert_eq!(Treasury::bounty_approvals(), vec![0]);
+
+			Balances::make_free_balance_be(&Treasury::account_id(), 61);
+			<Treasury as OnFinalize<u64>>::on_finalize(4);
+
+			assert_eq!(Treasury::pot(), 0);
+			assert_eq!(Treasury::bounties(0).unwrap().status, BountyStatus::Funded);
+		});
+	}
+
+	/// Create, approve and fund a bounty of `value`, returning its index.
+	fn funded_bounty(value: u64) -> BountyIndex {
+		let index = Treasury::bounty_count();
+		Balances::make_free_balance_be(&Treasury::account_id(), value + 1);
+		assert_ok!(Treasury::propose_bounty(Origin::signed(0), value, vec![]));
+		assert_ok!(Treasury::approve_bounty(Origin::ROOT, index));
+		<Treasury as OnFinalize<u64>>::on_finalize(2);
+		assert_eq!(Treasury::bounties(index).unwrap().status, BountyStatus::Funded);
+		index
+	}
+
+	#[test]
+	fn assign_curator_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+
+			assert_noop!(Treasury::propose_curator(Origin::ROOT, index, 4, 50), Error::<Test>::InvalidFee);
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_eq!(
+				Treasury::bounties(index).unwrap().status,
+				BountyStatus::CuratorProposed { curator: 4 },
+			);
+
+			assert_noop!(Treasury::accept_curator(Origin::signed(1), index), Error::<Test>::RequireCurator);
+			assert_noop!(
+				Treasury::accept_curator(Origin::signed(4), index),
+				pallet_balances::Error::<Test, _>::InsufficientBalance,
+			);
+
+			Balances::make_free_balance_be(&4, 10);
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+
+			let bounty = Treasury::bounties(index).unwrap();
+			assert_eq!(bounty.fee, 4);
+			assert_eq!(bounty.curator_deposit, 2);
+			assert_eq!(bounty.status, BountyStatus::Active { curator: 4, update_due: 21 });
+			assert_eq!(Balances::free_balance(&4), 8);
+			assert_eq!(Balances::reserved_balance(&4), 2);
+		});
+	}
+
+	#[test]
+	fn award_and_claim_bounty_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+			Balances::make_free_balance_be(&4, 10);
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+
+			assert_noop!(Treasury::award_bounty(Origin::signed(1), index, 3), Error::<Test>::RequireCurator);
+			assert_ok!(Treasury::award_bounty(Origin::signed(4), index, 3));
+			assert_eq!(
+				Treasury::bounties(index).unwrap().status,
+				BountyStatus::PendingPayout { curator: 4, beneficiary: 3, unlock_at: 4 },
+			);
+			assert_noop!(Treasury::close_bounty(Origin::ROOT, index), Error::<Test>::PendingPayout);
+			assert_noop!(Treasury::claim_bounty(Origin::signed(1), index), Error::<Test>::Premature);
+
+			System::set_block_number(4);
+			assert_ok!(Treasury::claim_bounty(Origin::signed(1), index));
+
+			// The curator gets its fee and deposit, the beneficiary the rest.
+			assert_eq!(Balances::free_balance(&4), 14);
+			assert_eq!(Balances::reserved_balance(&4), 0);
+			assert_eq!(Balances::free_balance(&3), 46);
+			assert_eq!(Balances::free_balance(Treasury::bounty_account_id(index)), 0);
+			assert_eq!(Treasury::bounties(index), None);
+			assert_eq!(Treasury::bounty_descriptions(index), None);
+		});
+	}
+
+	#[test]
+	fn unassign_curator_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+			Balances::make_free_balance_be(&4, 10);
+			assert_noop!(Treasury::unassign_curator(Origin::ROOT, index), Error::<Test>::UnexpectedStatus);
+
+			// A proposed curator may be unassigned by itself or `RejectOrigin`.
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_noop!(Treasury::unassign_curator(Origin::signed(1), index), BadOrigin);
+			assert_ok!(Treasury::unassign_curator(Origin::signed(4), index));
+			assert_eq!(Treasury::bounties(index).unwrap().status, BountyStatus::Funded);
+
+			// An active curator stepping down gets its deposit back.
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+			assert_ok!(Treasury::unassign_curator(Origin::signed(4), index));
+			assert_eq!(Treasury::bounties(index).unwrap().status, BountyStatus::Funded);
+			assert_eq!(Treasury::bounties(index).unwrap().curator_deposit, 0);
+			assert_eq!(Balances::free_balance(&4), 10);
+
+			// Anyone may unassign and slash an inactive curator.
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+			assert_noop!(Treasury::unassign_curator(Origin::signed(1), index), Error::<Test>::Premature);
+
+			System::set_block_number(15);
+			assert_ok!(Treasury::extend_bounty_expiry(Origin::signed(4), index, vec![]));
+			assert_eq!(
+				Treasury::bounties(index).unwrap().status,
+				BountyStatus::Active { curator: 4, update_due: 35 },
+			);
+
+			System::set_block_number(36);
+			assert_ok!(Treasury::unassign_curator(Origin::signed(1), index));
+			assert_eq!(Treasury::bounties(index).unwrap().status, BountyStatus::Funded);
+			assert_eq!(Balances::free_balance(&4), 8);
+			assert_eq!(Balances::reserved_balance(&4), 0);
+
+			// `RejectOrigin` may unassign and slash even after the bounty is awarded.
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+			assert_ok!(Treasury::award_bounty(Origin::signed(4), index, 3));
+			assert_noop!(Treasury::unassign_curator(Origin::signed(4), index), BadOrigin);
+			assert_ok!(Treasury::unassign_curator(Origin::ROOT, index));
+			assert_eq!(Treasury::bounties(index).unwrap().status, BountyStatus::Funded);
+			assert_eq!(Balances::free_balance(&4), 6);
+			assert_eq!(Balances::reserved_balance(&4), 0);
+		});
+	}
+
+	#[test]
+	fn close_active_bounty_returns_funds() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+			Balances::make_free_balance_be(&4, 10);
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+
+			let pot = Treasury::pot();
+			assert_ok!(Treasury::close_bounty(Origin::ROOT, index));
+
+			assert_eq!(Treasury::pot(), pot + 50);
+			assert_eq!(Balances::free_balance(Treasury::bounty_account_id(index)), 0);
+			assert_eq!(Balances::free_balance(&4), 10);
+			assert_eq!(Balances::reserved_balance(&4), 0);
+			assert_eq!(Treasury::bounties(index), None);
+		});
+	}
+
+	#[test]
+	fn child_bounty_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+			Balances::make_free_balance_be(&4, 10);
+			Balances::make_free_balance_be(&5, 10);
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+
+			assert_noop!(
+				Treasury::add_child_bounty(Origin::signed(1), index, 10, vec![]),
+				Error::<Test>::RequireCurator,
+			);
+			// The parent bounty account must be kept alive.
+			assert_noop!(
+				Treasury::add_child_bounty(Origin::signed(4), index, 50, vec![]),
+				Error::<Test>::InvalidValue,
+			);
+			assert_ok!(Treasury::add_child_bounty(Origin::signed(4), index, 10, b"child".to_vec()));
+			assert_eq!(Treasury::parent_child_bounties(index), 1);
+			assert_eq!(Treasury::child_bounty_descriptions(0).unwrap(), b"child".to_vec());
+			assert_eq!(Balances::free_balance(Treasury::bounty_account_id(index)), 40);
+			assert_eq!(Balances::free_balance(Treasury::child_bounty_account_id(0)), 10);
+
+			// The parent can neither be awarded nor closed while it has child bounties.
+			assert_noop!(
+				Treasury::award_bounty(Origin::signed(4), index, 3),
+				Error::<Test>::HasChildBounties,
+			);
+			assert_noop!(Treasury::close_bounty(Origin::ROOT, index), Error::<Test>::HasChildBounties);
+
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 0, 5, 2));
+			assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_eq!(Balances::reserved_balance(&5), 1);
+			assert_ok!(Treasury::award_child_bounty(Origin::signed(5), index, 0, 6));
+			assert_noop!(
+				Treasury::claim_child_bounty(Origin::signed(1), index, 0),
+				Error::<Test>::Premature,
+			);
+
+			System::set_block_number(4);
+			assert_ok!(Treasury::claim_child_bounty(Origin::signed(1), index, 0));
+			assert_eq!(Balances::free_balance(&6), 8);
+			assert_eq!(Balances::free_balance(&5), 12);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+			assert_eq!(Treasury::child_bounties(index, 0), None);
+			assert_eq!(Treasury::parent_child_bounties(index), 0);
+
+			// A closed child bounty returns its funds to the parent.
+			assert_ok!(Treasury::add_child_bounty(Origin::signed(4), index, 10, vec![]));
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 1, 5, 2));
+			assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), index, 1));
+			assert_noop!(
+				Treasury::close_child_bounty(Origin::signed(5), index, 1),
+				Error::<Test>::RequireCurator,
+			);
+			assert_ok!(Treasury::close_child_bounty(Origin::signed(4), index, 1));
+			assert_eq!(Balances::free_balance(Treasury::bounty_account_id(index)), 40);
+			assert_eq!(Balances::free_balance(&5), 12);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+			assert_eq!(Treasury::parent_child_bounties(index), 0);
+
+			assert_ok!(Treasury::award_bounty(Origin::signed(4), index, 3));
+		});
+	}
+
+	#[test]
+	fn child_bounty_curator_can_step_down_or_be_unassigned() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+			Balances::make_free_balance_be(&4, 10);
+			Balances::make_free_balance_be(&5, 10);
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+			assert_ok!(Treasury::add_child_bounty(Origin::signed(4), index, 10, vec![]));
+			assert_noop!(
+				Treasury::unassign_child_bounty_curator(Origin::ROOT, index, 0),
+				Error::<Test>::UnexpectedStatus,
+			);
+
+			// A proposed curator may be unassigned by itself or the parent curator.
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 0, 5, 2));
+			assert_noop!(Treasury::unassign_child_bounty_curator(Origin::signed(1), index, 0), BadOrigin);
+			assert_ok!(Treasury::unassign_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_eq!(Treasury::child_bounties(index, 0).unwrap().status, ChildBountyStatus::Added);
+
+			// The curator may step down and get its deposit back.
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 0, 5, 2));
+			assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_eq!(Balances::reserved_balance(&5), 1);
+			assert_ok!(Treasury::unassign_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_eq!(Treasury::child_bounties(index, 0).unwrap().status, ChildBountyStatus::Added);
+			assert_eq!(Balances::free_balance(&5), 10);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+
+			// The parent curator may unassign the curator, which is slashed.
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 0, 5, 2));
+			assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_ok!(Treasury::unassign_child_bounty_curator(Origin::signed(4), index, 0));
+			assert_eq!(Balances::free_balance(&5), 9);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+			assert_eq!(Treasury::child_bounties(index, 0).unwrap().curator_deposit, 0);
+		});
+	}
+
+	#[test]
+	fn inactive_child_bounty_curator_can_be_slashed() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let index = funded_bounty(50);
+			Balances::make_free_balance_be(&4, 10);
+			Balances::make_free_balance_be(&5, 10);
+			assert_ok!(Treasury::propose_curator(Origin::ROOT, index, 4, 4));
+			assert_ok!(Treasury::accept_curator(Origin::signed(4), index));
+			assert_ok!(Treasury::add_child_bounty(Origin::signed(4), index, 10, vec![]));
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 0, 5, 2));
+			assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_eq!(
+				Treasury::child_bounties(index, 0).unwrap().status,
+				ChildBountyStatus::Active { curator: 5, update_due: 21 },
+			);
+
+			// The update deadline is postponed by the curator only.
+			System::set_block_number(10);
+			assert_noop!(
+				Treasury::extend_child_bounty_expiry(Origin::signed(4), index, 0, vec![]),
+				Error::<Test>::RequireCurator,
+			);
+			assert_ok!(Treasury::extend_child_bounty_expiry(Origin::signed(5), index, 0, vec![]));
+			assert_eq!(
+				Treasury::child_bounties(index, 0).unwrap().status,
+				ChildBountyStatus::Active { curator: 5, update_due: 30 },
+			);
+
+			System::set_block_number(30);
+			assert_noop!(
+				Treasury::unassign_child_bounty_curator(Origin::signed(1), index, 0),
+				Error::<Test>::Premature,
+			);
+			System::set_block_number(31);
+			assert_ok!(Treasury::unassign_child_bounty_curator(Origin::signed(1), index, 0));
+			assert_eq!(Treasury::child_bounties(index, 0).unwrap().status, ChildBountyStatus::Added);
+			assert_eq!(Balances::free_balance(&5), 9);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+
+			// Only `RejectOrigin` may unassign the curator of an awarded child bounty.
+			assert_ok!(Treasury::propose_child_bounty_curator(Origin::signed(4), index, 0, 5, 2));
+			assert_ok!(Treasury::accept_child_bounty_curator(Origin::signed(5), index, 0));
+			assert_ok!(Treasury::award_child_bounty(Origin::signed(5), index, 0, 6));
+			assert_noop!(Treasury::unassign_child_bounty_curator(Origin::signed(4), index, 0), BadOrigin);
+			assert_ok!(Treasury::unassign_child_bounty_curator(Origin::ROOT, index, 0));
+			assert_eq!(Balances::free_balance(&5), 8);
+			assert_eq!(Balances::reserved_balance(&5), 0);
+		});
+	}
+
+	#[test]
+	fn spend_tracks_limit_approvals_per_spend_period() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			for _ in 0..4 {
+				assert_ok!(Treasury::propose_spend(Origin::signed(0), 6, 3));
+			}
+
+			assert_noop!(Treasury::approve_proposal(Origin::signed(1), 0), BadOrigin);
+			assert_ok!(Treasury::approve_proposal(Origin::signed(20), 0));
+			assert_eq!(Treasury::spend_track_usage(0), (0, 6));
+			assert_noop!(
+				Treasury::approve_proposal(Origin::signed(20), 1),
+				Error::<Test>::SpendLimitExceeded,
+			);
+			// Other tracks and `ApproveOrigin` are not affected.
+			assert_ok!(Treasury::approve_proposal(Origin::signed(21), 1));
+			assert_ok!(Treasury::approve_proposal(Origin::ROOT, 2));
+			assert_eq!(Treasury::approvals(), vec![0, 1, 2]);
+			// Approving again would charge the track twice for a single payout.
+			assert_noop!(
+				Treasury::approve_proposal(Origin::signed(21), 1),
+				Error::<Test>::ProposalAlreadyApproved,
+			);
+			assert_eq!(Treasury::spend_track_usage(1), (0, 6));
+
+			// The limit applies afresh in the next spend period.
+			System::set_block_number(2);
+			assert_ok!(Treasury::approve_proposal(Origin::signed(20), 3));
+			assert_eq!(Treasury::spend_track_usage(0), (2, 6));
+		});
+	}
+
+	#[test]
+	fn asset_spends_are_paid_out_on_spend_period() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(pallet_assets::Call::<Test>::issue(100).dispatch(Origin::signed(0)));
+
+			assert_noop!(Treasury::spend_asset(Origin::signed(1), 0, 10, 3), BadOrigin);
+			assert_ok!(Treasury::spend_asset(Origin::ROOT, 0, 10, 3));
+			// Valued at two per unit against the limit of 10 of track 0.
+			assert_ok!(Treasury::spend_asset(Origin::signed(20), 0, 5, 4));
+			assert_noop!(
+				Treasury::spend_asset(Origin::signed(20), 0, 1, 4),
+				Error::<Test>::SpendLimitExceeded,
+			);
+			assert_eq!(Treasury::asset_spends().len(), 2);
+
+			// The treasury holds none of the asset yet.
+			<Treasury as OnFinalize<u64>>::on_finalize(2);
+			assert_eq!(Assets::balance(0, 3), 0);
+			assert_eq!(Treasury::asset_spends().len(), 2);
+
+			let to_treasury = pallet_assets::Call::<Test>::transfer(0, Treasury::account_id(), 50);
+			assert_ok!(to_treasury.dispatch(Origin::signed(0)));
+			<Treasury as OnFinalize<u64>>::on_finalize(4);
+			assert_eq!(Assets::balance(0, 3), 10);
+			assert_eq!(Assets::balance(0, 4), 5);
+			assert_eq!(Assets::balance(0, Treasury::account_id()), 35);
+			assert!(Treasury::asset_spends().is_empty());
+		});
+	}
+
+	#[test]
+	fn asset_spends_are_bounded() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(pallet_assets::Call::<Test>::issue(100).dispatch(Origin::signed(0)));
+
+			for _ in 0..MaxAssetSpends::get() {
+				assert_ok!(Treasury::spend_asset(Origin::ROOT, 0, 10, 3));
+			}
+			assert_noop!(
+				Treasury::spend_asset(Origin::ROOT, 0, 10, 3),
+				Error::<Test>::TooManyAssetSpends,
+			);
+			// A rejected spend does not count against the spend track.
+			assert_noop!(
+				Treasury::spend_asset(Origin::signed(20), 0, 5, 4),
+				Error::<Test>::TooManyAssetSpends,
+			);
+			assert_eq!(Treasury::spend_track_usage(0), (0, 0));
+
+			let to_treasury = pallet_assets::Call::<Test>::transfer(0, Treasury::account_id(), 50);
+			assert_ok!(to_treasury.dispatch(Origin::signed(0)));
+			<Treasury as OnFinalize<u64>>::on_finalize(2);
+			assert!(Treasury::asset_spends().is_empty());
+			assert_ok!(Treasury::spend_asset(Origin::ROOT, 0, 10, 3));
+		});
+	}
+
+	#[test]
+	fn asset_spends_can_be_voided() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Treasury::spend_asset(Origin::signed(20), 0, 3, 3));
+			assert_ok!(Treasury::spend_asset(Origin::ROOT, 0, 10, 3));
+			assert_ok!(Treasury::spend_asset(Origin::ROOT, 0, 10, 4));
+			assert_eq!(Treasury::spend_track_usage(0), (0, 6));
+
+			// The treasury holds none of the asset, so the spends are kept.
+			<Treasury as OnFinalize<u64>>::on_finalize(2);
+			assert_eq!(Treasury::asset_spends().len(), 3);
+			assert_noop!(
+				Treasury::spend_asset(Origin::ROOT, 0, 10, 3),
+				Error::<Test>::TooManyAssetSpends,
+			);
+
+			assert_noop!(Treasury::void_asset_spend(Origin::signed(20), 0), BadOrigin);
+			assert_noop!(
+				Treasury::void_asset_spend(Origin::ROOT, 3),
+				Error::<Test>::InvalidAssetSpendIndex,
+			);
+			assert_ok!(Treasury::void_asset_spend(Origin::ROOT, 2));
+			assert_eq!(Treasury::asset_spends().len(), 2);
+
+			// The track is only released within the spend period of the approval.
+			System::set_block_number(2);
+			assert_ok!(Treasury::spend_asset(Origin::signed(20), 0, 4, 3));
+			assert_eq!(Treasury::spend_track_usage(0), (2, 8));
+			assert_ok!(Treasury::void_asset_spend(Origin::ROOT, 0));
+			assert_eq!(Treasury::spend_track_usage(0), (2, 8));
+			assert_ok!(Treasury::void_asset_spend(Origin::ROOT, 1));
+			assert_eq!(Treasury::spend_track_usage(0), (2, 0));
+			assert_ok!(Treasury::void_asset_spend(Origin::ROOT, 0));
+			assert!(Treasury::asset_spends().is_empty());
+		});
+	}
+
+	#[test]
+	fn unpriced_assets_exceed_any_spend_limit() {
+		assert_eq!(<UnpricedAssets as Convert<(u32, u64), u64>>::convert((0, 1)), u64::max_value());
+	}
 }
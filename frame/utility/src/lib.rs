@@ -67,7 +67,8 @@ use sp_core::TypeId;
 use sp_io::hashing::blake2_256;
 use frame_support::{decl_module, decl_event, decl_error, decl_storage, Parameter, ensure, RuntimeDebug};
 use frame_support::{traits::{Get, ReservableCurrency, Currency}, weights::{
-	GetDispatchInfo, ClassifyDispatch, WeighData, Weight, DispatchClass, PaysFee
+	GetDispatchInfo, ClassifyDispatch, WeighData, Weight, DispatchClass, PaysFee, PostDispatchInfo,
+	DispatchResultWithPostInfo,
 }};
 use frame_system::{self as system, ensure_signed};
 use sp_runtime::{DispatchError, DispatchResult, traits::Dispatchable};
@@ -80,7 +81,9 @@ pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// The overarching call type.
-	type Call: Parameter + Dispatchable<Origin=Self::Origin> + GetDispatchInfo;
+	type Call: Parameter
+		+ Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo>
+		+ GetDispatchInfo;
 
 	/// The currency mechanism.
 	type Currency: ReservableCurrency<Self::AccountId>;
@@ -336,21 +339,31 @@ decl_module! {
 		/// - One event.
 		/// # </weight>
 		///
+		/// Only the weight actually consumed by the calls which were dispatched is charged.
+		///
 		/// This will return `Ok` in all circumstances. To determine the success of the batch, an
 		/// event is deposited. If a call failed and the batch was interrupted, then the
 		/// `BatchInterrupted` event is deposited, along with the number of successful calls made
 		/// and the error of the failed call. If all were successful, then the `BatchCompleted`
 		/// event is deposited.
 		#[weight = <BatchPassthrough<<T as Trait>::Call>>::new()]
-		fn batch(origin, calls: Vec<<T as Trait>::Call>) {
+		fn batch(origin, calls: Vec<<T as Trait>::Call>) -> DispatchResultWithPostInfo {
+			let mut weight: Weight = 10_000;
 			for (index, call) in calls.into_iter().enumerate() {
+				let info = call.get_dispatch_info();
 				let result = call.dispatch(origin.clone());
+				let post_info = match result {
+					Ok(post_info) => post_info,
+					Err(e) => e.post_info,
+				};
+				weight = weight.saturating_add(post_info.calc_actual_weight(&info));
 				if let Err(e) = result {
-					Self::deposit_event(Event::<T>::BatchInterrupted(index as u32, e));
-					return Ok(());
+					Self::deposit_event(Event::<T>::BatchInterrupted(index as u32, e.error));
+					return Ok(Some(weight).into());
 				}
 			}
 			Self::deposit_event(Event::<T>::BatchCompleted);
+			Ok(Some(weight).into())
 		}
 
 		/// Send a call through an indexed pseudonym of the sender.
@@ -361,10 +374,15 @@ decl_module! {
 		/// - The weight of the `call`.
 		/// # </weight>
 		#[weight = <Passthrough<<T as Trait>::Call>>::new()]
-		fn as_sub(origin, index: u16, call: Box<<T as Trait>::Call>) -> DispatchResult {
+		fn as_sub(origin, index: u16, call: Box<<T as Trait>::Call>) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let pseudonym = Self::sub_account_id(who, index);
 			call.dispatch(frame_system::RawOrigin::Signed(pseudonym).into())
+				.map(Self::passthrough_post_info)
+				.map_err(|mut e| {
+					e.post_info = Self::passthrough_post_info(e.post_info);
+					e
+				})
 		}
 
 		/// Register approval for a dispatch to be made from a deterministic composite account if
@@ -442,7 +460,8 @@ decl_module! {
 					}
 				}
 
-				let result = call.dispatch(frame_system::RawOrigin::Signed(id.clone()).into());
+				let result = call.dispatch(frame_system::RawOrigin::Signed(id.clone()).into())
+					.map(|_| ()).map_err(|e| e.error);
 				let _ = T::Currency::unreserve(&m.depositor, m.deposit);
 				<Multisigs<T>>::remove(&id, call_hash);
 				Self::deposit_event(RawEvent::MultisigExecuted(who, timepoint, id, result));
@@ -461,6 +480,7 @@ decl_module! {
 					Self::deposit_event(RawEvent::NewMultisig(who, id));
 				} else {
 					return call.dispatch(frame_system::RawOrigin::Signed(id).into())
+						.map(|_| ()).map_err(|e| e.error)
 				}
 			}
 			Ok(())
@@ -598,6 +618,14 @@ decl_module! {
 }
 
 impl<T: Trait> Module<T> {
+	/// The post dispatch info of a passthrough call, given the one of the call it dispatched.
+	///
+	/// The overhead of the passthrough is added to whatever the inner call reported, matching
+	/// the weight declared by `Passthrough`.
+	fn passthrough_post_info(post_info: PostDispatchInfo) -> PostDispatchInfo {
+		post_info.actual_weight.map(|weight| weight.saturating_add(10_000)).into()
+	}
+
 	/// Derive a sub-account ID from the owner account and the sub-account index.
 	pub fn sub_account_id(who: T::AccountId, index: u16) -> T::AccountId {
 		let entropy = (b"modlpy/utilisuba", who, index).using_encoded(blake2_256);
@@ -1064,4 +1092,21 @@ mod tests {
 			assert_eq!(Balances::free_balance(2), 15);
 		});
 	}
+
+	#[test]
+	fn batch_early_exit_only_charges_dispatched_calls() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Balances(BalancesCall::transfer(2, 5));
+			let call_weight = call.get_dispatch_info().weight;
+			let batch = Call::Utility(utility::Call::batch(vec![
+				call.clone(),
+				Call::Balances(BalancesCall::transfer(2, 10)),
+				call,
+			]));
+			assert_eq!(batch.get_dispatch_info().weight, 10_000 + 3 * call_weight);
+
+			let post_info = batch.dispatch(Origin::signed(1)).unwrap();
+			assert_eq!(post_info.actual_weight, Some(10_000 + 2 * call_weight));
+		});
+	}
 }
@@ -25,6 +25,9 @@
 //! module ensures that there is a lock in place preventing the balance to drop below the *unvested*
 //! amount for any reason other than transaction fee payment.
 //!
+//! An account may have up to `MaxVestingSchedules` concurrent vesting schedules. The amount locked
+//! is the sum of the amounts still unvested under each of them.
+//!
 //! As the amount vested increases over time, the amount unvested reduces. However, locks remain in
 //! place and explicit action is needed on behalf of the user to ensure that the amount locked is
 //! equivalent to the amount remaining to be vested. This is done through a dispatchable function,
@@ -40,23 +43,32 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer funds to another account and place them under a new vesting
+//!   schedule.
+//! - `force_vested_transfer` - As `vested_transfer`, but from an arbitrary source account. Root
+//!   only.
+//! - `merge_schedules` - Merge two of the sender's vesting schedules into one.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Trait`]: ./trait.Trait.html
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_std::prelude::*;
+use sp_std::{prelude::*, vec};
 use sp_std::fmt::Debug;
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchResult, RuntimeDebug, traits::{
-	StaticLookup, Zero, SimpleArithmetic, MaybeSerializeDeserialize, Saturating, Convert
+	StaticLookup, Zero, One, Bounded, SimpleArithmetic, MaybeSerializeDeserialize, Saturating,
+	Convert,
 }};
 use frame_support::{decl_module, decl_event, decl_storage, ensure, decl_error};
+use frame_support::storage::StoragePrefixedMap;
+use frame_support::weights::{Weight, WeighData, ClassifyDispatch, DispatchClass, PaysFee};
 use frame_support::traits::{
-	Currency, LockableCurrency, VestingSchedule, WithdrawReason, LockIdentifier
+	Currency, LockableCurrency, VestingSchedule, WithdrawReason, LockIdentifier, Get,
+	ExistenceRequirement,
 };
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_signed, ensure_root};
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
@@ -69,6 +81,12 @@ pub trait Trait: frame_system::Trait {
 
 	/// Convert the block number into a balance.
 	type BlockNumberToBalance: Convert<Self::BlockNumber, BalanceOf<Self>>;
+
+	/// The minimum amount to be transferred to create a new vesting schedule.
+	type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+	/// The maximum number of vesting schedules an account may have at a given moment.
+	type MaxVestingSchedules: Get<u32>;
 }
 
 const VESTING_ID: LockIdentifier = *b"vesting ";
@@ -88,6 +106,11 @@ impl<
 	Balance: SimpleArithmetic + Copy,
 	BlockNumber: SimpleArithmetic + Copy,
 > VestingInfo<Balance, BlockNumber> {
+	/// Whether this schedule is well formed, i.e. it locks something and eventually unlocks it.
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+
 	/// Amount locked at block `n`.
 	pub fn locked_at<
 		BlockNumberToBalance: Convert<BlockNumber, Balance>
@@ -104,13 +127,41 @@ impl<
 			Zero::zero()
 		}
 	}
+
+	/// Block number at which the schedule is fully vested, expressed as a balance.
+	///
+	/// A schedule which never unlocks (`per_block` of zero) never ends.
+	pub fn ending_block_as_balance<
+		BlockNumberToBalance: Convert<BlockNumber, Balance>
+	>(&self) -> Balance {
+		if self.per_block.is_zero() {
+			return Bounded::max_value()
+		}
+		let starting_block = BlockNumberToBalance::convert(self.starting_block);
+		let duration = if self.per_block >= self.locked {
+			One::one()
+		} else {
+			let whole_blocks = self.locked / self.per_block;
+			if (self.locked % self.per_block).is_zero() {
+				whole_blocks
+			} else {
+				whole_blocks.saturating_add(One::one())
+			}
+		};
+		starting_block.saturating_add(duration)
+	}
 }
 
 decl_storage! {
 	trait Store for Module<T: Trait> as Vesting {
 		/// Information regarding the vesting of a given account.
 		pub Vesting get(fn vesting):
-			map hasher(blake2_256) T::AccountId => Option<VestingInfo<BalanceOf<T>, T::BlockNumber>>;
+			map hasher(blake2_256) T::AccountId => Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>;
+
+		/// True if network has been upgraded to store several vesting schedules per account.
+		///
+		/// True for new networks.
+		IsUpgraded build(|_: &GenesisConfig<T>| true): bool;
 	}
 	add_extra_genesis {
 		config(vesting): Vec<(T::AccountId, T::BlockNumber, T::BlockNumber, BalanceOf<T>)>;
@@ -128,13 +179,22 @@ decl_storage! {
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
 				let per_block = locked / length_as_balance.max(sp_runtime::traits::One::one());
 
-				Vesting::<T>::insert(who, VestingInfo {
-					locked: locked,
-					per_block: per_block,
-					starting_block: begin
+				let schedules = Vesting::<T>::mutate(who, |schedules| {
+					schedules.push(VestingInfo {
+						locked: locked,
+						per_block: per_block,
+						starting_block: begin
+					});
+					schedules.clone()
 				});
+				assert!(
+					schedules.len() <= T::MaxVestingSchedules::get() as usize,
+					"Too many vesting schedules at genesis"
+				);
+				let total_locked = schedules.iter()
+					.fold(Zero::zero(), |acc: BalanceOf<T>, s| acc.saturating_add(s.locked));
 				let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
-				T::Currency::set_lock(VESTING_ID, who, locked, reasons);
+				T::Currency::set_lock(VESTING_ID, who, total_locked, reasons);
 			}
 		})
 	}
@@ -155,8 +215,37 @@ decl_error! {
 	pub enum Error for Module<T: Trait> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// An existing vesting schedule already exists for this account that cannot be clobbered.
-		ExistingVestingSchedule,
+		/// The account already has `MaxVestingSchedules` vesting schedules and cannot be given
+		/// another one.
+		AtMaxVestingSchedules,
+		/// Amount being transferred is too low to create a vesting schedule.
+		AmountLow,
+		/// The vesting schedule given locks nothing or never unlocks.
+		InvalidScheduleParams,
+		/// An index was out of bounds of the account's vesting schedules.
+		ScheduleIndexOutOfBounds,
+	}
+}
+
+/// The weight of a call going through the vesting schedules of an account: a fixed part plus a
+/// part per schedule, charged for `MaxVestingSchedules` schedules.
+struct PerSchedule<T>(Weight, Weight, sp_std::marker::PhantomData<T>);
+impl<T> PerSchedule<T> {
+	fn new(fixed: Weight, per_schedule: Weight) -> Self { Self(fixed, per_schedule, Default::default()) }
+}
+impl<T: Trait, Args> WeighData<Args> for PerSchedule<T> {
+	fn weigh_data(&self, _: Args) -> Weight {
+		self.1.saturating_mul(T::MaxVestingSchedules::get()).saturating_add(self.0)
+	}
+}
+impl<T, Args> ClassifyDispatch<Args> for PerSchedule<T> {
+	fn classify_dispatch(&self, _: Args) -> DispatchClass {
+		DispatchClass::Normal
+	}
+}
+impl<T, Args> PaysFee<Args> for PerSchedule<T> {
+	fn pays_fee(&self, _: Args) -> bool {
+		true
 	}
 }
 
@@ -165,8 +254,21 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 
+		/// The minimum amount to be transferred to create a new vesting schedule.
+		const MinVestedTransfer: BalanceOf<T> = T::MinVestedTransfer::get();
+
+		/// The maximum number of vesting schedules an account may have at a given moment.
+		const MaxVestingSchedules: u32 = T::MaxVestingSchedules::get();
+
 		fn deposit_event() = default;
 
+		fn on_initialize() {
+			if !IsUpgraded::get() {
+				IsUpgraded::put(true);
+				Self::do_upgrade();
+			}
+		}
+
 		/// Unlock any vested funds of the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
@@ -175,11 +277,13 @@ decl_module! {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender, bounded by
+		///   `MaxVestingSchedules`.
 		/// - One balance-lock operation.
-		/// - One storage read (codec `O(1)`) and up to one removal.
+		/// - One storage read (codec `O(S)`) and one write or removal.
 		/// - One event.
 		/// # </weight>
+		#[weight = PerSchedule::<T>::new(10_000, 10_000)]
 		fn vest(origin) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::update_lock(who)
@@ -195,36 +299,239 @@ decl_module! {
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`, bounded by
+		///   `MaxVestingSchedules`.
 		/// - Up to one account lookup.
 		/// - One balance-lock operation.
-		/// - One storage read (codec `O(1)`) and up to one removal.
+		/// - One storage read (codec `O(S)`) and one write or removal.
 		/// - One event.
 		/// # </weight>
+		#[weight = PerSchedule::<T>::new(10_000, 10_000)]
 		fn vest_other(origin, target: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			ensure_signed(origin)?;
 			Self::update_lock(T::Lookup::lookup(target)?)
 		}
+
+		/// Create a vested transfer.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `target`: The account that should be transferred the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer. Its `locked` amount is
+		/// transferred from the sender and must be at least `MinVestedTransfer`.
+		///
+		/// Fails if `target` already has `MaxVestingSchedules` vesting schedules.
+		///
+		/// Emits `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`, bounded by
+		///   `MaxVestingSchedules`.
+		/// - Up to one account lookup.
+		/// - One balance transfer and one balance-lock operation.
+		/// - One storage read (codec `O(S)`) and one write.
+		/// - One event.
+		/// # </weight>
+		#[weight = PerSchedule::<T>::new(1_000_000, 10_000)]
+		fn vested_transfer(
+			origin,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			let transactor = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			Self::do_vested_transfer(transactor, target, schedule)
+		}
+
+		/// Force a vested transfer.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// - `source`: The account whose funds should be transferred.
+		/// - `target`: The account that should be transferred the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer. Its `locked` amount must
+		/// be at least `MinVestedTransfer`.
+		///
+		/// Emits `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of `target`, bounded by
+		///   `MaxVestingSchedules`.
+		/// - Up to two account lookups.
+		/// - One balance transfer and one balance-lock operation.
+		/// - One storage read (codec `O(S)`) and one write.
+		/// - One event.
+		/// # </weight>
+		#[weight = PerSchedule::<T>::new(1_000_000, 10_000)]
+		fn force_vested_transfer(
+			origin,
+			source: <T::Lookup as StaticLookup>::Source,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let source = T::Lookup::lookup(source)?;
+			let target = T::Lookup::lookup(target)?;
+			Self::do_vested_transfer(source, target, schedule)
+		}
+
+		/// Merge two vesting schedules of the sender into one.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+		/// locked under this module.
+		///
+		/// - `schedule1_index`: Index of the first schedule to merge.
+		/// - `schedule2_index`: Index of the second schedule to merge.
+		///
+		/// The merged schedule locks the sum of what is still unvested under both schedules. It
+		/// starts at the later of the current block and both starting blocks, and ends at the later
+		/// of both ending blocks. A schedule which has already fully vested is simply dropped.
+		/// Merging a schedule with itself is a no-op.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of vesting schedules of the sender, bounded by
+		///   `MaxVestingSchedules`.
+		/// - One balance-lock operation.
+		/// - One storage read (codec `O(S)`) and one write or removal.
+		/// - One event.
+		/// # </weight>
+		#[weight = PerSchedule::<T>::new(10_000, 10_000)]
+		fn merge_schedules(origin, schedule1_index: u32, schedule2_index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index { return Ok(()) }
+
+			let mut schedules = Self::vesting(&who);
+			ensure!(!schedules.is_empty(), Error::<T>::NotVesting);
+			let (index1, index2) = (schedule1_index as usize, schedule2_index as usize);
+			ensure!(
+				index1 < schedules.len() && index2 < schedules.len(),
+				Error::<T>::ScheduleIndexOutOfBounds
+			);
+
+			// Remove the higher index first so the lower one stays valid.
+			let (low, high) = (index1.min(index2), index1.max(index2));
+			let schedule_high = schedules.remove(high);
+			let schedule_low = schedules.remove(low);
+
+			let now = <frame_system::Module<T>>::block_number();
+			if let Some(merged) = Self::merge_vesting_info(now, schedule_low, schedule_high) {
+				schedules.push(merged);
+			}
+			Vesting::<T>::insert(&who, schedules);
+			Self::update_lock(who)
+		}
 	}
 }
 
 impl<T: Trait> Module<T> {
+	/// Migrate from a single vesting schedule per account to several.
+	fn do_upgrade() {
+		let res = <Module<T> as Store>::Vesting::translate_values(
+			|schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>| vec![schedule]
+		);
+		if res.is_err() {
+			frame_support::print("Encountered error in migration of Vesting::Vesting map.");
+		}
+	}
+
 	/// (Re)set or remove the module's currency lock on `who`'s account in accordance with their
 	/// current unvested amount.
+	///
+	/// Schedules which have fully vested are removed.
 	fn update_lock(who: T::AccountId) -> DispatchResult {
 		ensure!(Vesting::<T>::contains_key(&who), Error::<T>::NotVesting);
-		let unvested = Self::vesting_balance(&who);
-		if unvested.is_zero() {
+		let now = <frame_system::Module<T>>::block_number();
+		let schedules: Vec<_> = Self::vesting(&who).into_iter()
+			.filter(|s| !s.locked_at::<T::BlockNumberToBalance>(now).is_zero())
+			.collect();
+		let unvested = Self::unvested_amount(&who, &schedules, now);
+		if schedules.is_empty() || unvested.is_zero() {
 			T::Currency::remove_lock(VESTING_ID, &who);
 			Vesting::<T>::remove(&who);
 			Self::deposit_event(RawEvent::VestingCompleted(who));
 		} else {
 			let reasons = WithdrawReason::Transfer | WithdrawReason::Reserve;
 			T::Currency::set_lock(VESTING_ID, &who, unvested, reasons);
+			Vesting::<T>::insert(&who, schedules);
 			Self::deposit_event(RawEvent::VestingUpdated(who, unvested));
 		}
 		Ok(())
 	}
+
+	/// The amount of `who`'s balance which is unvested at block `now` under `schedules`.
+	fn unvested_amount(
+		who: &T::AccountId,
+		schedules: &[VestingInfo<BalanceOf<T>, T::BlockNumber>],
+		now: T::BlockNumber,
+	) -> BalanceOf<T> {
+		let locked_now = schedules.iter().fold(Zero::zero(), |acc: BalanceOf<T>, s| {
+			acc.saturating_add(s.locked_at::<T::BlockNumberToBalance>(now))
+		});
+		T::Currency::free_balance(who).min(locked_now)
+	}
+
+	/// Merge two vesting schedules into one at block `now`.
+	///
+	/// Returns `None` if both schedules have already fully vested.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+		let schedule1_locked = schedule1.locked_at::<T::BlockNumberToBalance>(now);
+		let schedule2_locked = schedule2.locked_at::<T::BlockNumberToBalance>(now);
+		match (schedule1_locked.is_zero(), schedule2_locked.is_zero()) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			(false, false) => {},
+		}
+
+		let locked = schedule1_locked.saturating_add(schedule2_locked);
+		let ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>()
+			.max(schedule2.ending_block_as_balance::<T::BlockNumberToBalance>());
+		let starting_block = now
+			.max(schedule1.starting_block)
+			.max(schedule2.starting_block);
+		let duration = ending_block
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block));
+		let per_block = if duration.is_zero() {
+			// Both schedules end no later than the merged one starts; unlock everything at once.
+			locked
+		} else if duration > locked {
+			One::one()
+		} else {
+			locked / duration
+		};
+
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
+
+	/// Transfer `schedule.locked` from `source` to `target` and place it under `schedule`.
+	fn do_vested_transfer(
+		source: T::AccountId,
+		target: T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		<Self as VestingSchedule<_>>::can_add_vesting_schedule(
+			&target,
+			schedule.locked,
+			schedule.per_block,
+			schedule.starting_block,
+		)?;
+
+		T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+
+		<Self as VestingSchedule<_>>::add_vesting_schedule(
+			&target,
+			schedule.locked,
+			schedule.per_block,
+			schedule.starting_block,
+		)
+	}
 }
 
 impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
@@ -235,19 +542,30 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> BalanceOf<T> {
-		if let Some(v) = Self::vesting(who) {
-			let now = <frame_system::Module<T>>::block_number();
-			let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
-			T::Currency::free_balance(who).min(locked_now)
-		} else {
-			Zero::zero()
-		}
+		let now = <frame_system::Module<T>>::block_number();
+		Self::unvested_amount(who, &Self::vesting(who), now)
+	}
+
+	/// Checks whether a vesting schedule could be added to a given account, without adding it.
+	fn can_add_vesting_schedule(
+		who: &T::AccountId,
+		locked: BalanceOf<T>,
+		per_block: BalanceOf<T>,
+		starting_block: T::BlockNumber
+	) -> DispatchResult {
+		let schedule = VestingInfo { locked, per_block, starting_block };
+		ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+		ensure!(
+			Vesting::<T>::decode_len(who).unwrap_or_default() < T::MaxVestingSchedules::get() as usize,
+			Error::<T>::AtMaxVestingSchedules
+		);
+		Ok(())
 	}
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
-	/// and nothing is updated.
+	/// If the account already has `MaxVestingSchedules` vesting schedules, or the schedule never
+	/// unlocks, an `Err` is returned and nothing is updated.
 	///
 	/// Is a no-op if the amount to be vested is zero.
 	fn add_vesting_schedule(
@@ -257,25 +575,34 @@ impl<T: Trait> VestingSchedule<T::AccountId> for Module<T> where
 		starting_block: T::BlockNumber
 	) -> DispatchResult {
 		if locked.is_zero() { return Ok(()) }
-		if Vesting::<T>::contains_key(who) {
-			Err(Error::<T>::ExistingVestingSchedule)?
-		}
+		Self::can_add_vesting_schedule(who, locked, per_block, starting_block)?;
 		let vesting_schedule = VestingInfo {
 			locked,
 			per_block,
 			starting_block
 		};
-		Vesting::<T>::insert(who, vesting_schedule);
+		Vesting::<T>::mutate(who, |schedules| schedules.push(vesting_schedule));
 		// it can't fail, but even if somehow it did, we don't really care.
 		let _ = Self::update_lock(who.clone());
 		Ok(())
 	}
 
-	/// Remove a vesting schedule for a given account.
-	fn remove_vesting_schedule(who: &T::AccountId) {
-		Vesting::<T>::remove(who);
+	/// Remove the vesting schedule at `schedule_index` for a given account.
+	fn remove_vesting_schedule(who: &T::AccountId, schedule_index: u32) -> DispatchResult {
+		let mut schedules = Self::vesting(who);
+		ensure!(!schedules.is_empty(), Error::<T>::NotVesting);
+		ensure!((schedule_index as usize) < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+		schedules.remove(schedule_index as usize);
+		if schedules.is_empty() {
+			Vesting::<T>::remove(who);
+			T::Currency::remove_lock(VESTING_ID, who);
+			Self::deposit_event(RawEvent::VestingCompleted(who.clone()));
+			return Ok(())
+		}
+		Vesting::<T>::insert(who, schedules);
 		// it can't fail, but even if somehow it did, we don't really care.
 		let _ = Self::update_lock(who.clone());
+		Ok(())
 	}
 }
 
@@ -295,6 +622,7 @@ mod tests {
 		Perbill, testing::Header, traits::{BlakeTwo256, IdentityLookup, Identity, OnInitialize},
 	};
 	use sp_storage::Storage;
+	use frame_system::RawOrigin;
 
 	impl_outer_origin! {
 		pub enum Origin for Test  where system = frame_system {}
@@ -342,10 +670,16 @@ mod tests {
 		type ExistentialDeposit = ExistentialDeposit;
 		type CreationFee = CreationFee;
 	}
+	parameter_types! {
+		pub const MinVestedTransfer: u64 = 256 * 2;
+		pub const MaxVestingSchedules: u32 = 3;
+	}
 	impl Trait for Test {
 		type Event = ();
 		type Currency = Balances;
 		type BlockNumberToBalance = Identity;
+		type MinVestedTransfer = MinVestedTransfer;
+		type MaxVestingSchedules = MaxVestingSchedules;
 	}
 	type System = frame_system::Module<Test>;
 	type Balances = pallet_balances::Module<Test>;
@@ -431,6 +765,8 @@ mod tests {
 		s.top = data.into_iter().collect();
 		sp_io::TestExternalities::new(s).execute_with(|| {
 			Balances::on_initialize(1);
+			Vesting::on_initialize(1);
+			assert_eq!(Vesting::vesting(&6), vec![VestingInfo { locked: 30, per_block: 6, starting_block: 2 }]);
 			assert_eq!(Balances::free_balance(6), 60);
 			assert_eq!(Balances::usable_balance(&6), 30);
 			System::set_block_number(2);
@@ -473,9 +809,9 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&1), Some(user1_vesting_schedule)); // Account 1 has a vesting schedule
-				assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule)); // Account 2 has a vesting schedule
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule)); // Account 12 has a vesting schedule
+				assert_eq!(Vesting::vesting(&1), vec![user1_vesting_schedule]); // Account 1 has a vesting schedule
+				assert_eq!(Vesting::vesting(&2), vec![user2_vesting_schedule]); // Account 2 has a vesting schedule
+				assert_eq!(Vesting::vesting(&12), vec![user12_vesting_schedule]); // Account 12 has a vesting schedule
 
 				// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
 				assert_eq!(Vesting::vesting_balance(&1), 128 * 9);
@@ -601,10 +937,214 @@ mod tests {
 					per_block: 64, // Vesting over 20 blocks
 					starting_block: 10,
 				};
-				assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule));
+				assert_eq!(Vesting::vesting(&12), vec![user12_vesting_schedule]);
 
 				// Account 12 can still send liquid funds
 				assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
 			});
 	}
+
+	#[test]
+	fn vested_transfer_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				assert_eq!(System::block_number(), 1);
+				let user3_free_balance = Balances::free_balance(&3);
+				let user4_free_balance = Balances::free_balance(&4);
+				assert_eq!(user3_free_balance, 256 * 30);
+				assert_eq!(user4_free_balance, 256 * 40);
+				// Account 4 should not have any vesting yet.
+				assert!(Vesting::vesting(&4).is_empty());
+				// Make the schedule for the new transfer.
+				let new_vesting_schedule = VestingInfo {
+					locked: 256 * 5,
+					per_block: 64, // Vesting over 20 blocks
+					starting_block: 10,
+				};
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
+				// Now account 4 should have vesting.
+				assert_eq!(Vesting::vesting(&4), vec![new_vesting_schedule]);
+				// Ensure the transfer happened correctly.
+				assert_eq!(Balances::free_balance(&3), user3_free_balance - 256 * 5);
+				assert_eq!(Balances::free_balance(&4), user4_free_balance + 256 * 5);
+				// Account 4 has 5 * 256 locked.
+				assert_eq!(Vesting::vesting_balance(&4), 256 * 5);
+
+				System::set_block_number(20);
+				// Account 4 has 5 * 64 units vested by block 20.
+				assert_eq!(Vesting::vesting_balance(&4), 10 * 64);
+
+				System::set_block_number(30);
+				// Account 4 has fully vested, and the schedule is cleaned up on the next `vest`.
+				assert_eq!(Vesting::vesting_balance(&4), 0);
+				assert_ok!(Vesting::vest(Some(4).into()));
+				assert!(!<Vesting as Store>::Vesting::contains_key(&4));
+				assert_ok!(Balances::transfer(Some(4).into(), 3, user4_free_balance));
+			});
+	}
+
+	#[test]
+	fn vested_transfer_correctly_fails() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				assert_eq!(System::block_number(), 1);
+				// Amount too low to create a vesting schedule.
+				let schedule = VestingInfo { locked: 256, per_block: 64, starting_block: 10 };
+				assert_noop!(Vesting::vested_transfer(Some(3).into(), 4, schedule), Error::<Test>::AmountLow);
+
+				// A schedule which never unlocks.
+				let schedule = VestingInfo { locked: 256 * 5, per_block: 0, starting_block: 10 };
+				assert_noop!(
+					Vesting::vested_transfer(Some(3).into(), 4, schedule),
+					Error::<Test>::InvalidScheduleParams,
+				);
+
+				// More than the sender has.
+				let schedule = VestingInfo { locked: 256 * 31, per_block: 64, starting_block: 10 };
+				assert_noop!(
+					Vesting::vested_transfer(Some(3).into(), 4, schedule),
+					pallet_balances::Error::<Test, _>::InsufficientBalance,
+				);
+
+				assert!(Vesting::vesting(&4).is_empty());
+			});
+	}
+
+	#[test]
+	fn vested_transfer_respects_max_schedules() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user2_free_balance = Balances::free_balance(&2);
+				let schedule = VestingInfo { locked: 256 * 2, per_block: 64, starting_block: 10 };
+				// Account 2 already has one schedule from genesis.
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, schedule));
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, schedule));
+				assert_eq!(Vesting::vesting(&2).len(), 3);
+				assert_noop!(
+					Vesting::vested_transfer(Some(3).into(), 2, schedule),
+					Error::<Test>::AtMaxVestingSchedules,
+				);
+				// Everything transferred so far is locked.
+				assert_eq!(Balances::free_balance(&2), user2_free_balance + 256 * 4);
+				assert_eq!(Vesting::vesting_balance(&2), user2_free_balance + 256 * 4);
+			});
+	}
+
+	#[test]
+	fn force_vested_transfer_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let user3_free_balance = Balances::free_balance(&3);
+				let user4_free_balance = Balances::free_balance(&4);
+				let schedule = VestingInfo { locked: 256 * 5, per_block: 64, starting_block: 10 };
+				assert_noop!(
+					Vesting::force_vested_transfer(Some(4).into(), 3, 4, schedule),
+					sp_runtime::traits::BadOrigin,
+				);
+				assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, schedule));
+				assert_eq!(Vesting::vesting(&4), vec![schedule]);
+				assert_eq!(Balances::free_balance(&3), user3_free_balance - 256 * 5);
+				assert_eq!(Balances::free_balance(&4), user4_free_balance + 256 * 5);
+				assert_eq!(Vesting::vesting_balance(&4), 256 * 5);
+			});
+	}
+
+	#[test]
+	fn merge_schedules_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				// Account 2 vests 256 * 20 over blocks 10 to 30.
+				let schedule = VestingInfo { locked: 256 * 4, per_block: 128, starting_block: 5 };
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, schedule));
+				assert_eq!(Vesting::vesting_balance(&2), 256 * 24);
+
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+				// The merged schedule starts at block 10 and ends at block 30.
+				let merged = VestingInfo { locked: 256 * 24, per_block: 256 * 24 / 20, starting_block: 10 };
+				assert_eq!(Vesting::vesting(&2), vec![merged]);
+				assert_eq!(Vesting::vesting_balance(&2), 256 * 24);
+
+				System::set_block_number(20);
+				assert_eq!(Vesting::vesting_balance(&2), 256 * 24 - 10 * (256 * 24 / 20));
+			});
+	}
+
+	#[test]
+	fn merge_schedules_drops_vested_schedule() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let schedule = VestingInfo { locked: 256 * 2, per_block: 64, starting_block: 10 };
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 1, schedule));
+				assert_eq!(Vesting::vesting(&1).len(), 2);
+
+				// Account 1's genesis schedule has fully vested by block 12.
+				System::set_block_number(12);
+				assert_ok!(Vesting::merge_schedules(Some(1).into(), 1, 0));
+				assert_eq!(Vesting::vesting(&1), vec![schedule]);
+				assert_eq!(Vesting::vesting_balance(&1), 256 * 2 - 2 * 64);
+			});
+	}
+
+	#[test]
+	fn merge_schedules_checks_indices() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				assert_noop!(Vesting::merge_schedules(Some(4).into(), 0, 1), Error::<Test>::NotVesting);
+				assert_noop!(
+					Vesting::merge_schedules(Some(2).into(), 0, 1),
+					Error::<Test>::ScheduleIndexOutOfBounds,
+				);
+				// Merging a schedule with itself does nothing.
+				let schedules = Vesting::vesting(&2);
+				assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 0));
+				assert_eq!(Vesting::vesting(&2), schedules);
+			});
+	}
+
+	#[test]
+	fn remove_vesting_schedule_works() {
+		ExtBuilder::default()
+			.existential_deposit(256)
+			.build()
+			.execute_with(|| {
+				let schedule = VestingInfo { locked: 256 * 2, per_block: 64, starting_block: 10 };
+				assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, schedule));
+				assert_noop!(
+					Vesting::remove_vesting_schedule(&2, 2),
+					Error::<Test>::ScheduleIndexOutOfBounds,
+				);
+				assert_ok!(Vesting::remove_vesting_schedule(&2, 0));
+				assert_eq!(Vesting::vesting(&2), vec![schedule]);
+				assert_eq!(Vesting::vesting_balance(&2), 256 * 2);
+				assert_ok!(Vesting::remove_vesting_schedule(&2, 0));
+				assert!(!<Vesting as Store>::Vesting::contains_key(&2));
+				assert_eq!(Vesting::vesting_balance(&2), 0);
+			});
+	}
+
+	#[test]
+	fn call_weights_scale_with_max_vesting_schedules() {
+		use frame_support::weights::GetDispatchInfo;
+		let schedule = VestingInfo { locked: 256 * 2, per_block: 64, starting_block: 10 };
+		assert_eq!(Call::<Test>::vest().get_dispatch_info().weight, 10_000 + 3 * 10_000);
+		assert_eq!(Call::<Test>::merge_schedules(0, 1).get_dispatch_info().weight, 10_000 + 3 * 10_000);
+		assert_eq!(
+			Call::<Test>::vested_transfer(2, schedule).get_dispatch_info().weight,
+			1_000_000 + 3 * 10_000,
+		);
+	}
 }
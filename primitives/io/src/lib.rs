@@ -57,6 +57,9 @@ use codec::{Encode, Decode};
 #[cfg(feature = "std")]
 use sp_externalities::{ExternalitiesExt, Externalities};
 
+#[cfg(feature = "std")]
+mod tracing_proxy;
+
 /// Error verifying ECDSA signature
 #[derive(Encode, Decode)]
 pub enum EcdsaVerifyError {
@@ -771,6 +774,33 @@ pub trait Logging {
 	}
 }
 
+/// Interface that provides functions for tracing from within the runtime.
+///
+/// The spans and events are forwarded to the `tracing` subscriber of the host, with the
+/// target `wasm_tracing` and the actual target and name in the `wasm_target` and
+/// `wasm_name` fields.
+#[runtime_interface]
+pub trait WasmTracing {
+	/// Enter a span with the given target and name.
+	///
+	/// Returns the id to pass to `exit_span` when the span is left.
+	fn enter_span(target: &str, name: &str) -> u64 {
+		tracing_proxy::enter_span(target, name)
+	}
+
+	/// Exit the span with the given id.
+	fn exit_span(id: u64) {
+		tracing_proxy::exit_span(id)
+	}
+
+	/// Emit an event with the given target, level and message.
+	fn event(target: &str, level: LogLevel, message: &[u8]) {
+		if let Ok(message) = std::str::from_utf8(message) {
+			tracing_proxy::event(target, level, message)
+		}
+	}
+}
+
 /// Wasm-only interface that provides functions for interacting with the sandbox.
 #[runtime_interface(wasm_only)]
 pub trait Sandbox {
@@ -921,6 +951,7 @@ pub type SubstrateHostFunctions = (
 	hashing::HostFunctions,
 	allocator::HostFunctions,
 	logging::HostFunctions,
+	wasm_tracing::HostFunctions,
 	sandbox::HostFunctions,
 	crate::trie::HostFunctions,
 );
@@ -0,0 +1,135 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Native side of the `wasm_tracing` host functions.
+//!
+//! The target and name of a span are only known at runtime, while `tracing` requires them
+//! to be static. Spans are therefore created with the static target and name
+//! [`WASM_TRACE_IDENTIFIER`] and carry the actual ones in the `wasm_target` and `wasm_name`
+//! fields. Subscribers are expected to unwrap them.
+
+use std::cell::RefCell;
+
+use sp_core::LogLevel;
+
+/// Target and name of the spans and events proxied for the runtime.
+pub const WASM_TRACE_IDENTIFIER: &str = "wasm_tracing";
+
+/// Maximal number of spans entered by the runtime that are kept open.
+///
+/// A runtime that traps does not exit its spans, this bounds how many of them leak.
+const MAX_OPEN_SPANS: usize = 1000;
+
+thread_local! {
+	static PROXY: RefCell<TracingProxy> = RefCell::new(TracingProxy { next_id: 0, spans: Vec::new() });
+}
+
+/// The spans the runtime has entered on this thread, the innermost one last.
+struct TracingProxy {
+	next_id: u64,
+	spans: Vec<(u64, tracing::Span)>,
+}
+
+/// Create and enter a span, returns the id to exit it with.
+pub fn enter_span(target: &str, name: &str) -> u64 {
+	let span = tracing::span!(
+		target: WASM_TRACE_IDENTIFIER,
+		tracing::Level::DEBUG,
+		WASM_TRACE_IDENTIFIER,
+		wasm_target = target,
+		wasm_name = name,
+	);
+	span.with_subscriber(|(id, dispatch)| dispatch.enter(id));
+
+	PROXY.with(|proxy| {
+		let mut proxy = proxy.borrow_mut();
+		if proxy.spans.len() >= MAX_OPEN_SPANS {
+			log::warn!(target: WASM_TRACE_IDENTIFIER, "Too many open runtime spans, closing the oldest one");
+			let (_, span) = proxy.spans.remove(0);
+			exit(span);
+		}
+		proxy.next_id += 1;
+		let id = proxy.next_id;
+		proxy.spans.push((id, span));
+		id
+	})
+}
+
+/// Exit and close the span with the given id.
+///
+/// Spans entered after it, which the runtime did not exit, are exited as well.
+pub fn exit_span(id: u64) {
+	PROXY.with(|proxy| {
+		let mut proxy = proxy.borrow_mut();
+		match proxy.spans.iter().rposition(|(span_id, _)| *span_id == id) {
+			Some(position) => for (_, span) in proxy.spans.drain(position..).rev() {
+				exit(span);
+			},
+			None => log::warn!(target: WASM_TRACE_IDENTIFIER, "Tried to exit unknown runtime span {}", id),
+		}
+	})
+}
+
+/// Emit an event with the given target and message.
+pub fn event(target: &str, level: LogLevel, message: &str) {
+	match level {
+		LogLevel::Error => tracing::event!(
+			target: WASM_TRACE_IDENTIFIER, tracing::Level::ERROR, wasm_target = target, "{}", message
+		),
+		LogLevel::Warn => tracing::event!(
+			target: WASM_TRACE_IDENTIFIER, tracing::Level::WARN, wasm_target = target, "{}", message
+		),
+		LogLevel::Info => tracing::event!(
+			target: WASM_TRACE_IDENTIFIER, tracing::Level::INFO, wasm_target = target, "{}", message
+		),
+		LogLevel::Debug => tracing::event!(
+			target: WASM_TRACE_IDENTIFIER, tracing::Level::DEBUG, wasm_target = target, "{}", message
+		),
+		LogLevel::Trace => tracing::event!(
+			target: WASM_TRACE_IDENTIFIER, tracing::Level::TRACE, wasm_target = target, "{}", message
+		),
+	}
+}
+
+fn exit(span: tracing::Span) {
+	span.with_subscriber(|(id, dispatch)| dispatch.exit(id));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn open_spans() -> Vec<u64> {
+		PROXY.with(|proxy| proxy.borrow().spans.iter().map(|(id, _)| *id).collect())
+	}
+
+	#[test]
+	fn should_exit_spans_entered_after_the_exited_one() {
+		let outer = enter_span("pallet_balances", "transfer");
+		let inner = enter_span("pallet_balances", "deposit");
+		let innermost = enter_span("frame_system", "deposit_event");
+		assert_eq!(open_spans(), vec![outer, inner, innermost]);
+
+		exit_span(inner);
+		assert_eq!(open_spans(), vec![outer]);
+
+		exit_span(inner);
+		assert_eq!(open_spans(), vec![outer]);
+
+		exit_span(outer);
+		assert!(open_spans().is_empty());
+	}
+}
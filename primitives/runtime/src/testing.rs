@@ -364,14 +364,21 @@ impl<Origin, Call, Extra, Info> Applyable for TestXt<Call, Extra> where
 		info: Self::DispatchInfo,
 		len: usize,
 	) -> ApplyExtrinsicResult {
-		let maybe_who = if let Some((who, extra)) = self.0 {
-			Extra::pre_dispatch(extra, &who, &self.1, info, len)?;
-			Some(who)
+		let (maybe_who, pre) = if let Some((who, extra)) = self.0 {
+			let pre = Extra::pre_dispatch(extra, &who, &self.1, info.clone(), len)?;
+			(Some(who), pre)
 		} else {
-			Extra::pre_dispatch_unsigned(&self.1, info, len)?;
-			None
+			let pre = Extra::pre_dispatch_unsigned(&self.1, info.clone(), len)?;
+			(None, pre)
 		};
 
-		Ok(self.1.dispatch(maybe_who.into()).map_err(Into::into))
+		let res = self.1.dispatch(maybe_who.into());
+		let post_info = match res {
+			Ok(info) => info,
+			Err(err) => err.post_info,
+		};
+		let res = res.map(|_| ()).map_err(|e| e.error);
+		Extra::post_dispatch(pre, info, &post_info, len, &res);
+		Ok(res)
 	}
 }
@@ -680,8 +680,24 @@ pub trait Dispatchable {
 	type Origin;
 	/// ...
 	type Trait;
+	/// Additional information that is returned by `dispatch`. Can be used to supply the caller
+	/// with information about a `Dispatchable` that is only known post dispatch.
+	type PostInfo: Eq + PartialEq + Clone + Copy + Encode + Decode + Printable;
 	/// Actually dispatch this call and result the result of it.
-	fn dispatch(self, origin: Self::Origin) -> crate::DispatchResult;
+	fn dispatch(self, origin: Self::Origin) -> crate::DispatchResultWithInfo<Self::PostInfo>;
+}
+
+/// Shortcut to reference the `PostInfo` type of a `Dispatchable`.
+pub type PostDispatchInfoOf<T> = <T as Dispatchable>::PostInfo;
+
+/// Only for bare bone testing when you don't care about dispatching at all.
+impl Dispatchable for () {
+	type Origin = ();
+	type Trait = ();
+	type PostInfo = ();
+	fn dispatch(self, _origin: Self::Origin) -> crate::DispatchResultWithInfo<Self::PostInfo> {
+		panic!("This implementation should not be used for actual dispatch.");
+	}
 }
 
 /// Means by which a transaction may be extended. This type embodies both the data and the logic
@@ -697,7 +713,7 @@ pub trait SignedExtension: Codec + Debug + Sync + Send + Clone + Eq + PartialEq
 	type AccountId;
 
 	/// The type which encodes the call to be dispatched.
-	type Call;
+	type Call: Dispatchable;
 
 	/// Any additional data that will go into the signed payload. This may be created dynamically
 	/// from the transaction using the `additional_signed` function.
@@ -789,7 +805,13 @@ pub trait SignedExtension: Codec + Debug + Sync + Send + Clone + Eq + PartialEq
 	}
 
 	/// Do any post-flight stuff for a transaction.
-	fn post_dispatch(_pre: Self::Pre, _info: Self::DispatchInfo, _len: usize) { }
+	fn post_dispatch(
+		_pre: Self::Pre,
+		_info: Self::DispatchInfo,
+		_post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		_result: &crate::DispatchResult,
+	) { }
 
 	/// Returns the list of unique identifier for this signed extension.
 	///
@@ -804,7 +826,7 @@ pub trait SignedExtension: Codec + Debug + Sync + Send + Clone + Eq + PartialEq
 }
 
 #[impl_for_tuples(1, 12)]
-impl<AccountId, Call, Info: Clone> SignedExtension for Tuple {
+impl<AccountId, Call: Dispatchable, Info: Clone> SignedExtension for Tuple {
 	for_tuples!( where #( Tuple: SignedExtension<AccountId=AccountId, Call=Call, DispatchInfo=Info> )* );
 	type AccountId = AccountId;
 	type Call = Call;
@@ -856,9 +878,11 @@ impl<AccountId, Call, Info: Clone> SignedExtension for Tuple {
 	fn post_dispatch(
 		pre: Self::Pre,
 		info: Self::DispatchInfo,
+		post_info: &PostDispatchInfoOf<Self::Call>,
 		len: usize,
+		result: &crate::DispatchResult,
 	) {
-		for_tuples!( #( Tuple::post_dispatch(pre.Tuple, info.clone(), len); )* )
+		for_tuples!( #( Tuple::post_dispatch(pre.Tuple, info.clone(), post_info, len, result); )* )
 	}
 
 	fn identifier() -> Vec<&'static str> {
@@ -868,6 +892,29 @@ impl<AccountId, Call, Info: Clone> SignedExtension for Tuple {
 	}
 }
 
+/// A signed extension that may carry a tip for the block author.
+///
+/// This lets the tip of a transaction be found without knowing the position of the extension
+/// paying it in the runtime's extra data.
+pub trait GetTip<Balance> {
+	/// The tip carried by this extension, if it is the one paying it.
+	fn tip(&self) -> Option<Balance> {
+		None
+	}
+}
+
+#[impl_for_tuples(1, 12)]
+impl<Balance> GetTip<Balance> for Tuple {
+	for_tuples!( where #( Tuple: GetTip<Balance> )* );
+
+	fn tip(&self) -> Option<Balance> {
+		for_tuples!( #( if let Some(tip) = Tuple.tip() { return Some(tip) } )* );
+		None
+	}
+}
+
+impl<Balance> GetTip<Balance> for () {}
+
 /// Only for bare bone testing when you don't care about signed extensions at all.
 #[cfg(feature = "std")]
 impl SignedExtension for () {
@@ -1302,6 +1349,12 @@ impl Printable for &str {
 	}
 }
 
+impl Printable for () {
+	fn print(&self) {
+		"()".print()
+	}
+}
+
 #[impl_for_tuples(1, 12)]
 impl Printable for Tuple {
 	fn print(&self) {
@@ -71,6 +71,8 @@ impl PoolStatus {
 ///		- `Invalid`
 ///		- `Usurped`
 ///		- `Dropped`
+/// 4. Finalization of the block the transaction was included in:
+///		- `Finalized`
 ///
 /// The events will always be received in the order described above, however
 /// there might be cases where transactions alternate between `Future` and `Ready`
@@ -100,6 +102,12 @@ pub enum TransactionStatus<Hash, BlockHash> {
 	/// Transaction has been included in block with given hash.
 	#[serde(rename = "finalized")] // See #4438
 	InBlock(BlockHash),
+	/// The block the transaction has been included in has been finalized.
+	///
+	/// Not reported to `author_submitAndWatchExtrinsic` subscribers, which already receive
+	/// `InBlock` as `finalized`.
+	#[serde(rename = "blockFinalized")]
+	Finalized(BlockHash),
 	/// Transaction has been replaced in the pool, by another transaction
 	/// that provides the same tags. (e.g. same (sender, nonce)).
 	Usurped(Hash),
@@ -215,6 +223,23 @@ pub trait TransactionPool: Send + Sync + MaybeMallocSizeOf {
 
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
+
+	// *** RPC
+
+	/// Returns the last known status of the transaction with given hash.
+	///
+	/// The status of transactions which left the pool is only kept for a limited time.
+	fn transaction_status(&self, _hash: &TxHash<Self>) -> Option<TransactionStatus<TxHash<Self>, BlockHash<Self>>> {
+		None
+	}
+
+	/// Starts to watch the progress of a transaction already known to the pool.
+	///
+	/// Returns `None` if the transaction is neither in the pool nor awaiting finality
+	/// of the block it has been included in.
+	fn watch(&self, _hash: &TxHash<Self>) -> Option<Box<TransactionStatusStreamFor<Self>>> {
+		None
+	}
 }
 
 /// Trait for transaction pool maintenance.
@@ -222,6 +247,9 @@ pub trait MaintainedTransactionPool : TransactionPool {
 	/// Perform maintenance
 	fn maintain(&self, block: &BlockId<Self::Block>, retracted: &[BlockHash<Self>])
 		-> Pin<Box<dyn Future<Output=()> + Send>>;
+
+	/// Notify the pool about the finalization of the block with given hash.
+	fn on_block_finalized(&self, _block: BlockHash<Self>) {}
 }
 
 /// An abstraction for transaction pool.
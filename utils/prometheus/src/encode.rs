@@ -0,0 +1,99 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Encoding of metrics in the Prometheus text exposition format.
+
+use std::fmt::Write;
+use crate::MetricFamily;
+
+/// The content type of the encoded metrics.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Encode the given metric families in the text exposition format.
+pub fn encode(families: &[MetricFamily]) -> String {
+	let mut out = String::new();
+	for family in families {
+		let _ = writeln!(out, "# HELP {} {}", family.name, escape(&family.help, false));
+		let _ = writeln!(out, "# TYPE {} {}", family.name, family.metric_type.as_str());
+		for sample in &family.samples {
+			out.push_str(&sample.name);
+			if !sample.labels.is_empty() {
+				out.push('{');
+				for (i, (name, value)) in sample.labels.iter().enumerate() {
+					if i != 0 {
+						out.push(',');
+					}
+					let _ = write!(out, "{}=\"{}\"", name, escape(value, true));
+				}
+				out.push('}');
+			}
+			let _ = writeln!(out, " {}", format_value(sample.value));
+		}
+	}
+	out
+}
+
+/// Format a sample value or bucket bound.
+pub(crate) fn format_value(v: f64) -> String {
+	if v == std::f64::INFINITY {
+		"+Inf".into()
+	} else if v == std::f64::NEG_INFINITY {
+		"-Inf".into()
+	} else if v.is_nan() {
+		"NaN".into()
+	} else {
+		v.to_string()
+	}
+}
+
+fn escape(s: &str, quote: bool) -> String {
+	let mut escaped = String::with_capacity(s.len());
+	for c in s.chars() {
+		match c {
+			'\\' => escaped.push_str("\\\\"),
+			'\n' => escaped.push_str("\\n"),
+			'"' if quote => escaped.push_str("\\\""),
+			c => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Registry, Counter, GaugeVec, Opts, register};
+
+	#[test]
+	fn encoding_should_follow_the_text_format() {
+		let registry = Registry::new();
+		let counter = register(Counter::new("imported_total", "Blocks\nimported").unwrap(), &registry)
+			.unwrap();
+		let gauges = register(GaugeVec::new(Opts::new("peers", "Peers"), &["state"]).unwrap(), &registry)
+			.unwrap();
+		counter.inc_by(3);
+		gauges.with_label_values(&["a \"quoted\" state"]).set(1.5);
+
+		assert_eq!(encode(&registry.gather()), concat!(
+			"# HELP imported_total Blocks\\nimported\n",
+			"# TYPE imported_total counter\n",
+			"imported_total 3\n",
+			"# HELP peers Peers\n",
+			"# TYPE peers gauge\n",
+			"peers{state=\"a \\\"quoted\\\" state\"} 1.5\n",
+		));
+	}
+}
@@ -0,0 +1,258 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! [Prometheus] metrics endpoint
+//!
+//! Subsystems create typed metrics (counters, gauges and histograms, optionally with labels) and
+//! register them with a shared [`Registry`]. The `init_prometheus` function starts an HTTP server
+//! which exposes the current values of all registered metrics at `/metrics`, in the Prometheus
+//! text format. Other paths can be served by the same server with a [`Handler`].
+//!
+//! [Prometheus]: https://prometheus.io/
+//! [`Registry`]: ./struct.Registry.html
+//! [`Handler`]: ./type.Handler.html
+
+#![warn(missing_docs)]
+
+use std::{collections::BTreeMap, sync::Arc};
+use parking_lot::RwLock;
+
+mod metrics;
+mod encode;
+#[cfg(not(target_os = "unknown"))]
+mod networking;
+
+pub use metrics::{
+	Collector, Counter, CounterVec, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramTimer,
+	HistogramVec, Metric, MetricFamily, MetricType, MetricVec, Opts, Sample, DEFAULT_BUCKETS,
+};
+pub use encode::{encode, CONTENT_TYPE};
+
+/// A set of metrics which are exposed together.
+///
+/// Cloning a registry gives another handle to the same set.
+#[derive(Clone, Default)]
+pub struct Registry {
+	collectors: Arc<RwLock<BTreeMap<String, Box<dyn Collector>>>>,
+}
+
+impl std::fmt::Debug for Registry {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_list().entries(self.collectors.read().keys()).finish()
+	}
+}
+
+impl Registry {
+	/// Create a new, empty registry.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Register a collector. Fails if one with the same name is registered already.
+	pub fn register(&self, collector: Box<dyn Collector>) -> Result<(), PrometheusError> {
+		let mut collectors = self.collectors.write();
+		let name = collector.name().to_string();
+		if collectors.contains_key(&name) {
+			return Err(PrometheusError::AlreadyReg(name))
+		}
+		collectors.insert(name, collector);
+		Ok(())
+	}
+
+	/// Unregister the collector with the given name, if any.
+	pub fn unregister(&self, name: &str) {
+		self.collectors.write().remove(name);
+	}
+
+	/// Collect the current values of all registered metrics, ordered by name.
+	pub fn gather(&self) -> Vec<MetricFamily> {
+		self.collectors.read().values().map(|c| c.collect()).collect()
+	}
+}
+
+/// Register a metric with the registry and return it, to be kept by its subsystem.
+pub fn register<T: Collector + Clone + 'static>(
+	metric: T,
+	registry: &Registry,
+) -> Result<T, PrometheusError> {
+	registry.register(Box::new(metric.clone()))?;
+	Ok(metric)
+}
+
+/// Response to a request handled by a [`Handler`](./type.Handler.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+	/// HTTP status code.
+	pub status: u16,
+	/// Value of the `Content-Type` header.
+	pub content_type: &'static str,
+	/// Body of the response.
+	pub body: String,
+}
+
+/// Handles the requests to paths of the endpoint other than `/metrics`.
+///
+/// Called with the path of the request, returns `None` if the path is unknown.
+pub type Handler = Arc<dyn Fn(&str) -> Option<Response> + Send + Sync>;
+
+/// Error creating or registering a metric.
+#[derive(Debug, Clone, PartialEq, derive_more::Display)]
+pub enum PrometheusError {
+	/// A metric or label name is not valid.
+	#[display(fmt = "Invalid metric or label name: {}", _0)]
+	InvalidName(String),
+	/// The buckets of a histogram are not in increasing order.
+	#[display(fmt = "Histogram buckets must be in increasing order")]
+	InvalidBuckets,
+	/// A family of metrics was created without labels.
+	#[display(fmt = "No labels given for {}", _0)]
+	NoLabels(String),
+	/// A metric with the same name is registered already.
+	#[display(fmt = "Duplicate metric: {}", _0)]
+	AlreadyReg(String),
+}
+
+impl std::error::Error for PrometheusError {}
+
+/// Error of the Prometheus endpoint.
+#[derive(Debug, derive_more::Display, derive_more::From)]
+pub enum Error {
+	/// Hyper internal error.
+	Hyper(hyper::Error),
+	/// Http request error.
+	Http(hyper::http::Error),
+	/// i/o error.
+	Io(std::io::Error),
+	/// The port is in use already.
+	#[display(fmt = "Prometheus port {} already in use.", _0)]
+	PortInUse(std::net::SocketAddr),
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Error::Hyper(error) => Some(error),
+			Error::Http(error) => Some(error),
+			Error::Io(error) => Some(error),
+			Error::PortInUse(_) => None,
+		}
+	}
+}
+
+#[cfg(not(target_os = "unknown"))]
+mod known_os {
+	use futures_util::future::Future;
+	use hyper::{Body, Request, Response, StatusCode, header, Server};
+	use hyper::service::{service_fn, make_service_fn};
+	use super::{Registry, Handler, Error, encode, CONTENT_TYPE};
+
+	/// Given that we're not using hyper's tokio feature, we need to define our own executor.
+	#[derive(Clone)]
+	pub struct Executor;
+
+	impl<T> hyper::rt::Executor<T> for Executor
+		where
+			T: Future + Send + 'static,
+			T::Output: Send + 'static,
+	{
+		fn execute(&self, future: T) {
+			async_std::task::spawn(future);
+		}
+	}
+
+	async fn request_metrics(
+		req: Request<Body>,
+		registry: Registry,
+		handler: Option<Handler>,
+	) -> Result<Response<Body>, Error> {
+		let path = req.uri().path();
+		if path == "/metrics" {
+			Response::builder()
+				.status(StatusCode::OK)
+				.header(header::CONTENT_TYPE, CONTENT_TYPE)
+				.body(Body::from(encode(&registry.gather())))
+				.map_err(Error::Http)
+		} else if let Some(response) = handler.and_then(|handler| handler(path)) {
+			Response::builder()
+				.status(response.status)
+				.header(header::CONTENT_TYPE, response.content_type)
+				.body(Body::from(response.body))
+				.map_err(Error::Http)
+		} else {
+			Response::builder()
+				.status(StatusCode::NOT_FOUND)
+				.body(Body::from("Not found."))
+				.map_err(Error::Http)
+		}
+	}
+
+	/// Start the Prometheus endpoint, serving the metrics of `registry` at `address`.
+	pub async fn init_prometheus(address: std::net::SocketAddr, registry: Registry) -> Result<(), Error> {
+		init_prometheus_with_handler(address, registry, None).await
+	}
+
+	/// Start the Prometheus endpoint, serving the metrics of `registry` at `address` and the other
+	/// paths with `handler`.
+	pub async fn init_prometheus_with_handler(
+		address: std::net::SocketAddr,
+		registry: Registry,
+		handler: Option<Handler>,
+	) -> Result<(), Error> {
+		use async_std::{net, io};
+		use crate::networking::Incoming;
+
+		let listener = net::TcpListener::bind(&address).await.map_err(|err| match err.kind() {
+			io::ErrorKind::AddrInUse | io::ErrorKind::PermissionDenied => Error::PortInUse(address),
+			_ => err.into(),
+		})?;
+		log::info!("Prometheus server started at {}", address);
+
+		let service = make_service_fn(move |_| {
+			let registry = registry.clone();
+			let handler = handler.clone();
+			async move {
+				Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+					request_metrics(req, registry.clone(), handler.clone())
+				}))
+			}
+		});
+
+		Server::builder(Incoming(listener.incoming()))
+			.executor(Executor)
+			.serve(service)
+			.await
+			.map_err(Into::into)
+	}
+}
+
+#[cfg(not(target_os = "unknown"))]
+pub use known_os::{init_prometheus, init_prometheus_with_handler};
+
+/// Start the Prometheus endpoint. Does nothing on this platform.
+#[cfg(target_os = "unknown")]
+pub async fn init_prometheus(_: std::net::SocketAddr, _: Registry) -> Result<(), Error> {
+	Ok(())
+}
+
+/// Start the Prometheus endpoint. Does nothing on this platform.
+#[cfg(target_os = "unknown")]
+pub async fn init_prometheus_with_handler(
+	_: std::net::SocketAddr,
+	_: Registry,
+	_: Option<Handler>,
+) -> Result<(), Error> {
+	Ok(())
+}
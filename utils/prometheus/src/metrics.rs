@@ -0,0 +1,618 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The metric types, and the collection of their values.
+
+use std::{collections::BTreeMap, sync::Arc, time::{Duration, Instant}};
+use std::sync::atomic::{AtomicU64, Ordering};
+use parking_lot::{Mutex, RwLock};
+use crate::PrometheusError;
+
+/// The default buckets of a histogram, suited to measure durations in seconds.
+pub const DEFAULT_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// The type of a metric, as announced to Prometheus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+	/// A value that only ever goes up.
+	Counter,
+	/// A value that can go up and down.
+	Gauge,
+	/// Observations counted into buckets.
+	Histogram,
+}
+
+impl MetricType {
+	pub(crate) fn as_str(&self) -> &'static str {
+		match self {
+			MetricType::Counter => "counter",
+			MetricType::Gauge => "gauge",
+			MetricType::Histogram => "histogram",
+		}
+	}
+}
+
+/// A single value of a metric family.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+	/// The name of the sample, which is the name of the family plus maybe a suffix.
+	pub name: String,
+	/// The labels of the sample.
+	pub labels: Vec<(String, String)>,
+	/// The value of the sample.
+	pub value: f64,
+}
+
+/// All samples of a metric, as gathered from a `Registry`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricFamily {
+	/// The name of the metric.
+	pub name: String,
+	/// The help text of the metric.
+	pub help: String,
+	/// The type of the metric.
+	pub metric_type: MetricType,
+	/// The current samples of the metric.
+	pub samples: Vec<Sample>,
+}
+
+/// Something that can be registered with a `Registry` and collected from.
+pub trait Collector: Send + Sync {
+	/// The name of the metric family this collects.
+	fn name(&self) -> &str;
+
+	/// Collect the current values.
+	fn collect(&self) -> MetricFamily;
+}
+
+/// The name and help text of a metric.
+#[derive(Debug, Clone)]
+pub struct Opts {
+	/// The name of the metric.
+	pub name: String,
+	/// The help text of the metric.
+	pub help: String,
+}
+
+impl Opts {
+	/// Create new options with the given name and help text.
+	pub fn new(name: impl Into<String>, help: impl Into<String>) -> Self {
+		Opts { name: name.into(), help: help.into() }
+	}
+}
+
+/// The options of a histogram.
+#[derive(Debug, Clone)]
+pub struct HistogramOpts {
+	/// The name and help text.
+	pub common_opts: Opts,
+	/// The upper bounds of the buckets, in increasing order.
+	pub buckets: Vec<f64>,
+}
+
+impl HistogramOpts {
+	/// Create new options with the given name and help text, and the `DEFAULT_BUCKETS`.
+	pub fn new(name: impl Into<String>, help: impl Into<String>) -> Self {
+		HistogramOpts { common_opts: Opts::new(name, help), buckets: DEFAULT_BUCKETS.to_vec() }
+	}
+
+	/// Use the given bucket upper bounds.
+	pub fn buckets(mut self, buckets: Vec<f64>) -> Self {
+		self.buckets = buckets;
+		self
+	}
+}
+
+/// Description of a metric family shared by all of its children.
+#[derive(Debug)]
+pub struct Desc {
+	name: String,
+	help: String,
+	label_names: Vec<String>,
+	buckets: Vec<f64>,
+}
+
+impl Desc {
+	fn new(opts: Opts, label_names: &[&str], buckets: Vec<f64>) -> Result<Arc<Self>, PrometheusError> {
+		if !is_valid_name(&opts.name, true) {
+			return Err(PrometheusError::InvalidName(opts.name))
+		}
+		for label in label_names {
+			if !is_valid_name(label, false) || label.starts_with("__") {
+				return Err(PrometheusError::InvalidName(label.to_string()))
+			}
+		}
+		if !buckets.windows(2).all(|w| w[0] < w[1]) {
+			return Err(PrometheusError::InvalidBuckets)
+		}
+		Ok(Arc::new(Desc {
+			name: opts.name,
+			help: opts.help,
+			label_names: label_names.iter().map(|l| l.to_string()).collect(),
+			buckets,
+		}))
+	}
+}
+
+fn is_valid_name(name: &str, allow_colon: bool) -> bool {
+	let valid = |c: char, first: bool| c == '_' || c.is_ascii_alphabetic() ||
+		(allow_colon && c == ':') || (!first && c.is_ascii_digit());
+	let mut chars = name.chars();
+	chars.next().map_or(false, |c| valid(c, true)) && chars.all(|c| valid(c, false))
+}
+
+/// A metric which can be a member of a `MetricVec`.
+pub trait Metric: Clone + Send + Sync + 'static {
+	/// The type of the metric.
+	const TYPE: MetricType;
+
+	/// Create a new instance which is described by `desc`.
+	fn from_desc(desc: Arc<Desc>) -> Self;
+
+	/// Append the samples of this metric, with the given labels, to `samples`.
+	fn samples(&self, labels: Vec<(String, String)>, samples: &mut Vec<Sample>);
+
+	/// The description of this metric.
+	fn desc(&self) -> &Desc;
+}
+
+/// Collect a single metric without labels.
+fn collect_one<M: Metric>(metric: &M) -> MetricFamily {
+	let desc = metric.desc();
+	let mut samples = Vec::new();
+	metric.samples(Vec::new(), &mut samples);
+	MetricFamily { name: desc.name.clone(), help: desc.help.clone(), metric_type: M::TYPE, samples }
+}
+
+/// A monotonically increasing counter.
+#[derive(Debug, Clone)]
+pub struct Counter {
+	desc: Arc<Desc>,
+	value: Arc<AtomicU64>,
+}
+
+impl Counter {
+	/// Create a new counter with the given name and help text.
+	pub fn new(name: impl Into<String>, help: impl Into<String>) -> Result<Self, PrometheusError> {
+		Self::with_opts(Opts::new(name, help))
+	}
+
+	/// Create a new counter with the given options.
+	pub fn with_opts(opts: Opts) -> Result<Self, PrometheusError> {
+		Ok(Self::from_desc(Desc::new(opts, &[], Vec::new())?))
+	}
+
+	/// Increase the counter by one.
+	pub fn inc(&self) {
+		self.inc_by(1)
+	}
+
+	/// Increase the counter by `v`.
+	pub fn inc_by(&self, v: u64) {
+		self.value.fetch_add(v, Ordering::Relaxed);
+	}
+
+	/// The current value of the counter.
+	pub fn get(&self) -> u64 {
+		self.value.load(Ordering::Relaxed)
+	}
+}
+
+impl Metric for Counter {
+	const TYPE: MetricType = MetricType::Counter;
+
+	fn from_desc(desc: Arc<Desc>) -> Self {
+		Counter { desc, value: Default::default() }
+	}
+
+	fn samples(&self, labels: Vec<(String, String)>, samples: &mut Vec<Sample>) {
+		samples.push(Sample { name: self.desc.name.clone(), labels, value: self.get() as f64 });
+	}
+
+	fn desc(&self) -> &Desc {
+		&self.desc
+	}
+}
+
+impl Collector for Counter {
+	fn name(&self) -> &str {
+		&self.desc.name
+	}
+
+	fn collect(&self) -> MetricFamily {
+		collect_one(self)
+	}
+}
+
+/// A value which can go up and down.
+#[derive(Debug, Clone)]
+pub struct Gauge {
+	desc: Arc<Desc>,
+	// The bits of an `f64`.
+	value: Arc<AtomicU64>,
+}
+
+impl Gauge {
+	/// Create a new gauge with the given name and help text.
+	pub fn new(name: impl Into<String>, help: impl Into<String>) -> Result<Self, PrometheusError> {
+		Self::with_opts(Opts::new(name, help))
+	}
+
+	/// Create a new gauge with the given options.
+	pub fn with_opts(opts: Opts) -> Result<Self, PrometheusError> {
+		Ok(Self::from_desc(Desc::new(opts, &[], Vec::new())?))
+	}
+
+	/// Set the gauge to `v`.
+	pub fn set(&self, v: f64) {
+		self.value.store(v.to_bits(), Ordering::Relaxed);
+	}
+
+	/// Add `v` to the gauge.
+	pub fn add(&self, v: f64) {
+		let mut current = self.value.load(Ordering::Relaxed);
+		loop {
+			let new = (f64::from_bits(current) + v).to_bits();
+			match self.value.compare_exchange_weak(current, new, Ordering::Relaxed, Ordering::Relaxed) {
+				Ok(_) => break,
+				Err(actual) => current = actual,
+			}
+		}
+	}
+
+	/// Subtract `v` from the gauge.
+	pub fn sub(&self, v: f64) {
+		self.add(-v)
+	}
+
+	/// Increase the gauge by one.
+	pub fn inc(&self) {
+		self.add(1.0)
+	}
+
+	/// Decrease the gauge by one.
+	pub fn dec(&self) {
+		self.add(-1.0)
+	}
+
+	/// The current value of the gauge.
+	pub fn get(&self) -> f64 {
+		f64::from_bits(self.value.load(Ordering::Relaxed))
+	}
+}
+
+impl Metric for Gauge {
+	const TYPE: MetricType = MetricType::Gauge;
+
+	fn from_desc(desc: Arc<Desc>) -> Self {
+		Gauge { desc, value: Arc::new(AtomicU64::new(0f64.to_bits())) }
+	}
+
+	fn samples(&self, labels: Vec<(String, String)>, samples: &mut Vec<Sample>) {
+		samples.push(Sample { name: self.desc.name.clone(), labels, value: self.get() });
+	}
+
+	fn desc(&self) -> &Desc {
+		&self.desc
+	}
+}
+
+impl Collector for Gauge {
+	fn name(&self) -> &str {
+		&self.desc.name
+	}
+
+	fn collect(&self) -> MetricFamily {
+		collect_one(self)
+	}
+}
+
+#[derive(Debug)]
+struct HistogramCore {
+	// The number of observations in each bucket (not cumulative), plus one for `+Inf`.
+	counts: Vec<u64>,
+	sum: f64,
+}
+
+/// Observations counted into configurable buckets.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+	desc: Arc<Desc>,
+	core: Arc<Mutex<HistogramCore>>,
+}
+
+impl Histogram {
+	/// Create a new histogram with the given options.
+	pub fn with_opts(opts: HistogramOpts) -> Result<Self, PrometheusError> {
+		Ok(Self::from_desc(Desc::new(opts.common_opts, &[], opts.buckets)?))
+	}
+
+	/// Add a single observation.
+	pub fn observe(&self, v: f64) {
+		let bucket = self.desc.buckets.iter().position(|b| v <= *b).unwrap_or(self.desc.buckets.len());
+		let mut core = self.core.lock();
+		core.counts[bucket] += 1;
+		core.sum += v;
+	}
+
+	/// Observe the given duration in seconds.
+	pub fn observe_duration(&self, d: Duration) {
+		self.observe(d.as_secs_f64())
+	}
+
+	/// Start a timer which observes the time elapsed until it is dropped, in seconds.
+	pub fn start_timer(&self) -> HistogramTimer {
+		HistogramTimer { histogram: self.clone(), start: Instant::now() }
+	}
+
+	/// The number of observations so far, and their sum.
+	pub fn get_sample_count_and_sum(&self) -> (u64, f64) {
+		let core = self.core.lock();
+		(core.counts.iter().sum(), core.sum)
+	}
+}
+
+impl Metric for Histogram {
+	const TYPE: MetricType = MetricType::Histogram;
+
+	fn from_desc(desc: Arc<Desc>) -> Self {
+		let counts = vec![0; desc.buckets.len() + 1];
+		Histogram { desc, core: Arc::new(Mutex::new(HistogramCore { counts, sum: 0.0 })) }
+	}
+
+	fn samples(&self, labels: Vec<(String, String)>, samples: &mut Vec<Sample>) {
+		let (counts, sum) = {
+			let core = self.core.lock();
+			(core.counts.clone(), core.sum)
+		};
+		let bucket_name = format!("{}_bucket", self.desc.name);
+		let bounds = self.desc.buckets.iter().cloned().chain(std::iter::once(std::f64::INFINITY));
+		let mut cumulative = 0;
+		for (bound, count) in bounds.zip(counts.iter()) {
+			cumulative += count;
+			let mut labels = labels.clone();
+			labels.push(("le".into(), crate::encode::format_value(bound)));
+			samples.push(Sample { name: bucket_name.clone(), labels, value: cumulative as f64 });
+		}
+		samples.push(Sample {
+			name: format!("{}_sum", self.desc.name),
+			labels: labels.clone(),
+			value: sum,
+		});
+		samples.push(Sample {
+			name: format!("{}_count", self.desc.name),
+			labels,
+			value: cumulative as f64,
+		});
+	}
+
+	fn desc(&self) -> &Desc {
+		&self.desc
+	}
+}
+
+impl Collector for Histogram {
+	fn name(&self) -> &str {
+		&self.desc.name
+	}
+
+	fn collect(&self) -> MetricFamily {
+		collect_one(self)
+	}
+}
+
+/// Observes the time elapsed since its creation into a histogram when dropped.
+#[must_use = "the timer observes when dropped"]
+pub struct HistogramTimer {
+	histogram: Histogram,
+	start: Instant,
+}
+
+impl HistogramTimer {
+	/// Observe the elapsed time now.
+	pub fn observe_duration(self) {
+		drop(self)
+	}
+
+	/// Drop the timer without observing anything.
+	pub fn stop_and_discard(self) {
+		std::mem::forget(self)
+	}
+}
+
+impl Drop for HistogramTimer {
+	fn drop(&mut self) {
+		self.histogram.observe_duration(self.start.elapsed())
+	}
+}
+
+/// A family of metrics of the same type, told apart by the values of their labels.
+#[derive(Debug, Clone)]
+pub struct MetricVec<M> {
+	desc: Arc<Desc>,
+	children: Arc<RwLock<BTreeMap<Vec<String>, M>>>,
+}
+
+/// A family of counters with labels.
+pub type CounterVec = MetricVec<Counter>;
+/// A family of gauges with labels.
+pub type GaugeVec = MetricVec<Gauge>;
+/// A family of histograms with labels.
+pub type HistogramVec = MetricVec<Histogram>;
+
+impl MetricVec<Counter> {
+	/// Create a new family of counters with the given label names.
+	pub fn new(opts: Opts, label_names: &[&str]) -> Result<Self, PrometheusError> {
+		Self::from_desc(Desc::new(opts, label_names, Vec::new())?)
+	}
+}
+
+impl MetricVec<Gauge> {
+	/// Create a new family of gauges with the given label names.
+	pub fn new(opts: Opts, label_names: &[&str]) -> Result<Self, PrometheusError> {
+		Self::from_desc(Desc::new(opts, label_names, Vec::new())?)
+	}
+}
+
+impl MetricVec<Histogram> {
+	/// Create a new family of histograms with the given label names.
+	pub fn new(opts: HistogramOpts, label_names: &[&str]) -> Result<Self, PrometheusError> {
+		if label_names.contains(&"le") {
+			return Err(PrometheusError::InvalidName("le".into()))
+		}
+		Self::from_desc(Desc::new(opts.common_opts, label_names, opts.buckets)?)
+	}
+}
+
+impl<M: Metric> MetricVec<M> {
+	fn from_desc(desc: Arc<Desc>) -> Result<Self, PrometheusError> {
+		if desc.label_names.is_empty() {
+			return Err(PrometheusError::NoLabels(desc.name.clone()))
+		}
+		Ok(MetricVec { desc, children: Default::default() })
+	}
+
+	/// The metric with the given label values, in the order of the label names. It is created if
+	/// it doesn't exist yet.
+	///
+	/// # Panics
+	///
+	/// If the number of values doesn't match the number of label names.
+	pub fn with_label_values(&self, values: &[&str]) -> M {
+		assert_eq!(
+			values.len(),
+			self.desc.label_names.len(),
+			"wrong number of label values for {}",
+			self.desc.name,
+		);
+		let key = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+		if let Some(metric) = self.children.read().get(&key) {
+			return metric.clone()
+		}
+		self.children.write()
+			.entry(key)
+			.or_insert_with(|| M::from_desc(self.desc.clone()))
+			.clone()
+	}
+
+	/// Remove the metric with the given label values, if it exists.
+	pub fn remove_label_values(&self, values: &[&str]) {
+		let key = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+		self.children.write().remove(&key);
+	}
+
+	/// Remove all metrics of this family.
+	pub fn reset(&self) {
+		self.children.write().clear();
+	}
+}
+
+impl<M: Metric> Collector for MetricVec<M> {
+	fn name(&self) -> &str {
+		&self.desc.name
+	}
+
+	fn collect(&self) -> MetricFamily {
+		let mut samples = Vec::new();
+		for (values, metric) in self.children.read().iter() {
+			let labels = self.desc.label_names.iter().cloned().zip(values.iter().cloned()).collect();
+			metric.samples(labels, &mut samples);
+		}
+		MetricFamily {
+			name: self.desc.name.clone(),
+			help: self.desc.help.clone(),
+			metric_type: M::TYPE,
+			samples,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn invalid_names_are_rejected() {
+		assert!(Counter::new("good_name:total", "help").is_ok());
+		assert!(Counter::new("0bad", "help").is_err());
+		assert!(Counter::new("bad-name", "help").is_err());
+		assert!(CounterVec::new(Opts::new("good", "help"), &["bad:label"]).is_err());
+		assert!(CounterVec::new(Opts::new("good", "help"), &["__reserved"]).is_err());
+		assert!(HistogramVec::new(HistogramOpts::new("good", "help"), &["le"]).is_err());
+		assert!(Histogram::with_opts(HistogramOpts::new("good", "help").buckets(vec![2.0, 1.0])).is_err());
+	}
+
+	#[test]
+	fn gauge_should_go_up_and_down() {
+		let gauge = Gauge::new("gauge", "help").unwrap();
+		gauge.inc();
+		gauge.add(2.5);
+		gauge.dec();
+		assert_eq!(gauge.get(), 2.5);
+		gauge.set(-1.0);
+		assert_eq!(gauge.clone().get(), -1.0);
+	}
+
+	#[test]
+	fn histogram_buckets_should_be_cumulative() {
+		let histogram = Histogram::with_opts(
+			HistogramOpts::new("latency", "help").buckets(vec![1.0, 2.0])
+		).unwrap();
+		histogram.observe(0.5);
+		histogram.observe(1.5);
+		histogram.observe(1.5);
+		histogram.observe(3.0);
+		assert_eq!(histogram.get_sample_count_and_sum(), (4, 6.5));
+
+		let values = histogram.collect().samples.into_iter()
+			.map(|s| (s.name, s.labels.into_iter().map(|l| l.1).collect::<Vec<_>>(), s.value))
+			.collect::<Vec<_>>();
+		assert_eq!(values, vec![
+			("latency_bucket".into(), vec!["1".into()], 1.0),
+			("latency_bucket".into(), vec!["2".into()], 3.0),
+			("latency_bucket".into(), vec!["+Inf".into()], 4.0),
+			("latency_sum".into(), vec![], 6.5),
+			("latency_count".into(), vec![], 4.0),
+		]);
+	}
+
+	#[test]
+	fn metric_vec_children_should_share_values() {
+		let counters = CounterVec::new(Opts::new("requests", "help"), &["method", "code"]).unwrap();
+		counters.with_label_values(&["get", "200"]).inc();
+		counters.with_label_values(&["get", "200"]).inc_by(2);
+		counters.with_label_values(&["post", "500"]).inc();
+		assert_eq!(counters.with_label_values(&["get", "200"]).get(), 3);
+
+		let family = counters.collect();
+		assert_eq!(family.metric_type, MetricType::Counter);
+		assert_eq!(family.samples.len(), 2);
+		assert_eq!(family.samples[1].labels, vec![
+			("method".to_string(), "post".to_string()),
+			("code".to_string(), "500".to_string()),
+		]);
+
+		counters.remove_label_values(&["post", "500"]);
+		assert_eq!(counters.collect().samples.len(), 1);
+	}
+
+	#[test]
+	#[should_panic(expected = "wrong number of label values")]
+	fn metric_vec_should_check_label_count() {
+		let gauges = GaugeVec::new(Opts::new("peers", "help"), &["state"]).unwrap();
+		gauges.with_label_values(&["a", "b"]);
+	}
+}